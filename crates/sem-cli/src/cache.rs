@@ -5,7 +5,9 @@ use std::path::Path;
 use rayon::prelude::*;
 use rusqlite::{params, params_from_iter, Connection, OpenFlags, OptionalExtension};
 use sem_core::model::entity::SemanticEntity;
-use sem_core::parser::graph::{EntityGraph, EntityInfo, EntityInfoMap, EntityRef, RefType};
+use sem_core::parser::graph::{
+    EntityGraph, EntityInfo, EntityInfoMap, EntityRef, ImpactScore, RefType,
+};
 use sem_core::parser::{
     js_ts_has_default_re_export_from_content,
     js_ts_import_source_files_from_filesystem_with_unscoped,
@@ -194,11 +196,19 @@ pub enum CachedImpactMode {
     Tests,
 }
 
+/// Transitive dependents with their BFS depth, plus each one's reference path.
+type ImpactWithPaths = (Vec<(EntityInfo, usize)>, HashMap<String, Vec<String>>);
+
 pub struct CachedImpactResult {
     pub entity: EntityInfo,
     pub dependencies: Vec<EntityInfo>,
     pub dependents: Vec<EntityInfo>,
     pub impact: Vec<(EntityInfo, usize)>,
+    /// One shortest reference path (entity ids, source first) per impacted
+    /// entity id; empty outside All mode.
+    pub impact_paths: HashMap<String, Vec<String>>,
+    /// Weighted score over `impact`; empty outside All mode.
+    pub impact_score: ImpactScore,
     pub tests: Vec<EntityInfo>,
     pub tests_truncated: bool,
 }
@@ -315,15 +325,22 @@ impl DiskCache {
         // Insert edges with prepared statement
         {
             let mut stmt = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 let rt = match edge.ref_type {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                stmt.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                stmt.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         }
 
@@ -371,7 +388,7 @@ impl DiskCache {
 
         {
             let mut stmt = tx.prepare(
-                "INSERT OR REPLACE INTO entities (id, name, entity_type, file_path, start_line, end_line, content, content_hash, structural_hash, parent_id, metadata_json) VALUES (?1, ?2, ?3, ?4, ?5, ?6, '', '', NULL, ?7, NULL)",
+                "INSERT OR REPLACE INTO entities (id, name, entity_type, file_path, start_line, end_line, content, content_hash, structural_hash, parent_id, metadata_json, is_test, is_private) VALUES (?1, ?2, ?3, ?4, ?5, ?6, '', '', NULL, ?7, NULL, ?8, ?9)",
             )?;
             for e in graph.entities.values() {
                 stmt.execute(params![
@@ -382,21 +399,30 @@ impl DiskCache {
                     e.start_line as i64,
                     e.end_line as i64,
                     e.parent_id,
+                    e.is_test,
+                    e.is_private,
                 ])?;
             }
         }
 
         {
             let mut stmt = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 let rt = match edge.ref_type {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                stmt.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                stmt.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         }
 
@@ -445,11 +471,14 @@ impl DiskCache {
                     end_line: row.get::<_, i64>(5)? as usize,
                     start_byte: row.get::<_, Option<i64>>(11)?.map(|v| v as usize),
                     end_byte: row.get::<_, Option<i64>>(12)?.map(|v| v as usize),
+                    name_start: None,
+                    name_end: None,
                     content: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
                     content_hash: row.get(7)?,
                     structural_hash: row.get(8)?,
                     parent_id: row.get(9)?,
                     metadata,
+                    stable_id: None,
                 })
             })
             .ok()?
@@ -472,7 +501,7 @@ impl DiskCache {
 
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -480,18 +509,23 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
             .filter_map(|r| r.ok())
             .collect();
 
+        let test_ids = sem_core::parser::test_detect::test_partition_ids(&entities);
+        let private_ids = sem_core::parser::visibility::private_ids(&entities);
         let entity_map: EntityInfoMap = entities
             .iter()
             .map(|e| {
@@ -505,6 +539,8 @@ impl DiskCache {
                         start_line: e.start_line,
                         end_line: e.end_line,
                         parent_id: e.parent_id.clone(),
+                        is_test: test_ids.contains(&e.id),
+                        is_private: private_ids.contains(&e.id),
                     },
                 )
             })
@@ -636,13 +672,24 @@ impl DiskCache {
         } else {
             Vec::new()
         };
-        let impact = if matches!(mode, CachedImpactMode::All) {
+        let (impact, impact_paths) = if matches!(mode, CachedImpactMode::All) {
             match self.impact_entities(&entity.id, depth, None) {
                 Ok(impact) => impact,
                 Err(_) => return Err(CachedImpactError::CacheReadFailed),
             }
         } else {
-            Vec::new()
+            (Vec::new(), HashMap::new())
+        };
+        let impact_score = if impact.is_empty() {
+            ImpactScore::default()
+        } else {
+            let ids: Vec<String> = impact.iter().map(|(info, _)| info.id.clone()).collect();
+            let counts = self
+                .dependent_counts(&ids)
+                .map_err(|_| CachedImpactError::CacheReadFailed)?;
+            ImpactScore::from_impact(impact.iter().map(|(info, depth)| (info, *depth)), |id| {
+                counts.get(id).copied().unwrap_or(0)
+            })
         };
         let dependents = if matches!(mode, CachedImpactMode::All) {
             impact
@@ -673,6 +720,8 @@ impl DiskCache {
             dependencies,
             dependents,
             impact,
+            impact_paths,
+            impact_score,
             tests,
             tests_truncated,
         }))
@@ -720,6 +769,8 @@ impl DiskCache {
             dependencies,
             dependents: Vec::new(),
             impact: Vec::new(),
+            impact_paths: HashMap::new(),
+            impact_score: ImpactScore::default(),
             tests: Vec::new(),
             tests_truncated: false,
         }))
@@ -958,7 +1009,7 @@ impl DiskCache {
         let mut entity_stmt = self
             .conn
             .prepare(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id FROM entities",
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private FROM entities",
             )
             .ok()?;
         let entity_map: EntityInfoMap = entity_stmt
@@ -974,6 +1025,8 @@ impl DiskCache {
                         start_line: row.get::<_, i64>(4)? as usize,
                         end_line: row.get::<_, i64>(5)? as usize,
                         parent_id: row.get(6)?,
+                        is_test: row.get::<_, i64>(7)? != 0,
+                        is_private: row.get::<_, i64>(8)? != 0,
                     },
                 ))
             })
@@ -1039,7 +1092,7 @@ impl DiskCache {
     fn entity_by_id(&self, id: &str) -> Result<Option<EntityInfo>, rusqlite::Error> {
         self.conn
             .query_row(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                  FROM entities WHERE id = ?1",
                 params![id],
                 entity_info_from_row,
@@ -1056,14 +1109,14 @@ impl DiskCache {
 
         if let Some(file_hint) = file_hint {
             self.add_entity_candidates(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                  FROM entities WHERE name = ?1 AND file_path = ?2",
                 &[query, file_hint],
                 &mut by_id,
             )?;
         } else {
             self.add_entity_candidates(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                  FROM entities WHERE name = ?1",
                 &[query],
                 &mut by_id,
@@ -1073,7 +1126,7 @@ impl DiskCache {
         if let Some((entity_type, name)) = split_type_qualified_query(query) {
             if let Some(file_hint) = file_hint {
                 self.add_entity_candidates(
-                    "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                    "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                      FROM entities
                      WHERE entity_type = ?1 AND name = ?2 AND file_path = ?3",
                     &[entity_type, name, file_hint],
@@ -1081,7 +1134,7 @@ impl DiskCache {
                 )?;
             } else {
                 self.add_entity_candidates(
-                    "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                    "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                      FROM entities WHERE entity_type = ?1 AND name = ?2",
                     &[entity_type, name],
                     &mut by_id,
@@ -1097,7 +1150,7 @@ impl DiskCache {
             if let Some(file_hint) = file_hint {
                 self.add_entity_candidates(
                     "SELECT child.id, child.name, child.entity_type, child.file_path,
-                            child.start_line, child.end_line, child.parent_id
+                            child.start_line, child.end_line, child.parent_id, child.is_test, child.is_private
                      FROM entities child
                      JOIN entities parent ON child.parent_id = parent.id
                      WHERE child.name = ?1 AND parent.name = ?2 AND child.file_path = ?3",
@@ -1107,7 +1160,7 @@ impl DiskCache {
             } else {
                 self.add_entity_candidates(
                     "SELECT child.id, child.name, child.entity_type, child.file_path,
-                            child.start_line, child.end_line, child.parent_id
+                            child.start_line, child.end_line, child.parent_id, child.is_test, child.is_private
                      FROM entities child
                      JOIN entities parent ON child.parent_id = parent.id
                      WHERE child.name = ?1 AND parent.name = ?2",
@@ -1146,7 +1199,7 @@ impl DiskCache {
     fn direct_dependencies(&self, entity_id: &str) -> Result<Vec<EntityInfo>, rusqlite::Error> {
         let mut stmt = self.conn.prepare(
             "SELECT entities.id, entities.name, entities.entity_type, entities.file_path,
-                    entities.start_line, entities.end_line, entities.parent_id
+                    entities.start_line, entities.end_line, entities.parent_id, entities.is_test, entities.is_private
              FROM edges
              JOIN entities ON entities.id = edges.to_entity
              WHERE edges.from_entity = ?1
@@ -1159,7 +1212,7 @@ impl DiskCache {
     fn direct_dependents(&self, entity_id: &str) -> Result<Vec<EntityInfo>, rusqlite::Error> {
         let mut stmt = self.conn.prepare(
             "SELECT entities.id, entities.name, entities.entity_type, entities.file_path,
-                    entities.start_line, entities.end_line, entities.parent_id
+                    entities.start_line, entities.end_line, entities.parent_id, entities.is_test, entities.is_private
              FROM edges
              JOIN entities ON entities.id = edges.from_entity
              WHERE edges.to_entity = ?1
@@ -1169,19 +1222,62 @@ impl DiskCache {
         rows.collect()
     }
 
+    /// Incoming edge count per entity id, the cached counterpart of
+    /// `EntityGraph::dependents` lengths. Ids without dependents are absent.
+    fn dependent_counts(
+        &self,
+        entity_ids: &[String],
+    ) -> Result<HashMap<String, usize>, rusqlite::Error> {
+        let mut counts = HashMap::new();
+        for chunk in entity_ids.chunks(SQL_PARAM_CHUNK) {
+            let placeholders = repeat_vars(chunk.len());
+            let sql = format!(
+                "SELECT to_entity, COUNT(*) FROM edges
+                 WHERE to_entity IN ({placeholders})
+                 GROUP BY to_entity"
+            );
+            let mut stmt = self.conn.prepare(&sql)?;
+            let rows = stmt
+                .query_map(params_from_iter(chunk.iter().map(String::as_str)), |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+                })?;
+            for row in rows {
+                let (id, count) = row?;
+                counts.insert(id, count);
+            }
+        }
+        Ok(counts)
+    }
+
     fn impact_entities(
         &self,
         entity_id: &str,
         max_depth: usize,
         max_count: Option<usize>,
-    ) -> Result<Vec<(EntityInfo, usize)>, rusqlite::Error> {
+    ) -> Result<ImpactWithPaths, rusqlite::Error> {
         let impact_ids = self.impact_ids(entity_id, max_depth, max_count)?;
-        let ids: Vec<String> = impact_ids.iter().map(|(id, _)| id.clone()).collect();
+        let ids: Vec<String> = impact_ids.iter().map(|(id, _, _)| id.clone()).collect();
         let infos = self.entity_infos_by_id(&ids)?;
-        Ok(impact_ids
+        let parents: HashMap<&str, &str> = impact_ids
+            .iter()
+            .map(|(id, _, parent)| (id.as_str(), parent.as_str()))
+            .collect();
+        let mut paths = HashMap::new();
+        for id in &ids {
+            let mut path = vec![id.clone()];
+            let mut current = id.as_str();
+            while let Some(parent) = parents.get(current) {
+                path.push(parent.to_string());
+                current = parent;
+            }
+            path.reverse();
+            paths.insert(id.clone(), path);
+        }
+        let impact = impact_ids
             .into_iter()
-            .filter_map(|(id, depth)| infos.get(&id).cloned().map(|info| (info, depth)))
-            .collect())
+            .filter_map(|(id, depth, _)| infos.get(&id).cloned().map(|info| (info, depth)))
+            .collect();
+        Ok((impact, paths))
     }
 
     fn test_impact_entities(
@@ -1193,7 +1289,7 @@ impl DiskCache {
         if tests_truncated {
             impact_ids.truncate(CACHED_TEST_IMPACT_LIMIT);
         }
-        let ids: Vec<String> = impact_ids.into_iter().map(|(id, _)| id).collect();
+        let ids: Vec<String> = impact_ids.into_iter().map(|(id, _, _)| id).collect();
         let test_ids = self.test_ids_from(&ids)?;
         let ordered_test_ids: Vec<String> = ids
             .iter()
@@ -1220,7 +1316,7 @@ impl DiskCache {
             Err(_) => return Ok((tests, tests_truncated)),
         };
         let mut stmt = self.conn.prepare(
-            "SELECT e.id, e.name, e.entity_type, e.file_path, e.start_line, e.end_line, e.parent_id
+            "SELECT e.id, e.name, e.entity_type, e.file_path, e.start_line, e.end_line, e.parent_id, e.is_test, e.is_private
              FROM entities e
              JOIN entity_flags f ON f.entity_id = e.id AND f.is_test != 0
              WHERE e.content LIKE '%' || ?1 || '%'
@@ -1255,7 +1351,7 @@ impl DiskCache {
         entity_id: &str,
         max_depth: usize,
         max_count: Option<usize>,
-    ) -> Result<Vec<(String, usize)>, rusqlite::Error> {
+    ) -> Result<Vec<(String, usize, String)>, rusqlite::Error> {
         let mut visited = HashSet::new();
         let mut frontier = vec![entity_id.to_string()];
         let mut result = Vec::new();
@@ -1268,9 +1364,9 @@ impl DiskCache {
             }
             let next_depth = depth + 1;
             let mut next_frontier = Vec::new();
-            for dependent_id in self.dependent_ids_for(&frontier)? {
+            for (parent_id, dependent_id) in self.dependent_ids_for(&frontier)? {
                 if visited.insert(dependent_id.clone()) {
-                    result.push((dependent_id.clone(), next_depth));
+                    result.push((dependent_id.clone(), next_depth, parent_id));
                     next_frontier.push(dependent_id);
                     if max_count.is_some_and(|limit| result.len() >= limit) {
                         return Ok(result);
@@ -1284,7 +1380,12 @@ impl DiskCache {
         Ok(result)
     }
 
-    fn dependent_ids_for(&self, entity_ids: &[String]) -> Result<Vec<String>, rusqlite::Error> {
+    /// Direct dependents of `entity_ids` as `(target, dependent)` pairs, in
+    /// frontier order.
+    fn dependent_ids_for(
+        &self,
+        entity_ids: &[String],
+    ) -> Result<Vec<(String, String)>, rusqlite::Error> {
         let mut dependents = Vec::new();
         for chunk in entity_ids.chunks(SQL_PARAM_CHUNK) {
             if chunk.is_empty() {
@@ -1307,7 +1408,7 @@ impl DiskCache {
             }
             for entity_id in chunk {
                 if let Some(ids) = by_target.remove(entity_id) {
-                    dependents.extend(ids);
+                    dependents.extend(ids.into_iter().map(|id| (entity_id.clone(), id)));
                 }
             }
         }
@@ -1322,7 +1423,7 @@ impl DiskCache {
             }
             let placeholders = repeat_vars(chunk.len());
             let sql = format!(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                  FROM entities WHERE id IN ({placeholders})"
             );
             let mut stmt = self.conn.prepare(&sql)?;
@@ -1364,11 +1465,14 @@ impl DiskCache {
                         end_line: row.get::<_, i64>(5)? as usize,
                         start_byte: row.get::<_, Option<i64>>(11)?.map(|v| v as usize),
                         end_byte: row.get::<_, Option<i64>>(12)?.map(|v| v as usize),
+                        name_start: None,
+                        name_end: None,
                         content: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
                         content_hash: row.get(7)?,
                         structural_hash: row.get(8)?,
                         parent_id: row.get(9)?,
                         metadata,
+                        stable_id: None,
                     })
                 })
                 .ok()?;
@@ -1401,7 +1505,7 @@ impl DiskCache {
             }
             let placeholders = repeat_vars(chunk.len());
             let sql = format!(
-                "SELECT from_entity, to_entity, ref_type FROM edges WHERE from_entity IN ({placeholders})"
+                "SELECT from_entity, to_entity, ref_type, confidence FROM edges WHERE from_entity IN ({placeholders})"
             );
             let mut stmt = self.conn.prepare(&sql).ok()?;
             let rows = stmt
@@ -1412,12 +1516,15 @@ impl DiskCache {
                         let ref_type = match rt.as_str() {
                             "calls" => RefType::Calls,
                             "imports" => RefType::Imports,
+                            "implements" => RefType::Implements,
+                            "extends" => RefType::Extends,
                             _ => RefType::TypeRef,
                         };
                         Ok(EntityRef {
                             from_entity: row.get(0)?,
                             to_entity: row.get(1)?,
                             ref_type,
+                            confidence: row.get(3)?,
                         })
                     },
                 )
@@ -1552,6 +1659,8 @@ impl DiskCache {
 
         // 3. Induced-subgraph edges + assembly.
         let edges = self.edges_among(&visited)?;
+        let test_ids = sem_core::parser::test_detect::test_partition_ids(&entities);
+        let private_ids = sem_core::parser::visibility::private_ids(&entities);
         let entity_map: EntityInfoMap = entities
             .iter()
             .map(|e| {
@@ -1565,6 +1674,8 @@ impl DiskCache {
                         start_line: e.start_line,
                         end_line: e.end_line,
                         parent_id: e.parent_id.clone(),
+                        is_test: test_ids.contains(&e.id),
+                        is_private: private_ids.contains(&e.id),
                     },
                 )
             })
@@ -1637,13 +1748,13 @@ impl DiskCache {
         };
 
         let mut entity_stmt = match self.conn.prepare(
-            "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id FROM entities ORDER BY id",
+            "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private FROM entities ORDER BY id",
         ) {
             Ok(stmt) => stmt,
             Err(_) => return Ok(false),
         };
         let mut edge_stmt = match self.conn.prepare(
-            "SELECT from_entity, to_entity, ref_type FROM edges ORDER BY from_entity, to_entity, CASE ref_type WHEN 'calls' THEN 0 WHEN 'imports' THEN 1 ELSE 2 END",
+            "SELECT from_entity, to_entity, ref_type, confidence FROM edges ORDER BY from_entity, to_entity, CASE ref_type WHEN 'calls' THEN 0 WHEN 'imports' THEN 1 WHEN 'typeref' THEN 2 WHEN 'implements' THEN 3 ELSE 4 END, confidence",
         ) {
             Ok(stmt) => stmt,
             Err(_) => return Ok(false),
@@ -1666,6 +1777,8 @@ impl DiskCache {
                 start_line: row.get::<_, i64>(4).map_err(sql_io_error)? as usize,
                 end_line: row.get::<_, i64>(5).map_err(sql_io_error)? as usize,
                 parent_id: row.get(6).map_err(sql_io_error)?,
+                is_test: row.get::<_, i64>(7).map_err(sql_io_error)? != 0,
+                is_private: row.get::<_, i64>(8).map_err(sql_io_error)? != 0,
             };
             serde_json::to_writer(&mut writer, &entity).map_err(json_io_error)?;
         }
@@ -1683,12 +1796,15 @@ impl DiskCache {
             let ref_type = match rt.as_str() {
                 "calls" => RefType::Calls,
                 "imports" => RefType::Imports,
+                "implements" => RefType::Implements,
+                "extends" => RefType::Extends,
                 _ => RefType::TypeRef,
             };
             let edge = EntityRef {
                 from_entity: row.get(0).map_err(sql_io_error)?,
                 to_entity: row.get(1).map_err(sql_io_error)?,
                 ref_type,
+                confidence: row.get(3).map_err(sql_io_error)?,
             };
             serde_json::to_writer(&mut writer, &edge).map_err(json_io_error)?;
         }
@@ -1719,7 +1835,7 @@ impl DiskCache {
         for chunk in files.chunks(SQL_PARAM_CHUNK) {
             let placeholders = repeat_vars(chunk.len());
             let sql = format!(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private
                  FROM entities
                  WHERE file_path IN ({placeholders})
                  ORDER BY file_path, start_line, end_line, entity_type, name"
@@ -1967,7 +2083,7 @@ impl DiskCache {
     fn load_edges(&self) -> Option<Vec<EntityRef>> {
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -1975,12 +2091,15 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
@@ -2142,11 +2261,14 @@ impl DiskCache {
                 end_line: row.get::<_, i64>(5).ok()? as usize,
                 start_byte: row.get::<_, Option<i64>>(11).ok()?.map(|v| v as usize),
                 end_byte: row.get::<_, Option<i64>>(12).ok()?.map(|v| v as usize),
+                name_start: None,
+                name_end: None,
                 content: row.get::<_, Option<String>>(6).ok()?.unwrap_or_default(),
                 content_hash: row.get(7).ok()?,
                 structural_hash: row.get(8).ok()?,
                 parent_id: row.get(9).ok()?,
                 metadata: metadata_json.and_then(|j| serde_json::from_str(&j).ok()),
+                stable_id: None,
             };
             let mut entity = entity;
             if entity.content.is_empty() {
@@ -2163,7 +2285,7 @@ impl DiskCache {
         // Load ALL cached edges (build_incremental decides which to keep)
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let cached_edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -2171,12 +2293,15 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
@@ -2327,15 +2452,22 @@ impl DiskCache {
         if repair_changed_clean_entity_ids {
             tx.execute("DELETE FROM edges", [])?;
             let mut ins = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 let rt = match edge.ref_type {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                ins.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                ins.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         } else {
             let mut affected_sources: HashSet<String> =
@@ -2378,7 +2510,7 @@ impl DiskCache {
             }
 
             let mut ins = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 if !affected_sources.contains(&edge.from_entity)
@@ -2391,8 +2523,15 @@ impl DiskCache {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                ins.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                ins.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         }
 
@@ -2419,6 +2558,8 @@ fn entity_info_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<EntityInfo>
         start_line: row.get::<_, i64>(4)? as usize,
         end_line: row.get::<_, i64>(5)? as usize,
         parent_id: row.get(6)?,
+        is_test: row.get::<_, i64>(7)? != 0,
+        is_private: row.get::<_, i64>(8)? != 0,
     })
 }
 
@@ -2555,7 +2696,10 @@ mod tests {
             end_line: 1,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -2577,6 +2721,8 @@ mod tests {
             parent_id: None,
             start_line: 1,
             end_line: 1,
+            is_test: false,
+            is_private: false,
         }
     }
 
@@ -2598,6 +2744,7 @@ mod tests {
             from_entity: from_entity.to_string(),
             to_entity: to_entity.to_string(),
             ref_type: RefType::Calls,
+            confidence: 1.0,
         }
     }
 
@@ -3221,6 +3368,10 @@ mod tests {
                 ("a-leaf-id", 3),
             ]
         );
+        assert_eq!(
+            result.impact_paths["a-leaf-id"],
+            vec!["root-id", "c-parent-id", "a-mid-id", "a-leaf-id"]
+        );
 
         drop(cache);
         cleanup(root);
@@ -1,9 +1,12 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::Path;
 
 use colored::Colorize;
+use rayon::prelude::*;
 use sem_core::git::bridge::GitBridge;
 use sem_core::git::types::BlameLineInfo;
+use sem_core::model::entity::SemanticEntity;
 
 use super::truncate_str;
 
@@ -11,8 +14,12 @@ pub struct BlameOptions {
     pub cwd: String,
     pub file_path: String,
     pub json: bool,
+    /// Only blame entities overlapping these 1-based, inclusive lines, or
+    /// the raw lines when no entity does (`--range START:END`).
+    pub range: Option<(usize, usize)>,
 }
 
+#[derive(Debug, PartialEq)]
 struct EntityBlame {
     name: String,
     entity_type: String,
@@ -43,8 +50,26 @@ pub fn blame_command(opts: BlameOptions) {
         }
     };
 
-    let entities = registry.extract_entities(&opts.file_path, &content);
-    if entities.is_empty() {
+    if let Some((start, end)) = opts.range {
+        let line_count = content.lines().count();
+        if start > line_count {
+            eprintln!(
+                "{} Range {}:{} is past the end of {} ({} lines)",
+                "error:".red().bold(),
+                start,
+                end,
+                opts.file_path,
+                line_count
+            );
+            std::process::exit(1);
+        }
+    }
+
+    let mut entities = registry.extract_entities(&opts.file_path, &content);
+    if let Some((start, end)) = opts.range {
+        entities = entities_in_range(entities, start, end);
+    }
+    if entities.is_empty() && opts.range.is_none() {
         if opts.json {
             println!("[]");
             return;
@@ -92,57 +117,11 @@ pub fn blame_command(opts: BlameOptions) {
             std::process::exit(1);
         }
     };
-    let blame_by_line: HashMap<usize, BlameLineInfo> = blame
-        .into_iter()
-        .map(|line| (line.line_number, line))
-        .collect();
-
-    // For each entity, find the most recent commit that touched its lines
-    let mut results: Vec<EntityBlame> = Vec::new();
-
-    for entity in &entities {
-        // Find the latest commit across the entity's line range
-        let mut selected: Option<&BlameLineInfo> = None;
-
-        for line in entity.start_line..=entity.end_line {
-            if let Some(info) = blame_by_line.get(&line) {
-                if info.commit_sha.is_none() {
-                    selected = Some(info);
-                    break;
-                }
-
-                let is_newer = match (info.author_time, selected.and_then(|s| s.author_time)) {
-                    (Some(current), Some(previous)) => current > previous,
-                    (Some(_), None) => true,
-                    _ => selected.is_none(),
-                };
-                if is_newer {
-                    selected = Some(info);
-                }
-            }
-        }
-
-        let (author, date, commit_sha, summary) = match selected {
-            Some(info) => (
-                info.author.clone(),
-                info.author_time.map(chrono_lite_format).unwrap_or_default(),
-                info.commit_sha.clone(),
-                info.summary.clone(),
-            ),
-            None => (String::new(), String::new(), None, String::new()),
-        };
-
-        results.push(EntityBlame {
-            name: entity.name.clone(),
-            entity_type: entity.entity_type.clone(),
-            start_line: entity.start_line,
-            end_line: entity.end_line,
-            author,
-            date,
-            commit_sha,
-            summary,
-        });
+    if let Some((start, end)) = opts.range.filter(|_| entities.is_empty()) {
+        print_line_blames(&opts, &blame, start, end);
+        return;
     }
+    let results = entity_blames(&entities, &blame);
 
     if opts.json {
         let output: Vec<_> = results
@@ -210,41 +189,382 @@ pub fn blame_command(opts: BlameOptions) {
     }
 }
 
-/// Simple timestamp formatting without external deps.
-fn chrono_lite_format(unix_seconds: i64) -> String {
-    // Convert unix timestamp to date string
-    let days = unix_seconds / 86400;
-    let mut y = 1970;
-    let mut remaining_days = days;
-
-    loop {
-        let year_days = if is_leap(y) { 366 } else { 365 };
-        if remaining_days < year_days {
-            break;
-        }
-        remaining_days -= year_days;
-        y += 1;
-    }
+/// Entities overlapping lines `start..=end`.
+fn entities_in_range(
+    entities: Vec<SemanticEntity>,
+    start: usize,
+    end: usize,
+) -> Vec<SemanticEntity> {
+    entities
+        .into_iter()
+        .filter(|entity| entity.start_line <= end && entity.end_line >= start)
+        .collect()
+}
 
-    let month_days = if is_leap(y) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+/// Plain per-line blame for a `--range` no entity overlaps.
+fn print_line_blames(opts: &BlameOptions, blame: &[BlameLineInfo], start: usize, end: usize) {
+    let lines: Vec<&BlameLineInfo> = blame
+        .iter()
+        .filter(|line| (start..=end).contains(&line.line_number))
+        .collect();
+    let date = |info: &BlameLineInfo| {
+        info.author_time
+            .map(|time| format_commit_time(time, info.author_tz_offset_minutes))
+            .unwrap_or_default()
     };
 
-    let mut m = 0;
-    for (i, &md) in month_days.iter().enumerate() {
-        if remaining_days < md {
-            m = i;
-            break;
+    if opts.json {
+        let output: Vec<_> = lines
+            .iter()
+            .map(|info| {
+                serde_json::json!({
+                    "line": info.line_number,
+                    "author": if info.author.is_empty() { "unknown" } else { &info.author },
+                    "date": date(info),
+                    "commit": info.commit_sha,
+                    "summary": info.summary,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string(&output).unwrap());
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "┌─ {} (lines {}-{}, no entity) ",
+            opts.file_path, start, end
+        )
+        .bold()
+    );
+    println!("│");
+    let width = end.to_string().len();
+    for info in lines {
+        let sha_short = match info.commit_sha.as_deref() {
+            Some(sha) => &sha[..sha.len().min(8)],
+            None => "uncommtd",
+        };
+        println!(
+            "│  {:>width$}  {}  {}  {}  {}",
+            info.line_number,
+            sha_short.yellow(),
+            info.author.cyan(),
+            date(info).dimmed(),
+            truncate_str(&info.summary, 40),
+            width = width,
+        );
+    }
+    println!("│");
+    println!("└{}", "─".repeat(60));
+}
+
+/// Attribute each entity to the most recent commit touching its lines.
+/// Blame lines are indexed by line number once, so each entity only scans
+/// its own slice, and entities are resolved in parallel.
+fn entity_blames(entities: &[SemanticEntity], blame: &[BlameLineInfo]) -> Vec<EntityBlame> {
+    let line_count = blame.iter().map(|line| line.line_number).max().unwrap_or(0);
+    let mut by_line: Vec<Option<&BlameLineInfo>> = vec![None; line_count + 1];
+    for line in blame {
+        by_line[line.line_number] = Some(line);
+    }
+
+    entities
+        .par_iter()
+        .map(|entity| {
+            let end = entity.end_line.saturating_add(1).min(by_line.len());
+            let start = entity.start_line.min(end);
+            let selected = select_latest_line(by_line[start..end].iter().flatten().copied());
+
+            let (author, date, commit_sha, summary) = match selected {
+                Some(info) => (
+                    info.author.clone(),
+                    info.author_time
+                        .map(|time| format_commit_time(time, info.author_tz_offset_minutes))
+                        .unwrap_or_default(),
+                    info.commit_sha.clone(),
+                    info.summary.clone(),
+                ),
+                None => (String::new(), String::new(), None, String::new()),
+            };
+
+            EntityBlame {
+                name: entity.name.clone(),
+                entity_type: entity.entity_type.clone(),
+                start_line: entity.start_line,
+                end_line: entity.end_line,
+                author,
+                date,
+                commit_sha,
+                summary,
+            }
+        })
+        .collect()
+}
+
+/// Last author and commit of a line range, as shown by `--blame` outputs.
+pub(crate) struct Owner {
+    pub author: String,
+    pub commit_sha: Option<String>,
+    pub date: String,
+}
+
+impl Owner {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "author": if self.author.is_empty() { "unknown" } else { &self.author },
+            "commit": self.commit_sha,
+            "date": self.date,
+        })
+    }
+
+    /// `author (sha)`, or `author (uncommitted)`.
+    pub(crate) fn label(&self) -> String {
+        let sha = match self.commit_sha.as_deref() {
+            Some(sha) => &sha[..sha.len().min(8)],
+            None => "uncommitted",
+        };
+        format!("{} ({})", self.author, sha)
+    }
+}
+
+/// Looks up owners of line ranges across a repository, blaming each file
+/// once. Files git can't blame (untracked, outside the repo) have no owner.
+pub(crate) struct OwnerLookup {
+    git: GitBridge,
+    files: RefCell<HashMap<String, Vec<BlameLineInfo>>>,
+}
+
+impl OwnerLookup {
+    pub(crate) fn open(cwd: &Path) -> Option<Self> {
+        Some(Self {
+            git: GitBridge::open(cwd).ok()?,
+            files: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Owner of `start_line..=end_line` in a repo-relative `file_path`.
+    pub(crate) fn owner(
+        &self,
+        file_path: &str,
+        start_line: usize,
+        end_line: usize,
+    ) -> Option<Owner> {
+        let mut files = self.files.borrow_mut();
+        let blame = files.entry(file_path.to_string()).or_insert_with(|| {
+            self.git
+                .blame_file_porcelain(Path::new(file_path))
+                .unwrap_or_default()
+        });
+        let info = select_latest_line(
+            blame
+                .iter()
+                .filter(|line| (start_line..=end_line).contains(&line.line_number)),
+        )?;
+        Some(Owner {
+            author: info.author.clone(),
+            commit_sha: info.commit_sha.clone(),
+            date: info
+                .author_time
+                .map(|time| format_commit_time(time, info.author_tz_offset_minutes))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The newest line in an entity's range; an uncommitted line wins outright.
+fn select_latest_line<'a>(
+    lines: impl Iterator<Item = &'a BlameLineInfo>,
+) -> Option<&'a BlameLineInfo> {
+    let mut selected: Option<&BlameLineInfo> = None;
+    for info in lines {
+        if info.commit_sha.is_none() {
+            return Some(info);
+        }
+
+        let is_newer = match (info.author_time, selected.and_then(|s| s.author_time)) {
+            (Some(current), Some(previous)) => current > previous,
+            (Some(_), None) => true,
+            _ => selected.is_none(),
+        };
+        if is_newer {
+            selected = Some(info);
         }
-        remaining_days -= md;
     }
+    selected
+}
+
+/// ISO-8601 timestamp in the author's own timezone, e.g.
+/// `2024-03-01T09:15:00-05:00`. Without an offset the time is shown in UTC.
+pub(super) fn format_commit_time(unix_seconds: i64, offset_minutes: Option<i32>) -> String {
+    let offset_minutes = offset_minutes.unwrap_or(0);
+    let local = unix_seconds + i64::from(offset_minutes) * 60;
+    let days = local.div_euclid(86_400);
+    let seconds_of_day = local.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
 
-    let d = remaining_days + 1;
-    format!("{:04}-{:02}-{:02}", y, m + 1, d)
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let offset = offset_minutes.unsigned_abs();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        sign,
+        offset / 60,
+        offset % 60,
+    )
 }
 
-fn is_leap(y: i64) -> bool {
-    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+/// Proleptic Gregorian (year, month, day) for days since 1970-01-01
+/// (Howard Hinnant's `civil_from_days`); valid for negative days too.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sem_core::parser::plugins::create_default_registry;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    /// The original per-entity lookup through a line-number map.
+    fn reference_entity_blames(
+        entities: &[SemanticEntity],
+        blame: &[BlameLineInfo],
+    ) -> Vec<EntityBlame> {
+        let blame_by_line: HashMap<usize, &BlameLineInfo> =
+            blame.iter().map(|line| (line.line_number, line)).collect();
+        entities
+            .iter()
+            .map(|entity| {
+                let selected = select_latest_line(
+                    (entity.start_line..=entity.end_line)
+                        .filter_map(|line| blame_by_line.get(&line).copied()),
+                );
+                EntityBlame {
+                    name: entity.name.clone(),
+                    entity_type: entity.entity_type.clone(),
+                    start_line: entity.start_line,
+                    end_line: entity.end_line,
+                    author: selected.map(|i| i.author.clone()).unwrap_or_default(),
+                    date: selected
+                        .and_then(|i| {
+                            i.author_time
+                                .map(|time| format_commit_time(time, i.author_tz_offset_minutes))
+                        })
+                        .unwrap_or_default(),
+                    commit_sha: selected.and_then(|i| i.commit_sha.clone()),
+                    summary: selected.map(|i| i.summary.clone()).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entity_blames_match_reference_on_500_entity_file() {
+        let source: String = (0..500)
+            .map(|i| format!("def func_{i}(x):\n    y = x + {i}\n    return y\n\n"))
+            .collect();
+        let registry = create_default_registry();
+        let entities = registry.extract_entities("big.py", &source);
+        assert_eq!(entities.len(), 500);
+
+        let line_count = source.lines().count();
+        let blame: Vec<BlameLineInfo> = (1..=line_count)
+            .map(|line| {
+                // Spread lines over 37 commits with shuffled timestamps, and
+                // leave some lines uncommitted.
+                let commit = (line * 7919) % 37;
+                BlameLineInfo {
+                    line_number: line,
+                    commit_sha: (line % 97 != 0).then(|| format!("{commit:040x}")),
+                    author: format!("author-{}", commit % 5),
+                    author_time: Some(1_600_000_000 + ((commit * 104_729) % 37) as i64 * 86_400),
+                    author_tz_offset_minutes: Some(((commit % 3) as i32 - 1) * 300),
+                    summary: format!("commit {commit}"),
+                }
+            })
+            .collect();
+
+        let started = Instant::now();
+        let reference = reference_entity_blames(&entities, &blame);
+        let reference_elapsed = started.elapsed();
+
+        let started = Instant::now();
+        let actual = entity_blames(&entities, &blame);
+        let actual_elapsed = started.elapsed();
+        eprintln!(
+            "blame 500 entities: reference {reference_elapsed:?}, indexed {actual_elapsed:?}"
+        );
+
+        assert_eq!(actual, reference);
+    }
+
+    #[test]
+    fn entities_in_range_keeps_only_overlapping_entities() {
+        let source = "def first():\n    return 1\n\ndef second():\n    return 2\n\ndef third():\n    return 3\n";
+        let registry = create_default_registry();
+        let entities = registry.extract_entities("three.py", source);
+        assert_eq!(entities.len(), 3);
+
+        let names = |start, end| {
+            entities_in_range(entities.clone(), start, end)
+                .into_iter()
+                .map(|entity| entity.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(2, 4), vec!["first", "second"]);
+        assert_eq!(names(5, 5), vec!["second"]);
+        assert!(names(6, 6).is_empty());
+    }
+
+    #[test]
+    fn format_commit_time_at_epoch() {
+        assert_eq!(format_commit_time(0, None), "1970-01-01T00:00:00+00:00");
+        assert_eq!(format_commit_time(0, Some(0)), "1970-01-01T00:00:00+00:00");
+        assert_eq!(format_commit_time(0, Some(60)), "1970-01-01T01:00:00+01:00");
+        // West of UTC the epoch is still the evening of the previous day.
+        assert_eq!(
+            format_commit_time(0, Some(-300)),
+            "1969-12-31T19:00:00-05:00"
+        );
+    }
+
+    #[test]
+    fn format_commit_time_applies_negative_offset() {
+        // 2024-03-01T03:30:00Z is still Feb 29 (leap day) in New York.
+        assert_eq!(
+            format_commit_time(1_709_263_800, Some(-300)),
+            "2024-02-29T22:30:00-05:00"
+        );
+        // Later that day the local date matches the UTC date again.
+        assert_eq!(
+            format_commit_time(1_709_310_600, Some(-300)),
+            "2024-03-01T11:30:00-05:00"
+        );
+        // Half-hour offsets are kept, and east of UTC rolls forward.
+        assert_eq!(
+            format_commit_time(1_709_310_600, Some(330)),
+            "2024-03-01T22:00:00+05:30"
+        );
+        assert_eq!(
+            format_commit_time(1_709_337_600, Some(-150)),
+            "2024-03-01T21:30:00-02:30"
+        );
+    }
 }
@@ -1194,14 +1194,28 @@ fn working_tree_clean(repo_root: &Path) -> bool {
 
 /// Try to run `sem impact` via cloud. Returns Some(()) on success.
 pub fn try_cloud_impact(opts: &ImpactOptions) -> Option<()> {
-    // --tests needs test classification data the cloud API doesn't expose.
-    if matches!(opts.mode, super::impact::ImpactMode::Tests) {
+    // --tests needs test classification data the cloud API doesn't expose,
+    // and --callers-only/--callees-only need depth-bounded traversals.
+    if matches!(
+        opts.mode,
+        super::impact::ImpactMode::Tests
+            | super::impact::ImpactMode::Callers
+            | super::impact::ImpactMode::Callees
+    ) {
         return None;
     }
     // --no-cache means "compute fresh": serving a remote snapshot would violate it.
     if opts.no_cache {
         return None;
     }
+    // The cloud index doesn't carry edge confidence, so it can't filter on it.
+    if opts.min_confidence > 0.0 {
+        return None;
+    }
+    // Owners come from local git blame.
+    if opts.blame {
+        return None;
+    }
     // A --file hint exists to disambiguate same-named entities, but the cloud
     // resolves by name with a silent name-only fallback when the file doesn't
     // match its index — which returns the WRONG entity's graph (repro: weave's
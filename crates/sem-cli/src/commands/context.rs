@@ -17,6 +17,7 @@ pub struct ContextOptions {
     pub file_exts: Vec<String>,
     pub no_cache: bool,
     pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
 }
 
 pub fn context_command(opts: ContextOptions) {
@@ -35,8 +36,12 @@ pub fn context_command(opts: ContextOptions) {
     let root = root.as_path();
     let registry = super::create_registry(&root.to_string_lossy());
     let ext_filter = super::graph::normalize_exts(&opts.file_exts);
-    let source_scope =
-        super::graph::cache_source_scope(root, &ext_filter, opts.no_default_excludes);
+    let source_scope = super::graph::cache_source_scope(
+        root,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
 
     let file_path = opts
         .file_path
@@ -74,6 +79,7 @@ pub fn context_command(opts: ContextOptions) {
                 &registry,
                 &ext_filter,
                 opts.no_default_excludes,
+                opts.follow_symlinks,
             );
             let prog = crate::progress::Progress::start_staged();
             let (graph, all_entities) = super::graph::get_or_build_graph(
@@ -284,6 +290,7 @@ fn try_sidecar_context(opts: &ContextOptions) -> bool {
     if opts.json
         || opts.no_cache
         || opts.no_default_excludes
+        || opts.follow_symlinks
         || !opts.file_exts.is_empty()
         || opts.entity_id.is_some()
         || opts.file_path.is_some()
@@ -297,7 +304,7 @@ fn try_sidecar_context(opts: &ContextOptions) -> bool {
         return false;
     };
     let root = git.repo_root().to_path_buf();
-    if root.join(".semignore").exists() {
+    if super::files::has_custom_ignores(&root) {
         return false;
     }
     let mut request = serde_json::json!({
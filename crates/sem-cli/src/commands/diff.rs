@@ -1,23 +1,30 @@
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::process;
 use std::time::Instant;
 
+use colored::Colorize;
 use git2::{ObjectType, Oid, Repository};
-use sem_core::git::bridge::GitBridge;
+use sem_core::git::bridge::{GitBridge, GitError};
 use sem_core::git::jj::maybe_resolve_ref;
-use sem_core::git::types::{DiffScope, FileChange, FileStatus};
+use sem_core::git::types::{CommitFilter, CommitInfo, DiffScope, FileChange, FileStatus};
 use sem_core::model::change::ChangeType;
+use sem_core::model::diagnostic::ParseDiagnostic;
+use sem_core::model::identity::SimilarityMetric;
 use sem_core::parser::differ::{
-    collect_binary_file_changes, compute_semantic_diff, BinaryFileChange, DiffResult,
+    collect_binary_file_changes, compute_semantic_diff_with, BinaryFileChange, DiffResult,
+    DiffSettings,
 };
 use sem_core::parser::plugins::code::languages::get_language_config;
 use sem_core::parser::registry::{detect_ext_from_content, ParserRegistry};
 
 use crate::formatters::{
-    json::format_json, markdown::format_markdown, plain::format_plain, terminal::format_terminal,
+    json::{format_json, format_json_with_diff},
+    markdown::format_markdown,
+    plain::{format_entities_only, format_name_only, format_plain},
+    terminal::{format_terminal, write_terminal},
 };
 use crate::stats::SemLifetimeStats;
 
@@ -28,12 +35,52 @@ pub struct DiffOptions {
     pub commit: Option<String>,
     pub from: Option<String>,
     pub to: Option<String>,
+    /// `--author`/`--since`: range diffs only count matching commits
+    pub commit_filter: CommitFilter,
     pub stdin: bool,
     pub patch: bool,
     pub verbose: bool,
     pub profile: bool,
     pub file_exts: Vec<String>,
     pub no_cosmetics: bool,
+    /// Keep only changes to entities of these types (empty = all)
+    pub only_types: Vec<String>,
+    /// Drop changes to entities of these types
+    pub exclude_types: Vec<String>,
+    /// Only diff these repo-relative files, from `--files-from` (None = all)
+    pub files_from: Option<Vec<String>>,
+    /// Print tab-separated change type and entity ID per change instead of `format`
+    pub entity_ids: bool,
+    /// Print the sorted paths of changed files instead of `format`
+    pub name_only: bool,
+    /// Print sorted `entity_id<TAB>change_type` lines instead of `format`
+    pub entities_only: bool,
+    /// Add per-change line diff hunks to JSON output
+    pub with_diff: bool,
+    /// Keep running and redraw the working-tree diff as files change
+    pub watch: bool,
+    /// Also list unchanged entities of changed files (full inventory)
+    pub include_unchanged: bool,
+    /// Content similarity used to match renamed or moved entities
+    pub similarity: Similarity,
+    /// Minimum content similarity for a fuzzy rename/move match
+    pub rename_threshold: f64,
+    /// Compare two files or directories on disk instead of git revisions
+    pub disk_compare: Option<DiskCompare>,
+    /// Exit with status 1 when any change of these types is found
+    pub fail_on: Vec<FailOn>,
+    /// Exit with status 1 when more than this many changes are found
+    pub fail_on_count: Option<usize>,
+    /// Show at most this many changes in terminal output
+    pub limit: Option<usize>,
+    /// Lines of context around each modified entity's changed lines
+    pub context: Option<usize>,
+    /// Leave out test files and `#[cfg(test)]` modules
+    pub exclude_tests: bool,
+    /// Leave out private entities and anything nested in them
+    pub public_only: bool,
+    /// Swap each file's before and after content before matching
+    pub reverse: bool,
     pub label: Option<String>,
     pub args: Vec<String>,
 }
@@ -47,6 +94,102 @@ pub enum OutputFormat {
     Markdown,
 }
 
+/// `--file-a/--file-b` or `--dir-a/--dir-b`: two snapshots read straight
+/// from disk, without git. Either side may be missing.
+#[derive(Clone)]
+pub enum DiskCompare {
+    Files { a: String, b: String },
+    Dirs { a: String, b: String },
+}
+
+/// Change types `--fail-on` can gate on.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FailOn {
+    Added,
+    Modified,
+    Deleted,
+    Moved,
+    Renamed,
+    Reordered,
+}
+
+impl FailOn {
+    fn count(self, result: &DiffResult) -> usize {
+        match self {
+            FailOn::Added => result.added_count,
+            FailOn::Modified => result.modified_count,
+            FailOn::Deleted => result.deleted_count,
+            FailOn::Moved => result.moved_count,
+            FailOn::Renamed => result.renamed_count,
+            FailOn::Reordered => result.reordered_count,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            FailOn::Added => "added",
+            FailOn::Modified => "modified",
+            FailOn::Deleted => "deleted",
+            FailOn::Moved => "moved",
+            FailOn::Renamed => "renamed",
+            FailOn::Reordered => "reordered",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Similarity {
+    #[default]
+    Jaccard,
+    Levenshtein,
+    TokenSort,
+}
+
+impl From<Similarity> for SimilarityMetric {
+    fn from(similarity: Similarity) -> Self {
+        match similarity {
+            Similarity::Jaccard => SimilarityMetric::Jaccard,
+            Similarity::Levenshtein => SimilarityMetric::Levenshtein,
+            Similarity::TokenSort => SimilarityMetric::TokenSort,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Whether output is one of the line-per-item scripting listings, which
+    /// print nothing at all when there are no changes.
+    fn listing_output(&self) -> bool {
+        self.entity_ids || self.name_only || self.entities_only
+    }
+
+    pub(super) fn diff_settings(&self) -> DiffSettings {
+        DiffSettings {
+            include_unchanged: self.include_unchanged,
+            similarity: self.similarity.into(),
+            rename_threshold: self.rename_threshold,
+            eol_normalize: crate::config::eol_normalize(),
+        }
+    }
+
+    /// Apply `--no-cosmetics` and `--only-type`/`--exclude-type` to a
+    /// computed diff, recounting the summary for what's left.
+    pub(super) fn filter_changes(&self, result: &mut DiffResult) {
+        if self.no_cosmetics {
+            retain_non_cosmetic_changes(result);
+        }
+        if !self.only_types.is_empty() || !self.exclude_types.is_empty() {
+            retain_entity_types(result, &self.only_types, &self.exclude_types);
+        }
+    }
+
+    pub(super) fn registry(&self) -> ParserRegistry {
+        let mut registry = super::create_registry(&self.cwd);
+        registry.exclude_tests = self.exclude_tests;
+        registry.public_only = self.public_only;
+        registry
+    }
+}
+
 /// Parsed result of git-diff-style positional arguments
 struct ParsedArgs {
     /// The resolved diff scope (None = auto-detect)
@@ -426,9 +569,7 @@ fn parse_output_format(value: &str) -> OutputFormat {
         "json" => OutputFormat::Json,
         "markdown" | "md" => OutputFormat::Markdown,
         _ => {
-            eprintln!(
-                "\x1b[31mError: invalid output format '{value}'. Expected terminal, plain, json, markdown, or md.\x1b[0m"
-            );
+            eprintln!("{}", format!("Error: invalid output format '{value}'. Expected terminal, plain, json, markdown, or md.").red());
             process::exit(1);
         }
     }
@@ -475,11 +616,14 @@ fn normalize_trailing_output_format(opts: &mut DiffOptions) {
                     continue;
                 }
                 Some(value) => {
-                    eprintln!("\x1b[31mError: --format requires a value before '{value}'.\x1b[0m");
+                    eprintln!(
+                        "{}",
+                        format!("Error: --format requires a value before '{value}'.").red()
+                    );
                     process::exit(1);
                 }
                 None => {
-                    eprintln!("\x1b[31mError: --format requires a value.\x1b[0m");
+                    eprintln!("{}", "Error: --format requires a value.".red());
                     process::exit(1);
                 }
             }
@@ -698,7 +842,10 @@ fn parse_args(args: Vec<String>, cwd: &str) -> ParsedArgs {
         };
     }
 
-    eprintln!("\x1b[31mError: too many positional arguments. Use -- to separate pathspecs.\x1b[0m");
+    eprintln!(
+        "{}",
+        "Error: too many positional arguments. Use -- to separate pathspecs.".red()
+    );
     process::exit(1);
 }
 
@@ -1199,9 +1346,11 @@ fn parse_unified_diff(
 
             if !has_binary_content && before_content.is_none() && after_content.is_none() {
                 eprintln!(
-                    "\x1b[33mwarning:\x1b[0m could not resolve contents for \x1b[1m{}\x1b[0m. \
-                     Try running from inside the repo, or use \x1b[1m-C /path/to/repo\x1b[0m.",
-                    e.file_path
+                    "{} could not resolve contents for {}. \
+                     Try running from inside the repo, or use {}.",
+                    "warning:".yellow(),
+                    e.file_path.bold(),
+                    "-C /path/to/repo".bold()
                 );
             }
 
@@ -1327,6 +1476,159 @@ fn file_compare_changes(
     )
 }
 
+/// Check that each piped change carries the content its status implies, so
+/// a mistyped payload fails loudly instead of diffing as empty or binary.
+fn validate_stdin_changes(changes: &[FileChange]) -> Result<(), String> {
+    for (index, change) in changes.iter().enumerate() {
+        if change.file_path.is_empty() {
+            return Err(format!("change {index} has an empty filePath"));
+        }
+        let needs_before = change.status != FileStatus::Added;
+        let needs_after = change.status != FileStatus::Deleted;
+        let missing = match (
+            needs_before && change.before_content.is_none(),
+            needs_after && change.after_content.is_none(),
+        ) {
+            (true, true) => "beforeContent and afterContent",
+            (true, false) => "beforeContent",
+            (false, true) => "afterContent",
+            (false, false) => continue,
+        };
+        return Err(format!(
+            "change {index} ({}) is {} but has no {missing}",
+            change.file_path, change.status
+        ));
+    }
+    Ok(())
+}
+
+/// One side of a disk compare: `None` when the path doesn't exist, else its
+/// content as [`read_file_compare_content`] reads it (`Some(None)` = binary).
+fn read_disk_side(path: &Path) -> Option<Option<String>> {
+    match read_file_compare_content(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            eprintln!("{}", format!("Error reading {}: {e}", path.display()).red());
+            process::exit(1);
+        }
+    }
+}
+
+/// A file-level change between two disk sides, added or deleted when one
+/// side is missing. A binary side stays `None` so the pipeline reports it as
+/// a binary change.
+fn disk_side_change(
+    file_path: String,
+    before: Option<Option<String>>,
+    after: Option<Option<String>>,
+) -> Option<FileChange> {
+    let status = match (&before, &after) {
+        (None, None) => return None,
+        (None, Some(_)) => FileStatus::Added,
+        (Some(_), None) => FileStatus::Deleted,
+        (Some(_), Some(_)) => FileStatus::Modified,
+    };
+    Some(FileChange {
+        file_path,
+        status,
+        old_file_path: None,
+        before_content: before.flatten(),
+        after_content: after.flatten(),
+    })
+}
+
+fn disk_compare_changes(
+    compare: &DiskCompare,
+    opts: &DiffOptions,
+    pathspecs: &[String],
+) -> Vec<FileChange> {
+    let cwd = Path::new(&opts.cwd);
+    match compare {
+        DiskCompare::Files { a, b } => {
+            let before = read_disk_side(&cwd.join(a));
+            let after = read_disk_side(&cwd.join(b));
+            match (before, after) {
+                (None, None) => {
+                    eprintln!("{}", format!("Error: neither {a} nor {b} exists").red());
+                    process::exit(1);
+                }
+                (Some(Some(before)), Some(Some(after))) => {
+                    let target_label = opts.label.clone().unwrap_or_else(|| b.clone());
+                    let registry = opts.registry();
+                    let (changes, language_mismatch) =
+                        file_compare_changes(a, &target_label, before, after, &registry);
+                    if let Some((language_a, language_b)) = language_mismatch {
+                        eprintln!(
+                            "warning: comparing files with different languages: {a} ({language_a}) and {b} ({language_b}); rendering as delete/add"
+                        );
+                    }
+                    changes
+                }
+                (before, after) => {
+                    let file_path = match (&opts.label, &after) {
+                        (Some(label), _) => label.clone(),
+                        (None, Some(_)) => b.clone(),
+                        (None, None) => a.clone(),
+                    };
+                    disk_side_change(file_path, before, after)
+                        .into_iter()
+                        .collect()
+                }
+            }
+        }
+        DiskCompare::Dirs { a, b } => {
+            let dir_a = cwd.join(a);
+            let dir_b = cwd.join(b);
+            if !dir_a.is_dir() && !dir_b.is_dir() {
+                eprintln!(
+                    "{}",
+                    format!("Error: neither {a} nor {b} is a directory").red()
+                );
+                process::exit(1);
+            }
+            let mut relative_paths = snapshot_files(&dir_a);
+            relative_paths.extend(snapshot_files(&dir_b));
+            relative_paths
+                .into_iter()
+                .filter(|path| {
+                    pathspecs.is_empty()
+                        || pathspecs.iter().any(|spec| path_matches_spec(path, spec))
+                })
+                .filter(|path| {
+                    // Skip identical files before decoding either side.
+                    let bytes_a = std::fs::read(dir_a.join(path)).ok();
+                    bytes_a.is_none() || bytes_a != std::fs::read(dir_b.join(path)).ok()
+                })
+                .filter_map(|path| {
+                    let before = read_disk_side(&dir_a.join(&path));
+                    let after = read_disk_side(&dir_b.join(&path));
+                    disk_side_change(path, before, after)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Files under `dir` as sorted `/`-separated relative paths. Nothing is
+/// ignored except `.git`: a snapshot is compared as it sits on disk.
+fn snapshot_files(dir: &Path) -> std::collections::BTreeSet<String> {
+    if !dir.is_dir() {
+        return Default::default();
+    }
+    ignore::WalkBuilder::new(dir)
+        .standard_filters(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(dir).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
 pub fn diff_command(mut opts: DiffOptions) {
     let total_start = Instant::now();
 
@@ -1389,19 +1691,65 @@ pub fn diff_command(mut opts: DiffOptions) {
     // trip at any repo size (measured 33ms local vs 249ms cloud on a 147k
     // entity repo).
 
-    let (file_changes, from_stdin) = if opts.stdin {
+    let targets_range = (opts.from.is_some() && opts.to.is_some())
+        || matches!(
+            parsed.scope,
+            Some(
+                ParsedScope::Range(..)
+                    | ParsedScope::MergeBaseRange(..)
+                    | ParsedScope::FileCompare { .. }
+            )
+        );
+    if !opts.commit_filter.is_empty() && (!targets_range || opts.stdin || opts.patch) {
+        exit_commit_filter_needs_range();
+    }
+
+    if opts.watch {
+        if opts.stdin
+            || opts.patch
+            || opts.staged
+            || opts.commit.is_some()
+            || opts.from.is_some()
+            || opts.to.is_some()
+            || parsed.scope.is_some()
+        {
+            eprintln!(
+                "{}",
+                "Error: --watch only follows working-tree changes".red()
+            );
+            process::exit(1);
+        }
+        super::watch::watch_command(&opts, &parsed.pathspecs);
+        return;
+    }
+
+    let mut skipped_files = Vec::new();
+    let (file_changes, from_stdin) = if let Some(compare) = &opts.disk_compare {
+        if parsed.scope.is_some() || !opts.commit_filter.is_empty() {
+            eprintln!("{}", "Error: --file-a/--dir-a compare disk snapshots; revisions and commit filters don't apply".red());
+            process::exit(1);
+        }
+        (
+            disk_compare_changes(compare, &opts, &parsed.pathspecs),
+            false,
+        )
+    } else if opts.stdin {
         // Read FileChange[] from stdin — no git repo needed
         let mut input = String::new();
         std::io::stdin()
             .read_to_string(&mut input)
             .unwrap_or_else(|e| {
-                eprintln!("\x1b[31mError reading stdin: {e}\x1b[0m");
+                eprintln!("{}", format!("Error reading stdin: {e}").red());
                 process::exit(1);
             });
         let changes: Vec<FileChange> = serde_json::from_str(&input).unwrap_or_else(|e| {
-            eprintln!("\x1b[31mError parsing stdin JSON: {e}\x1b[0m");
+            eprintln!("{}", format!("Error parsing stdin JSON: {e}").red());
             process::exit(1);
         });
+        if let Err(e) = validate_stdin_changes(&changes) {
+            eprintln!("{}", format!("Error in stdin JSON: {e}").red());
+            process::exit(1);
+        }
         (changes, true)
     } else if let Some(ParsedScope::FileCompare {
         ref before,
@@ -1414,30 +1762,48 @@ pub fn diff_command(mut opts: DiffOptions) {
         let path_b = Path::new(&opts.cwd).join(after);
 
         // If we're in a git repo and both resolve as refs, prefer ref comparison
-        if let Ok(git) = GitBridge::open(Path::new(&opts.cwd)) {
+        if let Ok(git) = open_diff_bridge(&opts) {
             if git.is_valid_rev(before) && git.is_valid_rev(after) {
                 let scope = DiffScope::Range {
                     from: before.clone(),
                     to: after.clone(),
                 };
-                match git.get_changed_files(&scope, &parsed.pathspecs) {
+                match scope_changed_files(&git, &scope, &opts, &parsed.pathspecs) {
                     Ok(files) => {
-                        return run_diff_pipeline(files, false, &opts, &parsed, total_start, t0)
+                        return run_diff_pipeline(
+                            files,
+                            git.take_skipped_files(),
+                            false,
+                            &opts,
+                            &parsed,
+                            total_start,
+                            t0,
+                        )
                     }
                     Err(e) => {
-                        eprintln!("\x1b[31mError: {e}\x1b[0m");
+                        eprintln!("{}", format!("Error: {e}").red());
                         process::exit(1);
                     }
                 }
             }
         }
 
+        if !opts.commit_filter.is_empty() {
+            exit_commit_filter_needs_range();
+        }
+
         let content_a = read_file_compare_content(&path_a).unwrap_or_else(|e| {
-            eprintln!("\x1b[31mError reading {}: {e}\x1b[0m", path_a.display());
+            eprintln!(
+                "{}",
+                format!("Error reading {}: {e}", path_a.display()).red()
+            );
             process::exit(1);
         });
         let content_b = read_file_compare_content(&path_b).unwrap_or_else(|e| {
-            eprintln!("\x1b[31mError reading {}: {e}\x1b[0m", path_b.display());
+            eprintln!(
+                "{}",
+                format!("Error reading {}: {e}", path_b.display()).red()
+            );
             process::exit(1);
         });
 
@@ -1458,7 +1824,7 @@ pub fn diff_command(mut opts: DiffOptions) {
             };
             (vec![change], false)
         } else {
-            let registry = super::create_registry(&opts.cwd);
+            let registry = opts.registry();
             let (changes, language_mismatch) = file_compare_changes(
                 before,
                 &target_label,
@@ -1480,7 +1846,7 @@ pub fn diff_command(mut opts: DiffOptions) {
         std::io::stdin()
             .read_to_string(&mut input)
             .unwrap_or_else(|e| {
-                eprintln!("\x1b[31mError reading stdin: {e}\x1b[0m");
+                eprintln!("{}", format!("Error reading stdin: {e}").red());
                 process::exit(1);
             });
         let worktree_root = patch_worktree_root
@@ -1505,10 +1871,10 @@ pub fn diff_command(mut opts: DiffOptions) {
         };
         (changes, true)
     } else {
-        let git = match GitBridge::open(Path::new(&opts.cwd)) {
+        let git = match open_diff_bridge(&opts) {
             Ok(g) => g,
             Err(e) => {
-                eprintln!("\x1b[31mError: {e}\x1b[0m");
+                eprintln!("{}", format!("Error: {e}").red());
                 process::exit(1);
             }
         };
@@ -1519,7 +1885,7 @@ pub fn diff_command(mut opts: DiffOptions) {
             match git.get_changed_files(&scope, &parsed.pathspecs) {
                 Ok(files) => files,
                 Err(e) => {
-                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    eprintln!("{}", format!("Error: {e}").red());
                     process::exit(1);
                 }
             }
@@ -1528,10 +1894,10 @@ pub fn diff_command(mut opts: DiffOptions) {
                 from: from.clone(),
                 to: to.clone(),
             };
-            match git.get_changed_files(&scope, &parsed.pathspecs) {
+            match scope_changed_files(&git, &scope, &opts, &parsed.pathspecs) {
                 Ok(files) => files,
                 Err(e) => {
-                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    eprintln!("{}", format!("Error: {e}").red());
                     process::exit(1);
                 }
             }
@@ -1541,7 +1907,7 @@ pub fn diff_command(mut opts: DiffOptions) {
                 match git.get_staged_files_with_base_ref(refspec, &parsed.pathspecs) {
                     Ok(files) => files,
                     Err(e) => {
-                        eprintln!("\x1b[31mError: {e}\x1b[0m");
+                        eprintln!("{}", format!("Error: {e}").red());
                         process::exit(1);
                     }
                 }
@@ -1552,7 +1918,7 @@ pub fn diff_command(mut opts: DiffOptions) {
                 match git.get_changed_files(&scope, &parsed.pathspecs) {
                     Ok(files) => files,
                     Err(e) => {
-                        eprintln!("\x1b[31mError: {e}\x1b[0m");
+                        eprintln!("{}", format!("Error: {e}").red());
                         process::exit(1);
                     }
                 }
@@ -1572,17 +1938,17 @@ pub fn diff_command(mut opts: DiffOptions) {
                             to: ref2.clone(),
                         },
                         Err(e) => {
-                            eprintln!("\x1b[31mError resolving merge base: {e}\x1b[0m");
+                            eprintln!("{}", format!("Error resolving merge base: {e}").red());
                             process::exit(1);
                         }
                     }
                 }
                 ParsedScope::FileCompare { .. } => unreachable!(),
             };
-            match git.get_changed_files(&scope, &parsed.pathspecs) {
+            match scope_changed_files(&git, &scope, &opts, &parsed.pathspecs) {
                 Ok(files) => files,
                 Err(e) => {
-                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    eprintln!("{}", format!("Error: {e}").red());
                     process::exit(1);
                 }
             }
@@ -1591,7 +1957,7 @@ pub fn diff_command(mut opts: DiffOptions) {
             match git.get_changed_files(&scope, &parsed.pathspecs) {
                 Ok(files) => files,
                 Err(e) => {
-                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    eprintln!("{}", format!("Error: {e}").red());
                     process::exit(1);
                 }
             }
@@ -1599,19 +1965,160 @@ pub fn diff_command(mut opts: DiffOptions) {
             match git.detect_and_get_files(&parsed.pathspecs) {
                 Ok((_scope, files)) => files,
                 Err(e) => {
-                    eprintln!("\x1b[31mError: {e}\x1b[0m");
+                    eprintln!("{}", format!("Error: {e}").red());
                     process::exit(1);
                 }
             }
         };
+        skipped_files = git.take_skipped_files();
         (file_changes, false)
     };
+    let file_changes = match &opts.files_from {
+        Some(listed) => retain_listed_files(file_changes, listed),
+        None => file_changes,
+    };
+
+    run_diff_pipeline(
+        file_changes,
+        skipped_files,
+        from_stdin,
+        &opts,
+        &parsed,
+        total_start,
+        t0,
+    );
+}
+
+/// Open the repo for reading a diff's file contents under `--max-file-size`.
+fn open_diff_bridge(opts: &DiffOptions) -> Result<GitBridge, GitError> {
+    GitBridge::open(Path::new(&opts.cwd)).map(|git| {
+        git.with_max_file_size(crate::config::max_file_size())
+            .with_eol_normalize(crate::config::eol_normalize())
+    })
+}
+
+/// Keep the file changes a `--files-from` list names, by new or old path,
+/// so a `git diff --name-only` list keeps renames and deletions too. Unlike
+/// pathspecs, entries match exactly, and an empty list keeps nothing.
+fn retain_listed_files(file_changes: Vec<FileChange>, listed: &[String]) -> Vec<FileChange> {
+    let listed: HashSet<&str> = listed.iter().map(String::as_str).collect();
+    file_changes
+        .into_iter()
+        .filter(|file_change| {
+            listed.contains(file_change.file_path.as_str())
+                || file_change
+                    .old_file_path
+                    .as_deref()
+                    .is_some_and(|old_path| listed.contains(old_path))
+        })
+        .collect()
+}
+
+/// Files changed in `scope`; a range honors `--author`/`--since`.
+fn scope_changed_files(
+    git: &GitBridge,
+    scope: &DiffScope,
+    opts: &DiffOptions,
+    pathspecs: &[String],
+) -> Result<Vec<FileChange>, GitError> {
+    match scope {
+        DiffScope::Range { from, to } if !opts.commit_filter.is_empty() => {
+            git.get_range_diff_files_filtered(from, to, &opts.commit_filter, pathspecs)
+        }
+        _ => git.get_changed_files(scope, pathspecs),
+    }
+}
+
+fn exit_commit_filter_needs_range() -> ! {
+    eprintln!(
+        "{}",
+        "Error: --author and --since need a commit range (A..B or --from/--to)".red()
+    );
+    process::exit(1);
+}
+
+/// Parse `--rename-threshold`: a similarity in (0, 1].
+pub fn parse_rename_threshold(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(threshold) if threshold > 0.0 && threshold <= 1.0 => Ok(threshold),
+        _ => Err(format!(
+            "invalid threshold '{value}' (expected a number in (0, 1])"
+        )),
+    }
+}
+
+/// Parse `--since`: `YYYY-MM-DD`, optionally followed by `THH:MM[:SS]` and a
+/// `Z` or `±HH:MM` offset. Times without an offset are read as UTC.
+pub fn parse_since(value: &str) -> Result<i64, String> {
+    let invalid = || format!("invalid date '{value}' (expected YYYY-MM-DD[THH:MM[:SS]][Z|±HH:MM])");
+    let value = value.trim();
+    let (date, rest) = value.split_at(value.len().min(10));
+    let mut parts = date.splitn(3, '-');
+    let mut field = |len: usize| {
+        parts
+            .next()
+            .filter(|p| p.len() == len && p.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|p| p.parse::<i64>().ok())
+    };
+    let (Some(year), Some(month), Some(day)) = (field(4), field(2), field(2)) else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(invalid());
+    }
 
-    run_diff_pipeline(file_changes, from_stdin, &opts, &parsed, total_start, t0);
+    let mut seconds = 0;
+    let mut offset = 0;
+    if !rest.is_empty() {
+        let time = rest.strip_prefix(['T', ' ']).ok_or_else(invalid)?;
+        let (clock, zone) = match time.find(['Z', '+', '-']) {
+            Some(at) => time.split_at(at),
+            None => (time, ""),
+        };
+        let clock: Vec<i64> = clock
+            .split(':')
+            .map(|p| {
+                (p.len() == 2)
+                    .then(|| p.parse::<i64>().ok())
+                    .flatten()
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<_, _>>()?;
+        seconds = match clock[..] {
+            [h, m] if h < 24 && m < 60 => h * 3600 + m * 60,
+            [h, m, s] if h < 24 && m < 60 && s < 60 => h * 3600 + m * 60 + s,
+            _ => return Err(invalid()),
+        };
+        offset = match zone {
+            "" | "Z" => 0,
+            _ => {
+                let sign = if zone.starts_with('-') { -1 } else { 1 };
+                let (h, m) = zone[1..].split_once(':').ok_or_else(invalid)?;
+                let h: i64 = h.parse().map_err(|_| invalid())?;
+                let m: i64 = m.parse().map_err(|_| invalid())?;
+                sign * (h * 3600 + m * 60)
+            }
+        };
+    }
+
+    Ok(days_from_civil(year, month, day) * 86_400 + seconds - offset)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's
+/// `days_from_civil`).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
 }
 
 fn run_diff_pipeline(
     file_changes: Vec<FileChange>,
+    skipped_files: Vec<ParseDiagnostic>,
     from_stdin: bool,
     opts: &DiffOptions,
     parsed: &ParsedArgs,
@@ -1620,35 +2127,20 @@ fn run_diff_pipeline(
 ) {
     let git_diff_ms = t0.elapsed().as_secs_f64() * 1000.0;
 
-    // Filter by file extensions if specified
-    let file_changes = if opts.file_exts.is_empty() {
-        file_changes
+    let file_changes = if opts.reverse {
+        file_changes.into_iter().map(FileChange::reversed).collect()
     } else {
-        let exts: Vec<String> = opts
-            .file_exts
-            .iter()
-            .map(|e| {
-                if e.starts_with('.') {
-                    e.clone()
-                } else {
-                    format!(".{}", e)
-                }
-            })
-            .collect();
         file_changes
-            .into_iter()
-            .filter(|fc| {
-                exts.iter().any(|ext| {
-                    fc.file_path.ends_with(ext.as_str())
-                        || fc
-                            .old_file_path
-                            .as_ref()
-                            .is_some_and(|old| old.ends_with(ext.as_str()))
-                })
-            })
-            .collect()
     };
 
+    // Filter by file extensions and configured ignore globs
+    let file_changes = filter_ignored(
+        filter_file_exts(file_changes, &opts.file_exts),
+        &super::repo_root_or_cwd(&opts.cwd),
+    );
+
+    let commit = shown_commit(opts, from_stdin);
+
     if file_changes.is_empty() {
         // A diff with nothing to compare is still a diff the user performed —
         // count it so `sem stats` reflects every run, not only the ones that
@@ -1657,12 +2149,19 @@ fn run_diff_pipeline(
         let _ = SemLifetimeStats::load()
             .record_diff(&DiffResult::default(), 0)
             .save();
+        if opts.listing_output() {
+            return;
+        }
         match opts.format {
             OutputFormat::Json => {
-                println!("{}", format_json(&DiffResult::default(), &[]));
+                let output = format_json(&DiffResult::default(), &[]);
+                println!("{}", json_with_commit(output, commit.as_ref()));
             }
             _ => {
-                println!("\x1b[2mNo semantic changes detected.\x1b[0m");
+                if let Some(commit) = &commit {
+                    println!("{}", commit_header(commit, &opts.format));
+                }
+                println!("{}", "No semantic changes detected.".dimmed());
             }
         }
         return;
@@ -1673,20 +2172,41 @@ fn run_diff_pipeline(
     let prog = crate::progress::Progress::start("Computing semantic diff");
 
     let t2 = Instant::now();
-    let registry = super::create_registry(&opts.cwd);
+    let registry = opts.registry();
     let registry_ms = t2.elapsed().as_secs_f64() * 1000.0;
 
     let t3 = Instant::now();
-    let binary_changes = collect_binary_file_changes(&file_changes);
-    let mut result = compute_semantic_diff(&file_changes, &registry, None, None);
+    // Files left unread for their size have no contents either, but they're
+    // reported as skipped rather than as binary changes.
+    let mut binary_changes = collect_binary_file_changes(&file_changes);
+    let mut result =
+        compute_semantic_diff_with(&file_changes, &registry, None, None, opts.diff_settings());
+    if !skipped_files.is_empty() {
+        let diffed: HashSet<&str> = file_changes
+            .iter()
+            .map(|file| file.file_path.as_str())
+            .collect();
+        let skipped: Vec<ParseDiagnostic> = skipped_files
+            .into_iter()
+            .filter(|skip| diffed.contains(skip.file_path.as_str()))
+            .collect();
+        let skipped_paths: HashSet<&str> =
+            skipped.iter().map(|skip| skip.file_path.as_str()).collect();
+        binary_changes.retain(|change| !skipped_paths.contains(change.file_path.as_str()));
+        result
+            .diagnostics
+            .retain(|diagnostic| !skipped_paths.contains(diagnostic.file_path.as_str()));
+        result.diagnostics.extend(skipped);
+        result
+            .diagnostics
+            .sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    }
     let parse_diff_ms = t3.elapsed().as_secs_f64() * 1000.0;
 
     prog.clear();
 
-    // Filter out cosmetic-only changes when --no-cosmetics is set
-    if opts.no_cosmetics {
-        retain_non_cosmetic_changes(&mut result);
-    }
+    // Filter out cosmetic-only changes and unwanted entity types
+    opts.filter_changes(&mut result);
 
     // Record lifetime stats (best-effort)
     let _ = SemLifetimeStats::load()
@@ -1694,15 +2214,40 @@ fn run_diff_pipeline(
         .save();
 
     let t4 = Instant::now();
-    let output = match opts.format {
-        OutputFormat::Json => format_json(&result, &binary_changes),
-        OutputFormat::Markdown => format_markdown(&result, &binary_changes, opts.verbose),
-        OutputFormat::Plain => format_plain(&result, &binary_changes),
-        OutputFormat::Terminal => format_terminal(&result, &binary_changes, opts.verbose),
-    };
+    if matches!(opts.format, OutputFormat::Terminal) && !opts.listing_output() {
+        // Stream file by file so a giant diff starts printing right away.
+        let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+        let header = match &commit {
+            Some(commit) => writeln!(out, "{}", commit_header(commit, &opts.format)),
+            None => Ok(()),
+        };
+        let _ = header
+            .and_then(|()| {
+                write_terminal(
+                    &mut out,
+                    &result,
+                    &binary_changes,
+                    opts.verbose,
+                    opts.limit,
+                    opts.context,
+                )
+            })
+            .and_then(|()| out.flush());
+    } else {
+        let output = render_diff(&result, &binary_changes, opts);
+        let output = match (&commit, &opts.format) {
+            (Some(_), OutputFormat::Json) => json_with_commit(output, commit.as_ref()),
+            (Some(commit), OutputFormat::Plain) => {
+                format!("{}\n{output}", commit_header(commit, &opts.format))
+            }
+            _ => output,
+        };
+        if !output.is_empty() {
+            println!("{output}");
+        }
+    }
     let format_ms = t4.elapsed().as_secs_f64() * 1000.0;
-
-    println!("{output}");
+    super::report_skipped_files(&result.diagnostics);
 
     maybe_upload_cloud_diff_snapshot(
         opts,
@@ -1716,38 +2261,215 @@ fn run_diff_pipeline(
     if opts.profile {
         let total_ms = total_start.elapsed().as_secs_f64() * 1000.0;
         eprintln!();
-        eprintln!("\x1b[2m── Profile ──────────────────────────────────\x1b[0m");
-        eprintln!(
-            "\x1b[2m  input ({})  {git_diff_ms:>8.2}ms\x1b[0m",
-            if from_stdin { "stdin" } else { "git" }
-        );
-        eprintln!("\x1b[2m  registry init        {registry_ms:>8.2}ms\x1b[0m");
-        eprintln!("\x1b[2m  parse + match        {parse_diff_ms:>8.2}ms\x1b[0m");
-        eprintln!("\x1b[2m  format output        {format_ms:>8.2}ms\x1b[0m");
-        eprintln!("\x1b[2m  ─────────────────────────────────────────────\x1b[0m");
-        eprintln!("\x1b[2m  total                {total_ms:>8.2}ms\x1b[0m");
-        eprintln!(
-            "\x1b[2m  files: {}  entities: {}  changes: {}\x1b[0m",
-            file_changes.len(),
-            result.changes.len(),
-            result.added_count
-                + result.modified_count
-                + result.deleted_count
-                + result.moved_count
-                + result.renamed_count
-                + result.reordered_count
-                + binary_changes.len()
-        );
-        eprintln!("\x1b[2m─────────────────────────────────────────────\x1b[0m");
+        let lines = [
+            "── Profile ──────────────────────────────────".to_string(),
+            format!(
+                "  input ({})  {git_diff_ms:>8.2}ms",
+                if from_stdin { "stdin" } else { "git" }
+            ),
+            format!("  registry init        {registry_ms:>8.2}ms"),
+            format!("  parse + match        {parse_diff_ms:>8.2}ms"),
+            format!("  format output        {format_ms:>8.2}ms"),
+            "  ─────────────────────────────────────────────".to_string(),
+            format!("  total                {total_ms:>8.2}ms"),
+            format!(
+                "  files: {}  entities: {}  changes: {}",
+                file_changes.len(),
+                result.changes.len(),
+                total_change_count(&result, &binary_changes)
+            ),
+            "─────────────────────────────────────────────".to_string(),
+        ];
+        for line in lines {
+            eprintln!("{}", line.dimmed());
+        }
     }
 
     // Conversion nudge: after an interactive diff with real entity changes,
     // hint (at most weekly, logged-out only) that the cloud can show what these
     // changes break across repos — something a local single-repo diff can't.
     // Only for human terminal output; JSON/plain/markdown (piping, CI) skip it.
-    if matches!(opts.format, OutputFormat::Terminal) {
+    if matches!(opts.format, OutputFormat::Terminal) && !opts.listing_output() {
         crate::commands::cloud::maybe_suggest_cloud_after_diff(result.changes.len());
     }
+
+    if let Some(reason) = fail_on_reason(&result, &binary_changes, opts) {
+        eprintln!("{}", format!("Error: {reason}").red());
+        process::exit(1);
+    }
+}
+
+/// Changes counted in the summary line, binary files included.
+fn total_change_count(result: &DiffResult, binary_changes: &[BinaryFileChange]) -> usize {
+    result.added_count
+        + result.modified_count
+        + result.deleted_count
+        + result.moved_count
+        + result.renamed_count
+        + result.reordered_count
+        + binary_changes.len()
+}
+
+/// Why `--fail-on`/`--fail-on-count` fail this diff, if they do.
+fn fail_on_reason(
+    result: &DiffResult,
+    binary_changes: &[BinaryFileChange],
+    opts: &DiffOptions,
+) -> Option<String> {
+    let hits: Vec<String> = opts
+        .fail_on
+        .iter()
+        .filter(|kind| kind.count(result) > 0)
+        .map(|kind| format!("{} {}", kind.count(result), kind.as_str()))
+        .collect();
+    if !hits.is_empty() {
+        return Some(format!("--fail-on matched: {}", hits.join(", ")));
+    }
+    let total = total_change_count(result, binary_changes);
+    opts.fail_on_count
+        .filter(|&limit| total > limit)
+        .map(|limit| format!("{total} changes exceed --fail-on-count {limit}"))
+}
+
+/// Keep only files whose new or old path ends in one of `file_exts`.
+pub(super) fn filter_file_exts(
+    file_changes: Vec<FileChange>,
+    file_exts: &[String],
+) -> Vec<FileChange> {
+    if file_exts.is_empty() {
+        return file_changes;
+    }
+    let exts: Vec<String> = file_exts
+        .iter()
+        .map(|e| {
+            if e.starts_with('.') {
+                e.clone()
+            } else {
+                format!(".{}", e)
+            }
+        })
+        .collect();
+    file_changes
+        .into_iter()
+        .filter(|fc| {
+            exts.iter().any(|ext| {
+                fc.file_path.ends_with(ext.as_str())
+                    || fc
+                        .old_file_path
+                        .as_ref()
+                        .is_some_and(|old| old.ends_with(ext.as_str()))
+            })
+        })
+        .collect()
+}
+
+/// Drop files matched by the `ignore` globs in `.sem/config.toml` or by the
+/// path globs in `.semignore` at `root`.
+pub(super) fn filter_ignored(file_changes: Vec<FileChange>, root: &Path) -> Vec<FileChange> {
+    let config = crate::config::ignore_matcher(Path::new(""));
+    let semignore = crate::config::semignore_matcher(root);
+    if config.is_none() && semignore.is_none() {
+        return file_changes;
+    }
+    file_changes
+        .into_iter()
+        .filter(|fc| {
+            let path = Path::new(&fc.file_path);
+            let config_ignored = config
+                .as_ref()
+                .is_some_and(|matcher| matcher.matched(path, false).is_ignore());
+            let semignored = path.is_relative()
+                && semignore.as_ref().is_some_and(|matcher| {
+                    matcher.matched_path_or_any_parents(path, false).is_ignore()
+                });
+            !config_ignored && !semignored
+        })
+        .collect()
+}
+
+/// Format a computed diff the way `opts` asks for.
+/// The commit `--commit` diffs, looked up for a `git show`-style header.
+/// `None` for other comparisons and for listing output.
+fn shown_commit(opts: &DiffOptions, from_stdin: bool) -> Option<CommitInfo> {
+    let sha = opts.commit.as_ref()?;
+    if from_stdin || opts.listing_output() {
+        return None;
+    }
+    GitBridge::open(Path::new(&opts.cwd))
+        .ok()?
+        .commit_info(sha)
+        .ok()
+}
+
+/// `git show`'s commit header: sha, author, date and the indented subject.
+fn commit_header(commit: &CommitInfo, format: &OutputFormat) -> String {
+    let sha = format!("commit {}", commit.sha);
+    let sha = match format {
+        OutputFormat::Terminal => sha.yellow().to_string(),
+        _ => sha,
+    };
+    format!(
+        "{sha}\nAuthor: {}\nDate:   {}\n\n    {}\n",
+        commit.author,
+        commit_date(commit),
+        commit_subject(commit)
+    )
+}
+
+/// Adds a `commit` object describing the diffed commit to diff JSON.
+fn json_with_commit(output: String, commit: Option<&CommitInfo>) -> String {
+    let Some(commit) = commit else {
+        return output;
+    };
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&output) else {
+        return output;
+    };
+    value["commit"] = serde_json::json!({
+        "sha": commit.sha,
+        "author": commit.author,
+        "date": commit_date(commit),
+        "subject": commit_subject(commit),
+    });
+    value.to_string()
+}
+
+/// Author date as ISO-8601 UTC; `CommitInfo` carries unix seconds.
+fn commit_date(commit: &CommitInfo) -> String {
+    commit
+        .date
+        .parse()
+        .map(|seconds| super::blame::format_commit_time(seconds, None))
+        .unwrap_or_else(|_| commit.date.clone())
+}
+
+fn commit_subject(commit: &CommitInfo) -> &str {
+    commit.message.lines().next().unwrap_or("").trim()
+}
+
+pub(super) fn render_diff(
+    result: &DiffResult,
+    binary_changes: &[BinaryFileChange],
+    opts: &DiffOptions,
+) -> String {
+    if opts.name_only {
+        format_name_only(result, binary_changes)
+    } else if opts.entities_only || opts.entity_ids {
+        format_entities_only(result)
+    } else {
+        match opts.format {
+            OutputFormat::Json if opts.with_diff => format_json_with_diff(result, binary_changes),
+            OutputFormat::Json => format_json(result, binary_changes),
+            OutputFormat::Markdown => format_markdown(result, binary_changes, opts.verbose),
+            OutputFormat::Plain => format_plain(result, binary_changes),
+            OutputFormat::Terminal => format_terminal(
+                result,
+                binary_changes,
+                opts.verbose,
+                opts.limit,
+                opts.context,
+            ),
+        }
+    }
 }
 
 fn maybe_upload_cloud_diff_snapshot(
@@ -1758,7 +2480,7 @@ fn maybe_upload_cloud_diff_snapshot(
     result: &DiffResult,
     binary_changes: &[BinaryFileChange],
 ) {
-    if from_stdin || super::cloud::is_local_forced() {
+    if from_stdin || opts.disk_compare.is_some() || super::cloud::is_local_forced() {
         return;
     }
 
@@ -1887,9 +2609,14 @@ fn build_changed_entity_relations(opts: &DiffOptions, result: &DiffResult) -> se
         let root = root.as_path();
         let registry = super::create_registry(&root.to_string_lossy());
         let ext_filter = super::graph::normalize_exts(&opts.file_exts);
-        let source_scope = super::graph::cache_source_scope(root, &ext_filter, false);
-        let file_paths =
-            super::graph::find_supported_files_with_options(root, &registry, &ext_filter, false);
+        let source_scope = super::graph::cache_source_scope(root, &ext_filter, false, false);
+        let file_paths = super::graph::find_supported_files_with_options(
+            root,
+            &registry,
+            &ext_filter,
+            false,
+            false,
+        );
         // no_cache = false: reuse the disk cache; do not force a full rebuild.
         let (graph, _entities) =
             super::graph::get_or_build_graph(root, &file_paths, &registry, false, source_scope);
@@ -1957,7 +2684,7 @@ fn build_changed_entity_relations(opts: &DiffOptions, result: &DiffResult) -> se
         .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()))
 }
 
-fn retain_non_cosmetic_changes(result: &mut DiffResult) {
+pub(super) fn retain_non_cosmetic_changes(result: &mut DiffResult) {
     // A move/rename/reorder that also carries a content change is a compound change:
     // keep it visible under --no-cosmetics, but drop the purely cosmetic content payload.
     for change in &mut result.changes {
@@ -1979,10 +2706,38 @@ fn retain_non_cosmetic_changes(result: &mut DiffResult) {
                 ChangeType::Moved | ChangeType::Renamed | ChangeType::Reordered
             )
     });
+    // The inventory only covers files that still have changes.
+    let files: HashSet<&str> = result
+        .changes
+        .iter()
+        .map(|c| c.file_path.as_str())
+        .collect();
+    result
+        .unchanged
+        .retain(|u| files.contains(u.file_path.as_str()));
+    recalculate_diff_summary(result);
+}
+
+/// Keep changes whose entity type is in `only` (when non-empty) and not in
+/// `exclude`. Types compare case-insensitively.
+pub(super) fn retain_entity_types(result: &mut DiffResult, only: &[String], exclude: &[String]) {
+    let wanted = |entity_type: &str| {
+        let listed = |types: &[String]| types.iter().any(|t| t.eq_ignore_ascii_case(entity_type));
+        (only.is_empty() || listed(only)) && !listed(exclude)
+    };
+    result.changes.retain(|c| wanted(&c.entity_type));
+    let files: HashSet<&str> = result
+        .changes
+        .iter()
+        .map(|c| c.file_path.as_str())
+        .collect();
+    result
+        .unchanged
+        .retain(|u| files.contains(u.file_path.as_str()) && wanted(&u.entity_type));
     recalculate_diff_summary(result);
 }
 
-fn recalculate_diff_summary(result: &mut DiffResult) {
+pub(super) fn recalculate_diff_summary(result: &mut DiffResult) {
     // Mirrors compute_semantic_diff: orphan_count is cross-cutting metadata,
     // while retained orphans still contribute to change-type buckets.
     result.file_count = result
@@ -2077,6 +2832,8 @@ mod tests {
             orphan_count: 99,
             total_entities_before: 0,
             total_entities_after: 0,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 
@@ -2130,6 +2887,60 @@ mod tests {
         assert_eq!(result.orphan_count, 0);
     }
 
+    #[test]
+    fn entity_type_filter_keeps_listed_types_and_recounts_summary() {
+        let function = change("src/lib.rs", ChangeType::Modified, Some(true));
+        let mut variable = change("src/config.rs", ChangeType::Added, Some(true));
+        variable.entity_type = "variable".to_string();
+        let mut method = change("src/lib.rs", ChangeType::Deleted, Some(true));
+        method.entity_type = "method".to_string();
+
+        let mut only = diff_result(vec![function.clone(), variable.clone(), method.clone()]);
+        retain_entity_types(&mut only, &["Function".to_string()], &[]);
+        assert_eq!(only.changes.len(), 1);
+        assert_eq!(only.changes[0].entity_type, "function");
+        assert_eq!(only.file_count, 1);
+        assert_eq!(only.modified_count, 1);
+        assert_eq!(only.added_count, 0);
+        assert_eq!(only.deleted_count, 0);
+
+        let mut excluded = diff_result(vec![function, variable, method]);
+        retain_entity_types(&mut excluded, &[], &["variable".to_string()]);
+        assert_eq!(excluded.changes.len(), 2);
+        assert_eq!(excluded.file_count, 1);
+        assert_eq!(excluded.added_count, 0);
+        assert_eq!(excluded.deleted_count, 1);
+    }
+
+    #[test]
+    fn files_from_list_matches_new_or_old_path_exactly() {
+        let file = |path: &str, old_path: Option<&str>| FileChange {
+            file_path: path.to_string(),
+            status: if old_path.is_some() {
+                FileStatus::Renamed
+            } else {
+                FileStatus::Modified
+            },
+            old_file_path: old_path.map(str::to_string),
+            before_content: Some(String::new()),
+            after_content: Some(String::new()),
+        };
+        let changes = vec![
+            file("src/a.rs", None),
+            file("src/ab.rs", None),
+            file("src/new.rs", Some("src/old.rs")),
+            file("lib/c.rs", None),
+        ];
+
+        let listed = vec!["src/a.rs".to_string(), "src/old.rs".to_string()];
+        let kept: Vec<String> = retain_listed_files(changes.clone(), &listed)
+            .into_iter()
+            .map(|file_change| file_change.file_path)
+            .collect();
+        assert_eq!(kept, vec!["src/a.rs", "src/new.rs"]);
+        assert!(retain_listed_files(changes, &[]).is_empty());
+    }
+
     #[test]
     fn hunk_header_validation_accepts_git_forms() {
         assert!(is_unified_hunk_header("@@ -1 +1 @@"));
@@ -2482,4 +3293,39 @@ mod tests {
 
         std::fs::remove_dir_all(dir).unwrap();
     }
+
+    #[test]
+    fn parse_rename_threshold_accepts_only_zero_to_one() {
+        assert_eq!(parse_rename_threshold("0.65"), Ok(0.65));
+        assert_eq!(parse_rename_threshold("1"), Ok(1.0));
+        for invalid in ["0", "-0.5", "1.01", "NaN", "high"] {
+            assert!(
+                parse_rename_threshold(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_since_accepts_dates_times_and_offsets() {
+        assert_eq!(parse_since("1970-01-01"), Ok(0));
+        assert_eq!(parse_since("2024-03-01"), Ok(1_709_251_200));
+        assert_eq!(parse_since("2024-02-29T22:30"), Ok(1_709_245_800));
+        assert_eq!(parse_since("2024-03-01T03:30:00Z"), Ok(1_709_263_800));
+        assert_eq!(parse_since("2024-02-29T22:30:00-05:00"), Ok(1_709_263_800));
+        assert_eq!(parse_since("1969-12-31"), Ok(-86_400));
+
+        for invalid in [
+            "",
+            "yesterday",
+            "2024-13-01",
+            "2024-3-1",
+            "2024-03-01T25:00",
+        ] {
+            assert!(
+                parse_since(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
 }
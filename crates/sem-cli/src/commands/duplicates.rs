@@ -0,0 +1,91 @@
+//! `sem graph --duplicates`: entities with the same structural hash as
+//! another entity. The hash ignores formatting, comments and the entity's
+//! own name, so a copy that was reformatted or renamed still groups with the
+//! original.
+
+use std::collections::{HashMap, HashSet};
+
+use sem_core::model::entity::SemanticEntity;
+use serde::Serialize;
+
+/// Shorter entities (one-line getters, constants) match too often for a
+/// match to mean anything.
+const MIN_CLONE_LINES: usize = 3;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub structural_hash: String,
+    /// Sorted by file, then line.
+    pub clones: Vec<DuplicateEntity>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateEntity {
+    pub id: String,
+    pub name: String,
+    pub entity_type: String,
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// Groups of two or more entities sharing a structural hash, largest group
+/// first. When whole parents are duplicated, only the parents' group is
+/// reported, not another group for each pair of identical children.
+pub fn find_duplicates<'a>(
+    entities: impl IntoIterator<Item = &'a SemanticEntity>,
+) -> Vec<DuplicateGroup> {
+    let mut by_hash: HashMap<&str, Vec<&SemanticEntity>> = HashMap::new();
+    for entity in entities {
+        if entity.end_line.saturating_sub(entity.start_line) + 1 < MIN_CLONE_LINES {
+            continue;
+        }
+        if let Some(hash) = entity.structural_hash.as_deref() {
+            by_hash.entry(hash).or_default().push(entity);
+        }
+    }
+    by_hash.retain(|_, members| members.len() > 1);
+
+    let duplicated: HashSet<&str> = by_hash
+        .values()
+        .flatten()
+        .map(|entity| entity.id.as_str())
+        .collect();
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .filter(|(_, members)| {
+            !members.iter().all(|entity| {
+                entity
+                    .parent_id
+                    .as_deref()
+                    .is_some_and(|parent| duplicated.contains(parent))
+            })
+        })
+        .map(|(hash, mut members)| {
+            members.sort_by(|a, b| (&a.file_path, a.start_line).cmp(&(&b.file_path, b.start_line)));
+            DuplicateGroup {
+                structural_hash: hash.to_string(),
+                clones: members
+                    .into_iter()
+                    .map(|entity| DuplicateEntity {
+                        id: entity.id.clone(),
+                        name: entity.name.clone(),
+                        entity_type: entity.entity_type.clone(),
+                        file: entity.file_path.clone(),
+                        start_line: entity.start_line,
+                        end_line: entity.end_line,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        let (first_a, first_b) = (&a.clones[0], &b.clones[0]);
+        b.clones.len().cmp(&a.clones.len()).then_with(|| {
+            (&first_a.file, first_a.start_line).cmp(&(&first_b.file, first_b.start_line))
+        })
+    });
+    groups
+}
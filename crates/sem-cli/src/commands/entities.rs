@@ -14,6 +14,7 @@ pub struct EntitiesOptions {
     pub paths: Vec<String>,
     pub json: bool,
     pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
     pub file_exts: Vec<String>,
     /// Keep only entities whose kind is in this list (empty = no filter).
     pub only_kinds: Vec<String>,
@@ -112,6 +113,7 @@ pub fn entities_command(opts: EntitiesOptions) {
                 &registry,
                 &ext_filter,
                 opts.no_default_excludes,
+                opts.follow_symlinks,
             );
             discovered_file_count += file_paths.len();
             processed_file_count += file_paths.len();
@@ -124,6 +126,7 @@ pub fn entities_command(opts: EntitiesOptions) {
                     &file_paths,
                     &ext_filter,
                     opts.no_default_excludes,
+                    opts.follow_symlinks,
                     &mut timings,
                 )
             {
@@ -141,6 +144,7 @@ pub fn entities_command(opts: EntitiesOptions) {
                 &file_paths,
                 &ext_filter,
                 opts.no_default_excludes,
+                opts.follow_symlinks,
                 &mut timings,
             ) {
                 entities.extend(cached_entities);
@@ -282,9 +286,11 @@ fn try_cached_entities(
     file_paths: &[String],
     ext_filter: &[String],
     no_default_excludes: bool,
+    follow_symlinks: bool,
     timings: &mut Timings,
 ) -> Option<Vec<SemanticEntity>> {
-    let source_scope = super::graph::cache_source_scope(root, ext_filter, no_default_excludes);
+    let source_scope =
+        super::graph::cache_source_scope(root, ext_filter, no_default_excludes, follow_symlinks);
     let cache = match DiskCache::open_existing_readonly(root) {
         Ok(cache) => {
             timings.mark("cache_open");
@@ -318,9 +324,11 @@ fn try_write_cached_entities_json(
     file_paths: &[String],
     ext_filter: &[String],
     no_default_excludes: bool,
+    follow_symlinks: bool,
     timings: &mut Timings,
 ) -> bool {
-    let source_scope = super::graph::cache_source_scope(root, ext_filter, no_default_excludes);
+    let source_scope =
+        super::graph::cache_source_scope(root, ext_filter, no_default_excludes, follow_symlinks);
     let cache = match DiskCache::open_existing_readonly(root) {
         Ok(cache) => {
             timings.mark("cache_open");
@@ -371,7 +379,10 @@ fn entity_info_to_entity(entity: EntityInfo) -> SemanticEntity {
         end_line: entity.end_line,
         start_byte: None,
         end_byte: None,
+        name_start: None,
+        name_end: None,
         metadata: None,
+        stable_id: None,
     }
 }
 
@@ -392,8 +403,10 @@ fn text_search(opts: &EntitiesOptions, needle: &str) {
         Ok(git) => git.repo_root().to_path_buf(),
         Err(_) => Path::new(&opts.cwd).to_path_buf(),
     };
-    let scope_is_default =
-        opts.file_exts.is_empty() && !opts.no_default_excludes && !root.join(".semignore").exists();
+    let scope_is_default = opts.file_exts.is_empty()
+        && !opts.no_default_excludes
+        && !opts.follow_symlinks
+        && !super::files::has_custom_ignores(&root);
 
     if scope_is_default {
         let request = serde_json::json!({
@@ -411,13 +424,18 @@ fn text_search(opts: &EntitiesOptions, needle: &str) {
 
     let registry = super::create_registry(&root.to_string_lossy());
     let ext_filter = super::graph::normalize_exts(&opts.file_exts);
-    let source_scope =
-        super::graph::cache_source_scope(&root, &ext_filter, opts.no_default_excludes);
+    let source_scope = super::graph::cache_source_scope(
+        &root,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
     let file_paths = super::graph::find_supported_files_with_options(
         &root,
         &registry,
         &ext_filter,
         opts.no_default_excludes,
+        opts.follow_symlinks,
     );
     let (_, all_entities) =
         super::graph::get_or_build_graph(&root, &file_paths, &registry, false, source_scope);
@@ -447,6 +465,10 @@ struct EntityJsonRow<'a> {
     start_byte: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     end_byte: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name_end: Option<usize>,
     parent_id: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     file: Option<&'a str>,
@@ -467,6 +489,8 @@ fn write_entities_json(entities: &[SemanticEntity], include_file: bool) -> io::R
             end_line: entity.end_line,
             start_byte: entity.start_byte,
             end_byte: entity.end_byte,
+            name_start: entity.name_start,
+            name_end: entity.name_end,
             parent_id: entity.parent_id.as_deref(),
             file: include_file.then_some(entity.file_path.as_str()),
         };
@@ -603,7 +627,10 @@ mod tests {
             end_line: 1,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
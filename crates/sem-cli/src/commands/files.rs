@@ -1,8 +1,11 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 use colored::Colorize;
 use sem_core::parser::registry::ParserRegistry;
-use sem_core::utils::scan::{is_default_excluded, is_probably_binary_path};
+use sem_core::utils::scan::is_probably_binary_path;
+use sem_core::utils::walk::WalkFilter;
 
 pub fn find_supported_files_in_path(
     root: &Path,
@@ -10,6 +13,7 @@ pub fn find_supported_files_in_path(
     registry: &ParserRegistry,
     ext_filter: &[String],
     no_default_excludes: bool,
+    follow_symlinks: bool,
 ) -> Vec<String> {
     let mut files = Vec::new();
 
@@ -18,33 +22,55 @@ pub fn find_supported_files_in_path(
         .hidden(true)
         .git_ignore(true)
         .git_global(true)
-        .git_exclude(true);
+        .git_exclude(true)
+        .follow_links(follow_symlinks);
 
     let semignore = root.join(".semignore");
     if semignore.exists() {
         builder.add_ignore(semignore);
     }
+    if let Some(overrides) = crate::config::ignore_matcher(root) {
+        builder.overrides(overrides);
+    }
 
-    if !no_default_excludes {
-        let root = root.to_path_buf();
-        builder.filter_entry(move |entry| {
-            if !entry
-                .file_type()
-                .is_some_and(|file_type| file_type.is_dir())
-            {
-                return true;
-            }
+    // Symlinks are skipped unless opted in. When following them, a directory
+    // reachable through several links (or a link back to an ancestor) is
+    // descended only once, keyed by its canonical path.
+    let visited_dirs: Arc<Mutex<HashSet<PathBuf>>> = Arc::default();
+    let filter_root = root.to_path_buf();
+    let walk_filter = WalkFilter::new(no_default_excludes, crate::config::include_globs());
+    let dir_filter = walk_filter.clone();
+    builder.filter_entry(move |entry| {
+        if !follow_symlinks && entry.depth() > 0 && entry.path_is_symlink() {
+            return false;
+        }
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+        {
+            return true;
+        }
 
-            let rel_path = file_path_for_entity(&root, entry.path());
-            !is_default_excluded(&rel_path)
-        });
-    }
+        if dir_filter.skips_dir(&file_path_for_entity(&filter_root, entry.path())) {
+            return false;
+        }
+
+        if !follow_symlinks {
+            return true;
+        }
+        let Ok(canonical) = entry.path().canonicalize() else {
+            return false;
+        };
+        visited_dirs.lock().unwrap().insert(canonical)
+    });
 
     let walker = builder.build();
 
     for entry in walker {
         let entry = match entry {
             Ok(entry) => entry,
+            // A symlink cycle the visited set didn't catch first: skip it.
+            Err(e) if is_symlink_loop(&e) => continue,
             Err(e) => {
                 eprintln!(
                     "{} Cannot walk '{}': {}",
@@ -62,7 +88,7 @@ pub fn find_supported_files_in_path(
         }
 
         let rel_path = file_path_for_entity(root, path);
-        if !no_default_excludes && is_default_excluded(&rel_path) {
+        if walk_filter.skips_file(&rel_path) {
             continue;
         }
         if is_hidden_path(&rel_path) {
@@ -88,6 +114,16 @@ pub fn find_supported_files_in_path(
     files
 }
 
+fn is_symlink_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_symlink_loop(err),
+        _ => false,
+    }
+}
+
 fn is_hidden_path(rel_path: &str) -> bool {
     rel_path
         .split('/')
@@ -115,6 +151,71 @@ fn has_supported_plugin(
     registry.detect_plugin_from_content(&content).is_some()
 }
 
+/// Read a `--files-from` list: one repo-relative path per line, from the
+/// file at `source`, or from stdin when it's `-`. Blank lines are skipped.
+pub fn read_file_list(source: &str) -> std::io::Result<Vec<String>> {
+    let text = if source == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(source)?
+    };
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.strip_prefix("./").unwrap_or(line).replace('\\', "/"))
+        .collect())
+}
+
+/// The supported files among `listed` (repo-relative paths from
+/// `--files-from`), in place of a walk. A listed path that isn't a file is
+/// skipped with a warning. The rest go through the walk's extension, binary
+/// and plugin checks, but not its ignore rules: the list is explicit.
+pub fn find_listed_supported_files(
+    root: &Path,
+    listed: &[String],
+    registry: &ParserRegistry,
+    ext_filter: &[String],
+) -> Vec<String> {
+    let mut files = Vec::new();
+    for rel_path in listed {
+        let path = root.join(rel_path);
+        if !path.is_file() {
+            eprintln!(
+                "{} skipping '{}' from --files-from: no such file",
+                "warning:".yellow().bold(),
+                rel_path
+            );
+            continue;
+        }
+        if !ext_filter.is_empty()
+            && !ext_filter
+                .iter()
+                .any(|ext| rel_path.ends_with(ext.as_str()))
+        {
+            continue;
+        }
+        if is_probably_binary_path(rel_path)
+            || !has_supported_plugin(&path, rel_path, registry, ext_filter)
+        {
+            continue;
+        }
+        files.push(rel_path.clone());
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Whether `.semignore`, config ignore globs or `--include` change this repo's
+/// default file scope, so cached or resident results for the default scope
+/// don't apply.
+pub fn has_custom_ignores(root: &Path) -> bool {
+    root.join(".semignore").exists()
+        || !crate::config::ignore_globs().is_empty()
+        || !crate::config::include_globs().is_empty()
+}
+
 pub fn file_path_for_entity(root: &Path, path: &Path) -> String {
     path.strip_prefix(root)
         .ok()
@@ -183,14 +284,15 @@ mod tests {
         fs::write(root.join("dist/generated.js"), "function generated() {}\n").unwrap();
 
         let registry = create_default_registry();
-        let files = find_supported_files_in_path(&root, &root, &registry, &[], false);
+        let files = find_supported_files_in_path(&root, &root, &registry, &[], false, false);
 
         assert_eq!(
             files,
             vec!["src/main.rs".to_string(), "src/run".to_string()]
         );
 
-        let files_with_generated = find_supported_files_in_path(&root, &root, &registry, &[], true);
+        let files_with_generated =
+            find_supported_files_in_path(&root, &root, &registry, &[], true, false);
         assert!(files_with_generated.contains(&"src/main.rs".to_string()));
         assert!(files_with_generated.contains(&"src/run".to_string()));
         assert!(files_with_generated.contains(&"src/generated/schema.ts".to_string()));
@@ -202,10 +304,50 @@ mod tests {
         assert!(!files_with_generated.contains(&"src/blob.weird".to_string()));
         assert!(!files_with_generated.contains(&"src/icon.png".to_string()));
 
-        let rs_files =
-            find_supported_files_in_path(&root, &root, &registry, &[".rs".to_string()], true);
+        let rs_files = find_supported_files_in_path(
+            &root,
+            &root,
+            &registry,
+            &[".rs".to_string()],
+            true,
+            false,
+        );
         assert_eq!(rs_files, vec!["src/main.rs".to_string()]);
 
         fs::remove_dir_all(root).unwrap();
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_skips_symlinks_unless_followed_and_survives_cycles() {
+        use std::os::unix::fs::symlink;
+
+        let root = temp_dir();
+        let outside = temp_dir();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(outside.join("lib.rs"), "fn lib() {}\n").unwrap();
+        symlink(&outside, root.join("linked")).unwrap();
+        symlink(root.join("src/main.rs"), root.join("alias.rs")).unwrap();
+        // Both a link back to an ancestor and a second link to the same dir.
+        symlink(&root, root.join("src/loop")).unwrap();
+        symlink(&outside, root.join("src/linked_again")).unwrap();
+
+        let registry = create_default_registry();
+        let files = find_supported_files_in_path(&root, &root, &registry, &[], false, false);
+        assert_eq!(files, vec!["src/main.rs".to_string()]);
+
+        let followed = find_supported_files_in_path(&root, &root, &registry, &[], false, true);
+        assert!(followed.contains(&"src/main.rs".to_string()));
+        assert!(followed.contains(&"alias.rs".to_string()));
+        let lib_copies = followed
+            .iter()
+            .filter(|path| path.ends_with("lib.rs"))
+            .count();
+        assert_eq!(lib_copies, 1, "linked dir scanned once: {followed:?}");
+        assert!(!followed.iter().any(|path| path.contains("loop/")));
+
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(outside).unwrap();
+    }
 }
@@ -1,12 +1,17 @@
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
 
 use colored::Colorize;
 use sem_core::git::bridge::GitBridge;
 use sem_core::model::entity::SemanticEntity;
-use sem_core::parser::graph::{EntityGraph, EntityRef, RefType};
+use sem_core::parser::graph::{EntityGraph, EntityInfo, EntityInfoMap, EntityRef, RefType};
 use sem_core::parser::registry::ParserRegistry;
 use serde::ser::{SerializeMap, Serializer};
 
+use super::duplicates::{find_duplicates, DuplicateGroup};
+use super::orphans::{find_orphans, OrphanFinding, OrphanScope};
 use crate::cache::DiskCache;
 use crate::timings::Timings;
 use sem_mcp::cache::CacheSourceScope;
@@ -17,6 +22,74 @@ pub struct GraphOptions {
     pub file_exts: Vec<String>,
     pub no_cache: bool,
     pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
+    /// Drop edges scored below this confidence (0.0 keeps every edge).
+    pub min_confidence: f32,
+    /// Leave out test files and `#[cfg(test)]` modules.
+    pub exclude_tests: bool,
+    /// Leave out private entities and anything nested in them.
+    pub public_only: bool,
+    /// Aggregate entities and edges into clusters instead of listing them.
+    pub cluster_by: Option<ClusterBy>,
+    /// Restrict the graph to this entity and its transitive dependents.
+    pub entity: Option<String>,
+    /// Hops of dependents kept around `entity` (0 = unlimited).
+    pub depth: usize,
+    /// Keep `entity`'s direct dependencies and dependents instead.
+    pub neighbors: bool,
+    /// List unreferenced functions and methods instead of the graph.
+    pub orphans: Option<OrphanScope>,
+    /// List groups of structurally identical entities instead of the graph.
+    pub duplicates: bool,
+    /// Repo-relative directories to walk instead of the whole repo.
+    pub subtrees: Vec<String>,
+    /// Repo-relative files to analyze instead of walking (`--files-from`).
+    pub files_from: Option<Vec<String>>,
+}
+
+impl GraphOptions {
+    fn graph_filters(&self) -> GraphFilters {
+        GraphFilters {
+            min_confidence: self.min_confidence,
+            exclude_tests: self.exclude_tests,
+            public_only: self.public_only,
+        }
+    }
+}
+
+/// The edge and entity filters `sem graph` and `sem impact` apply to a
+/// built graph.
+pub struct GraphFilters {
+    pub min_confidence: f32,
+    pub exclude_tests: bool,
+    pub public_only: bool,
+}
+
+impl GraphFilters {
+    /// Whether any filter is on. Cached topology doesn't carry confidences
+    /// or test and private tags, so a filtered query needs the graph in
+    /// memory.
+    pub fn any(&self) -> bool {
+        self.min_confidence > 0.0 || self.exclude_tests || self.public_only
+    }
+}
+
+/// Drop edges below `--min-confidence`, then the test partition and private
+/// entities when `--exclude-tests` or `--public-only` ask for it.
+pub fn apply_graph_filters(graph: &mut EntityGraph, filters: &GraphFilters) {
+    graph.retain_min_confidence(filters.min_confidence);
+    if filters.exclude_tests {
+        graph.exclude_tests();
+    }
+    if filters.public_only {
+        graph.public_only();
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ClusterBy {
+    /// Outermost module/namespace, else the file's directory
+    Module,
 }
 
 pub fn graph_command(opts: GraphOptions) {
@@ -26,13 +99,37 @@ pub fn graph_command(opts: GraphOptions) {
         Err(_) => Path::new(&opts.cwd).to_path_buf(),
     };
     let root = root.as_path();
+    // The cache holds the whole repo's graph; a subtree or listed-files graph
+    // is built fresh rather than overwriting it. Parse diagnostics only come from a fresh
+    // build, so `--show-skipped` bypasses the cache too, as does `--no-ignore`
+    // since the cached edges already leave out `sem:ignore` entities.
+    let opts = GraphOptions {
+        no_cache: opts.no_cache
+            || !opts.subtrees.is_empty()
+            || opts.files_from.is_some()
+            || crate::config::show_skipped()
+            || crate::config::no_ignore(),
+        ..opts
+    };
     let ext_filter = normalize_exts(&opts.file_exts);
-    let source_scope = cache_source_scope(root, &ext_filter, opts.no_default_excludes);
+    let source_scope = cache_source_scope(
+        root,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
 
     // Oracle fast path: when git proves the cache is fresh, the file walk (the
     // dominant cost on large repos) is redundant — the cache already holds the
     // topology. Skip discovery entirely and serve straight from cache.
-    if !opts.no_cache {
+    // Confidence filtering needs the edges in memory, so it skips the
+    // cache-streaming fast paths.
+    let filter_edges = opts.graph_filters().any()
+        || opts.cluster_by.is_some()
+        || opts.entity.is_some()
+        || opts.orphans.is_some()
+        || opts.duplicates;
+    if !opts.no_cache && !filter_edges {
         if let Ok(disk) = DiskCache::open(root) {
             if opts.json {
                 if let Some(graph) = disk.oracle_fresh_topology(root, source_scope) {
@@ -46,7 +143,7 @@ pub fn graph_command(opts: GraphOptions) {
                 timings.mark("oracle_fast_path");
                 println!(
                     "{} {} entities, {} edges",
-                    "⊕".green(),
+                    crate::formatters::glyph("⊕", "+").green(),
                     entities.to_string().bold(),
                     edges.to_string().bold(),
                 );
@@ -57,10 +154,17 @@ pub fn graph_command(opts: GraphOptions) {
     }
 
     let registry = super::create_registry(&root.to_string_lossy());
-    let file_paths =
-        find_supported_files_inner(root, &registry, &ext_filter, opts.no_default_excludes);
+    let file_paths = find_supported_files_for(
+        root,
+        &opts.subtrees,
+        opts.files_from.as_deref(),
+        &registry,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
     timings.mark("file_discovery");
-    if opts.json && !opts.no_cache {
+    if opts.json && !opts.no_cache && !filter_edges {
         if let Ok(disk) = DiskCache::open(root) {
             timings.mark("cache_open");
             let stdout = std::io::stdout();
@@ -83,8 +187,35 @@ pub fn graph_command(opts: GraphOptions) {
         }
     }
 
+    if let Some(scope) = opts.orphans {
+        orphans_output(
+            root,
+            &file_paths,
+            &registry,
+            &opts,
+            scope,
+            source_scope,
+            &mut timings,
+        );
+        timings.finish();
+        return;
+    }
+
+    if opts.duplicates {
+        duplicates_output(
+            root,
+            &file_paths,
+            &registry,
+            &opts,
+            source_scope,
+            &mut timings,
+        );
+        timings.finish();
+        return;
+    }
+
     let prog = crate::progress::Progress::start_staged();
-    let graph = get_or_build_graph_topology_with_timings(
+    let mut graph = get_or_build_graph_topology_with_timings(
         root,
         &file_paths,
         &registry,
@@ -92,27 +223,446 @@ pub fn graph_command(opts: GraphOptions) {
         source_scope,
         &mut timings,
     );
+    let ambiguous_names = graph.ambiguous_names();
+    apply_graph_filters(&mut graph, &opts.graph_filters());
     prog.done(&format!(
         "{} entities, {} files",
         fmt_count(graph.entities.len()),
         fmt_count(file_paths.len())
     ));
+    super::warn_skipped_files(&registry);
+    super::warn_ambiguous_names(&ambiguous_names);
 
-    if opts.json {
+    let mut focus = None;
+    if let Some(name) = opts.entity.as_deref() {
+        let entity_id = graph.entities.contains_key(name).then_some(name);
+        let entity_id = super::impact::find_entity(&graph, Some(name), entity_id, None)
+            .id
+            .clone();
+        graph = if opts.neighbors {
+            neighbor_subgraph(&graph, &entity_id)
+        } else {
+            impact_subgraph(&graph, &entity_id, opts.depth)
+        };
+        focus = Some(entity_id);
+    }
+
+    if let Some(ClusterBy::Module) = opts.cluster_by {
+        let clusters = module_clusters(&graph);
+        if opts.json {
+            println!("{}", serde_json::to_string(&clusters).unwrap());
+        } else {
+            print_module_clusters(&clusters);
+        }
+        timings.mark("cli_output_serialization");
+    } else if opts.json {
         write_graph_json(&graph).unwrap();
         timings.mark("cli_output_serialization");
     } else {
         timings.mark("cli_output_serialization");
         println!(
             "{} {} entities, {} edges",
-            "⊕".green(),
+            crate::formatters::glyph("⊕", "+").green(),
             graph.entities.len().to_string().bold(),
             graph.edges.len().to_string().bold(),
         );
+        if let Some(breakdown) = edge_kind_breakdown(&graph.edges) {
+            println!("  {breakdown}");
+        }
+        if let Some(entity_id) = focus.as_deref().filter(|_| opts.neighbors) {
+            print_neighbors(&graph, entity_id);
+        }
     }
     timings.finish();
 }
 
+/// `--orphans`: needs full entities (content, metadata) rather than the
+/// topology, and scores findings itself, so `--min-confidence` filters them
+/// instead of the edges.
+fn orphans_output(
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+    opts: &GraphOptions,
+    scope: OrphanScope,
+    source_scope: CacheSourceScope,
+    timings: &mut Timings,
+) {
+    let prog = crate::progress::Progress::start_staged();
+    let (mut graph, entities) = get_or_build_graph_with_timings(
+        root,
+        file_paths,
+        registry,
+        opts.no_cache,
+        source_scope,
+        timings,
+    );
+    let ambiguous_names = graph.ambiguous_names();
+    apply_graph_filters(&mut graph, &opts.graph_filters());
+    prog.done(&format!(
+        "{} entities, {} files",
+        fmt_count(graph.entities.len()),
+        fmt_count(file_paths.len())
+    ));
+    super::warn_skipped_files(registry);
+    // Unlinked references to these names can make their definitions look orphaned.
+    super::warn_ambiguous_names(&ambiguous_names);
+
+    let test_ids =
+        graph.filter_test_entities_with_custom_dirs(&entities, &registry.custom_test_dirs);
+    let findings: Vec<OrphanFinding> = find_orphans(&graph, &entities, &test_ids, scope)
+        .into_iter()
+        .filter(|finding| finding.confidence >= opts.min_confidence)
+        .collect();
+
+    if opts.json {
+        println!(
+            "{}",
+            serde_json::to_string(&serde_json::json!({ "orphans": findings })).unwrap()
+        );
+    } else if findings.is_empty() {
+        println!("{} no unreferenced functions found", "✓".green());
+    } else {
+        for finding in &findings {
+            println!(
+                "{} {}:{}  {} {} {}",
+                "?".yellow(),
+                finding.file,
+                finding.line,
+                finding.entity_type.dimmed(),
+                finding.name.bold(),
+                format!("(confidence {:.2})", finding.confidence).dimmed(),
+            );
+        }
+        println!(
+            "\n{} unreferenced function{} (no dependents found; references through dynamic dispatch are invisible to sem)",
+            findings.len(),
+            if findings.len() == 1 { "" } else { "s" }
+        );
+    }
+    timings.mark("cli_output_serialization");
+}
+
+/// Duplicate groups printed to the terminal; `--json` lists every group.
+const MAX_PRINTED_DUPLICATE_GROUPS: usize = 20;
+
+/// `--duplicates`: needs full entities for their structural hashes, like
+/// `--orphans`. `--exclude-tests` and `--public-only` narrow which entities
+/// are compared.
+fn duplicates_output(
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+    opts: &GraphOptions,
+    source_scope: CacheSourceScope,
+    timings: &mut Timings,
+) {
+    let prog = crate::progress::Progress::start_staged();
+    let (mut graph, entities) = get_or_build_graph_with_timings(
+        root,
+        file_paths,
+        registry,
+        opts.no_cache,
+        source_scope,
+        timings,
+    );
+    apply_graph_filters(&mut graph, &opts.graph_filters());
+    prog.done(&format!(
+        "{} entities, {} files",
+        fmt_count(graph.entities.len()),
+        fmt_count(file_paths.len())
+    ));
+    super::warn_skipped_files(registry);
+
+    let groups = find_duplicates(
+        entities
+            .iter()
+            .filter(|entity| graph.entities.contains_key(&entity.id)),
+    );
+
+    if opts.json {
+        println!(
+            "{}",
+            serde_json::to_string(&serde_json::json!({ "duplicates": groups })).unwrap()
+        );
+    } else if groups.is_empty() {
+        println!("{} no duplicated entities found", "✓".green());
+    } else {
+        print_duplicate_groups(&groups);
+    }
+    timings.mark("cli_output_serialization");
+}
+
+fn print_duplicate_groups(groups: &[DuplicateGroup]) {
+    for group in groups.iter().take(MAX_PRINTED_DUPLICATE_GROUPS) {
+        println!(
+            "{} {} structurally identical entities",
+            "=".yellow(),
+            group.clones.len().to_string().bold()
+        );
+        for clone in &group.clones {
+            println!(
+                "  {}:{}-{}  {} {}",
+                clone.file,
+                clone.start_line,
+                clone.end_line,
+                clone.entity_type.dimmed(),
+                clone.name.bold(),
+            );
+        }
+    }
+    if groups.len() > MAX_PRINTED_DUPLICATE_GROUPS {
+        println!(
+            "... and {} more groups (--json lists all)",
+            groups.len() - MAX_PRINTED_DUPLICATE_GROUPS
+        );
+    }
+    let clones: usize = groups.iter().map(|group| group.clones.len()).sum();
+    println!(
+        "\n{} duplicate group{}, {} entities (structure compared with formatting, comments and names ignored)",
+        groups.len(),
+        if groups.len() == 1 { "" } else { "s" },
+        clones
+    );
+}
+
+/// The entity, its dependents within `depth` hops (0 = unlimited), and the
+/// edges among them.
+fn impact_subgraph(graph: &EntityGraph, entity_id: &str, depth: usize) -> EntityGraph {
+    let mut keep: HashSet<&str> = graph
+        .impact_analysis_bounded(entity_id, depth)
+        .into_iter()
+        .map(|(entity, _)| entity.id.as_str())
+        .collect();
+    keep.insert(entity_id);
+    subgraph(graph, &keep)
+}
+
+/// The entity, what it directly depends on, what directly depends on it,
+/// and the edges among them.
+fn neighbor_subgraph(graph: &EntityGraph, entity_id: &str) -> EntityGraph {
+    let mut keep: HashSet<&str> = graph
+        .get_dependencies(entity_id)
+        .into_iter()
+        .chain(graph.get_dependents(entity_id))
+        .map(|entity| entity.id.as_str())
+        .collect();
+    keep.insert(entity_id);
+    subgraph(graph, &keep)
+}
+
+fn subgraph(graph: &EntityGraph, keep: &HashSet<&str>) -> EntityGraph {
+    let entities = graph
+        .entities
+        .iter()
+        .filter(|(id, _)| keep.contains(id.as_str()))
+        .map(|(id, entity)| (id.clone(), entity.clone()))
+        .collect();
+    let edges = graph
+        .edges
+        .iter()
+        .filter(|edge| {
+            keep.contains(edge.from_entity.as_str()) && keep.contains(edge.to_entity.as_str())
+        })
+        .cloned()
+        .collect();
+    EntityGraph::from_parts(entities, edges)
+}
+
+/// `--neighbors` in the terminal: the entity, then its direct dependencies
+/// and dependents by name.
+fn print_neighbors(graph: &EntityGraph, entity_id: &str) {
+    let Some(entity) = graph.entities.get(entity_id) else {
+        return;
+    };
+    println!();
+    println!(
+        "{} {} {}",
+        entity.entity_type.dimmed(),
+        entity.name.bold(),
+        entity.file_path.dimmed()
+    );
+    let sections = [
+        ("depends on", graph.get_dependencies(entity_id)),
+        ("used by", graph.get_dependents(entity_id)),
+    ];
+    for (label, neighbors) in sections {
+        println!("  {} ({})", label, neighbors.len());
+        for neighbor in neighbors {
+            println!(
+                "    {} {} {}",
+                neighbor.entity_type.dimmed(),
+                neighbor.name,
+                neighbor.file_path.dimmed()
+            );
+        }
+    }
+}
+
+/// One `symbol count kind` entry per edge kind present, e.g.
+/// `→ 12 calls  ▲ 2 extends`.
+fn edge_kind_breakdown(edges: &[EntityRef]) -> Option<String> {
+    let mut counts: BTreeMap<u8, (&RefType, usize)> = BTreeMap::new();
+    for edge in edges {
+        counts
+            .entry(ref_type_sort_key(&edge.ref_type))
+            .or_insert((&edge.ref_type, 0))
+            .1 += 1;
+    }
+    if counts.is_empty() {
+        return None;
+    }
+    let parts: Vec<String> = counts
+        .into_values()
+        .map(|(ref_type, count)| {
+            let (symbol, label) = ref_type_symbol(ref_type);
+            format!("{} {} {}", symbol.cyan(), fmt_count(count), label.dimmed())
+        })
+        .collect();
+    Some(parts.join("  "))
+}
+
+/// Terminal symbol and label for an edge kind. Inheritance uses UML-style
+/// triangles: filled for `extends`, hollow for `implements`.
+fn ref_type_symbol(ref_type: &RefType) -> (&'static str, &'static str) {
+    match ref_type {
+        RefType::Calls => ("→", "calls"),
+        RefType::Imports => ("⇢", "imports"),
+        RefType::TypeRef => ("∷", "type refs"),
+        RefType::Implements => ("△", "implements"),
+        RefType::Extends => ("▲", "extends"),
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModuleClusters {
+    clusters: Vec<ModuleCluster>,
+    edges: Vec<ModuleEdge>,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModuleCluster {
+    module: String,
+    entity_count: usize,
+    internal_edges: usize,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModuleEdge {
+    from: String,
+    to: String,
+    count: usize,
+}
+
+/// The cluster an entity belongs to: its outermost `module` ancestor (Rust
+/// `mod`, C++/C#/PHP namespace, Ruby/Elixir module) by name, otherwise the
+/// directory of its file with a trailing `/` (`./` at the repo root).
+fn module_of(entity: &EntityInfo, entities: &EntityInfoMap) -> String {
+    let mut outermost_module = None;
+    let mut current = Some(entity);
+    while let Some(info) = current {
+        if info.entity_type == "module" {
+            outermost_module = Some(info);
+        }
+        current = info.parent_id.as_deref().and_then(|id| entities.get(id));
+    }
+
+    match outermost_module {
+        Some(module) => module.name.clone(),
+        None => match entity.file_path.rsplit_once('/') {
+            Some((dir, _)) => format!("{dir}/"),
+            None => "./".to_string(),
+        },
+    }
+}
+
+/// Roll entity-level edges up to module granularity. Edges within a module
+/// are counted on the cluster; edges between modules become weighted edges.
+fn module_clusters(graph: &EntityGraph) -> ModuleClusters {
+    let module_by_entity: HashMap<&str, String> = graph
+        .entities
+        .values()
+        .map(|entity| (entity.id.as_str(), module_of(entity, &graph.entities)))
+        .collect();
+
+    let mut clusters: BTreeMap<&str, ModuleCluster> = BTreeMap::new();
+    for module in module_by_entity.values() {
+        clusters
+            .entry(module)
+            .or_insert_with(|| ModuleCluster {
+                module: module.clone(),
+                entity_count: 0,
+                internal_edges: 0,
+            })
+            .entity_count += 1;
+    }
+
+    let mut edges: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for edge in &graph.edges {
+        let (Some(from), Some(to)) = (
+            module_by_entity.get(edge.from_entity.as_str()),
+            module_by_entity.get(edge.to_entity.as_str()),
+        ) else {
+            continue;
+        };
+        if from == to {
+            if let Some(cluster) = clusters.get_mut(from.as_str()) {
+                cluster.internal_edges += 1;
+            }
+        } else {
+            *edges.entry((from, to)).or_default() += 1;
+        }
+    }
+
+    ModuleClusters {
+        clusters: clusters.into_values().collect(),
+        edges: edges
+            .into_iter()
+            .map(|((from, to), count)| ModuleEdge {
+                from: from.to_string(),
+                to: to.to_string(),
+                count,
+            })
+            .collect(),
+    }
+}
+
+fn print_module_clusters(clusters: &ModuleClusters) {
+    let cross_edges: usize = clusters.edges.iter().map(|edge| edge.count).sum();
+    println!(
+        "{} {} modules, {} cross-module edges",
+        crate::formatters::glyph("⊕", "+").green(),
+        clusters.clusters.len().to_string().bold(),
+        cross_edges.to_string().bold(),
+    );
+
+    for cluster in &clusters.clusters {
+        println!();
+        println!(
+            "  {} {}",
+            cluster.module.bold(),
+            format!(
+                "({} entities, {} internal edges)",
+                fmt_count(cluster.entity_count),
+                fmt_count(cluster.internal_edges)
+            )
+            .dimmed(),
+        );
+        let mut outgoing: Vec<&ModuleEdge> = clusters
+            .edges
+            .iter()
+            .filter(|edge| edge.from == cluster.module)
+            .collect();
+        outgoing.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.to.cmp(&b.to)));
+        for edge in outgoing {
+            println!("    → {}  {}", edge.to, fmt_count(edge.count).yellow());
+        }
+    }
+}
+
 /// Format a count with thousands separators (1234 -> "1,234"), uv-style.
 pub fn fmt_count(n: usize) -> String {
     let s = n.to_string();
@@ -164,6 +714,7 @@ fn compare_entity_refs(a: &&EntityRef, b: &&EntityRef) -> std::cmp::Ordering {
         .cmp(&b.from_entity)
         .then_with(|| a.to_entity.cmp(&b.to_entity))
         .then_with(|| ref_type_sort_key(&a.ref_type).cmp(&ref_type_sort_key(&b.ref_type)))
+        .then_with(|| a.confidence.total_cmp(&b.confidence))
 }
 
 fn ref_type_sort_key(ref_type: &RefType) -> u8 {
@@ -171,6 +722,8 @@ fn ref_type_sort_key(ref_type: &RefType) -> u8 {
         RefType::Calls => 0,
         RefType::Imports => 1,
         RefType::TypeRef => 2,
+        RefType::Implements => 3,
+        RefType::Extends => 4,
     }
 }
 
@@ -193,7 +746,7 @@ pub fn find_supported_files_public(
     registry: &ParserRegistry,
     ext_filter: &[String],
 ) -> Vec<String> {
-    find_supported_files_with_options(root, registry, ext_filter, false)
+    find_supported_files_with_options(root, registry, ext_filter, false, false)
 }
 
 pub fn find_supported_files_with_options(
@@ -201,6 +754,7 @@ pub fn find_supported_files_with_options(
     registry: &ParserRegistry,
     ext_filter: &[String],
     no_default_excludes: bool,
+    follow_symlinks: bool,
 ) -> Vec<String> {
     super::files::find_supported_files_in_path(
         root,
@@ -208,28 +762,98 @@ pub fn find_supported_files_with_options(
         registry,
         ext_filter,
         no_default_excludes,
+        follow_symlinks,
     )
 }
 
-pub fn cache_source_scope(
+/// Find supported files under each repo-relative directory in `subtrees`,
+/// or in the whole repo when there are none. Paths stay repo-relative, so
+/// entity ids match a full-repo walk; references into files outside the
+/// walked subtrees don't resolve.
+pub fn find_supported_files_in_subtrees(
     root: &Path,
+    subtrees: &[String],
+    registry: &ParserRegistry,
     ext_filter: &[String],
     no_default_excludes: bool,
-) -> CacheSourceScope {
-    if ext_filter.is_empty() && !no_default_excludes && !root.join(".semignore").exists() {
-        CacheSourceScope::Default
-    } else {
-        CacheSourceScope::Custom
+    follow_symlinks: bool,
+) -> Vec<String> {
+    if subtrees.is_empty() {
+        return find_supported_files_with_options(
+            root,
+            registry,
+            ext_filter,
+            no_default_excludes,
+            follow_symlinks,
+        );
     }
+
+    let mut files = Vec::new();
+    for subtree in subtrees {
+        let scan_path = root.join(subtree);
+        if !scan_path.is_dir() {
+            eprintln!(
+                "{} Not a directory in the repository: '{}'",
+                "error:".red().bold(),
+                subtree
+            );
+            std::process::exit(1);
+        }
+        files.extend(super::files::find_supported_files_in_path(
+            root,
+            &scan_path,
+            registry,
+            ext_filter,
+            no_default_excludes,
+            follow_symlinks,
+        ));
+    }
+    files.sort();
+    files.dedup();
+    files
 }
 
-fn find_supported_files_inner(
+/// The files a graph or impact run analyzes: the `--files-from` list when
+/// there is one, else a walk of `subtrees` or the whole repo.
+pub fn find_supported_files_for(
     root: &Path,
+    subtrees: &[String],
+    files_from: Option<&[String]>,
     registry: &ParserRegistry,
     ext_filter: &[String],
     no_default_excludes: bool,
+    follow_symlinks: bool,
 ) -> Vec<String> {
-    find_supported_files_with_options(root, registry, ext_filter, no_default_excludes)
+    match files_from {
+        Some(listed) => {
+            super::files::find_listed_supported_files(root, listed, registry, ext_filter)
+        }
+        None => find_supported_files_in_subtrees(
+            root,
+            subtrees,
+            registry,
+            ext_filter,
+            no_default_excludes,
+            follow_symlinks,
+        ),
+    }
+}
+
+pub fn cache_source_scope(
+    root: &Path,
+    ext_filter: &[String],
+    no_default_excludes: bool,
+    follow_symlinks: bool,
+) -> CacheSourceScope {
+    if ext_filter.is_empty()
+        && !no_default_excludes
+        && !follow_symlinks
+        && !super::files::has_custom_ignores(root)
+    {
+        CacheSourceScope::Default
+    } else {
+        CacheSourceScope::Custom
+    }
 }
 
 /// Build the entity graph + entities, using the disk cache when possible.
@@ -492,6 +1116,14 @@ pub fn get_or_build_graph_topology_with_timings(
                 return graph;
             }
         }
+        if let Some((graph, replayed)) = crate::snapshot::load_updated(root, file_paths, registry) {
+            timings.mark("snapshot_update");
+            if replayed {
+                crate::snapshot::save(root, file_paths, registry, &graph);
+                timings.mark("snapshot_save");
+            }
+            return graph;
+        }
     }
 
     let (graph, _entities) = get_or_build_graph_with_timings(
@@ -502,6 +1134,11 @@ pub fn get_or_build_graph_topology_with_timings(
         source_scope,
         timings,
     );
+    // Reaching a build means the snapshot was missing or stale.
+    if !no_cache {
+        crate::snapshot::save(root, file_paths, registry, &graph);
+        timings.mark("snapshot_save");
+    }
     graph
 }
 
@@ -523,6 +1160,14 @@ pub fn get_or_build_graph_topology_with_topology_save_on_miss_with_timings(
                 return graph;
             }
         }
+        if let Some((graph, replayed)) = crate::snapshot::load_updated(root, file_paths, registry) {
+            timings.mark("snapshot_update");
+            if replayed {
+                crate::snapshot::save(root, file_paths, registry, &graph);
+                timings.mark("snapshot_save");
+            }
+            return graph;
+        }
     }
 
     let (graph, _entities) = get_or_build_graph_with_topology_save_on_miss_with_timings(
@@ -533,6 +1178,11 @@ pub fn get_or_build_graph_topology_with_topology_save_on_miss_with_timings(
         source_scope,
         timings,
     );
+    // Reaching a build means the snapshot was missing or stale.
+    if !no_cache {
+        crate::snapshot::save(root, file_paths, registry, &graph);
+        timings.mark("snapshot_save");
+    }
     graph
 }
 
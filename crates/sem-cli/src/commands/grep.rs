@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use colored::Colorize;
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
+use sem_core::git::bridge::GitBridge;
+use sem_core::model::entity::SemanticEntity;
+use sem_core::parser::registry::ParserRegistry;
+use serde::Serialize;
+
+pub struct GrepOptions {
+    pub cwd: String,
+    pub pattern: String,
+    /// Files or directories to search (empty = whole repository).
+    pub paths: Vec<String>,
+    /// Attribute matches only to entities of these kinds (empty = any kind).
+    pub kinds: Vec<String>,
+    pub ignore_case: bool,
+    pub json: bool,
+    pub file_exts: Vec<String>,
+    pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrepHit {
+    pub file: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub matches: Vec<GrepLine>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GrepLine {
+    pub line: usize,
+    pub text: String,
+}
+
+pub fn grep_command(opts: GrepOptions) {
+    let regex = match RegexBuilder::new(&opts.pattern)
+        .case_insensitive(opts.ignore_case)
+        .build()
+    {
+        Ok(regex) => regex,
+        Err(e) => {
+            eprintln!("{} invalid pattern: {e}", "error:".red().bold());
+            std::process::exit(2);
+        }
+    };
+
+    let root = match GitBridge::open(Path::new(&opts.cwd)) {
+        Ok(git) => git.repo_root().to_path_buf(),
+        Err(_) => Path::new(&opts.cwd).to_path_buf(),
+    };
+    let registry = super::create_registry(&root.to_string_lossy());
+    let ext_filter = super::graph::normalize_exts(&opts.file_exts);
+
+    let mut file_paths = Vec::new();
+    let scan_paths = if opts.paths.is_empty() {
+        vec![root.clone()]
+    } else {
+        opts.paths
+            .iter()
+            .map(|path| Path::new(&opts.cwd).join(path))
+            .collect()
+    };
+    for scan_path in &scan_paths {
+        if scan_path.is_file() {
+            file_paths.push(super::files::file_path_for_entity(&root, scan_path));
+        } else if scan_path.is_dir() {
+            file_paths.extend(super::files::find_supported_files_in_path(
+                &root,
+                scan_path,
+                &registry,
+                &ext_filter,
+                opts.no_default_excludes,
+                opts.follow_symlinks,
+            ));
+        } else {
+            eprintln!(
+                "{} Path not found '{}'",
+                "error:".red().bold(),
+                scan_path.display()
+            );
+            std::process::exit(1);
+        }
+    }
+    file_paths.sort();
+    file_paths.dedup();
+
+    let hits = grep_files(&root, &file_paths, &registry, &regex, &opts.kinds);
+
+    if opts.json {
+        println!("{}", serde_json::to_string(&hits).unwrap());
+    } else if hits.is_empty() {
+        println!("no entity matches /{}/", opts.pattern);
+    } else {
+        print_hits(&hits, &regex);
+    }
+
+    if hits.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+/// Search the files that match at all, then attribute each matching line to
+/// the innermost entity (of an allowed kind) that contains it.
+fn grep_files(
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+    regex: &Regex,
+    kinds: &[String],
+) -> Vec<GrepHit> {
+    let mut hits: Vec<GrepHit> = file_paths
+        .par_iter()
+        .flat_map_iter(|file_path| {
+            let content = std::fs::read_to_string(root.join(file_path)).unwrap_or_default();
+            if !regex.is_match(&content) {
+                return Vec::new();
+            }
+            let mut entities = registry.extract_entities(file_path, &content);
+            entities.retain(|e| kinds.is_empty() || kinds.contains(&e.entity_type));
+            grep_entities(&entities, regex)
+        })
+        .collect();
+    hits.sort_by(|a, b| (&a.file, a.start_line).cmp(&(&b.file, b.start_line)));
+    hits
+}
+
+fn grep_entities(entities: &[SemanticEntity], regex: &Regex) -> Vec<GrepHit> {
+    // (file, line) -> (index of the smallest enclosing entity, line text)
+    let mut best: HashMap<(&str, usize), (usize, &str)> = HashMap::new();
+    for (index, entity) in entities.iter().enumerate() {
+        let span = entity.end_line.saturating_sub(entity.start_line);
+        for (offset, text) in entity.content.lines().enumerate() {
+            if !regex.is_match(text) {
+                continue;
+            }
+            let key = (entity.file_path.as_str(), entity.start_line + offset);
+            match best.get(&key) {
+                Some((other, _))
+                    if entities[*other]
+                        .end_line
+                        .saturating_sub(entities[*other].start_line)
+                        <= span => {}
+                _ => {
+                    best.insert(key, (index, text));
+                }
+            }
+        }
+    }
+
+    let mut by_entity: HashMap<usize, Vec<GrepLine>> = HashMap::new();
+    for ((_, line), (index, text)) in best {
+        by_entity.entry(index).or_default().push(GrepLine {
+            line,
+            text: text.trim_end().to_string(),
+        });
+    }
+    by_entity
+        .into_iter()
+        .map(|(index, mut matches)| {
+            matches.sort_by_key(|m| m.line);
+            let entity = &entities[index];
+            GrepHit {
+                file: entity.file_path.clone(),
+                name: entity.name.clone(),
+                entity_type: entity.entity_type.clone(),
+                start_line: entity.start_line,
+                end_line: entity.end_line,
+                matches,
+            }
+        })
+        .collect()
+}
+
+fn print_hits(hits: &[GrepHit], regex: &Regex) {
+    let mut current_file: Option<&str> = None;
+    let mut lines = 0;
+    for hit in hits {
+        if current_file != Some(hit.file.as_str()) {
+            if current_file.is_some() {
+                println!();
+            }
+            current_file = Some(hit.file.as_str());
+            println!("{}", hit.file.bold());
+        }
+        println!(
+            "  {} {} (L{}:{})",
+            hit.entity_type.dimmed(),
+            hit.name.bold(),
+            hit.start_line,
+            hit.end_line
+        );
+        for m in &hit.matches {
+            let text = regex.replace_all(m.text.trim(), |caps: &regex::Captures| {
+                caps[0].red().bold().to_string()
+            });
+            println!("    {} {}", format!("{:>5}:", m.line).dimmed(), text);
+        }
+        lines += hit.matches.len();
+    }
+    println!("\n{} matching lines in {} entities", lines, hits.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sem_core::parser::plugins::create_default_registry;
+
+    fn grep(content: &str, pattern: &str, kinds: &[&str]) -> Vec<GrepHit> {
+        let registry = create_default_registry();
+        let mut entities = registry.extract_entities("lib.rs", content);
+        entities.retain(|e| kinds.is_empty() || kinds.contains(&e.entity_type.as_str()));
+        let mut hits = grep_entities(&entities, &Regex::new(pattern).unwrap());
+        hits.sort_by_key(|hit| hit.start_line);
+        hits
+    }
+
+    const SOURCE: &str = "\
+struct Buffer {
+    ptr: *mut u8,
+}
+
+impl Buffer {
+    fn read(&self) -> u8 {
+        unsafe { *self.ptr }
+    }
+
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+fn write(buffer: &Buffer) {
+    unsafe { *buffer.ptr = 1 }
+}
+";
+
+    #[test]
+    fn matches_are_attributed_to_the_innermost_entity() {
+        let hits = grep(SOURCE, r"unsafe", &[]);
+        let summary: Vec<(&str, &str, Vec<usize>)> = hits
+            .iter()
+            .map(|hit| {
+                (
+                    hit.name.as_str(),
+                    hit.entity_type.as_str(),
+                    hit.matches.iter().map(|m| m.line).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("read", "function", vec![7]),
+                ("write", "function", vec![16]),
+            ]
+        );
+        assert_eq!(hits[0].matches[0].text, "        unsafe { *self.ptr }");
+    }
+
+    #[test]
+    fn kind_filter_attributes_to_the_nearest_allowed_kind() {
+        let hits = grep(SOURCE, r"\*self", &["impl"]);
+        assert_eq!(hits.len(), 1, "{hits:?}");
+        assert_eq!(hits[0].entity_type, "impl");
+        assert!(grep(SOURCE, r"unsafe", &["struct"]).is_empty());
+    }
+}
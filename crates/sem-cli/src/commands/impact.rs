@@ -1,10 +1,15 @@
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 
 use colored::Colorize;
 use sem_core::git::bridge::GitBridge;
-use sem_core::parser::graph::{EntityGraph, EntityInfo};
+use sem_core::parser::graph::{EntityGraph, EntityInfo, ImpactScore};
+use sem_core::parser::registry::ParserRegistry;
 use sem_mcp::cache::CacheSourceScope;
 
+use super::blame::OwnerLookup;
 use crate::cache::{CachedImpactError, CachedImpactMode, CachedImpactResult, DiskCache};
 use crate::timings::Timings;
 
@@ -19,6 +24,29 @@ pub struct ImpactOptions {
     pub depth: usize,
     pub no_cache: bool,
     pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
+    /// Drop edges scored below this confidence (0.0 keeps every edge).
+    pub min_confidence: f32,
+    /// Leave out test files and `#[cfg(test)]` modules.
+    pub exclude_tests: bool,
+    /// Leave out private entities and anything nested in them.
+    pub public_only: bool,
+    /// Attach each impacted entity's last author and commit.
+    pub blame: bool,
+    /// Repo-relative directories to walk instead of the whole repo.
+    pub subtrees: Vec<String>,
+    /// Repo-relative files to analyze instead of walking (`--files-from`).
+    pub files_from: Option<Vec<String>>,
+}
+
+impl ImpactOptions {
+    fn graph_filters(&self) -> super::graph::GraphFilters {
+        super::graph::GraphFilters {
+            min_confidence: self.min_confidence,
+            exclude_tests: self.exclude_tests,
+            public_only: self.public_only,
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -27,6 +55,10 @@ pub enum ImpactMode {
     Deps,
     Dependents,
     Tests,
+    /// Only the transitive dependents, out to `depth` (`--callers-only`).
+    Callers,
+    /// Only the transitive dependencies, out to `depth` (`--callees-only`).
+    Callees,
 }
 
 const LARGE_IMPACT_CACHE_MISS_FILE_THRESHOLD: usize = 20_000;
@@ -36,6 +68,7 @@ const LARGE_IMPACT_CACHE_MISS_FILE_THRESHOLD: usize = 20_000;
 /// command output). `count` extracts the entity count for the summary line.
 fn build_with_spinner<T>(
     file_count: usize,
+    registry: &ParserRegistry,
     build: impl FnOnce() -> T,
     count: impl FnOnce(&T) -> usize,
 ) -> T {
@@ -46,13 +79,33 @@ fn build_with_spinner<T>(
         super::graph::fmt_count(count(&result)),
         super::graph::fmt_count(file_count)
     ));
+    super::warn_skipped_files(registry);
     result
 }
 
 pub fn impact_command(opts: ImpactOptions) {
+    // The cache holds the whole repo's graph; a subtree or listed-files graph
+    // is built fresh rather than overwriting it. Parse diagnostics only come from a fresh
+    // build, so `--show-skipped` bypasses the cache too, as does `--no-ignore`
+    // since the cached edges already leave out `sem:ignore` entities.
+    let opts = ImpactOptions {
+        no_cache: opts.no_cache
+            || !opts.subtrees.is_empty()
+            || opts.files_from.is_some()
+            || crate::config::show_skipped()
+            || crate::config::no_ignore(),
+        ..opts
+    };
+    let owners = if opts.blame {
+        OwnerLookup::open(Path::new(&opts.cwd))
+    } else {
+        None
+    };
+    let owners = owners.as_ref();
+
     // A resident server beats the cloud path on both freshness and latency,
     // so the sidecar goes first.
-    if try_sidecar_impact(&opts) {
+    if try_sidecar_impact(&opts, owners) {
         return;
     }
 
@@ -70,15 +123,31 @@ pub fn impact_command(opts: ImpactOptions) {
     let registry = super::create_registry(&root.to_string_lossy());
 
     let ext_filter = super::graph::normalize_exts(&opts.file_exts);
-    let source_scope =
-        super::graph::cache_source_scope(root, &ext_filter, opts.no_default_excludes);
+    let source_scope = super::graph::cache_source_scope(
+        root,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
     let file_hint = opts
         .file_hint
         .as_deref()
         .map(|file| super::normalize_repo_relative_path(Path::new(&opts.cwd), root, file));
     let cache_first_entity_scope = opts.entity_id.is_some() || file_hint.is_some();
+    let query = CachedImpactQuery {
+        root,
+        file_hint: file_hint.as_deref(),
+        source_scope,
+        owners,
+    };
+
+    // Cached topology queries don't see edge confidence or drop tests or
+    // private entities; a --min-confidence, --exclude-tests or --public-only
+    // filter always runs on the in-memory graph.
+    let filter_edges = opts.graph_filters().any();
 
     if !opts.no_cache
+        && !filter_edges
         && matches!(opts.mode, ImpactMode::Deps)
         && matches!(source_scope, CacheSourceScope::Default)
         && cache_first_entity_scope
@@ -86,16 +155,7 @@ pub fn impact_command(opts: ImpactOptions) {
         match DiskCache::open(root) {
             Ok(disk) => {
                 timings.mark("cache_open");
-                if try_cached_impact_query(
-                    &disk,
-                    root,
-                    &[],
-                    &opts,
-                    file_hint.as_deref(),
-                    source_scope,
-                    true,
-                    &mut timings,
-                ) {
+                if try_cached_impact_query(&disk, &query, &[], &opts, true, &mut timings) {
                     return;
                 }
             }
@@ -105,28 +165,22 @@ pub fn impact_command(opts: ImpactOptions) {
         }
     }
 
-    let file_paths = super::graph::find_supported_files_with_options(
+    let file_paths = super::graph::find_supported_files_for(
         root,
+        &opts.subtrees,
+        opts.files_from.as_deref(),
         &registry,
         &ext_filter,
         opts.no_default_excludes,
+        opts.follow_symlinks,
     );
     timings.mark("file_discovery");
 
-    if !opts.no_cache {
+    if !opts.no_cache && !filter_edges {
         match DiskCache::open(root) {
             Ok(disk) => {
                 timings.mark("cache_open");
-                if try_cached_impact_query(
-                    &disk,
-                    root,
-                    &file_paths,
-                    &opts,
-                    file_hint.as_deref(),
-                    source_scope,
-                    false,
-                    &mut timings,
-                ) {
+                if try_cached_impact_query(&disk, &query, &file_paths, &opts, false, &mut timings) {
                     return;
                 }
             }
@@ -138,8 +192,9 @@ pub fn impact_command(opts: ImpactOptions) {
 
     match opts.mode {
         ImpactMode::Deps => {
-            let graph = build_with_spinner(
+            let mut graph = build_with_spinner(
                 file_paths.len(),
+                &registry,
                 || {
                     if opts.no_cache || file_paths.len() > LARGE_IMPACT_CACHE_MISS_FILE_THRESHOLD {
                         let entity_name = opts.entity_name.clone();
@@ -181,6 +236,7 @@ pub fn impact_command(opts: ImpactOptions) {
                 },
                 |g| g.entities.len(),
             );
+            super::graph::apply_graph_filters(&mut graph, &opts.graph_filters());
             let entity = find_entity(
                 &graph,
                 opts.entity_name.as_deref(),
@@ -191,9 +247,10 @@ pub fn impact_command(opts: ImpactOptions) {
             print_deps(&graph, entity, opts.json);
             timings.mark("cli_output_serialization");
         }
-        ImpactMode::Dependents => {
-            let graph = build_with_spinner(
+        ImpactMode::Dependents | ImpactMode::Callers | ImpactMode::Callees => {
+            let mut graph = build_with_spinner(
                 file_paths.len(),
+                &registry,
                 || {
                     if file_paths.len() > LARGE_IMPACT_CACHE_MISS_FILE_THRESHOLD {
                         super::graph::get_or_build_graph_topology_with_topology_save_on_miss_with_timings(
@@ -217,6 +274,7 @@ pub fn impact_command(opts: ImpactOptions) {
                 },
                 |g| g.entities.len(),
             );
+            super::graph::apply_graph_filters(&mut graph, &opts.graph_filters());
             let entity = find_entity(
                 &graph,
                 opts.entity_name.as_deref(),
@@ -224,13 +282,19 @@ pub fn impact_command(opts: ImpactOptions) {
                 file_hint.as_deref(),
             );
             timings.mark("entity_lookup");
-            print_dependents(&graph, entity, opts.json);
+            match opts.mode {
+                ImpactMode::Dependents => print_dependents(&graph, entity, opts.json, owners),
+                ImpactMode::Callers => print_callers(&graph, entity, opts.json, opts.depth, owners),
+                ImpactMode::Callees => print_callees(&graph, entity, opts.json, opts.depth),
+                _ => unreachable!(),
+            }
             timings.mark("cli_output_serialization");
         }
         ImpactMode::Tests | ImpactMode::All => {
             if file_paths.len() > LARGE_IMPACT_CACHE_MISS_FILE_THRESHOLD {
                 let graph_data = build_with_spinner(
                     file_paths.len(),
+                    &registry,
                     || {
                         super::graph::get_or_build_graph_with_test_data_and_topology_save_on_miss_with_timings(
                             root,
@@ -249,7 +313,8 @@ pub fn impact_command(opts: ImpactOptions) {
                     },
                 );
                 match graph_data {
-                    super::graph::GraphWithTestData::Full(graph, all_entities) => {
+                    super::graph::GraphWithTestData::Full(mut graph, all_entities) => {
+                        super::graph::apply_graph_filters(&mut graph, &opts.graph_filters());
                         let entity = find_entity(
                             &graph,
                             opts.entity_name.as_deref(),
@@ -272,14 +337,16 @@ pub fn impact_command(opts: ImpactOptions) {
                                 opts.json,
                                 opts.depth,
                                 &registry.custom_test_dirs,
+                                owners,
                             ),
                             _ => unreachable!(),
                         }
                     }
                     super::graph::GraphWithTestData::Topology {
-                        graph,
+                        mut graph,
                         test_entity_ids,
                     } => {
+                        super::graph::apply_graph_filters(&mut graph, &opts.graph_filters());
                         let entity = find_entity(
                             &graph,
                             opts.entity_name.as_deref(),
@@ -297,14 +364,16 @@ pub fn impact_command(opts: ImpactOptions) {
                                 &test_entity_ids,
                                 opts.json,
                                 opts.depth,
+                                owners,
                             ),
                             _ => unreachable!(),
                         }
                     }
                 }
             } else {
-                let (graph, all_entities) = build_with_spinner(
+                let (mut graph, all_entities) = build_with_spinner(
                     file_paths.len(),
+                    &registry,
                     || {
                         super::graph::get_or_build_graph_with_timings(
                             root,
@@ -317,6 +386,7 @@ pub fn impact_command(opts: ImpactOptions) {
                     },
                     |(g, _)| g.entities.len(),
                 );
+                super::graph::apply_graph_filters(&mut graph, &opts.graph_filters());
                 let entity = find_entity(
                     &graph,
                     opts.entity_name.as_deref(),
@@ -339,6 +409,7 @@ pub fn impact_command(opts: ImpactOptions) {
                         opts.json,
                         opts.depth,
                         &registry.custom_test_dirs,
+                        owners,
                     ),
                     _ => unreachable!(),
                 }
@@ -359,6 +430,11 @@ struct SidecarImpactResult {
     dependencies: Vec<EntityInfo>,
     dependents: Vec<EntityInfo>,
     impact: Vec<(EntityInfo, usize)>,
+    /// Required, not defaulted: a sidecar too old to report paths fails to
+    /// parse and the query falls back to the local path.
+    paths: HashMap<String, Vec<String>>,
+    #[serde(rename = "impactScore")]
+    impact_score: ImpactScore,
     tests: Vec<EntityInfo>,
 }
 
@@ -367,11 +443,14 @@ struct SidecarImpactResult {
 /// server can answer with identical semantics: default source scope, resolve
 /// by name. Everything else — and any sidecar miss, error, or ambiguity —
 /// falls back to the normal local path and its richer diagnostics.
-fn try_sidecar_impact(opts: &ImpactOptions) -> bool {
+fn try_sidecar_impact(opts: &ImpactOptions, owners: Option<&OwnerLookup>) -> bool {
     if opts.no_cache
         || opts.no_default_excludes
+        || opts.follow_symlinks
+        || opts.graph_filters().any()
         || !opts.file_exts.is_empty()
         || opts.entity_id.is_some()
+        || matches!(opts.mode, ImpactMode::Callees)
     {
         return false;
     }
@@ -382,9 +461,9 @@ fn try_sidecar_impact(opts: &ImpactOptions) -> bool {
         return false;
     };
     let root = git.repo_root().to_path_buf();
-    // A .semignore means this repo's default scope is custom; the resident
+    // Custom ignores mean this repo's default scope is custom; the resident
     // server may not share it, so stay local (mirrors cache_source_scope).
-    if root.join(".semignore").exists() {
+    if super::files::has_custom_ignores(&root) {
         return false;
     }
 
@@ -419,32 +498,46 @@ fn try_sidecar_impact(opts: &ImpactOptions) -> bool {
         dependencies: parsed.dependencies,
         dependents: parsed.dependents,
         impact: parsed.impact,
+        impact_paths: parsed.paths,
+        impact_score: parsed.impact_score,
         tests: parsed.tests,
         tests_truncated: false,
     };
-    print_cached_result(&cached, opts.mode, opts.json, opts.depth);
+    print_cached_result(&cached, opts.mode, opts.json, opts.depth, owners);
     true
 }
 
+/// What a cached impact query needs besides the options: the same for the
+/// cache-first attempt and the one after file discovery.
+struct CachedImpactQuery<'a> {
+    root: &'a Path,
+    file_hint: Option<&'a str>,
+    source_scope: CacheSourceScope,
+    owners: Option<&'a OwnerLookup>,
+}
+
 fn try_cached_impact_query(
     disk: &DiskCache,
-    root: &Path,
+    query: &CachedImpactQuery,
     file_paths: &[String],
     opts: &ImpactOptions,
-    file_hint: Option<&str>,
-    source_scope: CacheSourceScope,
     cache_first: bool,
     timings: &mut Timings,
 ) -> bool {
+    // The cache answers dependents transitively but dependencies only one
+    // hop deep, so `--callees-only` always walks the in-memory graph.
+    let Some(mode) = cached_mode_for(opts.mode) else {
+        return false;
+    };
     match disk.query_impact_topology(
-        root,
+        query.root,
         file_paths,
-        source_scope,
+        query.source_scope,
         cache_first,
         opts.entity_name.as_deref(),
         opts.entity_id.as_deref(),
-        file_hint,
-        cached_mode_for(opts.mode),
+        query.file_hint,
+        mode,
         opts.depth,
     ) {
         Ok(Some(result)) => {
@@ -456,7 +549,7 @@ fn try_cached_impact_query(
                 timings.mark("cache_tests_empty_fallthrough");
                 return false;
             }
-            print_cached_result(&result, opts.mode, opts.json, opts.depth);
+            print_cached_result(&result, opts.mode, opts.json, opts.depth, query.owners);
             timings.mark("cli_output_serialization");
             timings.finish();
             true
@@ -473,16 +566,17 @@ fn try_cached_impact_query(
     }
 }
 
-fn cached_mode_for(mode: ImpactMode) -> CachedImpactMode {
+fn cached_mode_for(mode: ImpactMode) -> Option<CachedImpactMode> {
     match mode {
-        ImpactMode::All => CachedImpactMode::All,
-        ImpactMode::Deps => CachedImpactMode::Deps,
-        ImpactMode::Dependents => CachedImpactMode::Dependents,
-        ImpactMode::Tests => CachedImpactMode::Tests,
+        ImpactMode::All | ImpactMode::Callers => Some(CachedImpactMode::All),
+        ImpactMode::Deps => Some(CachedImpactMode::Deps),
+        ImpactMode::Dependents => Some(CachedImpactMode::Dependents),
+        ImpactMode::Tests => Some(CachedImpactMode::Tests),
+        ImpactMode::Callees => None,
     }
 }
 
-fn find_entity<'a>(
+pub(super) fn find_entity<'a>(
     graph: &'a EntityGraph,
     name: Option<&str>,
     entity_id: Option<&str>,
@@ -574,10 +668,106 @@ fn owned_entity_list_json(entities: &[EntityInfo]) -> Vec<serde_json::Value> {
     entities.iter().map(entity_json).collect()
 }
 
+/// `entity_json` plus `isTest` for test-partition entities and an `owner`
+/// field when `--blame` is on.
+fn impacted_json(e: &EntityInfo, owners: Option<&OwnerLookup>) -> serde_json::Value {
+    let mut value = entity_json(e);
+    if e.is_test {
+        value
+            .as_object_mut()
+            .unwrap()
+            .insert("isTest".to_string(), serde_json::json!(true));
+    }
+    if let Some(owners) = owners {
+        let owner = owners
+            .owner(&e.file_path, e.start_line, e.end_line)
+            .map_or(serde_json::Value::Null, |owner| owner.to_json());
+        value
+            .as_object_mut()
+            .unwrap()
+            .insert("owner".to_string(), owner);
+    }
+    value
+}
+
+/// Trailing owner column for an impacted entity's terminal line.
+fn owner_column(e: &EntityInfo, owners: Option<&OwnerLookup>) -> String {
+    match owners {
+        Some(owners) => {
+            let label = owners
+                .owner(&e.file_path, e.start_line, e.end_line)
+                .map_or_else(|| "unknown".to_string(), |owner| owner.label());
+            format!("  {}", label.cyan())
+        }
+        None => String::new(),
+    }
+}
+
+/// `dependents` without test-partition entities, unless `entity` is a test
+/// itself: tests exercising production code aren't counted among its
+/// dependents. They still show up as affected tests and, labeled, in the
+/// transitive impact.
+fn production_dependents<'a>(
+    entity: &EntityInfo,
+    dependents: impl IntoIterator<Item = &'a EntityInfo>,
+) -> Vec<&'a EntityInfo> {
+    dependents
+        .into_iter()
+        .filter(|dependent| entity.is_test || !dependent.is_test)
+        .collect()
+}
+
+/// Header suffix saying how many of the transitively affected entities are
+/// tests, so test callers don't read as production blast radius.
+fn in_tests_label(in_tests: usize) -> String {
+    if in_tests == 0 {
+        String::new()
+    } else {
+        format!(", {in_tests} in tests")
+    }
+}
+
+/// Dimmed "via" line under an impacted entity two or more hops away, naming
+/// the entities its reference path runs through.
+fn via_line(path: &[String], name_of: impl Fn(&str) -> Option<String>) -> Option<String> {
+    if path.len() <= 2 {
+        return None;
+    }
+    let hops: Vec<String> = path[1..path.len() - 1]
+        .iter()
+        .map(|id| name_of(id).unwrap_or_else(|| id.clone()))
+        .collect();
+    let arrow = format!(" {} ", crate::formatters::glyph("→", "->"));
+    Some(format!("via {}", hops.join(&arrow)))
+}
+
+/// Weighted impact score under the transitive-impact header, naming the
+/// heaviest affected entities.
+fn print_impact_score(score: &ImpactScore, name_of: impl Fn(&str) -> Option<String>) {
+    let heaviest: Vec<String> = score
+        .heaviest
+        .iter()
+        .map(|weighted| {
+            let name = name_of(&weighted.entity_id).unwrap_or_else(|| weighted.entity_id.clone());
+            format!("{name} ({})", weighted.weight)
+        })
+        .collect();
+    println!(
+        "    {} {}{}",
+        "impact score".dimmed(),
+        score.score.to_string().bold(),
+        if heaviest.is_empty() {
+            String::new()
+        } else {
+            format!("{} {}", ", heaviest:".dimmed(), heaviest.join(", "))
+        }
+    );
+}
+
 fn print_entity_header(e: &sem_core::parser::graph::EntityInfo) {
     println!(
         "{} {} {} ({}:{}–{})",
-        "⊕".green(),
+        crate::formatters::glyph("⊕", "+").green(),
         e.entity_type.dimmed(),
         e.name.bold(),
         e.file_path.dimmed(),
@@ -586,20 +776,30 @@ fn print_entity_header(e: &sem_core::parser::graph::EntityInfo) {
     );
 }
 
-fn print_cached_result(result: &CachedImpactResult, mode: ImpactMode, json: bool, depth: usize) {
+fn print_cached_result(
+    result: &CachedImpactResult,
+    mode: ImpactMode,
+    json: bool,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) {
     match mode {
         ImpactMode::Deps => {
             print_cached_deps(&result.entity, &result.dependencies, json);
         }
         ImpactMode::Dependents => {
-            print_cached_dependents(&result.entity, &result.dependents, json);
+            print_cached_dependents(&result.entity, &result.dependents, json, owners);
         }
         ImpactMode::Tests => {
             print_cached_tests(&result.entity, &result.tests, result.tests_truncated, json);
         }
         ImpactMode::All => {
-            print_cached_all(result, json, depth);
+            print_cached_all(result, json, depth, owners);
+        }
+        ImpactMode::Callers => {
+            print_cached_callers(result, json, depth, owners);
         }
+        ImpactMode::Callees => unreachable!("--callees-only is never answered from the cache"),
     }
 }
 
@@ -630,11 +830,21 @@ fn print_cached_deps(entity: &EntityInfo, deps: &[EntityInfo], json: bool) {
     }
 }
 
-fn print_cached_dependents(entity: &EntityInfo, dependents: &[EntityInfo], json: bool) {
+fn print_cached_dependents(
+    entity: &EntityInfo,
+    dependents: &[EntityInfo],
+    json: bool,
+    owners: Option<&OwnerLookup>,
+) {
+    let dependents = production_dependents(entity, dependents);
     if json {
+        let dependents: Vec<_> = dependents
+            .iter()
+            .map(|e| impacted_json(e, owners))
+            .collect();
         let output = serde_json::json!({
             "entity": entity_json(entity),
-            "dependents": owned_entity_list_json(dependents),
+            "dependents": dependents,
         });
         println!("{}", serde_json::to_string(&output).unwrap());
     } else {
@@ -643,13 +853,14 @@ fn print_cached_dependents(entity: &EntityInfo, dependents: &[EntityInfo], json:
             println!("\n  {} {}", "✓".green().bold(), "No dependents.".dimmed());
         } else {
             println!("\n  {} {}", "←".yellow(), "depended on by:".dimmed());
-            for dep in dependents {
+            for dep in &dependents {
                 println!(
-                    "    {} {} {} ({})",
+                    "    {} {} {} ({}){}",
                     "←".yellow(),
                     dep.entity_type.dimmed(),
                     dep.name.bold(),
                     dep.file_path.dimmed(),
+                    owner_column(dep, owners),
                 );
             }
         }
@@ -720,29 +931,23 @@ fn print_cached_tests_truncation_warning(truncated: bool) {
     }
 }
 
-fn print_cached_all(result: &CachedImpactResult, json: bool, depth: usize) {
+fn print_cached_all(
+    result: &CachedImpactResult,
+    json: bool,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) {
+    let dependents = production_dependents(&result.entity, &result.dependents);
     if json {
-        let impact_entities: Vec<serde_json::Value> = result
-            .impact
+        let dependents: Vec<_> = dependents
             .iter()
-            .map(|(entity, depth)| {
-                let mut value = entity_json(entity);
-                value
-                    .as_object_mut()
-                    .unwrap()
-                    .insert("depth".to_string(), serde_json::json!(depth));
-                value
-            })
+            .map(|e| impacted_json(e, owners))
             .collect();
         let mut output = serde_json::json!({
             "entity": entity_json(&result.entity),
             "dependencies": owned_entity_list_json(&result.dependencies),
-            "dependents": owned_entity_list_json(&result.dependents),
-            "impact": {
-                "depth": depth,
-                "total": result.impact.len(),
-                "entities": impact_entities,
-            },
+            "dependents": dependents,
+            "impact": cached_impact_json(result, depth, owners),
             "tests": owned_entity_list_json(&result.tests),
         });
         if result.tests_truncated {
@@ -770,19 +975,94 @@ fn print_cached_all(result: &CachedImpactResult, json: bool, depth: usize) {
         }
     }
 
-    if !result.dependents.is_empty() {
+    if !dependents.is_empty() {
         println!("\n  {} {}", "←".yellow(), "depended on by:".dimmed());
-        for dep in &result.dependents {
+        for dep in &dependents {
             println!(
-                "    {} {} {} ({})",
+                "    {} {} {} ({}){}",
                 "←".yellow(),
                 dep.entity_type.dimmed(),
                 dep.name.bold(),
                 dep.file_path.dimmed(),
+                owner_column(dep, owners),
             );
         }
     }
 
+    print_cached_impact(result, depth, owners);
+
+    if !result.tests.is_empty() {
+        println!(
+            "\n  {} {}",
+            "⚡".yellow(),
+            format!("{} tests affected:", result.tests.len()).bold()
+        );
+        for test in &result.tests {
+            println!(
+                "    {} {} ({})",
+                test.entity_type.dimmed(),
+                test.name.bold(),
+                test.file_path.dimmed(),
+            );
+        }
+    }
+    print_cached_tests_truncation_warning(result.tests_truncated);
+
+    println!();
+}
+
+fn print_cached_callers(
+    result: &CachedImpactResult,
+    json: bool,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) {
+    if json {
+        let output = serde_json::json!({
+            "entity": entity_json(&result.entity),
+            "impact": cached_impact_json(result, depth, owners),
+        });
+        println!("{}", serde_json::to_string(&output).unwrap());
+        return;
+    }
+
+    print_entity_header(&result.entity);
+    print_cached_impact(result, depth, owners);
+    println!();
+}
+
+fn cached_impact_json(
+    result: &CachedImpactResult,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) -> serde_json::Value {
+    let impact_entities: Vec<serde_json::Value> = result
+        .impact
+        .iter()
+        .map(|(entity, depth)| {
+            let mut value = impacted_json(entity, owners);
+            let object = value.as_object_mut().unwrap();
+            object.insert("depth".to_string(), serde_json::json!(depth));
+            let path = result
+                .impact_paths
+                .get(&entity.id)
+                .cloned()
+                .unwrap_or_default();
+            object.insert("path".to_string(), serde_json::json!(path));
+            value
+        })
+        .collect();
+    serde_json::json!({
+        "depth": depth,
+        "total": result.impact.len(),
+        "score": result.impact_score.score,
+        "heaviest": result.impact_score.heaviest,
+        "entities": impact_entities,
+    })
+}
+
+/// The transitive-impact block of a cached result, grouped by depth.
+fn print_cached_impact(result: &CachedImpactResult, depth: usize, owners: Option<&OwnerLookup>) {
     if result.impact.is_empty() {
         println!(
             "\n  {} {}",
@@ -796,21 +1076,26 @@ fn print_cached_all(result: &CachedImpactResult, json: bool, depth: usize) {
             .map(|(_, depth)| *depth)
             .max()
             .unwrap_or(0);
-        let depth_label = if depth == 0 {
-            "unlimited".to_string()
-        } else {
-            format!("depth {}", depth)
-        };
+        let depth_label = depth_label(depth);
+        let in_tests = result.impact.iter().filter(|(e, _)| e.is_test).count();
         println!(
             "\n  {} {}",
             "!".red().bold(),
             format!(
-                "{} entities transitively affected ({}):",
+                "{} entities transitively affected ({}{}):",
                 result.impact.len(),
-                depth_label
+                depth_label,
+                in_tests_label(in_tests)
             )
             .red(),
         );
+        print_impact_score(&result.impact_score, |id| {
+            result
+                .impact
+                .iter()
+                .find(|(e, _)| e.id == id)
+                .map(|(e, _)| e.name.clone())
+        });
 
         for current_depth in 1..=max_depth_seen {
             let at_depth: Vec<_> = result
@@ -831,35 +1116,29 @@ fn print_cached_all(result: &CachedImpactResult, json: bool, depth: usize) {
             println!("\n    {} ({})", label.bold(), at_depth.len());
             for entity in at_depth {
                 println!(
-                    "      {} {} {} ({}:L{})",
+                    "      {} {} {} ({}:L{}){}",
                     "→".red(),
                     entity.entity_type.dimmed(),
                     entity.name.bold(),
                     entity.file_path.dimmed(),
                     entity.start_line,
+                    owner_column(entity, owners),
                 );
+                let via = result.impact_paths.get(&entity.id).and_then(|path| {
+                    via_line(path, |id| {
+                        result
+                            .impact
+                            .iter()
+                            .find(|(e, _)| e.id == id)
+                            .map(|(e, _)| e.name.clone())
+                    })
+                });
+                if let Some(via) = via {
+                    println!("          {}", via.dimmed());
+                }
             }
         }
     }
-
-    if !result.tests.is_empty() {
-        println!(
-            "\n  {} {}",
-            "⚡".yellow(),
-            format!("{} tests affected:", result.tests.len()).bold()
-        );
-        for test in &result.tests {
-            println!(
-                "    {} {} ({})",
-                test.entity_type.dimmed(),
-                test.name.bold(),
-                test.file_path.dimmed(),
-            );
-        }
-    }
-    print_cached_tests_truncation_warning(result.tests_truncated);
-
-    println!();
 }
 
 fn print_cached_error(error: CachedImpactError) -> ! {
@@ -944,13 +1223,22 @@ fn print_deps(graph: &EntityGraph, entity: &sem_core::parser::graph::EntityInfo,
     }
 }
 
-fn print_dependents(graph: &EntityGraph, entity: &sem_core::parser::graph::EntityInfo, json: bool) {
-    let dependents = graph.get_dependents(&entity.id);
+fn print_dependents(
+    graph: &EntityGraph,
+    entity: &EntityInfo,
+    json: bool,
+    owners: Option<&OwnerLookup>,
+) {
+    let dependents = production_dependents(entity, graph.get_dependents(&entity.id));
 
     if json {
+        let dependents: Vec<_> = dependents
+            .iter()
+            .map(|e| impacted_json(e, owners))
+            .collect();
         let output = serde_json::json!({
             "entity": entity_json(entity),
-            "dependents": entity_list_json(&dependents),
+            "dependents": dependents,
         });
         println!("{}", serde_json::to_string(&output).unwrap());
     } else {
@@ -961,11 +1249,12 @@ fn print_dependents(graph: &EntityGraph, entity: &sem_core::parser::graph::Entit
             println!("\n  {} {}", "←".yellow(), "depended on by:".dimmed());
             for dep in &dependents {
                 println!(
-                    "    {} {} {} ({})",
+                    "    {} {} {} ({}){}",
                     "←".yellow(),
                     dep.entity_type.dimmed(),
                     dep.name.bold(),
                     dep.file_path.dimmed(),
+                    owner_column(dep, owners),
                 );
             }
         }
@@ -1001,6 +1290,8 @@ fn print_tests(
             parent_id: e.parent_id.clone(),
             start_line: e.start_line,
             end_line: e.end_line,
+            is_test: true,
+            is_private: false,
         })
         .collect();
     if !owned.is_empty() && !json {
@@ -1099,9 +1390,10 @@ fn print_all(
     json: bool,
     depth: usize,
     custom_test_dirs: &[String],
+    owners: Option<&OwnerLookup>,
 ) {
     let tests = graph.test_impact_with_custom_dirs(&entity.id, all_entities, custom_test_dirs);
-    print_all_with_tests(graph, entity, &tests, json, depth);
+    print_all_with_tests(graph, entity, &tests, json, depth, owners);
 }
 
 fn print_all_with_ids(
@@ -1110,9 +1402,10 @@ fn print_all_with_ids(
     test_entity_ids: &HashSet<String>,
     json: bool,
     depth: usize,
+    owners: Option<&OwnerLookup>,
 ) {
     let tests = test_impact_from_ids(graph, &entity.id, test_entity_ids);
-    print_all_with_tests(graph, entity, &tests, json, depth);
+    print_all_with_tests(graph, entity, &tests, json, depth, owners);
 }
 
 fn test_impact_from_ids<'a>(
@@ -1133,31 +1426,22 @@ fn print_all_with_tests(
     tests: &[&EntityInfo],
     json: bool,
     depth: usize,
+    owners: Option<&OwnerLookup>,
 ) {
     let deps = graph.get_dependencies(&entity.id);
-    let dependents = graph.get_dependents(&entity.id);
-    let impact_bounded = graph.impact_analysis_bounded(&entity.id, depth);
+    let dependents = production_dependents(entity, graph.get_dependents(&entity.id));
+    let impact_bounded = graph.impact_analysis_with_paths_bounded(&entity.id, depth);
+    let impact_score = graph.impact_score(&entity.id, depth);
 
     if json {
-        let impact_entities: Vec<serde_json::Value> = impact_bounded
-            .iter()
-            .map(|(e, d)| {
-                let mut v = entity_json(e);
-                v.as_object_mut()
-                    .unwrap()
-                    .insert("depth".to_string(), serde_json::json!(d));
-                v
-            })
-            .collect();
         let output = serde_json::json!({
             "entity": entity_json(entity),
             "dependencies": entity_list_json(&deps),
-            "dependents": entity_list_json(&dependents),
-            "impact": {
-                "depth": depth,
-                "total": impact_bounded.len(),
-                "entities": impact_entities,
-            },
+            "dependents": dependents
+                .iter()
+                .map(|e| impacted_json(e, owners))
+                .collect::<Vec<_>>(),
+            "impact": impact_json(&impact_bounded, &impact_score, depth, owners),
             "tests": entity_list_json(tests),
         });
         println!("{}", serde_json::to_string(&output).unwrap());
@@ -1183,86 +1467,225 @@ fn print_all_with_tests(
             println!("\n  {} {}", "←".yellow(), "depended on by:".dimmed());
             for dep in &dependents {
                 println!(
-                    "    {} {} {} ({})",
+                    "    {} {} {} ({}){}",
                     "←".yellow(),
                     dep.entity_type.dimmed(),
                     dep.name.bold(),
                     dep.file_path.dimmed(),
+                    owner_column(dep, owners),
                 );
             }
         }
 
-        // Transitive impact grouped by depth
-        if impact_bounded.is_empty() {
+        print_impact(graph, &impact_bounded, &impact_score, depth, owners);
+
+        // Tests
+        if !tests.is_empty() {
             println!(
                 "\n  {} {}",
-                "✓".green().bold(),
-                "No other entities are affected by changes to this entity.".dimmed()
+                "⚡".yellow(),
+                format!("{} tests affected:", tests.len()).bold()
             );
+            for t in tests {
+                println!(
+                    "    {} {} ({})",
+                    t.entity_type.dimmed(),
+                    t.name.bold(),
+                    t.file_path.dimmed(),
+                );
+            }
+        }
+
+        println!();
+    }
+}
+
+/// `--callers-only`: the transitive impact set without dependencies or tests.
+fn print_callers(
+    graph: &EntityGraph,
+    entity: &EntityInfo,
+    json: bool,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) {
+    let impact_bounded = graph.impact_analysis_with_paths_bounded(&entity.id, depth);
+    let impact_score = graph.impact_score(&entity.id, depth);
+
+    if json {
+        let output = serde_json::json!({
+            "entity": entity_json(entity),
+            "impact": impact_json(&impact_bounded, &impact_score, depth, owners),
+        });
+        println!("{}", serde_json::to_string(&output).unwrap());
+    } else {
+        print_entity_header(entity);
+        print_impact(graph, &impact_bounded, &impact_score, depth, owners);
+        println!();
+    }
+}
+
+/// `--callees-only`: what the entity depends on, out to `depth` hops.
+fn print_callees(graph: &EntityGraph, entity: &EntityInfo, json: bool, depth: usize) {
+    let deps = graph.dependencies_bounded(&entity.id, depth);
+
+    if json {
+        let dependencies: Vec<serde_json::Value> = deps
+            .iter()
+            .map(|(e, d)| {
+                let mut v = entity_json(e);
+                v.as_object_mut()
+                    .unwrap()
+                    .insert("depth".to_string(), serde_json::json!(d));
+                v
+            })
+            .collect();
+        let output = serde_json::json!({
+            "entity": entity_json(entity),
+            "dependencies": dependencies,
+        });
+        println!("{}", serde_json::to_string(&output).unwrap());
+    } else {
+        print_entity_header(entity);
+        if deps.is_empty() {
+            println!("\n  {} {}", "✓".green().bold(), "No dependencies.".dimmed());
         } else {
-            let max_depth_seen = impact_bounded.iter().map(|(_, d)| *d).max().unwrap_or(0);
-            let depth_label = if depth == 0 {
-                "unlimited".to_string()
-            } else {
-                format!("depth {}", depth)
-            };
             println!(
                 "\n  {} {}",
-                "!".red().bold(),
+                "→".blue(),
                 format!(
-                    "{} entities transitively affected ({}):",
-                    impact_bounded.len(),
-                    depth_label
+                    "depends on {} entities ({}):",
+                    deps.len(),
+                    depth_label(depth)
                 )
-                .red(),
+                .dimmed()
             );
-
+            let max_depth_seen = deps.iter().map(|(_, d)| *d).max().unwrap_or(0);
             for d in 1..=max_depth_seen {
-                let at_depth: Vec<_> = impact_bounded
-                    .iter()
-                    .filter(|(_, dd)| *dd == d)
-                    .map(|(e, _)| *e)
-                    .collect();
+                let at_depth: Vec<_> = deps.iter().filter(|(_, dd)| *dd == d).collect();
                 if at_depth.is_empty() {
                     continue;
                 }
 
                 let label = if d == 1 {
-                    "Direct dependents".to_string()
+                    "Direct dependencies".to_string()
                 } else {
                     format!("Depth {}", d)
                 };
                 println!("\n    {} ({})", label.bold(), at_depth.len());
-                for imp in &at_depth {
+                for (dep, _) in at_depth {
                     println!(
                         "      {} {} {} ({}:L{})",
-                        "→".red(),
-                        imp.entity_type.dimmed(),
-                        imp.name.bold(),
-                        imp.file_path.dimmed(),
-                        imp.start_line,
+                        "→".blue(),
+                        dep.entity_type.dimmed(),
+                        dep.name.bold(),
+                        dep.file_path.dimmed(),
+                        dep.start_line,
                     );
                 }
             }
         }
+        println!();
+    }
+}
 
-        // Tests
-        if !tests.is_empty() {
-            println!(
-                "\n  {} {}",
-                "⚡".yellow(),
-                format!("{} tests affected:", tests.len()).bold()
-            );
-            for t in tests {
+fn impact_json(
+    impact_bounded: &[(&EntityInfo, usize, Vec<String>)],
+    impact_score: &ImpactScore,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) -> serde_json::Value {
+    let impact_entities: Vec<serde_json::Value> = impact_bounded
+        .iter()
+        .map(|(e, d, path)| {
+            let mut v = impacted_json(e, owners);
+            let object = v.as_object_mut().unwrap();
+            object.insert("depth".to_string(), serde_json::json!(d));
+            object.insert("path".to_string(), serde_json::json!(path));
+            v
+        })
+        .collect();
+    serde_json::json!({
+        "depth": depth,
+        "total": impact_bounded.len(),
+        "score": impact_score.score,
+        "heaviest": impact_score.heaviest,
+        "entities": impact_entities,
+    })
+}
+
+/// The transitive-impact block, grouped by depth.
+fn print_impact(
+    graph: &EntityGraph,
+    impact_bounded: &[(&EntityInfo, usize, Vec<String>)],
+    impact_score: &ImpactScore,
+    depth: usize,
+    owners: Option<&OwnerLookup>,
+) {
+    if impact_bounded.is_empty() {
+        println!(
+            "\n  {} {}",
+            "✓".green().bold(),
+            "No other entities are affected by changes to this entity.".dimmed()
+        );
+    } else {
+        let max_depth_seen = impact_bounded.iter().map(|(_, d, _)| *d).max().unwrap_or(0);
+        let depth_label = depth_label(depth);
+        let in_tests = impact_bounded.iter().filter(|(e, _, _)| e.is_test).count();
+        println!(
+            "\n  {} {}",
+            "!".red().bold(),
+            format!(
+                "{} entities transitively affected ({}{}):",
+                impact_bounded.len(),
+                depth_label,
+                in_tests_label(in_tests)
+            )
+            .red(),
+        );
+        print_impact_score(impact_score, |id| {
+            graph.entities.get(id).map(|info| info.name.clone())
+        });
+
+        for d in 1..=max_depth_seen {
+            let at_depth: Vec<_> = impact_bounded
+                .iter()
+                .filter(|(_, dd, _)| *dd == d)
+                .collect();
+            if at_depth.is_empty() {
+                continue;
+            }
+
+            let label = if d == 1 {
+                "Direct dependents".to_string()
+            } else {
+                format!("Depth {}", d)
+            };
+            println!("\n    {} ({})", label.bold(), at_depth.len());
+            for (imp, _, path) in at_depth {
                 println!(
-                    "    {} {} ({})",
-                    t.entity_type.dimmed(),
-                    t.name.bold(),
-                    t.file_path.dimmed(),
+                    "      {} {} {} ({}:L{}){}",
+                    "→".red(),
+                    imp.entity_type.dimmed(),
+                    imp.name.bold(),
+                    imp.file_path.dimmed(),
+                    imp.start_line,
+                    owner_column(imp, owners),
                 );
+                let via = via_line(path, |id| {
+                    graph.entities.get(id).map(|info| info.name.clone())
+                });
+                if let Some(via) = via {
+                    println!("          {}", via.dimmed());
+                }
             }
         }
+    }
+}
 
-        println!();
+fn depth_label(depth: usize) -> String {
+    if depth == 0 {
+        "unlimited".to_string()
+    } else {
+        format!("depth {}", depth)
     }
 }
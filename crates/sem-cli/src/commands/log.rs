@@ -159,7 +159,9 @@ pub fn history_command(opts: HistoryOptions) {
 
     println!(
         "{} {}",
-        "⊕ repo history".green().bold(),
+        format!("{} repo history", crate::formatters::glyph("⊕", "+"))
+            .green()
+            .bold(),
         format!("· last {} commits", analytics.commits_scanned).dimmed()
     );
 
@@ -3,16 +3,22 @@ pub mod cloud;
 pub mod consent;
 pub mod context;
 pub mod diff;
+pub mod duplicates;
 pub mod entities;
 pub mod files;
 pub mod graph;
+pub mod grep;
 pub mod hook;
 pub mod impact;
 pub mod log;
+pub mod orphans;
 pub mod repos;
+pub mod schema;
 pub mod setup;
 pub mod sidecar;
 pub mod stats;
+pub mod verify;
+pub mod watch;
 
 #[cfg(feature = "self-update")]
 pub mod update;
@@ -33,6 +39,9 @@ pub mod update {
     }
 }
 
+use colored::Colorize;
+use sem_core::model::diagnostic::ParseDiagnostic;
+use sem_core::parser::graph::AMBIGUOUS_NAME_TARGETS;
 use sem_core::parser::plugins::create_default_registry;
 use sem_core::parser::registry::ParserRegistry;
 use std::path::{Component, Path, PathBuf};
@@ -45,10 +54,69 @@ pub fn create_registry(cwd: &str) -> ParserRegistry {
     let mut registry = create_default_registry();
     let root = Path::new(cwd);
     registry.load_semrc(root);
+    registry.load_semignore(root);
     registry.load_gitattributes(root);
+    registry.max_file_size = crate::config::max_file_size();
+    registry.ignore_directives = !crate::config::no_ignore();
     registry
 }
 
+/// Warn on stderr about files a graph build skipped (too large, not UTF-8).
+pub fn warn_skipped_files(registry: &ParserRegistry) {
+    report_skipped_files(&registry.take_diagnostics());
+}
+
+/// Print parse diagnostics to stderr. Only files that couldn't be read are
+/// reported, truncated to a few entries, unless `--show-skipped` asks for
+/// every file that produced no entities.
+pub fn report_skipped_files(diagnostics: &[ParseDiagnostic]) {
+    const SHOWN: usize = 5;
+    let show_all = crate::config::show_skipped();
+    let skipped: Vec<&ParseDiagnostic> = diagnostics
+        .iter()
+        .filter(|diagnostic| show_all || diagnostic.is_read_skip())
+        .collect();
+    if skipped.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{} skipped {} file{}:",
+        "warning:".yellow().bold(),
+        skipped.len(),
+        if skipped.len() == 1 { "" } else { "s" }
+    );
+    let shown = if show_all { skipped.len() } else { SHOWN };
+    for diagnostic in skipped.iter().take(shown) {
+        eprintln!("  {} ({})", diagnostic.file_path, diagnostic.reason);
+    }
+    if skipped.len() > shown {
+        eprintln!("  ... and {} more", skipped.len() - shown);
+    }
+}
+
+/// Print the names defined so often that bare cross-file references to them
+/// were left unlinked (`names` from
+/// [`sem_core::parser::graph::EntityGraph::ambiguous_names`]).
+pub fn warn_ambiguous_names(names: &[(String, usize)]) {
+    const SHOWN: usize = 5;
+    if names.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{} {} name{} defined more than {} times; cross-file references to them are not linked:",
+        "warning:".yellow().bold(),
+        names.len(),
+        if names.len() == 1 { " is" } else { "s are" },
+        AMBIGUOUS_NAME_TARGETS
+    );
+    for (name, count) in names.iter().take(SHOWN) {
+        eprintln!("  {name} ({count} definitions)");
+    }
+    if names.len() > SHOWN {
+        eprintln!("  ... and {} more", names.len() - SHOWN);
+    }
+}
+
 pub fn repo_root_or_cwd(cwd: &str) -> PathBuf {
     GitBridge::open(Path::new(cwd))
         .map(|git| git.repo_root().to_path_buf())
@@ -231,6 +299,8 @@ mod tests {
             parent_id: None,
             start_line: 1,
             end_line: 1,
+            is_test: false,
+            is_private: false,
         }
     }
 
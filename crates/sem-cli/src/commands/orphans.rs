@@ -0,0 +1,155 @@
+//! `sem graph --orphans`: functions and methods nothing in the repo depends
+//! on. The graph resolves references by name, so a missing edge is a hint,
+//! not proof; every finding carries a confidence below 1.0.
+
+use std::collections::{HashMap, HashSet};
+
+use sem_core::model::entity::SemanticEntity;
+use sem_core::parser::graph::{EntityGraph, RefType};
+use serde::Serialize;
+
+/// What besides plain unreferenced functions to report.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrphanScope {
+    /// Also report exported/public API, whose callers may live elsewhere.
+    pub include_exported: bool,
+    /// Also report test functions, which a test runner calls.
+    pub include_tests: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanFinding {
+    pub id: String,
+    pub name: String,
+    pub entity_type: String,
+    pub file: String,
+    pub line: usize,
+    pub exported: bool,
+    pub confidence: f32,
+}
+
+/// Names a runtime or framework calls without a reference in the source.
+const ENTRY_POINTS: &[&str] = &["main", "init", "_start", "setup", "teardown"];
+
+/// Languages where calls often go through dynamic dispatch, reflection or
+/// string lookups the graph can't see.
+const DYNAMIC_EXTENSIONS: &[&str] = &[
+    ".py", ".js", ".jsx", ".mjs", ".cjs", ".ts", ".tsx", ".rb", ".php", ".lua",
+];
+
+pub fn find_orphans(
+    graph: &EntityGraph,
+    entities: &[SemanticEntity],
+    test_ids: &HashSet<String>,
+    scope: OrphanScope,
+) -> Vec<OrphanFinding> {
+    let by_id: HashMap<&str, &SemanticEntity> =
+        entities.iter().map(|e| (e.id.as_str(), e)).collect();
+    // Trait impls and overrides are reached through the contract they fulfil.
+    let fulfils_contract: HashSet<&str> = graph
+        .edges
+        .iter()
+        .filter(|edge| matches!(edge.ref_type, RefType::Implements | RefType::Extends))
+        .map(|edge| edge.from_entity.as_str())
+        .collect();
+
+    let mut findings: Vec<OrphanFinding> = entities
+        .iter()
+        .filter(|entity| matches!(entity.entity_type.as_str(), "function" | "method"))
+        .filter(|entity| {
+            graph
+                .dependents
+                .get(&entity.id)
+                .is_none_or(|dependents| dependents.is_empty())
+        })
+        .filter(|entity| !is_entry_point(entity) && !fulfils_contract.contains(entity.id.as_str()))
+        .filter(|entity| scope.include_tests || !test_ids.contains(&entity.id))
+        .filter_map(|entity| {
+            let exported = is_exported(entity, &by_id);
+            if exported && !scope.include_exported {
+                return None;
+            }
+            Some(OrphanFinding {
+                id: entity.id.clone(),
+                name: entity.name.clone(),
+                entity_type: entity.entity_type.clone(),
+                file: entity.file_path.clone(),
+                line: entity.start_line,
+                exported,
+                confidence: orphan_confidence(entity, exported),
+            })
+        })
+        .collect();
+
+    findings.sort_by(|a, b| (&a.file, a.line, &a.name).cmp(&(&b.file, b.line, &b.name)));
+    findings
+}
+
+fn is_entry_point(entity: &SemanticEntity) -> bool {
+    let name = entity.name.as_str();
+    ENTRY_POINTS.contains(&name)
+        || (name.starts_with("__") && name.ends_with("__"))
+        // Decorated functions are usually registered with a framework
+        // (routes, CLI commands, fixtures).
+        || entity
+            .metadata
+            .as_ref()
+            .is_some_and(|metadata| metadata.contains_key("decorators"))
+}
+
+/// Whether the entity is marked as public API: `export`, `pub`, `public`, or
+/// a capitalized Go name. Python and other languages without a marker never
+/// count as exported.
+fn is_exported(entity: &SemanticEntity, by_id: &HashMap<&str, &SemanticEntity>) -> bool {
+    if entity.file_path.ends_with(".go") {
+        return entity.name.starts_with(|c: char| c.is_uppercase());
+    }
+    let header = entity
+        .content
+        .lines()
+        .map(str::trim_start)
+        .find(|line| {
+            !line.is_empty()
+                && !["@", "#[", "//", "/*", "*"]
+                    .iter()
+                    .any(|p| line.starts_with(p))
+        })
+        .unwrap_or("");
+    let marked = header.starts_with("export ")
+        || header.starts_with("pub ")
+        || header.split_whitespace().any(|word| word == "public");
+    // `export` can sit on an enclosing declaration, e.g. `export class`.
+    marked
+        || entity
+            .parent_id
+            .as_deref()
+            .and_then(|parent| by_id.get(parent))
+            .is_some_and(|parent| {
+                parent.content.trim_start().starts_with("export ")
+                    && !header.starts_with("private ")
+                    && !header.starts_with('#')
+            })
+}
+
+/// How likely an unreferenced entity really is dead. Private helpers in
+/// statically resolved languages score highest; public methods in dynamic
+/// languages lowest.
+fn orphan_confidence(entity: &SemanticEntity, exported: bool) -> f32 {
+    let mut confidence: f32 = 0.6;
+    if entity.entity_type == "method" {
+        confidence -= 0.2;
+    }
+    if DYNAMIC_EXTENSIONS
+        .iter()
+        .any(|ext| entity.file_path.ends_with(ext))
+    {
+        confidence -= 0.1;
+    }
+    if exported {
+        confidence -= 0.2;
+    } else if entity.name.starts_with('_') || entity.parent_id.is_none() {
+        confidence += 0.1;
+    }
+    confidence.clamp(0.1, 0.8)
+}
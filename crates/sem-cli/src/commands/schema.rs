@@ -0,0 +1,44 @@
+/// Commands whose `--json` output has a published schema.
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaTarget {
+    Diff,
+    Graph,
+    Impact,
+    Blame,
+}
+
+impl SchemaTarget {
+    /// The JSON Schema (draft 2020-12) for the command's output. The outputs
+    /// are serialized by hand, so these are kept in sync by
+    /// `tests/schema_cli.rs`, which validates real output against them.
+    pub fn schema(self) -> &'static str {
+        match self {
+            SchemaTarget::Diff => include_str!("../../schemas/diff.json"),
+            SchemaTarget::Graph => include_str!("../../schemas/graph.json"),
+            SchemaTarget::Impact => include_str!("../../schemas/impact.json"),
+            SchemaTarget::Blame => include_str!("../../schemas/blame.json"),
+        }
+    }
+}
+
+pub fn run(target: SchemaTarget) {
+    print!("{}", target.schema());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn every_schema_is_a_json_schema_object() {
+        for target in SchemaTarget::value_variants() {
+            let schema: serde_json::Value = serde_json::from_str(target.schema()).unwrap();
+            assert_eq!(
+                schema["$schema"],
+                "https://json-schema.org/draft/2020-12/schema"
+            );
+            assert!(schema["title"].is_string());
+        }
+    }
+}
@@ -5,7 +5,8 @@
 //! skipping this process's cache open + hydrate entirely. The sidecar is an
 //! accelerator, never a requirement: any failure (no socket, no server, slow
 //! reply, protocol mismatch) returns `None` and the caller runs the normal
-//! local path. `SEM_NO_SIDECAR=1` disables it explicitly.
+//! local path. `SEM_NO_SIDECAR=1` disables it explicitly, and so does
+//! `SEM_TIMINGS`: the phases it reports only exist on the local path.
 
 use std::path::Path;
 
@@ -17,7 +18,7 @@ pub fn query(repo_root: &Path, request: &serde_json::Value) -> Option<serde_json
     use std::os::unix::net::UnixStream;
     use std::time::Duration;
 
-    if std::env::var_os("SEM_NO_SIDECAR").is_some() {
+    if std::env::var_os("SEM_NO_SIDECAR").is_some() || crate::timings::Timings::requested() {
         return None;
     }
     let path = sem_mcp::sidecar::socket_path_for(repo_root)?;
@@ -0,0 +1,991 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use colored::Colorize;
+use sem_core::git::bridge::GitBridge;
+use sem_core::model::entity::SemanticEntity;
+use sem_core::parser::graph::{EntityGraph, RefType};
+use sem_core::parser::signature::{self, Signature};
+use serde::Serialize;
+
+/// Findings at or above this confidence fail the command.
+pub const HIGH_CONFIDENCE: f32 = 0.8;
+
+pub struct VerifyOptions {
+    pub cwd: String,
+    pub json: bool,
+    pub file_exts: Vec<String>,
+    pub no_cache: bool,
+    pub no_default_excludes: bool,
+    pub follow_symlinks: bool,
+    /// Also check literal arguments against declared parameter types.
+    pub strict: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyFinding {
+    pub kind: &'static str,
+    pub caller: String,
+    pub caller_id: String,
+    pub callee: String,
+    pub callee_id: String,
+    pub file: String,
+    pub line: usize,
+    /// Accepted argument count (`2`, `1-3`, `2+`), or for a type mismatch
+    /// the declared parameter type.
+    pub expected: String,
+    /// Number of arguments at the call site.
+    pub actual: usize,
+    /// 1-based position of the mistyped argument (type mismatches only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument: Option<usize>,
+    /// What was passed instead, e.g. `string literal` (type mismatches only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub found: Option<&'static str>,
+    pub confidence: f32,
+    pub message: String,
+}
+
+pub fn verify_command(opts: VerifyOptions) {
+    let root = match GitBridge::open(Path::new(&opts.cwd)) {
+        Ok(git) => git.repo_root().to_path_buf(),
+        Err(_) => Path::new(&opts.cwd).to_path_buf(),
+    };
+    let root = root.as_path();
+    let registry = super::create_registry(&root.to_string_lossy());
+    let ext_filter = super::graph::normalize_exts(&opts.file_exts);
+    let source_scope = super::graph::cache_source_scope(
+        root,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
+    let file_paths = super::graph::find_supported_files_with_options(
+        root,
+        &registry,
+        &ext_filter,
+        opts.no_default_excludes,
+        opts.follow_symlinks,
+    );
+
+    let prog = crate::progress::Progress::start_staged();
+    let (graph, all_entities) =
+        super::graph::get_or_build_graph(root, &file_paths, &registry, opts.no_cache, source_scope);
+    prog.done(&format!(
+        "{} entities, {} files",
+        super::graph::fmt_count(graph.entities.len()),
+        super::graph::fmt_count(file_paths.len())
+    ));
+
+    let findings = find_contract_mismatches(&graph, &all_entities, opts.strict);
+    let high_confidence = findings
+        .iter()
+        .filter(|finding| finding.confidence >= HIGH_CONFIDENCE)
+        .count();
+
+    if opts.json {
+        let output = serde_json::json!({
+            "findings": findings,
+            "highConfidence": high_confidence,
+        });
+        println!("{}", serde_json::to_string(&output).unwrap());
+    } else if findings.is_empty() {
+        println!("{} no contract mismatches found", "✓".green());
+    } else {
+        for finding in &findings {
+            let marker = if finding.confidence >= HIGH_CONFIDENCE {
+                "✗".red().bold()
+            } else {
+                "?".yellow()
+            };
+            println!(
+                "{} {}:{}  {} {}",
+                marker,
+                finding.file,
+                finding.line,
+                finding.message,
+                format!("(confidence {:.2})", finding.confidence).dimmed()
+            );
+        }
+        println!(
+            "\n{} findings, {} high confidence",
+            findings.len(),
+            high_confidence
+        );
+    }
+
+    if high_confidence > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Walk `Calls` edges and report call sites whose argument count can't
+/// satisfy the callee's parameter list. With `strict`, calls into Rust,
+/// TypeScript and Java also have literal arguments checked against declared
+/// primitive parameter types.
+pub fn find_contract_mismatches(
+    graph: &EntityGraph,
+    entities: &[SemanticEntity],
+    strict: bool,
+) -> Vec<VerifyFinding> {
+    let by_id: HashMap<&str, &SemanticEntity> =
+        entities.iter().map(|e| (e.id.as_str(), e)).collect();
+    let mut overloads: HashMap<(&str, Option<&str>, &str), usize> = HashMap::new();
+    for entity in entities {
+        *overloads
+            .entry((
+                entity.file_path.as_str(),
+                entity.parent_id.as_deref(),
+                entity.name.as_str(),
+            ))
+            .or_default() += 1;
+    }
+    let mut seen: HashSet<(&str, &str, usize)> = HashSet::new();
+    let mut findings = Vec::new();
+
+    for edge in &graph.edges {
+        if edge.ref_type != RefType::Calls {
+            continue;
+        }
+        let (Some(caller), Some(callee)) = (
+            by_id.get(edge.from_entity.as_str()),
+            by_id.get(edge.to_entity.as_str()),
+        ) else {
+            continue;
+        };
+        if !matches!(callee.entity_type.as_str(), "function" | "method") || encloses(caller, callee)
+        {
+            continue;
+        }
+        let Some((params, arity)) = callee_signature(callee) else {
+            continue;
+        };
+        let owner = callee
+            .parent_id
+            .as_deref()
+            .and_then(|parent| graph.entities.get(parent))
+            .map(|parent| parent.name.as_str());
+        // Type checks need the exact signature: skip overloaded names, and
+        // calls from another language (e.g. JS into a TS module).
+        let typed = TypedLanguage::of(&callee.file_path)
+            .filter(|language| strict && TypedLanguage::of(&caller.file_path) == Some(*language))
+            .filter(|_| {
+                overloads[&(
+                    callee.file_path.as_str(),
+                    callee.parent_id.as_deref(),
+                    callee.name.as_str(),
+                )] == 1
+            });
+        let declared = typed.map(|language| declared_param_types(&params, language));
+
+        for site in caller_call_sites(caller, &callee.name) {
+            if !site_can_reach(site.qualifier.as_deref(), &arity, owner) {
+                continue;
+            }
+            let line = caller.start_line + site.line_offset;
+            if !arity.accepts(site.args) {
+                if !seen.insert((caller.id.as_str(), callee.id.as_str(), line)) {
+                    continue;
+                }
+                let expected = arity.describe();
+                findings.push(VerifyFinding {
+                    kind: "arity_mismatch",
+                    caller: caller.name.clone(),
+                    caller_id: caller.id.clone(),
+                    callee: callee.name.clone(),
+                    callee_id: callee.id.clone(),
+                    file: caller.file_path.clone(),
+                    line,
+                    message: format!(
+                        "{} calls {} with {} argument{}, expected {}",
+                        caller.name,
+                        callee.name,
+                        site.args,
+                        if site.args == 1 { "" } else { "s" },
+                        expected
+                    ),
+                    expected,
+                    actual: site.args,
+                    argument: None,
+                    found: None,
+                    confidence: edge.confidence,
+                });
+                continue;
+            }
+
+            let (Some(language), Some(declared)) = (typed, declared.as_ref()) else {
+                continue;
+            };
+            for (index, (argument, declared_type)) in
+                site.arguments.iter().zip(declared).enumerate()
+            {
+                let Some(declared_type) = declared_type else {
+                    continue;
+                };
+                let (Some(expected_kind), Some(found_kind)) = (
+                    ValueKind::of_type(declared_type, language),
+                    ValueKind::of_literal(argument, language),
+                ) else {
+                    continue;
+                };
+                if expected_kind == found_kind
+                    || !seen.insert((caller.id.as_str(), callee.id.as_str(), line))
+                {
+                    continue;
+                }
+                findings.push(VerifyFinding {
+                    kind: "type_mismatch",
+                    caller: caller.name.clone(),
+                    caller_id: caller.id.clone(),
+                    callee: callee.name.clone(),
+                    callee_id: callee.id.clone(),
+                    file: caller.file_path.clone(),
+                    line,
+                    message: format!(
+                        "{} passes a {} as argument {} of {}, declared `{}`",
+                        caller.name,
+                        found_kind.describe(),
+                        index + 1,
+                        callee.name,
+                        declared_type
+                    ),
+                    expected: declared_type.clone(),
+                    actual: site.args,
+                    argument: Some(index + 1),
+                    found: Some(found_kind.describe()),
+                    confidence: edge.confidence,
+                });
+            }
+        }
+    }
+
+    findings.sort_by(|a, b| (&a.file, a.line, &a.callee).cmp(&(&b.file, b.line, &b.callee)));
+    findings
+}
+
+/// Whether a call written as `qualifier.name(...)` / `qualifier::name(...)`
+/// plausibly binds to the callee the graph resolved it to.
+fn site_can_reach(qualifier: Option<&str>, arity: &Arity, owner: Option<&str>) -> bool {
+    match qualifier {
+        // A bare call can't supply a `self` receiver.
+        None => !arity.has_receiver,
+        Some("self" | "this" | "Self" | "cls" | "super") => true,
+        // `Type.method(instance, ...)` passes the receiver explicitly, and a
+        // different type's associated function only shares the name.
+        Some(qualifier) if qualifier.starts_with(|c: char| c.is_ascii_uppercase()) => {
+            !arity.has_receiver && owner == Some(qualifier)
+        }
+        Some(_) => true,
+    }
+}
+
+fn encloses(outer: &SemanticEntity, inner: &SemanticEntity) -> bool {
+    outer.file_path == inner.file_path
+        && outer.start_line <= inner.start_line
+        && inner.end_line <= outer.end_line
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Arity {
+    min: usize,
+    /// `None` when the callee takes variadic arguments.
+    max: Option<usize>,
+    has_receiver: bool,
+}
+
+impl From<&Signature> for Arity {
+    fn from(signature: &Signature) -> Self {
+        Self {
+            min: signature.min_args(),
+            max: signature.max_args(),
+            has_receiver: signature.has_receiver(),
+        }
+    }
+}
+
+impl Arity {
+    fn accepts(&self, args: usize) -> bool {
+        args >= self.min && self.max.is_none_or(|max| args <= max)
+    }
+
+    fn describe(&self) -> String {
+        match self.max {
+            None => format!("{}+", self.min),
+            Some(max) if max == self.min => max.to_string(),
+            Some(max) => format!("{}-{}", self.min, max),
+        }
+    }
+}
+
+/// The callee's raw parameter list and arity, read from its syntax tree
+/// where the language is supported and from the source text otherwise.
+fn callee_signature(callee: &SemanticEntity) -> Option<(Vec<String>, Arity)> {
+    match signature::parse_signature(&callee.content, &callee.entity_type, &callee.file_path) {
+        Some(parsed) => {
+            let params = parsed.parameters.iter().map(|p| p.text.clone()).collect();
+            Some((params, Arity::from(&parsed)))
+        }
+        None => {
+            let rust = callee.file_path.ends_with(".rs");
+            let params = signature_params(&callee.content, &callee.name, rust)?;
+            let arity = params_arity(&params);
+            Some((params, arity))
+        }
+    }
+}
+
+/// Calls to `name` in the caller's body, from its syntax tree where the
+/// language is supported and from the source text otherwise.
+fn caller_call_sites(caller: &SemanticEntity, name: &str) -> Vec<CallSite> {
+    match signature::call_arguments(&caller.content, &caller.file_path, name) {
+        Some(calls) => calls
+            .into_iter()
+            .map(|call| CallSite {
+                args: call.arguments.len(),
+                arguments: call.arguments,
+                line_offset: call.line_offset,
+                qualifier: call.qualifier,
+            })
+            .collect(),
+        None => call_sites(&caller.content, name, caller.file_path.ends_with(".rs")),
+    }
+}
+
+/// The raw parameter list: the first parenthesised list after `name` in a
+/// function's source.
+fn signature_params(content: &str, name: &str, rust: bool) -> Option<Vec<String>> {
+    let name_at = find_word(content, name)?;
+    let open = name_at + content[name_at..].find('(')?;
+    split_top_level(&content[open..], true, rust)
+}
+
+#[cfg(test)]
+fn parse_signature_arity(content: &str, name: &str, rust: bool) -> Option<Arity> {
+    signature_params(content, name, rust).map(|params| params_arity(&params))
+}
+
+/// Count parameters. Receivers (`self`, `&mut self`, `cls`, `this: T`) are
+/// not counted; defaulted or optional parameters only raise the maximum.
+fn params_arity(params: &[String]) -> Arity {
+    let mut arity = Arity {
+        min: 0,
+        max: Some(0),
+        has_receiver: false,
+    };
+    for (index, param) in params.iter().enumerate() {
+        let param = param.trim();
+        if param.is_empty() || param == "/" || param == "*" {
+            continue;
+        }
+        if index == 0 && is_receiver(param) {
+            arity.has_receiver = true;
+            continue;
+        }
+        if params.len() == 1 && param == "void" {
+            continue;
+        }
+        if param.starts_with('*') || param.contains("...") || param.starts_with("vararg ") {
+            arity.max = None;
+            continue;
+        }
+        arity.max = arity.max.map(|max| max + 1);
+        if !is_optional_param(param) {
+            arity.min += 1;
+        }
+    }
+    arity
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypedLanguage {
+    Rust,
+    TypeScript,
+    Java,
+}
+
+impl TypedLanguage {
+    fn of(file_path: &str) -> Option<Self> {
+        let ext = file_path.rsplit_once('.')?.1;
+        match ext {
+            "rs" => Some(Self::Rust),
+            "ts" | "tsx" | "mts" | "cts" => Some(Self::TypeScript),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
+}
+
+/// Declared type of each positional parameter, receivers excluded, in the
+/// order call arguments bind to them. Varargs end the list.
+fn declared_param_types(params: &[String], language: TypedLanguage) -> Vec<Option<String>> {
+    let mut types = Vec::new();
+    for (index, param) in params.iter().enumerate() {
+        let param = param.trim();
+        if param.is_empty() || (index == 0 && is_receiver(param)) {
+            continue;
+        }
+        if param.contains("...") {
+            break;
+        }
+        let declared = match language {
+            TypedLanguage::Rust => param
+                .split_once(':')
+                .filter(|(pattern, _)| !pattern.contains(['(', '[', '{']))
+                .map(|(_, ty)| ty),
+            TypedLanguage::TypeScript => param.split_once(':').map(|(_, ty)| {
+                ty.split_once('=')
+                    .filter(|(_, rest)| !rest.starts_with('>'))
+                    .map_or(ty, |(ty, _)| ty)
+            }),
+            TypedLanguage::Java => {
+                let words: Vec<&str> = param
+                    .split_whitespace()
+                    .filter(|word| !word.starts_with('@') && *word != "final")
+                    .collect();
+                (words.len() == 2).then(|| words[0])
+            }
+        };
+        types.push(declared.map(|ty| ty.trim().to_string()));
+    }
+    types
+}
+
+/// Coarse value categories that a literal argument and a primitive parameter
+/// type can both be placed in unambiguously.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Text,
+    Number,
+    Bool,
+}
+
+impl ValueKind {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Text => "string literal",
+            Self::Number => "number literal",
+            Self::Bool => "boolean literal",
+        }
+    }
+
+    /// Only plain primitive types map to a kind; generics, unions, aliases
+    /// and everything else are unknown and never flagged.
+    fn of_type(ty: &str, language: TypedLanguage) -> Option<Self> {
+        match language {
+            TypedLanguage::Rust => match ty {
+                "&str" | "String" | "&String" => Some(Self::Text),
+                "bool" => Some(Self::Bool),
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                | "u128" | "usize" | "f32" | "f64" => Some(Self::Number),
+                _ if ty.starts_with("&'") && ty.ends_with(" str") => Some(Self::Text),
+                _ => None,
+            },
+            TypedLanguage::TypeScript => match ty {
+                "string" => Some(Self::Text),
+                "number" => Some(Self::Number),
+                "boolean" => Some(Self::Bool),
+                _ => None,
+            },
+            TypedLanguage::Java => match ty {
+                "String" => Some(Self::Text),
+                "boolean" | "Boolean" => Some(Self::Bool),
+                "int" | "long" | "short" | "byte" | "float" | "double" | "Integer" | "Long"
+                | "Short" | "Byte" | "Float" | "Double" => Some(Self::Number),
+                _ => None,
+            },
+        }
+    }
+
+    /// The kind of an argument that is a single literal and nothing else.
+    fn of_literal(argument: &str, language: TypedLanguage) -> Option<Self> {
+        let argument = argument.trim();
+        let bytes = argument.as_bytes();
+        let rust = language == TypedLanguage::Rust;
+        let whole_literal = |close: Option<usize>| close == Some(bytes.len() - 1);
+        match bytes.first()? {
+            b'"' if whole_literal(find_string_end(bytes, 0, b'"')) => Some(Self::Text),
+            b'\'' | b'`'
+                if language == TypedLanguage::TypeScript
+                    && whole_literal(find_string_end(bytes, 0, bytes[0])) =>
+            {
+                Some(Self::Text)
+            }
+            b'r' if rust && whole_literal(find_rust_raw_string_end(bytes, 0)) => Some(Self::Text),
+            _ if argument == "true" || argument == "false" => Some(Self::Bool),
+            _ => {
+                let digits = argument.strip_prefix('-').unwrap_or(argument);
+                (digits.starts_with(|c: char| c.is_ascii_digit())
+                    && !digits.contains("..")
+                    && digits
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'.'))
+                .then_some(Self::Number)
+            }
+        }
+    }
+}
+
+fn is_receiver(param: &str) -> bool {
+    let head = param.split(':').next().unwrap_or(param).trim();
+    matches!(
+        head,
+        "self" | "&self" | "&mut self" | "mut self" | "cls" | "this"
+    ) || param.starts_with("&'") && param.ends_with("self")
+}
+
+fn is_optional_param(param: &str) -> bool {
+    let bytes = param.as_bytes();
+    let has_default = bytes.iter().enumerate().any(|(i, &b)| {
+        b == b'='
+            && !matches!(bytes.get(i + 1), Some(b'=' | b'>'))
+            && !matches!(
+                i.checked_sub(1).map(|p| bytes[p]),
+                Some(b'=' | b'<' | b'>' | b'!')
+            )
+    });
+    has_default
+        || param
+            .split(':')
+            .next()
+            .is_some_and(|head| head.trim_end().ends_with('?'))
+}
+
+struct CallSite {
+    args: usize,
+    arguments: Vec<String>,
+    line_offset: usize,
+    /// Identifier before a `.` or `::` preceding the callee name, if any.
+    qualifier: Option<String>,
+}
+
+/// Find `name(...)` call expressions in `content`, skipping definitions and
+/// calls whose argument count can't be known statically (spreads, splats).
+fn call_sites(content: &str, name: &str, rust: bool) -> Vec<CallSite> {
+    let mut sites = Vec::new();
+    let bytes = content.as_bytes();
+    let literals = string_literal_spans(bytes, rust);
+    let mut from = 0;
+    while let Some(found) = content[from..].find(name) {
+        let start = from + found;
+        let end = start + name.len();
+        from = end;
+
+        if bytes.get(end) != Some(&b'(') {
+            continue;
+        }
+        if start > 0 && is_ident_byte(bytes[start - 1]) {
+            continue;
+        }
+        if literals
+            .iter()
+            .any(|&(open, close)| open < start && start < close)
+        {
+            continue;
+        }
+        let before = content[..start].trim_end();
+        let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_prefix = content[line_start..start].trim_start();
+        if ["def", "fn", "func", "function", "new"]
+            .iter()
+            .any(|keyword| ends_with_word(before, keyword))
+            || ["//", "#", "*", "--"]
+                .iter()
+                .any(|marker| line_prefix.starts_with(marker))
+        {
+            continue;
+        }
+        let Some(mut arguments) = split_top_level(&content[end..], false, rust) else {
+            continue;
+        };
+        for argument in arguments.iter_mut() {
+            *argument = argument.trim().to_string();
+        }
+        if arguments
+            .iter()
+            .any(|arg| arg.starts_with('*') || arg.starts_with("..."))
+        {
+            continue;
+        }
+        if arguments.len() == 1 && arguments[0].is_empty() {
+            arguments.clear();
+        }
+        let qualifier = before
+            .strip_suffix('.')
+            .or_else(|| before.strip_suffix("::"))
+            .and_then(|head| head.rsplit(|c: char| !is_ident_byte(c as u8)).next())
+            .filter(|qualifier| !qualifier.is_empty())
+            .map(str::to_string);
+
+        sites.push(CallSite {
+            args: arguments.len(),
+            arguments,
+            line_offset: content[..start].matches('\n').count(),
+            qualifier,
+        });
+    }
+    sites
+}
+
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    text.match_indices(word).map(|(at, _)| at).find(|&at| {
+        (at == 0 || !is_ident_byte(bytes[at - 1]))
+            && !bytes
+                .get(at + word.len())
+                .is_some_and(|&b| is_ident_byte(b))
+    })
+}
+
+fn ends_with_word(text: &str, word: &str) -> bool {
+    text.strip_suffix(word)
+        .is_some_and(|rest| !rest.bytes().last().is_some_and(is_ident_byte))
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Split the parenthesised list starting at `text[0] == '('` on top-level
+/// commas. Strings and nested brackets are skipped; `angles` also nests on
+/// `<...>` for generic parameter types. Returns `None` if unbalanced.
+fn split_top_level(text: &str, angles: bool, rust: bool) -> Option<Vec<String>> {
+    let bytes = text.as_bytes();
+    if bytes.first() != Some(&b'(') {
+        return None;
+    }
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut i = 1;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match b {
+            b'"' | b'`' => {
+                let close = find_string_end(bytes, i, b)?;
+                current.push_str(&text[i..=close]);
+                i = close + 1;
+                continue;
+            }
+            b'r' if rust
+                && !is_ident_byte(bytes[i - 1])
+                && matches!(bytes.get(i + 1), Some(b'#' | b'"')) =>
+            {
+                if let Some(close) = find_rust_raw_string_end(bytes, i) {
+                    current.push_str(&text[i..=close]);
+                    i = close + 1;
+                    continue;
+                }
+            }
+            // Closure parameters: `|a, b| a + b`.
+            b'|' if rust && (current.trim().is_empty() || current.trim_end().ends_with("move")) => {
+                let close = i + 1 + text[i + 1..].find('|')?;
+                current.push_str(&text[i..=close]);
+                i = close + 1;
+                continue;
+            }
+            b'\'' if !rust || is_rust_char_literal(bytes, i) => {
+                let close = find_string_end(bytes, i, b)?;
+                current.push_str(&text[i..=close]);
+                i = close + 1;
+                continue;
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b'<' if angles => depth += 1,
+            b'>' if angles && !matches!(bytes[i - 1], b'-' | b'=') => {
+                depth = depth.checked_sub(1)?
+            }
+            b')' | b']' | b'}' => {
+                if depth == 0 {
+                    if b != b')' {
+                        return None;
+                    }
+                    // A trailing comma doesn't start another element.
+                    if !current.trim().is_empty() || parts.is_empty() {
+                        parts.push(current);
+                    }
+                    return Some(parts);
+                }
+                depth -= 1;
+            }
+            b',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let ch_len = text[i..].chars().next().map_or(1, char::len_utf8);
+        current.push_str(&text[i..i + ch_len]);
+        i += ch_len;
+    }
+    None
+}
+
+/// Byte ranges of string literals, so names mentioned inside them aren't
+/// mistaken for calls.
+fn string_literal_spans(bytes: &[u8], rust: bool) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let close = match bytes[i] {
+            b'r' if rust && (i == 0 || !is_ident_byte(bytes[i - 1])) => {
+                find_rust_raw_string_end(bytes, i)
+            }
+            b'"' | b'`' => find_string_end(bytes, i, bytes[i]),
+            b'\'' if !rust || is_rust_char_literal(bytes, i) => find_string_end(bytes, i, b'\''),
+            _ => None,
+        };
+        match close {
+            Some(close) => {
+                spans.push((i, close));
+                i = close + 1;
+            }
+            None => i += 1,
+        }
+    }
+    spans
+}
+
+fn find_string_end(bytes: &[u8], open: usize, quote: u8) -> Option<usize> {
+    let mut i = open + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// End of a raw string literal `r"..."` / `r#"..."#` starting at `open`.
+fn find_rust_raw_string_end(bytes: &[u8], open: usize) -> Option<usize> {
+    let hashes = bytes[open + 1..].iter().take_while(|&&b| b == b'#').count();
+    let quote = open + 1 + hashes;
+    if bytes.get(quote) != Some(&b'"') {
+        return None;
+    }
+    let mut i = quote + 1;
+    while i < bytes.len() {
+        if bytes[i] == b'"'
+            && bytes[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&b| b == b'#')
+                .count()
+                == hashes
+        {
+            return Some(i + hashes);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// `'x'` or `'\n'`, as opposed to a lifetime like `'a`.
+fn is_rust_char_literal(bytes: &[u8], open: usize) -> bool {
+    match bytes.get(open + 1) {
+        Some(b'\\') => true,
+        Some(_) => bytes.get(open + 2) == Some(&b'\''),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sem_core::parser::plugins::create_default_registry;
+    use std::fs;
+
+    fn findings_for(files: &[(&str, &str)]) -> Vec<VerifyFinding> {
+        findings_with(files, false)
+    }
+
+    fn findings_with(files: &[(&str, &str)], strict: bool) -> Vec<VerifyFinding> {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        let registry = create_default_registry();
+        let paths: Vec<String> = files.iter().map(|(name, _)| name.to_string()).collect();
+        let (graph, entities) = EntityGraph::build(dir.path(), &paths, &registry);
+        find_contract_mismatches(&graph, &entities, strict)
+    }
+
+    #[test]
+    fn reports_call_with_too_few_arguments() {
+        let findings = findings_for(&[(
+            "app.py",
+            "def helper(a, b):\n    return a + b\n\n\ndef caller():\n    return helper(1)\n",
+        )]);
+
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        let finding = &findings[0];
+        assert_eq!(finding.caller, "caller");
+        assert_eq!(finding.callee, "helper");
+        assert_eq!(finding.expected, "2");
+        assert_eq!(finding.actual, 1);
+        assert_eq!(finding.line, 6);
+        assert!(finding.confidence >= HIGH_CONFIDENCE);
+    }
+
+    #[test]
+    fn matching_calls_produce_no_findings() {
+        let findings = findings_for(&[(
+            "app.py",
+            "\
+def helper(a, b=2, *rest):
+    return a
+
+class Service:
+    def load(self, key):
+        return key
+
+    def run(self):
+        helper(1)
+        helper(1, 2, 3, 4)
+        return self.load(\"a, b\")
+",
+        )]);
+
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+
+    #[test]
+    fn js_arrow_functions_and_methods_use_real_parameter_counts() {
+        let findings = findings_for(&[(
+            "app.js",
+            "\
+const add = (a, b) => a + b;
+const double = x => x * 2;
+
+class Cart {
+  total(items, tax) {
+    return add(items.length, tax) + double(tax);
+  }
+}
+
+function checkout(cart) {
+  // add(1) in a comment is not a call
+  return cart.total([], 2) + add(1);
+}
+",
+        )]);
+
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(findings[0].caller, "checkout");
+        assert_eq!(findings[0].expected, "2");
+        assert_eq!(findings[0].actual, 1);
+        assert_eq!(findings[0].line, 12);
+    }
+
+    #[test]
+    fn signature_arity_handles_receivers_generics_and_defaults() {
+        let rust = parse_signature_arity(
+            "pub fn merge<K, V>(&mut self, left: HashMap<K, V>, right: &'a str) {}",
+            "merge",
+            true,
+        )
+        .unwrap();
+        assert_eq!((rust.min, rust.max, rust.has_receiver), (2, Some(2), true));
+
+        let ts = parse_signature_arity(
+            "function fetch(url: string, opts?: Options, retries = 3) {}",
+            "fetch",
+            false,
+        )
+        .unwrap();
+        assert_eq!(ts.describe(), "1-3");
+
+        let c = parse_signature_arity("int tick(void) { return 0; }", "tick", false).unwrap();
+        assert_eq!(c.describe(), "0");
+    }
+
+    #[test]
+    fn strict_flags_literals_of_the_wrong_primitive_type() {
+        let rust = "\
+fn resize(width: u32, label: &str, visible: bool) {}
+
+fn caller() {
+    resize(\"wide\", \"ok\", true);
+    resize(10, \"ok\", false);
+}
+";
+        assert!(findings_with(&[("lib.rs", rust)], false).is_empty());
+        let findings = findings_with(&[("lib.rs", rust)], true);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        let finding = &findings[0];
+        assert_eq!(finding.kind, "type_mismatch");
+        assert_eq!(finding.expected, "u32");
+        assert_eq!(finding.argument, Some(1));
+        assert_eq!(finding.found, Some("string literal"));
+        assert_eq!(finding.line, 4);
+
+        let ts = "\
+function retry(count: number, reason?: string) {}
+
+function caller() {
+    retry(3, 'timeout');
+    retry(true);
+}
+";
+        let findings = findings_with(&[("app.ts", ts)], true);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(findings[0].expected, "number");
+        assert_eq!(findings[0].found, Some("boolean literal"));
+
+        let java = "\
+class Greeter {
+    void greet(final String name, int times) {}
+
+    void run() {
+        greet(\"ada\", 2);
+        greet(\"ada\", \"twice\");
+    }
+}
+";
+        let findings = findings_with(&[("Greeter.java", java)], true);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(findings[0].expected, "int");
+        assert_eq!(findings[0].argument, Some(2));
+    }
+
+    #[test]
+    fn strict_ignores_unknown_types_and_overloads() {
+        let findings = findings_with(
+            &[(
+                "Shapes.java",
+                "\
+class Shapes {
+    void area(int side) {}
+    void area(String name) {}
+    void label(Object value, T item) {}
+
+    void run() {
+        area(\"square\");
+        label(\"x\", 3);
+    }
+}
+",
+            )],
+            true,
+        );
+        assert!(findings.is_empty(), "{findings:?}");
+
+        let findings = findings_with(
+            &[(
+                "app.ts",
+                "\
+function show(value: string | number, fallback: any = 0) {}
+
+function caller() {
+    show(1, 'x');
+}
+",
+            )],
+            true,
+        );
+        assert!(findings.is_empty(), "{findings:?}");
+    }
+}
@@ -0,0 +1,426 @@
+//! `sem diff --watch`: keep the working-tree diff on screen and refresh it as
+//! files change.
+//!
+//! Events come from the same OS watcher the MCP server uses. A burst of saves
+//! is debounced into one refresh, and only files whose before/after content
+//! actually changed are re-parsed; every other file keeps its cached per-file
+//! diff. Entities are matched file by file, then the stitched result goes
+//! through the same cross-file move pass as a full recompute, so an entity
+//! moved between files is one move rather than a deletion and an addition.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use sem_core::git::bridge::{GitBridge, GitError};
+use sem_core::git::types::{DiffScope, FileChange};
+use sem_core::parser::differ::{
+    collect_binary_file_changes, compute_semantic_diff_with_leftovers, fold_cross_file_moves,
+    BinaryFileChange, DiffResult, DiffSettings, Leftovers,
+};
+use sem_core::parser::registry::ParserRegistry;
+use sem_mcp::watch::{Drained, RepoWatcher};
+
+use super::diff::{
+    filter_file_exts, filter_ignored, recalculate_diff_summary, render_diff, DiffOptions,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// A source of batched file-change notifications: the OS watcher, or a
+/// scripted fake in tests.
+trait ChangeSource {
+    fn generation(&self) -> u64;
+    fn drain(&self) -> Drained;
+}
+
+impl ChangeSource for RepoWatcher {
+    fn generation(&self) -> u64 {
+        RepoWatcher::generation(self)
+    }
+
+    fn drain(&self) -> Drained {
+        RepoWatcher::drain(self)
+    }
+}
+
+/// Block until the generation moves past `seen`, then until it has been
+/// still for `debounce`, so rapid successive saves become a single refresh.
+fn wait_for_changes(
+    source: &impl ChangeSource,
+    seen: u64,
+    debounce: Duration,
+    poll: Duration,
+) -> Drained {
+    let mut last_generation = seen;
+    let mut last_event = Instant::now();
+    loop {
+        std::thread::sleep(poll);
+        let generation = source.generation();
+        if generation != last_generation {
+            last_generation = generation;
+            last_event = Instant::now();
+        } else if generation != seen && last_event.elapsed() >= debounce {
+            return source.drain();
+        }
+    }
+}
+
+struct FileDiff {
+    input: FileChange,
+    result: DiffResult,
+    /// Deleted and added entities left for the cross-file move pass.
+    leftovers: Leftovers,
+    binary: Vec<BinaryFileChange>,
+}
+
+fn same_input(a: &FileChange, b: &FileChange) -> bool {
+    a.status == b.status
+        && a.old_file_path == b.old_file_path
+        && a.before_content == b.before_content
+        && a.after_content == b.after_content
+}
+
+/// Per-file diffs of the working tree against HEAD, keyed by path.
+#[derive(Default)]
+struct WatchState {
+    files: BTreeMap<String, FileDiff>,
+    settings: DiffSettings,
+}
+
+impl WatchState {
+    /// Fold a fresh listing into the state and return how many files were
+    /// re-diffed. With `touched`, the listing covers only those paths and
+    /// every other entry is kept; without it, the listing is the whole diff.
+    fn apply(
+        &mut self,
+        listing: Vec<FileChange>,
+        touched: Option<&HashSet<String>>,
+        registry: &ParserRegistry,
+    ) -> usize {
+        let listed: HashSet<&str> = listing.iter().map(|f| f.file_path.as_str()).collect();
+        self.files.retain(|path, _| {
+            listed.contains(path.as_str()) || touched.is_some_and(|t| !t.contains(path))
+        });
+
+        let mut recomputed = 0;
+        for file in listing {
+            if let Some(cached) = self.files.get(&file.file_path) {
+                if same_input(&cached.input, &file) {
+                    continue;
+                }
+            }
+            let changes = std::slice::from_ref(&file);
+            let (result, leftovers) =
+                compute_semantic_diff_with_leftovers(changes, registry, None, None, self.settings);
+            let binary = collect_binary_file_changes(changes);
+            self.files.insert(
+                file.file_path.clone(),
+                FileDiff {
+                    input: file,
+                    result,
+                    leftovers,
+                    binary,
+                },
+            );
+            recomputed += 1;
+        }
+        recomputed
+    }
+
+    fn result(&self) -> (DiffResult, Vec<BinaryFileChange>) {
+        let mut result = DiffResult::default();
+        let mut leftovers: Leftovers = (Vec::new(), Vec::new());
+        let mut binary = Vec::new();
+        for file in self.files.values() {
+            result.changes.extend(file.result.changes.iter().cloned());
+            result
+                .unchanged
+                .extend(file.result.unchanged.iter().cloned());
+            result.total_entities_before += file.result.total_entities_before;
+            result.total_entities_after += file.result.total_entities_after;
+            leftovers.0.extend(file.leftovers.0.iter().cloned());
+            leftovers.1.extend(file.leftovers.1.iter().cloned());
+            binary.extend(file.binary.iter().cloned());
+        }
+        fold_cross_file_moves(
+            &mut result.changes,
+            &mut leftovers,
+            self.settings,
+            None,
+            None,
+        );
+        recalculate_diff_summary(&mut result);
+        (result, binary)
+    }
+}
+
+/// Working-tree changes, limited to `touched` (repo-relative) when given.
+fn list_changes(
+    git: &GitBridge,
+    touched: Option<&HashSet<String>>,
+    pathspecs: &[String],
+) -> Result<Vec<FileChange>, GitError> {
+    match touched {
+        Some(paths) if paths.is_empty() => Ok(Vec::new()),
+        Some(paths) => {
+            let specs: Vec<String> = paths
+                .iter()
+                .map(|path| git.repo_root().join(path).to_string_lossy().into_owned())
+                .collect();
+            git.get_changed_files(&DiffScope::Working, &specs)
+        }
+        None => git.get_changed_files(&DiffScope::Working, pathspecs),
+    }
+}
+
+pub(super) fn watch_command(opts: &DiffOptions, pathspecs: &[String]) {
+    let git = match GitBridge::open(Path::new(&opts.cwd)) {
+        Ok(git) => git.with_eol_normalize(crate::config::eol_normalize()),
+        Err(e) => {
+            eprintln!("{}", format!("Error: {e}").red());
+            process::exit(1);
+        }
+    };
+    let registry = opts.registry();
+    let watcher = match RepoWatcher::start(git.repo_root()) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("Error: cannot watch {}: {e}", git.repo_root().display()).red()
+            );
+            process::exit(1);
+        }
+    };
+    let clear_screen = std::io::stdout().is_terminal();
+
+    let mut state = WatchState {
+        settings: opts.diff_settings(),
+        ..WatchState::default()
+    };
+    let mut seen = watcher.generation();
+    let mut touched: Option<HashSet<String>> = None;
+    let mut last_output: Option<String> = None;
+    loop {
+        match list_changes(&git, touched.as_ref(), pathspecs) {
+            Ok(listing) => {
+                let listing =
+                    filter_ignored(filter_file_exts(listing, &opts.file_exts), git.repo_root());
+                state.apply(listing, touched.as_ref(), &registry);
+            }
+            Err(e) => eprintln!("{} {e}", "warning:".yellow().bold()),
+        }
+
+        let (mut result, binary_changes) = state.result();
+        opts.filter_changes(&mut result);
+        let output = render_diff(&result, &binary_changes, opts);
+        if last_output.as_ref() != Some(&output) {
+            if clear_screen {
+                print!("\x1b[2J\x1b[H");
+            }
+            if !output.is_empty() {
+                println!("{output}");
+            }
+            eprintln!("{}", "Watching for changes (Ctrl-C to stop)".dimmed());
+            last_output = Some(output);
+        }
+
+        let drained = wait_for_changes(&watcher, seen, DEBOUNCE, POLL_INTERVAL);
+        seen = drained.generation;
+        // Creates, removes and renames can change which files differ (and
+        // pair up renames), so they re-list everything; cached per-file
+        // diffs still spare the re-parse for files that didn't change.
+        touched = (!drained.needs_rewalk && pathspecs.is_empty())
+            .then(|| drained.changed.into_iter().collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sem_core::model::change::ChangeType;
+    use sem_core::parser::plugins::create_default_registry;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::{fs, process::Command};
+
+    #[derive(Default)]
+    struct FakeSource {
+        generation: AtomicU64,
+        changed: Mutex<Vec<String>>,
+        drains: AtomicU64,
+    }
+
+    impl FakeSource {
+        fn touch(&self, path: &str) {
+            self.changed.lock().unwrap().push(path.to_string());
+            self.generation.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl ChangeSource for FakeSource {
+        fn generation(&self) -> u64 {
+            self.generation.load(Ordering::SeqCst)
+        }
+
+        fn drain(&self) -> Drained {
+            self.drains.fetch_add(1, Ordering::SeqCst);
+            Drained {
+                generation: self.generation(),
+                needs_rewalk: false,
+                changed: std::mem::take(&mut *self.changed.lock().unwrap()),
+            }
+        }
+    }
+
+    #[test]
+    fn rapid_saves_are_debounced_into_one_batch() {
+        let source = Arc::new(FakeSource::default());
+        let writer = {
+            let source = source.clone();
+            std::thread::spawn(move || {
+                for i in 0..5 {
+                    source.touch(&format!("src/file{i}.py"));
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+            })
+        };
+
+        let started = Instant::now();
+        let drained = wait_for_changes(
+            source.as_ref(),
+            0,
+            Duration::from_millis(150),
+            Duration::from_millis(5),
+        );
+        writer.join().unwrap();
+
+        assert_eq!(drained.generation, 5);
+        assert_eq!(drained.changed.len(), 5);
+        assert_eq!(source.drains.load(Ordering::SeqCst), 1);
+        assert!(started.elapsed() >= Duration::from_millis(230));
+    }
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn changes(state: &WatchState) -> Vec<(String, ChangeType)> {
+        let mut changes: Vec<_> = state
+            .result()
+            .0
+            .changes
+            .into_iter()
+            .map(|c| (c.entity_id, c.change_type))
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
+    #[test]
+    fn watch_state_refreshes_only_touched_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        fs::write(repo.join("a.py"), "def a():\n    return 1\n").unwrap();
+        fs::write(repo.join("b.py"), "def b():\n    return 1\n").unwrap();
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+
+        let bridge = GitBridge::open(repo).unwrap();
+        let registry = create_default_registry();
+        let mut state = WatchState::default();
+        let refresh = |state: &mut WatchState, touched: Option<&[&str]>| {
+            let touched: Option<HashSet<String>> =
+                touched.map(|paths| paths.iter().map(|p| p.to_string()).collect());
+            let listing = list_changes(&bridge, touched.as_ref(), &[]).unwrap();
+            state.apply(listing, touched.as_ref(), &registry)
+        };
+
+        fs::write(repo.join("a.py"), "def a():\n    return 2\n").unwrap();
+        fs::write(repo.join("b.py"), "def b():\n    return 2\n").unwrap();
+        assert_eq!(refresh(&mut state, None), 2);
+
+        // Saving a.py again re-diffs a.py only; b.py keeps its cached diff.
+        fs::write(
+            repo.join("a.py"),
+            "def a():\n    return 3\n\ndef a2():\n    pass\n",
+        )
+        .unwrap();
+        assert_eq!(refresh(&mut state, Some(&["a.py"])), 1);
+        assert_eq!(
+            changes(&state),
+            vec![
+                ("a.py::function::a".to_string(), ChangeType::Modified),
+                ("a.py::function::a2".to_string(), ChangeType::Added),
+                ("b.py::function::b".to_string(), ChangeType::Modified),
+            ]
+        );
+
+        // A touch that doesn't change content re-lists but doesn't re-parse.
+        assert_eq!(refresh(&mut state, Some(&["a.py", "b.py"])), 0);
+
+        // Reverting a file drops it from the diff.
+        fs::write(repo.join("b.py"), "def b():\n    return 1\n").unwrap();
+        assert_eq!(refresh(&mut state, Some(&["b.py"])), 0);
+        assert_eq!(changes(&state).len(), 2);
+
+        // Deleting a file shows its entities as deleted.
+        fs::remove_file(repo.join("b.py")).unwrap();
+        assert_eq!(refresh(&mut state, None), 1);
+        assert!(changes(&state).contains(&("b.py::function::b".to_string(), ChangeType::Deleted)));
+    }
+
+    #[test]
+    fn watch_state_reports_a_function_moved_between_files_as_one_move() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        git(repo, &["config", "user.name", "Test"]);
+        fs::write(
+            repo.join("a.py"),
+            "def keep():\n    return 1\n\ndef travel(x):\n    return x * 2\n",
+        )
+        .unwrap();
+        fs::write(repo.join("b.py"), "def other():\n    return 1\n").unwrap();
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+
+        let bridge = GitBridge::open(repo).unwrap();
+        let registry = create_default_registry();
+        let mut state = WatchState::default();
+
+        // The two halves of the move arrive in separate refreshes.
+        fs::write(repo.join("a.py"), "def keep():\n    return 1\n").unwrap();
+        let touched: HashSet<String> = HashSet::from(["a.py".to_string()]);
+        let listing = list_changes(&bridge, Some(&touched), &[]).unwrap();
+        state.apply(listing, Some(&touched), &registry);
+        fs::write(
+            repo.join("b.py"),
+            "def other():\n    return 1\n\ndef travel(x):\n    return x * 2\n",
+        )
+        .unwrap();
+        let touched: HashSet<String> = HashSet::from(["b.py".to_string()]);
+        let listing = list_changes(&bridge, Some(&touched), &[]).unwrap();
+        state.apply(listing, Some(&touched), &registry);
+
+        assert_eq!(
+            changes(&state),
+            vec![("b.py::function::travel".to_string(), ChangeType::Moved)]
+        );
+        assert_eq!(state.result().0.moved_count, 1);
+    }
+}
@@ -0,0 +1,274 @@
+//! Per-repository defaults from `.sem/config.toml`.
+//!
+//! ```toml
+//! file_exts = [".rs", ".ts"]
+//! ignore = ["generated/**", "*.pb.rs"]
+//! min_confidence = 0.5
+//! format = "json"
+//!
+//! [entity_type_names]
+//! impl = "implementation"
+//! ```
+//!
+//! The file only fills in what an invocation left unset; CLI flags always win.
+//! `ignore` globs use gitignore syntax and apply on top of `.semignore`.
+//! `entity_type_names` renames entity types in diff output only; entity ids
+//! and `--only-type`/`--exclude-type` filters keep the canonical names.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
+use serde::Deserialize;
+
+pub const CONFIG_FILE: &str = ".sem/config.toml";
+
+const FORMATS: &[&str] = &["terminal", "plain", "json", "markdown", "md"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SemConfig {
+    pub file_exts: Vec<String>,
+    pub ignore: Vec<String>,
+    pub min_confidence: Option<f32>,
+    pub format: Option<String>,
+    pub entity_type_names: HashMap<String, String>,
+}
+
+static IGNORE_GLOBS: OnceLock<Vec<String>> = OnceLock::new();
+static INCLUDE_GLOBS: OnceLock<Vec<String>> = OnceLock::new();
+static MAX_FILE_SIZE: OnceLock<u64> = OnceLock::new();
+static SHOW_SKIPPED: OnceLock<bool> = OnceLock::new();
+static NO_IGNORE: OnceLock<bool> = OnceLock::new();
+static NO_GLYPHS: OnceLock<bool> = OnceLock::new();
+static EOL_NORMALIZE: OnceLock<bool> = OnceLock::new();
+static ENTITY_TYPE_NAMES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Load `.sem/config.toml` from `root`. A missing file yields the defaults.
+pub fn load(root: &Path) -> Result<SemConfig, String> {
+    let path = root.join(CONFIG_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(SemConfig::default()),
+        Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+    };
+    let config: SemConfig =
+        toml::from_str(&content).map_err(|e| format!("invalid {}: {e}", path.display()))?;
+
+    if let Some(format) = &config.format {
+        if !FORMATS.contains(&format.as_str()) {
+            return Err(format!(
+                "invalid {}: unknown format \"{format}\" (expected one of: {})",
+                path.display(),
+                FORMATS.join(", ")
+            ));
+        }
+    }
+    if config
+        .min_confidence
+        .is_some_and(|c| !(0.0..=1.0).contains(&c))
+    {
+        return Err(format!(
+            "invalid {}: min_confidence must be between 0.0 and 1.0",
+            path.display()
+        ));
+    }
+    if let Some(entity_type) = config
+        .entity_type_names
+        .iter()
+        .find_map(|(entity_type, name)| name.trim().is_empty().then_some(entity_type))
+    {
+        return Err(format!(
+            "invalid {}: entity_type_names.{entity_type} is empty",
+            path.display()
+        ));
+    }
+    ignore_override(root, &config.ignore)
+        .map_err(|e| format!("invalid {}: bad ignore glob: {e}", path.display()))?;
+    Ok(config)
+}
+
+/// Make the config's ignore globs visible to file discovery and diff
+/// filtering for the rest of the process.
+pub fn set_ignore_globs(globs: Vec<String>) {
+    let _ = IGNORE_GLOBS.set(globs);
+}
+
+pub fn ignore_globs() -> &'static [String] {
+    IGNORE_GLOBS.get().map_or(&[], Vec::as_slice)
+}
+
+pub fn set_include_globs(globs: Vec<String>) {
+    let _ = INCLUDE_GLOBS.set(globs);
+}
+
+/// Globs that opt default-excluded paths (`dist/`, `vendor/`, `*.min.js`,
+/// ...) back into repo-wide scans (`--include`).
+pub fn include_globs() -> &'static [String] {
+    INCLUDE_GLOBS.get().map_or(&[], Vec::as_slice)
+}
+
+pub fn set_max_file_size(bytes: u64) {
+    let _ = MAX_FILE_SIZE.set(bytes);
+}
+
+/// Largest file parsed by repo-wide scans (`--max-file-size`).
+pub fn max_file_size() -> u64 {
+    MAX_FILE_SIZE
+        .get()
+        .copied()
+        .unwrap_or(sem_core::utils::source::DEFAULT_MAX_FILE_SIZE)
+}
+
+pub fn set_show_skipped(show: bool) {
+    let _ = SHOW_SKIPPED.set(show);
+}
+
+/// List every file that produced no entities, not just unreadable ones
+/// (`--show-skipped`).
+pub fn show_skipped() -> bool {
+    SHOW_SKIPPED.get().copied().unwrap_or(false)
+}
+
+pub fn set_no_ignore(no_ignore: bool) {
+    let _ = NO_IGNORE.set(no_ignore);
+}
+
+/// Report entities annotated `sem:ignore` like any other (`--no-ignore`).
+pub fn no_ignore() -> bool {
+    NO_IGNORE.get().copied().unwrap_or(false)
+}
+
+pub fn set_no_glyphs(no_glyphs: bool) {
+    let _ = NO_GLYPHS.set(no_glyphs);
+}
+
+/// Mark changes with ASCII instead of Unicode glyphs (`--no-glyphs`).
+pub fn no_glyphs() -> bool {
+    NO_GLYPHS.get().copied().unwrap_or(false)
+}
+
+pub fn set_eol_normalize(enabled: bool) {
+    let _ = EOL_NORMALIZE.set(enabled);
+}
+
+/// Treat CRLF and LF line endings and a leading UTF-8 BOM as the same
+/// content in diffs; off with `--no-eol-normalize`.
+pub fn eol_normalize() -> bool {
+    EOL_NORMALIZE.get().copied().unwrap_or(true)
+}
+
+pub fn set_entity_type_names(names: HashMap<String, String>) {
+    let _ = ENTITY_TYPE_NAMES.set(names);
+}
+
+/// Display names for entity types, from `[entity_type_names]`.
+pub fn entity_type_names() -> Option<&'static HashMap<String, String>> {
+    ENTITY_TYPE_NAMES.get()
+}
+
+/// Matcher for the configured ignore globs, relative to `root`.
+pub fn ignore_matcher(root: &Path) -> Option<Override> {
+    let globs = ignore_globs();
+    if globs.is_empty() {
+        return None;
+    }
+    ignore_override(root, globs).ok()
+}
+
+/// Matcher for the path globs in `root`'s `.semignore`. Its `type:` lines
+/// name entity types rather than paths and are left to the parser registry.
+pub fn semignore_matcher(root: &Path) -> Option<Gitignore> {
+    let content = std::fs::read_to_string(root.join(".semignore")).ok()?;
+    let mut builder = GitignoreBuilder::new(root);
+    for line in content.lines() {
+        if !line.trim_start().starts_with("type:") {
+            builder.add_line(None, line).ok()?;
+        }
+    }
+    builder.build().ok()
+}
+
+fn ignore_override(root: &Path, globs: &[String]) -> Result<Override, ignore::Error> {
+    let mut builder = OverrideBuilder::new(root);
+    for glob in globs {
+        // Overrides whitelist by default; `!` turns a glob into an ignore.
+        builder.add(&format!("!{glob}"))?;
+    }
+    builder.build()
+}
+
+/// Directory whose repository root holds the config for this invocation.
+pub fn repo_root(dir: &Path) -> PathBuf {
+    match sem_core::git::bridge::GitBridge::open(dir) {
+        Ok(git) => git.repo_root().to_path_buf(),
+        Err(_) => dir.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_str(content: &str) -> Result<SemConfig, String> {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sem")).unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE), content).unwrap();
+        load(dir.path())
+    }
+
+    #[test]
+    fn missing_file_is_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = load(dir.path()).unwrap();
+        assert!(config.file_exts.is_empty());
+        assert!(config.format.is_none());
+        assert!(config.entity_type_names.is_empty());
+    }
+
+    #[test]
+    fn parses_all_keys() {
+        let config = load_str(
+            "file_exts = [\".rs\"]\nignore = [\"gen/**\"]\nmin_confidence = 0.5\nformat = \"json\"\n\
+             [entity_type_names]\nimpl = \"implementation\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.file_exts, vec![".rs"]);
+        assert_eq!(config.ignore, vec!["gen/**"]);
+        assert_eq!(config.min_confidence, Some(0.5));
+        assert_eq!(config.format.as_deref(), Some("json"));
+        assert_eq!(
+            config.entity_type_names.get("impl").map(String::as_str),
+            Some("implementation")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_or_invalid_values() {
+        assert!(load_str("file_exts = \".rs\"")
+            .unwrap_err()
+            .contains("invalid"));
+        assert!(load_str("exts = []").unwrap_err().contains("unknown field"));
+        assert!(load_str("format = \"yaml\"")
+            .unwrap_err()
+            .contains("unknown format"));
+        assert!(load_str("min_confidence = 2.0")
+            .unwrap_err()
+            .contains("min_confidence"));
+        assert!(load_str("ignore = [\"a/{b\"]")
+            .unwrap_err()
+            .contains("bad ignore glob"));
+        assert!(load_str("[entity_type_names]\nstruct = \"\"")
+            .unwrap_err()
+            .contains("entity_type_names.struct"));
+    }
+
+    #[test]
+    fn ignore_globs_exclude_matching_paths() {
+        let matcher = ignore_override(Path::new("/repo"), &["gen/**".to_string()]).unwrap();
+        assert!(matcher.matched("gen/a.rs", false).is_ignore());
+        assert!(!matcher.matched("src/a.rs", false).is_ignore());
+    }
+}
@@ -1,7 +1,108 @@
 use sem_core::parser::differ::{BinaryFileChange, DiffResult};
+use serde_json::{json, Value};
+use similar::{Algorithm, ChangeTag, TextDiff};
 
 pub fn format_json(result: &DiffResult, binary_changes: &[BinaryFileChange]) -> String {
-    sem_core::format::json::format_diff_json_with_binary_changes(result, binary_changes)
+    let renames_types = crate::config::entity_type_names().is_some_and(|names| !names.is_empty());
+    if !renames_types {
+        return sem_core::format::json::format_diff_json_with_binary_changes(
+            result,
+            binary_changes,
+        );
+    }
+    let mut value =
+        sem_core::format::json::diff_json_value_with_binary_changes(result, binary_changes);
+    display_entity_types(&mut value);
+    value.to_string()
+}
+
+/// `format_json`, plus a `hunks` array on every change that has both before
+/// and after content: a Myers line diff of the entity, with file line numbers.
+pub fn format_json_with_diff(result: &DiffResult, binary_changes: &[BinaryFileChange]) -> String {
+    let mut value =
+        sem_core::format::json::diff_json_value_with_binary_changes(result, binary_changes);
+    if let Some(changes) = value["changes"].as_array_mut() {
+        for (json, change) in changes.iter_mut().zip(&result.changes) {
+            let (Some(before), Some(after)) = (&change.before_content, &change.after_content)
+            else {
+                continue;
+            };
+            let old_start = change.old_start_line.unwrap_or(change.start_line);
+            json["hunks"] = Value::Array(line_hunks(before, after, old_start, change.start_line));
+        }
+    }
+    display_entity_types(&mut value);
+    value.to_string()
+}
+
+/// Rewrites `entityType` on changes and unchanged entities to its display
+/// name; `entityId` keeps the canonical type.
+fn display_entity_types(value: &mut Value) {
+    for key in ["changes", "unchanged"] {
+        let Some(entries) = value.get_mut(key).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for entry in entries {
+            if let Some(entity_type) = entry["entityType"].as_str() {
+                let display = super::display_entity_type(entity_type).to_string();
+                entry["entityType"] = Value::String(display);
+            }
+        }
+    }
+}
+
+/// A run of consecutive lines with the same diff tag.
+struct LineRun {
+    tag: ChangeTag,
+    old_line: usize,
+    new_line: usize,
+    count: usize,
+    text: String,
+}
+
+impl LineRun {
+    /// The hunk as JSON, with the file line range it covers on the side(s)
+    /// it exists on; the other side's range is null.
+    fn to_json(&self) -> Value {
+        let op = match self.tag {
+            ChangeTag::Equal => "equal",
+            ChangeTag::Delete => "delete",
+            ChangeTag::Insert => "insert",
+        };
+        let old = (self.tag != ChangeTag::Insert).then_some(self.old_line);
+        let new = (self.tag != ChangeTag::Delete).then_some(self.new_line);
+        json!({
+            "op": op,
+            "oldStart": old,
+            "oldEnd": old.map(|line| line + self.count - 1),
+            "newStart": new,
+            "newEnd": new.map(|line| line + self.count - 1),
+            "text": self.text,
+        })
+    }
+}
+
+fn line_hunks(before: &str, after: &str, old_start: usize, new_start: usize) -> Vec<Value> {
+    let diff = TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .diff_lines(before, after);
+    let mut runs: Vec<LineRun> = Vec::new();
+    for change in diff.iter_all_changes() {
+        match runs.last_mut() {
+            Some(run) if run.tag == change.tag() => {
+                run.count += 1;
+                run.text.push_str(change.value());
+            }
+            _ => runs.push(LineRun {
+                tag: change.tag(),
+                old_line: change.old_index().map_or(0, |i| old_start + i),
+                new_line: change.new_index().map_or(0, |i| new_start + i),
+                count: 1,
+                text: change.value().to_string(),
+            }),
+        }
+    }
+    runs.iter().map(LineRun::to_json).collect()
 }
 
 #[cfg(test)]
@@ -81,6 +182,8 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 1,
             total_entities_after: 1,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
 
         let output: serde_json::Value = serde_json::from_str(&format_json(&result, &[])).unwrap();
@@ -92,6 +195,54 @@ mod tests {
         assert_eq!(change["oldEndLine"], 5);
     }
 
+    #[test]
+    fn with_diff_adds_line_hunks_for_a_two_line_modification() {
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[modified_file(
+                "calc.py",
+                "import os\n\ndef total(a, b):\n    x = a + b\n    y = x * 2\n    return y\n",
+                "import os\n\ndef total(a, b):\n    x = a - b\n    y = x * 3\n    return y\n",
+            )],
+            &registry,
+            None,
+            None,
+        );
+
+        let output: serde_json::Value =
+            serde_json::from_str(&format_json_with_diff(&result, &[])).unwrap();
+        let change = &output["changes"][0];
+        assert_eq!(change["entityName"], "total");
+        assert_eq!(
+            change["hunks"],
+            serde_json::json!([
+                {
+                    "op": "equal",
+                    "oldStart": 3, "oldEnd": 3, "newStart": 3, "newEnd": 3,
+                    "text": "def total(a, b):\n",
+                },
+                {
+                    "op": "delete",
+                    "oldStart": 4, "oldEnd": 5, "newStart": null, "newEnd": null,
+                    "text": "    x = a + b\n    y = x * 2\n",
+                },
+                {
+                    "op": "insert",
+                    "oldStart": null, "oldEnd": null, "newStart": 4, "newEnd": 5,
+                    "text": "    x = a - b\n    y = x * 3\n",
+                },
+                {
+                    "op": "equal",
+                    "oldStart": 6, "oldEnd": 6, "newStart": 6, "newEnd": 6,
+                    "text": "    return y",
+                },
+            ])
+        );
+        // Without the flag the schema is unchanged.
+        let plain: serde_json::Value = serde_json::from_str(&format_json(&result, &[])).unwrap();
+        assert!(plain["changes"][0].get("hunks").is_none());
+    }
+
     #[test]
     fn json_includes_binary_changes_in_summary_and_binary_changes() {
         let result = DiffResult {
@@ -106,6 +257,8 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 0,
             total_entities_after: 0,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
         let binary_changes = vec![BinaryFileChange {
             file_path: "pic.png".to_string(),
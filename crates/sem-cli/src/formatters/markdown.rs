@@ -1,10 +1,10 @@
-use super::{estimated_output_capacity, orphan_summary_parts, push_line};
+use super::{estimated_output_capacity, language_summary_parts, orphan_summary_parts, push_line};
 use sem_core::model::change::ChangeType;
 use sem_core::parser::differ::{BinaryFileChange, DiffResult};
 use similar::{ChangeTag, TextDiff};
 use std::collections::BTreeMap;
 
-use super::{binary_display_name, file_count, has_reportable_changes};
+use super::{binary_display_name, display_entity_type, file_count, has_reportable_changes};
 
 fn longest_backtick_run(input: &str) -> usize {
     let mut longest = 0;
@@ -107,7 +107,12 @@ pub fn format_markdown(
             };
             push_line(
                 &mut output,
-                format!("| {} | {} | {} |", status, change.entity_type, name_display),
+                format!(
+                    "| {} | {} | {} |",
+                    status,
+                    display_entity_type(&change.entity_type),
+                    name_display
+                ),
             );
 
             // Show content diff
@@ -236,7 +241,7 @@ pub fn format_markdown(
         parts.push(format!("{} reordered", result.reordered_count));
     }
     if !binary_changes.is_empty() {
-        parts.push(format!("{} binary", binary_changes.len()));
+        parts.push(format!("{} binary (not analyzed)", binary_changes.len()));
     }
 
     let reported_file_count = file_count(result, binary_changes);
@@ -262,6 +267,14 @@ pub fn format_markdown(
         ),
     );
 
+    let language_parts = language_summary_parts(result);
+    if !language_parts.is_empty() {
+        push_line(
+            &mut output,
+            format!("**By language:** {}", language_parts.join("; ")),
+        );
+    }
+
     output
 }
 
@@ -283,6 +296,8 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 1,
             total_entities_after: 1,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 
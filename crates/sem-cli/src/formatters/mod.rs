@@ -1,5 +1,7 @@
 use sem_core::model::change::ChangeType;
 use sem_core::parser::differ::{BinaryFileChange, DiffResult};
+use sem_core::parser::plugins::code::languages::get_language_config;
+use std::collections::HashMap;
 
 pub mod json;
 pub mod markdown;
@@ -47,6 +49,24 @@ pub(crate) fn estimated_output_capacity(
     256 + content_len + result.changes.len() * 160 + binary_changes.len() * 96
 }
 
+/// `unicode`, or its ASCII stand-in under `--no-glyphs` for terminals and
+/// fonts that mangle symbols like `⊕` and `↻`.
+pub(crate) fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if crate::config::no_glyphs() {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+/// How `entity_type` is shown, after any `[entity_type_names]` renaming
+/// from `.sem/config.toml`.
+pub(crate) fn display_entity_type(entity_type: &str) -> &str {
+    crate::config::entity_type_names()
+        .and_then(|names| names.get(entity_type))
+        .map_or(entity_type, String::as_str)
+}
+
 pub(crate) fn push_line(output: &mut String, line: impl AsRef<str>) {
     if !output.is_empty() {
         output.push('\n');
@@ -93,6 +113,112 @@ pub(crate) fn orphan_summary_parts(result: &DiffResult) -> Vec<String> {
     .collect()
 }
 
+/// Display name for the language of `file_path`, from its code language
+/// config when there is one and its extension otherwise.
+pub(crate) fn language_label(file_path: &str) -> String {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    let Some(dot) = file_name.rfind('.').filter(|&dot| dot > 0) else {
+        return "Other".to_string();
+    };
+    let ext = file_name[dot..].to_ascii_lowercase();
+    let id = get_language_config(&ext).map_or(&ext[1..], |config| config.id);
+    let label = match id {
+        "typescript" | "tsx" => "TypeScript",
+        "javascript" => "JavaScript",
+        "python" => "Python",
+        "go" => "Go",
+        "rust" => "Rust",
+        "java" => "Java",
+        "c" | "h" => "C",
+        "cpp" => "C++",
+        "ruby" => "Ruby",
+        "csharp" => "C#",
+        "php" => "PHP",
+        "fortran" => "Fortran",
+        "swift" => "Swift",
+        "elixir" => "Elixir",
+        "bash" | "sh" => "Bash",
+        "hcl" => "HCL",
+        "kotlin" => "Kotlin",
+        "xml" => "XML",
+        "dart" => "Dart",
+        "perl" => "Perl",
+        "sql" => "SQL",
+        "ocaml" | "ocaml_interface" => "OCaml",
+        "scala" => "Scala",
+        "zig" => "Zig",
+        "nix" => "Nix",
+        "haskell" => "Haskell",
+        "elm" => "Elm",
+        "edn" => "EDN",
+        "clojure" => "Clojure",
+        "d" => "D",
+        "lua" => "Lua",
+        "fish" => "Fish",
+        "yaml" | "yml" => "YAML",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "csv" | "tsv" => "CSV",
+        "md" | "markdown" => "Markdown",
+        "tex" => "LaTeX",
+        "svelte" => "Svelte",
+        "vue" => "Vue",
+        "erb" => "ERB",
+        other => return other.to_ascii_uppercase(),
+    };
+    label.to_string()
+}
+
+/// Per-language change counts, e.g. `Rust: 5 modified`, busiest language
+/// first. Empty unless the changes span more than one language.
+pub(crate) fn language_summary_parts(result: &DiffResult) -> Vec<String> {
+    let mut by_language: HashMap<String, [usize; 6]> = HashMap::new();
+    for change in &result.changes {
+        let slot = match change.change_type {
+            ChangeType::Added => 0,
+            ChangeType::Modified => 1,
+            ChangeType::Deleted => 2,
+            ChangeType::Moved => 3,
+            ChangeType::Renamed => 4,
+            ChangeType::Reordered => 5,
+        };
+        by_language
+            .entry(language_label(&change.file_path))
+            .or_default()[slot] += 1;
+    }
+    if by_language.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut languages: Vec<(String, [usize; 6])> = by_language.into_iter().collect();
+    languages.sort_by(|(a_name, a), (b_name, b)| {
+        let a_total: usize = a.iter().sum();
+        let b_total: usize = b.iter().sum();
+        b_total.cmp(&a_total).then_with(|| a_name.cmp(b_name))
+    });
+
+    const LABELS: [&str; 6] = [
+        "added",
+        "modified",
+        "deleted",
+        "moved",
+        "renamed",
+        "reordered",
+    ];
+    languages
+        .into_iter()
+        .map(|(language, counts)| {
+            let counts = counts
+                .iter()
+                .zip(LABELS)
+                .filter(|(count, _)| **count > 0)
+                .map(|(count, label)| format!("{count} {label}"))
+                .collect::<Vec<_>>();
+            format!("{language}: {}", counts.join(", "))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +236,61 @@ mod tests {
         }
     }
 
+    fn added_file(path: &str, after: &str) -> FileChange {
+        FileChange {
+            file_path: path.to_string(),
+            status: FileStatus::Added,
+            old_file_path: None,
+            before_content: None,
+            after_content: Some(after.to_string()),
+        }
+    }
+
+    #[test]
+    fn language_summary_parts_group_changes_by_language() {
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[
+                modified_file(
+                    "src/lib.rs",
+                    "fn a() -> i32 {\n    1\n}\n\nfn b() -> i32 {\n    2\n}\n",
+                    "fn a() -> i32 {\n    10\n}\n\nfn b() -> i32 {\n    20\n}\n",
+                ),
+                added_file("web/app.ts", "export function render() {\n  return 1;\n}\n"),
+                modified_file("config.yml", "name: old\n", "name: new\n"),
+            ],
+            &registry,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            language_summary_parts(&result),
+            vec![
+                "Rust: 2 modified".to_string(),
+                "TypeScript: 1 added".to_string(),
+                "YAML: 1 modified".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn language_summary_parts_empty_for_single_language() {
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[modified_file(
+                "app.py",
+                "def foo():\n    return 1\n",
+                "def foo():\n    return 2\n",
+            )],
+            &registry,
+            None,
+            None,
+        );
+
+        assert!(language_summary_parts(&result).is_empty());
+    }
+
     #[test]
     fn orphan_summary_parts_partition_orphans_by_change_type() {
         let registry = create_default_registry();
@@ -1,10 +1,10 @@
-use super::{estimated_output_capacity, orphan_summary_parts, push_line};
+use super::{estimated_output_capacity, language_summary_parts, orphan_summary_parts, push_line};
 use colored::Colorize;
 use sem_core::model::change::ChangeType;
 use sem_core::parser::differ::{BinaryFileChange, DiffResult};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use super::{binary_display_name, file_count, has_reportable_changes};
+use super::{binary_display_name, display_entity_type, file_count, has_reportable_changes};
 
 pub fn format_plain(result: &DiffResult, binary_changes: &[BinaryFileChange]) -> String {
     if !has_reportable_changes(result, binary_changes) {
@@ -57,7 +57,7 @@ pub fn format_plain(result: &DiffResult, binary_changes: &[BinaryFileChange]) ->
                 ChangeType::Reordered => "O".magenta().to_string(),
             };
 
-            let type_label = format!("{:<12}", change.entity_type);
+            let type_label = format!("{:<12}", display_entity_type(&change.entity_type));
             let name_display = if let Some(ref old_name) = change.old_entity_name {
                 format!("{old_name} -> {}", change.entity_name)
             } else {
@@ -125,7 +125,7 @@ pub fn format_plain(result: &DiffResult, binary_changes: &[BinaryFileChange]) ->
     }
     if !binary_changes.is_empty() {
         parts.push(
-            format!("{} binary", binary_changes.len())
+            format!("{} binary (not analyzed)", binary_changes.len())
                 .yellow()
                 .to_string(),
         );
@@ -155,5 +155,52 @@ pub fn format_plain(result: &DiffResult, binary_changes: &[BinaryFileChange]) ->
         ),
     );
 
+    let language_parts = language_summary_parts(result);
+    if !language_parts.is_empty() {
+        push_line(
+            &mut output,
+            format!("By language: {}", language_parts.join("; "))
+                .dimmed()
+                .to_string(),
+        );
+    }
+
+    output
+}
+
+/// The paths of files with changes, one per line, sorted and deduplicated.
+/// Binary files count: they changed even though they have no entities.
+pub fn format_name_only(result: &DiffResult, binary_changes: &[BinaryFileChange]) -> String {
+    let paths: BTreeSet<&str> = result
+        .changes
+        .iter()
+        .map(|change| change.file_path.as_str())
+        .chain(
+            binary_changes
+                .iter()
+                .map(|change| change.file_path.as_str()),
+        )
+        .collect();
+    let mut output = String::new();
+    for path in paths {
+        push_line(&mut output, path);
+    }
+    output
+}
+
+/// One `entity_id<TAB>change_type` line per change, sorted so the listing
+/// diffs cleanly between runs. Backs both `--entities-only` and
+/// `--entity-ids`; binary file changes have no entity and are omitted.
+pub fn format_entities_only(result: &DiffResult) -> String {
+    let mut lines: Vec<String> = result
+        .changes
+        .iter()
+        .map(|change| format!("{}\t{}", change.entity_id, change.change_type))
+        .collect();
+    lines.sort();
+    let mut output = String::new();
+    for line in lines {
+        push_line(&mut output, line);
+    }
     output
 }
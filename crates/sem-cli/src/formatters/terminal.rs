@@ -1,11 +1,12 @@
-use super::{estimated_output_capacity, orphan_summary_parts, push_line};
+use super::{estimated_output_capacity, language_summary_parts, orphan_summary_parts, push_line};
 use colored::Colorize;
-use sem_core::model::change::{ChangeType, SemanticChange};
+use sem_core::model::change::{ChangeType, SemanticChange, UnchangedEntity};
 use sem_core::parser::differ::{BinaryFileChange, DiffResult};
 use similar::{ChangeTag, TextDiff};
 use std::collections::BTreeMap;
+use std::io::{self, Write};
 
-use super::{binary_display_name, file_count, has_reportable_changes};
+use super::{binary_display_name, display_entity_type, file_count, glyph, has_reportable_changes};
 
 fn sanitize_terminal_text(input: &str) -> String {
     if !input.chars().any(char::is_control) {
@@ -49,9 +50,88 @@ fn render_inline_diff(old_line: &str, new_line: &str) -> (String, String) {
     (del, ins)
 }
 
+/// Line diff of `before` → `after` as unified hunks keeping `radius` lines of
+/// context, with paired changed lines word-highlighted.
+fn push_line_diff(output: &mut String, before: &str, after: &str, radius: usize) {
+    let diff = TextDiff::from_lines(before, after);
+    for hunk in diff.unified_diff().context_radius(radius).iter_hunks() {
+        push_line(
+            output,
+            format!(
+                "{}    {}",
+                "│".dimmed(),
+                format!("{}", hunk.header()).dimmed(),
+            ),
+        );
+        for op in hunk.ops() {
+            let mut deletes: Vec<String> = Vec::new();
+            let mut inserts: Vec<String> = Vec::new();
+
+            for diff_change in diff.iter_changes(op) {
+                let line = sanitize_terminal_text(diff_change.value().trim_end_matches('\n'));
+                match diff_change.tag() {
+                    ChangeTag::Delete => deletes.push(line),
+                    ChangeTag::Insert => inserts.push(line),
+                    ChangeTag::Equal => {
+                        push_line(
+                            output,
+                            format!("{}    {}", "│".dimmed(), format!("  {line}").dimmed()),
+                        );
+                    }
+                }
+            }
+
+            let paired = deletes.len().min(inserts.len());
+            for i in 0..paired {
+                let (del, ins) = render_inline_diff(&deletes[i], &inserts[i]);
+                push_line(output, format!("{}    {} {}", "│".dimmed(), "-".red(), del));
+                push_line(
+                    output,
+                    format!("{}    {} {}", "│".dimmed(), "+".green(), ins),
+                );
+            }
+            for d in &deletes[paired..] {
+                push_line(
+                    output,
+                    format!("{}    {}", "│".dimmed(), format!("- {d}").red()),
+                );
+            }
+            for i in &inserts[paired..] {
+                push_line(
+                    output,
+                    format!("{}    {}", "│".dimmed(), format!("+ {i}").green()),
+                );
+            }
+        }
+    }
+}
+
 /// The colored marker glyph and status tag for a change, e.g. `⊖` / `[deleted]`.
 /// Shared by the per-entity renderer and the consolidated-chunk summary so both
 /// stay in sync.
+fn push_unchanged_row(output: &mut String, entity: &UnchangedEntity) {
+    let name = sanitize_terminal_text(&entity.entity_name);
+    let display_name = match &entity.parent_name {
+        Some(p) => format!("{}::{name}", sanitize_terminal_text(p)),
+        None => name,
+    };
+    push_line(
+        output,
+        format!(
+            "{}  {} {} {} {}",
+            "│".dimmed(),
+            "·".dimmed(),
+            format!(
+                "{:<10}",
+                sanitize_terminal_text(display_entity_type(&entity.entity_type))
+            )
+            .dimmed(),
+            format!("{display_name:<25}").dimmed(),
+            "[unchanged]".dimmed(),
+        ),
+    );
+}
+
 fn change_symbol_and_tag(change: &SemanticChange) -> (String, String) {
     let content_suffix = if change.has_content_change() {
         if change.structural_change == Some(false) {
@@ -62,28 +142,42 @@ fn change_symbol_and_tag(change: &SemanticChange) -> (String, String) {
     } else {
         ""
     };
-    match change.change_type {
-        ChangeType::Added => ("⊕".green().to_string(), "[added]".green().to_string()),
+    let (symbol, tag) = match change.change_type {
+        ChangeType::Added => (
+            glyph("⊕", "+").green().to_string(),
+            "[added]".green().to_string(),
+        ),
         ChangeType::Modified => {
             if change.structural_change == Some(false) {
                 ("~".dimmed().to_string(), "[cosmetic]".dimmed().to_string())
             } else {
-                ("∆".yellow().to_string(), "[modified]".yellow().to_string())
+                (
+                    glyph("∆", "~").yellow().to_string(),
+                    "[modified]".yellow().to_string(),
+                )
             }
         }
-        ChangeType::Deleted => ("⊖".red().to_string(), "[deleted]".red().to_string()),
+        ChangeType::Deleted => (
+            glyph("⊖", "-").red().to_string(),
+            "[deleted]".red().to_string(),
+        ),
         ChangeType::Moved => (
-            "→".blue().to_string(),
+            glyph("→", "->").blue().to_string(),
             format!("[moved{content_suffix}]").blue().to_string(),
         ),
         ChangeType::Renamed => (
-            "↻".cyan().to_string(),
+            glyph("↻", "R").cyan().to_string(),
             format!("[renamed{content_suffix}]").cyan().to_string(),
         ),
         ChangeType::Reordered => (
-            "↕".magenta().to_string(),
+            glyph("↕", "^").magenta().to_string(),
             format!("[reordered{content_suffix}]").magenta().to_string(),
         ),
+    };
+    if change.signature_change == Some(true) {
+        (symbol, format!("{tag} {}", "[api-change]".red().bold()))
+    } else {
+        (symbol, tag)
     }
 }
 
@@ -110,15 +204,41 @@ pub fn format_terminal(
     result: &DiffResult,
     binary_changes: &[BinaryFileChange],
     verbose: bool,
+    limit: Option<usize>,
+    context: Option<usize>,
 ) -> String {
+    let mut buffer = Vec::with_capacity(estimated_output_capacity(result, binary_changes, verbose));
+    write_terminal(&mut buffer, result, binary_changes, verbose, limit, context)
+        .expect("writing to a Vec cannot fail");
+    let mut output = String::from_utf8(buffer).expect("terminal output is UTF-8");
+    // Callers print the report with a trailing newline of their own.
+    output.pop();
+    output
+}
+
+/// Write the terminal report to `out` one file box at a time, so a huge diff
+/// starts printing before the whole report is rendered. `limit` caps the
+/// change rows shown and ends the listing with an "... and N more" footer;
+/// the summary still counts every change. `context` replaces the "both sides
+/// at most 3 lines" content rule for modified entities with a line diff
+/// keeping that many lines of context (0 shows no content); in verbose mode
+/// it sets the hunk context instead of the default 2.
+pub fn write_terminal<W: Write>(
+    out: &mut W,
+    result: &DiffResult,
+    binary_changes: &[BinaryFileChange],
+    verbose: bool,
+    limit: Option<usize>,
+    context: Option<usize>,
+) -> io::Result<()> {
     if !has_reportable_changes(result, binary_changes) {
-        return "No semantic changes detected.".dimmed().to_string();
+        return writeln!(out, "{}", "No semantic changes detected.".dimmed());
     }
 
     let box_width = box_width();
-
-    let mut output =
-        String::with_capacity(estimated_output_capacity(result, binary_changes, verbose));
+    let mut rows = 0usize;
+    let mut shown = 0usize;
+    let at_limit = |rows: usize| limit.is_some_and(|limit| rows >= limit);
 
     // Group changes by file (BTreeMap for sorted output)
     let mut by_file: BTreeMap<&str, (Vec<usize>, Vec<usize>)> = BTreeMap::new();
@@ -128,8 +248,18 @@ pub fn format_terminal(
     for (i, change) in binary_changes.iter().enumerate() {
         by_file.entry(&change.file_path).or_default().1.push(i);
     }
+    let mut unchanged_by_file: BTreeMap<&str, Vec<&UnchangedEntity>> = BTreeMap::new();
+    for entity in &result.unchanged {
+        unchanged_by_file
+            .entry(&entity.file_path)
+            .or_default()
+            .push(entity);
+    }
 
     for (file_path, (indices, binary_indices)) in &by_file {
+        if at_limit(rows) {
+            break;
+        }
         // Skip files where all changes are orphans in non-verbose mode
         if !verbose
             && binary_indices.is_empty()
@@ -140,6 +270,7 @@ pub fn format_terminal(
             continue;
         }
 
+        let mut output = String::new();
         let header = format!("─ {} ", sanitize_terminal_text(file_path));
         let pad_len = box_width.saturating_sub(header.len());
         push_line(
@@ -151,6 +282,11 @@ pub fn format_terminal(
         push_line(&mut output, "│".dimmed().to_string());
 
         for &idx in binary_indices {
+            if at_limit(rows) {
+                break;
+            }
+            rows += 1;
+            shown += 1;
             let change = &binary_changes[idx];
             let symbol = "■".yellow().to_string();
             let tag = format!("[binary {}]", change.status).yellow().to_string();
@@ -170,16 +306,35 @@ pub fn format_terminal(
             );
         }
 
+        // Unchanged entities (with --include-unchanged) are interleaved by line
+        // so the changes show up in the context of the whole file.
+        let mut unchanged = unchanged_by_file
+            .get(file_path)
+            .map(|entities| entities.as_slice())
+            .unwrap_or_default();
+
         let mut cursor = 0usize;
         while cursor < indices.len() {
             let idx = indices[cursor];
             let change = &result.changes[idx];
 
+            while let Some((entity, rest)) = unchanged.split_first() {
+                if entity.start_line >= change.start_line {
+                    break;
+                }
+                push_unchanged_row(&mut output, entity);
+                unchanged = rest;
+            }
+
             // Orphan changes (module-level) only shown in verbose mode
             if change.entity_type == "orphan" && !verbose {
                 cursor += 1;
                 continue;
             }
+            if at_limit(rows) {
+                break;
+            }
+            rows += 1;
 
             // Collapse a run of contiguous, same-type line chunks (how
             // unsupported/binary-ish files are split when no grammar applies)
@@ -203,6 +358,7 @@ pub fn format_terminal(
                 }
                 if run_end - cursor > 1 {
                     let count = run_end - cursor;
+                    shown += count;
                     let first = &result.changes[indices[cursor]];
                     let last = &result.changes[indices[run_end - 1]];
                     let (symbol, tag) = change_symbol_and_tag(change);
@@ -227,9 +383,13 @@ pub fn format_terminal(
                 }
             }
 
+            shown += 1;
             let (symbol, tag) = change_symbol_and_tag(change);
 
-            let type_label = format!("{:<10}", sanitize_terminal_text(&change.entity_type));
+            let type_label = format!(
+                "{:<10}",
+                sanitize_terminal_text(display_entity_type(&change.entity_type))
+            );
             let base_name = if let Some(ref old_name) = change.old_entity_name {
                 format!(
                     "{} -> {}",
@@ -296,79 +456,19 @@ pub fn format_terminal(
                         if let (Some(before), Some(after)) =
                             (&change.before_content, &change.after_content)
                         {
-                            let diff = TextDiff::from_lines(before.as_str(), after.as_str());
-                            for hunk in diff.unified_diff().context_radius(2).iter_hunks() {
-                                push_line(
-                                    &mut output,
-                                    format!(
-                                        "{}    {}",
-                                        "│".dimmed(),
-                                        format!("{}", hunk.header()).dimmed(),
-                                    ),
-                                );
-                                for op in hunk.ops() {
-                                    let mut deletes: Vec<String> = Vec::new();
-                                    let mut inserts: Vec<String> = Vec::new();
-
-                                    for diff_change in diff.iter_changes(op) {
-                                        let line = sanitize_terminal_text(
-                                            diff_change.value().trim_end_matches('\n'),
-                                        );
-                                        match diff_change.tag() {
-                                            ChangeTag::Delete => deletes.push(line),
-                                            ChangeTag::Insert => inserts.push(line),
-                                            ChangeTag::Equal => {
-                                                push_line(
-                                                    &mut output,
-                                                    format!(
-                                                        "{}    {}",
-                                                        "│".dimmed(),
-                                                        format!("  {line}").dimmed(),
-                                                    ),
-                                                );
-                                            }
-                                        }
-                                    }
-
-                                    let paired = deletes.len().min(inserts.len());
-                                    for i in 0..paired {
-                                        let (del, ins) =
-                                            render_inline_diff(&deletes[i], &inserts[i]);
-                                        push_line(
-                                            &mut output,
-                                            format!("{}    {} {}", "│".dimmed(), "-".red(), del),
-                                        );
-                                        push_line(
-                                            &mut output,
-                                            format!("{}    {} {}", "│".dimmed(), "+".green(), ins),
-                                        );
-                                    }
-                                    for d in &deletes[paired..] {
-                                        push_line(
-                                            &mut output,
-                                            format!(
-                                                "{}    {}",
-                                                "│".dimmed(),
-                                                format!("- {d}").red()
-                                            ),
-                                        );
-                                    }
-                                    for i in &inserts[paired..] {
-                                        push_line(
-                                            &mut output,
-                                            format!(
-                                                "{}    {}",
-                                                "│".dimmed(),
-                                                format!("+ {i}").green()
-                                            ),
-                                        );
-                                    }
-                                }
-                            }
+                            push_line_diff(&mut output, before, after, context.unwrap_or(2));
                         }
                     }
                     _ => {}
                 }
+            } else if let Some(radius) = context {
+                if radius > 0 && change.change_type == ChangeType::Modified {
+                    if let (Some(before), Some(after)) =
+                        (&change.before_content, &change.after_content)
+                    {
+                        push_line_diff(&mut output, before, after, radius);
+                    }
+                }
             } else if change.change_type == ChangeType::Modified {
                 if let (Some(before), Some(after)) = (&change.before_content, &change.after_content)
                 {
@@ -421,6 +521,11 @@ pub fn format_terminal(
 
             cursor += 1;
         }
+        if !at_limit(rows) {
+            for entity in unchanged {
+                push_unchanged_row(&mut output, entity);
+            }
+        }
 
         push_line(&mut output, "│".dimmed().to_string());
         push_line(
@@ -428,9 +533,31 @@ pub fn format_terminal(
             format!("└{}", "─".repeat(box_width)).dimmed().to_string(),
         );
         push_line(&mut output, "");
+        writeln!(out, "{output}")?;
+    }
+
+    let listed = binary_changes.len()
+        + result
+            .changes
+            .iter()
+            .filter(|change| verbose || change.entity_type != "orphan")
+            .count();
+    let hidden = listed.saturating_sub(shown);
+    if hidden > 0 {
+        let noun = if hidden == 1 { "change" } else { "changes" };
+        writeln!(
+            out,
+            "{}\n",
+            format!(
+                "... and {hidden} more {noun} (--limit {})",
+                limit.unwrap_or(shown)
+            )
+            .dimmed()
+        )?;
     }
 
     // Summary
+    let mut output = String::new();
     let mut parts: Vec<String> = Vec::new();
     if result.added_count > 0 {
         parts.push(format!("{} added", result.added_count).green().to_string());
@@ -468,7 +595,7 @@ pub fn format_terminal(
     }
     if !binary_changes.is_empty() {
         parts.push(
-            format!("{} binary", binary_changes.len())
+            format!("{} binary (not analyzed)", binary_changes.len())
                 .yellow()
                 .to_string(),
         );
@@ -499,6 +626,16 @@ pub fn format_terminal(
         ),
     );
 
+    let language_parts = language_summary_parts(result);
+    if !language_parts.is_empty() {
+        push_line(
+            &mut output,
+            format!("By language: {}", language_parts.join("; "))
+                .dimmed()
+                .to_string(),
+        );
+    }
+
     // Show noise-filtered line when entities were analyzed
     let entities_analyzed = result
         .total_entities_before
@@ -551,7 +688,7 @@ pub fn format_terminal(
         );
     }
 
-    output
+    writeln!(out, "{output}")
 }
 
 #[cfg(test)]
@@ -607,9 +744,11 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 1,
             total_entities_after: 1,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
 
-        let output = format_terminal(&result, &[], true);
+        let output = format_terminal(&result, &[], true, None, None);
 
         assert!(!output.contains('\u{1b}'), "{output}");
         assert!(output.contains("bad\\u{1b}[31m.txt"), "{output}");
@@ -619,6 +758,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn signature_changes_are_tagged_as_api_changes() {
+        colored::control::set_override(false);
+        let modified = |signature_change: bool| -> SemanticChange {
+            serde_json::from_value(serde_json::json!({
+                "id": "change::lib.rs::function::run",
+                "entityId": "lib.rs::function::run",
+                "changeType": "modified",
+                "entityType": "function",
+                "entityName": "run",
+                "filePath": "lib.rs",
+                "signatureChange": signature_change
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(
+            change_symbol_and_tag(&modified(true)).1,
+            "[modified] [api-change]"
+        );
+        assert_eq!(change_symbol_and_tag(&modified(false)).1, "[modified]");
+    }
+
+    #[test]
+    fn context_shows_only_the_changed_line_and_its_neighbours() {
+        colored::control::set_override(false);
+        let before: String = (1..=10).map(|i| format!("let v{i} = {i};\n")).collect();
+        let after = before.replace("let v5 = 5;", "let v5 = 50;");
+        let change: SemanticChange = serde_json::from_value(serde_json::json!({
+            "id": "change::lib.rs::function::run",
+            "entityId": "lib.rs::function::run",
+            "changeType": "modified",
+            "entityType": "function",
+            "entityName": "run",
+            "filePath": "lib.rs",
+            "beforeContent": before,
+            "afterContent": after
+        }))
+        .unwrap();
+        let result = DiffResult {
+            changes: vec![change],
+            file_count: 1,
+            added_count: 0,
+            modified_count: 1,
+            deleted_count: 0,
+            moved_count: 0,
+            renamed_count: 0,
+            reordered_count: 0,
+            orphan_count: 0,
+            total_entities_before: 1,
+            total_entities_after: 1,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let output = format_terminal(&result, &[], false, None, Some(1));
+        assert!(output.contains("- let v5 = 5;"), "{output}");
+        assert!(output.contains("+ let v5 = 50;"), "{output}");
+        assert!(output.contains("  let v4 = 4;"), "{output}");
+        assert!(output.contains("  let v6 = 6;"), "{output}");
+        assert!(!output.contains("v3"), "{output}");
+        assert!(!output.contains("v7"), "{output}");
+
+        let output = format_terminal(&result, &[], false, None, Some(0));
+        assert!(!output.contains("v5"), "{output}");
+    }
+
     fn deleted_chunk(start: usize, end: usize) -> SemanticChange {
         serde_json::from_value(serde_json::json!({
             "id": format!("change::conf.txt::chunk::{start}"),
@@ -654,9 +860,11 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 3,
             total_entities_after: 0,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
 
-        let output = format_terminal(&result, &[], false);
+        let output = format_terminal(&result, &[], false, None, None);
 
         // Collapsed to a single "3 chunks / lines 1-60" line, not three lines.
         assert!(output.contains("3 chunks"), "{output}");
@@ -680,9 +888,11 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 1,
             total_entities_after: 0,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
 
-        let output = format_terminal(&result, &[], false);
+        let output = format_terminal(&result, &[], false, None, None);
 
         // A lone chunk keeps its own range and is not pluralized.
         assert!(output.contains("lines 1-12"), "{output}");
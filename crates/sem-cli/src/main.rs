@@ -1,8 +1,10 @@
 mod cache;
 mod commands;
+mod config;
 mod formatters;
 mod hyperlinks;
 mod progress;
+mod snapshot;
 mod stats;
 mod telemetry;
 mod timings;
@@ -16,17 +18,98 @@ use colored::control;
 use colored::Colorize;
 use commands::blame::{blame_command, BlameOptions};
 use commands::context::{context_command, ContextOptions};
-use commands::diff::{diff_command, DiffOptions, OutputFormat};
+use commands::diff::{diff_command, DiffOptions, DiskCompare, FailOn, OutputFormat, Similarity};
 use commands::entities::{entities_command, EntitiesOptions};
-use commands::graph::{graph_command, GraphOptions};
+use commands::graph::{graph_command, ClusterBy, GraphOptions};
+use commands::grep::{grep_command, GrepOptions};
 use commands::impact::{impact_command, ImpactMode, ImpactOptions};
 use commands::log::{history_command, log_command, HistoryOptions, LogOptions};
+use commands::orphans::OrphanScope;
+use commands::verify::{verify_command, VerifyOptions};
+use sem_core::git::types::CommitFilter;
+use sem_core::model::identity::DEFAULT_RENAME_THRESHOLD;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "sem", version = env!("CARGO_PKG_VERSION"), about = "Semantic version control")]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Worker threads for parsing and graph building. `--threads 1` runs
+    /// sequentially for reproducible output (also SEM_SINGLE_THREAD=1)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Skip files larger than this in diffs and repo-wide scans, in bytes or
+    /// with a K/M/G suffix [default: 10M]
+    #[arg(long, global = true, value_name = "SIZE", value_parser = parse_byte_size)]
+    max_file_size: Option<u64>,
+
+    /// List files that produced no entities and why (unsupported type,
+    /// parse error, empty, too large, not UTF-8)
+    #[arg(long, global = true)]
+    show_skipped: bool,
+
+    /// Scan paths the built-in denylist skips (dist/, build/, vendor/,
+    /// node_modules/, *.min.js, ...) when they match this glob; repeatable
+    #[arg(long = "include", global = true, value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Report entities annotated with a `sem:ignore` or `sem:ignore-impact`
+    /// comment like any other
+    #[arg(long, global = true)]
+    no_ignore: bool,
+
+    /// Count CRLF vs LF line endings and a leading UTF-8 BOM as changes
+    /// instead of normalizing them away
+    #[arg(long, global = true)]
+    no_eol_normalize: bool,
+
+    /// When to use colors. `auto` colors a terminal unless NO_COLOR is set
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: ColorMode,
+
+    /// Mark changes with ASCII (+ ~ - -> R) instead of Unicode glyphs
+    #[arg(long, global = true)]
+    no_glyphs: bool,
+}
+
+/// `4096`, `512K`, `10M`, `1G` (binary multiples).
+fn parse_byte_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (digits, multiplier) = match value.char_indices().last() {
+        Some((at, 'k' | 'K')) => (&value[..at], 1 << 10),
+        Some((at, 'm' | 'M')) => (&value[..at], 1 << 20),
+        Some((at, 'g' | 'G')) => (&value[..at], 1 << 30),
+        _ => (value, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size '{value}' (expected e.g. 4096, 512K, 10M)"))
+}
+
+/// `START:END`, 1-based and inclusive; an `L` prefix (`L10:L20`) is accepted.
+fn parse_line_range(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid range '{value}' (expected START:END, e.g. 10:20)");
+    let (start, end) = value.split_once(':').ok_or_else(invalid)?;
+    let line = |part: &str| {
+        let part = part.trim();
+        part.strip_prefix(['L', 'l'])
+            .unwrap_or(part)
+            .parse::<usize>()
+            .map_err(|_| invalid())
+    };
+    let (start, end) = (line(start)?, line(end)?);
+    if start == 0 || end == 0 {
+        return Err(format!("invalid range '{value}' (lines start at 1)"));
+    }
+    if start > end {
+        return Err(format!("invalid range '{value}' (start is after end)"));
+    }
+    Ok((start, end))
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -37,6 +120,7 @@ enum ColorMode {
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // parsed once per run
 enum Commands {
     /// Show semantic diff of changes (supports git diff syntax). Untracked files are excluded, matching git behavior.
     Diff {
@@ -68,6 +152,14 @@ enum Commands {
         #[arg(long)]
         to: Option<String>,
 
+        /// Only include range commits whose author (`Name <email>`) contains this
+        #[arg(long, value_name = "NAME")]
+        author: Option<String>,
+
+        /// Only include range commits authored on or after this date (YYYY-MM-DD, optional THH:MM[:SS] and Z/±HH:MM; UTC by default)
+        #[arg(long, value_name = "DATE", value_parser = commands::diff::parse_since)]
+        since: Option<i64>,
+
         /// Read FileChange[] JSON from stdin instead of git
         #[arg(long)]
         stdin: bool,
@@ -76,9 +168,9 @@ enum Commands {
         #[arg(long)]
         patch: bool,
 
-        /// Output format
-        #[arg(long, default_value = "terminal")]
-        format: OutputFormat,
+        /// Output format [default: terminal]
+        #[arg(long)]
+        format: Option<OutputFormat>,
 
         /// Shorthand for --format json
         #[arg(long)]
@@ -100,9 +192,103 @@ enum Commands {
         #[arg(long)]
         no_cosmetics: bool,
 
-        /// When to use colors
-        #[arg(long, default_value = "auto")]
-        color: ColorMode,
+        /// Only show changes to entities of this type, e.g. function (repeatable, comma-separated)
+        #[arg(
+            long = "only-type",
+            alias = "only-types",
+            value_name = "TYPE",
+            value_delimiter = ','
+        )]
+        only_types: Vec<String>,
+
+        /// Hide changes to entities of this type, e.g. variable (repeatable, comma-separated)
+        #[arg(
+            long = "exclude-type",
+            alias = "exclude-types",
+            value_name = "TYPE",
+            value_delimiter = ','
+        )]
+        exclude_types: Vec<String>,
+
+        /// Same as --entities-only: print "<entity_id>\t<change_type>" per change, sorted by entity id
+        #[arg(long, conflicts_with_all = ["name_only", "entities_only"])]
+        entity_ids: bool,
+
+        /// Print only the paths of changed files, one per line (sorted)
+        #[arg(long, conflicts_with = "entities_only")]
+        name_only: bool,
+
+        /// Print "<entity_id>\t<change_type>" per change, sorted by entity id
+        #[arg(long)]
+        entities_only: bool,
+
+        /// With JSON output, add a line-level `hunks` diff to each modified change
+        #[arg(long)]
+        with_diff: bool,
+
+        /// Keep running and redraw the working-tree diff whenever files change
+        #[arg(long)]
+        watch: bool,
+
+        /// Also list the unchanged entities of each changed file, for a full inventory
+        #[arg(long)]
+        include_unchanged: bool,
+
+        /// Similarity used to match renamed or moved entities by content
+        #[arg(long, value_enum, default_value = "jaccard")]
+        similarity: Similarity,
+
+        /// Minimum similarity (0-1] to report a rename or move instead of an add/delete pair
+        #[arg(long, value_name = "SCORE", default_value_t = DEFAULT_RENAME_THRESHOLD, value_parser = commands::diff::parse_rename_threshold)]
+        rename_threshold: f64,
+
+        /// Compare this file (before side) with --file-b, without git; either may be missing
+        #[arg(long, value_name = "PATH", requires = "file_b", conflicts_with_all = ["dir_a", "dir_b", "stdin", "patch", "watch", "staged", "commit", "from", "to"])]
+        file_a: Option<String>,
+
+        /// The after side of --file-a
+        #[arg(long, value_name = "PATH", requires = "file_a")]
+        file_b: Option<String>,
+
+        /// Compare this directory (before side) with --dir-b, file by file, without git
+        #[arg(long, value_name = "DIR", requires = "dir_b", conflicts_with_all = ["stdin", "patch", "watch", "staged", "commit", "from", "to"])]
+        dir_a: Option<String>,
+
+        /// The after side of --dir-a
+        #[arg(long, value_name = "DIR", requires = "dir_a")]
+        dir_b: Option<String>,
+
+        /// Exit with status 1 if any change of this type is found (repeatable, for CI)
+        #[arg(long, value_enum, value_name = "TYPE", conflicts_with = "watch")]
+        fail_on: Vec<FailOn>,
+
+        /// Exit with status 1 if more than N changes are found (for CI)
+        #[arg(long, value_name = "N", conflicts_with = "watch")]
+        fail_on_count: Option<usize>,
+
+        /// Show at most N changes in terminal output; the summary still counts all
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Show a line diff of each modified entity with N lines of context (0 = no content)
+        #[arg(long, value_name = "N")]
+        context: Option<usize>,
+
+        /// Leave out test files and #[cfg(test)] modules
+        #[arg(long)]
+        exclude_tests: bool,
+
+        /// Keep only the public surface: public and exported entities outside private parents
+        #[arg(long)]
+        public_only: bool,
+
+        /// Read the diff backward: swap the before and after sides before matching
+        #[arg(long, conflicts_with = "watch")]
+        reverse: bool,
+
+        /// Only diff the repo-relative files listed one per line in this file (- for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["watch", "stdin", "patch"])]
+        files_from: Option<String>,
 
         /// Run as if started in this directory (like git -C)
         #[arg(short = 'C', long = "cwd")]
@@ -134,6 +320,14 @@ enum Commands {
         #[arg(long)]
         dependents: bool,
 
+        /// Show only the transitive dependents (the impact set), up to --depth
+        #[arg(long, conflicts_with_all = ["deps", "dependents", "tests"])]
+        callers_only: bool,
+
+        /// Show only what the entity depends on, transitively up to --depth
+        #[arg(long, conflicts_with_all = ["deps", "dependents", "tests", "callers_only"])]
+        callees_only: bool,
+
         /// Show affected test entities only
         #[arg(long)]
         tests: bool,
@@ -154,6 +348,19 @@ enum Commands {
         #[arg(long, default_value = "2")]
         depth: usize,
 
+        /// Ignore edges scored below this confidence (0.0-1.0) [default: 0]
+        #[arg(long)]
+        min_confidence: Option<f32>,
+
+        /// Leave out test files and #[cfg(test)] modules. Tests are never
+        /// listed as direct dependents of production code either way
+        #[arg(long, conflicts_with = "tests")]
+        exclude_tests: bool,
+
+        /// Keep only the public surface: public and exported entities outside private parents
+        #[arg(long)]
+        public_only: bool,
+
         /// Skip the SQLite entity cache (rebuild from scratch)
         #[arg(long)]
         no_cache: bool,
@@ -161,6 +368,22 @@ enum Commands {
         /// Include files and directories excluded by default (generated, fixtures, vendor, benchmarks)
         #[arg(long)]
         no_default_excludes: bool,
+
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
+
+        /// Show the last author and commit of each affected entity
+        #[arg(long)]
+        blame: bool,
+
+        /// Only analyze this directory (relative to the repo root); repeatable
+        #[arg(long = "path", value_name = "DIR")]
+        subtrees: Vec<String>,
+
+        /// Only analyze the repo-relative files listed one per line in this file (- for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "subtrees")]
+        files_from: Option<String>,
     },
     /// Show the full entity dependency graph
     Graph {
@@ -180,6 +403,88 @@ enum Commands {
         #[arg(long, num_args = 1..)]
         file_exts: Vec<String>,
 
+        /// Only emit edges scored at or above this confidence (0.0-1.0) [default: 0]
+        #[arg(long)]
+        min_confidence: Option<f32>,
+
+        /// Leave out test files and #[cfg(test)] modules
+        #[arg(long, conflicts_with = "include_tests")]
+        exclude_tests: bool,
+
+        /// Keep only the public surface: public and exported entities outside private parents
+        #[arg(long)]
+        public_only: bool,
+
+        /// Group entities into modules and report edge counts between them
+        #[arg(long, value_enum, value_name = "LEVEL")]
+        cluster_by: Option<ClusterBy>,
+
+        /// Only show this entity (name, "type name" or ID) and what depends on it
+        #[arg(long, value_name = "NAME")]
+        entity: Option<String>,
+
+        /// With --entity, how many hops of dependents to follow (default 2, 0 = unlimited)
+        #[arg(long, default_value = "2", requires = "entity")]
+        depth: usize,
+
+        /// With --entity, show just its direct dependencies and dependents
+        #[arg(long, requires = "entity", conflicts_with = "depth")]
+        neighbors: bool,
+
+        /// List functions and methods nothing depends on (likely dead code)
+        #[arg(long, conflicts_with_all = ["cluster_by", "entity"])]
+        orphans: bool,
+
+        /// With --orphans, also list exported/public API
+        #[arg(long, requires = "orphans")]
+        include_exported: bool,
+
+        /// With --orphans, also list test functions
+        #[arg(long, requires = "orphans")]
+        include_tests: bool,
+
+        /// List groups of structurally identical entities (likely copy-paste)
+        #[arg(long, conflicts_with_all = ["cluster_by", "entity", "orphans"])]
+        duplicates: bool,
+
+        /// Only analyze this directory (relative to the repo root); repeatable
+        #[arg(long = "path", value_name = "DIR")]
+        subtrees: Vec<String>,
+
+        /// Only analyze the repo-relative files listed one per line in this file (- for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "subtrees")]
+        files_from: Option<String>,
+
+        /// Skip the SQLite entity cache (rebuild from scratch)
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Include files and directories excluded by default (generated, fixtures, vendor, benchmarks)
+        #[arg(long)]
+        no_default_excludes: bool,
+
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
+    },
+    /// Check call sites against callee signatures (e.g. argument count mismatches)
+    Verify {
+        /// Repository path (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Output format
+        #[arg(long, value_parser = ["terminal", "json"])]
+        format: Option<String>,
+
+        /// Output as JSON (shorthand for --format json)
+        #[arg(long)]
+        json: bool,
+
+        /// Only include files with these extensions (e.g. --file-exts .py .rs)
+        #[arg(long, num_args = 1..)]
+        file_exts: Vec<String>,
+
         /// Skip the SQLite entity cache (rebuild from scratch)
         #[arg(long)]
         no_cache: bool,
@@ -187,6 +492,14 @@ enum Commands {
         /// Include files and directories excluded by default (generated, fixtures, vendor, benchmarks)
         #[arg(long)]
         no_default_excludes: bool,
+
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
+
+        /// Also flag literal arguments whose type contradicts the declared parameter type (Rust, TypeScript, Java)
+        #[arg(long)]
+        strict: bool,
     },
     /// Show semantic blame — who last modified each entity
     Blame {
@@ -194,6 +507,11 @@ enum Commands {
         #[arg()]
         file: String,
 
+        /// Only blame entities overlapping these lines (1-based, inclusive),
+        /// or the raw lines when no entity does, e.g. `--range 40:55`
+        #[arg(long, value_name = "START:END", value_parser = parse_line_range)]
+        range: Option<(usize, usize)>,
+
         /// Output format
         #[arg(long, value_parser = ["terminal", "json"])]
         format: Option<String>,
@@ -253,6 +571,10 @@ enum Commands {
         #[arg(long)]
         no_default_excludes: bool,
 
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
+
         /// Only include files with these extensions (e.g. --file-exts .ts .tsx)
         #[arg(long, num_args = 1..)]
         file_exts: Vec<String>,
@@ -273,6 +595,43 @@ enum Commands {
         #[arg(long, value_name = "SUBSTRING")]
         text: Option<String>,
     },
+    /// Search entity bodies for a regex; matches are reported per containing entity
+    Grep {
+        /// Regular expression to search for
+        pattern: String,
+
+        /// Files or directories to search (defaults to the whole repository)
+        #[arg(num_args = 0..)]
+        paths: Vec<String>,
+
+        /// Only attribute matches to entities of these kinds (repeatable), e.g. --type function
+        #[arg(long = "type", value_name = "KIND")]
+        kinds: Vec<String>,
+
+        /// Match case-insensitively
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
+        /// Output format
+        #[arg(long, value_parser = ["terminal", "json"])]
+        format: Option<String>,
+
+        /// Output as JSON (shorthand for --format json)
+        #[arg(long)]
+        json: bool,
+
+        /// Only include files with these extensions (e.g. --file-exts .py .rs)
+        #[arg(long, num_args = 1..)]
+        file_exts: Vec<String>,
+
+        /// Include files and directories excluded by default (generated, fixtures, vendor, benchmarks)
+        #[arg(long)]
+        no_default_excludes: bool,
+
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
+    },
     /// Show token-budgeted context for an entity
     Context {
         /// Name of the entity, optionally as "type name"
@@ -314,6 +673,10 @@ enum Commands {
         /// Include files and directories excluded by default (generated, fixtures, vendor, benchmarks)
         #[arg(long)]
         no_default_excludes: bool,
+
+        /// Follow symlinked files and directories during file discovery (skipped by default)
+        #[arg(long)]
+        follow_symlinks: bool,
     },
     /// Show lifetime diff statistics
     Stats,
@@ -365,6 +728,12 @@ enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// Print the JSON Schema of a command's --json output
+    Schema {
+        /// Command whose output to describe
+        #[arg(value_enum)]
+        target: commands::schema::SchemaTarget,
+    },
     /// Update sem to the latest released version
     Update,
     /// Generate shell completions
@@ -420,10 +789,12 @@ fn telemetry_command_name(command: &Option<Commands>) -> Option<&'static str> {
         Some(Commands::Diff { .. }) => "diff",
         Some(Commands::Impact { .. }) => "impact",
         Some(Commands::Graph { .. }) => "graph",
+        Some(Commands::Verify { .. }) => "verify",
         Some(Commands::Blame { .. }) => "blame",
         Some(Commands::Hook { .. }) => "hook",
         Some(Commands::Log { .. }) => "log",
         Some(Commands::Entities { .. }) => "entities",
+        Some(Commands::Grep { .. }) => "grep",
         Some(Commands::Context { .. }) => "context",
         Some(Commands::Stats) => "stats",
         Some(Commands::Mcp { .. }) => "mcp",
@@ -436,6 +807,7 @@ fn telemetry_command_name(command: &Option<Commands>) -> Option<&'static str> {
         Some(Commands::Telemetry { .. }) => "telemetry",
         Some(Commands::Xref { .. }) => "xref",
         Some(Commands::Repos { .. }) => "repos",
+        Some(Commands::Schema { .. }) => "schema",
         Some(Commands::Update) => "update",
         Some(Commands::Completions { .. }) => "completions",
         Some(Commands::TelemetryFlush) | Some(Commands::UpdateCheck) => return None,
@@ -452,6 +824,23 @@ fn resolve_json(format: Option<String>, json: bool) -> bool {
     }
 }
 
+/// Read a `--files-from` list, exiting when it can't be read.
+fn read_files_from(source: Option<String>) -> Option<Vec<String>> {
+    let source = source?;
+    match commands::files::read_file_list(&source) {
+        Ok(files) => Some(files),
+        Err(e) => {
+            eprintln!(
+                "{} cannot read --files-from '{}': {}",
+                "error:".red().bold(),
+                source,
+                e
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
 fn combine_diff_positionals(mut args: Vec<String>, pathspecs: Vec<String>) -> Vec<String> {
     if !pathspecs.is_empty() {
         args.push("--".to_string());
@@ -468,8 +857,152 @@ fn apply_color_mode(mode: ColorMode) {
     }
 }
 
+/// Size rayon's global pool before any parallel work starts. An explicit
+/// `--threads` wins over `SEM_SINGLE_THREAD`; otherwise rayon's default applies.
+fn configure_thread_pool(threads: Option<u16>) {
+    let single_thread = std::env::var("SEM_SINGLE_THREAD").is_ok_and(|v| !v.is_empty() && v != "0");
+    let threads = match threads {
+        Some(n) => usize::from(n),
+        None if single_thread => 1,
+        None => return,
+    };
+    let _ = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global();
+}
+
+/// Directory whose repository `.sem/config.toml` applies to this command, or
+/// `None` for commands that take no configurable options.
+fn config_dir(command: &Option<Commands>) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    match command {
+        Some(Commands::Diff { directory, .. }) => {
+            Some(directory.as_ref().map_or(cwd.clone(), |dir| cwd.join(dir)))
+        }
+        Some(Commands::Graph { path, .. }) | Some(Commands::Verify { path, .. }) => {
+            Some(cwd.join(path))
+        }
+        Some(
+            Commands::Impact { .. }
+            | Commands::Entities { .. }
+            | Commands::Grep { .. }
+            | Commands::Context { .. }
+            | Commands::Blame { .. }
+            | Commands::Log { .. },
+        )
+        | None => Some(cwd),
+        _ => None,
+    }
+}
+
+/// Fill options the invocation left unset from `.sem/config.toml`.
+fn apply_config(command: &mut Option<Commands>, config: &config::SemConfig) {
+    fn fill_format(format: &mut Option<String>, json: bool, config: &config::SemConfig) {
+        if format.is_none() && !json {
+            *format = config
+                .format
+                .clone()
+                .filter(|f| f == "terminal" || f == "json");
+        }
+    }
+    fn fill_exts(file_exts: &mut Vec<String>, config: &config::SemConfig) {
+        if file_exts.is_empty() {
+            file_exts.clone_from(&config.file_exts);
+        }
+    }
+
+    match command {
+        Some(Commands::Diff {
+            format,
+            json,
+            file_exts,
+            ..
+        }) => {
+            if format.is_none() && !*json {
+                *format = config
+                    .format
+                    .as_deref()
+                    .and_then(|f| OutputFormat::from_str(f, true).ok());
+            }
+            fill_exts(file_exts, config);
+        }
+        Some(Commands::Impact {
+            format,
+            json,
+            file_exts,
+            min_confidence,
+            ..
+        })
+        | Some(Commands::Graph {
+            format,
+            json,
+            file_exts,
+            min_confidence,
+            ..
+        }) => {
+            fill_format(format, *json, config);
+            fill_exts(file_exts, config);
+            if min_confidence.is_none() {
+                *min_confidence = config.min_confidence;
+            }
+        }
+        Some(Commands::Verify {
+            format,
+            json,
+            file_exts,
+            ..
+        })
+        | Some(Commands::Entities {
+            format,
+            json,
+            file_exts,
+            ..
+        })
+        | Some(Commands::Grep {
+            format,
+            json,
+            file_exts,
+            ..
+        })
+        | Some(Commands::Context {
+            format,
+            json,
+            file_exts,
+            ..
+        }) => {
+            fill_format(format, *json, config);
+            fill_exts(file_exts, config);
+        }
+        Some(Commands::Blame { format, json, .. }) | Some(Commands::Log { format, json, .. }) => {
+            fill_format(format, *json, config);
+        }
+        _ => {}
+    }
+}
+
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    configure_thread_pool(cli.threads);
+    if let Some(bytes) = cli.max_file_size {
+        config::set_max_file_size(bytes);
+    }
+    config::set_show_skipped(cli.show_skipped);
+    config::set_no_ignore(cli.no_ignore);
+    config::set_eol_normalize(!cli.no_eol_normalize);
+    config::set_no_glyphs(cli.no_glyphs);
+    apply_color_mode(cli.color);
+    config::set_include_globs(std::mem::take(&mut cli.include));
+
+    let config = match config_dir(&cli.command) {
+        Some(dir) => config::load(&config::repo_root(&dir)).unwrap_or_else(|e| {
+            eprintln!("{} {e}", "error:".red().bold());
+            std::process::exit(2);
+        }),
+        None => config::SemConfig::default(),
+    };
+    apply_config(&mut cli.command, &config);
+    config::set_ignore_globs(config.ignore.clone());
+    config::set_entity_type_names(config.entity_type_names.clone());
 
     if let Some(name) = telemetry_command_name(&cli.command) {
         telemetry::record(name);
@@ -485,6 +1018,8 @@ fn main() {
             commit,
             from,
             to,
+            author,
+            since,
             stdin,
             patch,
             verbose,
@@ -493,12 +1028,31 @@ fn main() {
             profile,
             file_exts,
             no_cosmetics,
-            color,
+            only_types,
+            exclude_types,
+            entity_ids,
+            name_only,
+            entities_only,
+            with_diff,
+            watch,
+            include_unchanged,
+            similarity,
+            rename_threshold,
+            file_a,
+            file_b,
+            dir_a,
+            dir_b,
+            fail_on,
+            fail_on_count,
+            limit,
+            context,
+            exclude_tests,
+            public_only,
+            reverse,
+            files_from,
             directory,
             pathspecs,
         }) => {
-            apply_color_mode(color);
-
             let cwd = directory.unwrap_or_else(|| {
                 std::env::current_dir()
                     .unwrap_or_default()
@@ -506,7 +1060,11 @@ fn main() {
                     .to_string()
             });
 
-            let effective_format = if json { OutputFormat::Json } else { format };
+            let effective_format = if json {
+                OutputFormat::Json
+            } else {
+                format.unwrap_or(OutputFormat::Terminal)
+            };
             let args = combine_diff_positionals(args, pathspecs);
 
             diff_command(DiffOptions {
@@ -516,12 +1074,36 @@ fn main() {
                 commit,
                 from,
                 to,
+                commit_filter: CommitFilter { author, since },
                 stdin,
                 patch,
                 verbose,
                 profile,
                 file_exts,
                 no_cosmetics,
+                only_types,
+                exclude_types,
+                files_from: read_files_from(files_from),
+                entity_ids,
+                name_only,
+                entities_only,
+                with_diff,
+                watch,
+                include_unchanged,
+                similarity,
+                rename_threshold,
+                disk_compare: match (file_a, file_b, dir_a, dir_b) {
+                    (Some(a), Some(b), _, _) => Some(DiskCompare::Files { a, b }),
+                    (_, _, Some(a), Some(b)) => Some(DiskCompare::Dirs { a, b }),
+                    _ => None,
+                },
+                fail_on,
+                fail_on_count,
+                limit,
+                context,
+                exclude_tests,
+                public_only,
+                reverse,
                 label,
                 args,
             });
@@ -531,8 +1113,22 @@ fn main() {
             format,
             json,
             file_exts,
+            min_confidence,
+            exclude_tests,
+            public_only,
+            cluster_by,
+            entity,
+            depth,
+            neighbors,
+            orphans,
+            include_exported,
+            include_tests,
+            duplicates,
+            subtrees,
+            files_from,
             no_cache,
             no_default_excludes,
+            follow_symlinks,
         }) => {
             let cwd = if path == "." {
                 std::env::current_dir()
@@ -544,14 +1140,63 @@ fn main() {
             };
 
             graph_command(GraphOptions {
+                cwd,
+                json: resolve_json(format, json),
+                file_exts,
+                min_confidence: min_confidence.unwrap_or(0.0),
+                exclude_tests,
+                public_only,
+                cluster_by,
+                entity,
+                depth,
+                neighbors,
+                orphans: orphans.then_some(OrphanScope {
+                    include_exported,
+                    include_tests,
+                }),
+                duplicates,
+                subtrees,
+                files_from: read_files_from(files_from),
+                no_cache,
+                no_default_excludes,
+                follow_symlinks,
+            });
+        }
+        Some(Commands::Verify {
+            path,
+            format,
+            json,
+            file_exts,
+            no_cache,
+            no_default_excludes,
+            follow_symlinks,
+            strict,
+        }) => {
+            let cwd = if path == "." {
+                std::env::current_dir()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            } else {
+                path
+            };
+
+            verify_command(VerifyOptions {
                 cwd,
                 json: resolve_json(format, json),
                 file_exts,
                 no_cache,
                 no_default_excludes,
+                follow_symlinks,
+                strict,
             });
         }
-        Some(Commands::Blame { file, format, json }) => {
+        Some(Commands::Blame {
+            file,
+            range,
+            format,
+            json,
+        }) => {
             blame_command(BlameOptions {
                 cwd: std::env::current_dir()
                     .unwrap_or_default()
@@ -559,6 +1204,7 @@ fn main() {
                     .to_string(),
                 file_path: file,
                 json: resolve_json(format, json),
+                range,
             });
         }
         Some(Commands::Impact {
@@ -567,18 +1213,31 @@ fn main() {
             file,
             deps,
             dependents,
+            callers_only,
+            callees_only,
             tests,
             format,
             json,
             file_exts,
             depth,
+            min_confidence,
+            exclude_tests,
+            public_only,
             no_cache,
             no_default_excludes,
+            follow_symlinks,
+            blame,
+            subtrees,
+            files_from,
         }) => {
             let mode = if deps {
                 ImpactMode::Deps
             } else if dependents {
                 ImpactMode::Dependents
+            } else if callers_only {
+                ImpactMode::Callers
+            } else if callees_only {
+                ImpactMode::Callees
             } else if tests {
                 ImpactMode::Tests
             } else {
@@ -597,8 +1256,15 @@ fn main() {
                 file_exts,
                 mode,
                 depth,
+                min_confidence: min_confidence.unwrap_or(0.0),
+                exclude_tests,
+                public_only,
                 no_cache,
                 no_default_excludes,
+                follow_symlinks,
+                blame,
+                subtrees,
+                files_from: read_files_from(files_from),
             });
         }
         Some(Commands::Hook { kind }) => {
@@ -641,6 +1307,7 @@ fn main() {
             format,
             json,
             no_default_excludes,
+            follow_symlinks,
             file_exts,
             only_kinds,
             except_kinds,
@@ -654,12 +1321,39 @@ fn main() {
                 paths,
                 json: resolve_json(format, json),
                 no_default_excludes,
+                follow_symlinks,
                 file_exts,
                 only_kinds,
                 except_kinds,
                 text,
             });
         }
+        Some(Commands::Grep {
+            pattern,
+            paths,
+            kinds,
+            ignore_case,
+            format,
+            json,
+            file_exts,
+            no_default_excludes,
+            follow_symlinks,
+        }) => {
+            grep_command(GrepOptions {
+                cwd: std::env::current_dir()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                pattern,
+                paths,
+                kinds,
+                ignore_case,
+                json: resolve_json(format, json),
+                file_exts,
+                no_default_excludes,
+                follow_symlinks,
+            });
+        }
         Some(Commands::Context {
             entity,
             entity_id,
@@ -671,6 +1365,7 @@ fn main() {
             file_exts,
             no_cache,
             no_default_excludes,
+            follow_symlinks,
         }) => {
             context_command(ContextOptions {
                 cwd: std::env::current_dir()
@@ -686,6 +1381,7 @@ fn main() {
                 file_exts,
                 no_cache,
                 no_default_excludes,
+                follow_symlinks,
             });
         }
         Some(Commands::Stats) => {
@@ -767,6 +1463,9 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Some(Commands::Schema { target }) => {
+            commands::schema::run(target);
+        }
         Some(Commands::Update) => {
             if let Err(e) = commands::update::run() {
                 eprintln!("{} {}", "error:".red().bold(), e);
@@ -789,17 +1488,41 @@ fn main() {
                     .unwrap_or_default()
                     .to_string_lossy()
                     .to_string(),
-                format: OutputFormat::Terminal,
+                format: config
+                    .format
+                    .as_deref()
+                    .and_then(|f| OutputFormat::from_str(f, true).ok())
+                    .unwrap_or(OutputFormat::Terminal),
                 staged: false,
                 commit: None,
                 from: None,
                 to: None,
+                commit_filter: CommitFilter::default(),
                 stdin: false,
                 patch: false,
                 verbose: false,
                 profile: false,
-                file_exts: vec![],
+                file_exts: config.file_exts,
                 no_cosmetics: false,
+                only_types: vec![],
+                exclude_types: vec![],
+                files_from: None,
+                entity_ids: false,
+                name_only: false,
+                entities_only: false,
+                with_diff: false,
+                watch: false,
+                include_unchanged: false,
+                similarity: Similarity::default(),
+                rename_threshold: DEFAULT_RENAME_THRESHOLD,
+                disk_compare: None,
+                fail_on: vec![],
+                fail_on_count: None,
+                limit: None,
+                context: None,
+                exclude_tests: false,
+                public_only: false,
+                reverse: false,
                 label: None,
                 args: vec![],
             });
@@ -857,12 +1580,24 @@ mod tests {
             } => {
                 assert_eq!(args, ["a.ts", "b.ts"]);
                 assert!(pathspecs.is_empty());
-                assert!(matches!(format, OutputFormat::Json));
+                assert!(matches!(format, Some(OutputFormat::Json)));
             }
             _ => panic!("expected diff command"),
         }
     }
 
+    #[test]
+    fn diff_similarity_defaults_to_jaccard() {
+        match parse_command(&["sem", "diff"]) {
+            Commands::Diff { similarity, .. } => assert!(similarity == Similarity::Jaccard),
+            _ => panic!("expected diff command"),
+        }
+        match parse_command(&["sem", "diff", "--similarity", "token-sort"]) {
+            Commands::Diff { similarity, .. } => assert!(similarity == Similarity::TokenSort),
+            _ => panic!("expected diff command"),
+        }
+    }
+
     #[test]
     fn diff_keeps_pathspecs_after_separator_distinct() {
         match parse_command(&[
@@ -890,4 +1625,18 @@ mod tests {
             _ => panic!("expected diff command"),
         }
     }
+
+    #[test]
+    fn blame_range_parses_and_rejects_bad_ranges() {
+        match parse_command(&["sem", "blame", "a.py", "--range", "L3:L7"]) {
+            Commands::Blame { range, .. } => assert_eq!(range, Some((3, 7))),
+            _ => panic!("expected blame command"),
+        }
+        for bad in ["0:4", "7:3", "5", "a:b"] {
+            assert!(
+                Cli::try_parse_from(["sem", "blame", "a.py", "--range", bad]).is_err(),
+                "{bad} should be rejected"
+            );
+        }
+    }
 }
@@ -0,0 +1,389 @@
+//! Whole-graph snapshot persisted between runs.
+//!
+//! `graph.json` sits next to the SQLite cache (outside the repo, see
+//! `sem_mcp::cache::cache_dir_for_repo`) and holds the topology — entities and
+//! edges — plus the git HEAD it was built at. The next run feeds the
+//! git-detected delta since that HEAD to [`EntityGraph::update_from_changes`]
+//! instead of rebuilding. Anything uncertain (a missing, corrupt, or
+//! other-version file, a changed `.semrc`/`.gitattributes`/`.semignore` or
+//! registry setting, a HEAD that no longer resolves, a root that isn't a git
+//! repo) is a miss, and the caller falls back to a full build.
+//!
+//! Replayed files are resolved the way `update_from_changes` resolves them,
+//! by name and import, so an edge only a full build's scope pass finds can be
+//! missing until the next full build.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use sem_core::git::types::{FileChange, FileStatus};
+use sem_core::parser::graph::{EntityGraph, EntityInfo, EntityInfoMap, EntityRef};
+use sem_core::parser::registry::ParserRegistry;
+use sem_mcp::cache as shared_cache;
+use serde::{Deserialize, Serialize};
+
+const SNAPSHOT_FILE: &str = "graph.json";
+
+#[derive(Serialize, Deserialize)]
+struct GraphSnapshot {
+    /// sem release that wrote it; extraction can change between releases.
+    version: String,
+    schema: i32,
+    /// [`config_key`] of the repo and registry the graph was built with.
+    config: u64,
+    /// HEAD commit the graph was built at.
+    head: String,
+    /// Paths that differed from `head` in the working tree at save time. The
+    /// graph holds their working-tree state, so they are replayed even when
+    /// the next delta no longer lists them (e.g. the edit was reverted).
+    dirty_files: Vec<String>,
+    /// The file set the graph was built from.
+    files: Vec<String>,
+    entities: Vec<EntityInfo>,
+    edges: Vec<EntityRef>,
+}
+
+fn snapshot_path(root: &Path) -> Option<PathBuf> {
+    Some(shared_cache::cache_dir_for_repo(root)?.join(SNAPSHOT_FILE))
+}
+
+/// Hash of everything besides the sources that shapes the graph: the
+/// manifest files the SQLite cache also tracks, and the registry settings
+/// loaded from them or from flags.
+fn config_key(root: &Path, registry: &ParserRegistry) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for (file_name, _) in shared_cache::CACHE_MANIFEST_FILES {
+        file_name.hash(&mut hasher);
+        std::fs::read(root.join(file_name)).ok().hash(&mut hasher);
+    }
+    registry.custom_test_dirs.hash(&mut hasher);
+    registry.max_file_size.hash(&mut hasher);
+    registry.ignore_directives.hash(&mut hasher);
+    registry.ignored_entity_types.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The stored graph brought up to date with the working tree, and whether
+/// that took any changes (so the snapshot is worth saving again), or `None`
+/// when there is no usable snapshot.
+pub fn load_updated(
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+) -> Option<(EntityGraph, bool)> {
+    load_updated_from(&snapshot_path(root)?, root, file_paths, registry)
+}
+
+/// Persist `graph` as the snapshot for `root`. Best-effort: a failed write
+/// only costs the next run a rebuild.
+pub fn save(root: &Path, file_paths: &[String], registry: &ParserRegistry, graph: &EntityGraph) {
+    if let Some(path) = snapshot_path(root) {
+        let _ = save_to(&path, root, file_paths, registry, graph);
+    }
+}
+
+fn load_updated_from(
+    path: &Path,
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+) -> Option<(EntityGraph, bool)> {
+    let bytes = std::fs::read(path).ok()?;
+    let snapshot: GraphSnapshot = serde_json::from_slice(&bytes).ok()?;
+    if snapshot.version != env!("CARGO_PKG_VERSION")
+        || snapshot.schema != shared_cache::CACHE_SCHEMA_VERSION
+        || snapshot.config != config_key(root, registry)
+    {
+        return None;
+    }
+
+    let repo = git2::Repository::open(root).ok()?;
+    let mut touched = changed_since(&repo, &snapshot.head)?;
+    touched.extend(snapshot.dirty_files);
+
+    // Files entering or leaving the walked set count as added or deleted
+    // even when git saw no change to them; changes outside both sets don't
+    // matter.
+    let stored: HashSet<String> = snapshot.files.into_iter().collect();
+    let current: HashSet<&str> = file_paths.iter().map(String::as_str).collect();
+    touched.extend(
+        stored
+            .iter()
+            .filter(|file| !current.contains(file.as_str()))
+            .cloned(),
+    );
+    touched.extend(
+        file_paths
+            .iter()
+            .filter(|file| !stored.contains(*file))
+            .cloned(),
+    );
+    let mut changes: Vec<FileChange> = touched
+        .into_iter()
+        .filter_map(|file_path| {
+            let status = match (
+                stored.contains(&file_path),
+                current.contains(file_path.as_str()),
+            ) {
+                (true, true) => FileStatus::Modified,
+                (true, false) => FileStatus::Deleted,
+                (false, true) => FileStatus::Added,
+                (false, false) => return None,
+            };
+            Some(FileChange {
+                file_path,
+                status,
+                old_file_path: None,
+                before_content: None,
+                after_content: None,
+            })
+        })
+        .collect();
+    changes.sort_by(|left, right| left.file_path.cmp(&right.file_path));
+
+    let entities: EntityInfoMap = snapshot
+        .entities
+        .into_iter()
+        .map(|entity| (entity.id.clone(), entity))
+        .collect();
+    let mut graph = EntityGraph::from_parts(entities, snapshot.edges);
+    if changes.is_empty() {
+        return Some((graph, false));
+    }
+    graph.update_from_changes(&changes, root, registry);
+    Some((graph, true))
+}
+
+fn save_to(
+    path: &Path,
+    root: &Path,
+    file_paths: &[String],
+    registry: &ParserRegistry,
+    graph: &EntityGraph,
+) -> Option<()> {
+    let repo = git2::Repository::open(root).ok()?;
+    let head = repo.head().ok()?.target()?.to_string();
+    let mut dirty_files: Vec<String> = changed_since(&repo, &head)?.into_iter().collect();
+    dirty_files.sort_unstable();
+
+    let snapshot = GraphSnapshot {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema: shared_cache::CACHE_SCHEMA_VERSION,
+        config: config_key(root, registry),
+        head,
+        dirty_files,
+        files: file_paths.to_vec(),
+        entities: graph.entities.values().cloned().collect(),
+        edges: graph.edges.clone(),
+    };
+    let bytes = serde_json::to_vec(&snapshot).ok()?;
+
+    // Write then rename, so a concurrent reader never sees a partial file.
+    std::fs::create_dir_all(path.parent()?).ok()?;
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    std::fs::write(&tmp, bytes).ok()?;
+    if std::fs::rename(&tmp, path).is_err() {
+        let _ = std::fs::remove_file(&tmp);
+        return None;
+    }
+    Some(())
+}
+
+/// Paths whose working-tree state (index and untracked files included)
+/// differs from the tree of commit `rev`. `None` when `rev` doesn't resolve.
+fn changed_since(repo: &git2::Repository, rev: &str) -> Option<HashSet<String>> {
+    let tree = repo.revparse_single(rev).ok()?.peel_to_tree().ok()?;
+    let mut opts = git2::DiffOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut opts))
+        .ok()?;
+    Some(
+        diff.deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .filter_map(|path| path.to_str())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sem_core::parser::plugins::create_default_registry;
+    use std::process::Command;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(repo)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn topology(graph: &EntityGraph) -> (Vec<String>, Vec<(String, String, String)>) {
+        let mut ids: Vec<String> = graph.entities.keys().cloned().collect();
+        ids.sort();
+        let mut edges: Vec<(String, String, String)> = graph
+            .edges
+            .iter()
+            .map(|edge| {
+                (
+                    edge.from_entity.clone(),
+                    edge.to_entity.clone(),
+                    format!("{:?}", edge.ref_type),
+                )
+            })
+            .collect();
+        edges.sort();
+        (ids, edges)
+    }
+
+    /// A repo with a TS import chain and a self-contained Rust file, committed.
+    fn init_repo(root: &Path) -> Vec<String> {
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.email", "t@t.com"]);
+        git(root, &["config", "user.name", "test"]);
+        git(root, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(
+            root.join("a.ts"),
+            "export function source() { return 1; }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("b.ts"),
+            "import { source } from './a';\nexport function consume() { return source(); }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("c.ts"),
+            "import { consume } from './b';\nexport function top() { return consume(); }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("d.rs"),
+            "struct Counter;\nimpl Counter {\n    fn create() -> Counter { Counter }\n    fn tick(&self) -> u32 { 1 }\n}\nfn run() -> u32 { 0 }\n",
+        )
+        .unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "init"]);
+        vec!["a.ts".into(), "b.ts".into(), "c.ts".into(), "d.rs".into()]
+    }
+
+    #[test]
+    fn reload_and_update_matches_fresh_build() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        let root = repo.path();
+        let path = cache.path().join(SNAPSHOT_FILE);
+        let registry = create_default_registry();
+        let files = init_repo(root);
+
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        save_to(&path, root, &files, &registry, &graph).unwrap();
+
+        // Give `run` a call into `Counter`, and commit, so the delta comes
+        // from HEAD moving past the stored sha.
+        std::fs::write(
+            root.join("d.rs"),
+            "struct Counter;\nimpl Counter {\n    fn create() -> Counter { Counter }\n    fn tick(&self) -> u32 { 1 }\n}\nfn run(counter: &Counter) -> u32 { counter.tick() }\n",
+        )
+        .unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "run"]);
+
+        let (updated, replayed) = load_updated_from(&path, root, &files, &registry).unwrap();
+        assert!(replayed);
+        let (fresh, _) = EntityGraph::build(root, &files, &registry);
+        assert_eq!(topology(&updated), topology(&fresh));
+        assert!(updated
+            .get_dependencies("d.rs::function::run")
+            .iter()
+            .any(|dep| dep.id == "d.rs::impl::Counter::tick"));
+    }
+
+    #[test]
+    fn cross_file_edit_matches_fresh_build() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        let root = repo.path();
+        let path = cache.path().join(SNAPSHOT_FILE);
+        let registry = create_default_registry();
+        let files = init_repo(root);
+
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        save_to(&path, root, &files, &registry, &graph).unwrap();
+
+        // An untouched tree loads as stored, with nothing to save again.
+        let (reloaded, replayed) = load_updated_from(&path, root, &files, &registry).unwrap();
+        assert!(!replayed);
+        assert_eq!(topology(&reloaded), topology(&graph));
+
+        // `helper` is new and imported `source`, and c.ts's `top -> consume`
+        // has to survive `consume` being re-extracted.
+        std::fs::write(
+            root.join("b.ts"),
+            "import { source } from './a';\nexport function helper() { return source(); }\nexport function consume() { return helper(); }\n",
+        )
+        .unwrap();
+        let (updated, replayed) = load_updated_from(&path, root, &files, &registry).unwrap();
+        assert!(replayed);
+        let (fresh, _) = EntityGraph::build(root, &files, &registry);
+        assert_eq!(topology(&updated), topology(&fresh));
+        assert!(updated
+            .get_dependencies("b.ts::function::helper")
+            .iter()
+            .any(|dep| dep.id == "a.ts::function::source"));
+    }
+
+    #[test]
+    fn config_change_is_a_miss() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        let root = repo.path();
+        let path = cache.path().join(SNAPSHOT_FILE);
+        let registry = create_default_registry();
+        let files = init_repo(root);
+
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        save_to(&path, root, &files, &registry, &graph).unwrap();
+
+        std::fs::write(root.join(".semignore"), "type:function\n").unwrap();
+        assert!(load_updated_from(&path, root, &files, &registry).is_none());
+        std::fs::remove_file(root.join(".semignore")).unwrap();
+
+        let mut registry = create_default_registry();
+        registry.max_file_size = 1;
+        assert!(load_updated_from(&path, root, &files, &registry).is_none());
+    }
+
+    #[test]
+    fn corrupt_or_foreign_snapshot_is_a_miss() {
+        let repo = tempfile::TempDir::new().unwrap();
+        let cache = tempfile::TempDir::new().unwrap();
+        let root = repo.path();
+        let path = cache.path().join(SNAPSHOT_FILE);
+        let registry = create_default_registry();
+        git(root, &["init", "-q"]);
+
+        std::fs::write(&path, b"not a snapshot").unwrap();
+        assert!(load_updated_from(&path, root, &[], &registry).is_none());
+
+        let foreign = GraphSnapshot {
+            version: "0.0.0".to_string(),
+            schema: shared_cache::CACHE_SCHEMA_VERSION,
+            config: config_key(root, &registry),
+            head: String::new(),
+            dirty_files: Vec::new(),
+            files: Vec::new(),
+            entities: Vec::new(),
+            edges: Vec::new(),
+        };
+        std::fs::write(&path, serde_json::to_vec(&foreign).unwrap()).unwrap();
+        assert!(load_updated_from(&path, root, &[], &registry).is_none());
+    }
+}
@@ -21,13 +21,18 @@ struct TimingCounter {
 }
 
 impl Timings {
+    /// Whether `SEM_TIMINGS` asks for phase timings.
+    pub fn requested() -> bool {
+        let value = std::env::var("SEM_TIMINGS").unwrap_or_default();
+        !matches!(value.as_str(), "" | "0" | "false" | "off")
+    }
+
     pub fn from_env(command: &'static str) -> Self {
         let value = std::env::var("SEM_TIMINGS").unwrap_or_default();
-        let enabled = !matches!(value.as_str(), "" | "0" | "false" | "off");
         let now = Instant::now();
         Self {
             command,
-            enabled,
+            enabled: Self::requested(),
             json: value == "json",
             start: now,
             last: now,
@@ -45,3 +45,54 @@ fn blame_json_marks_entity_with_uncommitted_line() {
     assert_eq!(json[0]["author"], "Not Committed Yet");
     assert!(json[0]["commit"].is_null());
 }
+
+#[test]
+fn blame_range_shows_only_overlapping_entities() {
+    let repo = TempDir::new().unwrap();
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["config", "user.email", "t@t.com"]);
+    git(&repo, &["config", "user.name", "test"]);
+
+    fs::write(
+        repo.path().join("a.py"),
+        "def first():\n    return 1\n\ndef second():\n    return 2\n\n\ndef third():\n    return 3\n",
+    )
+    .unwrap();
+    git(&repo, &["add", "a.py"]);
+    git(&repo, &["commit", "-q", "-m", "init"]);
+
+    let blame = |range: &str| {
+        Command::new(env!("CARGO_BIN_EXE_sem"))
+            .current_dir(repo.path())
+            .args(["blame", "a.py", "--json", "--range", range])
+            .output()
+            .unwrap()
+    };
+
+    let output = blame("2:4");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["first", "second"]);
+
+    // Blank lines between entities fall back to per-line blame.
+    let output = blame("6:7");
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let lines: Vec<u64> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, [6, 7]);
+    assert_eq!(json[0]["author"], "test");
+
+    let output = blame("4:2");
+    assert!(!output.status.success());
+}
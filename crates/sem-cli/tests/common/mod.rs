@@ -0,0 +1,110 @@
+//! A throwaway git repository and a `sem` runner shared by the CLI tests.
+
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub struct TestRepo {
+    pub path: PathBuf,
+    pub home: PathBuf,
+}
+
+impl TestRepo {
+    pub fn new(name: &str) -> Self {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after UNIX epoch")
+            .as_nanos();
+        let path =
+            std::env::temp_dir().join(format!("sem-cli-{name}-{}-{nonce}", std::process::id()));
+        let home = std::env::temp_dir().join(format!(
+            "sem-cli-{name}-home-{}-{nonce}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&path).expect("create temporary repo");
+        fs::create_dir_all(&home).expect("create temporary home");
+
+        git(&path, &["init", "-q"]);
+        git(&path, &["config", "user.email", "test@example.com"]);
+        git(&path, &["config", "user.name", "Test User"]);
+        git(&path, &["config", "commit.gpgsign", "false"]);
+
+        Self { path, home }
+    }
+
+    /// Write `file` and commit it with `author` as both author and committer,
+    /// dated `date`. The email is derived from the name.
+    pub fn commit_as(&self, author: &str, date: &str, message: &str, file: &str, contents: &str) {
+        fs::write(self.path.join(file), contents).expect("write source");
+        git(&self.path, &["add", "-A"]);
+        let email = format!("{}@example.com", author.to_lowercase().replace(' ', "."));
+        git_with_env(
+            &self.path,
+            &["commit", "-q", "-m", message],
+            &[
+                ("GIT_AUTHOR_NAME", author),
+                ("GIT_AUTHOR_EMAIL", &email),
+                ("GIT_AUTHOR_DATE", date),
+                ("GIT_COMMITTER_NAME", author),
+                ("GIT_COMMITTER_EMAIL", &email),
+                ("GIT_COMMITTER_DATE", date),
+            ],
+        );
+    }
+
+    pub fn head_sha(&self) -> String {
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(&self.path)
+            .output()
+            .expect("run git");
+        String::from_utf8(output.stdout)
+            .expect("utf8 sha")
+            .trim()
+            .to_string()
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+        let _ = fs::remove_dir_all(&self.home);
+    }
+}
+
+pub fn git(repo: &Path, args: &[&str]) {
+    git_with_env(repo, args, &[]);
+}
+
+fn git_with_env(repo: &Path, args: &[&str], envs: &[(&str, &str)]) {
+    let output = Command::new("git")
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(repo)
+        .output()
+        .expect("run git");
+
+    assert!(
+        output.status.success(),
+        "git {args:?} failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// `sem` run inside `repo` with its own home, for callers that need extra
+/// environment before running it.
+pub fn sem_command(repo: &TestRepo) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_sem"));
+    command.current_dir(&repo.path).env("HOME", &repo.home);
+    command
+}
+
+pub fn sem(repo: &TestRepo, args: &[&str]) -> Output {
+    sem_command(repo).args(args).output().expect("run sem")
+}
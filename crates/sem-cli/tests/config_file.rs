@@ -0,0 +1,174 @@
+mod common;
+
+use std::fs;
+use std::process::Output;
+
+use common::{git, sem, TestRepo};
+
+fn changed_files(output: &Output) -> Vec<String> {
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("diff json");
+    let mut files: Vec<String> = json["changes"]
+        .as_array()
+        .expect("changes array")
+        .iter()
+        .map(|change| change["filePath"].as_str().unwrap().to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+#[test]
+fn config_file_exts_limit_diff_unless_overridden() {
+    let repo = TestRepo::new("config-file-exts");
+    fs::write(repo.path.join("lib.rs"), "fn a() {}\n").expect("write rust");
+    fs::write(repo.path.join("app.py"), "def a():\n    pass\n").expect("write python");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(repo.path.join("lib.rs"), "fn a() {}\nfn b() {}\n").expect("edit rust");
+    fs::write(
+        repo.path.join("app.py"),
+        "def a():\n    pass\n\ndef b():\n    pass\n",
+    )
+    .expect("edit python");
+    fs::create_dir_all(repo.path.join(".sem")).expect("create .sem");
+    fs::write(
+        repo.path.join(".sem/config.toml"),
+        "file_exts = [\".rs\"]\nformat = \"json\"\n",
+    )
+    .expect("write config");
+
+    assert_eq!(changed_files(&sem(&repo, &["diff"])), vec!["lib.rs"]);
+    assert_eq!(
+        changed_files(&sem(&repo, &["diff", "--file-exts", ".py"])),
+        vec!["app.py"]
+    );
+}
+
+#[test]
+fn malformed_config_file_is_an_error() {
+    let repo = TestRepo::new("config-malformed");
+    fs::create_dir_all(repo.path.join(".sem")).expect("create .sem");
+    fs::write(repo.path.join(".sem/config.toml"), "file_exts = \".rs\"\n").expect("write config");
+
+    let output = sem(&repo, &["diff"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(".sem/config.toml"), "stderr: {stderr}");
+}
+
+#[test]
+fn semignore_filters_diff_files_and_entity_types() {
+    let repo = TestRepo::new("semignore-diff");
+    fs::create_dir_all(repo.path.join("gen")).expect("create gen");
+    fs::write(repo.path.join("gen/api.ts"), "export function a() {}\n").expect("write gen");
+    fs::write(
+        repo.path.join("store.ts"),
+        "export class Store {\n  count = 0;\n}\n",
+    )
+    .expect("write store");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("gen/api.ts"),
+        "export function a() { return 1; }\n",
+    )
+    .expect("edit gen");
+    fs::write(
+        repo.path.join("store.ts"),
+        "export class Store {\n  count = 1;\n}\n",
+    )
+    .expect("edit store");
+    assert_eq!(
+        changed_files(&sem(&repo, &["diff", "--format", "json"])),
+        vec!["gen/api.ts", "store.ts"]
+    );
+
+    fs::write(repo.path.join(".semignore"), "gen/\ntype:field\n").expect("write .semignore");
+    let output = sem(&repo, &["diff", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("diff json");
+    let entity_ids: Vec<&str> = json["changes"]
+        .as_array()
+        .expect("changes array")
+        .iter()
+        .map(|change| change["entityId"].as_str().unwrap())
+        .collect();
+    assert_eq!(entity_ids, vec!["store.ts::class::Store"]);
+}
+
+#[test]
+fn entity_type_names_rename_displayed_types_but_not_ids() {
+    let repo = TestRepo::new("config-entity-type-names");
+    fs::write(
+        repo.path.join("lib.rs"),
+        "pub struct Counter;\n\npub fn get() -> u32 {\n    0\n}\n",
+    )
+    .expect("write rust");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+    fs::write(
+        repo.path.join("lib.rs"),
+        "pub struct Counter {\n    n: u32,\n}\n\npub fn get() -> u32 {\n    1\n}\n",
+    )
+    .expect("edit rust");
+
+    let changes_with = |repo: &TestRepo, extra: &[&str]| -> Vec<(String, String)> {
+        let mut args = vec!["diff", "--format", "json"];
+        args.extend_from_slice(extra);
+        let output = sem(repo, &args);
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("diff json");
+        json["changes"]
+            .as_array()
+            .expect("changes array")
+            .iter()
+            .map(|change| {
+                (
+                    change["entityId"].as_str().unwrap().to_string(),
+                    change["entityType"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+    let changes = |repo: &TestRepo| changes_with(repo, &[]);
+    let before = changes(&repo);
+    assert!(before.iter().any(|(_, ty)| ty == "struct"), "{before:?}");
+    assert!(before.iter().any(|(_, ty)| ty == "function"), "{before:?}");
+    let terminal = String::from_utf8(sem(&repo, &["diff"]).stdout).unwrap();
+    assert!(!terminal.contains("record"), "{terminal}");
+
+    fs::create_dir_all(repo.path.join(".sem")).expect("create .sem");
+    fs::write(
+        repo.path.join(".sem/config.toml"),
+        "[entity_type_names]\nstruct = \"record\"\n",
+    )
+    .expect("write config");
+
+    let after = changes(&repo);
+    let ids = |changes: &[(String, String)]| -> Vec<String> {
+        changes.iter().map(|(id, _)| id.clone()).collect()
+    };
+    assert_eq!(ids(&after), ids(&before));
+    for ((_, old_type), (id, new_type)) in before.iter().zip(&after) {
+        let expected = if old_type == "struct" {
+            "record"
+        } else {
+            old_type
+        };
+        assert_eq!(new_type, expected, "{id}");
+    }
+    let terminal = String::from_utf8(sem(&repo, &["diff"]).stdout).unwrap();
+    assert!(terminal.contains("record"), "{terminal}");
+
+    // Type filters still take the canonical name.
+    assert_eq!(
+        changes_with(&repo, &["--only-type", "struct"]),
+        vec![("lib.rs::struct::Counter".to_string(), "record".to_string())]
+    );
+}
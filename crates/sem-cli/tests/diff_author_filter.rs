@@ -0,0 +1,137 @@
+mod common;
+
+use common::{sem, TestRepo};
+
+fn entity_ids(repo: &TestRepo, args: &[&str]) -> Vec<String> {
+    let output = sem(repo, args);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut lines: Vec<String> = String::from_utf8(output.stdout)
+        .expect("utf8 stdout")
+        .lines()
+        .map(str::to_owned)
+        .collect();
+    lines.sort();
+    lines
+}
+
+fn two_author_repo() -> TestRepo {
+    let repo = TestRepo::new("diff-author-filter");
+    repo.commit_as(
+        "Alice",
+        "2024-01-01T10:00:00Z",
+        "Alice edits shared.py",
+        "shared.py",
+        "def base():\n    return 0\n",
+    );
+    repo.commit_as(
+        "Bob",
+        "2024-01-02T10:00:00Z",
+        "Bob edits bob.py",
+        "bob.py",
+        "def bob_one():\n    return 1\n",
+    );
+    repo.commit_as(
+        "Alice",
+        "2024-01-03T10:00:00Z",
+        "Alice edits alice.py",
+        "alice.py",
+        "def alice_one():\n    return 1\n",
+    );
+    repo.commit_as(
+        "Bob",
+        "2024-01-04T10:00:00Z",
+        "Bob edits bob.py",
+        "bob.py",
+        "def bob_one():\n    return 1\n\ndef bob_two():\n    return 2\n",
+    );
+    repo
+}
+
+#[test]
+fn author_filter_keeps_only_that_authors_changes() {
+    let repo = two_author_repo();
+
+    assert_eq!(
+        entity_ids(
+            &repo,
+            &["diff", "HEAD~3..HEAD", "--author", "Bob", "--entity-ids"]
+        ),
+        vec![
+            "bob.py::function::bob_one\tadded",
+            "bob.py::function::bob_two\tadded",
+        ]
+    );
+    assert_eq!(
+        entity_ids(
+            &repo,
+            &[
+                "diff",
+                "--from",
+                "HEAD~3",
+                "--to",
+                "HEAD",
+                "--author",
+                "alice@example.com",
+                "--entity-ids",
+            ],
+        ),
+        vec!["alice.py::function::alice_one\tadded"]
+    );
+}
+
+#[test]
+fn since_filter_skips_older_commits() {
+    let repo = two_author_repo();
+
+    assert_eq!(
+        entity_ids(
+            &repo,
+            &[
+                "diff",
+                "HEAD~3..HEAD",
+                "--since",
+                "2024-01-03",
+                "--entity-ids"
+            ],
+        ),
+        vec![
+            "alice.py::function::alice_one\tadded",
+            "bob.py::function::bob_two\tadded",
+        ]
+    );
+    assert_eq!(
+        entity_ids(
+            &repo,
+            &[
+                "diff",
+                "HEAD~3..HEAD",
+                "--author",
+                "Bob",
+                "--since",
+                "2024-01-03T12:00:00+02:00",
+                "--entity-ids",
+            ],
+        ),
+        vec!["bob.py::function::bob_two\tadded"]
+    );
+}
+
+#[test]
+fn author_filter_requires_a_commit_range() {
+    let repo = two_author_repo();
+
+    let output = sem(&repo, &["diff", "--author", "Bob"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("need a commit range"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = sem(&repo, &["diff", "HEAD~1..HEAD", "--since", "last week"]);
+    assert!(!output.status.success());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::process::Output;
+
+use common::{sem_command, TestRepo};
+
+fn sem(repo: &TestRepo, args: &[&str]) -> Output {
+    let output = sem_command(repo)
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run sem");
+    assert!(
+        output.status.success(),
+        "sem {args:?} failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn stdout(repo: &TestRepo, args: &[&str]) -> String {
+    String::from_utf8(sem(repo, args).stdout).expect("utf8 stdout")
+}
+
+/// Two commits; the second, by Ada, adds `greet`.
+fn fixture_repo() -> TestRepo {
+    let repo = TestRepo::new("diff-commit-header");
+    repo.commit_as(
+        "Grace Hopper",
+        "2024-02-01T10:00:00Z",
+        "initial",
+        "app.py",
+        "def main():\n    return 0\n",
+    );
+    repo.commit_as(
+        "Ada Lovelace",
+        "2024-03-01T09:15:00-05:00",
+        "Add greeting\n\nSays hello before main runs.",
+        "app.py",
+        "def main():\n    return 0\n\ndef greet():\n    return 'hello'\n",
+    );
+    repo
+}
+
+#[test]
+fn json_includes_the_commit_object() {
+    let repo = fixture_repo();
+    let sha = repo.head_sha();
+
+    let output: serde_json::Value = serde_json::from_str(&stdout(
+        &repo,
+        &["diff", "--commit", "HEAD", "--format", "json"],
+    ))
+    .expect("diff JSON");
+    assert_eq!(output["commit"]["sha"], sha.as_str());
+    assert_eq!(output["commit"]["author"], "Ada Lovelace");
+    assert_eq!(output["commit"]["date"], "2024-03-01T14:15:00+00:00");
+    assert_eq!(output["commit"]["subject"], "Add greeting");
+    assert_eq!(output["changes"][0]["entityName"], "greet");
+
+    // Only a single-commit diff describes a commit.
+    let range: serde_json::Value = serde_json::from_str(&stdout(
+        &repo,
+        &[
+            "diff", "--from", "HEAD~1", "--to", "HEAD", "--format", "json",
+        ],
+    ))
+    .expect("diff JSON");
+    assert!(range.get("commit").is_none(), "{range}");
+}
+
+#[test]
+fn text_output_starts_with_a_git_show_header() {
+    let repo = fixture_repo();
+    let header = format!(
+        "commit {}\nAuthor: Ada Lovelace\nDate:   2024-03-01T14:15:00+00:00\n\n    Add greeting\n\n",
+        repo.head_sha()
+    );
+
+    for format in ["terminal", "plain"] {
+        let text = stdout(&repo, &["diff", "--commit", "HEAD", "--format", format]);
+        assert!(text.starts_with(&header), "{format}: {text}");
+        assert!(text.contains("greet"), "{format}: {text}");
+        assert!(!text.contains("Says hello"), "{format}: {text}");
+    }
+
+    // Listing output stays one entry per line.
+    let names = stdout(&repo, &["diff", "--commit", "HEAD", "--name-only"]);
+    assert!(!names.contains("Author:"), "{names}");
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+#[test]
+fn entity_ids_prints_sorted_id_and_change_type() {
+    let repo = TestRepo::new("diff-entity-ids");
+    fs::write(
+        repo.path.join("app.py"),
+        "def foo():\n    return 1\n\ndef gone():\n    return 2\n",
+    )
+    .expect("write initial source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "def foo():\n    return 10\n\ndef bar():\n    return 3\n",
+    )
+    .expect("write changed source");
+
+    let output = sem(&repo, &["diff", "--entity-ids"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).expect("utf8 stdout");
+    assert_eq!(
+        stdout,
+        "app.py::function::bar\tadded\n\
+         app.py::function::foo\tmodified\n\
+         app.py::function::gone\tdeleted\n"
+    );
+}
+
+#[test]
+fn entity_ids_prints_nothing_without_changes() {
+    let repo = TestRepo::new("diff-entity-ids-empty");
+    fs::write(repo.path.join("app.py"), "def foo():\n    return 1\n").expect("write source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    let output = sem(&repo, &["diff", "--entity-ids"]);
+    assert!(output.status.success());
+    assert!(
+        output.stdout.is_empty(),
+        "stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
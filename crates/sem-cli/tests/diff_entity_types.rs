@@ -0,0 +1,80 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+/// A working tree where one function and one class change and a class is added.
+fn repo_with_mixed_changes(name: &str) -> TestRepo {
+    let repo = TestRepo::new(name);
+    fs::write(
+        repo.path.join("app.py"),
+        "def helper():\n    return 1\n\nclass Store:\n    limit = 1\n",
+    )
+    .expect("write initial source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "def helper():\n    return 2\n\nclass Store:\n    limit = 2\n\nclass Cache:\n    size = 0\n",
+    )
+    .expect("write changed source");
+    repo
+}
+
+fn diff_json(repo: &TestRepo, args: &[&str]) -> serde_json::Value {
+    let output = sem(repo, &[&["diff", "--json"], args].concat());
+    assert!(
+        output.status.success(),
+        "sem diff failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("stdout should be json")
+}
+
+fn entity_types(json: &serde_json::Value) -> Vec<&str> {
+    json["changes"]
+        .as_array()
+        .expect("changes array")
+        .iter()
+        .map(|change| change["entityType"].as_str().expect("entity type"))
+        .collect()
+}
+
+#[test]
+fn only_type_keeps_matching_changes_and_recounts_summary() {
+    let repo = repo_with_mixed_changes("diff-only-type");
+
+    let json = diff_json(&repo, &["--only-type", "function"]);
+    assert_eq!(entity_types(&json), ["function"]);
+    assert_eq!(json["changes"][0]["entityName"], "helper");
+    assert_eq!(json["summary"]["total"], 1);
+    assert_eq!(json["summary"]["modified"], 1);
+    assert_eq!(json["summary"]["added"], 0);
+    assert_eq!(json["summary"]["fileCount"], 1);
+
+    // Comma-separated and repeated values both add to the list.
+    let json = diff_json(&repo, &["--only-types", "function,class"]);
+    assert_eq!(json["summary"]["total"], 3);
+}
+
+#[test]
+fn exclude_type_drops_matching_changes() {
+    let repo = repo_with_mixed_changes("diff-exclude-type");
+
+    let json = diff_json(&repo, &["--exclude-type", "function"]);
+    assert_eq!(entity_types(&json), ["class", "class"]);
+    assert_eq!(json["summary"]["total"], 2);
+    assert_eq!(json["summary"]["added"], 1);
+    assert_eq!(json["summary"]["modified"], 1);
+
+    let json = diff_json(
+        &repo,
+        &["--exclude-type", "function", "--exclude-type", "class"],
+    );
+    assert!(entity_types(&json).is_empty());
+    assert_eq!(json["summary"]["total"], 0);
+    assert_eq!(json["summary"]["fileCount"], 0);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+/// A working tree with one modified, one deleted and one added function.
+fn repo_with_changes(name: &str) -> TestRepo {
+    let repo = TestRepo::new(name);
+    fs::write(
+        repo.path.join("app.py"),
+        "def kept():\n    return 1\n\n\ndef removed():\n    return 2\n",
+    )
+    .expect("write initial source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "def kept():\n    return 10\n\n\ndef added():\n    return 3\n",
+    )
+    .expect("write changed source");
+    repo
+}
+
+fn exit_code(repo: &TestRepo, extra: &[&str]) -> (i32, String) {
+    let mut args = vec!["diff", "--format", "plain"];
+    args.extend_from_slice(extra);
+    let output = sem(repo, &args);
+    (
+        output.status.code().expect("sem exited with a status"),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn fail_on_exits_nonzero_only_for_present_change_types() {
+    let repo = repo_with_changes("diff-fail-on-types");
+
+    assert_eq!(exit_code(&repo, &[]).0, 0);
+    assert_eq!(exit_code(&repo, &["--fail-on", "moved"]).0, 0);
+    assert_eq!(
+        exit_code(&repo, &["--fail-on", "renamed", "--fail-on", "reordered"]).0,
+        0
+    );
+
+    let (code, stderr) = exit_code(&repo, &["--fail-on", "deleted"]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains("1 deleted"), "{stderr}");
+
+    let (code, stderr) = exit_code(&repo, &["--fail-on", "moved", "--fail-on", "added"]);
+    assert_eq!(code, 1);
+    assert!(stderr.contains("1 added"), "{stderr}");
+}
+
+#[test]
+fn fail_on_count_exits_nonzero_above_the_limit() {
+    let repo = repo_with_changes("diff-fail-on-count");
+
+    assert_eq!(exit_code(&repo, &["--fail-on-count", "3"]).0, 0);
+    let (code, stderr) = exit_code(&repo, &["--fail-on-count", "2"]);
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("3 changes exceed --fail-on-count 2"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn fail_on_still_prints_the_diff() {
+    let repo = repo_with_changes("diff-fail-on-output");
+
+    let output = sem(
+        &repo,
+        &["diff", "--format", "plain", "--fail-on", "modified"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("kept"));
+}
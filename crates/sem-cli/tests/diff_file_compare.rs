@@ -549,3 +549,136 @@ fn trailing_format_requires_value_before_another_flag() {
     let _ = fs::remove_dir_all(dir);
     let _ = fs::remove_dir_all(home);
 }
+
+fn changes_by_entity(output: &std::process::Output) -> Vec<(String, String)> {
+    assert!(
+        output.status.success(),
+        "sem failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be json");
+    let mut changes: Vec<(String, String)> = json["changes"]
+        .as_array()
+        .expect("changes should be an array")
+        .iter()
+        .map(|change| {
+            (
+                change["entityId"].as_str().unwrap_or_default().to_string(),
+                change["changeType"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            )
+        })
+        .collect();
+    changes.sort();
+    changes
+}
+
+#[test]
+fn file_a_file_b_compares_disk_files_outside_git() {
+    let dir = temp_dir("file-a-file-b");
+    let home = temp_dir("file-a-file-b-home");
+    fs::create_dir_all(dir.join("old")).expect("old dir should be created");
+    fs::create_dir_all(dir.join("new")).expect("new dir should be created");
+    fs::write(
+        dir.join("old/api.ts"),
+        "export function load() { return 1; }\n\nexport function drop() { return 0; }\n",
+    )
+    .expect("old file should be written");
+    fs::write(
+        dir.join("new/api.ts"),
+        "export function load() { return 2; }\n\nexport function save() { return 3; }\n",
+    )
+    .expect("new file should be written");
+
+    let diff = |a: &str, b: &str| {
+        run_sem_json(
+            &dir,
+            &home,
+            &["diff", "--file-a", a, "--file-b", b, "--format", "json"],
+        )
+    };
+    assert_eq!(
+        changes_by_entity(&diff("old/api.ts", "new/api.ts")),
+        vec![
+            ("new/api.ts::function::drop".into(), "deleted".into()),
+            ("new/api.ts::function::load".into(), "modified".into()),
+            ("new/api.ts::function::save".into(), "added".into()),
+        ]
+    );
+    assert_eq!(
+        changes_by_entity(&diff("old/api.ts", "new/missing.ts")),
+        vec![
+            ("old/api.ts::function::drop".into(), "deleted".into()),
+            ("old/api.ts::function::load".into(), "deleted".into()),
+        ]
+    );
+    assert_eq!(
+        changes_by_entity(&diff("old/missing.ts", "new/api.ts")),
+        vec![
+            ("new/api.ts::function::load".into(), "added".into()),
+            ("new/api.ts::function::save".into(), "added".into()),
+        ]
+    );
+    assert!(!diff("old/missing.ts", "new/missing.ts").status.success());
+
+    let _ = fs::remove_dir_all(dir);
+    let _ = fs::remove_dir_all(home);
+}
+
+#[test]
+fn dir_a_dir_b_compares_snapshots_file_by_file() {
+    let dir = temp_dir("dir-a-dir-b");
+    let home = temp_dir("dir-a-dir-b-home");
+    for (path, content) in [
+        ("v1/src/api.ts", "export function load() { return 1; }\n"),
+        ("v1/src/same.ts", "export function same() { return 1; }\n"),
+        ("v1/src/gone.ts", "export function gone() { return 1; }\n"),
+        ("v2/src/api.ts", "export function load() { return 2; }\n"),
+        ("v2/src/same.ts", "export function same() { return 1; }\n"),
+        ("v2/src/fresh.ts", "export function fresh() { return 1; }\n"),
+    ] {
+        fs::create_dir_all(dir.join(path).parent().unwrap()).expect("dir should be created");
+        fs::write(dir.join(path), content).expect("file should be written");
+    }
+
+    let output = run_sem_json(
+        &dir,
+        &home,
+        &["diff", "--dir-a", "v1", "--dir-b", "v2", "--format", "json"],
+    );
+    assert_eq!(
+        changes_by_entity(&output),
+        vec![
+            ("src/api.ts::function::load".into(), "modified".into()),
+            ("src/fresh.ts::function::fresh".into(), "added".into()),
+            ("src/gone.ts::function::gone".into(), "deleted".into()),
+        ]
+    );
+
+    let output = run_sem_json(
+        &dir,
+        &home,
+        &[
+            "diff",
+            "--dir-a",
+            "v1",
+            "--dir-b",
+            "v2",
+            "--format",
+            "json",
+            "--",
+            "src/api.ts",
+        ],
+    );
+    assert_eq!(
+        changes_by_entity(&output),
+        vec![("src/api.ts::function::load".into(), "modified".into())]
+    );
+
+    let _ = fs::remove_dir_all(dir);
+    let _ = fs::remove_dir_all(home);
+}
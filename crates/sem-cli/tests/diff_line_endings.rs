@@ -0,0 +1,60 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+fn diff_json(repo: &TestRepo, args: &[&str]) -> serde_json::Value {
+    let output = sem(repo, &[&["diff", "--json"], args].concat());
+    assert!(
+        output.status.success(),
+        "sem diff failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("stdout should be json")
+}
+
+/// A repo with an LF file committed and a CRLF, BOM-prefixed copy of the
+/// same content in the working tree.
+fn repo_with_crlf_checkout(name: &str) -> TestRepo {
+    let repo = TestRepo::new(name);
+    git(&repo.path, &["config", "core.autocrlf", "false"]);
+    fs::write(
+        repo.path.join("app.py"),
+        "def foo():\n    return 1\n\ndef bar():\n    return 2\n",
+    )
+    .expect("write initial source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "\u{feff}def foo():\r\n    return 1\r\n\r\ndef bar():\r\n    return 2\r\n",
+    )
+    .expect("write crlf source");
+    repo
+}
+
+#[test]
+fn crlf_and_bom_checkout_reports_no_changes() {
+    let repo = repo_with_crlf_checkout("diff-eol-normalized");
+
+    let json = diff_json(&repo, &[]);
+    assert_eq!(json["changes"].as_array().map(Vec::len), Some(0));
+    assert_eq!(json["summary"]["total"], 0);
+}
+
+#[test]
+fn no_eol_normalize_reports_line_ending_changes() {
+    let repo = repo_with_crlf_checkout("diff-no-eol-normalize");
+
+    let json = diff_json(&repo, &["--no-eol-normalize"]);
+    let changes = json["changes"].as_array().expect("changes array");
+    assert!(
+        changes
+            .iter()
+            .any(|change| change["entityName"] == "bar" && change["changeType"] == "modified"),
+        "expected bar to be modified: {changes:?}"
+    );
+}
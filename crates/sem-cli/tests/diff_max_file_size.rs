@@ -0,0 +1,54 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+#[test]
+fn oversized_files_are_skipped_and_counted() {
+    let repo = TestRepo::new("diff-max-file-size");
+    let big = |value: u32| {
+        (0..100)
+            .map(|i| format!("def generated_{i}():\n    return {value}\n\n"))
+            .collect::<String>()
+    };
+    fs::write(repo.path.join("small.py"), "def foo():\n    return 1\n").unwrap();
+    fs::write(repo.path.join("big.py"), big(1)).unwrap();
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(repo.path.join("small.py"), "def foo():\n    return 2\n").unwrap();
+    fs::write(repo.path.join("big.py"), big(2)).unwrap();
+
+    let output = sem(&repo, &["--max-file-size", "1K", "diff", "--json"]);
+    assert!(
+        output.status.success(),
+        "sem diff failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be json");
+    let changed: Vec<&str> = json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| change["filePath"].as_str().unwrap())
+        .collect();
+    assert_eq!(changed, ["small.py"]);
+    assert_eq!(json["binaryChanges"].as_array().map(Vec::len), Some(0));
+    assert_eq!(json["summary"]["skippedFiles"], 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("big.py"), "stderr: {stderr}");
+
+    // Under the default limit the same file is diffed.
+    let output = sem(&repo, &["diff", "--json"]);
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("stdout should be json");
+    assert!(json["summary"].get("skippedFiles").is_none());
+    assert!(json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|change| change["filePath"] == "big.py"));
+}
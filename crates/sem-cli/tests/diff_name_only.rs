@@ -0,0 +1,86 @@
+mod common;
+
+use std::fs;
+use std::process::Output;
+
+use common::{git, sem, TestRepo};
+
+fn commit_initial(repo: &TestRepo) {
+    fs::create_dir_all(repo.path.join("lib")).expect("create lib dir");
+    fs::write(
+        repo.path.join("app.py"),
+        "def foo():\n    return 1\n\ndef gone():\n    return 2\n",
+    )
+    .expect("write app.py");
+    fs::write(
+        repo.path.join("lib/util.py"),
+        "def helper():\n    return 1\n",
+    )
+    .expect("write lib/util.py");
+    fs::write(repo.path.join("README.txt"), "unchanged\n").expect("write README.txt");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "def foo():\n    return 10\n\ndef bar():\n    return 3\n",
+    )
+    .expect("write changed app.py");
+    fs::write(
+        repo.path.join("lib/util.py"),
+        "def helper():\n    return 2\n",
+    )
+    .expect("write changed lib/util.py");
+}
+
+fn stdout_of(output: Output) -> String {
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf8 stdout")
+}
+
+#[test]
+fn name_only_prints_sorted_unique_changed_paths() {
+    let repo = TestRepo::new("diff-name-only");
+    commit_initial(&repo);
+
+    let stdout = stdout_of(sem(&repo, &["diff", "--name-only"]));
+    assert_eq!(stdout, "app.py\nlib/util.py\n");
+}
+
+#[test]
+fn entities_only_prints_sorted_id_and_change_type() {
+    let repo = TestRepo::new("diff-entities-only");
+    commit_initial(&repo);
+
+    let stdout = stdout_of(sem(&repo, &["diff", "--entities-only"]));
+    assert_eq!(
+        stdout,
+        "app.py::function::bar\tadded\n\
+         app.py::function::foo\tmodified\n\
+         app.py::function::gone\tdeleted\n\
+         lib/util.py::function::helper\tmodified\n"
+    );
+}
+
+#[test]
+fn listing_flags_print_nothing_without_changes() {
+    let repo = TestRepo::new("diff-listing-empty");
+    fs::write(repo.path.join("app.py"), "def foo():\n    return 1\n").expect("write source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    for flag in ["--name-only", "--entities-only"] {
+        assert_eq!(stdout_of(sem(&repo, &["diff", flag])), "", "{flag}");
+    }
+}
+
+#[test]
+fn name_only_conflicts_with_entities_only() {
+    let repo = TestRepo::new("diff-listing-conflict");
+    let output = sem(&repo, &["diff", "--name-only", "--entities-only"]);
+    assert!(!output.status.success());
+}
@@ -147,3 +147,102 @@ fn terminal_unsupported_file_warning_escapes_file_path_control_bytes() {
         "{stdout}"
     );
 }
+
+#[test]
+fn color_never_is_global_and_wins_over_forced_color() {
+    let repo = TempRepo::new();
+    commit_file(&repo, "app.py", "def foo():\n    return 1\n");
+    std::fs::write(repo.path().join("app.py"), "def foo():\n    return 2\n")
+        .expect("write modified file");
+
+    let forced = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_sem"))
+            .args(args)
+            .current_dir(repo.path())
+            .env("HOME", repo.home.path())
+            .env("CLICOLOR_FORCE", "1")
+            .output()
+            .expect("run sem")
+    };
+
+    let colored = forced(&["diff"]);
+    assert!(
+        colored.stdout.contains(&0x1b),
+        "CLICOLOR_FORCE should color"
+    );
+
+    for args in [
+        &["diff", "--color", "never"][..],
+        &["--color", "never", "diff", "-v"],
+        &["diff", "--color", "never", "HEAD", "HEAD"],
+    ] {
+        let output = forced(args);
+        assert!(
+            output.status.success(),
+            "sem {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(
+            !output.stdout.contains(&0x1b) && !output.stderr.contains(&0x1b),
+            "sem {args:?} printed ANSI escapes: {}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+    }
+}
+
+#[test]
+fn no_glyphs_marks_changes_with_ascii() {
+    let repo = TempRepo::new();
+    commit_file(
+        &repo,
+        "app.py",
+        "def foo():\n    return 1\n\ndef gone():\n    return 0\n",
+    );
+    std::fs::write(
+        repo.path().join("app.py"),
+        "def foo():\n    return 2\n\ndef fresh():\n    return 3\n",
+    )
+    .expect("write modified file");
+
+    let output = repo.run_sem(&["diff", "--color", "never", "--no-glyphs"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf-8");
+    for glyph in ["⊕", "∆", "⊖"] {
+        assert!(!stdout.contains(glyph), "{glyph} in {stdout}");
+    }
+    for marker in ["+ function fresh", "~ function foo", "- function gone"] {
+        assert!(
+            stdout.lines().any(|line| line
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .contains(marker)),
+            "missing {marker:?} in {stdout}"
+        );
+    }
+}
+
+#[test]
+fn limit_caps_terminal_entries_and_keeps_summary_counts() {
+    let repo = TempRepo::new();
+    let source = |value: u32| {
+        (1..=5)
+            .map(|n| format!("def f{n}():\n    return {value}\n"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    commit_file(&repo, "app.py", &source(1));
+    std::fs::write(repo.path().join("app.py"), source(2)).expect("write modified file");
+
+    let output = repo.run_sem(&["diff", "--color", "never", "--limit", "2"]);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf-8");
+    let entries = stdout
+        .lines()
+        .filter(|line| line.contains("∆ function"))
+        .count();
+    assert_eq!(entries, 2, "{stdout}");
+    assert!(
+        stdout.contains("... and 3 more changes (--limit 2)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("5 modified"), "{stdout}");
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     path::{Path, PathBuf},
     process::{Command, Output},
@@ -227,6 +228,662 @@ def four():
     }
 }
 
+#[test]
+fn graph_json_entities_describe_every_edge_endpoint() {
+    let repo = TempRepo::new();
+    write_ambiguous_constructor_fixture(&repo, "a_");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    // Fresh, then cached: the first cached run writes the cache, the second
+    // is served from it.
+    for stdout in [
+        run_sem_graph_json_stdout(&repo.path),
+        run_cached_sem_graph_json_stdout(&repo),
+        run_cached_sem_graph_json_stdout(&repo),
+    ] {
+        let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+        let entities = graph["entities"].as_array().expect("entities array");
+        for entity in entities {
+            for key in ["name", "entityType", "filePath"] {
+                assert!(entity[key].is_string(), "{key} missing: {entity}");
+            }
+            assert!(entity["startLine"].as_u64() <= entity["endLine"].as_u64());
+        }
+        let ids: HashSet<&str> = entities
+            .iter()
+            .map(|entity| entity["id"].as_str().expect("entity id"))
+            .collect();
+
+        let edges = graph["edges"].as_array().expect("edges array");
+        assert!(!edges.is_empty());
+        for edge in edges {
+            for end in ["fromEntity", "toEntity"] {
+                let id = edge[end].as_str().expect("edge endpoint");
+                assert!(ids.contains(id), "{end} {id} has no entity entry");
+            }
+        }
+    }
+}
+
+#[test]
+fn graph_json_is_byte_identical_across_fresh_and_cached_runs() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("base.ts"),
+        "export class Model {}\nexport interface Auditable {}\n",
+    )
+    .expect("write base.ts");
+    fs::write(
+        repo.path.join("user.ts"),
+        "import { Model, Auditable } from './base';\n\nexport class User extends Model implements Auditable {\n    copy(other: Model): Model { return other; }\n}\n\nexport function load(): User { return new User(); }\n",
+    )
+    .expect("write user.ts");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let first = run_sem_graph_json_stdout(&repo.path);
+    assert_eq!(run_sem_graph_json_stdout(&repo.path), first);
+
+    // The first cached run builds and stores the graph; the second streams it
+    // back from the cache. Both must match a fresh build byte for byte.
+    assert_eq!(run_cached_sem_graph_json_stdout(&repo), first);
+    assert_eq!(run_cached_sem_graph_json_stdout(&repo), first);
+}
+
+#[test]
+fn graph_json_single_threaded_matches_default() {
+    let repo = TempRepo::new();
+    write_ambiguous_constructor_fixture(&repo, "");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let default_stdout = run_sem_graph_json_stdout(&repo.path);
+    let flag_stdout = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &["--threads", "1", "graph", ".", "--json", "--no-cache"],
+        None,
+    );
+    assert_eq!(flag_stdout, default_stdout);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sem"))
+        .args(["graph", ".", "--json", "--no-cache"])
+        .env("SEM_SINGLE_THREAD", "1")
+        .current_dir(&repo.path)
+        .output()
+        .expect("run sem graph");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), default_stdout);
+
+    let rejected = Command::new(env!("CARGO_BIN_EXE_sem"))
+        .args(["graph", ".", "--threads", "0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("run sem graph");
+    assert!(!rejected.status.success());
+}
+
+#[test]
+fn graph_cluster_by_module_rolls_edges_up_to_modules() {
+    let repo = TempRepo::new();
+    fs::create_dir_all(repo.path.join("api")).expect("create api dir");
+    fs::create_dir_all(repo.path.join("db")).expect("create db dir");
+    fs::write(
+        repo.path.join("api/handlers.py"),
+        r#"
+from db.store import load, save
+
+def get_user():
+    return load()
+
+def put_user():
+    save()
+    return get_user()
+"#,
+    )
+    .expect("write api fixture");
+    fs::write(
+        repo.path.join("db/store.py"),
+        r#"
+def load():
+    return connect()
+
+def save():
+    return connect()
+
+def connect():
+    return None
+"#,
+    )
+    .expect("write db fixture");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let stdout = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &[
+            "graph",
+            ".",
+            "--cluster-by",
+            "module",
+            "--json",
+            "--no-cache",
+        ],
+        None,
+    );
+    let clusters: Value = serde_json::from_str(&stdout).expect("parse cluster json");
+
+    let modules = clusters["clusters"].as_array().expect("clusters array");
+    let module = |name: &str| {
+        modules
+            .iter()
+            .find(|cluster| cluster["module"] == name)
+            .unwrap_or_else(|| panic!("missing cluster {name}: {modules:?}"))
+    };
+    assert_eq!(module("api/")["entityCount"], 2);
+    assert_eq!(module("api/")["internalEdges"], 1);
+    assert_eq!(module("db/")["entityCount"], 3);
+    assert_eq!(module("db/")["internalEdges"], 2);
+
+    let edges = clusters["edges"].as_array().expect("edges array");
+    let api_to_db = edges
+        .iter()
+        .find(|edge| edge["from"] == "api/" && edge["to"] == "db/")
+        .unwrap_or_else(|| panic!("missing api/ -> db/ edge: {edges:?}"));
+    assert!(api_to_db["count"].as_u64().expect("edge count") >= 2);
+    assert!(
+        !edges.iter().any(|edge| edge["from"] == "db/"),
+        "db/ should not depend on api/: {edges:?}"
+    );
+}
+
+#[test]
+fn graph_entity_depth_limits_dependents() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("chain.py"),
+        r#"
+def a():
+    return 1
+
+def b():
+    return a()
+
+def c():
+    return b()
+
+def unrelated():
+    return 0
+"#,
+    )
+    .expect("write fixture");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let entity_ids = |depth: &str| {
+        let stdout = run_sem_graph_json_stdout_with_args(
+            &repo.path,
+            &[
+                "graph",
+                ".",
+                "--json",
+                "--no-cache",
+                "--entity",
+                "a",
+                "--depth",
+                depth,
+            ],
+            None,
+        );
+        let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+        graph["entities"]
+            .as_array()
+            .expect("entities array")
+            .iter()
+            .map(|entity| entity["name"].as_str().expect("entity name").to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(entity_ids("1"), vec!["a", "b"]);
+    assert_eq!(entity_ids("2"), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn graph_entity_neighbors_keeps_direct_dependencies_and_dependents() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("chain.py"),
+        r#"
+def a():
+    return 1
+
+def d():
+    return 2
+
+def b():
+    return a() + d()
+
+def c():
+    return b()
+
+def e():
+    return c()
+
+def unrelated():
+    return 0
+"#,
+    )
+    .expect("write fixture");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let stdout = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &[
+            "graph",
+            ".",
+            "--json",
+            "--no-cache",
+            "--entity",
+            "b",
+            "--neighbors",
+        ],
+        None,
+    );
+    let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+    let mut names = graph["entities"]
+        .as_array()
+        .expect("entities array")
+        .iter()
+        .map(|entity| entity["name"].as_str().expect("entity name").to_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    // b's dependencies (a, d), its dependents (c), and b itself; not e,
+    // which only reaches b through c.
+    assert_eq!(names, vec!["a", "b", "c", "d"]);
+    // b -> a, b -> d and c -> b; e -> c falls outside the neighborhood.
+    assert_eq!(graph["edges"].as_array().expect("edges array").len(), 3);
+}
+
+fn write_three_file_fixture(repo: &TempRepo) {
+    fs::create_dir_all(repo.path.join("src")).expect("create src");
+    fs::write(
+        repo.path.join("src/a.py"),
+        "from src.b import helper\n\ndef run():\n    return helper()\n",
+    )
+    .expect("write a.py");
+    fs::write(repo.path.join("src/b.py"), "def helper():\n    return 1\n").expect("write b.py");
+    fs::write(repo.path.join("src/c.py"), "def other():\n    return 2\n").expect("write c.py");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+}
+
+fn graph_file_paths(graph: &Value) -> Vec<String> {
+    let files: std::collections::BTreeSet<String> = graph["entities"]
+        .as_array()
+        .expect("entities array")
+        .iter()
+        .map(|entity| entity["filePath"].as_str().expect("file path").to_owned())
+        .collect();
+    files.into_iter().collect()
+}
+
+#[test]
+fn graph_files_from_manifest_only_analyzes_listed_files() {
+    let repo = TempRepo::new();
+    write_three_file_fixture(&repo);
+    let manifest = repo.cache_path.join("files.txt");
+    fs::write(&manifest, "src/a.py\n./src/b.py\n").expect("write manifest");
+
+    let stdout = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &[
+            "graph",
+            ".",
+            "--json",
+            "--files-from",
+            manifest.to_str().expect("utf-8 manifest path"),
+        ],
+        Some(&repo.cache_path),
+    );
+    let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+    assert_eq!(graph_file_paths(&graph), vec!["src/a.py", "src/b.py"]);
+    assert!(
+        !graph["edges"].as_array().expect("edges array").is_empty(),
+        "references between listed files still resolve"
+    );
+}
+
+#[test]
+fn graph_files_from_stdin_warns_about_missing_paths() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let repo = TempRepo::new();
+    write_three_file_fixture(&repo);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sem"))
+        .args(["graph", ".", "--json", "--files-from", "-"])
+        .current_dir(&repo.path)
+        .env("SEM_CACHE_DIR", &repo.cache_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run sem graph");
+    child
+        .stdin
+        .take()
+        .expect("stdin")
+        .write_all(b"src/c.py\n\nsrc/gone.py\n")
+        .expect("write stdin");
+    let output = child.wait_with_output().expect("wait for sem graph");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "sem graph failed: {stderr}");
+    assert!(stderr.contains("src/gone.py"), "{stderr}");
+    let graph: Value = serde_json::from_slice(&output.stdout).expect("parse graph json");
+    assert_eq!(graph_file_paths(&graph), vec!["src/c.py"]);
+}
+
+#[test]
+fn graph_orphans_flags_unreferenced_helpers_only() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("lib.rs"),
+        r#"
+fn used_helper() -> i32 {
+    1
+}
+
+fn unused_helper() -> i32 {
+    2
+}
+
+pub fn public_api() -> i32 {
+    3
+}
+
+fn main() {
+    println!("{}", used_helper());
+}
+"#,
+    )
+    .expect("write fixture");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let orphan_names = |extra: &[&str]| {
+        let mut args = vec!["graph", ".", "--json", "--no-cache", "--orphans"];
+        args.extend_from_slice(extra);
+        let stdout = run_sem_graph_json_stdout_with_args(&repo.path, &args, None);
+        let report: Value = serde_json::from_str(&stdout).expect("parse orphans json");
+        report["orphans"]
+            .as_array()
+            .expect("orphans array")
+            .iter()
+            .map(|finding| {
+                let confidence = finding["confidence"].as_f64().expect("confidence");
+                assert!(confidence > 0.0 && confidence < 1.0, "{finding}");
+                finding["name"].as_str().expect("orphan name").to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(orphan_names(&[]), vec!["unused_helper"]);
+    assert_eq!(
+        orphan_names(&["--include-exported"]),
+        vec!["unused_helper", "public_api"]
+    );
+    assert!(orphan_names(&["--min-confidence", "0.9"]).is_empty());
+}
+
+#[test]
+fn graph_duplicates_groups_structurally_identical_entities() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("a.py"),
+        r#"def total(items):
+    count = 0
+    for item in items:
+        count += item
+    return count
+
+
+class Point:
+    def norm(self):
+        x = self.x
+        return x * x
+"#,
+    )
+    .expect("write a.py");
+    // The same function renamed, reformatted and commented, the same class,
+    // and a function that matches nothing.
+    fs::write(
+        repo.path.join("b.py"),
+        r#"def add_up(items):
+    # sum the items
+    count = 0
+    for item in items:
+        count   +=   item
+    return count
+
+
+class Point:
+    def norm(self):
+        x = self.x
+        return x * x
+
+
+def unrelated(items):
+    return len(items) + 1
+"#,
+    )
+    .expect("write b.py");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let groups = |stdout: &str| {
+        let report: Value = serde_json::from_str(stdout).expect("parse duplicates json");
+        report["duplicates"]
+            .as_array()
+            .expect("duplicates array")
+            .iter()
+            .map(|group| {
+                group["clones"]
+                    .as_array()
+                    .expect("clones array")
+                    .iter()
+                    .map(|clone| {
+                        format!(
+                            "{}:{}",
+                            clone["file"].as_str().expect("clone file"),
+                            clone["name"].as_str().expect("clone name")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    // Identical classes form one group; their identical `norm` methods
+    // aren't reported again on their own.
+    let expected = vec![
+        vec!["a.py:total", "b.py:add_up"],
+        vec!["a.py:Point", "b.py:Point"],
+    ];
+
+    let fresh = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &["graph", ".", "--json", "--no-cache", "--duplicates"],
+        None,
+    );
+    assert_eq!(groups(&fresh), expected);
+
+    for _ in 0..2 {
+        let cached = run_sem_graph_json_stdout_with_args(
+            &repo.path,
+            &["graph", ".", "--json", "--duplicates"],
+            Some(&repo.cache_path),
+        );
+        assert_eq!(groups(&cached), expected);
+    }
+}
+
+#[test]
+fn graph_public_only_keeps_the_public_surface() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("lib.rs"),
+        r#"
+pub fn api() -> i32 {
+    helper()
+}
+
+fn helper() -> i32 {
+    1
+}
+
+mod inner {
+    pub fn hidden() -> i32 {
+        2
+    }
+}
+"#,
+    )
+    .expect("write fixture");
+    fs::write(
+        repo.path.join("service.ts"),
+        r#"export class Service {
+  run() {
+    return local();
+  }
+  private reset() {}
+}
+
+function local() {
+  return 1;
+}
+"#,
+    )
+    .expect("write fixture");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let stdout = run_sem_graph_json_stdout_with_args(
+        &repo.path,
+        &["graph", ".", "--json", "--no-cache", "--public-only"],
+        None,
+    );
+    let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+    let names: Vec<String> = graph["entities"]
+        .as_array()
+        .expect("entities array")
+        .iter()
+        .map(|entity| entity["name"].as_str().expect("entity name").to_owned())
+        .collect();
+    assert_eq!(sorted(&names), vec!["Service", "api", "run"]);
+    assert!(graph["edges"].as_array().expect("edges array").is_empty());
+}
+
+#[test]
+fn graph_respects_semignore_paths_and_entity_types() {
+    let repo = TempRepo::new();
+    fs::write(
+        repo.path.join("store.ts"),
+        r#"export class Store {
+  count = 0;
+  bump() {
+    return this.count + 1;
+  }
+}
+"#,
+    )
+    .expect("write store");
+    fs::write(
+        repo.path.join("store.test.ts"),
+        r#"import { Store } from "./store";
+export function check() {
+  return new Store().bump();
+}
+"#,
+    )
+    .expect("write test");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let entity_ids = || {
+        let graph: Value = serde_json::from_str(&run_sem_graph_json_stdout_with_args(
+            &repo.path,
+            &["graph", ".", "--json", "--no-cache"],
+            None,
+        ))
+        .expect("parse graph json");
+        let mut ids: Vec<String> = graph["entities"]
+            .as_array()
+            .expect("entities array")
+            .iter()
+            .map(|entity| entity["id"].as_str().expect("entity id").to_owned())
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert!(entity_ids().contains(&"store.test.ts::function::check".to_string()));
+    assert!(entity_ids().contains(&"store.ts::class::Store::count".to_string()));
+
+    fs::write(
+        repo.path.join(".semignore"),
+        "*.test.ts
+type:field
+",
+    )
+    .expect("write .semignore");
+    assert_eq!(
+        entity_ids(),
+        vec!["store.ts::class::Store", "store.ts::class::Store::bump"]
+    );
+}
+
+#[test]
+fn graph_path_restricts_walk_to_subtrees() {
+    let repo = TempRepo::new();
+    for (file, body) in [
+        ("src/a/one.py", "def one():\n    return 1\n"),
+        ("src/b/two.py", "def two():\n    return 2\n"),
+        ("src/c/three.py", "def three():\n    return 3\n"),
+    ] {
+        let path = repo.path.join(file);
+        fs::create_dir_all(path.parent().unwrap()).expect("create fixture dir");
+        fs::write(path, body).expect("write fixture");
+    }
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let entity_ids = |args: &[&str]| {
+        let stdout = run_sem_graph_json_stdout_with_args(&repo.path, args, Some(&repo.cache_path));
+        let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+        graph["entities"]
+            .as_array()
+            .expect("entities array")
+            .iter()
+            .map(|entity| entity["id"].as_str().expect("entity id").to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    // Warm the full-repo cache first: a subtree run must neither read from
+    // it nor overwrite it.
+    assert_eq!(entity_ids(&["graph", ".", "--json"]).len(), 3);
+    assert_eq!(
+        entity_ids(&["graph", ".", "--json", "--path", "src/a"]),
+        vec!["src/a/one.py::function::one"]
+    );
+    assert_eq!(
+        entity_ids(&["graph", ".", "--json", "--path", "src/a", "--path", "src/c/"]),
+        vec![
+            "src/a/one.py::function::one",
+            "src/c/three.py::function::three"
+        ]
+    );
+    assert_eq!(entity_ids(&["graph", ".", "--json"]).len(), 3);
+}
+
 #[test]
 fn graph_json_is_stable_for_ambiguous_constructor_resolution() {
     let repo = TempRepo::new();
@@ -271,3 +928,118 @@ fn graph_json_cached_incremental_matches_full_for_ambiguous_constructor_resoluti
         );
     }
 }
+
+#[test]
+fn graph_show_skipped_lists_files_without_entities() {
+    let repo = TempRepo::new();
+    fs::write(repo.path.join("app.py"), "def main():\n    return 1\n").expect("write app.py");
+    fs::write(repo.path.join("broken.py"), "def (:\n").expect("write broken.py");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_sem"))
+            .args(args)
+            .current_dir(&repo.path)
+            .env("SEM_CACHE_DIR", &repo.cache_path)
+            .output()
+            .expect("run sem graph")
+    };
+
+    // Unparseable files stay quiet by default; only unreadable ones warn.
+    let quiet = run(&["graph", ".", "--json"]);
+    assert!(quiet.status.success());
+    assert_eq!(String::from_utf8_lossy(&quiet.stderr), "");
+
+    let output = run(&["graph", ".", "--json", "--show-skipped"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("broken.py (parse error: syntax error at line 1)"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("app.py"), "stderr: {stderr}");
+}
+
+#[test]
+fn graph_include_reopens_default_excluded_dirs() {
+    let repo = TempRepo::new();
+    for (file, body) in [
+        ("src/app.py", "def app():\n    return 1\n"),
+        ("dist/bundle.py", "def bundle():\n    return 2\n"),
+        ("vendor/lib.py", "def lib():\n    return 3\n"),
+    ] {
+        let path = repo.path.join(file);
+        fs::create_dir_all(path.parent().unwrap()).expect("create fixture dir");
+        fs::write(path, body).expect("write fixture");
+    }
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let entity_ids = |args: &[&str]| {
+        let stdout = run_sem_graph_json_stdout_with_args(&repo.path, args, Some(&repo.cache_path));
+        let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+        graph["entities"]
+            .as_array()
+            .expect("entities array")
+            .iter()
+            .map(|entity| entity["id"].as_str().expect("entity id").to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        entity_ids(&["graph", ".", "--json"]),
+        vec!["src/app.py::function::app"]
+    );
+    // The full-repo cache from the first run must not hide re-included files.
+    assert_eq!(
+        entity_ids(&["graph", ".", "--json", "--include", "dist/**"]),
+        vec![
+            "dist/bundle.py::function::bundle",
+            "src/app.py::function::app"
+        ]
+    );
+}
+
+#[test]
+fn graph_reports_extends_and_implements_edges() {
+    let repo = TempRepo::new();
+    fs::write(repo.path.join("base.ts"), "export class Model {}\n").expect("write base.ts");
+    fs::write(
+        repo.path.join("user.ts"),
+        "import { Model } from './base';\n\ninterface Auditable {}\n\nexport class User extends Model implements Auditable {}\n",
+    )
+    .expect("write user.ts");
+    run_git(&repo.path, &["add", "-A"]);
+    run_git(&repo.path, &["commit", "-q", "-m", "init"]);
+
+    let stdout = run_sem_graph_json_stdout(&repo.path);
+    let graph: Value = serde_json::from_str(&stdout).expect("parse graph json");
+    let ref_type = |to: &str| {
+        graph["edges"]
+            .as_array()
+            .expect("edges array")
+            .iter()
+            .find(|edge| edge["fromEntity"] == "user.ts::class::User" && edge["toEntity"] == to)
+            .map(|edge| edge["refType"].as_str().expect("refType").to_owned())
+    };
+    assert_eq!(
+        ref_type("base.ts::class::Model").as_deref(),
+        Some("extends")
+    );
+    assert_eq!(
+        ref_type("user.ts::interface::Auditable").as_deref(),
+        Some("implements")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sem"))
+        .args(["graph", ".", "--no-cache"])
+        .current_dir(&repo.path)
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run sem graph");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("△ 1 implements"), "stdout: {stdout}");
+    assert!(stdout.contains("▲ 1 extends"), "stdout: {stdout}");
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+#[test]
+fn grep_json_reports_matches_per_entity() {
+    let repo = TestRepo::new("grep-json");
+    fs::write(
+        repo.path.join("app.py"),
+        "class Store:\n    def load(self):\n        return eval(self.raw)\n\n\ndef run(code):\n    return eval(code)\n",
+    )
+    .expect("write source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    let output = sem(&repo, &["grep", r"eval\(", "--type", "class", "--json"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let hits: serde_json::Value = serde_json::from_slice(&output.stdout).expect("grep json");
+    assert_eq!(
+        hits,
+        serde_json::json!([{
+            "file": "app.py",
+            "name": "Store",
+            "type": "class",
+            "startLine": 1,
+            "endLine": 3,
+            "matches": [{"line": 3, "text": "        return eval(self.raw)"}],
+        }])
+    );
+
+    let output = sem(&repo, &["grep", "no_such_call", "--json"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "[]");
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use std::process::Output;
+
+use common::{sem_command, TestRepo};
+
+fn sem(repo: &TestRepo, args: &[&str]) -> Output {
+    sem_command(repo)
+        .args(args)
+        .env("SEM_CACHE_DIR", repo.home.join("cache"))
+        .output()
+        .expect("run sem")
+}
+
+fn impact_json(repo: &TestRepo, args: &[&str]) -> serde_json::Value {
+    let output = sem(repo, args);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("impact JSON")
+}
+
+fn owners_by_name(entities: &serde_json::Value) -> Vec<(String, String)> {
+    let mut owners: Vec<(String, String)> = entities
+        .as_array()
+        .expect("entity list")
+        .iter()
+        .map(|entity| {
+            (
+                entity["name"].as_str().unwrap().to_string(),
+                entity["owner"]["author"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    owners.sort();
+    owners
+}
+
+fn two_owner_repo() -> TestRepo {
+    let repo = TestRepo::new("impact-blame");
+    repo.commit_as(
+        "Carol",
+        "2024-01-01T10:00:00Z",
+        "Carol edits core.py",
+        "core.py",
+        "def base():\n    return 0\n",
+    );
+    repo.commit_as(
+        "Alice",
+        "2024-01-02T10:00:00Z",
+        "Alice edits alice.py",
+        "alice.py",
+        "from core import base\n\ndef alice_fn():\n    return base() + 1\n",
+    );
+    repo.commit_as(
+        "Bob",
+        "2024-01-03T10:00:00Z",
+        "Bob edits bob.py",
+        "bob.py",
+        "from core import base\n\ndef bob_fn():\n    return base() + 2\n",
+    );
+    repo
+}
+
+#[test]
+fn impact_blame_attaches_owner_to_each_impacted_entity() {
+    let repo = two_owner_repo();
+    let output = impact_json(
+        &repo,
+        &["impact", "base", "--json", "--blame", "--no-cache"],
+    );
+
+    let expected = vec![
+        ("alice_fn".to_string(), "Alice".to_string()),
+        ("bob_fn".to_string(), "Bob".to_string()),
+    ];
+    assert_eq!(owners_by_name(&output["impact"]["entities"]), expected);
+    assert_eq!(owners_by_name(&output["dependents"]), expected);
+    assert!(output["entity"].get("owner").is_none());
+}
+
+#[test]
+fn impact_without_blame_has_no_owner() {
+    let repo = two_owner_repo();
+    let output = impact_json(&repo, &["impact", "base", "--json", "--no-cache"]);
+
+    let entities = output["impact"]["entities"].as_array().unwrap();
+    assert!(!entities.is_empty());
+    assert!(entities.iter().all(|entity| entity.get("owner").is_none()));
+}
+
+#[test]
+fn impact_blame_terminal_shows_owner_column() {
+    let repo = two_owner_repo();
+    let output = sem(
+        &repo,
+        &["impact", "base", "--dependents", "--blame", "--no-cache"],
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let line = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(name))
+            .unwrap_or_else(|| panic!("no line for {name}:\n{stdout}"))
+            .to_string()
+    };
+    assert!(line("alice_fn").contains("Alice ("), "{stdout}");
+    assert!(line("bob_fn").contains("Bob ("), "{stdout}");
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use std::{fs, process::Output};
+
+use common::{git, sem_command, TestRepo};
+
+fn sem(repo: &TestRepo, args: &[&str]) -> Output {
+    let output = sem_command(repo)
+        .args(args)
+        .env("SEM_CACHE_DIR", repo.home.join("cache"))
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("run sem");
+    assert!(
+        output.status.success(),
+        "sem {args:?} failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn stdout(repo: &TestRepo, args: &[&str]) -> String {
+    String::from_utf8(sem(repo, args).stdout).expect("utf-8 output")
+}
+
+fn names(entities: &serde_json::Value) -> Vec<(String, u64)> {
+    entities
+        .as_array()
+        .expect("entity list")
+        .iter()
+        .map(|entity| {
+            (
+                entity["name"].as_str().unwrap().to_string(),
+                entity["depth"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// `top` calls `middle`, which calls `leaf`, which calls `base`.
+fn chain_repo() -> TestRepo {
+    let repo = TestRepo::new("impact-callers-callees");
+    fs::write(
+        repo.path.join("chain.py"),
+        "def base():\n    return 0\n\n\
+         def leaf():\n    return base()\n\n\
+         def middle():\n    return leaf()\n\n\
+         def top():\n    return middle()\n",
+    )
+    .unwrap();
+    git(&repo.path, &["add", "."]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+    repo
+}
+
+#[test]
+fn callers_only_shows_just_the_impact_set() {
+    let repo = chain_repo();
+
+    for cache in [&["--no-cache"][..], &[], &[]] {
+        let mut args = vec!["impact", "leaf", "--callers-only", "--json"];
+        args.extend_from_slice(cache);
+        let output: serde_json::Value =
+            serde_json::from_str(&stdout(&repo, &args)).expect("impact JSON");
+        assert_eq!(output["entity"]["name"], "leaf");
+        assert_eq!(
+            names(&output["impact"]["entities"]),
+            vec![("middle".to_string(), 1), ("top".to_string(), 2)]
+        );
+        for section in ["dependencies", "dependents", "tests"] {
+            assert!(output.get(section).is_none(), "{section} in {output}");
+        }
+    }
+
+    let text = stdout(&repo, &["impact", "leaf", "--callers-only"]);
+    assert!(text.contains("2 entities transitively affected"), "{text}");
+    assert!(!text.contains("depends on"), "{text}");
+    assert!(!text.contains("depended on by"), "{text}");
+}
+
+#[test]
+fn callees_only_shows_just_the_dependencies() {
+    let repo = chain_repo();
+
+    let output: serde_json::Value = serde_json::from_str(&stdout(
+        &repo,
+        &["impact", "top", "--callees-only", "--json"],
+    ))
+    .expect("impact JSON");
+    assert_eq!(output["entity"]["name"], "top");
+    assert_eq!(
+        names(&output["dependencies"]),
+        vec![("middle".to_string(), 1), ("leaf".to_string(), 2)]
+    );
+    for section in ["dependents", "impact", "tests"] {
+        assert!(output.get(section).is_none(), "{section} in {output}");
+    }
+
+    let unlimited: serde_json::Value = serde_json::from_str(&stdout(
+        &repo,
+        &["impact", "top", "--callees-only", "--depth", "0", "--json"],
+    ))
+    .expect("impact JSON");
+    assert_eq!(unlimited["dependencies"].as_array().unwrap().len(), 3);
+
+    let text = stdout(&repo, &["impact", "top", "--callees-only"]);
+    assert!(text.contains("depends on 2 entities (depth 2)"), "{text}");
+    assert!(!text.contains("transitively affected"), "{text}");
+    assert!(!text.contains("depended on by"), "{text}");
+}
@@ -975,3 +975,74 @@ fn impact_all_and_tests_match_no_cache_from_topology_cache() {
         assert!(!phases.iter().any(|phase| phase == "full_graph_build"));
     }
 }
+
+#[test]
+fn impact_keeps_test_callers_out_of_dependents() {
+    let repo = TempDir::new().unwrap();
+    let cache = TempDir::new().unwrap();
+    init_topology_repo(repo.path());
+
+    let dependents = |extra: &[&str]| {
+        let output = assert_success(
+            Command::new(env!("CARGO_BIN_EXE_sem"))
+                .current_dir(repo.path())
+                .env("SEM_NO_SIDECAR", "1")
+                .env("SEM_CACHE_DIR", cache.path())
+                .args([
+                    "impact",
+                    "source",
+                    "--file",
+                    "a.ts",
+                    "--dependents",
+                    "--json",
+                ])
+                .args(extra)
+                .output()
+                .unwrap(),
+            "impact dependents",
+        );
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["dependents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entity| {
+                (
+                    entity["file"].as_str().unwrap().to_string(),
+                    entity["isTest"].as_bool().unwrap_or(false),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Cached, in-memory and test-free graphs agree: the test calling
+    // `source` isn't one of its dependents.
+    for extra in [&[][..], &["--no-cache"], &["--exclude-tests"]] {
+        assert_eq!(
+            dependents(extra),
+            vec![("b.ts".to_string(), false)],
+            "{extra:?}"
+        );
+    }
+
+    // It's still reported as a test affected by `source`.
+    let output = assert_success(
+        Command::new(env!("CARGO_BIN_EXE_sem"))
+            .current_dir(repo.path())
+            .env("SEM_NO_SIDECAR", "1")
+            .env("SEM_CACHE_DIR", cache.path())
+            .args(["impact", "source", "--file", "a.ts", "--tests", "--json"])
+            .output()
+            .unwrap(),
+        "impact tests",
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        json["tests"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|test| test["file"] == "a.test.ts"),
+        "{json}"
+    );
+}
@@ -111,3 +111,70 @@ fn log_limit_is_not_applied_as_result_entry_truncation() {
     assert_eq!(changes[1]["change_type"], "modified (logic)");
     assert_eq!(changes[1]["commit"]["message"], "v4");
 }
+
+#[test]
+fn log_reports_each_commit_that_touched_the_entity() {
+    let repo = init_repo();
+
+    fs::write(
+        repo.path().join("a.py"),
+        "def foo():\n    return 1\n\ndef other():\n    return 0\n",
+    )
+    .expect("write a.py");
+    commit_all(repo.path(), "add foo");
+
+    fs::write(
+        repo.path().join("a.py"),
+        "def foo():\n    return 2\n\ndef other():\n    return 0\n",
+    )
+    .expect("modify a.py");
+    commit_all(repo.path(), "change foo");
+
+    fs::write(
+        repo.path().join("a.py"),
+        "def foo():\n    return 2\n\ndef other():\n    return 1\n",
+    )
+    .expect("modify other");
+    commit_all(repo.path(), "change other");
+
+    fs::write(
+        repo.path().join("a.py"),
+        "def foo(x):\n    return x\n\ndef other():\n    return 1\n",
+    )
+    .expect("modify a.py");
+    commit_all(repo.path(), "change foo again");
+
+    let output = sem_log_json(repo.path(), &["foo", "--file", "a.py"]);
+    assert!(
+        output.status.success(),
+        "sem log failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("parse json");
+    assert_eq!(json["entity"], "foo");
+    assert_eq!(json["file"], "a.py");
+    let changes = json["changes"].as_array().expect("changes array");
+    let summary: Vec<(&str, &str)> = changes
+        .iter()
+        .map(|c| {
+            (
+                c["change_type"].as_str().unwrap(),
+                c["commit"]["message"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("added", "add foo"),
+            ("modified (logic)", "change foo"),
+            ("modified (logic)", "change foo again"),
+        ]
+    );
+    for change in changes {
+        assert_eq!(change["commit"]["author"], "Test User");
+        assert!(change["commit"]["date"].as_str().is_some());
+    }
+}
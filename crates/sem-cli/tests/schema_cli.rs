@@ -0,0 +1,272 @@
+mod common;
+
+use std::fs;
+
+use serde_json::Value;
+
+use common::{git, sem, TestRepo};
+
+fn sem_json(repo: &TestRepo, args: &[&str]) -> Value {
+    let output = sem(repo, args);
+    assert!(
+        output.status.success(),
+        "sem {args:?} failed\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout)
+        .unwrap_or_else(|e| panic!("sem {args:?} printed invalid JSON: {e}"))
+}
+
+/// A committed file with a caller, a callee and a dead function, then a
+/// working-tree edit to the callee and a new function.
+fn repo_with_changes() -> TestRepo {
+    let repo = TestRepo::new("schema");
+    fs::create_dir_all(repo.path.join("src")).unwrap();
+    fs::write(
+        repo.path.join("src/a.ts"),
+        "export function helper(x: number): number {\n  return x + 1;\n}\n\n\
+         export function caller(): number {\n  return helper(1);\n}\n\n\
+         function unused(): number {\n  return 0;\n}\n",
+    )
+    .unwrap();
+    git(&repo.path, &["add", "."]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+    fs::write(
+        repo.path.join("src/a.ts"),
+        "export function helper(x: number): number {\n  return x + 2;\n}\n\n\
+         export function caller(): number {\n  return helper(1);\n}\n\n\
+         function unused(): number {\n  return 0;\n}\n\n\
+         export function added(): number {\n  return caller();\n}\n",
+    )
+    .unwrap();
+    repo
+}
+
+/// Checks `value` against `schema`, covering the subset of JSON Schema
+/// 2020-12 that sem's schemas use. Panics on any other keyword, so a schema
+/// can't silently outgrow the check.
+fn violations(root: &Value, schema: &Value, value: &Value, at: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    for (keyword, expected) in schema {
+        match keyword.as_str() {
+            "$ref" => {
+                let name = expected
+                    .as_str()
+                    .and_then(|r| r.strip_prefix("#/$defs/"))
+                    .expect("only local $defs references");
+                violations(root, &root["$defs"][name], value, at, errors);
+            }
+            "allOf" => {
+                for branch in expected.as_array().unwrap() {
+                    violations(root, branch, value, at, errors);
+                }
+            }
+            "anyOf" => {
+                let matched = expected.as_array().unwrap().iter().any(|branch| {
+                    let mut branch_errors = Vec::new();
+                    violations(root, branch, value, at, &mut branch_errors);
+                    branch_errors.is_empty()
+                });
+                if !matched {
+                    errors.push(format!("{at}: matches no anyOf branch"));
+                }
+            }
+            "type" => {
+                let matched = match expected {
+                    Value::String(name) => has_type(value, name),
+                    Value::Array(names) => names
+                        .iter()
+                        .any(|name| has_type(value, name.as_str().unwrap())),
+                    _ => panic!("bad type keyword {expected}"),
+                };
+                if !matched {
+                    errors.push(format!("{at}: expected type {expected}, got {value}"));
+                }
+            }
+            "enum" => {
+                if !expected.as_array().unwrap().contains(value) {
+                    errors.push(format!("{at}: {value} is not one of {expected}"));
+                }
+            }
+            "const" => {
+                if expected != value {
+                    errors.push(format!("{at}: expected {expected}, got {value}"));
+                }
+            }
+            "required" => {
+                if let Some(object) = value.as_object() {
+                    for key in expected.as_array().unwrap() {
+                        let key = key.as_str().unwrap();
+                        if !object.contains_key(key) {
+                            errors.push(format!("{at}: missing {key}"));
+                        }
+                    }
+                }
+            }
+            "properties" => {
+                if let Some(object) = value.as_object() {
+                    for (key, property) in expected.as_object().unwrap() {
+                        if let Some(field) = object.get(key) {
+                            violations(root, property, field, &format!("{at}.{key}"), errors);
+                        }
+                    }
+                }
+            }
+            "items" => {
+                if let Some(items) = value.as_array() {
+                    for (i, item) in items.iter().enumerate() {
+                        violations(root, expected, item, &format!("{at}[{i}]"), errors);
+                    }
+                }
+            }
+            "minimum" | "maximum" => {
+                if let Some(n) = value.as_f64() {
+                    let bound = expected.as_f64().unwrap();
+                    if (keyword == "minimum" && n < bound) || (keyword == "maximum" && n > bound) {
+                        errors.push(format!("{at}: {n} is outside {keyword} {bound}"));
+                    }
+                }
+            }
+            "minItems" | "maxItems" => {
+                if let Some(items) = value.as_array() {
+                    let bound = expected.as_u64().unwrap() as usize;
+                    if (keyword == "minItems" && items.len() < bound)
+                        || (keyword == "maxItems" && items.len() > bound)
+                    {
+                        errors.push(format!(
+                            "{at}: {} items is outside {keyword} {bound}",
+                            items.len()
+                        ));
+                    }
+                }
+            }
+            "$schema" | "$id" | "$defs" | "title" | "description" => {}
+            other => panic!("schema keyword {other} isn't covered by the test validator"),
+        }
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_u64() || value.is_i64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        other => panic!("unknown type {other}"),
+    }
+}
+
+fn schema(repo: &TestRepo, target: &str) -> Value {
+    let schema = sem_json(repo, &["schema", target]);
+    assert_eq!(
+        schema["$schema"], "https://json-schema.org/draft/2020-12/schema",
+        "sem schema {target} should declare its draft"
+    );
+    schema
+}
+
+fn assert_valid(schema: &Value, value: &Value) {
+    let mut errors = Vec::new();
+    violations(schema, schema, value, "$", &mut errors);
+    assert!(
+        errors.is_empty(),
+        "{} output doesn't match its schema:\n{}\n{value:#}",
+        schema["title"],
+        errors.join("\n")
+    );
+}
+
+#[test]
+fn diff_output_matches_diff_schema() {
+    let repo = repo_with_changes();
+    let schema = schema(&repo, "diff");
+
+    let plain = sem_json(&repo, &["diff", "--format", "json"]);
+    assert!(!plain["changes"].as_array().unwrap().is_empty());
+    assert_valid(&schema, &plain);
+    assert_valid(
+        &schema,
+        &sem_json(&repo, &["diff", "--format", "json", "--with-diff"]),
+    );
+    assert_valid(
+        &schema,
+        &sem_json(&repo, &["diff", "--format", "json", "--include-unchanged"]),
+    );
+    let shown = sem_json(&repo, &["diff", "--commit", "HEAD", "--format", "json"]);
+    assert!(shown["commit"].is_object());
+    assert_valid(&schema, &shown);
+
+    // The check isn't vacuous: a change without an entity id is rejected.
+    let mut broken = plain.clone();
+    broken["changes"][0]
+        .as_object_mut()
+        .unwrap()
+        .remove("entityId");
+    let mut errors = Vec::new();
+    violations(&schema, &schema, &broken, "$", &mut errors);
+    assert_eq!(errors, vec!["$.changes[0]: missing entityId"]);
+}
+
+#[test]
+fn graph_output_matches_graph_schema() {
+    let repo = repo_with_changes();
+    let schema = schema(&repo, "graph");
+
+    let graph = sem_json(&repo, &["graph", ".", "--json", "--no-cache"]);
+    assert!(!graph["edges"].as_array().unwrap().is_empty());
+    assert_valid(&schema, &graph);
+    assert_valid(
+        &schema,
+        &sem_json(&repo, &["graph", ".", "--json", "--cluster-by", "module"]),
+    );
+    assert_valid(
+        &schema,
+        &sem_json(&repo, &["graph", ".", "--json", "--orphans"]),
+    );
+    assert_valid(
+        &schema,
+        &sem_json(&repo, &["graph", ".", "--json", "--duplicates"]),
+    );
+}
+
+#[test]
+fn impact_output_matches_impact_schema() {
+    let repo = repo_with_changes();
+    let schema = schema(&repo, "impact");
+
+    let impact = sem_json(&repo, &["impact", "helper", "--json", "--blame"]);
+    assert!(!impact["dependents"].as_array().unwrap().is_empty());
+    assert_valid(&schema, &impact);
+    for section in [
+        "--deps",
+        "--dependents",
+        "--tests",
+        "--callers-only",
+        "--callees-only",
+    ] {
+        assert_valid(
+            &schema,
+            &sem_json(&repo, &["impact", "helper", "--json", section]),
+        );
+    }
+}
+
+#[test]
+fn blame_output_matches_blame_schema() {
+    let repo = repo_with_changes();
+    let schema = schema(&repo, "blame");
+
+    let blame = sem_json(&repo, &["blame", "src/a.ts", "--json"]);
+    assert!(!blame.as_array().unwrap().is_empty());
+    assert_valid(&schema, &blame);
+    // A blank line between functions falls back to per-line records.
+    let lines = sem_json(&repo, &["blame", "src/a.ts", "--json", "--range", "4:4"]);
+    assert_eq!(lines[0]["line"], 4);
+    assert_valid(&schema, &lines);
+}
@@ -47,3 +47,56 @@ fn diff_stdin_accepts_old_file_path_for_renames() {
     assert!(stdout.contains(r#""filePath":"b.ts""#));
     assert!(stdout.contains(r#""oldFilePath":"a.ts""#));
 }
+
+#[test]
+fn diff_stdin_reports_entity_changes_as_json() {
+    let output = run_sem_diff_stdin(
+        r#"[
+            {"filePath":"src/api.ts","status":"modified","beforeContent":"export function load() { return 1; }\n","afterContent":"export function load() { return 2; }\nexport function save() { return 3; }\n"},
+            {"file_path":"src/old.ts","status":"deleted","before_content":"export function gone() {}\n"}
+        ]"#,
+        &["--format", "json"],
+    );
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut changes: Vec<(&str, &str)> = json["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| {
+            (
+                change["entityId"].as_str().unwrap(),
+                change["changeType"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    changes.sort();
+    assert_eq!(
+        changes,
+        vec![
+            ("src/api.ts::function::load", "modified"),
+            ("src/api.ts::function::save", "added"),
+            ("src/old.ts::function::gone", "deleted"),
+        ]
+    );
+}
+
+#[test]
+fn diff_stdin_rejects_changes_missing_their_content() {
+    let output = run_sem_diff_stdin(
+        r#"[{"filePath":"a.ts","status":"modified","afterContent":"function foo(){}"}]"#,
+        &[],
+    );
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("change 0 (a.ts) is modified but has no beforeContent"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use std::fs;
+
+use common::{git, sem, TestRepo};
+
+#[test]
+fn verify_json_fails_only_on_high_confidence_arity_mismatch() {
+    let repo = TestRepo::new("verify-arity");
+    fs::write(
+        repo.path.join("app.py"),
+        "def helper(a, b):\n    return a + b\n\n\ndef caller():\n    return helper(1)\n",
+    )
+    .expect("write source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    let output = sem(&repo, &["verify", "--json", "--no-cache"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("verify json");
+    let findings = json["findings"].as_array().expect("findings array");
+    assert_eq!(findings.len(), 1, "{json}");
+    assert_eq!(findings[0]["kind"], "arity_mismatch");
+    assert_eq!(findings[0]["callee"], "helper");
+    assert_eq!(findings[0]["actual"], 1);
+    assert!(findings[0]["confidence"].as_f64().unwrap() >= 0.8);
+
+    fs::write(
+        repo.path.join("app.py"),
+        "def helper(a, b):\n    return a + b\n\n\ndef caller():\n    return helper(1, 2)\n",
+    )
+    .expect("write fixed source");
+
+    let output = sem(&repo, &["verify", "--json", "--no-cache"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("verify json");
+    assert_eq!(json["findings"], serde_json::json!([]));
+}
+
+#[test]
+fn verify_strict_reports_literal_type_mismatches() {
+    let repo = TestRepo::new("verify-strict");
+    fs::write(
+        repo.path.join("app.ts"),
+        "function retry(count: number) {}\n\nfunction caller() {\n    retry('three');\n}\n",
+    )
+    .expect("write source");
+    git(&repo.path, &["add", "-A"]);
+    git(&repo.path, &["commit", "-q", "-m", "initial"]);
+
+    let output = sem(&repo, &["verify", "--json", "--no-cache"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("verify json");
+    assert_eq!(json["findings"], serde_json::json!([]));
+
+    let output = sem(&repo, &["verify", "--json", "--no-cache", "--strict"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("verify json");
+    let findings = json["findings"].as_array().expect("findings array");
+    assert_eq!(findings.len(), 1, "{json}");
+    assert_eq!(findings[0]["kind"], "type_mismatch");
+    assert_eq!(findings[0]["expected"], "number");
+    assert_eq!(findings[0]["argument"], 1);
+    assert_eq!(findings[0]["found"], "string literal");
+}
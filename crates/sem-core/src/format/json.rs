@@ -13,7 +13,9 @@ impl Serialize for DiffJsonEnvelope<'_> {
     where
         S: Serializer,
     {
-        let field_count = if self.include_binary_changes { 3 } else { 2 };
+        let field_count = 2
+            + usize::from(self.include_binary_changes)
+            + usize::from(!self.result.unchanged.is_empty());
         let mut fields = serializer.serialize_struct("DiffJsonEnvelope", field_count)?;
         fields.serialize_field(
             "summary",
@@ -27,6 +29,9 @@ impl Serialize for DiffJsonEnvelope<'_> {
         if self.include_binary_changes {
             fields.serialize_field("binaryChanges", &BinaryChangesJson(self.binary_changes))?;
         }
+        if !self.result.unchanged.is_empty() {
+            fields.serialize_field("unchanged", &self.result.unchanged)?;
+        }
         fields.end()
     }
 }
@@ -42,7 +47,14 @@ impl Serialize for DiffJsonSummary<'_> {
     where
         S: Serializer,
     {
-        let field_count = if self.include_binary_count { 10 } else { 9 };
+        let skipped_files = self
+            .result
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_read_skip())
+            .count();
+        let field_count =
+            9 + usize::from(self.include_binary_count) + usize::from(skipped_files > 0);
         let mut fields = serializer.serialize_struct("DiffJsonSummary", field_count)?;
         fields.serialize_field("fileCount", &(self.result.file_count + self.binary_count))?;
         fields.serialize_field("added", &self.result.added_count)?;
@@ -56,6 +68,10 @@ impl Serialize for DiffJsonSummary<'_> {
         }
         fields.serialize_field("orphan", &self.result.orphan_count)?;
         fields.serialize_field("total", &(self.result.changes.len() + self.binary_count))?;
+        // Only present when files were left unread (too large, not UTF-8).
+        if skipped_files > 0 {
+            fields.serialize_field("skippedFiles", &skipped_files)?;
+        }
         fields.end()
     }
 }
@@ -83,7 +99,7 @@ impl Serialize for SemanticChangeJson<'_> {
         S: Serializer,
     {
         let change = self.0;
-        let mut fields = serializer.serialize_struct("SemanticChangeJson", 17)?;
+        let mut fields = serializer.serialize_struct("SemanticChangeJson", 19)?;
         fields.serialize_field("entityId", &change.entity_id)?;
         fields.serialize_field("changeType", &change.change_type)?;
         fields.serialize_field("entityType", &change.entity_type)?;
@@ -101,6 +117,8 @@ impl Serialize for SemanticChangeJson<'_> {
         fields.serialize_field("commitSha", &change.commit_sha)?;
         fields.serialize_field("author", &change.author)?;
         fields.serialize_field("structuralChange", &change.structural_change)?;
+        fields.serialize_field("signatureChange", &change.signature_change)?;
+        fields.serialize_field("stableId", &change.stable_id)?;
         fields.end()
     }
 }
@@ -233,6 +251,8 @@ mod tests {
                 author: Some("Ada".to_string()),
                 timestamp: Some("2026-05-26".to_string()),
                 structural_change: Some(true),
+                signature_change: Some(true),
+                stable_id: Some("5d41402abc4b2a76".to_string()),
             }],
             file_count: 1,
             added_count: 0,
@@ -244,6 +264,8 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 1,
             total_entities_after: 1,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
 
         let value = diff_json_value(&result);
@@ -283,6 +305,8 @@ mod tests {
                     "commitSha": "abc123",
                     "author": "Ada",
                     "structuralChange": true,
+                    "signatureChange": true,
+                    "stableId": "5d41402abc4b2a76",
                 }],
             })
         );
@@ -302,6 +326,8 @@ mod tests {
             orphan_count: 0,
             total_entities_before: 0,
             total_entities_after: 0,
+            unchanged: Vec::new(),
+            diagnostics: Vec::new(),
         };
         let binary_changes = vec![BinaryFileChange {
             file_path: "pic.png".to_string(),
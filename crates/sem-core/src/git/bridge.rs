@@ -1,14 +1,24 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 
-use git2::{Blame, Delta, Diff, DiffFindOptions, DiffOptions, ErrorCode, Oid, Repository};
+use git2::{
+    Blame, Delta, Diff, DiffFindOptions, DiffOptions, ErrorCode, Oid, Pathspec, PathspecFlags,
+    Repository,
+};
 use thiserror::Error;
 
 use super::types::BlameLineInfo;
-use super::types::{CommitInfo, DiffScope, FileChange, FileCommitInfo, FileStatus};
+use super::types::{CommitFilter, CommitInfo, DiffScope, FileChange, FileCommitInfo, FileStatus};
+use crate::model::diagnostic::{ParseDiagnostic, SkipReason};
+use crate::utils::source::DEFAULT_MAX_FILE_SIZE;
+
+/// Rename candidates considered when `diff.renameLimit` isn't set; git's
+/// own default.
+const DEFAULT_RENAME_LIMIT: usize = 1000;
 
 #[derive(Error, Debug)]
 pub enum GitError {
@@ -36,6 +46,13 @@ pub struct GitBridge {
     /// unaffected, so libgit2 keeps doing everything except ref resolution,
     /// which routes through the git CLI instead.
     cli_refs: bool,
+    /// File contents over this many bytes are left unread; see
+    /// [`GitBridge::with_max_file_size`].
+    max_file_size: u64,
+    skipped_files: Mutex<Vec<ParseDiagnostic>>,
+    /// Whether CRLF and CR line endings read from git become LF; see
+    /// [`GitBridge::with_eol_normalize`].
+    eol_normalize: bool,
 }
 
 impl GitBridge {
@@ -66,9 +83,52 @@ impl GitBridge {
             repo_root,
             cwd,
             cli_refs,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            skipped_files: Mutex::new(Vec::new()),
+            eol_normalize: true,
         })
     }
 
+    /// Leave file contents over `bytes` unread, in the index, the working
+    /// tree and every revision. Such a file's side of a [`FileChange`] is
+    /// `None` and the file is recorded (see [`GitBridge::take_skipped_files`]).
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Keep CRLF and CR line endings in the contents read, instead of turning
+    /// them into LF (`--no-eol-normalize`).
+    pub fn with_eol_normalize(mut self, enabled: bool) -> Self {
+        self.eol_normalize = enabled;
+        self
+    }
+
+    /// Files left unread for their size since the last call, one per file,
+    /// sorted by path.
+    pub fn take_skipped_files(&self) -> Vec<ParseDiagnostic> {
+        let mut skipped = std::mem::take(&mut *self.skipped_files.lock().unwrap());
+        skipped.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        skipped.dedup_by(|a, b| a.file_path == b.file_path);
+        skipped
+    }
+
+    /// Whether a `size`-byte version of `file_path` is within the size limit;
+    /// records the file when it isn't.
+    fn within_size_limit(&self, file_path: &str, size: u64) -> bool {
+        if size <= self.max_file_size {
+            return true;
+        }
+        self.skipped_files.lock().unwrap().push(ParseDiagnostic {
+            file_path: file_path.to_string(),
+            reason: SkipReason::TooLarge {
+                size,
+                limit: self.max_file_size,
+            },
+        });
+        false
+    }
+
     /// Resolve a refspec to an object id via the git CLI. Used when refs live
     /// in a backend libgit2 can't read (reftable): real git resolves the ref,
     /// then everything downstream proceeds through libgit2's ODB by OID.
@@ -264,6 +324,122 @@ impl GitBridge {
         Ok(files)
     }
 
+    /// Changed files for `from..to`, counting only the non-merge commits that
+    /// match `filter`. Their changes are unioned per file: the before side is
+    /// the file just before the first matching commit that touched it, the
+    /// after side the file at the last one.
+    pub fn get_range_diff_files_filtered(
+        &self,
+        from: &str,
+        to: &str,
+        filter: &CommitFilter,
+        pathspecs: &[String],
+    ) -> Result<Vec<FileChange>, GitError> {
+        struct Pending {
+            original_path: String,
+            file_path: String,
+            before_tree: Option<Oid>,
+            after_tree: Oid,
+            existed_before: bool,
+            exists_after: bool,
+        }
+
+        let from_oid = self.resolve_object(from)?.peel_to_commit()?.id();
+        let to_oid = self.resolve_object(to)?.peel_to_commit()?.id();
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(to_oid)?;
+        revwalk.hide(from_oid)?;
+
+        let mut pending: Vec<Pending> = Vec::new();
+        let mut by_path: HashMap<String, usize> = HashMap::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            let author = commit.author();
+            if commit.parent_count() > 1
+                || !filter.matches(
+                    author.name().unwrap_or(""),
+                    author.email().unwrap_or(""),
+                    author.when().seconds(),
+                )
+            {
+                continue;
+            }
+
+            let tree = commit.tree()?;
+            let parent_tree = if commit.parent_count() > 0 {
+                Some(commit.parent(0)?.tree()?)
+            } else {
+                None
+            };
+            for file in self.tree_diff_files(parent_tree.as_ref(), &tree, pathspecs)? {
+                let source_path = file.old_file_path.unwrap_or_else(|| file.file_path.clone());
+                let exists_after = file.status != FileStatus::Deleted;
+                let index = match by_path.remove(&source_path) {
+                    Some(index) => {
+                        let entry = &mut pending[index];
+                        entry.file_path = file.file_path;
+                        entry.after_tree = tree.id();
+                        entry.exists_after = exists_after;
+                        index
+                    }
+                    None => {
+                        pending.push(Pending {
+                            original_path: source_path,
+                            file_path: file.file_path,
+                            before_tree: parent_tree.as_ref().map(|t| t.id()),
+                            after_tree: tree.id(),
+                            existed_before: file.status != FileStatus::Added,
+                            exists_after,
+                        });
+                        pending.len() - 1
+                    }
+                };
+                by_path.insert(pending[index].file_path.clone(), index);
+            }
+        }
+
+        let mut files = Vec::new();
+        for entry in pending {
+            let (status, file_path, old_file_path) =
+                match (entry.existed_before, entry.exists_after) {
+                    (false, false) => continue,
+                    (false, true) => (FileStatus::Added, entry.file_path, None),
+                    (true, false) => (FileStatus::Deleted, entry.original_path, None),
+                    (true, true) if entry.original_path != entry.file_path => (
+                        FileStatus::Renamed,
+                        entry.file_path,
+                        Some(entry.original_path),
+                    ),
+                    (true, true) => (FileStatus::Modified, entry.file_path, None),
+                };
+            let before_content = match (status != FileStatus::Added, entry.before_tree) {
+                (true, Some(oid)) => {
+                    let tree = self.repo.find_tree(oid)?;
+                    let path = old_file_path.as_deref().unwrap_or(&file_path);
+                    self.read_blob_from_tree(&tree, path)
+                }
+                _ => None,
+            };
+            let after_content = if status != FileStatus::Deleted {
+                let tree = self.repo.find_tree(entry.after_tree)?;
+                self.read_blob_from_tree(&tree, &file_path)
+            } else {
+                None
+            };
+            files.push(FileChange {
+                file_path,
+                status,
+                old_file_path,
+                before_content,
+                after_content,
+            });
+        }
+
+        files.retain(|f| !f.file_path.starts_with(".sem/"));
+        Ok(files)
+    }
+
     /// True when this repo uses a sparse checkout. libgit2 cannot read a
     /// sparse index (`unsupported mandatory extension: 'sdir'`), and even when
     /// the index is readable, its workdir diff reports sparse-excluded files as
@@ -432,7 +608,7 @@ impl GitBridge {
         let mut diff =
             self.repo
                 .diff_tree_to_index(base_tree, Some(&self.repo.index()?), Some(&mut opts))?;
-        Self::detect_renames(&mut diff)?;
+        self.detect_renames(&mut diff)?;
 
         Ok(self.diff_to_file_changes(&diff))
     }
@@ -454,7 +630,7 @@ impl GitBridge {
                 .diff_tree_to_workdir_with_index(Some(head_tree), Some(&mut opts))?,
             None => self.repo.diff_index_to_workdir(None, Some(&mut opts))?,
         };
-        Self::detect_renames(&mut diff)?;
+        self.detect_renames(&mut diff)?;
         self.apply_index_rename_map(
             self.diff_to_file_changes(&diff),
             head_tree.as_ref(),
@@ -555,6 +731,22 @@ impl GitBridge {
         Ok(obj.peel_to_commit()?.parent_count())
     }
 
+    /// Sha, author, date and message of a single commit, for `git show`-style
+    /// headers.
+    pub fn commit_info(&self, sha: &str) -> Result<CommitInfo, GitError> {
+        let oid = self.resolve_object(sha)?.peel_to_commit()?.id();
+        let commit = self.repo.find_commit(oid)?;
+        let author = commit.author();
+        let sha = oid.to_string();
+        Ok(CommitInfo {
+            short_sha: sha[..7.min(sha.len())].to_string(),
+            sha,
+            author: author.name().unwrap_or("unknown").to_string(),
+            date: commit.time().seconds().to_string(),
+            message: commit.message().unwrap_or("").to_string(),
+        })
+    }
+
     fn get_commit_diff_files(
         &self,
         sha: &str,
@@ -570,13 +762,7 @@ impl GitBridge {
             None
         };
 
-        let mut opts = self.make_diff_opts(pathspecs)?;
-        let mut diff =
-            self.repo
-                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
-        Self::detect_renames(&mut diff)?;
-
-        Ok(self.diff_to_file_changes(&diff))
+        self.tree_diff_files(parent_tree.as_ref(), &tree, pathspecs)
     }
 
     fn get_range_diff_files(
@@ -591,13 +777,72 @@ impl GitBridge {
         let from_tree = from_obj.peel_to_commit()?.tree()?;
         let to_tree = to_obj.peel_to_commit()?.tree()?;
 
+        self.tree_diff_files(Some(&from_tree), &to_tree, pathspecs)
+    }
+
+    /// Changed files between two trees, with renames paired up. The diff is
+    /// limited to the pathspecs, which would drop the side of a rename
+    /// outside them, so when the scoped diff adds or deletes files the
+    /// deletions or additions elsewhere in the tree are pulled in as rename
+    /// candidates. Pathspecs are applied again after rename detection.
+    fn tree_diff_files(
+        &self,
+        old_tree: Option<&git2::Tree<'_>>,
+        new_tree: &git2::Tree<'_>,
+        pathspecs: &[String],
+    ) -> Result<Vec<FileChange>, GitError> {
+        let specs = self.normalize_pathspecs(pathspecs)?;
+        if specs.is_empty() {
+            let mut diff = self
+                .repo
+                .diff_tree_to_tree(old_tree, Some(new_tree), None)?;
+            self.detect_renames(&mut diff)?;
+            return Ok(self.diff_to_file_changes(&diff));
+        }
+
         let mut opts = self.make_diff_opts(pathspecs)?;
-        let mut diff =
-            self.repo
-                .diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut opts))?;
-        Self::detect_renames(&mut diff)?;
+        let mut diff = self
+            .repo
+            .diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut opts))?;
+        let wants_sources = diff.deltas().any(|delta| delta.status() == Delta::Added);
+        let wants_targets = diff.deltas().any(|delta| delta.status() == Delta::Deleted);
+        if wants_sources || wants_targets {
+            let whole = self
+                .repo
+                .diff_tree_to_tree(old_tree, Some(new_tree), None)?;
+            let candidates: Vec<&Path> = whole
+                .deltas()
+                .filter_map(|delta| match delta.status() {
+                    Delta::Deleted if wants_sources => delta.old_file().path(),
+                    Delta::Added if wants_targets => delta.new_file().path(),
+                    _ => None,
+                })
+                .collect();
+            if !candidates.is_empty() {
+                let mut candidate_opts = DiffOptions::new();
+                candidate_opts.disable_pathspec_match(true);
+                for path in &candidates {
+                    candidate_opts.pathspec(path);
+                }
+                let candidate_diff = self.repo.diff_tree_to_tree(
+                    old_tree,
+                    Some(new_tree),
+                    Some(&mut candidate_opts),
+                )?;
+                diff.merge(&candidate_diff)?;
+            }
+        }
+        self.detect_renames(&mut diff)?;
 
-        Ok(self.diff_to_file_changes(&diff))
+        let pathspec = Pathspec::new(specs.iter().map(String::as_str))?;
+        let matches = |path: &str| pathspec.matches_path(Path::new(path), PathspecFlags::DEFAULT);
+        Ok(self
+            .diff_to_file_changes(&diff)
+            .into_iter()
+            .filter(|file| {
+                matches(&file.file_path) || file.old_file_path.as_deref().is_some_and(matches)
+            })
+            .collect())
     }
 
     fn get_ref_to_working_diff_files(
@@ -610,13 +855,23 @@ impl GitBridge {
         let mut diff = self
             .repo
             .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut opts))?;
-        Self::detect_renames(&mut diff)?;
+        self.detect_renames(&mut diff)?;
         self.apply_index_rename_map(self.diff_to_file_changes(&diff), Some(&tree), pathspecs)
     }
 
-    fn detect_renames(diff: &mut Diff) -> Result<(), GitError> {
+    /// Pair deleted and added files into renames. Honors `diff.renameLimit`,
+    /// defaulting to git's 1000 candidates rather than libgit2's 200, past
+    /// which a large range silently reports every rename as add + delete.
+    fn detect_renames(&self, diff: &mut Diff) -> Result<(), GitError> {
+        let rename_limit = self
+            .repo
+            .config()
+            .and_then(|config| config.get_i64("diff.renameLimit"))
+            .ok()
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(DEFAULT_RENAME_LIMIT);
         let mut opts = DiffFindOptions::new();
-        opts.renames(true);
+        opts.renames(true).rename_limit(rename_limit);
         diff.find_similar(Some(&mut opts))?;
         Ok(())
     }
@@ -784,8 +1039,8 @@ impl GitBridge {
         Ok(commit.tree()?)
     }
 
-    fn normalize_line_endings(s: String) -> String {
-        if s.contains('\r') {
+    fn normalize_line_endings(&self, s: String) -> String {
+        if self.eol_normalize && s.contains('\r') {
             s.replace("\r\n", "\n").replace('\r', "\n")
         } else {
             s
@@ -795,24 +1050,31 @@ impl GitBridge {
     fn read_blob_from_tree(&self, tree: &git2::Tree, file_path: &str) -> Option<String> {
         let entry = tree.get_path(Path::new(file_path)).ok()?;
         let blob = self.repo.find_blob(entry.id()).ok()?;
+        if !self.within_size_limit(file_path, blob.size() as u64) {
+            return None;
+        }
         let bytes = blob.content();
         if blob.is_binary() || Self::bytes_look_binary(bytes, true) {
             return None;
         }
         std::str::from_utf8(bytes)
             .ok()
-            .map(|s| Self::normalize_line_endings(s.to_string()))
+            .map(|s| self.normalize_line_endings(s.to_string()))
     }
 
     fn read_working_file(&self, file_path: &str) -> Option<String> {
         let full_path = self.repo_root.join(file_path);
+        let size = fs::metadata(&full_path).ok()?.len();
+        if !self.within_size_limit(file_path, size) {
+            return None;
+        }
         let bytes = fs::read(full_path).ok()?;
         if Self::bytes_look_binary(&bytes, true) {
             return None;
         }
         String::from_utf8(bytes)
             .ok()
-            .map(Self::normalize_line_endings)
+            .map(|s| self.normalize_line_endings(s))
     }
 
     fn read_index_file(&self, file_path: &str) -> Option<String> {
@@ -822,13 +1084,16 @@ impl GitBridge {
         };
         let entry = index.get_path(Path::new(file_path), 0)?;
         let blob = self.repo.find_blob(entry.id).ok()?;
+        if !self.within_size_limit(file_path, blob.size() as u64) {
+            return None;
+        }
         let bytes = blob.content();
         if blob.is_binary() || Self::bytes_look_binary(bytes, true) {
             return None;
         }
         std::str::from_utf8(bytes)
             .ok()
-            .map(|s| Self::normalize_line_endings(s.to_string()))
+            .map(|s| self.normalize_line_endings(s.to_string()))
     }
 
     /// Read a file's staged (index) content via `git show :path`. Used when
@@ -841,12 +1106,15 @@ impl GitBridge {
             .arg(format!(":{file_path}"))
             .output()
             .ok()?;
-        if !output.status.success() || Self::bytes_look_binary(&output.stdout, true) {
+        if !output.status.success()
+            || !self.within_size_limit(file_path, output.stdout.len() as u64)
+            || Self::bytes_look_binary(&output.stdout, true)
+        {
             return None;
         }
         String::from_utf8(output.stdout)
             .ok()
-            .map(Self::normalize_line_endings)
+            .map(|s| self.normalize_line_endings(s))
     }
 
     /// Read file content at a specific git ref (commit SHA, branch, tag, etc.)
@@ -1230,6 +1498,7 @@ fn parse_blame_porcelain(output: &str) -> Vec<BlameLineInfo> {
 
         let mut author = String::new();
         let mut author_time = None;
+        let mut author_tz_offset_minutes = None;
         let mut summary = String::new();
 
         while index < lines.len() {
@@ -1242,6 +1511,8 @@ fn parse_blame_porcelain(output: &str) -> Vec<BlameLineInfo> {
                 author = value.to_string();
             } else if let Some(value) = line.strip_prefix("author-time ") {
                 author_time = value.parse::<i64>().ok();
+            } else if let Some(value) = line.strip_prefix("author-tz ") {
+                author_tz_offset_minutes = parse_tz_offset(value);
             } else if let Some(value) = line.strip_prefix("summary ") {
                 summary = value.to_string();
             }
@@ -1267,6 +1538,7 @@ fn parse_blame_porcelain(output: &str) -> Vec<BlameLineInfo> {
             commit_sha,
             author,
             author_time,
+            author_tz_offset_minutes,
             summary,
         });
     }
@@ -1275,6 +1547,22 @@ fn parse_blame_porcelain(output: &str) -> Vec<BlameLineInfo> {
     parsed
 }
 
+/// Parse a git `±HHMM` timezone into minutes east of UTC.
+fn parse_tz_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 60 + minutes))
+}
+
 fn parse_blame_header(line: &str) -> Option<(&str, usize)> {
     let mut parts = line.split_whitespace();
     let sha = parts.next()?;
@@ -1506,6 +1794,17 @@ mod tests {
     use tempfile::TempDir;
 
     fn commit_file(repo: &Repository, file_path: &str, contents: &str, message: &str) -> Oid {
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        commit_file_as(repo, file_path, contents, message, &sig)
+    }
+
+    fn commit_file_as(
+        repo: &Repository,
+        file_path: &str,
+        contents: &str,
+        message: &str,
+        sig: &Signature,
+    ) -> Oid {
         fs::write(repo.workdir().unwrap().join(file_path), contents).unwrap();
 
         let mut index = repo.index().unwrap();
@@ -1514,16 +1813,15 @@ mod tests {
 
         let tree_id = index.write_tree().unwrap();
         let tree = repo.find_tree(tree_id).unwrap();
-        let sig = Signature::now("Test User", "test@example.com").unwrap();
 
         match repo.head() {
             Ok(head) => {
                 let parent = repo.find_commit(head.target().unwrap()).unwrap();
-                repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                repo.commit(Some("HEAD"), sig, sig, message, &tree, &[&parent])
                     .unwrap()
             }
             Err(_) => repo
-                .commit(Some("HEAD"), &sig, &sig, message, &tree, &[])
+                .commit(Some("HEAD"), sig, sig, message, &tree, &[])
                 .unwrap(),
         }
     }
@@ -1556,6 +1854,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn filtered_range_unions_changes_from_matching_commits() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let alice = |seconds| {
+            Signature::new("Alice", "alice@example.com", &git2::Time::new(seconds, 0)).unwrap()
+        };
+        let bob = |seconds| {
+            Signature::new("Bob", "bob@example.com", &git2::Time::new(seconds, 0)).unwrap()
+        };
+
+        let base = commit_file_as(
+            &repo,
+            "a.py",
+            "def a():\n    return 0\n",
+            "base",
+            &alice(100),
+        );
+        commit_file_as(&repo, "b.py", "def b():\n    return 0\n", "b v0", &bob(200));
+        commit_file_as(
+            &repo,
+            "a.py",
+            "def a():\n    return 1\n",
+            "a v1",
+            &alice(300),
+        );
+        commit_file_as(&repo, "b.py", "def b():\n    return 2\n", "b v2", &bob(400));
+        commit_file_as(&repo, "c.py", "def c():\n    return 3\n", "c", &alice(500));
+
+        let bridge = GitBridge::open(temp.path()).unwrap();
+        let base = base.to_string();
+        let range = |filter: CommitFilter| {
+            let mut files = bridge
+                .get_range_diff_files_filtered(&base, "HEAD", &filter, &[])
+                .unwrap();
+            files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+            files
+        };
+
+        let by_bob = range(CommitFilter {
+            author: Some("Bob".into()),
+            since: None,
+        });
+        assert_eq!(by_bob.len(), 1);
+        assert_eq!(by_bob[0].file_path, "b.py");
+        assert_eq!(by_bob[0].status, FileStatus::Added);
+        assert_eq!(
+            by_bob[0].after_content.as_deref(),
+            Some("def b():\n    return 2\n")
+        );
+
+        let by_alice = range(CommitFilter {
+            author: Some("alice@example.com".into()),
+            since: None,
+        });
+        let paths: Vec<_> = by_alice.iter().map(|f| f.file_path.as_str()).collect();
+        assert_eq!(paths, ["a.py", "c.py"]);
+        assert_eq!(by_alice[0].status, FileStatus::Modified);
+        assert_eq!(
+            by_alice[0].before_content.as_deref(),
+            Some("def a():\n    return 0\n")
+        );
+
+        let recent_alice = range(CommitFilter {
+            author: Some("Alice".into()),
+            since: Some(400),
+        });
+        let paths: Vec<_> = recent_alice.iter().map(|f| f.file_path.as_str()).collect();
+        assert_eq!(paths, ["c.py"]);
+
+        let recent = range(CommitFilter {
+            author: None,
+            since: Some(300),
+        });
+        let paths: Vec<_> = recent.iter().map(|f| f.file_path.as_str()).collect();
+        assert_eq!(paths, ["a.py", "b.py", "c.py"]);
+        assert_eq!(recent[1].status, FileStatus::Modified);
+        assert_eq!(
+            recent[1].before_content.as_deref(),
+            Some("def b():\n    return 0\n")
+        );
+    }
+
     #[test]
     fn porcelain_blame_reports_uncommitted_lines() {
         let temp = TempDir::new().unwrap();
@@ -1572,6 +1953,24 @@ mod tests {
         assert_eq!(blame[1].author, "Not Committed Yet");
     }
 
+    #[test]
+    fn porcelain_blame_parses_author_timezone() {
+        let output = "\
+1111111111111111111111111111111111111111 1 1 1
+author Ada
+author-time 1700000000
+author-tz -0530
+summary init
+\tdef foo():
+";
+        let blame = parse_blame_porcelain(output);
+
+        assert_eq!(blame[0].author_time, Some(1_700_000_000));
+        assert_eq!(blame[0].author_tz_offset_minutes, Some(-330));
+        assert_eq!(parse_tz_offset("+0200"), Some(120));
+        assert_eq!(parse_tz_offset("UTC"), None);
+    }
+
     #[test]
     fn open_allows_relative_worktrees_extension() {
         let temp = TempDir::new().unwrap();
@@ -2009,12 +2408,122 @@ mod tests {
         assert_eq!(deleted_binary_changes[0].file_path, "deleted.png");
     }
 
+    #[test]
+    fn null_bytes_in_a_source_file_report_it_as_binary_instead_of_parsing() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+
+        commit_binary_file(
+            &repo,
+            "util.ts",
+            b"export function a() {\n  return 1;\n}\n",
+            "init",
+        );
+        fs::write(
+            temp.path().join("util.ts"),
+            b"export function a() {\n  return 2;\0\0\n}\n",
+        )
+        .unwrap();
+
+        let bridge = GitBridge::open(temp.path()).unwrap();
+        let files = bridge.get_changed_files(&DiffScope::Working, &[]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].before_content.is_some());
+        assert!(files[0].after_content.is_none());
+
+        let binary_changes = collect_binary_file_changes(&files);
+        assert_eq!(binary_changes.len(), 1);
+        assert_eq!(binary_changes[0].file_path, "util.ts");
+        assert_eq!(binary_changes[0].status, FileStatus::Modified);
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(&files, &registry, None, None);
+        assert!(result.changes.is_empty());
+    }
+
     #[test]
     fn partial_utf8_boundary_is_not_treated_as_binary() {
         assert!(!GitBridge::bytes_look_binary(&[0xe2, 0x82], false));
         assert!(GitBridge::bytes_look_binary(&[0xe2, 0x82], true));
     }
 
+    #[test]
+    fn range_diff_reports_renames_with_old_path_and_contents() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+
+        let contents = "def foo():\n    return 1\n\ndef bar():\n    return 2\n";
+        let from = commit_file(&repo, "old.py", contents, "init");
+        commit_file(&repo, "other.py", "x = 1\n", "unrelated");
+
+        fs::rename(temp.path().join("old.py"), temp.path().join("new.py")).unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("old.py")).unwrap();
+        index.write().unwrap();
+        let renamed = contents.replace("return 2", "return 3");
+        commit_file(&repo, "new.py", &renamed, "rename");
+
+        let bridge = GitBridge::open(temp.path()).unwrap();
+        let scope = DiffScope::Range {
+            from: from.to_string(),
+            to: "HEAD".to_string(),
+        };
+        // Scoping to either path alone still pairs it with the other one.
+        for pathspecs in [
+            vec![],
+            vec!["new.py".to_string()],
+            vec!["old.py".to_string()],
+        ] {
+            let files = bridge.get_changed_files(&scope, &pathspecs).unwrap();
+            let renames: Vec<&FileChange> = files
+                .iter()
+                .filter(|file| file.file_path != "other.py")
+                .collect();
+            assert_eq!(renames.len(), 1, "files: {files:?}");
+            assert_eq!(renames[0].status, FileStatus::Renamed);
+            assert_eq!(renames[0].file_path, "new.py");
+            assert_eq!(renames[0].old_file_path.as_deref(), Some("old.py"));
+            assert_eq!(renames[0].before_content.as_deref(), Some(contents));
+            assert_eq!(renames[0].after_content.as_deref(), Some(renamed.as_str()));
+        }
+    }
+
+    #[test]
+    fn commit_diff_reports_rename_as_single_moved_entity() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+
+        let contents = "export function foo() {\n  return 1;\n}\n";
+        commit_file(&repo, "old.ts", contents, "init");
+
+        fs::rename(temp.path().join("old.ts"), temp.path().join("new.ts")).unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("old.ts")).unwrap();
+        index.write().unwrap();
+        let sha = commit_file(&repo, "new.ts", contents, "rename");
+
+        let bridge = GitBridge::open(temp.path()).unwrap();
+        let files = bridge
+            .get_changed_files(
+                &DiffScope::Commit {
+                    sha: sha.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(files.len(), 1, "files: {files:?}");
+        assert_eq!(files[0].status, FileStatus::Renamed);
+        assert_eq!(files[0].file_path, "new.ts");
+        assert_eq!(files[0].old_file_path.as_deref(), Some("old.ts"));
+        assert_eq!(files[0].before_content.as_deref(), Some(contents));
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(&files, &registry, None, None);
+        assert_eq!(result.changes.len(), 1, "changes: {:?}", result.changes);
+        assert_eq!(result.changes[0].change_type, ChangeType::Moved);
+        assert_eq!(result.changes[0].entity_name, "foo");
+    }
+
     #[test]
     fn staged_file_rename_is_reported_as_single_rename_with_old_contents() {
         let temp = TempDir::new().unwrap();
@@ -2412,4 +2921,27 @@ export function bar(y: number) {
             "before_content read from CRLF blob should be normalized to LF"
         );
     }
+
+    #[test]
+    fn bom_is_kept_and_crlf_only_with_eol_normalize_off() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+
+        commit_file(&repo, "sample.rs", "fn a() {}\n", "init");
+        fs::write(temp.path().join("sample.rs"), "\u{feff}fn a() {}\r\n").unwrap();
+
+        let bridge = GitBridge::open(temp.path()).unwrap();
+        let files = bridge.get_changed_files(&DiffScope::Working, &[]).unwrap();
+        assert_eq!(
+            files[0].after_content.as_deref(),
+            Some("\u{feff}fn a() {}\n")
+        );
+
+        let bridge = bridge.with_eol_normalize(false);
+        let files = bridge.get_changed_files(&DiffScope::Working, &[]).unwrap();
+        assert_eq!(
+            files[0].after_content.as_deref(),
+            Some("\u{feff}fn a() {}\r\n")
+        );
+    }
 }
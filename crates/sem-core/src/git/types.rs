@@ -17,6 +17,31 @@ pub enum DiffScope {
     },
 }
 
+/// Restricts a range diff to commits by one author and/or after a date.
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    /// Substring of the author's `Name <email>`, like `git log --author`.
+    pub author: Option<String>,
+    /// Unix time; commits authored before it are skipped.
+    pub since: Option<i64>,
+}
+
+impl CommitFilter {
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none() && self.since.is_none()
+    }
+
+    pub fn matches(&self, author_name: &str, author_email: &str, author_time: i64) -> bool {
+        if self.since.is_some_and(|since| author_time < since) {
+            return false;
+        }
+        match &self.author {
+            Some(pattern) => format!("{author_name} <{author_email}>").contains(pattern.as_str()),
+            None => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FileStatus {
@@ -40,16 +65,40 @@ impl std::fmt::Display for FileStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct FileChange {
+    #[serde(alias = "file_path")]
     pub file_path: String,
     pub status: FileStatus,
-    #[serde(default)]
+    #[serde(default, alias = "old_file_path")]
     pub old_file_path: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "before_content")]
     pub before_content: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "after_content")]
     pub after_content: Option<String>,
 }
 
+impl FileChange {
+    /// The same change read backward: contents swap sides, so an added file
+    /// becomes a deletion and a rename points from the new path to the old.
+    pub fn reversed(self) -> Self {
+        let (file_path, old_file_path, status) = match self.status {
+            FileStatus::Added => (self.file_path, self.old_file_path, FileStatus::Deleted),
+            FileStatus::Deleted => (self.file_path, self.old_file_path, FileStatus::Added),
+            FileStatus::Modified => (self.file_path, self.old_file_path, FileStatus::Modified),
+            FileStatus::Renamed => match self.old_file_path {
+                Some(old_path) => (old_path, Some(self.file_path), FileStatus::Renamed),
+                None => (self.file_path, None, FileStatus::Renamed),
+            },
+        };
+        FileChange {
+            file_path,
+            status,
+            old_file_path,
+            before_content: self.after_content,
+            after_content: self.before_content,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitInfo {
     pub sha: String,
@@ -73,6 +122,9 @@ pub struct BlameLineInfo {
     pub commit_sha: Option<String>,
     pub author: String,
     pub author_time: Option<i64>,
+    /// Author's UTC offset in minutes at commit time (`author-tz`).
+    #[serde(default)]
+    pub author_tz_offset_minutes: Option<i32>,
     pub summary: String,
 }
 
@@ -101,4 +153,34 @@ mod tests {
         assert_eq!(change.old_file_path.as_deref(), Some("a.ts"));
         assert_eq!(change.status, FileStatus::Renamed);
     }
+
+    #[test]
+    fn file_change_accepts_snake_case_aliases() {
+        let change = serde_json::from_str::<FileChange>(
+            r#"{"file_path":"a.ts","status":"modified","before_content":"x","after_content":"y"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(change.file_path, "a.ts");
+        assert_eq!(change.before_content.as_deref(), Some("x"));
+        assert_eq!(change.after_content.as_deref(), Some("y"));
+    }
+
+    #[test]
+    fn reversed_rename_points_back_to_the_old_path() {
+        let change = FileChange {
+            file_path: "b.ts".to_string(),
+            status: FileStatus::Renamed,
+            old_file_path: Some("a.ts".to_string()),
+            before_content: Some("old".to_string()),
+            after_content: Some("new".to_string()),
+        }
+        .reversed();
+
+        assert_eq!(change.file_path, "a.ts");
+        assert_eq!(change.old_file_path.as_deref(), Some("b.ts"));
+        assert_eq!(change.status, FileStatus::Renamed);
+        assert_eq!(change.before_content.as_deref(), Some("new"));
+        assert_eq!(change.after_content.as_deref(), Some("old"));
+    }
 }
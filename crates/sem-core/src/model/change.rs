@@ -66,6 +66,31 @@ pub struct SemanticChange {
     /// None when structural hash is unavailable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub structural_change: Option<bool>,
+    /// Whether a function-like entity's signature (name, parameters, return
+    /// type) changed (true) or only its body (false). None for other entity
+    /// types and for additions, deletions and reorders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_change: Option<bool>,
+    /// The entity's `stable_id` from before the change (after, for
+    /// additions). Renames and moves change `entity_id` but keep this, so
+    /// consumers can follow an entity across them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<String>,
+}
+
+/// An entity that exists on both sides of a diff with identical content.
+/// Diffs only carry these when asked for the full entity inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnchangedEntity {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub entity_name: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_name: Option<String>,
+    pub file_path: String,
 }
 
 impl SemanticChange {
@@ -0,0 +1,56 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Why a file contributed no entities to a diff or graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Larger than the configured maximum file size; never read.
+    TooLarge { size: u64, limit: u64 },
+    /// Not valid UTF-8 (binary, or a legacy encoding).
+    NotUtf8,
+    /// No parser plugin handles this file.
+    Unsupported,
+    /// The plugin failed on the content: a syntax error, a missing grammar,
+    /// or a panic.
+    ParseError { message: String },
+    /// Blank or whitespace-only content.
+    Empty,
+    /// Parsed cleanly but holds nothing the plugin extracts as an entity.
+    NoEntities,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::TooLarge { size, limit } => {
+                write!(f, "{size} bytes exceeds the {limit}-byte limit")
+            }
+            SkipReason::NotUtf8 => write!(f, "not valid UTF-8"),
+            SkipReason::Unsupported => write!(f, "unsupported file type"),
+            SkipReason::ParseError { message } => write!(f, "parse error: {message}"),
+            SkipReason::Empty => write!(f, "empty file"),
+            SkipReason::NoEntities => write!(f, "no entities found"),
+        }
+    }
+}
+
+/// A file that produced no entities, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseDiagnostic {
+    pub file_path: String,
+    #[serde(flatten)]
+    pub reason: SkipReason,
+}
+
+impl ParseDiagnostic {
+    /// Skipped before parsing (too large, not UTF-8), as opposed to parsed
+    /// without yielding entities.
+    pub fn is_read_skip(&self) -> bool {
+        matches!(
+            self.reason,
+            SkipReason::TooLarge { .. } | SkipReason::NotUtf8
+        )
+    }
+}
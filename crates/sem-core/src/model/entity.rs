@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::hash::Hasher;
+use xxhash_rust::xxh3::Xxh3;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -30,8 +32,23 @@ pub struct SemanticEntity {
     /// (exclusive), matching tree-sitter's `end_byte()`. `None` when unknown.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub end_byte: Option<usize>,
+    /// Byte offset of the entity's name token in the source file, for jumping
+    /// to the identifier rather than the start of the definition (`fn`,
+    /// `def`, attributes). Set for code entities whose name is a tree-sitter
+    /// node; `None` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_start: Option<usize>,
+    /// Byte offset just past the name token (exclusive). `None` when unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_end: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+    /// Identity that survives renames: a fingerprint of the file, the entity
+    /// type and the content with the entity's own name masked out. `id`
+    /// changes when an entity is renamed; this doesn't, so history tools can
+    /// follow it. `None` until [`assign_stable_ids`] runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_id: Option<String>,
 }
 
 pub fn build_entity_id(
@@ -71,6 +88,68 @@ pub fn build_entity_id_disambiguated_with_ordinal(
     format!("{base}#{ordinal}")
 }
 
+/// Fingerprint behind [`SemanticEntity::stable_id`]. Identifier tokens equal
+/// to the entity's name are masked, so renaming `foo` to `bar` (including
+/// recursive calls to itself) leaves the fingerprint unchanged.
+pub fn stable_entity_fingerprint(entity: &SemanticEntity) -> String {
+    let mut hasher = Xxh3::new();
+    hasher.write(entity.file_path.as_bytes());
+    hasher.write(b"\0");
+    hasher.write(entity.entity_type.as_bytes());
+    hasher.write(b"\0");
+
+    let content = entity.content.as_str();
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut rest = content;
+    while let Some(start) = rest.find(is_ident) {
+        hasher.write(&rest.as_bytes()[..start]);
+        let token_len = rest[start..]
+            .find(|c| !is_ident(c))
+            .unwrap_or(rest.len() - start);
+        let token = &rest[start..start + token_len];
+        if token == entity.name {
+            hasher.write(b"\0");
+        } else {
+            hasher.write(token.as_bytes());
+        }
+        rest = &rest[start + token_len..];
+    }
+    hasher.write(rest.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+/// Fill in `stable_id` for freshly extracted entities. Entities whose
+/// fingerprints collide (identical bodies in one file) are told apart by an
+/// `#n` suffix in source order.
+pub fn assign_stable_ids(entities: &mut [SemanticEntity]) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for entity in entities {
+        let fingerprint = stable_entity_fingerprint(entity);
+        let count = seen.entry(fingerprint.clone()).or_default();
+        *count += 1;
+        entity.stable_id = Some(if *count == 1 {
+            fingerprint
+        } else {
+            format!("{fingerprint}#{count}")
+        });
+    }
+}
+
+/// `entity` followed by its enclosing entities, innermost first, with
+/// parents looked up in `by_id`. A malformed parent chain that loops is cut
+/// off after `by_id.len()` entities.
+pub fn ancestors<'a>(
+    entity: &'a SemanticEntity,
+    by_id: &'a HashMap<&str, &'a SemanticEntity>,
+) -> impl Iterator<Item = &'a SemanticEntity> + 'a {
+    std::iter::successors(Some(entity), move |at| {
+        at.parent_id
+            .as_deref()
+            .and_then(|parent| by_id.get(parent).copied())
+    })
+    .take(by_id.len().max(1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,4 +172,51 @@ mod tests {
         );
         assert_eq!(id, "src/main.ts::class::MyClass::greet");
     }
+
+    #[test]
+    fn ancestors_walk_out_and_stop_on_a_cycle() {
+        let registry = crate::parser::plugins::create_default_registry();
+        let mut entities = registry
+            .get_plugin("src/lib.rs")
+            .unwrap()
+            .extract_entities("mod outer {\n    fn inner() {}\n}\n", "src/lib.rs");
+        let by_id: HashMap<&str, &SemanticEntity> =
+            entities.iter().map(|e| (e.id.as_str(), e)).collect();
+        let inner = entities.iter().find(|e| e.name == "inner").unwrap();
+        let names: Vec<&str> = ancestors(inner, &by_id).map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["inner", "outer"]);
+
+        let inner_id = inner.id.clone();
+        for entity in &mut entities {
+            if entity.name == "outer" {
+                entity.parent_id = Some(inner_id.clone());
+            }
+        }
+        let by_id: HashMap<&str, &SemanticEntity> =
+            entities.iter().map(|e| (e.id.as_str(), e)).collect();
+        let inner = entities.iter().find(|e| e.name == "inner").unwrap();
+        assert_eq!(ancestors(inner, &by_id).count(), entities.len());
+    }
+
+    #[test]
+    fn test_structural_hash_serde() {
+        let registry = crate::parser::plugins::create_default_registry();
+        let code = registry
+            .get_plugin("src/lib.rs")
+            .unwrap()
+            .extract_entities("fn answer() -> u32 {\n    42\n}\n", "src/lib.rs");
+        let json = serde_json::to_string(&code[0]).unwrap();
+        let round_trip: SemanticEntity = serde_json::from_str(&json).unwrap();
+        assert!(code[0].structural_hash.is_some());
+        assert_eq!(round_trip.structural_hash, code[0].structural_hash);
+
+        let text = registry
+            .get_plugin("notes.txt")
+            .unwrap()
+            .extract_entities("just some notes\n", "notes.txt");
+        let json = serde_json::to_value(&text[0]).unwrap();
+        assert!(json.get("structuralHash").is_none());
+        let round_trip: SemanticEntity = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.structural_hash, None);
+    }
 }
@@ -1,8 +1,9 @@
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
-use super::change::{ChangeType, SemanticChange};
+use super::change::{ChangeType, SemanticChange, UnchangedEntity};
 use super::entity::SemanticEntity;
+use crate::utils::hash::content_hash;
 
 fn parent_name(entity: &SemanticEntity, by_id: &HashMap<&str, &SemanticEntity>) -> Option<String> {
     let mut parts: Vec<&str> = Vec::new();
@@ -38,12 +39,18 @@ fn parent_name(entity: &SemanticEntity, by_id: &HashMap<&str, &SemanticEntity>)
 
 pub struct MatchResult {
     pub changes: Vec<SemanticChange>,
+    /// Entities matched by ID with identical content and unchanged order.
+    pub unchanged: Vec<UnchangedEntity>,
 }
 
 type SameFileSignatureKey<'a> = (&'a str, &'a str, &'a str, Option<&'a str>);
 type RenameSignatureKey<'a> = (&'a str, &'a str, Option<&'a str>);
 const SAME_FILE_SIGNATURE_MIN_SIMILARITY: f64 = 0.3;
 
+/// Minimum content similarity for the fuzzy pass to pair an unmatched
+/// before/after entity as a rename or move.
+pub const DEFAULT_RENAME_THRESHOLD: f64 = 0.8;
+
 struct ContentTokens<'a> {
     token_count: usize,
     unique_tokens: HashSet<&'a str>,
@@ -126,6 +133,91 @@ fn structural_change_between(before: &SemanticEntity, after: &SemanticEntity) ->
     }
 }
 
+/// The declaration head of a function-like entity — name, parameters and
+/// return type — whitespace-normalized, with leading comment lines dropped.
+/// It runs up to the body: a `{`, a `;`, or a `:` ending the line (Python),
+/// outside any parentheses, brackets, braces or generic `<>`. A `{` right
+/// after a `:`, `|` or `&` opens an object type (`(): { id: string } {`),
+/// not the body. Without one of those, the first line stands in. `None` for
+/// entity types that have no call signature.
+pub fn entity_signature(entity_type: &str, content: &str) -> Option<String> {
+    if !matches!(
+        entity_type,
+        "function" | "method" | "constructor" | "getter" | "setter"
+    ) {
+        return None;
+    }
+
+    let mut lines = content.lines().skip_while(|line| {
+        let line = line.trim_start();
+        ["//", "/*", "*", "--"].iter().any(|p| line.starts_with(p))
+            || (line.starts_with('#') && !line.starts_with("#["))
+    });
+    let first = lines.next().unwrap_or("");
+    let mut head = String::new();
+    let mut depth = 0i32;
+    let mut angle = 0i32;
+    // The last non-whitespace character before the current one.
+    let mut prev = ' ';
+    for line in std::iter::once(first).chain(lines) {
+        for (i, c) in line.char_indices() {
+            let nested = depth > 0 || angle > 0;
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                '{' if nested || matches!(prev, ':' | '|' | '&') => depth += 1,
+                // Generic brackets follow a name (`run<T>`); a bare `<` is a
+                // comparison and `operator<` names an operator.
+                '<' if depth <= 0
+                    && line[..i]
+                        .chars()
+                        .next_back()
+                        .is_some_and(|p| p.is_alphanumeric() || p == '_')
+                    && !line[..i].ends_with("operator") =>
+                {
+                    angle += 1
+                }
+                '>' if depth <= 0 && angle > 0 && !matches!(prev, '-' | '=') => angle -= 1,
+                '{' | ';' if !nested => {
+                    head.push_str(&line[..i]);
+                    return Some(normalize_whitespace(&head));
+                }
+                ':' if !nested && {
+                    let rest = line[i + 1..].trim_start();
+                    rest.is_empty() || rest.starts_with('#')
+                } =>
+                {
+                    head.push_str(&line[..i]);
+                    return Some(normalize_whitespace(&head));
+                }
+                _ => {}
+            }
+            if !c.is_whitespace() {
+                prev = c;
+            }
+        }
+        head.push_str(line);
+        head.push('\n');
+    }
+    Some(normalize_whitespace(first))
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether the signature changed between two versions of a function-like
+/// entity (true) or only its body did (false). None when the content is
+/// identical or the entity has no signature.
+fn signature_change_between(before: &SemanticEntity, after: &SemanticEntity) -> Option<bool> {
+    if before.content_hash == after.content_hash {
+        return None;
+    }
+    let before_signature = entity_signature(&before.entity_type, &before.content)?;
+    let after_signature = entity_signature(&after.entity_type, &after.content)?;
+    Some(content_hash(&before_signature) != content_hash(&after_signature))
+}
+
 fn make_change(
     after_entity: &SemanticEntity,
     change_type: ChangeType,
@@ -153,6 +245,13 @@ fn make_change(
             structural_change_between(before, after_entity)
         }
     });
+    let signature_change = before_entity.and_then(|before| {
+        if matches!(change_type, ChangeType::Deleted | ChangeType::Reordered) {
+            None
+        } else {
+            signature_change_between(before, after_entity)
+        }
+    });
     SemanticChange {
         id: format!("change::{prefix}{}", primary.id),
         entity_id: primary.id.clone(),
@@ -190,6 +289,10 @@ fn make_change(
         author: author.map(String::from),
         timestamp: None,
         structural_change,
+        signature_change,
+        stable_id: before_entity
+            .and_then(|b| b.stable_id.clone())
+            .or_else(|| after_entity.stable_id.clone()),
     }
 }
 
@@ -197,12 +300,14 @@ fn make_change(
 /// 1. Exact ID match — same entity ID in before/after → modified or unchanged
 /// 2. Content hash match — same hash, different ID → modified, renamed, or moved
 /// 3. Same signature across file rename → moved, even if content changed
-/// 4. Fuzzy similarity — >80% content similarity → modified, renamed, or moved
+/// 4. Fuzzy similarity — content similarity of at least `threshold`
+///    (default [`DEFAULT_RENAME_THRESHOLD`]) → modified, renamed, or moved
 pub fn match_entities(
     before: &[SemanticEntity],
     after: &[SemanticEntity],
     _file_path: &str,
-    similarity_fn: Option<&dyn Fn(&SemanticEntity, &SemanticEntity) -> f64>,
+    similarity_fn: Option<SimilarityFn<'_>>,
+    threshold: f64,
     commit_sha: Option<&str>,
     author: Option<&str>,
 ) -> MatchResult {
@@ -303,6 +408,15 @@ pub fn match_entities(
             {
                 continue;
             }
+            // Fallback chunks are named by their line range, so an edit above
+            // one renames it without touching its content.
+            if before_entity.entity_type == "chunk"
+                && after_entity.entity_type == "chunk"
+                && before_entity.file_path == after_entity.file_path
+                && before_entity.content_hash == after_entity.content_hash
+            {
+                continue;
+            }
 
             changes.push(make_change(
                 after_entity,
@@ -513,7 +627,7 @@ pub fn match_entities(
         }
     }
 
-    // Phase 5: Fuzzy similarity (>80% threshold)
+    // Phase 5: Fuzzy similarity (at least `threshold`)
     // Cache token sets on demand and group by type.
     let still_unmatched_before: Vec<(usize, &SemanticEntity)> = unmatched_before
         .iter()
@@ -529,7 +643,6 @@ pub fn match_entities(
         .collect();
 
     if !still_unmatched_before.is_empty() && !still_unmatched_after.is_empty() {
-        const THRESHOLD: f64 = 0.8;
         const SIZE_RATIO_CUTOFF: f64 = 0.5;
 
         // Group before entities by type: O(sum(n_t × m_t)) instead of O(N×M)
@@ -575,17 +688,20 @@ pub fn match_entities(
                     continue;
                 }
 
-                // Jaccard on pre-computed sets
-                let score = jaccard_similarity(
-                    &unmatched_after_tokens
-                        .get(&unmatched_after, after_unmatched_idx)
-                        .unique_tokens,
-                    &unmatched_before_tokens
-                        .get(&unmatched_before, before_unmatched_idx)
-                        .unique_tokens,
-                );
-
-                if score >= THRESHOLD && score > best_score {
+                // Jaccard on pre-computed sets unless a metric was supplied
+                let score = match similarity_fn {
+                    Some(f) => f(before_entity, after_entity),
+                    None => jaccard_similarity(
+                        &unmatched_after_tokens
+                            .get(&unmatched_after, after_unmatched_idx)
+                            .unique_tokens,
+                        &unmatched_before_tokens
+                            .get(&unmatched_before, before_unmatched_idx)
+                            .unique_tokens,
+                    ),
+                };
+
+                if score >= threshold && score > best_score {
                     best_score = score;
                     best_idx = Some(bi);
                 }
@@ -661,7 +777,192 @@ pub fn match_entities(
         ));
     }
 
-    MatchResult { changes }
+    let changed_ids: HashSet<&str> = changes.iter().map(|c| c.entity_id.as_str()).collect();
+    let unchanged = after
+        .iter()
+        .filter(|e| !changed_ids.contains(e.id.as_str()))
+        .filter(|e| {
+            before_by_id
+                .get(e.id.as_str())
+                .is_some_and(|b| b.content_hash == e.content_hash)
+        })
+        .map(|e| UnchangedEntity {
+            entity_id: e.id.clone(),
+            entity_type: e.entity_type.clone(),
+            entity_name: e.name.clone(),
+            start_line: e.start_line,
+            end_line: e.end_line,
+            parent_name: parent_name(e, &combined_by_id),
+            file_path: e.file_path.clone(),
+        })
+        .collect();
+
+    MatchResult { changes, unchanged }
+}
+
+/// Most deleted entities sharing one type and name that the fuzzy phase of
+/// [`match_cross_file_moves`] compares an added entity against.
+const MAX_FUZZY_MOVE_CANDIDATES: usize = 16;
+
+/// A content similarity score in `0.0..=1.0` for a before/after pair.
+pub type SimilarityFn<'a> = &'a dyn Fn(&SemanticEntity, &SemanticEntity) -> f64;
+
+/// A hash an entity can be paired on across files.
+type MoveHashKey = fn(&SemanticEntity) -> Option<&str>;
+
+/// Pair entities left deleted in one file with entities left added in
+/// another, after every file went through [`match_entities`] on its own.
+/// A move keeps the entity's type and name; a name change across files
+/// stays a deletion and an addition. Matches by content hash, then
+/// structural hash, then fuzzy similarity (at least `threshold`, against at
+/// most [`MAX_FUZZY_MOVE_CANDIDATES`] same-named candidates). Each pair comes
+/// back as the ID of the deleted entity and the `Moved` change replacing
+/// both halves.
+pub fn match_cross_file_moves(
+    deleted: &[SemanticEntity],
+    added: &[SemanticEntity],
+    similarity_fn: Option<SimilarityFn<'_>>,
+    threshold: f64,
+    commit_sha: Option<&str>,
+    author: Option<&str>,
+) -> Vec<(String, SemanticChange)> {
+    let mut moves = Vec::new();
+    if deleted.is_empty() || added.is_empty() {
+        return moves;
+    }
+
+    let by_id: HashMap<&str, &SemanticEntity> = deleted
+        .iter()
+        .chain(added.iter())
+        .map(|e| (e.id.as_str(), e))
+        .collect();
+    let mut matched_before = vec![false; deleted.len()];
+    let mut matched_after = vec![false; added.len()];
+
+    let mut pair = |before_idx: usize,
+                    after_idx: usize,
+                    matched_before: &mut [bool],
+                    matched_after: &mut [bool]| {
+        matched_before[before_idx] = true;
+        matched_after[after_idx] = true;
+        let before_entity = &deleted[before_idx];
+        let after_entity = &added[after_idx];
+        moves.push((
+            before_entity.id.clone(),
+            make_change(
+                after_entity,
+                classify_match(before_entity, after_entity),
+                Some(before_entity),
+                commit_sha,
+                author,
+                &by_id,
+            ),
+        ));
+    };
+
+    // Exact content first, then same structure, keyed by hash.
+    let hash_phases: [MoveHashKey; 2] = [
+        |e| Some(e.content_hash.as_str()),
+        |e| e.structural_hash.as_deref(),
+    ];
+    for key_of in hash_phases {
+        let mut before_by_hash: HashMap<(&str, &str, &str), Vec<usize>> = HashMap::new();
+        for (i, e) in deleted.iter().enumerate() {
+            if matched_before[i] {
+                continue;
+            }
+            if let Some(hash) = key_of(e) {
+                before_by_hash
+                    .entry((e.entity_type.as_str(), e.name.as_str(), hash))
+                    .or_default()
+                    .push(i);
+            }
+        }
+        for (after_idx, after_entity) in added.iter().enumerate() {
+            if matched_after[after_idx] {
+                continue;
+            }
+            let Some(hash) = key_of(after_entity) else {
+                continue;
+            };
+            let key = (
+                after_entity.entity_type.as_str(),
+                after_entity.name.as_str(),
+                hash,
+            );
+            let Some(candidates) = before_by_hash.get_mut(&key) else {
+                continue;
+            };
+            let Some(pos) = candidates
+                .iter()
+                .position(|&i| deleted[i].file_path != after_entity.file_path)
+            else {
+                continue;
+            };
+            let before_idx = candidates.remove(pos);
+            pair(
+                before_idx,
+                after_idx,
+                &mut matched_before,
+                &mut matched_after,
+            );
+        }
+    }
+
+    // Fuzzy: best-scoring same-named candidate of the same type in another
+    // file.
+    let mut before_by_name: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+    for (i, e) in deleted.iter().enumerate() {
+        if !matched_before[i] {
+            before_by_name
+                .entry((e.entity_type.as_str(), e.name.as_str()))
+                .or_default()
+                .push(i);
+        }
+    }
+    let mut before_tokens: HashMap<usize, ContentTokens<'_>> = HashMap::new();
+    for (after_idx, after_entity) in added.iter().enumerate() {
+        if matched_after[after_idx] {
+            continue;
+        }
+        let key = (
+            after_entity.entity_type.as_str(),
+            after_entity.name.as_str(),
+        );
+        let Some(candidates) = before_by_name.get(&key) else {
+            continue;
+        };
+        let after_tokens = tokenize_content(&after_entity.content);
+        let mut best: Option<(usize, f64)> = None;
+        for &before_idx in candidates.iter().take(MAX_FUZZY_MOVE_CANDIDATES) {
+            let before_entity = &deleted[before_idx];
+            if matched_before[before_idx] || before_entity.file_path == after_entity.file_path {
+                continue;
+            }
+            let score = match similarity_fn {
+                Some(f) => f(before_entity, after_entity),
+                None => default_similarity_from_tokens(
+                    before_tokens
+                        .entry(before_idx)
+                        .or_insert_with(|| tokenize_content(&before_entity.content)),
+                    &after_tokens,
+                ),
+            };
+            if score >= threshold && best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((before_idx, score));
+            }
+        }
+        if let Some((before_idx, _)) = best {
+            pair(
+                before_idx,
+                after_idx,
+                &mut matched_before,
+                &mut matched_after,
+            );
+        }
+    }
+
+    moves
 }
 
 /// Default content similarity using Jaccard index on whitespace-split tokens
@@ -671,6 +972,68 @@ pub fn default_similarity(a: &SemanticEntity, b: &SemanticEntity) -> f64 {
     default_similarity_from_tokens(&tokens_a, &tokens_b)
 }
 
+/// Content similarity used to pair entities whose IDs changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SimilarityMetric {
+    /// Jaccard index on the sets of whitespace-split tokens.
+    #[default]
+    Jaccard,
+    /// Normalized Levenshtein ratio on whitespace-normalized content.
+    Levenshtein,
+    /// Levenshtein ratio after sorting tokens, so reordered bodies still match.
+    TokenSort,
+}
+
+impl SimilarityMetric {
+    /// The function to hand to [`match_entities`]. `None` for Jaccard, which
+    /// `match_entities` computes itself from cached token sets.
+    pub fn similarity_fn(self) -> Option<fn(&SemanticEntity, &SemanticEntity) -> f64> {
+        match self {
+            SimilarityMetric::Jaccard => None,
+            SimilarityMetric::Levenshtein => Some(levenshtein_similarity),
+            SimilarityMetric::TokenSort => Some(token_sort_similarity),
+        }
+    }
+}
+
+/// Normalized Levenshtein ratio (1.0 = identical) on content with runs of
+/// whitespace collapsed, so re-indentation alone doesn't lower the score.
+pub fn levenshtein_similarity(a: &SemanticEntity, b: &SemanticEntity) -> f64 {
+    let a: Vec<&str> = a.content.split_whitespace().collect();
+    let b: Vec<&str> = b.content.split_whitespace().collect();
+    levenshtein_ratio(&a.join(" "), &b.join(" "))
+}
+
+/// Levenshtein ratio on the whitespace-split tokens in sorted order.
+pub fn token_sort_similarity(a: &SemanticEntity, b: &SemanticEntity) -> f64 {
+    let mut a: Vec<&str> = a.content.split_whitespace().collect();
+    let mut b: Vec<&str> = b.content.split_whitespace().collect();
+    a.sort_unstable();
+    b.sort_unstable();
+    levenshtein_ratio(&a.join(" "), &b.join(" "))
+}
+
+fn levenshtein_ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    // Two-row dynamic programming over the edit-distance matrix.
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    1.0 - prev[b.len()] as f64 / max_len as f64
+}
+
 /// Detect intra-file reordering of unchanged entities.
 ///
 /// Takes entities that matched by exact ID with identical content and checks
@@ -831,7 +1194,10 @@ mod tests {
             end_line: 1,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -839,7 +1205,15 @@ mod tests {
     fn test_exact_match_modified() {
         let before = vec![make_entity("a::f::foo", "foo", "old content", "a.ts")];
         let after = vec![make_entity("a::f::foo", "foo", "new content", "a.ts")];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Modified);
     }
@@ -861,7 +1235,15 @@ mod tests {
             7,
         )];
 
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].start_line, 7);
@@ -874,7 +1256,15 @@ mod tests {
     fn test_exact_match_unchanged() {
         let before = vec![make_entity("a::f::foo", "foo", "same", "a.ts")];
         let after = vec![make_entity("a::f::foo", "foo", "same", "a.ts")];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 0);
     }
 
@@ -882,7 +1272,15 @@ mod tests {
     fn test_added_deleted() {
         let before = vec![make_entity("a::f::old", "old", "content", "a.ts")];
         let after = vec![make_entity("a::f::new", "new", "different", "a.ts")];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 2);
         let types: Vec<ChangeType> = result.changes.iter().map(|c| c.change_type).collect();
         assert!(types.contains(&ChangeType::Deleted));
@@ -893,11 +1291,44 @@ mod tests {
     fn test_content_hash_rename() {
         let before = vec![make_entity("a::f::old", "old", "same content", "a.ts")];
         let after = vec![make_entity("a::f::new", "new", "same content", "a.ts")];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Renamed);
     }
 
+    #[test]
+    fn test_rename_threshold_controls_fuzzy_rename() {
+        // 7 shared tokens out of 10 distinct: Jaccard similarity 0.7
+        let before = vec![make_entity("a::f::foo", "foo", "a b c d e f g h", "a.ts")];
+        let after = vec![make_entity("a::f::bar", "bar", "a b c d e f g x y", "a.ts")];
+        assert_eq!(default_similarity(&before[0], &after[0]), 0.7);
+
+        let result = match_entities(&before, &after, "a.ts", None, 0.65, None, None);
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].change_type, ChangeType::Renamed);
+
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
+        let mut types: Vec<ChangeType> = result.changes.iter().map(|c| c.change_type).collect();
+        types.sort_by_key(|t| format!("{t:?}"));
+        assert_eq!(types, vec![ChangeType::Added, ChangeType::Deleted]);
+    }
+
     #[test]
     fn test_same_name_fuzzy_match_is_modified() {
         let before = vec![make_entity(
@@ -913,7 +1344,15 @@ mod tests {
             "a.ts",
         )];
 
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Modified);
@@ -936,7 +1375,15 @@ mod tests {
             "a.ts",
         )];
 
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Renamed);
@@ -966,7 +1413,15 @@ mod tests {
         let before = vec![before_entity];
         let after = vec![after_entity];
 
-        let result = match_entities(&before, &after, "new.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "new.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Moved);
@@ -1027,7 +1482,15 @@ mod tests {
             ),
         ];
 
-        let result = match_entities(&before, &after, "new.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "new.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         let method_added_or_deleted = result
             .changes
             .iter()
@@ -1069,7 +1532,15 @@ mod tests {
             "new.ts",
         )];
 
-        let result = match_entities(&before, &after, "new.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "new.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Moved);
@@ -1094,7 +1565,10 @@ mod tests {
             end_line: 10,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
         let method_before = SemanticEntity {
             id: "a.ts::a.ts::class::DataStack::genPg".to_string(),
@@ -1109,7 +1583,10 @@ mod tests {
             end_line: 8,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
 
         let class_after = SemanticEntity {
@@ -1125,7 +1602,10 @@ mod tests {
             end_line: 10,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
         let method_after = SemanticEntity {
             id: "a.ts::a.ts::class::DataStack::genPg".to_string(),
@@ -1140,12 +1620,23 @@ mod tests {
             end_line: 8,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
 
         let before = vec![class_before, method_before];
         let after = vec![class_after, method_after];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         // match_entities no longer deduplicates — suppression happens in differ.rs.
         // Both the class and the method are Modified here.
@@ -1170,7 +1661,10 @@ mod tests {
             end_line: 5,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
         let method_before = SemanticEntity {
             id: "a.ts::a.ts::class::Foo::bar".to_string(),
@@ -1185,7 +1679,10 @@ mod tests {
             end_line: 4,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
 
         let class_after = SemanticEntity {
@@ -1201,7 +1698,10 @@ mod tests {
             end_line: 6,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
         let method_after = SemanticEntity {
             id: "a.ts::a.ts::class::Foo::bar".to_string(),
@@ -1216,12 +1716,23 @@ mod tests {
             end_line: 5,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
 
         let before = vec![class_before, method_before];
         let after = vec![class_after, method_after];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         // Class changed but method didn't, so class should still appear
         assert_eq!(result.changes.len(), 1);
@@ -1249,7 +1760,10 @@ mod tests {
             end_line: 1,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -1270,7 +1784,15 @@ mod tests {
             "a.rs",
             Some("a.rs::class::ClassB"),
         )];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Moved);
         assert_eq!(
@@ -1298,7 +1820,15 @@ mod tests {
             "a.rs",
             Some("a.rs::class::Foo"),
         )];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Renamed);
         assert!(result.changes[0].old_parent_id.is_none());
@@ -1324,7 +1854,10 @@ mod tests {
             end_line: line + 2,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -1340,7 +1873,15 @@ mod tests {
             make_entity_at("a::f::gamma", "gamma", "fn gamma() {}", "a.rs", 5),
             make_entity_at("a::f::beta", "beta", "fn beta() {}", "a.rs", 9),
         ];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Reordered);
         assert!(result.changes[0].before_content.is_none());
@@ -1366,7 +1907,15 @@ mod tests {
             make_entity_at("a::f::alpha", "alpha", "fn alpha() {}", "a.rs", 1),
             make_entity_at("a::f::beta", "beta", "fn beta() {}", "a.rs", 10),
         ];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         // Lines shifted but relative order is same, no reorder
         assert_eq!(result.changes.len(), 0);
     }
@@ -1385,7 +1934,15 @@ mod tests {
             make_entity_at("a::f::gamma", "gamma", "fn gamma() { 999 }", "a.rs", 1),
             make_entity_at("a::f::delta", "delta", "fn delta() {}", "a.rs", 1),
         ];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Modified);
@@ -1417,7 +1974,15 @@ mod tests {
             "a.ts",
             1,
         )];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         let modified = result
             .changes
             .iter()
@@ -1458,7 +2023,15 @@ mod tests {
                 1,
             ),
         ];
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         let modified = result
             .changes
             .iter()
@@ -1510,7 +2083,15 @@ mod tests {
             ),
         ];
 
-        let result = match_entities(&before, &after, "a.ts", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.ts",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
         let modified = result
             .changes
             .iter()
@@ -1544,7 +2125,15 @@ mod tests {
             make_entity_at("a::f::alpha", "alpha", "fn alpha() {}", "a.rs", 1),
             make_entity_at("a::f::gamma", "gamma", "fn gamma() {}", "a.rs", 1),
         ];
-        let result = match_entities(&before, &after, "a.rs", None, None, None);
+        let result = match_entities(
+            &before,
+            &after,
+            "a.rs",
+            None,
+            DEFAULT_RENAME_THRESHOLD,
+            None,
+            None,
+        );
 
         assert_eq!(result.changes.len(), 1);
         assert_eq!(result.changes[0].change_type, ChangeType::Reordered);
@@ -1562,6 +2151,137 @@ mod tests {
         assert!(score < 1.0);
     }
 
+    const BODY: &str = "fn total() {\n    let tax = price * rate;\n    let fee = base + extra;\n    log(tax, fee);\n}";
+    const REORDERED_BODY: &str = "fn sum() {\n    log(tax, fee);\n    let fee = base + extra;\n    let tax = price * rate;\n}";
+
+    #[test]
+    fn similarity_metrics_on_reordered_body() {
+        let a = make_entity("a", "total", BODY, "a.rs");
+        let b = make_entity("b", "sum", REORDERED_BODY, "a.rs");
+
+        let jaccard = default_similarity(&a, &b);
+        let levenshtein = levenshtein_similarity(&a, &b);
+        let token_sort = token_sort_similarity(&a, &b);
+
+        // Only the name token differs; Levenshtein also pays for the reorder.
+        assert!(token_sort > 0.8, "token-sort {token_sort}");
+        assert!(jaccard > 0.8, "jaccard {jaccard}");
+        assert!(levenshtein < 0.8, "levenshtein {levenshtein}");
+        assert!(token_sort > jaccard);
+
+        assert_eq!(levenshtein_similarity(&a, &a), 1.0);
+        let reindented = make_entity("c", "total", &BODY.replace("    ", "\t"), "a.rs");
+        assert_eq!(levenshtein_similarity(&a, &reindented), 1.0);
+    }
+
+    #[test]
+    fn similarity_metric_decides_fuzzy_rename_match() {
+        let before = vec![make_entity("a.rs::function::total", "total", BODY, "a.rs")];
+        let after = vec![make_entity(
+            "a.rs::function::sum",
+            "sum",
+            REORDERED_BODY,
+            "a.rs",
+        )];
+
+        let change_types = |metric: SimilarityMetric| {
+            let f = metric.similarity_fn();
+            let f = f
+                .as_ref()
+                .map(|f| f as &dyn Fn(&SemanticEntity, &SemanticEntity) -> f64);
+            let mut types: Vec<ChangeType> = match_entities(
+                &before,
+                &after,
+                "a.rs",
+                f,
+                DEFAULT_RENAME_THRESHOLD,
+                None,
+                None,
+            )
+            .changes
+            .into_iter()
+            .map(|c| c.change_type)
+            .collect();
+            types.sort_by_key(|t| format!("{t:?}"));
+            types
+        };
+
+        assert_eq!(
+            change_types(SimilarityMetric::TokenSort),
+            vec![ChangeType::Renamed]
+        );
+        assert_eq!(
+            change_types(SimilarityMetric::Jaccard),
+            vec![ChangeType::Renamed]
+        );
+        assert_eq!(
+            change_types(SimilarityMetric::Levenshtein),
+            vec![ChangeType::Added, ChangeType::Deleted]
+        );
+    }
+
+    #[test]
+    fn entity_signature_stops_at_the_body() {
+        let signature = |entity_type: &str, content: &str| entity_signature(entity_type, content);
+        assert_eq!(
+            signature(
+                "function",
+                "/// Docs\npub fn run<T: Clone>(\n    items: &[T],\n) -> Result<(), E>\nwhere\n    T: Send,\n{\n    todo!()\n}"
+            )
+            .as_deref(),
+            Some("pub fn run<T: Clone>( items: &[T], ) -> Result<(), E> where T: Send,")
+        );
+        assert_eq!(
+            signature("method", "area(width: number): number {\n  return 1;\n}").as_deref(),
+            Some("area(width: number): number")
+        );
+        assert_eq!(
+            signature(
+                "function",
+                "def area(width: int) -> int:  # square\n    return width\n"
+            )
+            .as_deref(),
+            Some("def area(width: int) -> int")
+        );
+        assert_eq!(
+            signature("method", "def area(width)\n  width * width\nend").as_deref(),
+            Some("def area(width)")
+        );
+        assert_eq!(signature("class", "class Shape {}"), None);
+    }
+
+    #[test]
+    fn entity_signature_skips_braces_in_generics_and_object_types() {
+        let signature = |content: &str| entity_signature("function", content);
+        assert_eq!(
+            signature(
+                "function first<T extends { id: string }>(items: T[]): T {\n  return items[0];\n}"
+            )
+            .as_deref(),
+            Some("function first<T extends { id: string }>(items: T[]): T")
+        );
+        assert_eq!(
+            signature("function load(): { id: string; name: string } {\n  return { id: '', name: '' };\n}")
+                .as_deref(),
+            Some("function load(): { id: string; name: string }")
+        );
+        assert_eq!(
+            signature(
+                "function pick(): Promise<{ id: string }> | { id: null } {\n  return null;\n}"
+            )
+            .as_deref(),
+            Some("function pick(): Promise<{ id: string }> | { id: null }")
+        );
+        assert_eq!(
+            signature("fn run() -> Result<Vec<u8>, E> {\n    todo!()\n}").as_deref(),
+            Some("fn run() -> Result<Vec<u8>, E>")
+        );
+        assert_eq!(
+            signature("bool operator<(const Shape& other) const {\n  return true;\n}").as_deref(),
+            Some("bool operator<(const Shape& other) const")
+        );
+    }
+
     #[test]
     fn parent_name_terminates_on_cyclic_parent_id() {
         // Two entities whose parent_id chains form a cycle. parent_name
@@ -1,3 +1,4 @@
 pub mod change;
+pub mod diagnostic;
 pub mod entity;
 pub mod identity;
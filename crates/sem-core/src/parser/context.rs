@@ -664,7 +664,10 @@ mod tests {
             end_line: content.lines().count(),
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -673,6 +676,7 @@ mod tests {
             from_entity: from_entity.to_string(),
             to_entity: to_entity.to_string(),
             ref_type: RefType::Calls,
+            confidence: 1.0,
         }
     }
 
@@ -690,6 +694,8 @@ mod tests {
                         parent_id: entity.parent_id.clone(),
                         start_line: entity.start_line,
                         end_line: entity.end_line,
+                        is_test: false,
+                        is_private: false,
                     },
                 )
             })
@@ -16,11 +16,16 @@ macro_rules! maybe_par_iter {
         }
     }};
 }
-use crate::model::change::{ChangeType, SemanticChange};
-use crate::model::entity::SemanticEntity;
-use crate::model::identity::match_entities;
+use crate::model::change::{ChangeType, SemanticChange, UnchangedEntity};
+use crate::model::diagnostic::{ParseDiagnostic, SkipReason};
+use crate::model::entity::{assign_stable_ids, SemanticEntity};
+use crate::model::identity::{
+    match_cross_file_moves, match_entities, SimilarityMetric, DEFAULT_RENAME_THRESHOLD,
+};
+use crate::parser::ignore::IgnoreDirective;
 use crate::parser::plugin::SemanticParserPlugin;
 use crate::parser::registry::ParserRegistry;
+use crate::utils::hash::content_hash_bytes;
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -37,6 +42,13 @@ pub struct DiffResult {
     pub orphan_count: usize,
     pub total_entities_before: usize,
     pub total_entities_after: usize,
+    /// Unchanged entities in files with changes; only filled by
+    /// [`compute_semantic_diff_with`] with `include_unchanged` set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchanged: Vec<UnchangedEntity>,
+    /// Diffed files that produced no entities on their current side, and why.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<ParseDiagnostic>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -81,11 +93,123 @@ pub fn compute_semantic_diff(
     commit_sha: Option<&str>,
     author: Option<&str>,
 ) -> DiffResult {
+    compute_semantic_diff_with(
+        file_changes,
+        registry,
+        commit_sha,
+        author,
+        DiffSettings::default(),
+    )
+}
+
+/// Knobs for [`compute_semantic_diff_with`]; the default matches
+/// [`compute_semantic_diff`].
+#[derive(Debug, Clone, Copy)]
+pub struct DiffSettings {
+    /// Also list the unchanged entities of every file that has changes, for
+    /// a full per-file entity inventory.
+    pub include_unchanged: bool,
+    /// How entities whose IDs changed are paired up by content.
+    pub similarity: SimilarityMetric,
+    /// Minimum similarity, in (0, 1], for the fuzzy pass to report a rename
+    /// or move instead of an add/delete pair.
+    pub rename_threshold: f64,
+    /// Whether an entity that differs only in CRLF vs LF line endings or a
+    /// leading UTF-8 BOM counts as unchanged.
+    pub eol_normalize: bool,
+}
+
+impl Default for DiffSettings {
+    fn default() -> Self {
+        Self {
+            include_unchanged: false,
+            similarity: SimilarityMetric::default(),
+            rename_threshold: DEFAULT_RENAME_THRESHOLD,
+            eol_normalize: true,
+        }
+    }
+}
+
+type FileDiff = (
+    String,
+    Vec<SemanticChange>,
+    Vec<UnchangedEntity>,
+    usize,
+    usize,
+    Leftovers,
+);
+
+/// Entities a file's own matching left deleted and added, for the cross-file
+/// move pass.
+pub type Leftovers = (Vec<SemanticEntity>, Vec<SemanticEntity>);
+
+/// The before entities behind `changes`' deletions and the after entities
+/// behind its additions.
+fn unmatched_entities(
+    changes: &[SemanticChange],
+    before_entities: Vec<SemanticEntity>,
+    after_entities: Vec<SemanticEntity>,
+) -> Leftovers {
+    let deleted: HashSet<&str> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Deleted)
+        .map(|c| c.entity_id.as_str())
+        .collect();
+    let added: HashSet<&str> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Added)
+        .map(|c| c.entity_id.as_str())
+        .collect();
+    (
+        before_entities
+            .into_iter()
+            .filter(|e| deleted.contains(e.id.as_str()))
+            .collect(),
+        after_entities
+            .into_iter()
+            .filter(|e| added.contains(e.id.as_str()))
+            .collect(),
+    )
+}
+
+/// Rehash the entities whose text carries a CR or a leading BOM from the raw
+/// text, which [`content_hash`](crate::utils::hash::content_hash) normalizes, so line ending changes show
+/// up when [`DiffSettings::eol_normalize`] is off.
+fn hash_raw_line_endings(entities: &mut [SemanticEntity]) {
+    for entity in entities {
+        if entity.content.contains('\r') || entity.content.starts_with('\u{feff}') {
+            entity.content_hash = content_hash_bytes(entity.content.as_bytes());
+        }
+    }
+}
+
+/// [`compute_semantic_diff`] with explicit [`DiffSettings`].
+pub fn compute_semantic_diff_with(
+    file_changes: &[FileChange],
+    registry: &ParserRegistry,
+    commit_sha: Option<&str>,
+    author: Option<&str>,
+    settings: DiffSettings,
+) -> DiffResult {
+    compute_semantic_diff_with_leftovers(file_changes, registry, commit_sha, author, settings).0
+}
+
+/// [`compute_semantic_diff_with`], also returning the deleted and added
+/// entities no move paired up, so results computed separately can be
+/// stitched and still go through [`fold_cross_file_moves`].
+pub fn compute_semantic_diff_with_leftovers(
+    file_changes: &[FileChange],
+    registry: &ParserRegistry,
+    commit_sha: Option<&str>,
+    author: Option<&str>,
+    settings: DiffSettings,
+) -> (DiffResult, Leftovers) {
+    let similarity_fn = settings.similarity.similarity_fn();
     // Process files in parallel: each file's entity extraction and matching is independent
-    let per_file_changes: Vec<(String, Vec<SemanticChange>, usize, usize)> =
+    let per_file_changes: Vec<(Option<FileDiff>, Option<ParseDiagnostic>)> =
         maybe_par_iter!(file_changes)
             .filter(|file| !lacks_diffable_content(file))
-            .filter_map(|file| {
+            .map(|file| {
                 let content_hint = file
                     .after_content
                     .as_deref()
@@ -93,9 +217,19 @@ pub fn compute_semantic_diff(
                     .unwrap_or("");
                 let resolved = registry.resolve_file_path(&file.file_path);
                 let detection_path = resolved.as_deref().unwrap_or(&file.file_path);
-                let plugin = registry.get_plugin_with_content(detection_path, content_hint)?;
+                let Some(plugin) = registry.get_plugin_with_content(detection_path, content_hint)
+                else {
+                    let diagnostic = ParseDiagnostic {
+                        file_path: file.file_path.clone(),
+                        reason: SkipReason::Unsupported,
+                    };
+                    return (None, Some(diagnostic));
+                };
 
-                let before_entities = if let Some(ref content) = file.before_content {
+                // A panicking plugin yields no entities for that side rather
+                // than aborting the whole diff.
+                let mut panicked = false;
+                let mut before_entities = if let Some(ref content) = file.before_content {
                     let before_path = file.old_file_path.as_deref().unwrap_or(&file.file_path);
                     let before_resolved = registry.resolve_file_path(before_path);
                     let before_detection = before_resolved.as_deref().unwrap_or(before_path);
@@ -103,22 +237,57 @@ pub fn compute_semantic_diff(
                         plugin.extract_entities(content, before_detection)
                     })) {
                         Ok(entities) => entities,
-                        Err(_) => Vec::new(),
+                        Err(_) => {
+                            panicked |= file.after_content.is_none();
+                            Vec::new()
+                        }
                     }
                 } else {
                     Vec::new()
                 };
 
-                let after_entities = if let Some(ref content) = file.after_content {
+                let mut after_entities = if let Some(ref content) = file.after_content {
                     match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                         plugin.extract_entities(content, detection_path)
                     })) {
                         Ok(entities) => entities,
-                        Err(_) => Vec::new(),
+                        Err(_) => {
+                            panicked = true;
+                            Vec::new()
+                        }
                     }
                 } else {
                     Vec::new()
                 };
+                if !settings.eol_normalize {
+                    hash_raw_line_endings(&mut before_entities);
+                    hash_raw_line_endings(&mut after_entities);
+                }
+                registry.drop_ignored_entity_types(&mut before_entities);
+                registry.drop_ignored_entity_types(&mut after_entities);
+                assign_stable_ids(&mut before_entities);
+                assign_stable_ids(&mut after_entities);
+
+                // Diagnose the side that represents the file now: the new
+                // content, or the old one for a deletion.
+                let current_entities = if file.after_content.is_some() {
+                    &after_entities
+                } else {
+                    &before_entities
+                };
+                let diagnostic = if panicked {
+                    Some(SkipReason::ParseError {
+                        message: "parser panicked".to_string(),
+                    })
+                } else if current_entities.is_empty() {
+                    Some(registry.diagnose_no_entities(detection_path, content_hint))
+                } else {
+                    None
+                }
+                .map(|reason| ParseDiagnostic {
+                    file_path: file.file_path.clone(),
+                    reason,
+                });
 
                 let before_count = before_entities.len();
                 let after_count = after_entities.len();
@@ -127,7 +296,10 @@ pub fn compute_semantic_diff(
                     &before_entities,
                     &after_entities,
                     &file.file_path,
-                    None,
+                    similarity_fn
+                        .as_ref()
+                        .map(|f| f as &dyn Fn(&SemanticEntity, &SemanticEntity) -> f64),
+                    settings.rename_threshold,
                     commit_sha,
                     author,
                 );
@@ -136,6 +308,10 @@ pub fn compute_semantic_diff(
                 // by child entity changes (e.g. impl blocks when methods changed).
                 suppress_redundant_parents(&mut result.changes, &before_entities, &after_entities);
 
+                if registry.ignore_directives {
+                    drop_ignored_changes(&mut result.changes, &before_entities, &after_entities);
+                }
+
                 // Detect orphan changes (lines that changed outside any entity span).
                 let orphans = detect_orphan_changes(
                     file,
@@ -148,32 +324,74 @@ pub fn compute_semantic_diff(
                 );
                 result.changes.extend(orphans);
 
+                if registry.exclude_tests {
+                    drop_test_changes(
+                        &mut result.changes,
+                        &file.file_path,
+                        &before_entities,
+                        &after_entities,
+                    );
+                }
+
+                if registry.public_only {
+                    drop_private_changes(&mut result.changes, &before_entities, &after_entities);
+                }
+
                 result.changes.sort_by_key(|change| change.entity_line);
 
                 if result.changes.is_empty() {
-                    None
+                    (None, diagnostic)
                 } else {
-                    Some((
+                    if !settings.include_unchanged {
+                        result.unchanged.clear();
+                    }
+                    let leftovers =
+                        unmatched_entities(&result.changes, before_entities, after_entities);
+                    let file_diff = (
                         file.file_path.clone(),
                         result.changes,
+                        result.unchanged,
                         before_count,
                         after_count,
-                    ))
+                        leftovers,
+                    );
+                    (Some(file_diff), diagnostic)
                 }
             })
             .collect();
 
     let mut all_changes: Vec<SemanticChange> = Vec::new();
+    let mut all_unchanged: Vec<UnchangedEntity> = Vec::new();
     let mut files_with_changes: HashSet<String> = HashSet::new();
     let mut total_entities_before: usize = 0;
     let mut total_entities_after: usize = 0;
-    for (file_path, changes, before_count, after_count) in per_file_changes {
+    let mut diagnostics: Vec<ParseDiagnostic> = Vec::new();
+    let mut leftover_deleted: Vec<SemanticEntity> = Vec::new();
+    let mut leftover_added: Vec<SemanticEntity> = Vec::new();
+    for (file_diff, diagnostic) in per_file_changes {
+        diagnostics.extend(diagnostic);
+        let Some((file_path, changes, unchanged, before_count, after_count, leftovers)) = file_diff
+        else {
+            continue;
+        };
         files_with_changes.insert(file_path);
         all_changes.extend(changes);
+        all_unchanged.extend(unchanged);
         total_entities_before += before_count;
         total_entities_after += after_count;
+        leftover_deleted.extend(leftovers.0);
+        leftover_added.extend(leftovers.1);
     }
 
+    let mut leftovers = (leftover_deleted, leftover_added);
+    fold_cross_file_moves(
+        &mut all_changes,
+        &mut leftovers,
+        settings,
+        commit_sha,
+        author,
+    );
+
     // Single-pass counting. Orphans are first-class changes for the
     // change-type buckets, and orphan_count is cross-cutting metadata.
     let mut added_count = 0;
@@ -213,7 +431,7 @@ pub fn compute_semantic_diff(
         }
     }
 
-    DiffResult {
+    let result = DiffResult {
         changes: all_changes,
         file_count: files_with_changes.len(),
         added_count,
@@ -225,7 +443,144 @@ pub fn compute_semantic_diff(
         orphan_count,
         total_entities_before,
         total_entities_after,
+        unchanged: all_unchanged,
+        diagnostics,
+    };
+    (result, leftovers)
+}
+
+/// Cross-file moves: an entity deleted from one file and added to another
+/// becomes one Moved change in place of the added half. Paired entities are
+/// taken out of `leftovers`.
+pub fn fold_cross_file_moves(
+    changes: &mut Vec<SemanticChange>,
+    leftovers: &mut Leftovers,
+    settings: DiffSettings,
+    commit_sha: Option<&str>,
+    author: Option<&str>,
+) {
+    let similarity_fn = settings.similarity.similarity_fn();
+    let moves = match_cross_file_moves(
+        &leftovers.0,
+        &leftovers.1,
+        similarity_fn
+            .as_ref()
+            .map(|f| f as &dyn Fn(&SemanticEntity, &SemanticEntity) -> f64),
+        settings.rename_threshold,
+        commit_sha,
+        author,
+    );
+    if moves.is_empty() {
+        return;
+    }
+    let deleted_ids: HashSet<String> = moves.iter().map(|(id, _)| id.clone()).collect();
+    let added_ids: HashSet<String> = moves
+        .iter()
+        .map(|(_, change)| change.entity_id.clone())
+        .collect();
+    leftovers.0.retain(|e| !deleted_ids.contains(&e.id));
+    leftovers.1.retain(|e| !added_ids.contains(&e.id));
+    let mut moved: HashMap<String, SemanticChange> = moves
+        .into_iter()
+        .map(|(_, change)| (change.entity_id.clone(), change))
+        .collect();
+    *changes = std::mem::take(changes)
+        .into_iter()
+        .filter_map(|change| match change.change_type {
+            ChangeType::Deleted if deleted_ids.contains(&change.entity_id) => None,
+            ChangeType::Added => match moved.remove(&change.entity_id) {
+                // The per-file pass saw the whole file, so its parent
+                // name is complete where the leftovers alone may not be.
+                Some(mut moved_change) => {
+                    moved_change.parent_name = change.parent_name;
+                    Some(moved_change)
+                }
+                None => Some(change),
+            },
+            _ => Some(change),
+        })
+        .collect();
+}
+
+/// Drop changes to entities annotated `sem:ignore`, judged on the version
+/// that exists now (the old one only for deletions).
+fn drop_ignored_changes(
+    changes: &mut Vec<SemanticChange>,
+    before: &[SemanticEntity],
+    after: &[SemanticEntity],
+) {
+    let ignored = |entities: &[SemanticEntity]| -> HashSet<String> {
+        entities
+            .iter()
+            .filter(|entity| IgnoreDirective::of(entity) == Some(IgnoreDirective::All))
+            .map(|entity| entity.id.clone())
+            .collect()
+    };
+    let ignored_before = ignored(before);
+    let ignored_after = ignored(after);
+    if ignored_before.is_empty() && ignored_after.is_empty() {
+        return;
     }
+
+    let after_ids: HashSet<&str> = after.iter().map(|entity| entity.id.as_str()).collect();
+    changes.retain(|change| {
+        if after_ids.contains(change.entity_id.as_str()) {
+            !ignored_after.contains(&change.entity_id)
+        } else {
+            !ignored_before.contains(&change.entity_id)
+        }
+    });
+}
+
+/// Drop changes in the test partition. The entities stay in the match, so
+/// their lines don't resurface as orphan changes.
+fn drop_test_changes(
+    changes: &mut Vec<SemanticChange>,
+    file_path: &str,
+    before: &[SemanticEntity],
+    after: &[SemanticEntity],
+) {
+    if crate::parser::test_detect::is_test_path(file_path) {
+        changes.clear();
+        return;
+    }
+    let tests_before = crate::parser::test_detect::test_partition_ids(before);
+    let tests_after = crate::parser::test_detect::test_partition_ids(after);
+    if tests_before.is_empty() && tests_after.is_empty() {
+        return;
+    }
+
+    let after_ids: HashSet<&str> = after.iter().map(|entity| entity.id.as_str()).collect();
+    changes.retain(|change| {
+        if after_ids.contains(change.entity_id.as_str()) {
+            !tests_after.contains(&change.entity_id)
+        } else {
+            !tests_before.contains(&change.entity_id)
+        }
+    });
+}
+
+/// Drop changes to entities outside the public surface on both sides, and
+/// orphan lines, which belong to no declaration. An entity that became public
+/// or stopped being public stays, since that is an API change.
+fn drop_private_changes(
+    changes: &mut Vec<SemanticChange>,
+    before: &[SemanticEntity],
+    after: &[SemanticEntity],
+) {
+    let private_before = crate::parser::visibility::private_ids(before);
+    let private_after = crate::parser::visibility::private_ids(after);
+    let before_ids: HashSet<&str> = before.iter().map(|entity| entity.id.as_str()).collect();
+    let after_ids: HashSet<&str> = after.iter().map(|entity| entity.id.as_str()).collect();
+    changes.retain(|change| {
+        if change.entity_type == "orphan" {
+            return false;
+        }
+        let id = change.entity_id.as_str();
+        let public_before = before_ids.contains(id) && !private_before.contains(id);
+        let public_after = after_ids.contains(id) && !private_after.contains(id);
+        public_before || public_after
+    });
 }
 
 fn suppress_redundant_parents(
@@ -253,7 +608,25 @@ fn suppress_redundant_parents(
         "variable",
         "svelte_instance_script",
         "svelte_module_script",
+        "svelte_style",
+        "sfc_block",
         "object",
+        "section",
+        "array_table",
+        "at_rule",
+        "rule",
+        "element",
+        "type",
+        "input",
+        "enum",
+        "extend_type",
+        "extend_input",
+        "extend_enum",
+        "extend_interface",
+        "message",
+        "service",
+        "oneof",
+        "stage",
     ];
 
     let before_by_id: HashMap<&str, &SemanticEntity> =
@@ -546,6 +919,8 @@ fn detect_orphan_changes(
                 plugin,
                 detection_path,
             ),
+            signature_change: None,
+            stable_id: None,
         });
     }
 
@@ -751,7 +1126,10 @@ mod tests {
             end_line,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -777,6 +1155,268 @@ mod tests {
         assert_eq!(result.changes[0].structural_change, Some(false));
     }
 
+    #[test]
+    fn exclude_tests_drops_test_file_and_cfg_test_changes_without_orphans() {
+        let before = "pub fn prod() -> i32 { 1 }\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn checks() { assert_eq!(1, 1); }\n}\n";
+        let after = "pub fn prod() -> i32 { 2 }\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn checks() { assert_eq!(2, 2); }\n}\n";
+        let files = [
+            modified_file("src/lib.rs", before, after),
+            modified_file(
+                "src/prod.test.ts",
+                "test('a', () => 1);\n",
+                "test('a', () => 2);\n",
+            ),
+        ];
+
+        let mut registry = create_default_registry();
+        registry.exclude_tests = true;
+        let result = compute_semantic_diff(&files, &registry, None, None);
+
+        let names: Vec<&str> = result
+            .changes
+            .iter()
+            .map(|change| change.entity_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["prod"]);
+        assert_eq!(result.orphan_count, 0);
+    }
+
+    #[test]
+    fn public_only_keeps_public_surface_and_visibility_changes() {
+        let files = [
+            modified_file(
+                "src/lib.rs",
+                "pub fn api() -> i32 { 1 }\nfn helper() -> i32 { 1 }\nfn opened() {}\n",
+                "pub fn api() -> i32 { 2 }\nfn helper() -> i32 { 2 }\npub fn opened() {}\n",
+            ),
+            modified_file(
+                "src/service.ts",
+                "export function run() { return 1; }\nfunction local() { return 1; }\n",
+                "export function run() { return 2; }\nfunction local() { return 2; }\n",
+            ),
+        ];
+
+        let mut registry = create_default_registry();
+        registry.public_only = true;
+        let result = compute_semantic_diff(&files, &registry, None, None);
+
+        let mut names: Vec<&str> = result
+            .changes
+            .iter()
+            .map(|change| change.entity_name.as_str())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["api", "opened", "run"]);
+    }
+
+    #[test]
+    fn line_inserted_into_unknown_file_changes_only_its_chunk() {
+        let blocks: Vec<String> = (0..12)
+            .map(|block| format!("block {block} a\nblock {block} b\nblock {block} c\n"))
+            .collect();
+        let before = blocks.join("\n");
+        let after = before.replacen("block 1 a", "inserted\nblock 1 a", 1);
+        let files = [modified_file("notes.unknown", &before, &after)];
+
+        let result = compute_semantic_diff(&files, &create_default_registry(), None, None);
+        assert!(!result.changes.is_empty());
+        assert!(
+            result.changes.iter().all(|change| change.start_line == 5),
+            "{:?}",
+            result
+                .changes
+                .iter()
+                .map(|change| &change.entity_name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn function_moved_between_files_is_one_moved_change() {
+        let moved = "export function total(items: number[]) {\n  return items.reduce((sum, n) => sum + n, 0);\n}\n";
+        let keep = "export function keep() {\n  return 1;\n}\n";
+        let other = "export function other() {\n  return 2;\n}\n";
+        let files = [
+            modified_file("a.ts", &format!("{keep}\n{moved}"), keep),
+            modified_file("b.ts", other, &format!("{other}\n{moved}")),
+        ];
+
+        let result = compute_semantic_diff(&files, &create_default_registry(), None, None);
+        assert_eq!(result.changes.len(), 1, "{:?}", result.changes);
+        let change = &result.changes[0];
+        assert_eq!(change.change_type, ChangeType::Moved);
+        assert_eq!(change.entity_name, "total");
+        assert_eq!(change.file_path, "b.ts");
+        assert_eq!(change.old_file_path.as_deref(), Some("a.ts"));
+        assert_eq!(
+            (result.moved_count, result.added_count, result.deleted_count),
+            (1, 0, 0)
+        );
+        assert_eq!(result.file_count, 2);
+    }
+
+    #[test]
+    fn same_body_under_another_name_in_another_file_is_not_a_move() {
+        let keep = "export function keep() {\n  return 1;\n}\n";
+        let other = "export function other() {\n  return 2;\n}\n";
+        let gone = "export function gone() {\n  return [1, 2, 3];\n}\n";
+        let fresh = "export function fresh() {\n  return [1, 2, 3];\n}\n";
+        let files = [
+            modified_file("a.ts", &format!("{keep}\n{gone}"), keep),
+            modified_file("b.ts", other, &format!("{other}\n{fresh}")),
+        ];
+
+        let result = compute_semantic_diff(&files, &create_default_registry(), None, None);
+        assert_eq!(
+            (result.moved_count, result.added_count, result.deleted_count),
+            (0, 1, 1),
+            "{:?}",
+            result.changes
+        );
+    }
+
+    #[test]
+    fn reversed_diff_swaps_added_and_deleted() {
+        let before = "export function kept() {\n  return 1;\n}\n\nexport function dropped() {\n  return 2;\n}\n";
+        let after = "export function kept() {\n  return 10;\n}\n\nexport function fresh() {\n  return [3, 4, 5];\n}\n";
+        let files = vec![modified_file("a.ts", before, after)];
+        let registry = create_default_registry();
+
+        let forward = compute_semantic_diff(&files, &registry, None, None);
+        let reversed: Vec<FileChange> = files.into_iter().map(FileChange::reversed).collect();
+        let backward = compute_semantic_diff(&reversed, &registry, None, None);
+
+        let kinds = |result: &DiffResult| {
+            let mut kinds: Vec<(String, ChangeType)> = result
+                .changes
+                .iter()
+                .map(|change| (change.entity_name.clone(), change.change_type))
+                .collect();
+            kinds.sort_by(|a, b| a.0.cmp(&b.0));
+            kinds
+        };
+        assert_eq!(
+            kinds(&forward),
+            vec![
+                ("dropped".to_string(), ChangeType::Deleted),
+                ("fresh".to_string(), ChangeType::Added),
+                ("kept".to_string(), ChangeType::Modified),
+            ]
+        );
+        assert_eq!(
+            kinds(&backward),
+            vec![
+                ("dropped".to_string(), ChangeType::Added),
+                ("fresh".to_string(), ChangeType::Deleted),
+                ("kept".to_string(), ChangeType::Modified),
+            ]
+        );
+        assert_eq!(
+            (forward.added_count, forward.deleted_count),
+            (backward.deleted_count, backward.added_count)
+        );
+        assert_eq!(forward.modified_count, backward.modified_count);
+        assert_eq!(forward.total_entities_before, backward.total_entities_after);
+        assert_eq!(forward.total_entities_after, backward.total_entities_before);
+    }
+
+    #[test]
+    fn include_unchanged_lists_untouched_entities() {
+        let before = "def a():\n    return 1\n\ndef b():\n    return 2\n";
+        let after = "def a():\n    return 10\n\ndef b():\n    return 2\n";
+        let files = [modified_file("app.py", before, after)];
+
+        let registry = create_default_registry();
+        let settings = DiffSettings {
+            include_unchanged: true,
+            ..DiffSettings::default()
+        };
+        let result = compute_semantic_diff_with(&files, &registry, None, None, settings);
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].entity_name, "a");
+        let unchanged: Vec<&str> = result
+            .unchanged
+            .iter()
+            .map(|e| e.entity_name.as_str())
+            .collect();
+        assert_eq!(unchanged, vec!["b"]);
+        assert_eq!(result.unchanged[0].file_path, "app.py");
+        assert_eq!(result.unchanged[0].start_line, 4);
+
+        assert!(compute_semantic_diff(&files, &registry, None, None)
+            .unchanged
+            .is_empty());
+    }
+
+    #[test]
+    fn crlf_copy_is_unchanged_unless_eol_normalize_is_off() {
+        let before = "def a():\n    return 1\n\ndef b():\n    return 2\n";
+        let after = "def a():\r\n    return 1\r\n\r\ndef b():\r\n    return 2\r\n";
+        let files = [modified_file("app.py", before, after)];
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(&files, &registry, None, None);
+        assert!(result.changes.is_empty(), "{:?}", result.changes);
+
+        let settings = DiffSettings {
+            eol_normalize: false,
+            ..DiffSettings::default()
+        };
+        let result = compute_semantic_diff_with(&files, &registry, None, None, settings);
+        let modified: Vec<&str> = result
+            .changes
+            .iter()
+            .filter(|c| c.change_type == ChangeType::Modified)
+            .map(|c| c.entity_name.as_str())
+            .collect();
+        assert_eq!(modified, vec!["a", "b"]);
+    }
+
+    fn signature_changes(path: &str, before: &str, after: &str) -> Vec<(String, Option<bool>)> {
+        let registry = create_default_registry();
+        let result =
+            compute_semantic_diff(&[modified_file(path, before, after)], &registry, None, None);
+        result
+            .changes
+            .into_iter()
+            .map(|change| (change.entity_name, change.signature_change))
+            .collect()
+    }
+
+    #[test]
+    fn rust_signature_change_is_separate_from_body_change() {
+        let before = "fn area(width: u32) -> u32 {\n    width * width\n}\n";
+        let body_only = "fn area(width: u32) -> u32 {\n    width.pow(2)\n}\n";
+        let param_added = "fn area(width: u32, height: u32) -> u32 {\n    width * height\n}\n";
+
+        assert_eq!(
+            signature_changes("lib.rs", before, body_only),
+            vec![("area".to_string(), Some(false))]
+        );
+        assert_eq!(
+            signature_changes("lib.rs", before, param_added),
+            vec![("area".to_string(), Some(true))]
+        );
+    }
+
+    #[test]
+    fn typescript_signature_change_is_separate_from_body_change() {
+        let before =
+            "class Shape {\n  area(width: number): number {\n    return width * width;\n  }\n}\n";
+        let body_only =
+            "class Shape {\n  area(width: number): number {\n    return width ** 2;\n  }\n}\n";
+        let param_added = "class Shape {\n  area(width: number, height: number): number {\n    return width * height;\n  }\n}\n";
+
+        assert_eq!(
+            signature_changes("shape.ts", before, body_only),
+            vec![("area".to_string(), Some(false))]
+        );
+        assert_eq!(
+            signature_changes("shape.ts", before, param_added),
+            vec![("area".to_string(), Some(true))]
+        );
+    }
+
     #[test]
     fn orphan_code_change_is_structural() {
         let before = "import os\n\ndef value():\n    return 1\n";
@@ -945,6 +1585,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_python_decorator_only_change_is_modified() {
+        let before = "def load(key):\n    return store[key]\n\nclass Repo:\n    def find(self, key):\n        return store[key]\n";
+        let after = "@cached\ndef load(key):\n    return store[key]\n\nclass Repo:\n    @cached\n    def find(self, key):\n        return store[key]\n";
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[modified_file("repo.py", before, after)],
+            &registry,
+            None,
+            None,
+        );
+
+        for name in ["load", "find"] {
+            let change = result
+                .changes
+                .iter()
+                .find(|c| c.entity_name == name)
+                .unwrap_or_else(|| panic!("{name} should be reported, got: {:?}", result.changes));
+            assert_eq!(change.change_type, ChangeType::Modified);
+        }
+    }
+
+    #[test]
+    fn test_sem_ignore_hides_annotated_entity_changes() {
+        let before = "// sem:ignore\nfunction generated() { return 1; }\n\nfunction handwritten() { return 1; }\n\nclass Table {\n  // sem:ignore\n  row() { return 1; }\n}\n";
+        let after = "// sem:ignore\nfunction generated() { return 2; }\n\nfunction handwritten() { return 2; }\n\nclass Table {\n  // sem:ignore\n  row() { return 2; }\n}\n";
+        let files = [modified_file("gen.ts", before, after)];
+
+        let mut registry = create_default_registry();
+        let result = compute_semantic_diff(&files, &registry, None, None);
+        let names: Vec<&str> = result
+            .changes
+            .iter()
+            .map(|c| c.entity_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["handwritten"]);
+
+        registry.ignore_directives = false;
+        let result = compute_semantic_diff(&files, &registry, None, None);
+        let names: Vec<&str> = result
+            .changes
+            .iter()
+            .map(|c| c.entity_name.as_str())
+            .collect();
+        assert!(names.contains(&"generated"), "got: {names:?}");
+        assert!(names.contains(&"row"), "got: {names:?}");
+    }
+
+    #[test]
+    fn test_sem_ignore_impact_keeps_changes_reported() {
+        let before = "# sem:ignore-impact\ndef build():\n    return 1\n";
+        let after = "# sem:ignore-impact\ndef build():\n    return 2\n";
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[modified_file("gen.py", before, after)],
+            &registry,
+            None,
+            None,
+        );
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].entity_name, "build");
+    }
+
     #[test]
     fn test_nested_typescript_class_field_diff_reports_leaf_method() {
         let before = r#"class L1 {
@@ -1142,6 +1847,37 @@ mod tests {
         assert_eq!(result.changes[0].structural_change, Some(true));
     }
 
+    #[test]
+    fn renamed_function_keeps_its_stable_id() {
+        let before = "def total(items):\n    return sum(items)\n\ndef other():\n    return 0\n";
+        let after =
+            "def grand_total(items):\n    return sum(items)\n\ndef other():\n    return 0\n";
+
+        let registry = create_default_registry();
+        let before_entities = registry.extract_entities("cart.py", before);
+        let after_entities = registry.extract_entities("cart.py", after);
+        let stable_id_of = |entities: &[SemanticEntity], name: &str| {
+            let entity = entities.iter().find(|e| e.name == name).unwrap();
+            (entity.id.clone(), entity.stable_id.clone().unwrap())
+        };
+        let (old_id, old_stable_id) = stable_id_of(&before_entities, "total");
+        let (new_id, new_stable_id) = stable_id_of(&after_entities, "grand_total");
+        assert_ne!(old_id, new_id);
+        assert_eq!(old_stable_id, new_stable_id);
+        assert_ne!(old_stable_id, stable_id_of(&after_entities, "other").1);
+
+        let result = compute_semantic_diff(
+            &[modified_file("cart.py", before, after)],
+            &registry,
+            None,
+            None,
+        );
+        assert_eq!(result.changes.len(), 1, "{:?}", result.changes);
+        assert_eq!(result.changes[0].change_type, ChangeType::Renamed);
+        assert_eq!(result.changes[0].entity_id, new_id);
+        assert_eq!(result.changes[0].stable_id, Some(old_stable_id));
+    }
+
     #[test]
     fn duplicate_markdown_heading_reports_first_section_modification() {
         let before = "# Same Title\n\noriginal content of section A\n\n# Same Title\n\ncontent of section B\n";
@@ -1174,6 +1910,26 @@ mod tests {
             .contains("MODIFIED content of section A"));
     }
 
+    #[test]
+    fn emptying_markdown_section_reports_modification() {
+        let before = "# Intro\n\ntext\n\n## Notes\n\nsome notes\nmore notes\n";
+        let after = "# Intro\n\ntext\n\n## Notes\n";
+
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(
+            &[modified_file("doc.md", before, after)],
+            &registry,
+            None,
+            None,
+        );
+
+        assert_eq!(result.changes.len(), 1, "{:?}", result.changes);
+        let change = &result.changes[0];
+        assert_eq!(change.change_type, ChangeType::Modified);
+        assert_eq!(change.entity_name, "Notes");
+        assert_eq!(change.after_content.as_deref(), Some("## Notes"));
+    }
+
     #[test]
     fn orphan_changes_count_toward_change_type_buckets() {
         let before = "def foo():\n    return 1\n\ndef bar():\n    return 2\n";
@@ -1371,4 +2127,91 @@ mod tests {
         assert_eq!(non_orphan[0].entity_name, ":published");
         assert_eq!(non_orphan[0].change_type, ChangeType::Added);
     }
+
+    #[test]
+    fn files_without_entities_are_reported_as_diagnostics() {
+        let files = vec![
+            modified_file(
+                "app.py",
+                "def ok():\n    return 1\n",
+                "def ok():\n    return 2\n",
+            ),
+            modified_file("broken.py", "def fine():\n    pass\n", "def (:\n"),
+            modified_file("empty.py", "def gone():\n    pass\n", "\n"),
+        ];
+        let registry = create_default_registry();
+        let result = compute_semantic_diff(&files, &registry, None, None);
+
+        let mut diagnostics = result.diagnostics.clone();
+        diagnostics.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        assert_eq!(
+            diagnostics,
+            vec![
+                ParseDiagnostic {
+                    file_path: "broken.py".to_string(),
+                    reason: SkipReason::ParseError {
+                        message: "syntax error at line 1".to_string(),
+                    },
+                },
+                ParseDiagnostic {
+                    file_path: "empty.py".to_string(),
+                    reason: SkipReason::Empty,
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_diff_matches_single_threaded() {
+        let mut files = Vec::new();
+        for i in 0..24 {
+            files.push(modified_file(
+                &format!("pkg/mod_{i}.py"),
+                &format!("def keep_{i}():\n    return {i}\n\ndef edit_{i}():\n    return 0\n"),
+                &format!(
+                    "def keep_{i}():\n    return {i}\n\ndef edit_{i}():\n    return 1\n\ndef new_{i}():\n    pass\n"
+                ),
+            ));
+            files.push(modified_file(
+                &format!("config/app_{i}.json"),
+                &format!("{{\n  \"name\": \"app{i}\",\n  \"port\": 80\n}}\n"),
+                &format!("{{\n  \"name\": \"app{i}\",\n  \"port\": 8080\n}}\n"),
+            ));
+        }
+        files.push(renamed_file(
+            "src/old.rs",
+            "src/new.rs",
+            "fn stays() -> u32 {\n    1\n}\n",
+            "fn stays() -> u32 {\n    2\n}\n",
+        ));
+
+        let registry = create_default_registry();
+        let diff_on = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let result = pool.install(|| compute_semantic_diff(&files, &registry, None, None));
+            let mut changes: Vec<String> = result
+                .changes
+                .iter()
+                .map(|change| serde_json::to_string(change).unwrap())
+                .collect();
+            changes.sort();
+            let counts = (
+                result.file_count,
+                result.added_count,
+                result.modified_count,
+                result.deleted_count,
+                result.total_entities_before,
+                result.total_entities_after,
+            );
+            (changes, counts)
+        };
+
+        let sequential = diff_on(1);
+        assert_eq!(sequential.0.len(), 24 * 3 + 1);
+        assert_eq!(diff_on(4), sequential);
+    }
 }
@@ -10,7 +10,7 @@
 use std::borrow::Cow;
 use std::collections::HashSet as StdHashSet;
 use std::io::BufRead;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock, OnceLock};
 
 #[cfg(feature = "parallel")]
@@ -91,13 +91,16 @@ macro_rules! maybe_par_iter {
 
 use crate::git::types::{FileChange, FileStatus};
 use crate::model::entity::SemanticEntity;
+use crate::parser::ignore::IgnoreDirective;
 use crate::parser::import_resolution::{
     find_import_file, find_import_target, import_source_matches_file, is_js_ts_file,
     js_ts_import_source_files_from_content, js_ts_named_exports_from_content,
-    sort_import_candidate_files, JS_TS_EXTENSIONS,
+    normalize_repo_path, sort_import_candidate_files, JS_TS_EXTENSIONS,
 };
+use crate::parser::plugins::markdown::LINKS_METADATA_KEY;
 use crate::parser::registry::{resolve_go_method_parent_ids, ParserRegistry};
 use crate::parser::scope_resolve;
+use crate::parser::stopwords::{StopwordOverrides, Stopwords};
 
 #[cfg(not(test))]
 const PARSED_FILE_REUSE_LIMIT: usize = 20_000;
@@ -338,6 +341,76 @@ pub struct EntityRef {
     pub from_entity: String,
     pub to_entity: String,
     pub ref_type: RefType,
+    /// How much to trust this edge, in `0.0..=1.0`. See [`edge_confidence`].
+    #[serde(default = "full_edge_confidence")]
+    pub confidence: f32,
+}
+
+fn full_edge_confidence() -> f32 {
+    1.0
+}
+
+/// Score a resolved edge. References are matched by name, so an edge is only
+/// as trustworthy as the evidence behind it: a `name(` call to a target in the
+/// same file is near-certain, a cross-file match backed by an import is
+/// strong, and a bare cross-file name match is weak — weaker still when
+/// `same_name_targets` entities share the target's name.
+pub fn edge_confidence(
+    ref_type: &RefType,
+    same_file: bool,
+    imported: bool,
+    same_name_targets: usize,
+) -> f32 {
+    match (ref_type, same_file, imported) {
+        (RefType::Calls | RefType::Implements | RefType::Extends, true, _) => 0.95,
+        (RefType::TypeRef, true, _) | (RefType::Imports, true, _) => 0.85,
+        (_, false, true) => 0.9,
+        (ref_type, false, false) => {
+            let base = match ref_type {
+                RefType::Implements | RefType::Extends => 0.8,
+                RefType::Calls => 0.7,
+                RefType::Imports => 0.6,
+                RefType::TypeRef => 0.5,
+            };
+            base / same_name_targets.max(1) as f32
+        }
+    }
+}
+
+/// Above this many same-named entities, a bare cross-file reference to the
+/// name is too ambiguous to link: the resolver could only pick one of them
+/// arbitrarily. Same-file and import-backed references still resolve.
+pub const AMBIGUOUS_NAME_TARGETS: usize = 32;
+
+/// Fill in [`EntityRef::confidence`] for freshly resolved edges.
+fn assign_edge_confidence(
+    edges: &mut [EntityRef],
+    entity_map: &HashMap<String, EntityInfo>,
+    imports_by_file: &ImportsByFile<'_>,
+) {
+    let mut name_counts: HashMap<&str, usize> = HashMap::default();
+    for entity in entity_map.values() {
+        *name_counts.entry(entity.name.as_str()).or_default() += 1;
+    }
+
+    for edge in edges {
+        let (Some(from), Some(to)) = (
+            entity_map.get(&edge.from_entity),
+            entity_map.get(&edge.to_entity),
+        ) else {
+            continue;
+        };
+        let imported = imports_by_file
+            .get(from.file_path.as_str())
+            .and_then(|imports| imports.get(to.name.as_str()))
+            .is_some_and(|target| *target == to.id);
+        edge.confidence = edge_confidence(
+            &edge.ref_type,
+            from.file_path == to.file_path,
+            imported,
+            name_counts.get(to.name.as_str()).copied().unwrap_or(1),
+        );
+    }
 }
 
 /// Type of reference between entities.
@@ -350,6 +423,13 @@ pub enum RefType {
     TypeRef,
     /// Import/use statement reference
     Imports,
+    /// Trait or interface implementation: an `impl Trait for Type` block to
+    /// the trait (and each of its methods to the trait method it provides), or
+    /// a class to an interface in its `implements` clause
+    Implements,
+    /// Inheritance: a class to its superclass, or an interface to an
+    /// interface it extends
+    Extends,
 }
 
 /// A complete entity dependency graph for a set of files.
@@ -385,6 +465,74 @@ pub struct EntityInfo {
     pub parent_id: Option<String>,
     pub start_line: usize,
     pub end_line: usize,
+    /// In the test partition: a test file or a Rust `#[cfg(test)]` module
+    /// (see [`crate::parser::test_detect::test_partition_ids`]).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_test: bool,
+    /// Outside the public surface: private itself or nested in something
+    /// private (see [`crate::parser::visibility::private_ids`]).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_private: bool,
+}
+
+/// How much a change to one entity weighs, beyond the raw count of what it
+/// reaches. Every transitive dependent contributes its size in lines times
+/// one plus its own dependent count, divided by its distance from the change.
+/// A large, widely used class nearby outweighs a one-line constant far away.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpactScore {
+    /// Sum of every impacted entity's weight, rounded to one decimal.
+    pub score: f64,
+    /// The heaviest impacted entities, highest weight first.
+    pub heaviest: Vec<WeightedImpact>,
+}
+
+/// One impacted entity's share of an [`ImpactScore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedImpact {
+    pub entity_id: String,
+    pub weight: f64,
+}
+
+/// How many entities [`ImpactScore::heaviest`] lists.
+pub const IMPACT_SCORE_HEAVIEST: usize = 5;
+
+impl ImpactScore {
+    /// Score an impact set of `(entity, depth)` pairs. `dependent_count`
+    /// gives each entity's own number of incoming references.
+    pub fn from_impact<'a>(
+        impact: impl IntoIterator<Item = (&'a EntityInfo, usize)>,
+        dependent_count: impl Fn(&str) -> usize,
+    ) -> Self {
+        let mut weighted: Vec<WeightedImpact> = impact
+            .into_iter()
+            .map(|(info, depth)| {
+                let lines = info.end_line.saturating_sub(info.start_line) + 1;
+                let reach = dependent_count(&info.id) + 1;
+                WeightedImpact {
+                    entity_id: info.id.clone(),
+                    weight: round_tenths((lines * reach) as f64 / depth.max(1) as f64),
+                }
+            })
+            .collect();
+        let score = round_tenths(weighted.iter().map(|w| w.weight).sum());
+        weighted.sort_by(|a, b| {
+            b.weight
+                .total_cmp(&a.weight)
+                .then_with(|| a.entity_id.cmp(&b.entity_id))
+        });
+        weighted.truncate(IMPACT_SCORE_HEAVIEST);
+        ImpactScore {
+            score,
+            heaviest: weighted,
+        }
+    }
+}
+
+fn round_tenths(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
 }
 
 // FxHashMap (rustc-hash), not std SipHash: these graph maps are built and
@@ -468,6 +616,8 @@ fn ref_type_sort_key(ref_type: &RefType) -> u8 {
         RefType::Calls => 0,
         RefType::Imports => 1,
         RefType::TypeRef => 2,
+        RefType::Implements => 3,
+        RefType::Extends => 4,
     }
 }
 
@@ -497,12 +647,20 @@ impl IndexedWordRef {
 
 impl FileReferenceIndex {
     #[cfg(test)]
-    fn from_content(content: &str, extra_ident_chars: &'static [char]) -> Self {
+    fn from_content(
+        content: &str,
+        extra_ident_chars: &'static [char],
+        stopwords: Stopwords,
+    ) -> Self {
         let stripped = strip_comments_and_strings(content);
-        Self::from_stripped(&stripped, extra_ident_chars)
+        Self::from_stripped(&stripped, extra_ident_chars, stopwords)
     }
 
-    fn from_stripped(stripped: &str, extra_ident_chars: &'static [char]) -> Self {
+    fn from_stripped(
+        stripped: &str,
+        extra_ident_chars: &'static [char],
+        stopwords: Stopwords,
+    ) -> Self {
         let mut index = Self {
             tokens: Vec::new(),
             token_ids: HashMap::default(),
@@ -510,7 +668,14 @@ impl FileReferenceIndex {
         };
         let lines = stripped
             .lines()
-            .map(|line| LineReferenceIndex::from_stripped_line(line, &mut index, extra_ident_chars))
+            .map(|line| {
+                LineReferenceIndex::from_stripped_line(
+                    line,
+                    &mut index,
+                    extra_ident_chars,
+                    stopwords,
+                )
+            })
             .collect();
         index.lines = lines;
         index
@@ -603,6 +768,7 @@ impl LineReferenceIndex {
         line: &str,
         file_index: &mut FileReferenceIndex,
         extra_ident_chars: &'static [char],
+        stopwords: Stopwords,
     ) -> Option<Self> {
         let mut words = Vec::new();
         let mut seen_words: HashSet<u32> = HashSet::default();
@@ -615,7 +781,7 @@ impl LineReferenceIndex {
         };
 
         for (word, end_byte) in identifier_tokens(line, extra_ident_chars) {
-            if !is_reference_word(word) {
+            if !is_reference_word(word, stopwords) {
                 continue;
             }
             let token_id = file_index.intern(word);
@@ -673,11 +839,11 @@ fn identifier_tokens<'a>(
     })
 }
 
-fn is_reference_word(word: &str) -> bool {
+fn is_reference_word(word: &str, stopwords: Stopwords) -> bool {
     if word.is_empty() {
         return false;
     }
-    if is_keyword(word) || word.len() < 2 {
+    if stopwords.is_keyword(word) || word.len() < 2 {
         return false;
     }
     if word.starts_with(|c: char| c.is_lowercase()) && word.len() < 3 {
@@ -702,7 +868,7 @@ fn is_reference_word(word: &str) -> bool {
     if !word.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '-' || c == '*') {
         return false;
     }
-    if is_common_local_name(word) {
+    if stopwords.is_common_local_name(word) {
         return false;
     }
     true
@@ -802,6 +968,7 @@ struct ReferenceResolutionContext<'a> {
     class_entity_files: &'a HashSet<(&'a str, &'a str)>,
     enclosing_class: &'a HashMap<&'a str, &'a str>,
     class_members: &'a HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+    stopword_overrides: &'a StopwordOverrides,
 }
 
 fn resolve_references_with_file_indexes<'a>(
@@ -845,7 +1012,7 @@ fn resolve_references_with_file_indexes<'a>(
                 !entity_requires_content_span_filter(entity, context.child_ranges_by_parent)
             });
             let reference_index = if needs_index {
-                build_file_reference_index(root, file_path)
+                build_file_reference_index(root, file_path, context.stopword_overrides)
             } else {
                 None
             };
@@ -866,7 +1033,28 @@ fn resolve_references_with_file_indexes<'a>(
         .collect()
 }
 
-fn build_file_reference_index(root: &Path, file_path: &str) -> Option<FileReferenceIndex> {
+/// [`ParserRegistry::extract_entities_with_tree`], recording a diagnostic
+/// for files that yield no entities.
+fn extract_or_diagnose(
+    registry: &ParserRegistry,
+    file_path: &str,
+    content: &str,
+) -> Option<(Vec<SemanticEntity>, Option<tree_sitter::Tree>)> {
+    let extracted = registry.extract_entities_with_tree(file_path, content);
+    if extracted
+        .as_ref()
+        .is_none_or(|(entities, _)| entities.is_empty())
+    {
+        registry.record_no_entities(file_path, content);
+    }
+    extracted
+}
+
+fn build_file_reference_index(
+    root: &Path,
+    file_path: &str,
+    stopword_overrides: &StopwordOverrides,
+) -> Option<FileReferenceIndex> {
     let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
     let config = crate::parser::plugins::code::languages::get_language_config(ext)?;
     let content = std::fs::read_to_string(root.join(file_path)).ok()?;
@@ -874,6 +1062,7 @@ fn build_file_reference_index(root: &Path, file_path: &str) -> Option<FileRefere
     Some(FileReferenceIndex::from_stripped(
         &stripped,
         extra_ident_chars_for_file(file_path),
+        Stopwords::for_language(Some(config.id), stopword_overrides),
     ))
 }
 
@@ -958,8 +1147,11 @@ fn resolve_entity_references(
     } else {
         None
     };
-    let local_bindings =
-        local_binding_names_filtered(&entity.content, ext, |local_line, start, end| {
+    let local_bindings = local_binding_names_filtered(
+        &entity.content,
+        ext,
+        context.stopword_overrides,
+        |local_line, start, end| {
             entity_owns_content_span(
                 entity.id.as_str(),
                 entity.file_path.as_str(),
@@ -968,7 +1160,8 @@ fn resolve_entity_references(
                 Some(end),
                 context.child_ranges_by_parent,
             )
-        });
+        },
+    );
 
     let dot_chains: Vec<(&str, &str, Option<(usize, usize, usize)>)> = match reference_index {
         Some(index) => index
@@ -1055,6 +1248,7 @@ fn resolve_entity_references(
                 &entity.name,
                 stripped,
                 extra_ident_chars_for_file(&entity.file_path),
+                Stopwords::for_language(Some(language_config.id), context.stopword_overrides),
                 |local_line, local_start_byte, local_end_byte| {
                     entity_owns_content_span(
                         entity.id.as_str(),
@@ -1161,9 +1355,127 @@ fn resolve_entity_references(
         }
     }
 
+    if language_config.id == "php" {
+        entity_edges.extend(php_qualified_reference_edges(
+            entity,
+            &strip_for_language(language_config.strip_strategy(), &entity.content),
+            imports_for_file,
+            context,
+        ));
+    }
+
     entity_edges
 }
 
+/// Edges for PHP references the bag-of-words pass can't see whole:
+/// fully-qualified names (`\App\Support\format_name($x)`, `new \App\User`),
+/// which otherwise split into bare segments that only resolve within the same
+/// file, and static calls through an imported or qualified class
+/// (`Account::find()`), which resolve to the class's method.
+fn php_qualified_reference_edges(
+    entity: &SemanticEntity,
+    stripped: &str,
+    imports_for_file: Option<&HashMap<&str, &str>>,
+    context: &ReferenceResolutionContext<'_>,
+) -> Vec<(String, String, RefType)> {
+    let entity_id = entity.id.as_str();
+    let owns = |start: usize, end: usize| {
+        entity_owns_content_span(
+            entity_id,
+            entity.file_path.as_str(),
+            source_line_for_entity_content(entity, line_for_byte(stripped, start)),
+            Some(start),
+            Some(end),
+            context.child_ranges_by_parent,
+        )
+    };
+    let related = |target_id: &str| {
+        target_id == entity_id
+            || context.parent_child_pairs.contains(&(entity_id, target_id))
+            || context.parent_child_pairs.contains(&(target_id, entity_id))
+    };
+
+    let mut edges = Vec::new();
+    for m in PHP_QUALIFIED_NAME_RE.find_iter(stripped) {
+        if !owns(m.start(), m.end()) {
+            continue;
+        }
+        let is_call = stripped[m.end()..].trim_start().starts_with('(');
+        let is_new = stripped[..m.start()].trim_end().ends_with("new");
+        let Some(target_id) = resolve_php_name(
+            m.as_str(),
+            is_call && !is_new,
+            context.symbol_table,
+            context.entity_map,
+        ) else {
+            continue;
+        };
+        if !related(target_id) {
+            let ref_type = if is_call {
+                RefType::Calls
+            } else {
+                RefType::TypeRef
+            };
+            edges.push((entity.id.clone(), target_id.clone(), ref_type));
+        }
+    }
+
+    for cap in PHP_STATIC_CALL_RE.captures_iter(stripped) {
+        let whole = cap.get(0).unwrap();
+        if !owns(whole.start(), whole.end()) {
+            continue;
+        }
+        let receiver = cap.get(1).unwrap().as_str();
+        let class_id = if receiver.starts_with('\\') {
+            resolve_php_name(receiver, false, context.symbol_table, context.entity_map)
+                .map(String::as_str)
+        } else {
+            imports_for_file.and_then(|imports| imports.get(receiver).copied())
+        };
+        let Some(class_id) = class_id else {
+            continue;
+        };
+        let method = cap.get(2).unwrap().as_str();
+        let Some(method_id) = context.symbol_table.get(method).and_then(|ids| {
+            ids.iter().find(|id| {
+                context
+                    .entity_map
+                    .get(*id)
+                    .is_some_and(|e| e.parent_id.as_deref() == Some(class_id))
+            })
+        }) else {
+            continue;
+        };
+        if !related(method_id) {
+            edges.push((entity.id.clone(), method_id.clone(), RefType::Calls));
+        }
+    }
+    edges
+}
+
+/// Entities carrying a `sem:ignore` or `sem:ignore-impact` directive.
+fn ignored_entity_ids(entities: &[SemanticEntity]) -> HashSet<&str> {
+    entities
+        .iter()
+        .filter(|entity| IgnoreDirective::of(entity).is_some())
+        .map(|entity| entity.id.as_str())
+        .collect()
+}
+
+/// Drop edges into or out of entities whose directive keeps them out of the
+/// graph.
+fn drop_ignored_entity_edges(
+    edges: &mut Vec<(String, String, RefType)>,
+    entities: &[SemanticEntity],
+) {
+    let ignored = ignored_entity_ids(entities);
+    if ignored.is_empty() {
+        return;
+    }
+    edges
+        .retain(|(from, to, _)| !ignored.contains(from.as_str()) && !ignored.contains(to.as_str()));
+}
+
 impl EntityGraph {
     /// Reconstruct an EntityGraph from pre-loaded parts (e.g. from a cache).
     pub fn from_parts(entities: EntityInfoMap, mut edges: Vec<EntityRef>) -> Self {
@@ -1188,6 +1500,81 @@ impl EntityGraph {
         }
     }
 
+    /// Names defined more than [`AMBIGUOUS_NAME_TARGETS`] times, most common
+    /// first, with their definition counts. Bare cross-file references to
+    /// these names are left unlinked.
+    pub fn ambiguous_names(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::default();
+        for entity in self.entities.values() {
+            *counts.entry(entity.name.as_str()).or_default() += 1;
+        }
+        let mut ambiguous: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > AMBIGUOUS_NAME_TARGETS)
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        ambiguous.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ambiguous
+    }
+
+    /// Drop edges scored below `min_confidence` and rebuild the adjacency
+    /// indexes. A threshold of `0.0` or less keeps every edge.
+    pub fn retain_min_confidence(&mut self, min_confidence: f32) {
+        if min_confidence <= 0.0 {
+            return;
+        }
+        let before = self.edges.len();
+        self.edges.retain(|edge| edge.confidence >= min_confidence);
+        if self.edges.len() == before {
+            return;
+        }
+        let entities = std::mem::take(&mut self.entities);
+        let edges = std::mem::take(&mut self.edges);
+        *self = EntityGraph::from_parts(entities, edges);
+    }
+
+    /// Drop the test partition (entities tagged `is_test`) along with every
+    /// edge touching it, and rebuild the adjacency indexes.
+    pub fn exclude_tests(&mut self) {
+        let test_ids: HashSet<String> = self
+            .entities
+            .values()
+            .filter(|info| info.is_test)
+            .map(|info| info.id.clone())
+            .collect();
+        if test_ids.is_empty() {
+            return;
+        }
+        let mut entities = std::mem::take(&mut self.entities);
+        let mut edges = std::mem::take(&mut self.edges);
+        entities.retain(|id, _| !test_ids.contains(id));
+        edges.retain(|edge| {
+            !test_ids.contains(&edge.from_entity) && !test_ids.contains(&edge.to_entity)
+        });
+        *self = EntityGraph::from_parts(entities, edges);
+    }
+
+    /// Keep only the public surface: drop entities tagged `is_private` along
+    /// with every edge touching them, and rebuild the adjacency indexes.
+    pub fn public_only(&mut self) {
+        let private_ids: HashSet<String> = self
+            .entities
+            .values()
+            .filter(|info| info.is_private)
+            .map(|info| info.id.clone())
+            .collect();
+        if private_ids.is_empty() {
+            return;
+        }
+        let mut entities = std::mem::take(&mut self.entities);
+        let mut edges = std::mem::take(&mut self.edges);
+        entities.retain(|id, _| !private_ids.contains(id));
+        edges.retain(|edge| {
+            !private_ids.contains(&edge.from_entity) && !private_ids.contains(&edge.to_entity)
+        });
+        *self = EntityGraph::from_parts(entities, edges);
+    }
+
     /// Build an entity graph from a set of files.
     ///
     /// Pass 1: Extract all entities from all files using the parser registry.
@@ -1212,15 +1599,16 @@ impl EntityGraph {
         )> = maybe_par_iter!(file_paths)
             .filter_map(|file_path| {
                 GRAPH_PARSE_DONE.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                let full_path = root.join(file_path);
-                let content = std::fs::read_to_string(&full_path).ok()?;
+                let content = registry.read_source(root, file_path)?;
                 if retain_parsed_files {
-                    let (entities, tree) =
-                        registry.extract_entities_with_tree(file_path, &content)?;
+                    let (entities, tree) = extract_or_diagnose(registry, file_path, &content)?;
                     let parsed = tree.map(|tree| (file_path.clone(), content, tree));
                     Some((entities, parsed))
                 } else {
                     let entities = registry.extract_entities(file_path, &content);
+                    if entities.is_empty() {
+                        registry.record_no_entities(file_path, &content);
+                    }
                     Some((entities, None))
                 }
             })
@@ -1253,6 +1641,8 @@ impl EntityGraph {
         let mut scope_entity_ranges: HashMap<String, Vec<(usize, usize, String)>> =
             HashMap::default();
 
+        let test_ids = crate::parser::test_detect::test_partition_ids(&all_entities);
+        let private_ids = crate::parser::visibility::private_ids(&all_entities);
         for entity in &all_entities {
             symbol_table
                 .entry(entity.name.clone())
@@ -1269,6 +1659,8 @@ impl EntityGraph {
                     parent_id: entity.parent_id.clone(),
                     start_line: entity.start_line,
                     end_line: entity.end_line,
+                    is_test: test_ids.contains(&entity.id),
+                    is_private: private_ids.contains(&entity.id),
                 },
             );
 
@@ -1449,6 +1841,7 @@ impl EntityGraph {
             class_entity_files: &class_entity_files,
             enclosing_class: &enclosing_class,
             class_members: &class_members,
+            stopword_overrides: &registry.stopwords,
         };
         let resolved_refs = resolve_references_with_file_indexes(
             root,
@@ -1458,13 +1851,23 @@ impl EntityGraph {
             &reference_context,
         );
 
-        let export_edges = build_export_alias_edges(&all_entities, &import_table);
+        let mut export_edges = build_export_alias_edges(&all_entities, &import_table);
+        export_edges.extend(build_php_use_edges(&all_entities, &import_table));
+        export_edges.extend(build_markdown_link_edges(&all_entities));
 
-        // Merge scope edges with bag-of-words edges, deduplicating
-        let mut combined: Vec<(String, String, RefType)> = scope_edges;
+        // Merge scope edges with bag-of-words edges, deduplicating. Explicit
+        // type relation edges go first so they win over a name-matched TypeRef
+        // to the same target.
+        let mut combined: Vec<(String, String, RefType)> =
+            build_type_relation_edges(&all_entities, &imports_by_file);
+        combined.extend(scope_edges);
         combined.extend(export_edges);
         combined.extend(resolved_refs);
+        combined.extend(build_require_edges(&all_entities, &import_table));
         let mut all_resolved = dedupe_resolved_edges(combined);
+        if registry.ignore_directives {
+            drop_ignored_entity_edges(&mut all_resolved, &all_entities);
+        }
         sort_resolved_refs(&mut all_resolved);
 
         // Build edge indexes from resolved references
@@ -1485,8 +1888,10 @@ impl EntityGraph {
                 from_entity,
                 to_entity,
                 ref_type,
+                confidence: full_edge_confidence(),
             });
         }
+        assign_edge_confidence(&mut edges, &entity_map, &imports_by_file);
 
         let graph = EntityGraph {
             entities: entity_map.into_iter().collect(),
@@ -1518,14 +1923,17 @@ impl EntityGraph {
             Option<(String, String, tree_sitter::Tree)>,
         )> = maybe_par_iter!(file_paths)
             .filter_map(|file_path| {
-                let content = std::fs::read_to_string(root.join(file_path)).ok()?;
+                let content = registry.read_source(root, file_path)?;
                 if retain_parsed_files {
-                    let (entities, tree) =
-                        registry.extract_entities_with_tree(file_path, &content)?;
+                    let (entities, tree) = extract_or_diagnose(registry, file_path, &content)?;
                     let parsed = tree.map(|tree| (file_path.clone(), content, tree));
                     Some((entities, parsed))
                 } else {
-                    Some((registry.extract_entities(file_path, &content), None))
+                    let entities = registry.extract_entities(file_path, &content);
+                    if entities.is_empty() {
+                        registry.record_no_entities(file_path, &content);
+                    }
+                    Some((entities, None))
                 }
             })
             .collect();
@@ -1555,6 +1963,8 @@ impl EntityGraph {
         let mut scope_entity_ranges: HashMap<String, Vec<(usize, usize, String)>> =
             HashMap::default();
 
+        let test_ids = crate::parser::test_detect::test_partition_ids(&all_entities);
+        let private_ids = crate::parser::visibility::private_ids(&all_entities);
         for entity in &all_entities {
             symbol_table
                 .entry(entity.name.clone())
@@ -1571,6 +1981,8 @@ impl EntityGraph {
                     parent_id: entity.parent_id.clone(),
                     start_line: entity.start_line,
                     end_line: entity.end_line,
+                    is_test: test_ids.contains(&entity.id),
+                    is_private: private_ids.contains(&entity.id),
                 },
             );
 
@@ -1695,7 +2107,7 @@ impl EntityGraph {
         } else {
             maybe_par_iter!(&scope_file_paths)
                 .filter_map(|file_path| {
-                    let content = std::fs::read_to_string(root.join(file_path)).ok()?;
+                    let content = registry.read_source(root, file_path)?;
                     let (_entities, tree) =
                         registry.extract_entities_with_tree(file_path, &content)?;
                     tree.map(|tree| (file_path.clone(), content, tree))
@@ -1758,6 +2170,7 @@ impl EntityGraph {
             class_entity_files: &class_entity_files,
             enclosing_class: &enclosing_class,
             class_members: &class_members,
+            stopword_overrides: &registry.stopwords,
         };
         let resolved_refs = resolve_references_with_file_indexes(
             root,
@@ -1769,16 +2182,27 @@ impl EntityGraph {
 
         let export_edges = build_export_alias_edges(&all_entities, &import_table)
             .into_iter()
+            .chain(build_php_use_edges(&all_entities, &import_table))
+            .chain(build_markdown_link_edges(&all_entities))
             .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity))
             .collect::<Vec<_>>();
 
-        let mut combined: Vec<(String, String, RefType)> = scope_edges
+        let mut combined: Vec<(String, String, RefType)> = build_type_relation_edges(&all_entities, &imports_by_file)
             .into_iter()
+            .chain(scope_edges)
             .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity))
             .collect();
         combined.extend(export_edges);
         combined.extend(resolved_refs);
+        combined.extend(
+            build_require_edges(&all_entities, &import_table)
+                .into_iter()
+                .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity)),
+        );
         let mut all_resolved = dedupe_resolved_edges(combined);
+        if registry.ignore_directives {
+            drop_ignored_entity_edges(&mut all_resolved, &all_entities);
+        }
         sort_resolved_refs(&mut all_resolved);
 
         let mut edges: Vec<EntityRef> = Vec::with_capacity(all_resolved.len());
@@ -1798,8 +2222,10 @@ impl EntityGraph {
                 from_entity,
                 to_entity,
                 ref_type,
+                confidence: full_edge_confidence(),
             });
         }
+        assign_edge_confidence(&mut edges, &entity_map, &imports_by_file);
 
         (
             EntityGraph {
@@ -1878,9 +2304,8 @@ impl EntityGraph {
             Option<(String, String, tree_sitter::Tree)>,
         )> = maybe_par_iter!(stale_files)
             .filter_map(|file_path| {
-                let full_path = root.join(file_path);
-                let content = std::fs::read_to_string(&full_path).ok()?;
-                let (entities, tree) = registry.extract_entities_with_tree(file_path, &content)?;
+                let content = registry.read_source(root, file_path)?;
+                let (entities, tree) = extract_or_diagnose(registry, file_path, &content)?;
                 let parsed = tree.map(|t| (file_path.clone(), content, t));
                 Some((entities, parsed))
             })
@@ -1960,6 +2385,8 @@ impl EntityGraph {
         let mut entity_map: HashMap<String, EntityInfo> =
             HashMap::with_capacity_and_hasher(all_entities.len(), Default::default());
 
+        let test_ids = crate::parser::test_detect::test_partition_ids(&all_entities);
+        let private_ids = crate::parser::visibility::private_ids(&all_entities);
         for entity in &all_entities {
             symbol_table
                 .entry(entity.name.clone())
@@ -1975,6 +2402,8 @@ impl EntityGraph {
                     parent_id: entity.parent_id.clone(),
                     start_line: entity.start_line,
                     end_line: entity.end_line,
+                    is_test: test_ids.contains(&entity.id),
+                    is_private: private_ids.contains(&entity.id),
                 },
             );
         }
@@ -2204,6 +2633,7 @@ impl EntityGraph {
                     &entity.content,
                     &entity.name,
                     extra,
+                    stopwords_for_file(&entity.file_path, &registry.stopwords),
                     strip_strategy,
                 );
                 if mentions_new_stale_name
@@ -2306,7 +2736,7 @@ impl EntityGraph {
         // is not affected by target changes. Drop ALL cached edges from stale-file entities
         // (even content_clean ones) because import/scope context may have changed even when
         // entity content didn't. See: https://github.com/Ataraxy-Labs/sem/issues/116
-        let kept_edges: Vec<EntityRef> = cached_edges
+        let mut kept_edges: Vec<EntityRef> = cached_edges
             .into_iter()
             .filter(|e| {
                 if !current_entity_ids.contains(e.from_entity.as_str())
@@ -2516,6 +2946,7 @@ impl EntityGraph {
             class_entity_files: &class_entity_files,
             enclosing_class: &enclosing_class,
             class_members: &class_members,
+            stopword_overrides: &registry.stopwords,
         };
         let resolved_refs = resolve_references_with_file_indexes(
             root,
@@ -2525,15 +2956,43 @@ impl EntityGraph {
             &reference_context,
         );
 
-        let export_edges = build_export_alias_edges(&all_entities, &import_table);
+        let mut export_edges = build_export_alias_edges(&all_entities, &import_table);
+        export_edges.extend(
+            build_php_use_edges(&all_entities, &import_table)
+                .into_iter()
+                .chain(build_markdown_link_edges(&all_entities))
+                .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity.as_str())),
+        );
 
-        // Merge scope edges + bag-of-words edges + kept cached edges
-        let mut combined: Vec<(String, String, RefType)> = scope_edges;
+        // Merge type relation edges + scope edges + bag-of-words edges + kept
+        // cached edges
+        let mut combined: Vec<(String, String, RefType)> =
+            build_type_relation_edges(&all_entities, &imports_by_file)
+                .into_iter()
+                .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity.as_str()))
+                .collect();
+        combined.extend(scope_edges);
         combined.extend(export_edges);
         combined.extend(resolved_refs);
+        combined.extend(
+            build_require_edges(&all_entities, &import_table)
+                .into_iter()
+                .filter(|(from_entity, _, _)| needs_resolution.contains(from_entity.as_str())),
+        );
         let mut all_resolved = dedupe_resolved_edges(combined);
+        if registry.ignore_directives {
+            drop_ignored_entity_edges(&mut all_resolved, &all_entities);
+        }
         sort_resolved_refs(&mut all_resolved);
 
+        if registry.ignore_directives {
+            let ignored = ignored_entity_ids(&all_entities);
+            kept_edges.retain(|edge| {
+                !ignored.contains(edge.from_entity.as_str())
+                    && !ignored.contains(edge.to_entity.as_str())
+            });
+        }
+
         // Build final edge list: kept edges + newly resolved edges
         let mut edges: Vec<EntityRef> = Vec::with_capacity(kept_edges.len() + all_resolved.len());
 
@@ -2555,14 +3014,17 @@ impl EntityGraph {
         for edge in kept_edges {
             edges.push(edge);
         }
+        let kept_edge_count = edges.len();
 
         for (from_entity, to_entity, ref_type) in new_edges {
             edges.push(EntityRef {
                 from_entity,
                 to_entity,
                 ref_type,
+                confidence: full_edge_confidence(),
             });
         }
+        assign_edge_confidence(&mut edges[kept_edge_count..], &entity_map, &imports_by_file);
 
         let graph = EntityGraph::from_parts(entity_map.into_iter().collect(), edges);
 
@@ -2589,20 +3051,26 @@ impl EntityGraph {
         )
     }
 
-    /// Get entities that depend on the given entity (reverse deps).
+    /// Get entities that depend on the given entity (reverse deps), sorted by id.
     pub fn get_dependents(&self, entity_id: &str) -> Vec<&EntityInfo> {
-        self.dependents
+        let mut dependents: Vec<&EntityInfo> = self
+            .dependents
             .get(entity_id)
             .map(|ids| ids.iter().filter_map(|id| self.entities.get(id)).collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        dependents.sort_by(|a, b| a.id.cmp(&b.id));
+        dependents
     }
 
-    /// Get entities that the given entity depends on (forward deps).
+    /// Get entities that the given entity depends on (forward deps), sorted by id.
     pub fn get_dependencies(&self, entity_id: &str) -> Vec<&EntityInfo> {
-        self.dependencies
+        let mut dependencies: Vec<&EntityInfo> = self
+            .dependencies
             .get(entity_id)
             .map(|ids| ids.iter().filter_map(|id| self.entities.get(id)).collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        dependencies.sort_by(|a, b| a.id.cmp(&b.id));
+        dependencies
     }
 
     /// Impact analysis: if the given entity changes, what else might be affected?
@@ -2651,6 +3119,121 @@ impl EntityGraph {
         result
     }
 
+    /// Depth-limited forward traversal: everything `entity_id` depends on,
+    /// directly or transitively, with its BFS depth. `max_depth == 0` means
+    /// unlimited.
+    pub fn dependencies_bounded(
+        &self,
+        entity_id: &str,
+        max_depth: usize,
+    ) -> Vec<(&EntityInfo, usize)> {
+        let mut visited: HashSet<&str> = HashSet::default();
+        let mut queue: std::collections::VecDeque<(&str, usize)> =
+            std::collections::VecDeque::new();
+        let mut result = Vec::new();
+
+        let start_key = match self.entities.get_key_value(entity_id) {
+            Some((k, _)) => k.as_str(),
+            None => return result,
+        };
+
+        queue.push_back((start_key, 0));
+        visited.insert(start_key);
+
+        while let Some((current, depth)) = queue.pop_front() {
+            let next_depth = depth + 1;
+            if max_depth > 0 && next_depth > max_depth {
+                continue;
+            }
+            let Some(deps) = self.dependencies.get(current) else {
+                continue;
+            };
+            let mut deps: Vec<&str> = deps.iter().map(String::as_str).collect();
+            deps.sort_unstable();
+            for dep in deps {
+                if visited.insert(dep) {
+                    if let Some(info) = self.entities.get(dep) {
+                        result.push((info, next_depth));
+                    }
+                    queue.push_back((dep, next_depth));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Impact analysis that also explains each hit: every transitive dependent
+    /// comes with its BFS distance and one shortest reference path, listed as
+    /// entity ids from `entity_id` to the dependent.
+    pub fn impact_analysis_with_paths(
+        &self,
+        entity_id: &str,
+    ) -> Vec<(&EntityInfo, usize, Vec<String>)> {
+        self.impact_analysis_with_paths_bounded(entity_id, 0)
+    }
+
+    /// Depth-limited [`Self::impact_analysis_with_paths`]. `max_depth == 0`
+    /// means unlimited.
+    pub fn impact_analysis_with_paths_bounded(
+        &self,
+        entity_id: &str,
+        max_depth: usize,
+    ) -> Vec<(&EntityInfo, usize, Vec<String>)> {
+        let mut parent: HashMap<&str, &str> = HashMap::default();
+        let mut queue: std::collections::VecDeque<(&str, usize)> =
+            std::collections::VecDeque::new();
+        let mut order = Vec::new();
+
+        let start_key = match self.entities.get_key_value(entity_id) {
+            Some((k, _)) => k.as_str(),
+            None => return Vec::new(),
+        };
+
+        queue.push_back((start_key, 0));
+        parent.insert(start_key, start_key);
+
+        while let Some((current, depth)) = queue.pop_front() {
+            let next_depth = depth + 1;
+            if max_depth > 0 && next_depth > max_depth {
+                continue;
+            }
+            if let Some(deps) = self.dependents.get(current) {
+                for dep in deps {
+                    if parent.contains_key(dep.as_str()) {
+                        continue;
+                    }
+                    parent.insert(dep.as_str(), current);
+                    order.push((dep.as_str(), next_depth));
+                    queue.push_back((dep.as_str(), next_depth));
+                }
+            }
+        }
+
+        order
+            .into_iter()
+            .filter_map(|(id, depth)| {
+                let info = self.entities.get(id)?;
+                let mut path = vec![id.to_string()];
+                let mut current = id;
+                while current != start_key {
+                    current = parent[current];
+                    path.push(current.to_string());
+                }
+                path.reverse();
+                Some((info, depth, path))
+            })
+            .collect()
+    }
+
+    /// Weighted score for changing `entity_id`, over the same transitive
+    /// dependents as [`Self::impact_analysis_bounded`].
+    pub fn impact_score(&self, entity_id: &str, max_depth: usize) -> ImpactScore {
+        ImpactScore::from_impact(self.impact_analysis_bounded(entity_id, max_depth), |id| {
+            self.dependents.get(id).map_or(0, Vec::len)
+        })
+    }
+
     /// Impact analysis with a cap on maximum nodes visited.
     /// Returns transitive dependents up to the cap. Uses borrowed strings.
     pub fn impact_analysis_capped(&self, entity_id: &str, max_visited: usize) -> Vec<&EntityInfo> {
@@ -2785,6 +3368,9 @@ impl EntityGraph {
     /// - Deleted: remove all entities from that file, prune edges
     /// - Added/Modified: remove old entities, extract new ones, rebuild references
     /// - Renamed: update file paths in entity info
+    ///
+    /// Entities in unchanged files are re-resolved too when they pointed into
+    /// a changed file or mention a name the update newly defines.
     pub fn update_from_changes(
         &mut self,
         changed_files: &[FileChange],
@@ -2793,13 +3379,37 @@ impl EntityGraph {
     ) {
         let mut affected_files: HashSet<String> = HashSet::default();
         let mut new_entities: Vec<SemanticEntity> = Vec::new();
-
         for change in changed_files {
             affected_files.insert(change.file_path.clone());
             if let Some(ref old_path) = change.old_file_path {
                 affected_files.insert(old_path.clone());
             }
+        }
+
+        let replaced_names: HashSet<String> = self
+            .entities
+            .values()
+            .filter(|e| affected_files.contains(&e.file_path))
+            .map(|e| e.name.clone())
+            .collect();
+
+        // Entities in unchanged files that point into changed files lose those
+        // edges when the old entities are removed below, so note them first.
+        let mut entities_to_recheck: HashSet<String> = self
+            .entities
+            .values()
+            .filter(|e| affected_files.contains(&e.file_path))
+            .filter_map(|e| self.dependents.get(&e.id))
+            .flatten()
+            .filter(|id| {
+                self.entities
+                    .get(id.as_str())
+                    .is_some_and(|e| !affected_files.contains(&e.file_path))
+            })
+            .cloned()
+            .collect();
 
+        for change in changed_files {
             match change.status {
                 FileStatus::Deleted => {
                     self.remove_entities_for_file(&change.file_path);
@@ -2836,6 +3446,8 @@ impl EntityGraph {
         }
 
         // Add new entities to the entity map
+        let test_ids = crate::parser::test_detect::test_partition_ids(&new_entities);
+        let private_ids = crate::parser::visibility::private_ids(&new_entities);
         for entity in &new_entities {
             self.entities.insert(
                 entity.id.clone(),
@@ -2847,6 +3459,8 @@ impl EntityGraph {
                     parent_id: entity.parent_id.clone(),
                     start_line: entity.start_line,
                     end_line: entity.end_line,
+                    is_test: test_ids.contains(&entity.id),
+                    is_private: private_ids.contains(&entity.id),
                 },
             );
         }
@@ -2854,40 +3468,146 @@ impl EntityGraph {
         // Rebuild the global symbol table from all current entities
         let symbol_table = self.build_symbol_table();
         let child_ranges_by_parent = build_child_ranges_by_parent(&new_entities);
+        let changed_contents: HashMap<&str, &str> = changed_files
+            .iter()
+            .filter_map(|change| {
+                Some((change.file_path.as_str(), change.after_content.as_deref()?))
+            })
+            .collect();
+        let mut new_entity_files: Vec<String> =
+            new_entities.iter().map(|e| e.file_path.clone()).collect();
+        new_entity_files.sort_unstable();
+        new_entity_files.dedup();
+        let import_table =
+            self.import_table_for_files(root, &new_entity_files, &symbol_table, &changed_contents);
+        let imports_by_file = build_imports_by_file(&import_table);
 
         // Re-resolve references for new entities
         for entity in &new_entities {
-            self.resolve_entity_references(entity, &symbol_table, &child_ranges_by_parent);
+            self.resolve_entity_references(
+                entity,
+                &symbol_table,
+                &imports_by_file,
+                &child_ranges_by_parent,
+                &registry.stopwords,
+            );
         }
 
         // Also re-resolve references for entities in OTHER files that might
-        // reference entities in changed files (their targets may have changed)
-        let changed_entity_names: HashSet<String> =
-            new_entities.iter().map(|e| e.name.clone()).collect();
+        // reference entities in changed files: a same-named target may now be
+        // a better match than the one they resolved to.
+        let changed_entity_names: HashSet<&str> =
+            new_entities.iter().map(|e| e.name.as_str()).collect();
+        entities_to_recheck.extend(
+            self.entities
+                .values()
+                .filter(|e| !affected_files.contains(&e.file_path))
+                .filter(|e| {
+                    self.dependencies.get(&e.id).is_some_and(|deps| {
+                        deps.iter().any(|dep_id| {
+                            self.entities
+                                .get(dep_id)
+                                .is_some_and(|dep| changed_entity_names.contains(dep.name.as_str()))
+                        })
+                    })
+                })
+                .map(|e| e.id.clone()),
+        );
+        entities_to_recheck.retain(|id| self.entities.contains_key(id));
 
-        // Find entities in unchanged files that reference any changed entity name
-        let entities_to_recheck: Vec<String> = self
-            .entities
-            .values()
-            .filter(|e| !affected_files.contains(&e.file_path))
-            .filter(|e| {
-                self.dependencies.get(&e.id).map_or(false, |deps| {
-                    deps.iter().any(|dep_id| {
+        // Names nothing defined before the update never resolved, so any
+        // unchanged entity mentioning one may now have a new edge.
+        let new_names: HashSet<&str> = new_entities
+            .iter()
+            .map(|e| e.name.as_str())
+            .filter(|name| !replaced_names.contains(*name))
+            .filter(|name| {
+                symbol_table.get(*name).is_some_and(|ids| {
+                    ids.iter().all(|id| {
                         self.entities
-                            .get(dep_id)
-                            .map_or(false, |dep| changed_entity_names.contains(&dep.name))
+                            .get(id)
+                            .is_some_and(|e| affected_files.contains(&e.file_path))
                     })
                 })
             })
-            .map(|e| e.id.clone())
             .collect();
+        let mentioning_files: HashSet<String> = if new_names.is_empty() {
+            HashSet::default()
+        } else {
+            let mut unchanged_files: Vec<&str> = self
+                .entities
+                .values()
+                .map(|e| e.file_path.as_str())
+                .filter(|file_path| !affected_files.contains(*file_path))
+                .collect();
+            unchanged_files.sort_unstable();
+            unchanged_files.dedup();
+            maybe_par_iter!(unchanged_files)
+                .filter(|file_path| {
+                    registry
+                        .read_source(root, file_path)
+                        .is_some_and(|content| {
+                            text_mentions_any_name(
+                                &content,
+                                &new_names,
+                                extra_ident_chars_for_file(file_path),
+                            )
+                        })
+                })
+                .map(|file_path| file_path.to_string())
+                .collect()
+        };
+        if entities_to_recheck.is_empty() && mentioning_files.is_empty() {
+            return;
+        }
 
-        // We don't have the full SemanticEntity for unchanged files, so we skip
-        // deep re-resolution here. The forward/reverse indexes are already updated
-        // by remove_entities_for_file and resolve_entity_references.
-        // For entities that had dangling references (their target was deleted),
-        // the edges were already pruned.
-        let _ = entities_to_recheck; // acknowledge but don't act on for now
+        // Unchanged files are unchanged on disk, so re-extracting them gives
+        // back the full entities needed to resolve their references again.
+        let mut recheck_files: Vec<&str> = entities_to_recheck
+            .iter()
+            .filter_map(|id| self.entities.get(id))
+            .map(|e| e.file_path.as_str())
+            .chain(mentioning_files.iter().map(String::as_str))
+            .collect();
+        recheck_files.sort_unstable();
+        recheck_files.dedup();
+        let recheck_files: Vec<String> = recheck_files.into_iter().map(String::from).collect();
+        let mut recheck_entities: Vec<SemanticEntity> = Vec::new();
+        for file_path in &recheck_files {
+            if let Some(file_entities) = self.extract_file_entities(file_path, None, root, registry)
+            {
+                recheck_entities.extend(file_entities);
+            }
+        }
+        let import_table =
+            self.import_table_for_files(root, &recheck_files, &symbol_table, &changed_contents);
+        let imports_by_file = build_imports_by_file(&import_table);
+        let child_ranges_by_parent = build_child_ranges_by_parent(&recheck_entities);
+        let rechecked_ids: HashSet<&str> = recheck_entities
+            .iter()
+            .filter(|e| {
+                entities_to_recheck.contains(&e.id)
+                    || (mentioning_files.contains(&e.file_path)
+                        && text_mentions_any_name(
+                            &strip_comments_and_strings(&e.content),
+                            &new_names,
+                            extra_ident_chars_for_file(&e.file_path),
+                        ))
+            })
+            .map(|e| e.id.as_str())
+            .collect();
+        self.remove_outgoing_edges(&rechecked_ids);
+        for entity in &recheck_entities {
+            if rechecked_ids.contains(entity.id.as_str()) {
+                self.resolve_entity_references(
+                    entity,
+                    &symbol_table,
+                    &imports_by_file,
+                    &child_ranges_by_parent,
+                    &registry.stopwords,
+                );
+            }
+        }
     }
 
     /// Extract entities from a file, using provided content or reading from disk.
@@ -2901,17 +3621,42 @@ impl EntityGraph {
         let content = if let Some(c) = content {
             c.to_string()
         } else {
-            let full_path = root.join(file_path);
-            std::fs::read_to_string(&full_path).ok()?
+            registry.read_source(root, file_path)?
         };
 
         Some(registry.extract_entities(file_path, &content))
     }
 
-    /// Remove all entities belonging to a specific file and prune their edges.
-    fn remove_entities_for_file(&mut self, file_path: &str) {
-        // Collect entity IDs to remove
-        let ids_to_remove: Vec<String> = self
+    /// Resolve the imports of `file_paths` against every file in the graph,
+    /// reading `contents` in place of the files they cover.
+    fn import_table_for_files(
+        &self,
+        root: &Path,
+        file_paths: &[String],
+        symbol_table: &HashMap<String, Vec<String>>,
+        contents: &HashMap<&str, &str>,
+    ) -> HashMap<(String, String), String> {
+        let mut all_file_paths: Vec<String> = self
+            .entities
+            .values()
+            .map(|e| e.file_path.clone())
+            .collect();
+        all_file_paths.sort_unstable();
+        all_file_paths.dedup();
+        build_import_table_from_contents(
+            root,
+            file_paths,
+            &all_file_paths,
+            symbol_table,
+            &self.entities,
+            contents,
+        )
+    }
+
+    /// Remove all entities belonging to a specific file and prune their edges.
+    fn remove_entities_for_file(&mut self, file_path: &str) {
+        // Collect entity IDs to remove
+        let ids_to_remove: Vec<String> = self
             .entities
             .values()
             .filter(|e| e.file_path == file_path)
@@ -2953,6 +3698,21 @@ impl EntityGraph {
         }
     }
 
+    /// Drop every edge out of `ids`, keeping the dependency indexes in sync.
+    fn remove_outgoing_edges(&mut self, ids: &HashSet<&str>) {
+        if ids.is_empty() {
+            return;
+        }
+        self.edges.retain(|e| !ids.contains(e.from_entity.as_str()));
+        for id in ids {
+            for dep in self.dependencies.remove(*id).unwrap_or_default() {
+                if let Some(dependents) = self.dependents.get_mut(&dep) {
+                    dependents.retain(|d| !ids.contains(d.as_str()));
+                }
+            }
+        }
+    }
+
     /// Build a symbol table from all current entities.
     fn build_symbol_table(&self) -> HashMap<String, Vec<String>> {
         let mut symbol_table: HashMap<String, Vec<String>> = HashMap::default();
@@ -2978,25 +3738,46 @@ impl EntityGraph {
         &mut self,
         entity: &SemanticEntity,
         symbol_table: &HashMap<String, Vec<String>>,
+        imports_by_file: &ImportsByFile<'_>,
         child_ranges_by_parent: &HashMap<&str, Vec<ChildRange<'_>>>,
+        stopword_overrides: &StopwordOverrides,
     ) {
+        let imports_for_file = imports_by_file.get(entity.file_path.as_str());
         let stripped = strip_comments_and_strings(&entity.content);
-        let refs = extract_references_with_stripped_filtered(
+        let owns_span = |local_line, local_start_byte, local_end_byte| {
+            entity_owns_content_span(
+                entity.id.as_str(),
+                entity.file_path.as_str(),
+                source_line_for_entity_content(entity, local_line),
+                Some(local_start_byte),
+                Some(local_end_byte),
+                child_ranges_by_parent,
+            )
+        };
+        let mut refs = extract_references_with_stripped_filtered(
             &entity.content,
             &entity.name,
             &stripped,
             extra_ident_chars_for_file(&entity.file_path),
-            |local_line, local_start_byte, local_end_byte| {
-                entity_owns_content_span(
-                    entity.id.as_str(),
-                    entity.file_path.as_str(),
-                    source_line_for_entity_content(entity, local_line),
-                    Some(local_start_byte),
-                    Some(local_end_byte),
-                    child_ranges_by_parent,
-                )
-            },
+            stopwords_for_file(&entity.file_path, stopword_overrides),
+            owns_span,
         );
+        // A full build's scope pass links import bindings even when they
+        // look like common local names (`source`, `data`).
+        if let Some(imports) = imports_for_file {
+            for imported in extract_imported_references(
+                &entity.content,
+                &entity.name,
+                &stripped,
+                extra_ident_chars_for_file(&entity.file_path),
+                imports,
+                owns_span,
+            ) {
+                if !refs.contains(&imported) {
+                    refs.push(imported);
+                }
+            }
+        }
 
         for ref_name in refs {
             if let Some(target_ids) = symbol_table.get(ref_name) {
@@ -3009,14 +3790,35 @@ impl EntityGraph {
                                 .get(*id)
                                 .map_or(false, |e| e.file_path == entity.file_path)
                     })
-                    .or_else(|| target_ids.iter().find(|id| *id != &entity.id));
+                    .or_else(|| {
+                        let import_target = imports_for_file?.get(ref_name)?;
+                        target_ids
+                            .iter()
+                            .find(|id| *id != &entity.id && id.as_str() == *import_target)
+                    })
+                    .or_else(|| {
+                        if target_ids.len() > AMBIGUOUS_NAME_TARGETS {
+                            return None;
+                        }
+                        target_ids.iter().find(|id| *id != &entity.id)
+                    });
 
                 if let Some(target_id) = target {
                     let ref_type = infer_ref_type(&entity.content, &ref_name);
+                    let same_file = self
+                        .entities
+                        .get(target_id)
+                        .is_some_and(|e| e.file_path == entity.file_path);
+                    let imported = imports_for_file
+                        .and_then(|imports| imports.get(ref_name))
+                        .is_some_and(|import_target| *import_target == target_id.as_str());
+                    let confidence =
+                        edge_confidence(&ref_type, same_file, imported, target_ids.len());
                     self.edges.push(EntityRef {
                         from_entity: entity.id.clone(),
                         to_entity: target_id.clone(),
                         ref_type,
+                        confidence,
                     });
                     self.dependents
                         .entry(target_id.clone())
@@ -3111,6 +3913,363 @@ fn build_export_alias_edges(
         .collect()
 }
 
+/// `Imports` edges from a PHP file's namespace declaration to each entity
+/// its `use` statements bring in.
+fn build_php_use_edges(
+    all_entities: &[SemanticEntity],
+    import_table: &HashMap<(String, String), String>,
+) -> Vec<(String, String, RefType)> {
+    let mut namespace_by_file: HashMap<&str, &str> = HashMap::default();
+    for entity in all_entities {
+        if entity.entity_type == "module" && is_php_file(&entity.file_path) {
+            namespace_by_file
+                .entry(entity.file_path.as_str())
+                .or_insert(entity.id.as_str());
+        }
+    }
+    if namespace_by_file.is_empty() {
+        return Vec::new();
+    }
+
+    import_table
+        .iter()
+        .filter_map(|((file_path, _), target_id)| {
+            let namespace_id = namespace_by_file.get(file_path.as_str())?;
+            (*namespace_id != target_id.as_str()).then(|| {
+                (
+                    namespace_id.to_string(),
+                    target_id.clone(),
+                    RefType::Imports,
+                )
+            })
+        })
+        .collect()
+}
+
+/// `Imports` edges from each entity of a Ruby or PHP file to the required (or
+/// `use`d) entities it mentions. A method that uses a required class links
+/// to it, and so does its enclosing class, which depends on it as a whole.
+/// Scope resolution consumes receivers like `User` in `User.new.save`
+/// without linking the class itself, so this covers method bodies too.
+/// Merged after the resolved references so a `Calls` or `TypeRef` edge to the
+/// same target wins.
+fn build_require_edges(
+    all_entities: &[SemanticEntity],
+    import_table: &HashMap<(String, String), String>,
+) -> Vec<(String, String, RefType)> {
+    let mut imports_by_file: HashMap<&str, Vec<(&str, &str)>> = HashMap::default();
+    for ((file_path, name), target_id) in import_table {
+        if is_ruby_file(file_path) || is_php_file(file_path) {
+            imports_by_file
+                .entry(file_path.as_str())
+                .or_default()
+                .push((name.as_str(), target_id.as_str()));
+        }
+    }
+    if imports_by_file.is_empty() {
+        return Vec::new();
+    }
+
+    let mut edges = Vec::new();
+    for entity in all_entities {
+        let Some(imports) = imports_by_file.get(entity.file_path.as_str()) else {
+            continue;
+        };
+        let mentioned = plain_identifiers(&entity.content);
+        for (name, target_id) in imports {
+            if *target_id != entity.id && mentioned.contains(name) {
+                edges.push((entity.id.clone(), target_id.to_string(), RefType::Imports));
+            }
+        }
+    }
+    edges
+}
+
+/// A local link in a Markdown section and the section it lands on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownLink {
+    /// Section the link appears in.
+    pub from_entity: String,
+    /// Target as written, e.g. `./other.md#setup` or `#usage`.
+    pub target: String,
+    /// The heading the anchor names, or the document's first heading for a
+    /// link without one. `None` when the file or anchor doesn't exist.
+    pub to_entity: Option<String>,
+}
+
+/// Resolve every `.md`/`.mdx` link recorded by the Markdown plugin. Paths
+/// are relative to the linking file (or to the repo root with a leading
+/// `/`); anchors match GitHub-style heading slugs.
+pub fn resolve_markdown_links(all_entities: &[SemanticEntity]) -> Vec<MarkdownLink> {
+    let mut sections_by_file: HashMap<&str, Vec<&SemanticEntity>> = HashMap::default();
+    for entity in all_entities {
+        if is_markdown_file(&entity.file_path) {
+            sections_by_file
+                .entry(entity.file_path.as_str())
+                .or_default()
+                .push(entity);
+        }
+    }
+    for sections in sections_by_file.values_mut() {
+        sections.sort_by_key(|section| section.start_line);
+    }
+
+    let mut links = Vec::new();
+    for entity in all_entities {
+        if !is_markdown_file(&entity.file_path) {
+            continue;
+        }
+        let Some(targets) = entity
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get(LINKS_METADATA_KEY))
+        else {
+            continue;
+        };
+        for target in targets.lines() {
+            let (path, anchor) = match target.split_once('#') {
+                Some((path, anchor)) => (path, Some(anchor)),
+                None => (target, None),
+            };
+            let path = path.split_once('?').map_or(path, |(path, _)| path);
+            if !path.is_empty() && !is_markdown_file(path) {
+                continue;
+            }
+            let file = if path.is_empty() {
+                Some(entity.file_path.clone())
+            } else if let Some(from_root) = path.strip_prefix('/') {
+                normalize_repo_path(PathBuf::from(from_root))
+            } else {
+                let dir = Path::new(&entity.file_path)
+                    .parent()
+                    .unwrap_or(Path::new(""));
+                normalize_repo_path(dir.join(path))
+            };
+            let sections = file.as_deref().and_then(|file| sections_by_file.get(file));
+            let to_entity = sections.and_then(|sections| {
+                let mut headings = sections
+                    .iter()
+                    .filter(|section| section.entity_type == "heading");
+                match anchor.filter(|anchor| !anchor.is_empty()) {
+                    Some(anchor) => {
+                        let anchor = anchor.to_lowercase();
+                        headings.find(|heading| heading_slug(&heading.name) == anchor)
+                    }
+                    None => headings.next().or(sections.first()),
+                }
+                .map(|section| section.id.clone())
+            });
+            links.push(MarkdownLink {
+                from_entity: entity.id.clone(),
+                target: target.to_string(),
+                to_entity,
+            });
+        }
+    }
+    links
+}
+
+/// `Imports` edges for Markdown links that resolve to another section.
+fn build_markdown_link_edges(all_entities: &[SemanticEntity]) -> Vec<(String, String, RefType)> {
+    resolve_markdown_links(all_entities)
+        .into_iter()
+        .filter_map(|link| {
+            let to_entity = link.to_entity?;
+            (to_entity != link.from_entity).then_some((
+                link.from_entity,
+                to_entity,
+                RefType::Imports,
+            ))
+        })
+        .collect()
+}
+
+fn is_markdown_file(file_path: &str) -> bool {
+    let lower = file_path.to_ascii_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".mdx")
+}
+
+/// GitHub's anchor for a heading: lowercase, punctuation dropped, spaces
+/// turned into hyphens.
+fn heading_slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Explicit type relationships: Rust trait impls, class/interface heritage
+/// clauses and Go method receivers.
+fn build_type_relation_edges(
+    all_entities: &[SemanticEntity],
+    imports_by_file: &ImportsByFile<'_>,
+) -> Vec<(String, String, RefType)> {
+    let mut edges = build_trait_impl_edges(all_entities);
+    edges.extend(build_heritage_edges(all_entities, imports_by_file));
+    edges.extend(build_go_receiver_edges(all_entities));
+    edges
+}
+
+/// Link each Go method to its receiver type. Go methods sit outside the
+/// type's body, so without this edge a change to a struct wouldn't reach the
+/// methods declared on it.
+fn build_go_receiver_edges(all_entities: &[SemanticEntity]) -> Vec<(String, String, RefType)> {
+    let type_ids: HashSet<&str> = all_entities
+        .iter()
+        .filter(|entity| entity.entity_type == "type")
+        .map(|entity| entity.id.as_str())
+        .collect();
+    all_entities
+        .iter()
+        .filter(|entity| {
+            entity
+                .metadata
+                .as_ref()
+                .is_some_and(|metadata| metadata.contains_key("go.receiver"))
+        })
+        .filter_map(|entity| {
+            let parent_id = entity.parent_id.as_deref()?;
+            type_ids
+                .contains(parent_id)
+                .then(|| (entity.id.clone(), parent_id.to_string(), RefType::TypeRef))
+        })
+        .collect()
+}
+
+/// Link classes and interfaces to the base types recorded in their
+/// `extends`/`implements` metadata. A base name resolves through the file's
+/// imports first, then to a class or interface in the same file, then to
+/// every same-named class or interface in the repo.
+fn build_heritage_edges(
+    all_entities: &[SemanticEntity],
+    imports_by_file: &ImportsByFile<'_>,
+) -> Vec<(String, String, RefType)> {
+    let mut types_by_name: HashMap<&str, Vec<&SemanticEntity>> = HashMap::default();
+    for entity in all_entities {
+        if matches!(entity.entity_type.as_str(), "class" | "interface") {
+            types_by_name
+                .entry(entity.name.as_str())
+                .or_default()
+                .push(entity);
+        }
+    }
+
+    let mut edges = Vec::new();
+    for entity in all_entities {
+        let Some(metadata) = entity.metadata.as_ref() else {
+            continue;
+        };
+        for (key, ref_type) in [
+            ("extends", RefType::Extends),
+            ("implements", RefType::Implements),
+        ] {
+            let Some(names) = metadata.get(key) else {
+                continue;
+            };
+            for name in names.split(',') {
+                let imported = imports_by_file
+                    .get(entity.file_path.as_str())
+                    .and_then(|imports| imports.get(name));
+                let targets: Vec<&str> = if let Some(target) = imported {
+                    vec![*target]
+                } else {
+                    let candidates = types_by_name.get(name).map_or(&[][..], Vec::as_slice);
+                    let same_file: Vec<&str> = candidates
+                        .iter()
+                        .filter(|t| t.file_path == entity.file_path)
+                        .map(|t| t.id.as_str())
+                        .collect();
+                    if same_file.is_empty() {
+                        candidates.iter().map(|t| t.id.as_str()).collect()
+                    } else {
+                        same_file
+                    }
+                };
+                for target in targets {
+                    if target != entity.id {
+                        edges.push((entity.id.clone(), target.to_string(), ref_type.clone()));
+                    }
+                }
+            }
+        }
+    }
+    edges
+}
+
+/// Link each `impl Trait for Type` entity to the trait it implements, and
+/// each method in it to the trait's method of the same name. A trait defined
+/// in the impl's own file wins over same-named traits elsewhere.
+fn build_trait_impl_edges(all_entities: &[SemanticEntity]) -> Vec<(String, String, RefType)> {
+    let mut traits_by_name: HashMap<&str, Vec<&SemanticEntity>> = HashMap::default();
+    let mut children: HashMap<&str, Vec<&SemanticEntity>> = HashMap::default();
+    for entity in all_entities {
+        if entity.entity_type == "trait" {
+            traits_by_name
+                .entry(entity.name.as_str())
+                .or_default()
+                .push(entity);
+        }
+        if let Some(parent_id) = entity.parent_id.as_deref() {
+            children.entry(parent_id).or_default().push(entity);
+        }
+    }
+
+    let mut edges = Vec::new();
+    for imp in all_entities.iter().filter(|e| e.entity_type == "impl") {
+        let Some(trait_name) = implemented_trait_name(&imp.name) else {
+            continue;
+        };
+        let Some(candidates) = traits_by_name.get(trait_name) else {
+            continue;
+        };
+        let same_file: Vec<&SemanticEntity> = candidates
+            .iter()
+            .copied()
+            .filter(|t| t.file_path == imp.file_path)
+            .collect();
+        let targets = if same_file.is_empty() {
+            candidates
+        } else {
+            &same_file
+        };
+
+        for target in targets {
+            edges.push((imp.id.clone(), target.id.clone(), RefType::Implements));
+            let trait_methods = children.get(target.id.as_str());
+            for method in children.get(imp.id.as_str()).into_iter().flatten() {
+                if let Some(trait_method) = trait_methods
+                    .into_iter()
+                    .flatten()
+                    .find(|m| m.name == method.name)
+                {
+                    edges.push((
+                        method.id.clone(),
+                        trait_method.id.clone(),
+                        RefType::Implements,
+                    ));
+                }
+            }
+        }
+    }
+    edges
+}
+
+/// The bare trait name from a Rust impl entity name: `"fmt::Display for Foo"`
+/// gives `"Display"`, `"From<u8> for Foo"` gives `"From"`. Inherent impls
+/// (`"Foo"`) have none.
+fn implemented_trait_name(impl_name: &str) -> Option<&str> {
+    let (trait_path, _) = impl_name.split_once(" for ")?;
+    let trait_path = trait_path.split('<').next().unwrap_or(trait_path);
+    let name = trait_path.rsplit("::").next().unwrap_or(trait_path).trim();
+    (!name.is_empty()).then_some(name)
+}
+
 struct TsDefaultExportTable {
     exports_by_file: HashMap<String, String>,
     sorted_files: Vec<String>,
@@ -3690,6 +4849,55 @@ fn scan_import_file(
         scan.local_imports.extend(local_import_table);
     }
 
+    if is_php_file(file_path) {
+        for php_use in php_use_imports(content) {
+            if let Some(target_id) =
+                resolve_php_name(&php_use.path, php_use.function, symbol_table, entity_map)
+            {
+                scan.local_imports
+                    .push(((file_path.to_string(), php_use.local), target_id.clone()));
+            }
+        }
+    }
+
+    if is_jvm_file(file_path) {
+        for jvm_import in jvm_imports(content) {
+            if let Some(target_id) = resolve_jvm_import(&jvm_import.path, symbol_table, entity_map)
+            {
+                scan.local_imports
+                    .push(((file_path.to_string(), jvm_import.local), target_id.clone()));
+            }
+        }
+    }
+
+    if is_c_family_file(file_path) {
+        let includes = c_includes(file_path, content);
+        if !includes.is_empty() {
+            for name in plain_identifiers(content) {
+                if let Some(target_id) =
+                    resolve_c_include_name(name, &includes, symbol_table, entity_map)
+                {
+                    scan.local_imports
+                        .push(((file_path.to_string(), name.to_string()), target_id.clone()));
+                }
+            }
+        }
+    }
+
+    if is_ruby_file(file_path) || is_php_file(file_path) {
+        let required = required_files(file_path, content);
+        if !required.is_empty() {
+            for name in plain_identifiers(content) {
+                if let Some(target_id) =
+                    resolve_required_name(name, &required, symbol_table, entity_map)
+                {
+                    scan.local_imports
+                        .push(((file_path.to_string(), name.to_string()), target_id.clone()));
+                }
+            }
+        }
+    }
+
     let file_ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
     if let Some(file_config) =
         crate::parser::plugins::code::languages::get_language_config(file_ext)
@@ -3768,6 +4976,26 @@ fn build_import_table_with_default_export_paths(
                 .map(|(fp, content, _)| (fp.as_str(), content.as_str())),
         );
     }
+    build_import_table_from_contents(
+        root,
+        file_paths,
+        default_export_file_paths,
+        symbol_table,
+        entity_map,
+        &pre_parsed_content_map,
+    )
+}
+
+/// Like [`build_import_table_with_default_export_paths`], reading each file
+/// from `pre_parsed_content_map` when present and from disk otherwise.
+fn build_import_table_from_contents(
+    root: &Path,
+    file_paths: &[String],
+    default_export_file_paths: &[String],
+    symbol_table: &HashMap<String, Vec<String>>,
+    entity_map: &HashMap<String, EntityInfo>,
+    pre_parsed_content_map: &HashMap<&str, &str>,
+) -> HashMap<(String, String), String> {
     let import_source_set: HashSet<&str> = file_paths.iter().map(String::as_str).collect();
     let clojure_ns_index = build_clojure_ns_index(entity_map);
     let mut content_file_set: HashSet<String> = file_paths.iter().cloned().collect();
@@ -3784,7 +5012,7 @@ fn build_import_table_with_default_export_paths(
                 continue;
             }
 
-            let Some(content) = import_source_content(root, &pre_parsed_content_map, &file_path)
+            let Some(content) = import_source_content(root, pre_parsed_content_map, &file_path)
             else {
                 continue;
             };
@@ -3820,7 +5048,7 @@ fn build_import_table_with_default_export_paths(
                     return None;
                 }
 
-                let content = import_source_content(root, &pre_parsed_content_map, file_path)?;
+                let content = import_source_content(root, pre_parsed_content_map, file_path)?;
                 Some(scan_import_file(
                     file_path,
                     content.as_ref(),
@@ -3842,7 +5070,7 @@ fn build_import_table_with_default_export_paths(
                 continue;
             }
 
-            let Some(content) = import_source_content(root, &pre_parsed_content_map, file_path)
+            let Some(content) = import_source_content(root, pre_parsed_content_map, file_path)
             else {
                 continue;
             };
@@ -3956,32 +5184,409 @@ fn resolve_rust_import(
     }
 }
 
-/// Pre-built index for Clojure namespace resolution.
-/// Maps file-path-without-extension → Vec<(entity_name, entity_id)>.
-/// Built once before the import-table loop to avoid O(total-entities) scans per :as alias.
-type ClojureNsIndex = HashMap<String, Vec<(String, String)>>;
+fn is_php_file(file_path: &str) -> bool {
+    let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
+    crate::parser::plugins::code::languages::get_language_config(ext)
+        .is_some_and(|config| config.id == "php")
+}
+
+/// One name brought into scope by a PHP `use` statement.
+struct PhpUse {
+    /// Fully-qualified name without the leading `\`, e.g. `App\Models\User`.
+    path: String,
+    /// Name the file refers to it by: the alias, or the last path segment.
+    local: String,
+    /// `use function ...` rather than a class, interface, trait or enum.
+    function: bool,
+}
+
+/// Parse top-level `use` statements: `use A\B;`, `use A\B as C;`,
+/// `use function A\f;`, comma lists and `use A\{B, C as D};` groups.
+/// Indented `use` lines are trait uses inside a class body and are skipped.
+fn php_use_imports(content: &str) -> Vec<PhpUse> {
+    static PHP_USE_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?m)^use\s+(?:(function|const)\s+)?([^;]+);").unwrap());
+    static PHP_AS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\s+as\s+").unwrap());
+
+    let mut uses = Vec::new();
+    for cap in PHP_USE_RE.captures_iter(content) {
+        let statement_kind = cap.get(1).map(|m| m.as_str());
+        let body = cap.get(2).unwrap().as_str();
+        let (prefix, items) = match body.split_once('{') {
+            Some((prefix, group)) => (
+                prefix.trim().trim_end_matches('\\'),
+                group.trim_end().trim_end_matches('}'),
+            ),
+            None => ("", body),
+        };
 
-fn build_clojure_ns_index(entity_map: &HashMap<String, EntityInfo>) -> ClojureNsIndex {
-    let mut index: ClojureNsIndex = HashMap::default();
-    for (entity_id, entity_info) in entity_map {
-        let fp = &entity_info.file_path;
-        if !fp.ends_with(".clj") && !fp.ends_with(".cljs") && !fp.ends_with(".cljc") {
-            continue;
+        for item in items.split(',') {
+            let mut item = item.trim();
+            let mut kind = statement_kind;
+            for item_kind in ["function", "const"] {
+                if let Some(rest) = item.strip_prefix(item_kind) {
+                    if rest.starts_with(char::is_whitespace) {
+                        kind = Some(item_kind);
+                        item = rest.trim_start();
+                    }
+                }
+            }
+            if kind == Some("const") || item.is_empty() {
+                continue;
+            }
+            let mut parts = PHP_AS_RE.splitn(item, 2);
+            let name = parts.next().unwrap_or("").trim();
+            let path = if prefix.is_empty() {
+                name.trim_start_matches('\\').to_string()
+            } else {
+                format!("{}\\{}", prefix.trim_start_matches('\\'), name)
+            };
+            let local = match parts.next() {
+                Some(alias) => alias.trim().to_string(),
+                None => path.rsplit('\\').next().unwrap_or(&path).to_string(),
+            };
+            if local.is_empty() {
+                continue;
+            }
+            uses.push(PhpUse {
+                path,
+                local,
+                function: kind == Some("function"),
+            });
         }
-        let path_no_ext = fp.rsplit_once('.').map(|(p, _)| p).unwrap_or(fp.as_str());
-        index
-            .entry(path_no_ext.to_string())
-            .or_default()
-            .push((entity_info.name.clone(), entity_id.clone()));
     }
-    index
+    uses
 }
 
-/// Resolve one symbol from a Clojure (:require [ns :refer [symbol]]) form.
-/// Converts namespace dots to slashes and hyphens to underscores to derive the file path,
-/// then matches against entity_map to find the target entity.
-fn resolve_clojure_require(
-    file_path: &str,
+/// Resolve a fully-qualified PHP name (`App\Models\User`) to the entity
+/// declared in that namespace, falling back to a PSR-4 style file path
+/// (`.../Models/User.php`) for files without a `namespace` declaration.
+fn resolve_php_name<'a>(
+    qualified: &str,
+    function: bool,
+    symbol_table: &'a HashMap<String, Vec<String>>,
+    entity_map: &HashMap<String, EntityInfo>,
+) -> Option<&'a String> {
+    let qualified = qualified.trim_start_matches('\\');
+    let (namespace, name) = qualified.rsplit_once('\\').unwrap_or(("", qualified));
+    let candidates: Vec<(&String, &EntityInfo)> = symbol_table
+        .get(name)?
+        .iter()
+        .filter_map(|id| Some((id, entity_map.get(id)?)))
+        .filter(|(_, entity)| {
+            is_php_file(&entity.file_path)
+                && if function {
+                    entity.entity_type == "function"
+                } else {
+                    matches!(
+                        entity.entity_type.as_str(),
+                        "class" | "interface" | "trait" | "enum"
+                    )
+                }
+        })
+        .collect();
+
+    candidates
+        .iter()
+        .find(|(_, entity)| {
+            namespace.is_empty()
+                || entity_map.contains_key(&format!("{}::module::{}", entity.file_path, namespace))
+        })
+        .or_else(|| {
+            let namespace_dir = namespace.rsplit('\\').next().unwrap_or(namespace);
+            let suffix = format!("{namespace_dir}/{name}.php");
+            candidates.iter().find(|(_, entity)| {
+                entity.file_path == suffix || entity.file_path.ends_with(&format!("/{suffix}"))
+            })
+        })
+        .map(|(id, _)| *id)
+}
+
+fn is_jvm_file(file_path: &str) -> bool {
+    let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
+    crate::parser::plugins::code::languages::get_language_config(ext)
+        .is_some_and(|config| matches!(config.id, "java" | "kotlin"))
+}
+
+/// One name brought into scope by a Java or Kotlin `import`.
+struct JvmImport {
+    /// Dotted path as written, e.g. `com.acme.model.User`.
+    path: String,
+    /// Name the file refers to it by: the Kotlin alias, or the last segment.
+    local: String,
+}
+
+/// Parse single-name imports: `import a.b.C;`, `import static a.b.C.m;` and
+/// Kotlin's `import a.b.C as D`. Wildcard imports name no symbol and are
+/// skipped.
+fn jvm_imports(content: &str) -> Vec<JvmImport> {
+    static JVM_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"(?m)^\s*import\s+(?:static\s+)?([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)*)(\.\*)?(?:\s+as\s+([A-Za-z_]\w*))?",
+        )
+        .unwrap()
+    });
+
+    JVM_IMPORT_RE
+        .captures_iter(content)
+        .filter(|cap| cap.get(2).is_none())
+        .filter_map(|cap| {
+            let path = cap.get(1).unwrap().as_str();
+            let local = match cap.get(3) {
+                Some(alias) => alias.as_str(),
+                None => path.rsplit('.').next()?,
+            };
+            Some(JvmImport {
+                path: path.to_string(),
+                local: local.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Resolve a fully-qualified Java/Kotlin name to the entity it declares.
+/// Packages are matched against directories (`com.acme.model.User` lives
+/// under `.../com/acme/model/`), which is how both languages lay out source
+/// roots. A path naming a member (`a.b.Util.helper`, a static import or a
+/// nested class) resolves through its enclosing class.
+fn resolve_jvm_import<'a>(
+    qualified: &str,
+    symbol_table: &'a HashMap<String, Vec<String>>,
+    entity_map: &HashMap<String, EntityInfo>,
+) -> Option<&'a String> {
+    let (owner, name) = qualified.rsplit_once('.')?;
+    let candidates: Vec<(&String, &EntityInfo)> = symbol_table
+        .get(name)?
+        .iter()
+        .filter_map(|id| Some((id, entity_map.get(id)?)))
+        .filter(|(_, entity)| is_jvm_file(&entity.file_path))
+        .collect();
+    let in_package = |entity: &EntityInfo, package: &str| {
+        let dir = entity.file_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let package_dir = package.replace('.', "/");
+        dir == package_dir || dir.ends_with(&format!("/{package_dir}"))
+    };
+
+    candidates
+        .iter()
+        .find(|(_, entity)| entity.parent_id.is_none() && in_package(entity, owner))
+        .or_else(|| {
+            let (package, class) = owner.rsplit_once('.').unwrap_or(("", owner));
+            candidates.iter().find(|(_, entity)| {
+                entity
+                    .parent_id
+                    .as_ref()
+                    .and_then(|parent| entity_map.get(parent))
+                    .is_some_and(|parent| parent.name == class && in_package(parent, package))
+            })
+        })
+        .map(|(id, _)| *id)
+}
+
+fn is_c_family_file(file_path: &str) -> bool {
+    let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
+    crate::parser::plugins::code::languages::get_language_config(ext)
+        .is_some_and(|config| matches!(config.id, "c" | "cpp"))
+}
+
+fn is_c_header(file_path: &str) -> bool {
+    [".h", ".hh", ".hpp", ".hxx"]
+        .iter()
+        .any(|ext| file_path.ends_with(ext))
+}
+
+/// One `#include "..."` in a C or C++ file. System includes (`<...>`) point
+/// outside the repo and are skipped.
+struct CInclude {
+    /// Path as written, e.g. `util/foo.h`.
+    written: String,
+    /// The written path joined to the including file's directory.
+    relative: Option<String>,
+}
+
+impl CInclude {
+    /// Whether `file_path` is the header this include names: relative to the
+    /// including file first, else any header the path is a suffix of, since
+    /// include directories (`-Iinclude`) aren't known.
+    fn names(&self, file_path: &str) -> bool {
+        self.relative.as_deref() == Some(file_path)
+            || file_path == self.written
+            || file_path.ends_with(&format!("/{}", self.written))
+    }
+}
+
+fn c_includes(file_path: &str, content: &str) -> Vec<CInclude> {
+    static C_INCLUDE_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"(?m)^\s*#\s*include\s*"([^"]+)""#).unwrap());
+
+    let dir = Path::new(file_path).parent().unwrap_or(Path::new(""));
+    C_INCLUDE_RE
+        .captures_iter(content)
+        .map(|cap| {
+            let written = cap.get(1).unwrap().as_str();
+            CInclude {
+                written: written.to_string(),
+                relative: normalize_repo_path(dir.join(written)),
+            }
+        })
+        .collect()
+}
+
+fn plain_identifiers(content: &str) -> HashSet<&str> {
+    static PLAIN_IDENT_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b[A-Za-z_]\w*\b").unwrap());
+    PLAIN_IDENT_RE
+        .find_iter(content)
+        .map(|m| m.as_str())
+        .collect()
+}
+
+/// Resolve a name declared in one of a file's included headers. A function
+/// the header only declares resolves to its definition in a source file, so
+/// callers link to the body rather than the prototype: the definition in the
+/// header's namesake (`foo.h` -> `foo.c`) first, else the only one in the
+/// repo. Anything else, including a function with several candidate
+/// definitions, resolves to the header's own entity.
+fn resolve_c_include_name<'a>(
+    name: &str,
+    includes: &[CInclude],
+    symbol_table: &'a HashMap<String, Vec<String>>,
+    entity_map: &HashMap<String, EntityInfo>,
+) -> Option<&'a String> {
+    let candidates: Vec<(&String, &EntityInfo)> = symbol_table
+        .get(name)?
+        .iter()
+        .filter_map(|id| Some((id, entity_map.get(id)?)))
+        .filter(|(_, entity)| entity.parent_id.is_none() && is_c_family_file(&entity.file_path))
+        .collect();
+    let (declaration_id, declaration) = candidates.iter().find(|(_, entity)| {
+        is_c_header(&entity.file_path)
+            && includes
+                .iter()
+                .any(|include| include.names(&entity.file_path))
+    })?;
+    if declaration.entity_type != "function" {
+        return Some(declaration_id);
+    }
+
+    let stem = |path: &str| {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        file_name
+            .rsplit_once('.')
+            .map_or(file_name, |(stem, _)| stem)
+            .to_string()
+    };
+    let definitions: Vec<&(&String, &EntityInfo)> = candidates
+        .iter()
+        .filter(|(_, entity)| entity.entity_type == "function" && !is_c_header(&entity.file_path))
+        .collect();
+    let header_stem = stem(&declaration.file_path);
+    let definition = definitions
+        .iter()
+        .find(|(_, entity)| stem(&entity.file_path) == header_stem)
+        .or_else(|| (definitions.len() == 1).then(|| &definitions[0]));
+    Some(definition.map_or(*declaration_id, |(id, _)| *id))
+}
+
+fn is_ruby_file(file_path: &str) -> bool {
+    let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
+    crate::parser::plugins::code::languages::get_language_config(ext)
+        .is_some_and(|config| config.id == "ruby")
+}
+
+/// Repo paths a Ruby or PHP file pulls in with a path literal, in the order
+/// they should be tried: Ruby `require`/`require_relative` (`.rb` implied)
+/// and PHP `require`/`include` (and their `_once` forms). Paths are tried
+/// against the requiring file's directory, then the repo root; the root is
+/// skipped for `require_relative` and PHP paths built on `__DIR__`.
+fn required_files(file_path: &str, content: &str) -> Vec<String> {
+    static RUBY_REQUIRE_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"(?m)^\s*(require_relative|require)\s*\(?\s*['"]([^'"]+)['"]"#).unwrap()
+    });
+    static PHP_REQUIRE_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r#"\b(?:require|include)(?:_once)?\s*\(?\s*(__DIR__\s*\.\s*|dirname\(\s*__FILE__\s*\)\s*\.\s*)?['"]([^'"]+)['"]"#,
+        )
+        .unwrap()
+    });
+
+    let dir = Path::new(file_path).parent().unwrap_or(Path::new(""));
+    let mut required: Vec<String> = Vec::new();
+    let mut add = |written: &str, relative_only: bool| {
+        let written = written.trim_start_matches('/');
+        let mut candidates = vec![normalize_repo_path(dir.join(written))];
+        if !relative_only {
+            candidates.push(normalize_repo_path(PathBuf::from(written)));
+        }
+        for candidate in candidates.into_iter().flatten() {
+            if candidate != file_path && !required.contains(&candidate) {
+                required.push(candidate);
+            }
+        }
+    };
+
+    if is_ruby_file(file_path) {
+        for cap in RUBY_REQUIRE_RE.captures_iter(content) {
+            let written = cap.get(2).unwrap().as_str();
+            let written = if written.ends_with(".rb") {
+                written.to_string()
+            } else {
+                format!("{written}.rb")
+            };
+            add(&written, &cap[1] == "require_relative");
+        }
+    } else {
+        for cap in PHP_REQUIRE_RE.captures_iter(content) {
+            add(cap.get(2).unwrap().as_str(), cap.get(1).is_some());
+        }
+    }
+    required
+}
+
+/// Resolve a name to a top-level entity in one of a file's required files,
+/// trying them in `required` order.
+fn resolve_required_name<'a>(
+    name: &str,
+    required: &[String],
+    symbol_table: &'a HashMap<String, Vec<String>>,
+    entity_map: &HashMap<String, EntityInfo>,
+) -> Option<&'a String> {
+    let candidates = symbol_table.get(name)?;
+    required.iter().find_map(|path| {
+        candidates.iter().find(|id| {
+            entity_map
+                .get(*id)
+                .is_some_and(|entity| entity.parent_id.is_none() && &entity.file_path == path)
+        })
+    })
+}
+
+/// Pre-built index for Clojure namespace resolution.
+/// Maps file-path-without-extension → Vec<(entity_name, entity_id)>.
+/// Built once before the import-table loop to avoid O(total-entities) scans per :as alias.
+type ClojureNsIndex = HashMap<String, Vec<(String, String)>>;
+
+fn build_clojure_ns_index(entity_map: &HashMap<String, EntityInfo>) -> ClojureNsIndex {
+    let mut index: ClojureNsIndex = HashMap::default();
+    for (entity_id, entity_info) in entity_map {
+        let fp = &entity_info.file_path;
+        if !fp.ends_with(".clj") && !fp.ends_with(".cljs") && !fp.ends_with(".cljc") {
+            continue;
+        }
+        let path_no_ext = fp.rsplit_once('.').map(|(p, _)| p).unwrap_or(fp.as_str());
+        index
+            .entry(path_no_ext.to_string())
+            .or_default()
+            .push((entity_info.name.clone(), entity_id.clone()));
+    }
+    index
+}
+
+/// Resolve one symbol from a Clojure (:require [ns :refer [symbol]]) form.
+/// Converts namespace dots to slashes and hyphens to underscores to derive the file path,
+/// then matches against entity_map to find the target entity.
+fn resolve_clojure_require(
+    file_path: &str,
     ns_name: &str,
     symbol: &str,
     symbol_table: &HashMap<String, Vec<String>>,
@@ -4306,6 +5911,7 @@ fn extract_dot_chains_with_positions<'a>(
 fn local_binding_names_filtered<F>(
     content: &str,
     ext: &str,
+    stopword_overrides: &StopwordOverrides,
     mut include_token: F,
 ) -> HashSet<String>
 where
@@ -4315,6 +5921,10 @@ where
     if !matches!(ext, ".js" | ".jsx" | ".ts" | ".tsx" | ".py" | ".swift") {
         return names;
     }
+    let stopwords = Stopwords::for_language(
+        crate::parser::plugins::code::languages::get_language_config(ext).map(|c| c.id),
+        stopword_overrides,
+    );
 
     let mut line_no = 1;
     let mut line_start = 0;
@@ -4327,6 +5937,7 @@ where
                     line_no,
                     line_start,
                     &JS_TS_SWIFT_LOCAL_DECL_RE,
+                    stopwords,
                     &mut include_token,
                     &mut names,
                 );
@@ -4336,6 +5947,7 @@ where
                     line,
                     line_no,
                     line_start,
+                    stopwords,
                     &mut include_token,
                     &mut names,
                 );
@@ -4377,11 +5989,24 @@ static CLOJURE_QUALIFIED_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\b([a-zA-Z][a-zA-Z0-9_?!=*-]*/[a-zA-Z][a-zA-Z0-9_?!=*-]*)").unwrap()
 });
 
+// PHP fully-qualified names, e.g. `\App\Models\User`.
+static PHP_QUALIFIED_NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\\(?:[A-Za-z_][A-Za-z0-9_]*\\)+[A-Za-z_][A-Za-z0-9_]*").unwrap());
+
+// PHP static calls, e.g. `User::find(` or `\App\Models\User::find(`.
+static PHP_STATIC_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(\\?(?:[A-Za-z_][A-Za-z0-9_]*\\)*[A-Za-z_][A-Za-z0-9_]*)::([A-Za-z_][A-Za-z0-9_]*)\s*\(",
+    )
+    .unwrap()
+});
+
 fn collect_local_binding_captures<F>(
     line: &str,
     line_no: usize,
     line_start: usize,
     regex: &Regex,
+    stopwords: Stopwords,
     include_token: &mut F,
     names: &mut HashSet<String>,
 ) where
@@ -4394,6 +6019,7 @@ fn collect_local_binding_captures<F>(
                 line_no,
                 line_start,
                 name_match,
+                stopwords,
                 include_token,
                 names,
             );
@@ -4405,6 +6031,7 @@ fn collect_python_local_bindings<F>(
     line: &str,
     line_no: usize,
     line_start: usize,
+    stopwords: Stopwords,
     include_token: &mut F,
     names: &mut HashSet<String>,
 ) where
@@ -4418,6 +6045,7 @@ fn collect_python_local_bindings<F>(
                     line_no,
                     line_start,
                     name_match,
+                    stopwords,
                     include_token,
                     names,
                 );
@@ -4430,6 +6058,7 @@ fn collect_python_local_bindings<F>(
         line_no,
         line_start,
         &PY_FOR_BINDING_RE,
+        stopwords,
         include_token,
         names,
     );
@@ -4440,12 +6069,13 @@ fn maybe_add_local_binding_name<F>(
     line_no: usize,
     line_start: usize,
     name_match: regex::Match<'_>,
+    stopwords: Stopwords,
     include_token: &mut F,
     names: &mut HashSet<String>,
 ) where
     F: FnMut(usize, usize, usize) -> bool,
 {
-    if !is_reference_word(name) {
+    if !is_reference_word(name, stopwords) {
         return;
     }
     let start = line_start + name_match.start();
@@ -4463,6 +6093,15 @@ fn extra_ident_chars_for_file(file_path: &str) -> &'static [char] {
         .map_or(&[], |c| c.extra_ident_chars())
 }
 
+/// Returns the reference stopwords for the language of a given file path.
+fn stopwords_for_file<'a>(file_path: &str, overrides: &'a StopwordOverrides) -> Stopwords<'a> {
+    let ext = file_path.rfind('.').map(|i| &file_path[i..]).unwrap_or("");
+    Stopwords::for_language(
+        crate::parser::plugins::code::languages::get_language_config(ext).map(|c| c.id),
+        overrides,
+    )
+}
+
 fn strip_strategy_for_file(
     file_path: &str,
 ) -> crate::parser::plugins::code::languages::StripStrategy {
@@ -4480,10 +6119,11 @@ fn extract_references_from_content<'a>(
     content: &'a str,
     own_name: &str,
     extra_ident_chars: &'static [char],
+    stopwords: Stopwords,
     strip_strategy: crate::parser::plugins::code::languages::StripStrategy,
 ) -> Vec<&'a str> {
     let stripped = strip_for_language(strip_strategy, content);
-    extract_references_with_stripped(content, own_name, &stripped, extra_ident_chars)
+    extract_references_with_stripped(content, own_name, &stripped, extra_ident_chars, stopwords)
 }
 
 /// Yields each contiguous run of identifier characters (alphanumeric, `_`, or `extra`) as a
@@ -4720,12 +6360,14 @@ fn extract_references_with_stripped<'a>(
     own_name: &str,
     stripped: &str,
     extra_ident_chars: &'static [char],
+    stopwords: Stopwords,
 ) -> Vec<&'a str> {
     extract_references_with_stripped_filtered(
         content,
         own_name,
         stripped,
         extra_ident_chars,
+        stopwords,
         |_, _, _| true,
     )
 }
@@ -4735,13 +6377,18 @@ fn extract_references_with_stripped_filtered<'a, F>(
     own_name: &str,
     stripped: &str,
     extra_ident_chars: &'static [char],
+    stopwords: Stopwords,
     mut include_token: F,
 ) -> Vec<&'a str>
 where
     F: FnMut(usize, usize, usize) -> bool,
 {
-    let mut refs = Vec::new();
-    let mut seen: HashSet<&str> = HashSet::default();
+    let mut collector = ReferenceCollector {
+        own_name,
+        stopwords,
+        seen: HashSet::default(),
+        refs: Vec::new(),
+    };
     let mut token_start: Option<usize> = None;
     let mut line = 1;
 
@@ -4755,14 +6402,12 @@ where
 
         if let Some(start) = token_start.take() {
             maybe_push_reference_token(
+                &mut collector,
                 content,
                 stripped,
                 start,
                 idx,
                 line,
-                own_name,
-                &mut seen,
-                &mut refs,
                 &mut include_token,
             );
         }
@@ -4774,39 +6419,97 @@ where
 
     if let Some(start) = token_start {
         maybe_push_reference_token(
+            &mut collector,
             content,
             stripped,
             start,
             content.len(),
             line,
-            own_name,
-            &mut seen,
-            &mut refs,
             &mut include_token,
         );
     }
 
+    collector.refs
+}
+
+/// Identifier tokens of `content` bound by one of `imports`, with the same
+/// comment/string and ownership filtering as
+/// [`extract_references_with_stripped_filtered`] but no stopwords.
+fn extract_imported_references<'a, F>(
+    content: &'a str,
+    own_name: &str,
+    stripped: &str,
+    extra_ident_chars: &'static [char],
+    imports: &HashMap<&str, &str>,
+    mut include_token: F,
+) -> Vec<&'a str>
+where
+    F: FnMut(usize, usize, usize) -> bool,
+{
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_' || extra_ident_chars.contains(&ch);
+    let mut refs: Vec<&'a str> = Vec::new();
+    let mut token_start: Option<usize> = None;
+    let mut line = 1;
+    let mut push = |start: usize, end: usize, line: usize| {
+        let word = &content[start..end];
+        if word != own_name
+            && imports.contains_key(word)
+            && stripped.get(start..end) == Some(word)
+            && !refs.contains(&word)
+            && include_token(line, start, end)
+        {
+            refs.push(word);
+        }
+    };
+    for (idx, ch) in content.char_indices() {
+        if is_ident(ch) {
+            token_start.get_or_insert(idx);
+            continue;
+        }
+        if let Some(start) = token_start.take() {
+            push(start, idx, line);
+        }
+        if ch == '\n' {
+            line += 1;
+        }
+    }
+    if let Some(start) = token_start {
+        push(start, content.len(), line);
+    }
     refs
 }
 
+/// The references a token scan has kept so far, and the entity name and
+/// stopwords that decide which tokens it skips.
+struct ReferenceCollector<'a, 'n> {
+    own_name: &'n str,
+    stopwords: Stopwords<'n>,
+    seen: HashSet<&'a str>,
+    refs: Vec<&'a str>,
+}
+
 fn maybe_push_reference_token<'a, F>(
+    collector: &mut ReferenceCollector<'a, '_>,
     content: &'a str,
     stripped: &str,
     start: usize,
     end: usize,
     line: usize,
-    own_name: &str,
-    seen: &mut HashSet<&'a str>,
-    refs: &mut Vec<&'a str>,
     include_token: &mut F,
 ) where
     F: FnMut(usize, usize, usize) -> bool,
 {
+    let ReferenceCollector {
+        own_name,
+        stopwords,
+        seen,
+        refs,
+    } = collector;
     let word = &content[start..end];
-    if word.is_empty() || word == own_name {
+    if word.is_empty() || word == *own_name {
         return;
     }
-    if is_keyword(word) || word.len() < 2 {
+    if stopwords.is_keyword(word) || word.len() < 2 {
         return;
     }
     // Skip very short lowercase identifiers (likely local vars: i, x, a, ok, id, etc.)
@@ -4828,7 +6531,7 @@ fn maybe_push_reference_token<'a, F>(
         return;
     }
     // Skip common local variable names that create false graph edges
-    if is_common_local_name(word) {
+    if stopwords.is_common_local_name(word) {
         return;
     }
     // Skip words that only appear in comments/strings
@@ -4843,26 +6546,6 @@ fn maybe_push_reference_token<'a, F>(
     }
 }
 
-static COMMON_LOCAL_NAMES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    [
-        "result", "results", "data", "config", "value", "values", "item", "items", "input",
-        "output", "args", "opts", "name", "path", "file", "line", "count", "index", "temp", "prev",
-        "next", "curr", "current", "node", "left", "right", "root", "head", "tail", "body", "text",
-        "content", "source", "target", "entry", "error", "errors", "message", "response",
-        "request", "context", "state", "props", "event", "handler", "callback", "options",
-        "params", "query", "list", "base", "info", "meta", "kind", "mode", "flag", "size",
-        "length", "width", "height", "start", "stop", "begin", "done", "found", "status", "code",
-    ]
-    .into_iter()
-    .collect()
-});
-
-/// Names that are overwhelmingly local variables, not entity references.
-/// These create massive false-positive edges in the dependency graph.
-fn is_common_local_name(word: &str) -> bool {
-    COMMON_LOCAL_NAMES.contains(word)
-}
-
 /// Infer reference type from context using word-boundary-aware matching.
 fn infer_ref_type(content: &str, ref_name: &str) -> RefType {
     // Check if it's a function call: ref_name followed by ( with word boundary before.
@@ -4908,229 +6591,6 @@ fn infer_ref_type(content: &str, ref_name: &str) -> RefType {
     RefType::TypeRef
 }
 
-static KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    [
-        // Common across languages
-        "if",
-        "else",
-        "for",
-        "while",
-        "do",
-        "switch",
-        "case",
-        "break",
-        "continue",
-        "return",
-        "try",
-        "catch",
-        "finally",
-        "throw",
-        "new",
-        "delete",
-        "typeof",
-        "instanceof",
-        "in",
-        "of",
-        "true",
-        "false",
-        "null",
-        "undefined",
-        "void",
-        "this",
-        "super",
-        "class",
-        "extends",
-        "implements",
-        "interface",
-        "enum",
-        "const",
-        "let",
-        "var",
-        "function",
-        "async",
-        "await",
-        "yield",
-        "import",
-        "export",
-        "default",
-        "from",
-        "as",
-        "static",
-        "public",
-        "private",
-        "protected",
-        "abstract",
-        "final",
-        "override",
-        // Rust
-        "fn",
-        "pub",
-        "mod",
-        "use",
-        "struct",
-        "impl",
-        "trait",
-        "where",
-        "type",
-        "self",
-        "Self",
-        "mut",
-        "ref",
-        "match",
-        "loop",
-        "move",
-        "unsafe",
-        "extern",
-        "crate",
-        "dyn",
-        // Python
-        "def",
-        "elif",
-        "except",
-        "raise",
-        "with",
-        "pass",
-        "lambda",
-        "nonlocal",
-        "global",
-        "assert",
-        "True",
-        "False",
-        "and",
-        "or",
-        "not",
-        "is",
-        // Go
-        "func",
-        "package",
-        "range",
-        "select",
-        "chan",
-        "go",
-        "defer",
-        "map",
-        "make",
-        "append",
-        "len",
-        "cap",
-        // C/C++
-        "auto",
-        "register",
-        "volatile",
-        "sizeof",
-        "typedef",
-        "template",
-        "typename",
-        "namespace",
-        "virtual",
-        "inline",
-        "constexpr",
-        "nullptr",
-        "noexcept",
-        "explicit",
-        "friend",
-        "operator",
-        "using",
-        "cout",
-        "endl",
-        "cerr",
-        "cin",
-        "printf",
-        "scanf",
-        "malloc",
-        "free",
-        "NULL",
-        "include",
-        "ifdef",
-        "ifndef",
-        "endif",
-        "define",
-        "pragma",
-        // Ruby
-        "end",
-        "then",
-        "elsif",
-        "unless",
-        "until",
-        "begin",
-        "rescue",
-        "ensure",
-        "when",
-        "require",
-        "attr_accessor",
-        "attr_reader",
-        "attr_writer",
-        "puts",
-        "nil",
-        "module",
-        "defined",
-        // C#
-        "internal",
-        "sealed",
-        "readonly",
-        "partial",
-        "delegate",
-        "event",
-        "params",
-        "out",
-        "object",
-        "decimal",
-        "sbyte",
-        "ushort",
-        "uint",
-        "ulong",
-        "nint",
-        "nuint",
-        "dynamic",
-        "get",
-        "set",
-        "value",
-        "init",
-        "record",
-        // Types (primitives)
-        "string",
-        "number",
-        "boolean",
-        "int",
-        "float",
-        "double",
-        "bool",
-        "char",
-        "byte",
-        "i8",
-        "i16",
-        "i32",
-        "i64",
-        "u8",
-        "u16",
-        "u32",
-        "u64",
-        "f32",
-        "f64",
-        "usize",
-        "isize",
-        "str",
-        "String",
-        "Vec",
-        "Option",
-        "Result",
-        "Box",
-        "Arc",
-        "Rc",
-        "HashMap",
-        "HashSet",
-        "Some",
-        "Ok",
-        "Err",
-    ]
-    .into_iter()
-    .collect()
-});
-
-fn is_keyword(word: &str) -> bool {
-    KEYWORDS.contains(word)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5209,6 +6669,8 @@ mod tests {
             RefType::Calls => 0,
             RefType::Imports => 1,
             RefType::TypeRef => 2,
+            RefType::Implements => 3,
+            RefType::Extends => 4,
         }
     }
 
@@ -5237,7 +6699,11 @@ class Runner {
     validate(input) { return input; }
 }
 ";
-        let index = FileReferenceIndex::from_content(content, &[]);
+        let index = FileReferenceIndex::from_content(
+            content,
+            &[],
+            Stopwords::for_language(None, &StopwordOverrides::default()),
+        );
         let refs = index.refs_with_types_in_ranges(&[(1, 5)], "run");
         assert!(refs.iter().any(|(word, _)| *word == "Foo"));
         assert!(refs.iter().any(|(word, _)| *word == "Runner"));
@@ -5299,7 +6765,10 @@ export { default as PublicDefault, X as PublicX } from './stale';
             end_line: 7,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         };
         let mut child_line_ranges = HashMap::default();
         child_line_ranges.insert("parent".to_string(), vec![(3, 5)]);
@@ -5316,7 +6785,11 @@ function outer() {
   finish();
 }
 ";
-        let index = FileReferenceIndex::from_content(content, &[]);
+        let index = FileReferenceIndex::from_content(
+            content,
+            &[],
+            Stopwords::for_language(None, &StopwordOverrides::default()),
+        );
         let refs = index.refs_with_types_in_ranges(&ranges, "outer");
 
         assert!(refs.iter().any(|(word, _)| *word == "setup"));
@@ -5339,6 +6812,508 @@ function outer() {
         assert_eq!(entities.len(), depth + 1);
     }
 
+    #[test]
+    fn test_registry_stopwords_apply_to_reference_resolution() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+        write_file(
+            root,
+            "app.c",
+            "int payload(void) { return 1; }\n\nint run(void) { return payload(); }\n",
+        );
+        let files = vec!["app.c".to_string()];
+
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        assert_eq!(
+            dependency_ids(&graph, "app.c::function::run"),
+            ["app.c::function::payload"]
+        );
+
+        let mut skipping = crate::parser::plugins::create_default_registry();
+        skipping.stopwords.skip(Some("c"), ["payload"]);
+        let (graph, _) = EntityGraph::build(root, &files, &skipping);
+        assert!(dependency_ids(&graph, "app.c::function::run").is_empty());
+    }
+
+    #[test]
+    fn test_oversized_and_non_utf8_files_are_skipped_and_counted() {
+        use crate::model::diagnostic::{ParseDiagnostic, SkipReason};
+
+        let (dir, mut registry) = create_test_repo();
+        let root = dir.path();
+        registry.max_file_size = 1024;
+
+        write_file(
+            root,
+            "app.py",
+            "from bundle import build\n\ndef main():\n    return build()\n",
+        );
+        write_file(
+            root,
+            "bundle.py",
+            &"def build():\n    return 1\n".repeat(100),
+        );
+        std::fs::write(root.join("legacy.py"), b"def old():\n    return '\xe9'\n").unwrap();
+
+        let files = vec!["app.py".into(), "bundle.py".into(), "legacy.py".into()];
+        let (graph, entities) = EntityGraph::build(root, &files, &registry);
+
+        assert!(graph.entities.contains_key("app.py::function::main"));
+        assert!(entities.iter().all(|e| e.file_path == "app.py"));
+        assert_eq!(
+            registry.take_diagnostics(),
+            vec![
+                ParseDiagnostic {
+                    file_path: "bundle.py".into(),
+                    reason: SkipReason::TooLarge {
+                        size: 2600,
+                        limit: 1024
+                    },
+                },
+                ParseDiagnostic {
+                    file_path: "legacy.py".into(),
+                    reason: SkipReason::NotUtf8,
+                },
+            ]
+        );
+        assert!(registry.take_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_broken_and_empty_files_are_diagnosed() {
+        use crate::model::diagnostic::{ParseDiagnostic, SkipReason};
+
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+        write_file(root, "app.py", "def main():\n    return 1\n");
+        write_file(root, "broken.py", "def (:\n");
+        write_file(root, "blank.py", "\n\n");
+
+        let files = vec!["app.py".into(), "blank.py".into(), "broken.py".into()];
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+
+        assert!(graph.entities.contains_key("app.py::function::main"));
+        let diagnostics = registry.take_diagnostics();
+        assert_eq!(
+            diagnostics,
+            vec![
+                ParseDiagnostic {
+                    file_path: "blank.py".into(),
+                    reason: SkipReason::Empty,
+                },
+                ParseDiagnostic {
+                    file_path: "broken.py".into(),
+                    reason: SkipReason::ParseError {
+                        message: "syntax error at line 1".into(),
+                    },
+                },
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| !d.is_read_skip()));
+    }
+
+    fn assert_receiver_call_links_sibling_only(
+        graph: &EntityGraph,
+        caller: &str,
+        sibling_file: &str,
+        global_file: &str,
+    ) {
+        let deps = graph
+            .edges
+            .iter()
+            .filter(|edge| graph.entities[&edge.from_entity].name == caller)
+            .map(|edge| &graph.entities[&edge.to_entity])
+            .filter(|target| target.name == "helper")
+            .map(|target| target.file_path.as_str())
+            .collect::<Vec<_>>();
+        assert!(
+            deps.contains(&sibling_file),
+            "{caller} should link to the sibling helper. Deps: {deps:?}"
+        );
+        assert!(
+            !deps.contains(&global_file),
+            "{caller} must not link to the global helper. Deps: {deps:?}"
+        );
+    }
+
+    #[test]
+    fn test_rust_self_receiver_resolves_to_sibling_method_not_global() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "worker.rs",
+            "\
+pub struct Worker;
+
+impl Worker {
+    pub fn helper(&self) -> i32 {
+        1
+    }
+
+    pub fn run(&self) -> i32 {
+        self.helper()
+    }
+}
+",
+        );
+        write_file(root, "util.rs", "pub fn helper() -> i32 {\n    2\n}\n");
+
+        let (graph, _) =
+            EntityGraph::build(root, &["worker.rs".into(), "util.rs".into()], &registry);
+        assert_receiver_call_links_sibling_only(&graph, "run", "worker.rs", "util.rs");
+    }
+
+    #[test]
+    fn test_rust_trait_impls_link_to_trait_and_trait_methods() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "shape.rs",
+            "\
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn describe(&self) -> String;
+}
+",
+        );
+        for (file, ty, area) in [
+            ("circle.rs", "Circle", "3.14"),
+            ("square.rs", "Square", "1.0"),
+        ] {
+            write_file(
+                root,
+                file,
+                &format!(
+                    "\
+use crate::shape::Shape;
+
+pub struct {ty};
+
+impl Shape for {ty} {{
+    fn area(&self) -> f64 {{
+        {area}
+    }}
+
+    fn describe(&self) -> String {{
+        String::new()
+    }}
+}}
+
+impl {ty} {{
+    pub fn area(&self) -> f64 {{
+        0.0
+    }}
+}}
+"
+                ),
+            );
+        }
+
+        let files = vec!["shape.rs".into(), "circle.rs".into(), "square.rs".into()];
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        let implements = |from: &str, to: &str| {
+            graph.edges.iter().any(|edge| {
+                edge.from_entity == from
+                    && edge.to_entity == to
+                    && edge.ref_type == RefType::Implements
+            })
+        };
+
+        for ty in ["Circle", "Square"] {
+            let file = format!("{}.rs", ty.to_lowercase());
+            let imp = format!("{file}::impl::Shape for {ty}");
+            assert!(
+                implements(&imp, "shape.rs::trait::Shape"),
+                "{imp} edges: {:?}",
+                graph.edges
+            );
+            for method in ["area", "describe"] {
+                let trait_method = format!("shape.rs::trait::Shape::{method}");
+                assert!(
+                    graph.entities.contains_key(&trait_method),
+                    "missing {trait_method}"
+                );
+                assert!(implements(&format!("{imp}::{method}"), &trait_method));
+            }
+            // Inherent methods don't implement the trait, even with a shared name.
+            assert!(!graph.edges.iter().any(|edge| {
+                edge.from_entity == format!("{file}::impl::{ty}::area")
+                    && edge.ref_type == RefType::Implements
+            }));
+        }
+
+        let mut impls: Vec<&str> = graph
+            .get_dependents("shape.rs::trait::Shape::area")
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+        impls.sort_unstable();
+        assert_eq!(
+            impls,
+            vec![
+                "circle.rs::impl::Shape for Circle::area",
+                "square.rs::impl::Shape for Square::area",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ts_class_heritage_becomes_extends_and_implements_edges() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "base.ts",
+            "export class Model {\n  save() { return true; }\n}\n",
+        );
+        write_file(
+            root,
+            "auditable.ts",
+            "export interface Auditable {\n  audit(): string;\n}\nexport interface Named {\n  name: string;\n}\n",
+        );
+        write_file(
+            root,
+            "user.ts",
+            "\
+import { Model } from './base';
+import { Auditable, Named } from './auditable';
+
+export interface Account extends Named {}
+
+export class User extends Model implements Auditable {
+  audit() { return 'user'; }
+}
+",
+        );
+
+        let files = vec!["base.ts".into(), "auditable.ts".into(), "user.ts".into()];
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        let ref_type = |from: &str, to: &str| {
+            graph
+                .edges
+                .iter()
+                .find(|edge| edge.from_entity == from && edge.to_entity == to)
+                .map(|edge| edge.ref_type.clone())
+        };
+
+        assert_eq!(
+            ref_type("user.ts::class::User", "base.ts::class::Model"),
+            Some(RefType::Extends),
+            "edges: {:?}",
+            graph.edges
+        );
+        assert_eq!(
+            ref_type("user.ts::class::User", "auditable.ts::interface::Auditable"),
+            Some(RefType::Implements)
+        );
+        assert_eq!(
+            ref_type(
+                "user.ts::interface::Account",
+                "auditable.ts::interface::Named"
+            ),
+            Some(RefType::Extends)
+        );
+        let subclasses: Vec<&str> = graph
+            .get_dependents("base.ts::class::Model")
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(subclasses, vec!["user.ts::class::User"]);
+    }
+
+    #[test]
+    fn test_implemented_trait_name() {
+        assert_eq!(implemented_trait_name("Shape for Circle"), Some("Shape"));
+        assert_eq!(
+            implemented_trait_name("std::fmt::Display for Foo"),
+            Some("Display")
+        );
+        assert_eq!(implemented_trait_name("From<u8> for Foo"), Some("From"));
+        assert_eq!(implemented_trait_name("Foo"), None);
+    }
+
+    #[test]
+    fn test_this_and_cls_receivers_resolve_to_sibling_method_not_global() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "widget.ts",
+            "\
+export class Widget {
+    helper() { return 1; }
+    render() { return this.helper(); }
+}
+",
+        );
+        write_file(root, "util.ts", "export function helper() { return 2; }\n");
+        write_file(
+            root,
+            "model.py",
+            "\
+class Model:
+    @classmethod
+    def helper(cls):
+        return 1
+
+    @classmethod
+    def build(cls):
+        return cls.helper()
+",
+        );
+        write_file(root, "util.py", "def helper():\n    return 2\n");
+
+        let files: Vec<String> = ["widget.ts", "util.ts", "model.py", "util.py"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        assert_receiver_call_links_sibling_only(&graph, "render", "widget.ts", "util.ts");
+        assert_receiver_call_links_sibling_only(&graph, "build", "model.py", "util.py");
+    }
+
+    #[test]
+    fn test_same_file_call_outscores_cross_file_ambiguous_name() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "local.py",
+            "def helper():\n    return 1\n\ndef caller():\n    return helper()\n",
+        );
+        write_file(root, "one.py", "def shared():\n    return 1\n");
+        write_file(root, "two.py", "def shared():\n    return 2\n");
+        write_file(root, "user.py", "def consumer():\n    return shared()\n");
+
+        let files: Vec<String> = ["local.py", "one.py", "two.py", "user.py"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let (mut graph, _) = EntityGraph::build(root, &files, &registry);
+
+        let confidence_of = |graph: &EntityGraph, from: &str, to: &str| {
+            graph
+                .edges
+                .iter()
+                .find(|edge| {
+                    graph.entities[&edge.from_entity].name == from
+                        && graph.entities[&edge.to_entity].name == to
+                })
+                .map(|edge| edge.confidence)
+        };
+        let local = confidence_of(&graph, "caller", "helper").expect("same-file call edge");
+        let ambiguous = confidence_of(&graph, "consumer", "shared").expect("cross-file name edge");
+        assert!(
+            local > ambiguous,
+            "same-file call ({local}) should outscore ambiguous cross-file name ({ambiguous})"
+        );
+
+        graph.retain_min_confidence(0.5);
+        assert!(confidence_of(&graph, "caller", "helper").is_some());
+        assert!(confidence_of(&graph, "consumer", "shared").is_none());
+    }
+
+    #[test]
+    fn test_name_shared_by_many_entities_is_not_linked_across_files() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        let mut files = Vec::new();
+        for i in 0..50 {
+            let file = format!("mod{i:02}.py");
+            write_file(root, &file, &format!("def index():\n    return {i}\n"));
+            files.push(file);
+        }
+        write_file(root, "guess.py", "def consumer():\n    return index()\n");
+        write_file(
+            root,
+            "imported.py",
+            "from mod07 import index\n\ndef importer():\n    return index()\n",
+        );
+        write_file(
+            root,
+            "local.py",
+            "def index():\n    return -1\n\ndef caller():\n    return index()\n",
+        );
+        files.extend(["guess.py", "imported.py", "local.py"].map(String::from));
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+
+        let targets_of = |name: &str| {
+            let id = graph
+                .entities
+                .values()
+                .find(|entity| entity.name == name)
+                .map(|entity| entity.id.clone())
+                .unwrap_or_else(|| panic!("{name} entity should exist"));
+            graph
+                .get_dependencies(&id)
+                .into_iter()
+                .map(|entity| entity.file_path.clone())
+                .collect::<Vec<_>>()
+        };
+        assert!(targets_of("consumer").is_empty(), "{:?}", graph.edges);
+        assert_eq!(targets_of("importer"), vec!["mod07.py"]);
+        assert_eq!(targets_of("caller"), vec!["local.py"]);
+        assert_eq!(graph.ambiguous_names(), vec![("index".to_string(), 51)]);
+    }
+
+    #[test]
+    fn test_incremental_update_links_imported_name_shared_by_many_entities() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        let mut files = Vec::new();
+        for i in 0..50 {
+            let file = format!("mod{i:02}.ts");
+            write_file(
+                root,
+                &file,
+                &format!("export function render() {{ return {i}; }}\n"),
+            );
+            files.push(file);
+        }
+        write_file(
+            root,
+            "imported.ts",
+            "import { render } from './mod07';\nexport function importer() { return render(); }\n",
+        );
+        files.push("imported.ts".into());
+        let (mut graph, _) = EntityGraph::build(root, &files, &registry);
+        let importer_deps = |graph: &EntityGraph| {
+            graph
+                .get_dependencies("imported.ts::function::importer")
+                .into_iter()
+                .map(|entity| entity.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(importer_deps(&graph), vec!["mod07.ts::function::render"]);
+
+        write_file(
+            root,
+            "imported.ts",
+            "import { render } from './mod07';\nexport function importer() { return render() + 1; }\n",
+        );
+        graph.update_from_changes(
+            &[FileChange {
+                file_path: "imported.ts".into(),
+                status: FileStatus::Modified,
+                old_file_path: None,
+                before_content: None,
+                after_content: None,
+            }],
+            root,
+            &registry,
+        );
+        assert_eq!(importer_deps(&graph), vec!["mod07.ts::function::render"]);
+    }
+
     #[test]
     fn test_chunked_scope_resolution_keeps_cross_chunk_import_edges() {
         let (dir, registry) = create_test_repo();
@@ -5538,6 +7513,56 @@ comment with Helper
         );
     }
 
+    #[test]
+    fn test_incremental_add_file_links_unchanged_callers_of_new_names() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "a.ts",
+            "import { source } from './b';\nexport function caller() { return source(); }\n",
+        );
+        write_file(root, "c.ts", "export function other() { return 1; }\n");
+        let files: Vec<String> = vec!["a.ts".into(), "c.ts".into()];
+        let (mut graph, _) = EntityGraph::build(root, &files, &registry);
+        assert!(graph.get_dependencies("a.ts::function::caller").is_empty());
+
+        // `source` is a common local name, so only the import links it.
+        write_file(root, "b.ts", "export function source() { return 1; }\n");
+        graph.update_from_changes(
+            &[FileChange {
+                file_path: "b.ts".into(),
+                status: FileStatus::Added,
+                old_file_path: None,
+                before_content: None,
+                after_content: None,
+            }],
+            root,
+            &registry,
+        );
+
+        let deps: Vec<&str> = graph
+            .get_dependencies("a.ts::function::caller")
+            .iter()
+            .map(|d| d.id.as_str())
+            .collect();
+        assert_eq!(deps, vec!["b.ts::function::source"]);
+        let (fresh, _) = EntityGraph::build(
+            root,
+            &["a.ts".into(), "b.ts".into(), "c.ts".into()],
+            &registry,
+        );
+        assert_eq!(
+            fresh
+                .get_dependencies("a.ts::function::caller")
+                .iter()
+                .map(|d| d.id.as_str())
+                .collect::<Vec<_>>(),
+            deps
+        );
+    }
+
     #[test]
     fn test_incremental_delete_file() {
         let (dir, registry) = create_test_repo();
@@ -5562,15 +7587,78 @@ comment with Helper
             &registry,
         );
 
-        assert_eq!(graph.entities.len(), 1);
-        assert!(!graph.entities.contains_key("b.ts::function::bar"));
-        // foo's dependency on bar should be pruned
-        let foo_deps = graph.get_dependencies("a.ts::function::foo");
-        assert!(
-            foo_deps.is_empty(),
-            "foo's deps should be empty after bar deleted. Deps: {:?}",
-            foo_deps.iter().map(|d| &d.name).collect::<Vec<_>>()
+        assert_eq!(graph.entities.len(), 1);
+        assert!(!graph.entities.contains_key("b.ts::function::bar"));
+        // foo's dependency on bar should be pruned
+        let foo_deps = graph.get_dependencies("a.ts::function::foo");
+        assert!(
+            foo_deps.is_empty(),
+            "foo's deps should be empty after bar deleted. Deps: {:?}",
+            foo_deps.iter().map(|d| &d.name).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_incremental_update_keeps_inbound_edges_from_unchanged_files() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(root, "a.ts", "export function caller() { return foo(); }\n");
+        write_file(root, "c.ts", "export function foo() { return 1; }\n");
+
+        let (mut graph, _) = EntityGraph::build(root, &["a.ts".into(), "c.ts".into()], &registry);
+        let modified = |path: &str| FileChange {
+            file_path: path.into(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: None,
+            after_content: None,
+        };
+        let caller_deps = |graph: &EntityGraph| {
+            let mut ids: Vec<String> = graph
+                .get_dependencies("a.ts::function::caller")
+                .iter()
+                .map(|d| d.id.clone())
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        // Editing foo's body must not drop the edge from the unchanged caller.
+        write_file(root, "c.ts", "export function foo() { return 2; }\n");
+        graph.update_from_changes(&[modified("c.ts")], root, &registry);
+        assert_eq!(caller_deps(&graph), vec!["c.ts::function::foo"]);
+
+        // Moving foo to a new file re-resolves the caller to the new target.
+        write_file(root, "b.ts", "export function foo() { return 3; }\n");
+        write_file(root, "c.ts", "export function other() { return 4; }\n");
+        graph.update_from_changes(
+            &[
+                FileChange {
+                    status: FileStatus::Added,
+                    ..modified("b.ts")
+                },
+                modified("c.ts"),
+            ],
+            root,
+            &registry,
+        );
+        assert_eq!(caller_deps(&graph), vec!["b.ts::function::foo"]);
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .filter(|e| e.from_entity == "a.ts::function::caller")
+                .count(),
+            1
+        );
+
+        let (fresh, _) = EntityGraph::build(
+            root,
+            &["a.ts".into(), "b.ts".into(), "c.ts".into()],
+            &registry,
         );
+        assert_eq!(caller_deps(&graph), caller_deps(&fresh));
     }
 
     #[test]
@@ -6220,6 +8308,63 @@ func load(name: String) -> String { return "name" }
         );
     }
 
+    #[test]
+    fn test_incremental_update_scores_imported_edges_like_a_full_build() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "a.ts",
+            "import { foo } from './b';\nexport function caller() { return foo(); }\n",
+        );
+        write_file(root, "b.ts", "export function foo() { return 1; }\n");
+        write_file(root, "c.ts", "export function foo() { return 2; }\n");
+        let files: Vec<String> = vec!["a.ts".into(), "b.ts".into(), "c.ts".into()];
+        let (mut graph, _) = EntityGraph::build(root, &files, &registry);
+
+        let modified = |path: &str| FileChange {
+            file_path: path.into(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: None,
+            after_content: None,
+        };
+        let confidences = |graph: &EntityGraph| {
+            let mut edges: Vec<(String, String, f32)> = graph
+                .edges
+                .iter()
+                .map(|e| (e.from_entity.clone(), e.to_entity.clone(), e.confidence))
+                .collect();
+            edges.sort_by(|l, r| (&l.0, &l.1).cmp(&(&r.0, &r.1)));
+            edges
+        };
+
+        // The caller itself is re-resolved...
+        write_file(
+            root,
+            "a.ts",
+            "import { foo } from './b';\nexport function caller() { return foo() + 1; }\n",
+        );
+        graph.update_from_changes(&[modified("a.ts")], root, &registry);
+        let (fresh, _) = EntityGraph::build(root, &files, &registry);
+        assert_eq!(confidences(&graph), confidences(&fresh));
+        assert_eq!(
+            confidences(&graph),
+            vec![(
+                "a.ts::function::caller".to_string(),
+                "b.ts::function::foo".to_string(),
+                0.9
+            )]
+        );
+
+        // ...and so is an unchanged caller of an edited target.
+        write_file(root, "b.ts", "export function foo() { return 3; }\n");
+        graph.update_from_changes(&[modified("b.ts")], root, &registry);
+        let (fresh, _) = EntityGraph::build(root, &files, &registry);
+        assert_eq!(confidences(&graph), confidences(&fresh));
+    }
+
     #[test]
     fn test_incremental_with_content() {
         let (dir, registry) = create_test_repo();
@@ -6462,6 +8607,8 @@ func draw(widget: Widget) {
                         parent_id: entity.parent_id.clone(),
                         start_line: entity.start_line,
                         end_line: entity.end_line,
+                        is_test: false,
+                        is_private: false,
                     },
                 )
             })
@@ -6498,8 +8645,13 @@ func draw(widget: Widget) {
     #[test]
     fn test_extract_references() {
         let content = "function processData(input) {\n  const result = validateInput(input);\n  return transform(result);\n}";
-        let refs =
-            extract_references_from_content(content, "processData", &[], StripStrategy::Generic);
+        let refs = extract_references_from_content(
+            content,
+            "processData",
+            &[],
+            Stopwords::for_language(None, &StopwordOverrides::default()),
+            StripStrategy::Generic,
+        );
         assert!(refs.contains(&"validateInput"));
         assert!(refs.contains(&"transform"));
         assert!(!refs.contains(&"processData")); // self excluded
@@ -6666,7 +8818,13 @@ func draw(widget: Widget) {
     #[test]
     fn test_extract_references_skips_keywords() {
         let content = "function foo() { if (true) { return false; } }";
-        let refs = extract_references_from_content(content, "foo", &[], StripStrategy::Generic);
+        let refs = extract_references_from_content(
+            content,
+            "foo",
+            &[],
+            Stopwords::for_language(None, &StopwordOverrides::default()),
+            StripStrategy::Generic,
+        );
         assert!(!refs.contains(&"if"));
         assert!(!refs.contains(&"true"));
         assert!(!refs.contains(&"return"));
@@ -7646,136 +9804,654 @@ export function run(): string {
     }
 
     #[test]
-    fn test_python_relative_import_resolution_uses_full_path() {
+    fn test_python_relative_import_resolution_uses_full_path() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "src/a/util.py",
+            "\
+def helper():
+    return 1
+",
+        );
+        write_file(
+            root,
+            "src/b/util.py",
+            "\
+def helper():
+    return 2
+",
+        );
+        write_file(
+            root,
+            "src/main.py",
+            "\
+from .b.util import helper
+
+def caller():
+    return helper()
+",
+        );
+
+        let (graph, _) = EntityGraph::build(
+            root,
+            &[
+                "src/a/util.py".into(),
+                "src/b/util.py".into(),
+                "src/main.py".into(),
+            ],
+            &registry,
+        );
+
+        let caller_id = graph
+            .entities
+            .keys()
+            .find(|id| id.contains("caller"))
+            .expect("caller entity should exist");
+        let deps = graph.get_dependencies(caller_id);
+        assert!(
+            deps.iter()
+                .any(|d| d.name == "helper" && d.file_path == "src/b/util.py"),
+            "caller should resolve helper to src/b/util.py. Deps: {:?}",
+            deps.iter()
+                .map(|d| (&d.name, &d.file_path))
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            !deps
+                .iter()
+                .any(|d| d.name == "helper" && d.file_path == "src/a/util.py"),
+            "caller should not resolve helper to src/a/util.py. Deps: {:?}",
+            deps.iter()
+                .map(|d| (&d.name, &d.file_path))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_python_absolute_import_resolution_uses_full_path() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "src/a/util.py",
+            "\
+def helper():
+    return 1
+",
+        );
+        write_file(
+            root,
+            "src/b/util.py",
+            "\
+def helper():
+    return 2
+",
+        );
+        write_file(
+            root,
+            "src/main.py",
+            "\
+from src.b.util import helper
+
+def caller():
+    return helper()
+",
+        );
+
+        let (graph, _) = EntityGraph::build(
+            root,
+            &[
+                "src/a/util.py".into(),
+                "src/b/util.py".into(),
+                "src/main.py".into(),
+            ],
+            &registry,
+        );
+
+        let caller_id = graph
+            .entities
+            .keys()
+            .find(|id| id.contains("caller"))
+            .expect("caller entity should exist");
+        let deps = graph.get_dependencies(caller_id);
+        assert!(
+            deps.iter()
+                .any(|d| d.name == "helper" && d.file_path == "src/b/util.py"),
+            "caller should resolve helper to src/b/util.py. Deps: {:?}",
+            deps.iter()
+                .map(|d| (&d.name, &d.file_path))
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            !deps
+                .iter()
+                .any(|d| d.name == "helper" && d.file_path == "src/a/util.py"),
+            "caller should not resolve helper to src/a/util.py. Deps: {:?}",
+            deps.iter()
+                .map(|d| (&d.name, &d.file_path))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_go_methods_link_to_their_receiver_type() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "server.go",
+            "\
+package srv
+
+type Server struct {
+	addr string
+}
+
+func (s *Server) Start() error {
+	return nil
+}
+
+func (s Server) Addr() string {
+	return s.addr
+}
+",
+        );
+
+        let (graph, entities) = EntityGraph::build(root, &["server.go".into()], &registry);
+
+        let server_id = "server.go::type::Server";
+        for (method, receiver) in [("Start", "*Server"), ("Addr", "Server")] {
+            let entity = entities
+                .iter()
+                .find(|e| e.name == method)
+                .expect("method entity should exist");
+            assert_eq!(entity.parent_id.as_deref(), Some(server_id));
+            assert_eq!(
+                entity
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get("go.receiver"))
+                    .map(String::as_str),
+                Some(receiver)
+            );
+        }
+
+        let mut impacted: Vec<&str> = graph
+            .impact_analysis(server_id)
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        impacted.sort_unstable();
+        assert_eq!(impacted, vec!["Addr", "Start"]);
+    }
+
+    #[test]
+    fn test_sem_ignore_directives_drop_graph_edges() {
+        let (dir, mut registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "app.py",
+            "\
+def helper():
+    return 1
+
+# sem:ignore-impact
+def generated():
+    return helper()
+
+def handwritten():
+    return helper()
+",
+        );
+        let files = ["app.py".to_string()];
+
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        let dependents: Vec<&str> = graph
+            .get_dependents("app.py::function::helper")
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(dependents, vec!["handwritten"]);
+
+        registry.ignore_directives = false;
+        let (graph, _) = EntityGraph::build(root, &files, &registry);
+        let dependents: Vec<&str> = graph
+            .get_dependents("app.py::function::helper")
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(dependents, vec!["generated", "handwritten"]);
+    }
+
+    #[test]
+    fn test_markdown_links_resolve_to_heading_entities() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "docs/guide.md",
+            "# Guide\n\nRead [the section](./other.md#the-section) first.\n\n## Related\n\nSee [other](other.md), [back](#guide) and [gone](../missing.md).\n",
+        );
+        write_file(
+            root,
+            "docs/other.md",
+            "# Other\n\nIntro.\n\n## The Section!\n\nDetails.\n",
+        );
+
+        let files = ["docs/guide.md".to_string(), "docs/other.md".to_string()];
+        let (graph, entities) = EntityGraph::build(root, &files, &registry);
+
+        let links = resolve_markdown_links(&entities);
+        let resolved = |target: &str| {
+            links
+                .iter()
+                .find(|link| link.target == target)
+                .unwrap_or_else(|| panic!("missing link {target}: {links:?}"))
+                .to_entity
+                .clone()
+        };
+        assert_eq!(
+            resolved("./other.md#the-section").as_deref(),
+            Some("docs/other.md::heading::The Section!")
+        );
+        assert_eq!(
+            resolved("other.md").as_deref(),
+            Some("docs/other.md::heading::Other")
+        );
+        assert_eq!(
+            resolved("#guide").as_deref(),
+            Some("docs/guide.md::heading::Guide")
+        );
+        assert_eq!(resolved("../missing.md"), None);
+
+        let has_import = |from: &str, to: &str| {
+            graph.edges.iter().any(|edge| {
+                edge.from_entity == from
+                    && edge.to_entity == to
+                    && edge.ref_type == RefType::Imports
+            })
+        };
+        assert!(has_import(
+            "docs/guide.md::heading::Guide",
+            "docs/other.md::heading::The Section!"
+        ));
+        assert!(has_import(
+            "docs/guide.md::heading::Related",
+            "docs/other.md::heading::Other"
+        ));
+        assert!(has_import(
+            "docs/guide.md::heading::Related",
+            "docs/guide.md::heading::Guide"
+        ));
+    }
+
+    #[test]
+    fn test_php_use_alias_resolves_to_original_class() {
         let (dir, registry) = create_test_repo();
         let root = dir.path();
 
         write_file(
             root,
-            "src/a/util.py",
+            "src/Models/User.php",
             "\
-def helper():
-    return 1
+<?php
+namespace App\\Models;
+
+class User
+{
+    public static function find($id)
+    {
+        return new User();
+    }
+}
 ",
         );
         write_file(
             root,
-            "src/b/util.py",
+            "src/Legacy/User.php",
             "\
-def helper():
-    return 2
+<?php
+namespace Legacy;
+
+class User
+{
+}
 ",
         );
         write_file(
             root,
-            "src/main.py",
+            "src/Support/helpers.php",
             "\
-from .b.util import helper
+<?php
+namespace App\\Support;
 
-def caller():
-    return helper()
+function format_name($name)
+{
+    return trim($name);
+}
+",
+        );
+        write_file(
+            root,
+            "src/Http/Controller.php",
+            "\
+<?php
+namespace App\\Http;
+
+use App\\Models\\User as Account;
+
+class Controller
+{
+    public function show($id)
+    {
+        return Account::find($id);
+    }
+
+    public function label($user)
+    {
+        return \\App\\Support\\format_name($user);
+    }
+}
 ",
         );
 
         let (graph, _) = EntityGraph::build(
             root,
             &[
-                "src/a/util.py".into(),
-                "src/b/util.py".into(),
-                "src/main.py".into(),
+                "src/Http/Controller.php".into(),
+                "src/Legacy/User.php".into(),
+                "src/Models/User.php".into(),
+                "src/Support/helpers.php".into(),
             ],
             &registry,
         );
 
-        let caller_id = graph
-            .entities
-            .keys()
-            .find(|id| id.contains("caller"))
-            .expect("caller entity should exist");
-        let deps = graph.get_dependencies(caller_id);
-        assert!(
-            deps.iter()
-                .any(|d| d.name == "helper" && d.file_path == "src/b/util.py"),
-            "caller should resolve helper to src/b/util.py. Deps: {:?}",
-            deps.iter()
-                .map(|d| (&d.name, &d.file_path))
-                .collect::<Vec<_>>()
-        );
-        assert!(
-            !deps
+        let has_edge = |from: &str, to: &str, ref_type: RefType| {
+            graph
+                .edges
                 .iter()
-                .any(|d| d.name == "helper" && d.file_path == "src/a/util.py"),
-            "caller should not resolve helper to src/a/util.py. Deps: {:?}",
-            deps.iter()
-                .map(|d| (&d.name, &d.file_path))
-                .collect::<Vec<_>>()
-        );
+                .any(|e| e.from_entity == from && e.to_entity == to && e.ref_type == ref_type)
+        };
+        assert!(has_edge(
+            "src/Http/Controller.php::class::Controller::show",
+            "src/Models/User.php::class::User",
+            RefType::TypeRef,
+        ));
+        assert!(has_edge(
+            "src/Http/Controller.php::class::Controller::show",
+            "src/Models/User.php::class::User::find",
+            RefType::Calls,
+        ));
+        assert!(!graph
+            .edges
+            .iter()
+            .any(|e| e.to_entity == "src/Legacy/User.php::class::User"));
+        assert!(has_edge(
+            "src/Http/Controller.php::module::App\\Http",
+            "src/Models/User.php::class::User",
+            RefType::Imports,
+        ));
+        assert!(has_edge(
+            "src/Http/Controller.php::class::Controller::label",
+            "src/Support/helpers.php::function::format_name",
+            RefType::Calls,
+        ));
     }
 
     #[test]
-    fn test_python_absolute_import_resolution_uses_full_path() {
+    fn test_jvm_imports_pick_the_imported_class_among_same_named_ones() {
         let (dir, registry) = create_test_repo();
         let root = dir.path();
 
+        for package in ["model", "legacy"] {
+            write_file(
+                root,
+                &format!("src/main/java/com/acme/{package}/User.java"),
+                &format!(
+                    "\
+package com.acme.{package};
+
+public class User {{
+    public String name() {{
+        return \"{package}\";
+    }}
+}}
+"
+                ),
+            );
+        }
         write_file(
             root,
-            "src/a/util.py",
+            "src/main/java/com/acme/web/Controller.java",
             "\
-def helper():
-    return 1
+package com.acme.web;
+
+import com.acme.model.User;
+
+public class Controller {
+    public String show() {
+        User user = new User();
+        return user.name();
+    }
+}
 ",
         );
         write_file(
             root,
-            "src/b/util.py",
+            "src/main/kotlin/com/acme/web/Page.kt",
             "\
-def helper():
-    return 2
+package com.acme.web
+
+import com.acme.legacy.User as LegacyUser
+
+class Page {
+    fun render(): String {
+        val user = LegacyUser()
+        return user.toString()
+    }
+}
 ",
         );
+
+        let (graph, _) = EntityGraph::build(
+            root,
+            &[
+                "src/main/java/com/acme/legacy/User.java".into(),
+                "src/main/java/com/acme/model/User.java".into(),
+                "src/main/java/com/acme/web/Controller.java".into(),
+                "src/main/kotlin/com/acme/web/Page.kt".into(),
+            ],
+            &registry,
+        );
+
+        let targets_of = |from: &str| -> Vec<&str> {
+            graph
+                .edges
+                .iter()
+                .filter(|e| e.from_entity == from)
+                .map(|e| e.to_entity.as_str())
+                .collect()
+        };
+        let show =
+            targets_of("src/main/java/com/acme/web/Controller.java::class::Controller::show");
+        assert!(show.contains(&"src/main/java/com/acme/model/User.java::class::User"));
+        assert!(!show.contains(&"src/main/java/com/acme/legacy/User.java::class::User"));
+        assert_eq!(
+            targets_of("src/main/kotlin/com/acme/web/Page.kt::class::Page::render"),
+            vec!["src/main/java/com/acme/legacy/User.java::class::User"]
+        );
+    }
+
+    #[test]
+    fn test_jvm_imports_parse_aliases_and_skip_wildcards() {
+        let imports = jvm_imports(
+            "package a;\n\
+             import com.acme.model.User;\n\
+             import static com.acme.util.Strings.trim;\n\
+             import com.acme.legacy.*;\n\
+             import com.acme.legacy.User as LegacyUser\n",
+        );
+        let parsed: Vec<(&str, &str)> = imports
+            .iter()
+            .map(|i| (i.path.as_str(), i.local.as_str()))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("com.acme.model.User", "User"),
+                ("com.acme.util.Strings.trim", "trim"),
+                ("com.acme.legacy.User", "LegacyUser"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_c_calls_through_a_header_resolve_to_the_definition() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "src/foo.h",
+            "#ifndef FOO_H\n#define FOO_H\n\nint foo(int x);\n\n#endif\n",
+        );
         write_file(
             root,
-            "src/main.py",
+            "src/foo.c",
+            "#include \"foo.h\"\n\nint foo(int x) {\n    return x + 1;\n}\n",
+        );
+        write_file(
+            root,
+            "src/main.c",
+            "#include \"foo.h\"\n\nint main(void) {\n    return foo(1);\n}\n",
+        );
+
+        let (graph, _) = EntityGraph::build(
+            root,
+            &["src/foo.c".into(), "src/foo.h".into(), "src/main.c".into()],
+            &registry,
+        );
+
+        let targets: Vec<&str> = graph
+            .edges
+            .iter()
+            .filter(|e| e.from_entity == "src/main.c::function::main")
+            .map(|e| e.to_entity.as_str())
+            .collect();
+        assert_eq!(targets, vec!["src/foo.c::function::foo"]);
+    }
+
+    #[test]
+    fn test_ruby_require_relative_links_to_the_required_file() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(root, "user.rb", "class User\n  def save\n  end\nend\n");
+        write_file(root, "admin/user.rb", "class User\nend\n");
+        write_file(
+            root,
+            "a.rb",
             "\
-from src.b.util import helper
+require_relative 'user'
 
-def caller():
-    return helper()
+class Signup
+  def run
+    User.new.save
+  end
+end
 ",
         );
 
         let (graph, _) = EntityGraph::build(
             root,
-            &[
-                "src/a/util.py".into(),
-                "src/b/util.py".into(),
-                "src/main.py".into(),
-            ],
+            &["a.rb".into(), "admin/user.rb".into(), "user.rb".into()],
             &registry,
         );
 
-        let caller_id = graph
+        let user_id = |file: &str| {
+            graph
+                .entities
+                .values()
+                .find(|entity| entity.file_path == file && entity.name == "User")
+                .map(|entity| entity.id.clone())
+                .unwrap_or_else(|| panic!("{file} should define User"))
+        };
+        let signup_id = graph
             .entities
-            .keys()
-            .find(|id| id.contains("caller"))
-            .expect("caller entity should exist");
-        let deps = graph.get_dependencies(caller_id);
-        assert!(
-            deps.iter()
-                .any(|d| d.name == "helper" && d.file_path == "src/b/util.py"),
-            "caller should resolve helper to src/b/util.py. Deps: {:?}",
-            deps.iter()
-                .map(|d| (&d.name, &d.file_path))
-                .collect::<Vec<_>>()
+            .values()
+            .find(|entity| entity.name == "Signup")
+            .map(|entity| entity.id.clone())
+            .expect("Signup entity should exist");
+        assert!(graph
+            .edges
+            .iter()
+            .any(|e| e.from_entity.starts_with("a.rb::")
+                && e.from_entity != signup_id
+                && e.to_entity == user_id("user.rb")));
+        assert!(graph.edges.iter().any(|e| e.from_entity == signup_id
+            && e.to_entity == user_id("user.rb")
+            && e.ref_type == RefType::Imports));
+        assert!(!graph
+            .edges
+            .iter()
+            .any(|e| e.to_entity == user_id("admin/user.rb")));
+    }
+
+    #[test]
+    fn test_required_files_resolve_against_directory_then_root() {
+        assert_eq!(
+            required_files(
+                "lib/app/a.rb",
+                "require 'json'\nrequire_relative '../user'\nrequire \"lib/config.rb\"\n",
+            ),
+            vec![
+                "lib/app/json.rb",
+                "json.rb",
+                "lib/user.rb",
+                "lib/app/lib/config.rb",
+                "lib/config.rb",
+            ]
         );
-        assert!(
-            !deps
-                .iter()
-                .any(|d| d.name == "helper" && d.file_path == "src/a/util.py"),
-            "caller should not resolve helper to src/a/util.py. Deps: {:?}",
-            deps.iter()
-                .map(|d| (&d.name, &d.file_path))
-                .collect::<Vec<_>>()
+        assert_eq!(
+            required_files(
+                "src/index.php",
+                "<?php\nrequire_once 'helpers.php';\ninclude(__DIR__ . '/views/home.php');\n",
+            ),
+            vec!["src/helpers.php", "helpers.php", "src/views/home.php"]
+        );
+    }
+
+    #[test]
+    fn test_php_use_imports_parse_aliases_groups_and_functions() {
+        let uses = php_use_imports(
+            "<?php\n\
+             use App\\Models\\User as Account, App\\Models\\Post;\n\
+             use function App\\Support\\format_name;\n\
+             use const App\\VERSION;\n\
+             use App\\Http\\{Request, Response as Reply};\n\
+             class A {\n    use HasFactory;\n}\n",
+        );
+        let parsed: Vec<(&str, &str, bool)> = uses
+            .iter()
+            .map(|u| (u.path.as_str(), u.local.as_str(), u.function))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("App\\Models\\User", "Account", false),
+                ("App\\Models\\Post", "Post", false),
+                ("App\\Support\\format_name", "format_name", true),
+                ("App\\Http\\Request", "Request", false),
+                ("App\\Http\\Response", "Reply", false),
+            ]
         );
     }
 
@@ -8264,6 +10940,59 @@ def report():
         );
     }
 
+    #[test]
+    fn test_ruby_method_named_like_a_stopword_still_resolves() {
+        let (dir, registry) = create_test_repo();
+        let root = dir.path();
+
+        write_file(
+            root,
+            "job.rb",
+            "\
+class Job
+  def status
+    @state
+  end
+
+  def report
+    puts status
+  end
+
+  def describe
+    # the status is reported separately
+    \"job\"
+  end
+end
+",
+        );
+
+        let (graph, _) = EntityGraph::build(root, &["job.rb".into()], &registry);
+
+        let dependency_names = |name: &str| {
+            let id = graph
+                .entities
+                .iter()
+                .find(|(_, entity)| entity.name == name)
+                .map(|(id, _)| id)
+                .unwrap_or_else(|| panic!("{name} entity should exist"));
+            graph
+                .get_dependencies(id)
+                .into_iter()
+                .map(|entity| entity.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            dependency_names("report").contains(&"status".to_string()),
+            "report should depend on the status method. Edges: {:?}",
+            graph.edges
+        );
+        assert!(
+            !dependency_names("describe").contains(&"status".to_string()),
+            "a comment mentioning status should not create an edge. Edges: {:?}",
+            graph.edges
+        );
+    }
+
     #[test]
     fn test_constructor_return_type_tie_break_uses_stable_source_order() {
         let (dir, registry) = create_test_repo();
@@ -8939,11 +11668,14 @@ export function caller() {
             end_line: 5,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             content: content.to_string(),
             content_hash: String::new(),
             structural_hash: None,
             parent_id: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -9041,6 +11773,8 @@ export function caller() {
                         parent_id: None,
                         start_line: e.start_line,
                         end_line: e.end_line,
+                        is_test: false,
+                        is_private: false,
                     },
                 )
             })
@@ -9057,4 +11791,170 @@ export function caller() {
         assert!(with_custom.contains("qa/smoke.rs::function::run"));
         assert!(!with_custom.contains("src/main.rs::function::main"));
     }
+
+    #[test]
+    fn impact_analysis_bounded_stops_at_depth() {
+        // a <- b <- c <- d: each function calls the previous one.
+        let names = ["a", "b", "c", "d"];
+        let entity_map: EntityInfoMap = names
+            .iter()
+            .enumerate()
+            .map(|(line, name)| {
+                let id = format!("lib.py::function::{name}");
+                let info = EntityInfo {
+                    id: id.clone(),
+                    name: name.to_string(),
+                    entity_type: "function".to_string(),
+                    file_path: "lib.py".to_string(),
+                    parent_id: None,
+                    start_line: line + 1,
+                    end_line: line + 1,
+                    is_test: false,
+                    is_private: false,
+                };
+                (id, info)
+            })
+            .collect();
+        let edges = names
+            .windows(2)
+            .map(|pair| EntityRef {
+                from_entity: format!("lib.py::function::{}", pair[1]),
+                to_entity: format!("lib.py::function::{}", pair[0]),
+                ref_type: RefType::Calls,
+                confidence: 1.0,
+            })
+            .collect();
+        let graph = EntityGraph::from_parts(entity_map, edges);
+
+        let impact = |depth| {
+            graph
+                .impact_analysis_bounded("lib.py::function::a", depth)
+                .into_iter()
+                .map(|(entity, level)| (entity.name.as_str(), level))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(impact(1), vec![("b", 1)]);
+        assert_eq!(impact(2), vec![("b", 1), ("c", 2)]);
+        assert_eq!(impact(0), vec![("b", 1), ("c", 2), ("d", 3)]);
+
+        let dependencies = |depth| {
+            graph
+                .dependencies_bounded("lib.py::function::d", depth)
+                .into_iter()
+                .map(|(entity, level)| (entity.name.as_str(), level))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(dependencies(1), vec![("c", 1)]);
+        assert_eq!(dependencies(0), vec![("c", 1), ("b", 2), ("a", 3)]);
+    }
+
+    #[test]
+    fn impact_score_weighs_large_connected_dependents_higher() {
+        // `constant` and `core` each have exactly one direct dependent, but
+        // `core`'s dependent is a big class that three other functions use.
+        let entities = [
+            ("constant", 1, 1),
+            ("tiny_user", 2, 2),
+            ("core", 3, 3),
+            ("Service", 10, 59),
+            ("caller_a", 60, 62),
+            ("caller_b", 63, 65),
+            ("caller_c", 66, 68),
+        ];
+        let id = |name: &str| format!("lib.py::function::{name}");
+        let entity_map: EntityInfoMap = entities
+            .iter()
+            .map(|(name, start_line, end_line)| {
+                let info = EntityInfo {
+                    id: id(name),
+                    name: name.to_string(),
+                    entity_type: "function".to_string(),
+                    file_path: "lib.py".to_string(),
+                    parent_id: None,
+                    start_line: *start_line,
+                    end_line: *end_line,
+                    is_test: false,
+                    is_private: false,
+                };
+                (id(name), info)
+            })
+            .collect();
+        let edges = [
+            ("tiny_user", "constant"),
+            ("Service", "core"),
+            ("caller_a", "Service"),
+            ("caller_b", "Service"),
+            ("caller_c", "Service"),
+        ]
+        .iter()
+        .map(|(from, to)| EntityRef {
+            from_entity: id(from),
+            to_entity: id(to),
+            ref_type: RefType::Calls,
+            confidence: 1.0,
+        })
+        .collect();
+        let graph = EntityGraph::from_parts(entity_map, edges);
+
+        let constant = graph.impact_score(&id("constant"), 0);
+        let core = graph.impact_score(&id("core"), 0);
+        assert_eq!(graph.impact_analysis_bounded(&id("constant"), 1).len(), 1);
+        assert_eq!(graph.impact_analysis_bounded(&id("core"), 1).len(), 1);
+        assert_eq!(constant.score, 1.0);
+        assert!(core.score > constant.score * 100.0, "{core:?}");
+        assert_eq!(core.heaviest[0].entity_id, id("Service"));
+        assert_eq!(core.heaviest[0].weight, 200.0);
+    }
+
+    #[test]
+    fn impact_analysis_with_paths_reports_shortest_reference_chain() {
+        // b calls a, c calls b, d calls both c and b: d is two hops away via b.
+        let names = ["a", "b", "c", "d"];
+        let id = |name: &str| format!("lib.py::function::{name}");
+        let entity_map: EntityInfoMap = names
+            .iter()
+            .enumerate()
+            .map(|(line, name)| {
+                let info = EntityInfo {
+                    id: id(name),
+                    name: name.to_string(),
+                    entity_type: "function".to_string(),
+                    file_path: "lib.py".to_string(),
+                    parent_id: None,
+                    start_line: line + 1,
+                    end_line: line + 1,
+                    is_test: false,
+                    is_private: false,
+                };
+                (id(name), info)
+            })
+            .collect();
+        let edges = [("b", "a"), ("c", "b"), ("d", "c"), ("d", "b")]
+            .into_iter()
+            .map(|(from, to)| EntityRef {
+                from_entity: id(from),
+                to_entity: id(to),
+                ref_type: RefType::Calls,
+                confidence: 1.0,
+            })
+            .collect();
+        let graph = EntityGraph::from_parts(entity_map, edges);
+
+        let impact: Vec<_> = graph
+            .impact_analysis_with_paths(&id("a"))
+            .into_iter()
+            .map(|(entity, distance, path)| (entity.name.clone(), distance, path))
+            .collect();
+        assert_eq!(
+            impact,
+            vec![
+                ("b".to_string(), 1, vec![id("a"), id("b")]),
+                ("c".to_string(), 2, vec![id("a"), id("b"), id("c")]),
+                ("d".to_string(), 2, vec![id("a"), id("b"), id("d")]),
+            ]
+        );
+        let bounded = graph.impact_analysis_with_paths_bounded(&id("a"), 1);
+        assert_eq!(bounded.len(), 1);
+        assert!(graph.impact_analysis_with_paths("missing").is_empty());
+    }
 }
@@ -0,0 +1,188 @@
+//! `sem:ignore` directives: a comment that opts an entity out of sem's
+//! reporting. `sem:ignore` hides the entity from diffs and drops its graph
+//! edges; `sem:ignore-impact` only drops the edges. Detection is textual, so
+//! it works with any language's comment syntax.
+
+use std::collections::HashMap;
+
+use crate::model::entity::{ancestors, SemanticEntity};
+
+/// Entity metadata key holding the directive (`"all"` or `"impact"`).
+pub const IGNORE_METADATA_KEY: &str = "sem.ignore";
+
+const TOKEN: &str = "sem:ignore";
+
+/// How deep into an entity a directive may sit and still annotate it, e.g. a
+/// comment on the first line of a function body.
+const HEADER_LINES: usize = 3;
+
+const COMMENT_MARKERS: &[&str] = &["//", "#", "/*", "*", "--", ";", "%", "<!--", "(*", "{-"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IgnoreDirective {
+    /// `sem:ignore-impact`: keep reporting changes, drop graph edges.
+    Impact,
+    /// `sem:ignore`: drop the entity from diffs and the graph.
+    All,
+}
+
+impl IgnoreDirective {
+    /// The directive recorded on `entity` by [`attach_ignore_directives`].
+    pub fn of(entity: &SemanticEntity) -> Option<Self> {
+        match entity.metadata.as_ref()?.get(IGNORE_METADATA_KEY)?.as_str() {
+            "all" => Some(Self::All),
+            "impact" => Some(Self::Impact),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Impact => "impact",
+            Self::All => "all",
+        }
+    }
+}
+
+/// Record `sem:ignore` directives on the entities they annotate, under
+/// [`IGNORE_METADATA_KEY`]. A directive applies to the entity its comment
+/// block directly precedes (decorators and attributes in between are fine),
+/// or else to the innermost entity whose first few lines hold it. Nested
+/// entities inherit their parent's directive.
+pub fn attach_ignore_directives(source: &str, entities: &mut [SemanticEntity]) {
+    if entities.is_empty() || !source.contains(TOKEN) {
+        return;
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let mut direct: HashMap<usize, IgnoreDirective> = HashMap::new();
+    for (index, line) in lines.iter().enumerate() {
+        let Some(directive) = line_directive(line) else {
+            continue;
+        };
+        let line_no = index + 1;
+        let target_line = if is_preamble_line(line) {
+            (index + 1..lines.len())
+                .find(|&i| !is_preamble_line(lines[i]))
+                .map(|i| i + 1)
+        } else {
+            Some(line_no)
+        };
+
+        let preceded = target_line.and_then(|target| {
+            (0..entities.len())
+                .filter(|&i| entities[i].start_line == target)
+                .max_by_key(|&i| entities[i].end_line)
+        });
+        let enclosing = || {
+            (0..entities.len())
+                .filter(|&i| {
+                    let entity = &entities[i];
+                    entity.start_line <= line_no
+                        && line_no <= entity.end_line
+                        && line_no < entity.start_line + HEADER_LINES
+                })
+                .max_by_key(|&i| {
+                    (
+                        entities[i].start_line,
+                        std::cmp::Reverse(entities[i].end_line),
+                    )
+                })
+        };
+        if let Some(target) = preceded.or_else(enclosing) {
+            let slot = direct.entry(target).or_insert(directive);
+            *slot = (*slot).max(directive);
+        }
+    }
+    if direct.is_empty() {
+        return;
+    }
+
+    let index_by_id: HashMap<&str, usize> = entities
+        .iter()
+        .enumerate()
+        .map(|(index, entity)| (entity.id.as_str(), index))
+        .collect();
+    let by_id: HashMap<&str, &SemanticEntity> =
+        entities.iter().map(|e| (e.id.as_str(), e)).collect();
+    let resolved: Vec<Option<IgnoreDirective>> = entities
+        .iter()
+        .enumerate()
+        .map(|(index, entity)| {
+            let enclosing = ancestors(entity, &by_id)
+                .skip(1)
+                .filter_map(|at| direct.get(&index_by_id[at.id.as_str()]).copied())
+                .max();
+            direct.get(&index).copied().max(enclosing)
+        })
+        .collect();
+
+    for (entity, directive) in entities.iter_mut().zip(resolved) {
+        if let Some(directive) = directive {
+            entity.metadata.get_or_insert_with(HashMap::new).insert(
+                IGNORE_METADATA_KEY.to_string(),
+                directive.as_str().to_string(),
+            );
+        }
+    }
+}
+
+/// The strongest directive a comment on `line` carries.
+fn line_directive(line: &str) -> Option<IgnoreDirective> {
+    let mut strongest = None;
+    for (at, _) in line.match_indices(TOKEN) {
+        if !COMMENT_MARKERS
+            .iter()
+            .any(|marker| line[..at].contains(marker))
+        {
+            continue;
+        }
+        let rest = &line[at + TOKEN.len()..];
+        let directive = match rest.strip_prefix("-impact") {
+            Some(after) if !continues_word(after) => IgnoreDirective::Impact,
+            _ if !continues_word(rest) => IgnoreDirective::All,
+            _ => continue,
+        };
+        strongest = strongest.max(Some(directive));
+    }
+    strongest
+}
+
+fn continues_word(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Comment, decorator and attribute lines that may sit between a directive
+/// and the entity it annotates.
+fn is_preamble_line(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('@')
+        || COMMENT_MARKERS
+            .iter()
+            .any(|marker| line.starts_with(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_directive_requires_a_comment_and_a_whole_token() {
+        assert_eq!(line_directive("// sem:ignore"), Some(IgnoreDirective::All));
+        assert_eq!(
+            line_directive("# sem:ignore-impact"),
+            Some(IgnoreDirective::Impact)
+        );
+        assert_eq!(
+            line_directive("fn gen() { /* sem:ignore */"),
+            Some(IgnoreDirective::All)
+        );
+        assert_eq!(
+            line_directive("-- sem:ignore: generated"),
+            Some(IgnoreDirective::All)
+        );
+        assert_eq!(line_directive("let s = \"sem:ignore\";"), None);
+        assert_eq!(line_directive("// sem:ignored"), None);
+        assert_eq!(line_directive("// sem:ignore-impacts"), None);
+    }
+}
@@ -392,7 +392,7 @@ fn module_candidates(module_path: &str, extensions: &[&str]) -> Vec<String> {
     candidates
 }
 
-fn normalize_repo_path(path: PathBuf) -> Option<String> {
+pub(crate) fn normalize_repo_path(path: PathBuf) -> Option<String> {
     let mut parts: Vec<String> = Vec::new();
     for component in path.components() {
         match component {
@@ -448,6 +448,8 @@ mod tests {
             parent_id: None,
             start_line: 1,
             end_line: 1,
+            is_test: false,
+            is_private: false,
         }
     }
 
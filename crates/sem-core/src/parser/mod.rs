@@ -3,14 +3,18 @@ pub mod differ;
 pub mod graph;
 #[cfg(feature = "git")]
 pub mod hotspot;
+pub mod ignore;
 mod import_resolution;
 pub mod plugin;
 pub mod plugins;
 pub mod registry;
 pub mod test_detect;
+pub mod visibility;
 pub use import_resolution::{
     js_ts_has_default_re_export_from_content, js_ts_import_source_files_from_content,
     js_ts_import_source_files_from_filesystem,
     js_ts_import_source_files_from_filesystem_with_unscoped, js_ts_import_source_files_from_set,
 };
 pub mod scope_resolve;
+pub mod signature;
+pub mod stopwords;
@@ -3,6 +3,11 @@ use crate::model::entity::SemanticEntity;
 pub trait SemanticParserPlugin: Send + Sync {
     fn id(&self) -> &str;
     fn extensions(&self) -> &[&str];
+    /// Whole file names (matched case-insensitively) for formats that have
+    /// no extension, like `Dockerfile`.
+    fn filenames(&self) -> &[&str] {
+        &[]
+    }
     fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity>;
     fn extract_entities_brief(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
         let mut entities = self.extract_entities(content, file_path);
@@ -21,6 +26,12 @@ pub trait SemanticParserPlugin: Send + Sync {
     fn structural_hash_content(&self, _content: &str, _file_path: &str) -> Option<String> {
         None
     }
+    /// Why this content can't be parsed, if the plugin can tell (a syntax
+    /// error, a grammar missing from this build). Only asked about files
+    /// that produced no entities.
+    fn parse_error(&self, _content: &str, _file_path: &str) -> Option<String> {
+        None
+    }
     fn compute_similarity(&self, a: &SemanticEntity, b: &SemanticEntity) -> f64 {
         crate::model::identity::default_similarity(a, b)
     }
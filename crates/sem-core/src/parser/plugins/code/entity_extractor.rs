@@ -1,3 +1,4 @@
+use regex::Regex;
 use tree_sitter::{Node, Tree};
 
 use super::languages::LanguageConfig;
@@ -5,8 +6,10 @@ use crate::model::entity::{
     build_entity_id, build_entity_id_disambiguated, build_entity_id_disambiguated_with_ordinal,
     SemanticEntity,
 };
+use crate::parser::ignore::attach_ignore_directives;
 use crate::utils::hash::{content_hash, structural_hash, structural_hash_excluding_range};
 use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
 
 pub fn extract_entities(
     tree: &Tree,
@@ -35,10 +38,33 @@ pub fn extract_entities(
 
     if config.id == "go" {
         attach_go_package_metadata(tree.root_node(), source_code.as_bytes(), &mut entities);
+        attach_go_receiver_metadata(tree.root_node(), source_code.as_bytes(), &mut entities);
+    }
+
+    if matches!(config.id, "c" | "cpp") {
+        attach_preprocessor_guard_metadata(tree.root_node(), source_code.as_bytes(), &mut entities);
+    }
+
+    if matches!(
+        config.id,
+        "typescript" | "tsx" | "javascript" | "java" | "python"
+    ) {
+        attach_heritage_metadata(tree.root_node(), source_code.as_bytes(), &mut entities);
+    }
+
+    if matches!(config.id, "typescript" | "tsx" | "javascript") {
+        attach_export_clause_visibility(tree.root_node(), source_code.as_bytes(), &mut entities);
+    }
+
+    // Last of the span-keyed passes: it widens decorated entities' spans.
+    if matches!(config.id, "typescript" | "tsx" | "javascript" | "python") {
+        attach_decorator_metadata(tree.root_node(), source_code.as_bytes(), &mut entities);
     }
 
     disambiguate_colliding_entity_ids(&mut entities);
 
+    attach_ignore_directives(source_code, &mut entities);
+
     entities
 }
 
@@ -250,6 +276,426 @@ fn extract_go_package_name(root: Node, source: &[u8]) -> Option<String> {
     None
 }
 
+/// Record TS/JS decorators (`@Component(...)`, `@Get('/users')`) on the
+/// entity they annotate as newline-joined `decorators` metadata. Decorators on
+/// methods and exported classes sit outside the entity's own span, so they are
+/// also folded into the hashes; otherwise a route path or selector change would
+/// not register as a modification.
+fn attach_decorator_metadata(root: Node, source: &[u8], entities: &mut [SemanticEntity]) {
+    let mut by_span: HashMap<(usize, usize), usize> = entities
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entity)| Some(((entity.start_byte?, entity.end_byte?), index)))
+        .collect();
+    if by_span.is_empty() {
+        return;
+    }
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(index) = by_span.remove(&(node.start_byte(), node.end_byte())) {
+            let decorator_nodes = collect_decorators(node);
+            if let Some(first) = decorator_nodes.first() {
+                let decorators: Vec<String> = decorator_nodes
+                    .iter()
+                    .map(|decorator| node_text(*decorator, source).trim().to_string())
+                    .collect();
+                let entity = &mut entities[index];
+                let joined = decorators.join("\n");
+                // Decorators written before the declaration join its span, so
+                // a decorator-only edit changes both content and hashes.
+                if first.start_byte() < node.start_byte() {
+                    entity.start_byte = Some(first.start_byte());
+                    entity.start_line = first.start_position().row + 1;
+                    let span = &source[first.start_byte()..node.end_byte()];
+                    entity.content = std::str::from_utf8(span).unwrap_or("").to_string();
+                    entity.content_hash = content_hash(&entity.content);
+                    entity.structural_hash = entity
+                        .structural_hash
+                        .as_ref()
+                        .map(|hash| content_hash(&format!("{joined}\n{hash}")));
+                }
+                let metadata = entity.metadata.get_or_insert_with(HashMap::new);
+                if let Some(route) = decorators.iter().find_map(|d| route_from_decorator(d)) {
+                    metadata.insert("route".to_string(), route);
+                }
+                metadata.insert("decorators".to_string(), joined);
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+}
+
+/// Module-level declarations exported by name later in the file
+/// (`export { util }`, `export default util`) are public even though the
+/// declaration itself carries no `export`.
+fn attach_export_clause_visibility(root: Node, source: &[u8], entities: &mut [SemanticEntity]) {
+    let mut exported: HashSet<&str> = HashSet::new();
+    let mut cursor = root.walk();
+    for statement in root.named_children(&mut cursor) {
+        if statement.kind() != "export_statement"
+            || statement.child_by_field_name("source").is_some()
+        {
+            continue;
+        }
+        if let Some(value) = statement.child_by_field_name("value") {
+            if value.kind() == "identifier" {
+                exported.insert(node_text(value, source));
+            }
+        }
+        let mut clause_cursor = statement.walk();
+        for clause in statement.named_children(&mut clause_cursor) {
+            if clause.kind() != "export_clause" {
+                continue;
+            }
+            let mut specifier_cursor = clause.walk();
+            for specifier in clause.named_children(&mut specifier_cursor) {
+                if let Some(name) = specifier.child_by_field_name("name") {
+                    exported.insert(node_text(name, source));
+                }
+            }
+        }
+    }
+    if exported.is_empty() {
+        return;
+    }
+
+    for entity in entities {
+        if entity.parent_id.is_none() && exported.contains(entity.name.as_str()) {
+            entity.metadata.get_or_insert_with(HashMap::new).insert(
+                crate::parser::visibility::VISIBILITY_KEY.to_string(),
+                "public".to_string(),
+            );
+        }
+    }
+}
+
+/// Record the base types named in a class or interface header as comma-joined
+/// `extends` and `implements` metadata, so the graph can add explicit edges
+/// for them instead of inferring a type reference from the body text.
+fn attach_heritage_metadata(root: Node, source: &[u8], entities: &mut [SemanticEntity]) {
+    let mut by_span: HashMap<(usize, usize), usize> = entities
+        .iter()
+        .enumerate()
+        .filter(|(_, entity)| matches!(entity.entity_type.as_str(), "class" | "interface"))
+        .filter_map(|(index, entity)| Some(((entity.start_byte?, entity.end_byte?), index)))
+        .collect();
+    if by_span.is_empty() {
+        return;
+    }
+
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(index) = by_span.remove(&(node.start_byte(), node.end_byte())) {
+            let (extends, implements) = heritage_clauses(node, source);
+            for (key, names) in [("extends", extends), ("implements", implements)] {
+                if !names.is_empty() {
+                    entities[index]
+                        .metadata
+                        .get_or_insert_with(HashMap::new)
+                        .insert(key.to_string(), names.join(","));
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+}
+
+/// Base type names from a class or interface declaration: TS/JS
+/// `class_heritage`, Java `superclass`/`super_interfaces`/`extends_interfaces`,
+/// and Python's superclass list. Interfaces extending interfaces count as
+/// `extends`. Bases that aren't plain type names (`extends mixin(Base)`,
+/// `metaclass=...`) are skipped.
+fn heritage_clauses(node: Node, source: &[u8]) -> (Vec<String>, Vec<String>) {
+    let node = match node.kind() {
+        "export_statement" => node.child_by_field_name("declaration"),
+        "decorated_definition" => node.child_by_field_name("definition"),
+        _ => Some(node),
+    };
+    let mut extends = Vec::new();
+    let mut implements = Vec::new();
+    let Some(node) = node else {
+        return (extends, implements);
+    };
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let (target, types): (&mut Vec<String>, Vec<Node>) = match child.kind() {
+            "class_heritage" => {
+                let mut heritage_cursor = child.walk();
+                for clause in child.named_children(&mut heritage_cursor) {
+                    match clause.kind() {
+                        "extends_clause" => {
+                            extends.extend(
+                                clause
+                                    .child_by_field_name("value")
+                                    .and_then(|value| heritage_type_name(value, source)),
+                            );
+                        }
+                        "implements_clause" => {
+                            let mut clause_cursor = clause.walk();
+                            implements.extend(
+                                clause
+                                    .named_children(&mut clause_cursor)
+                                    .filter_map(|ty| heritage_type_name(ty, source)),
+                            );
+                        }
+                        // tree-sitter-javascript puts the base expression
+                        // directly under class_heritage.
+                        _ => extends.extend(heritage_type_name(clause, source)),
+                    }
+                }
+                continue;
+            }
+            "superclass" | "extends_type_clause" => (&mut extends, named_children(child)),
+            "extends_interfaces" => (&mut extends, type_list_children(child)),
+            "super_interfaces" => (&mut implements, type_list_children(child)),
+            "argument_list" if node.kind() == "class_definition" => {
+                (&mut extends, named_children(child))
+            }
+            _ => continue,
+        };
+        target.extend(
+            types
+                .into_iter()
+                .filter_map(|ty| heritage_type_name(ty, source)),
+        );
+    }
+    (extends, implements)
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+fn type_list_children(node: Node) -> Vec<Node> {
+    named_children(node)
+        .into_iter()
+        .flat_map(|child| {
+            if child.kind() == "type_list" {
+                named_children(child)
+            } else {
+                vec![child]
+            }
+        })
+        .collect()
+}
+
+/// The bare name of a base type: `ns.Base<T>` and `p.Base` give `Base`.
+fn heritage_type_name(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "identifier" | "type_identifier" => Some(node_text(node, source).to_string()),
+        "member_expression" => heritage_type_name(node.child_by_field_name("property")?, source),
+        "property_identifier" => Some(node_text(node, source).to_string()),
+        "attribute" => heritage_type_name(node.child_by_field_name("attribute")?, source),
+        "nested_type_identifier" => heritage_type_name(node.child_by_field_name("name")?, source),
+        "scoped_type_identifier" => {
+            heritage_type_name(named_children(node).into_iter().last()?, source)
+        }
+        "generic_type" => {
+            let name = node
+                .child_by_field_name("name")
+                .or_else(|| node.named_child(0))?;
+            heritage_type_name(name, source)
+        }
+        _ => None,
+    }
+}
+
+/// The route a web-framework decorator binds, e.g. `GET /items/{id}` for
+/// FastAPI's `@router.get('/items/{id}')`, or `/users` for Flask's
+/// `@app.route("/users")` (`POST /users` with `methods=["POST"]`).
+fn route_from_decorator(decorator: &str) -> Option<String> {
+    static ROUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r#"^@[\w.]+\.(route|get|post|put|patch|delete|head|options|websocket)\(\s*['"]([^'"]*)['"]"#,
+        )
+        .unwrap()
+    });
+    static METHODS_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"methods\s*=\s*[\[(]\s*['"](\w+)['"]"#).unwrap());
+
+    let cap = ROUTE_RE.captures(decorator)?;
+    let path = cap.get(2).unwrap().as_str();
+    let method = match cap.get(1).unwrap().as_str() {
+        "route" => METHODS_RE
+            .captures(decorator)
+            .map(|m| m.get(1).unwrap().as_str().to_ascii_uppercase()),
+        verb => Some(verb.to_ascii_uppercase()),
+    };
+    Some(match method {
+        Some(method) => format!("{method} {path}"),
+        None => path.to_string(),
+    })
+}
+
+/// Decorators applied to `node`, in source order: the run of `decorator`
+/// siblings directly before it (class members, and classes inside an
+/// `export` statement) followed by its own `decorator` children.
+fn collect_decorators(node: Node) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut preceding = Vec::new();
+    let mut sibling = node.prev_named_sibling();
+    while let Some(prev) = sibling {
+        match prev.kind() {
+            "decorator" => preceding.push(prev),
+            "comment" => {}
+            _ => break,
+        }
+        sibling = prev.prev_named_sibling();
+    }
+    nodes.extend(preceding.into_iter().rev());
+
+    let mut cursor = node.walk();
+    nodes.extend(
+        node.named_children(&mut cursor)
+            .filter(|child| child.kind() == "decorator"),
+    );
+
+    nodes
+}
+
+/// A conditional-compilation branch: the rows it spans and the condition
+/// under which its contents are compiled.
+struct PreprocessorGuard {
+    start_row: usize,
+    end_row: usize,
+    condition: String,
+}
+
+/// Record the `#if`/`#ifdef` conditions an entity is nested under as
+/// `c.preprocessor_guard`, e.g. `defined(DEBUG) && !(FEATURE_X > 1)`.
+/// Include guards are skipped since they wrap the whole header.
+fn attach_preprocessor_guard_metadata(root: Node, source: &[u8], entities: &mut [SemanticEntity]) {
+    let mut guards = Vec::new();
+    collect_preprocessor_guards(root, source, &mut guards);
+    if guards.is_empty() {
+        return;
+    }
+
+    for entity in entities {
+        let start_row = entity.start_line.saturating_sub(1);
+        let end_row = entity.end_line.saturating_sub(1);
+        // Guards are collected outermost-first, so the joined condition reads
+        // from the outer branch inwards.
+        let conditions: Vec<&str> = guards
+            .iter()
+            .filter(|guard| guard.start_row < start_row && end_row <= guard.end_row)
+            .map(|guard| guard.condition.as_str())
+            .collect();
+        if conditions.is_empty() {
+            continue;
+        }
+        entity
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert("c.preprocessor_guard".to_string(), conditions.join(" && "));
+    }
+}
+
+fn collect_preprocessor_guards(node: Node, source: &[u8], guards: &mut Vec<PreprocessorGuard>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if matches!(child.kind(), "preproc_if" | "preproc_ifdef")
+            && !is_include_guard(child, source)
+        {
+            collect_preprocessor_branches(child, source, Vec::new(), guards);
+        }
+        collect_preprocessor_guards(child, source, guards);
+    }
+}
+
+/// Walk an `#if`/`#elif`/`#else` chain. Each branch only applies when every
+/// earlier branch in the chain was false, so prior conditions are negated.
+fn collect_preprocessor_branches(
+    branch: Node,
+    source: &[u8],
+    mut prior: Vec<String>,
+    guards: &mut Vec<PreprocessorGuard>,
+) {
+    let condition = preprocessor_branch_condition(branch, source);
+    let alternative = branch.child_by_field_name("alternative");
+    let end_row = alternative
+        .map(|alt| alt.start_position().row)
+        .unwrap_or_else(|| branch.end_position().row);
+
+    let mut terms: Vec<String> = prior
+        .iter()
+        .map(|c| negate_preprocessor_condition(c))
+        .collect();
+    terms.extend(condition.clone());
+    if !terms.is_empty() {
+        guards.push(PreprocessorGuard {
+            start_row: branch.start_position().row,
+            end_row,
+            condition: terms.join(" && "),
+        });
+    }
+
+    if let Some(alternative) = alternative {
+        prior.extend(condition);
+        collect_preprocessor_branches(alternative, source, prior, guards);
+    }
+}
+
+fn preprocessor_branch_condition(branch: Node, source: &[u8]) -> Option<String> {
+    match branch.kind() {
+        "preproc_ifdef" | "preproc_elifdef" => {
+            let name = node_text(branch.child_by_field_name("name")?, source);
+            let directive = node_text(branch.child(0)?, source);
+            if directive.ends_with("ndef") {
+                Some(format!("!defined({name})"))
+            } else {
+                Some(format!("defined({name})"))
+            }
+        }
+        "preproc_if" | "preproc_elif" => {
+            let condition = branch.child_by_field_name("condition")?;
+            Some(node_text(condition, source).trim().to_string())
+        }
+        _ => None,
+    }
+}
+
+fn negate_preprocessor_condition(condition: &str) -> String {
+    if let Some(rest) = condition.strip_prefix("!defined(") {
+        format!("defined({rest}")
+    } else if condition.starts_with("defined(") && condition.ends_with(')') {
+        format!("!{condition}")
+    } else {
+        format!("!({condition})")
+    }
+}
+
+/// `#ifndef X` immediately followed by `#define X`, with no `#else` branch.
+fn is_include_guard(node: Node, source: &[u8]) -> bool {
+    if node.kind() != "preproc_ifdef" || node.child_by_field_name("alternative").is_some() {
+        return false;
+    }
+    let Some(name) = node.child_by_field_name("name") else {
+        return false;
+    };
+    let Some(directive) = node.child(0) else {
+        return false;
+    };
+    if node_text(directive, source) != "#ifndef" {
+        return false;
+    }
+    let mut cursor = node.walk();
+    let first_body_item = node
+        .named_children(&mut cursor)
+        .find(|child| child.id() != name.id() && child.kind() != "comment");
+    first_body_item.is_some_and(|item| {
+        item.kind() == "preproc_def"
+            && item
+                .child_by_field_name("name")
+                .is_some_and(|defined| node_text(defined, source) == node_text(name, source))
+    })
+}
+
 fn visit_node(
     root: Node,
     file_path: &str,
@@ -294,7 +740,10 @@ fn visit_node(
                     end_line: node.end_position().row + 1,
                     start_byte: Some(node.start_byte()),
                     end_byte: Some(node.end_byte()),
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 };
 
                 let entity_id = entity.id.clone();
@@ -354,7 +803,10 @@ fn visit_node(
                     end_line: value.end_position().row + 1,
                     start_byte: Some(key.start_byte()),
                     end_byte: Some(value.end_byte()),
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 };
                 entities.push(entity);
             }
@@ -383,7 +835,10 @@ fn visit_node(
                     end_line: node.end_position().row + 1,
                     start_byte: Some(node.start_byte()),
                     end_byte: Some(node.end_byte()),
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 };
                 entities.push(entity);
             }
@@ -422,7 +877,10 @@ fn visit_node(
                             end_line: binding.end_position().row + 1,
                             start_byte: Some(binding.start_byte()),
                             end_byte: Some(binding.end_byte()),
+                            name_start: None,
+                            name_end: None,
                             metadata: None,
+                            stable_id: None,
                         };
                         entities.push(entity);
                     }
@@ -503,6 +961,8 @@ fn visit_node(
                                 map_js_ts_declarator_entity_type(node, *declarator, config);
                             if !skip_declaration || entity_type == "function" {
                                 let name = node_text(name_node, source).to_string();
+                                let metadata =
+                                    with_visibility_metadata(None, node, &name, source, config);
                                 let content = node_text(*declarator, source).to_string();
                                 let struct_hash = compute_structural_hash(*declarator, source);
                                 let entity = SemanticEntity {
@@ -518,7 +978,10 @@ fn visit_node(
                                     end_line: declarator.end_position().row + 1,
                                     start_byte: Some(declarator.start_byte()),
                                     end_byte: Some(declarator.end_byte()),
-                                    metadata: None,
+                                    name_start: Some(name_node.start_byte()),
+                                    name_end: Some(name_node.end_byte()),
+                                    metadata,
+                                    stable_id: None,
                                 };
 
                                 let entity_id = entity.id.clone();
@@ -577,7 +1040,10 @@ fn visit_node(
                             end_line: binding.end_line,
                             start_byte: None,
                             end_byte: None,
+                            name_start: None,
+                            name_end: None,
                             metadata: None,
+                            stable_id: None,
                         });
                     }
                     continue;
@@ -632,7 +1098,10 @@ fn visit_node(
                                 end_line: spec.end_position().row + 1,
                                 start_byte: Some(spec.start_byte()),
                                 end_byte: Some(spec.end_byte()),
+                                name_start: Some(name_node.start_byte()),
+                                name_end: Some(name_node.end_byte()),
                                 metadata: None,
+                                stable_id: None,
                             };
                             entities.push(entity);
                         }
@@ -665,7 +1134,10 @@ fn visit_node(
                     end_line: node.end_position().row + 1,
                     start_byte: Some(node.start_byte()),
                     end_byte: Some(node.end_byte()),
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 };
 
                 let entity_id = entity.id.clone();
@@ -687,6 +1159,50 @@ fn visit_node(
             }
         }
 
+        // Lua: `M.foo = function() ... end` and `local f = function() ... end`
+        // are functions, named by the assigned field with the table recorded
+        // in metadata.
+        if node_type == "assignment_statement" && config.id == "lua" {
+            if let Some(function) = lua_assigned_function(node) {
+                if let Some(name) = extract_name(node, source) {
+                    let content = node_text(node, source).to_string();
+                    let struct_hash = compute_structural_hash(node, source);
+                    let metadata = lua_assignment_table(node, source)
+                        .map(|table| HashMap::from([("lua.table".to_string(), table)]));
+                    let entity = SemanticEntity {
+                        id: build_entity_id(file_path, "function", &name, parent_id),
+                        file_path: file_path.to_string(),
+                        entity_type: "function".to_string(),
+                        name,
+                        parent_id: parent_id.map(String::from),
+                        content_hash: content_hash(&content),
+                        structural_hash: Some(struct_hash),
+                        content,
+                        start_line: node.start_position().row + 1,
+                        end_line: node.end_position().row + 1,
+                        start_byte: Some(node.start_byte()),
+                        end_byte: Some(node.end_byte()),
+                        name_start: None,
+                        name_end: None,
+                        metadata,
+                        stable_id: None,
+                    };
+
+                    let entity_id = entity.id.clone();
+                    entities.push(entity);
+
+                    if let Some(body) = function.child_by_field_name("body") {
+                        let mut cursor = body.walk();
+                        let nested: Vec<_> = body.named_children(&mut cursor).collect();
+                        for n in nested.into_iter().rev() {
+                            worklist.push((n, Some(entity_id.clone()), sup_owned.clone()));
+                        }
+                    }
+                    continue;
+                }
+            }
+        }
+
         if should_skip_ts_overload_signature(
             node,
             config,
@@ -714,7 +1230,10 @@ fn visit_node(
                 end_line: node.end_position().row + 1,
                 start_byte: Some(node.start_byte()),
                 end_byte: Some(node.end_byte()),
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             };
 
             let entity_id = entity.id.clone();
@@ -723,6 +1242,12 @@ fn visit_node(
             continue;
         }
 
+        // Later equations of a Haskell function belong to the entity opened
+        // by its first equation.
+        if config.id == "haskell" && is_haskell_continuation_equation(node, source) {
+            continue;
+        }
+
         if config.entity_node_types.contains(&node_type) {
             if let Some(name) = extract_name(node, source) {
                 let name = qualify_hcl_name(&name, node_type, parent_id, suppression_context);
@@ -748,9 +1273,16 @@ fn visit_node(
                     } else {
                         None
                     };
-                    let end_byte = body.map_or(node.end_byte(), |b| b.end_byte());
-                    let end_line =
-                        body.map_or(node.end_position().row + 1, |b| b.end_position().row + 1);
+                    // A Haskell function defined by several pattern-matching
+                    // equations is one entity spanning all of them.
+                    let equations = if config.id == "haskell" {
+                        haskell_following_equations(node, source)
+                    } else {
+                        Vec::new()
+                    };
+                    let last = body.or(equations.last().copied()).unwrap_or(node);
+                    let end_byte = last.end_byte();
+                    let end_line = last.end_position().row + 1;
 
                     // Extend start backward to include outer attributes (e.g. Rust
                     // #[derive(...)], #[cfg(...)], #[test]) so attribute changes
@@ -763,12 +1295,20 @@ fn visit_node(
                     let content = std::str::from_utf8(&source[start_byte..end_byte])
                         .unwrap_or("")
                         .to_string();
+                    let name_range = find_name_byte_range(node, source);
                     let struct_hash = match body {
                         Some(b) => {
                             let sig = compute_structural_hash(node, source);
                             let bod = structural_hash(b, source);
                             content_hash(&format!("{}{}", sig, bod))
                         }
+                        None if !equations.is_empty() => {
+                            let hashes: String = std::iter::once(node)
+                                .chain(equations.iter().copied())
+                                .map(|eq| compute_structural_hash(eq, source))
+                                .collect();
+                            content_hash(&hashes)
+                        }
                         None => compute_structural_hash(node, source),
                     };
 
@@ -785,7 +1325,18 @@ fn visit_node(
                         end_line,
                         start_byte: Some(start_byte),
                         end_byte: Some(end_byte),
-                        metadata: None,
+                        name_start: name_range.map(|(start, _)| start),
+                        name_end: name_range.map(|(_, end)| end),
+                        metadata: with_visibility_metadata(
+                            c_prototype_metadata(node, entity_type, config)
+                                .or_else(|| csharp_metadata(node, source, config))
+                                .or_else(|| julia_metadata(node, source, config)),
+                            node,
+                            &name,
+                            source,
+                            config,
+                        ),
+                        stable_id: None,
                     };
 
                     let entity_id = entity.id.clone();
@@ -793,8 +1344,11 @@ fn visit_node(
 
                     // Visit children for nested entities (methods inside classes, etc.)
                     let next_suppression = Some(node_type.to_string());
-                    let mut cursor = node.walk();
-                    for child in node.named_children(&mut cursor) {
+                    // `template <...> class Box { ... }` keeps its member list on the
+                    // wrapped class, not on the template_declaration itself.
+                    let body_owner = cpp_template_body(node);
+                    let mut cursor = body_owner.walk();
+                    for child in body_owner.named_children(&mut cursor) {
                         if config.container_node_types.contains(&child.kind()) {
                             let mut inner_cursor = child.walk();
                             let nested: Vec<_> = child.named_children(&mut inner_cursor).collect();
@@ -807,6 +1361,14 @@ fn visit_node(
                             }
                         }
                     }
+                    let is_julia_module = config.id == "julia" && node_type == "module_definition";
+                    if is_julia_module || (config.id == "objc" && is_objc_container(node)) {
+                        let mut member_cursor = node.walk();
+                        let members: Vec<_> = node.named_children(&mut member_cursor).collect();
+                        for member in members.into_iter().rev() {
+                            worklist.push((member, Some(entity_id.clone()), None));
+                        }
+                    }
 
                     // For JS/TS variable declarations and class fields, traverse
                     // initializers that can contain nested entity declarations.
@@ -927,7 +1489,10 @@ fn emit_js_ts_re_export_entities(
                     end_line: child.end_position().row + 1,
                     start_byte: Some(child.start_byte()),
                     end_byte: Some(child.end_byte()),
+                    name_start: None,
+                    name_end: None,
                     metadata: Some(metadata),
+                    stable_id: None,
                 });
                 emitted = true;
             } else {
@@ -1057,7 +1622,10 @@ fn recover_swift_conditional_compilation_containers(
             end_line: container.end_line,
             start_byte: Some(container.start_byte),
             end_byte: Some(container.end_byte),
+            name_start: Some(container.name_start_byte),
+            name_end: Some(container.name_end_byte),
             metadata: None,
+            stable_id: None,
         });
     }
 
@@ -1521,6 +2089,52 @@ fn sibling_function_body(node: Node) -> Option<Node> {
     }
 }
 
+/// The equations after a Haskell `function` node that define the same
+/// function, e.g. the second line of `fact 0 = 1` / `fact n = n * fact (n - 1)`.
+/// Comments between equations don't break the run.
+fn haskell_following_equations<'a>(node: Node<'a>, source: &[u8]) -> Vec<Node<'a>> {
+    let mut equations = Vec::new();
+    let Some(name) = haskell_equation_name(node, source) else {
+        return equations;
+    };
+    let mut current = node;
+    while let Some(next) = next_non_comment_sibling(current) {
+        if haskell_equation_name(next, source) != Some(name) {
+            break;
+        }
+        equations.push(next);
+        current = next;
+    }
+    equations
+}
+
+fn is_haskell_continuation_equation(node: Node, source: &[u8]) -> bool {
+    let Some(name) = haskell_equation_name(node, source) else {
+        return false;
+    };
+    let mut prev = node.prev_named_sibling();
+    while let Some(p) = prev.filter(|p| p.kind() == "comment" || p.kind() == "haddock") {
+        prev = p.prev_named_sibling();
+    }
+    prev.is_some_and(|p| haskell_equation_name(p, source) == Some(name))
+}
+
+fn haskell_equation_name<'a>(node: Node, source: &'a [u8]) -> Option<&'a str> {
+    if node.kind() != "function" {
+        return None;
+    }
+    node.child_by_field_name("name")
+        .map(|name| node_text(name, source))
+}
+
+fn next_non_comment_sibling(node: Node) -> Option<Node> {
+    let mut next = node.next_named_sibling();
+    while let Some(n) = next.filter(|n| n.kind() == "comment" || n.kind() == "haddock") {
+        next = n.next_named_sibling();
+    }
+    next
+}
+
 /// Compute the structural hash for an entity, excluding the name token so that
 /// renames of otherwise identical entities produce the same hash.
 fn compute_structural_hash(node: Node, source: &[u8]) -> String {
@@ -1548,6 +2162,17 @@ fn find_name_byte_range(node: Node, _source: &[u8]) -> Option<(usize, usize)> {
         return None;
     }
 
+    if node_type == "binary_operator" {
+        return r_assigned_function_target(node).map(|t| (t.start_byte(), t.end_byte()));
+    }
+
+    if node_type == "assignment" {
+        return julia_definition_name(node).map(|n| (n.start_byte(), n.end_byte()));
+    }
+    if let Some(name_node) = julia_definition_name(node) {
+        return Some((name_node.start_byte(), name_node.end_byte()));
+    }
+
     // Try 'name' field first (works for most languages)
     if let Some(name_node) = node.child_by_field_name("name") {
         return Some((name_node.start_byte(), name_node.end_byte()));
@@ -1864,6 +2489,47 @@ fn extract_name(node: Node, source: &[u8]) -> Option<String> {
         }
     }
 
+    // Objective-C: classes and categories are named `Foo` / `Foo (Bar)`,
+    // methods by their selector.
+    if is_objc_container(node) {
+        return objc_container_name(node, source);
+    }
+    if let Some(selector) = objc_selector(node, source) {
+        return Some(selector);
+    }
+
+    // Lua function assignments: `M.foo = function` is named `foo`, the
+    // table prefix lives in metadata (see `lua_assignment_table`).
+    if node_type == "assignment_statement" {
+        let target = lua_assignment_target(node)?;
+        return match target.kind() {
+            "identifier" => Some(node_text(target, source).to_string()),
+            "dot_index_expression" => target
+                .child_by_field_name("field")
+                .map(|field| node_text(field, source).to_string()),
+            _ => None,
+        };
+    }
+
+    // R functions are assignments: `foo <- function(x)` is named `foo`. Only
+    // R lists `binary_operator` as an entity; any other operator, `x <- 1`
+    // or `z + 1`, has no name rather than falling back to its first operand.
+    if node_type == "binary_operator" {
+        return r_assigned_function_target(node).map(|t| r_symbol_name(node_text(t, source)));
+    }
+
+    // Julia functions and macros are named by the callee in their signature,
+    // structs by their type head: `function area(s::Square)`, `f(x) = x^2`,
+    // `struct Point{T} <: Shape`.
+    // Only Julia lists `assignment` as an entity; one that doesn't define a
+    // function has no name rather than falling back to its first identifier.
+    if node_type == "assignment" {
+        return julia_definition_name(node).map(|n| node_text(n, source).to_string());
+    }
+    if let Some(name_node) = julia_definition_name(node) {
+        return Some(node_text(name_node, source).to_string());
+    }
+
     // Try 'name' field first (works for most languages)
     if let Some(name_node) = node.child_by_field_name("name") {
         return Some(node_text(name_node, source).to_string());
@@ -2119,7 +2785,7 @@ fn extract_name(node: Node, source: &[u8]) -> Option<String> {
         return None;
     }
 
-    // For HCL blocks, combine block type with labels (e.g., resource.cloudflare_record.dns)
+    // For HCL blocks, combine block type with labels (e.g., module.vpc)
     if node_type == "block" {
         let mut parts = Vec::new();
         let mut cursor = node.walk();
@@ -2133,6 +2799,11 @@ fn extract_name(node: Node, source: &[u8]) -> Option<String> {
                 _ => break, // stop at body or other non-label nodes
             }
         }
+        // Terraform addresses resources by their two labels alone
+        // (aws_s3_bucket.logs); data sources, modules etc. keep the keyword.
+        if parts.len() == 3 && parts[0] == "resource" {
+            parts.remove(0);
+        }
         if !parts.is_empty() {
             return Some(parts.join("."));
         }
@@ -2312,6 +2983,232 @@ fn extract_declarator_name(mut node: Node, source: &[u8]) -> Option<String> {
     }
 }
 
+/// The single assigned variable of a Lua `assignment_statement`.
+fn lua_assignment_target(node: Node) -> Option<Node> {
+    let mut cursor = node.walk();
+    let variables = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "variable_list")?;
+    if variables.named_child_count() != 1 {
+        return None;
+    }
+    variables.named_child(0)
+}
+
+/// The `function ... end` value of a Lua `x = function() ... end` or
+/// `t.x = function() ... end` assignment.
+fn lua_assigned_function(node: Node) -> Option<Node> {
+    lua_assignment_target(node)?;
+    let mut cursor = node.walk();
+    let values = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "expression_list")?;
+    if values.named_child_count() != 1 {
+        return None;
+    }
+    values
+        .named_child(0)
+        .filter(|value| value.kind() == "function_definition")
+}
+
+/// `M` for `M.foo = function`, `a.b` for `a.b.foo = function`.
+fn lua_assignment_table(node: Node, source: &[u8]) -> Option<String> {
+    let target = lua_assignment_target(node)?;
+    if target.kind() != "dot_index_expression" {
+        return None;
+    }
+    let table = target.child_by_field_name("table")?;
+    Some(node_text(table, source).to_string())
+}
+
+/// The target of an R function assignment: `foo` in `foo <- function(x)`,
+/// `foo = function(x)`, `foo <<- function(x)` or `function(x) x -> foo`.
+/// Other assignments and operators aren't entities.
+fn r_assigned_function_target(node: Node) -> Option<Node> {
+    if node.kind() != "binary_operator" {
+        return None;
+    }
+    let lhs = node.child_by_field_name("lhs")?;
+    let rhs = node.child_by_field_name("rhs")?;
+    let (target, value) = match node.child_by_field_name("operator")?.kind() {
+        "<-" | "<<-" | "=" => (lhs, rhs),
+        "->" | "->>" => (rhs, lhs),
+        _ => return None,
+    };
+    (value.kind() == "function_definition" && matches!(target.kind(), "identifier" | "string"))
+        .then_some(target)
+}
+
+/// An R name without the backticks or quotes that make it syntactic:
+/// `` `%+%` `` and `"%+%"` both name `%+%`.
+fn r_symbol_name(text: &str) -> String {
+    text.trim_matches(|c| matches!(c, '`' | '"' | '\''))
+        .to_string()
+}
+
+/// `Person` in `setClass("Person", ...)` or `R6Class(classname = "Person")`:
+/// the first string argument.
+fn r_first_string_argument(args: Node, source: &[u8]) -> Option<String> {
+    let mut cursor = args.walk();
+    let value = args
+        .named_children(&mut cursor)
+        .filter_map(|argument| argument.child_by_field_name("value"))
+        .find(|value| value.kind() == "string")?;
+    let name = r_symbol_name(node_text(value, source));
+    (!name.is_empty()).then_some(name)
+}
+
+/// The name node of a Julia definition: the callee of a function or macro
+/// signature (`area` in `function area(s::Square)`, `Base.show` in
+/// `Base.show(io, p) = ...`) or the type in a struct's head. `None` for other
+/// nodes, including assignments that don't define a function.
+fn julia_definition_name(node: Node) -> Option<Node> {
+    match node.kind() {
+        "function_definition" | "macro_definition" => {
+            let signature = first_child_of_kind(node, "signature")?;
+            match julia_signature_call(signature) {
+                Some(call) => call.named_child(0),
+                // `function area end` declares a function with no methods.
+                None => signature
+                    .named_child(0)
+                    .filter(|n| n.kind() == "identifier"),
+            }
+        }
+        "assignment" => julia_signature_call(node.named_child(0)?)?.named_child(0),
+        "struct_definition" => {
+            let mut head = first_child_of_kind(node, "type_head")?.named_child(0)?;
+            // `Point{T} <: Shape`: the leftmost identifier is the name.
+            while head.kind() != "identifier" {
+                head = head.named_child(0)?;
+            }
+            Some(head)
+        }
+        _ => None,
+    }
+}
+
+/// The `call_expression` in a Julia signature, under any `where` clause or
+/// return type annotation.
+fn julia_signature_call(mut node: Node) -> Option<Node> {
+    loop {
+        match node.kind() {
+            "call_expression" => return Some(node),
+            "signature" | "where_expression" | "typed_expression" => node = node.named_child(0)?,
+            _ => return None,
+        }
+    }
+}
+
+fn first_child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let child = node.named_children(&mut cursor).find(|c| c.kind() == kind);
+    child
+}
+
+/// Julia methods share their function's name, so each records the types it
+/// dispatches on under `julia.signature`, e.g. `(Square, Float64)`.
+/// Unannotated arguments are `Any`; keyword arguments don't take part in
+/// dispatch and are left out.
+fn julia_metadata(
+    node: Node,
+    source: &[u8],
+    config: &LanguageConfig,
+) -> Option<HashMap<String, String>> {
+    if config.id != "julia" {
+        return None;
+    }
+    let call = match node.kind() {
+        "function_definition" => julia_signature_call(first_child_of_kind(node, "signature")?)?,
+        "assignment" => julia_signature_call(node.named_child(0)?)?,
+        _ => return None,
+    };
+    let arguments = first_child_of_kind(call, "argument_list")?;
+    let mut cursor = arguments.walk();
+    let types: Vec<String> = arguments
+        .named_children(&mut cursor)
+        .filter_map(|argument| julia_argument_type(argument, source))
+        .collect();
+    Some(HashMap::from([(
+        "julia.signature".to_string(),
+        format!("({})", types.join(", ")),
+    )]))
+}
+
+/// `Int` for `x::Int` or `::Int`, `Any` for a bare `x`; defaults (`y = 1`)
+/// and splats (`xs::Int...`) are typed by the argument they wrap.
+fn julia_argument_type(argument: Node, source: &[u8]) -> Option<String> {
+    match argument.kind() {
+        "identifier" => Some("Any".to_string()),
+        "typed_expression" | "unary_typed_expression" => {
+            let last = argument.named_child_count().checked_sub(1)?;
+            let ty = argument.named_child(u32::try_from(last).ok()?)?;
+            Some(node_text(ty, source).to_string())
+        }
+        "keyword_parameters" | "comment" => None,
+        _ => julia_argument_type(argument.named_child(0)?, source),
+    }
+}
+
+/// `@interface`, `@implementation`, and `@protocol` blocks, whose members sit
+/// directly under the node rather than in a body.
+fn is_objc_container(node: Node) -> bool {
+    match node.kind() {
+        "class_interface"
+        | "class_implementation"
+        | "category_interface"
+        | "category_implementation" => true,
+        // Swift shares the node type; only the ObjC one opens with `@protocol`.
+        "protocol_declaration" => node.child(0).is_some_and(|c| c.kind() == "@protocol"),
+        _ => false,
+    }
+}
+
+/// `Foo` for a class or protocol, `Foo (Bar)` for a category.
+fn objc_container_name(node: Node, source: &[u8]) -> Option<String> {
+    let mut name: Option<&str> = None;
+    let mut in_parens = false;
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "identifier" if name.is_none() => name = Some(node_text(child, source)),
+            "(" => in_parens = true,
+            "identifier" if in_parens => {
+                return Some(format!("{} ({})", name?, node_text(child, source)));
+            }
+            ")" => return name.map(|n| format!("{n} ()")),
+            _ if name.is_some() && !in_parens => break,
+            _ => {}
+        }
+    }
+    name.map(String::from)
+}
+
+/// The selector of an Objective-C method, e.g. `setName:age:` for
+/// `- (void)setName:(NSString *)name age:(int)age`.
+fn objc_selector(node: Node, source: &[u8]) -> Option<String> {
+    if !matches!(node.kind(), "method_declaration" | "method_definition")
+        || !node.child(0).is_some_and(|c| matches!(c.kind(), "-" | "+"))
+    {
+        return None;
+    }
+    let mut selector = String::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "identifier" => selector.push_str(node_text(child, source)),
+            "method_parameter" => selector.push(':'),
+            "keyword_declarator" => {
+                if let Some(keyword) = child.child_by_field_name("keyword") {
+                    selector.push_str(node_text(keyword, source));
+                }
+                selector.push(':');
+            }
+            _ => {}
+        }
+    }
+    (!selector.is_empty()).then_some(selector)
+}
+
 fn node_text<'a>(node: Node, source: &'a [u8]) -> &'a str {
     node.utf8_text(source).unwrap_or("")
 }
@@ -2481,6 +3378,7 @@ fn map_node_type(tree_sitter_type: &str) -> &str {
         | "generator_function_declaration"
         | "function_definition"
         | "function_item"
+        | "function_signature_item"
         | "function_signature"
         | "subroutine_declaration_statement" => "function",
         "method_declaration"
@@ -2495,7 +3393,8 @@ fn map_node_type(tree_sitter_type: &str) -> &str {
         | "class_definition"
         | "class_specifier"
         | "class" => "class",
-        "interface_declaration" => "interface",
+        "interface_declaration" | "class_interface" | "category_interface" => "interface",
+        "class_implementation" | "category_implementation" => "implementation",
         "protocol_declaration" => "protocol",
         "init_declaration" => "init",
         "deinit_declaration" => "deinit",
@@ -2529,6 +3428,7 @@ fn map_node_type(tree_sitter_type: &str) -> &str {
         "object_definition" => "object",
         "trait_definition" => "trait",
         "val_definition" => "val",
+        "var_definition" => "var",
         "given_definition" => "given",
         "extension_definition" => "extension",
         "package_statement" => "package",
@@ -2644,7 +3544,10 @@ fn extract_call_entity(
     config: &LanguageConfig,
     source: &[u8],
 ) -> Option<(String, &'static str)> {
-    let target = node.child_by_field_name("target")?;
+    // Elixir names the callee `target`, R `function`.
+    let target = node
+        .child_by_field_name("target")
+        .or_else(|| node.child_by_field_name("function"))?;
     if target.kind() != "identifier" {
         return None;
     }
@@ -2663,6 +3566,7 @@ fn extract_call_entity(
         "defimpl" => "impl",
         "defstruct" => "struct",
         "defexception" => "exception",
+        "setClass" | "setRefClass" | "R6Class" => "class",
         _ => return None,
     };
 
@@ -2684,6 +3588,7 @@ fn extract_call_entity(
         }
         "defstruct" => "__struct__".to_string(),
         "defexception" => "__exception__".to_string(),
+        "setClass" | "setRefClass" | "R6Class" => r_first_string_argument(args, source)?,
         _ => {
             // def, defp, defmacro, defguard, defdelegate
             // First arg is a call (fn with params), identifier (arity-0),
@@ -2837,6 +3742,10 @@ fn map_entity_type(node: Node, config: &LanguageConfig) -> &'static str {
         "class_member" => map_class_member_type(node),
         "method_definition" => map_js_ts_accessor_method_type(node, config)
             .unwrap_or_else(|| map_node_type(node.kind())),
+        "binary_operator" if config.id == "r" => "function",
+        "assignment" if config.id == "julia" => "function",
+        "struct_definition" if config.id == "julia" => "struct",
+        "macro_definition" if config.id == "julia" => "macro",
         "class_declaration" if config.id == "swift" => {
             swift_class_declaration_type(node).unwrap_or_else(|| map_node_type(node.kind()))
         }
@@ -2845,12 +3754,52 @@ fn map_entity_type(node: Node, config: &LanguageConfig) -> &'static str {
         "declaration" if matches!(config.id, "c" | "cpp") && has_function_declarator(node) => {
             "function"
         }
+        // C++ templates take the type of the declaration they wrap, so
+        // `template <class T> T max(T, T)` is a function like any other.
+        "template_declaration" if config.id == "cpp" => {
+            let inner = cpp_template_body(node);
+            match inner.kind() {
+                "function_definition"
+                | "declaration"
+                | "class_specifier"
+                | "struct_specifier"
+                | "union_specifier" => map_entity_type(inner, config),
+                _ => "template",
+            }
+        }
+        // Rust: a bodiless `fn` in a trait is a method signature; in an
+        // `extern` block it's a foreign function.
+        "function_signature_item"
+            if node
+                .parent()
+                .and_then(|list| list.parent())
+                .is_some_and(|owner| owner.kind() == "trait_item") =>
+        {
+            "method"
+        }
         _ => promote_zig_variable(node, config)
             .or_else(|| promote_js_ts_const_function(node, config))
             .unwrap_or_else(|| map_node_type(node.kind())),
     }
 }
 
+/// The declaration under a (possibly nested) C++ `template_declaration`, or
+/// `node` itself when it isn't a template.
+fn cpp_template_body(node: Node) -> Node {
+    let mut node = node;
+    while node.kind() == "template_declaration" {
+        let mut cursor = node.walk();
+        let inner = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() != "template_parameter_list");
+        match inner {
+            Some(inner) => node = inner,
+            None => break,
+        }
+    }
+    node
+}
+
 fn map_js_ts_accessor_method_type(node: Node, config: &LanguageConfig) -> Option<&'static str> {
     if !matches!(config.id, "typescript" | "tsx" | "javascript") {
         return None;
@@ -2890,6 +3839,258 @@ fn map_js_ts_declarator_entity_type(
         .unwrap_or_else(|| map_node_type(declaration.kind()))
 }
 
+/// A C/C++ prototype shares its name and type with the function's definition,
+/// so it is marked `c.prototype` to tell the two apart.
+fn c_prototype_metadata(
+    node: Node,
+    entity_type: &str,
+    config: &LanguageConfig,
+) -> Option<HashMap<String, String>> {
+    let is_prototype = matches!(config.id, "c" | "cpp")
+        && entity_type == "function"
+        && cpp_template_body(node).kind() == "declaration";
+    is_prototype.then(|| HashMap::from([("c.prototype".to_string(), "true".to_string())]))
+}
+
+/// C# declaration details. Each fragment of a `partial` type is its own
+/// entity, so fragments are marked `csharp.partial` to tie them together.
+/// Properties record their accessors (`get,set`, `get,init`, ...) under
+/// `csharp.accessors`, plus `csharp.auto_property` when none has a body.
+fn csharp_metadata(
+    node: Node,
+    source: &[u8],
+    config: &LanguageConfig,
+) -> Option<HashMap<String, String>> {
+    if config.id != "csharp" {
+        return None;
+    }
+    let mut metadata = HashMap::new();
+    let mut cursor = node.walk();
+    let is_partial = node
+        .named_children(&mut cursor)
+        .any(|child| child.kind() == "modifier" && node_text(child, source) == "partial");
+    if is_partial {
+        metadata.insert("csharp.partial".to_string(), "true".to_string());
+    }
+    if node.kind() == "property_declaration" {
+        if let Some(accessor_list) = node.child_by_field_name("accessors") {
+            let mut accessors = Vec::new();
+            let mut has_body = false;
+            let mut cursor = accessor_list.walk();
+            for accessor in accessor_list.named_children(&mut cursor) {
+                if accessor.kind() != "accessor_declaration" {
+                    continue;
+                }
+                if let Some(name) = accessor.child_by_field_name("name") {
+                    accessors.push(node_text(name, source));
+                }
+                has_body |= accessor
+                    .child_by_field_name("body")
+                    .is_some_and(|body| body.kind() != ";");
+            }
+            metadata.insert("csharp.accessors".to_string(), accessors.join(","));
+            if !has_body {
+                metadata.insert("csharp.auto_property".to_string(), "true".to_string());
+            }
+        } else if node.child_by_field_name("value").is_some() {
+            // `public int Total => a + b;` is a get-only property.
+            metadata.insert("csharp.accessors".to_string(), "get".to_string());
+        }
+    }
+    (!metadata.is_empty()).then_some(metadata)
+}
+
+/// Record the declaration's normalized visibility under `visibility` (see
+/// [`crate::parser::visibility`]). Languages without a visibility notion leave
+/// the metadata untouched.
+fn with_visibility_metadata(
+    metadata: Option<HashMap<String, String>>,
+    node: Node,
+    name: &str,
+    source: &[u8],
+    config: &LanguageConfig,
+) -> Option<HashMap<String, String>> {
+    let Some(visibility) = declared_visibility(node, name, source, config) else {
+        return metadata;
+    };
+    let mut metadata = metadata.unwrap_or_default();
+    metadata.insert(
+        crate::parser::visibility::VISIBILITY_KEY.to_string(),
+        visibility.to_string(),
+    );
+    Some(metadata)
+}
+
+fn declared_visibility(
+    node: Node,
+    name: &str,
+    source: &[u8],
+    config: &LanguageConfig,
+) -> Option<&'static str> {
+    match config.id {
+        "rust" => rust_visibility(node, source),
+        "typescript" | "tsx" | "javascript" => Some(js_ts_visibility(node, source)),
+        "java" => Some(java_visibility(node)),
+        "csharp" => csharp_visibility(node, source),
+        "python" => {
+            let dunder = name.starts_with("__") && name.ends_with("__");
+            Some(if name.starts_with('_') && !dunder {
+                "private"
+            } else {
+                "public"
+            })
+        }
+        _ => None,
+    }
+}
+
+/// `pub` is public and the scoped forms (`pub(crate)`, `pub(super)`,
+/// `pub(in path)`) are crate-visible. Trait items and items of trait impls
+/// are as visible as the trait, and inherent impl blocks carry none.
+fn rust_visibility(node: Node, source: &[u8]) -> Option<&'static str> {
+    let mut cursor = node.walk();
+    let modifier = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier");
+    if let Some(modifier) = modifier {
+        return Some(match node_text(modifier, source) {
+            "pub" => "public",
+            "pub(self)" => "private",
+            _ => "crate",
+        });
+    }
+    match node.kind() {
+        "impl_item" => return None,
+        "macro_definition" => {
+            let mut current = node;
+            while let Some(prev) = current.prev_named_sibling() {
+                if prev.kind() != "attribute_item" {
+                    break;
+                }
+                if node_text(prev, source).contains("macro_export") {
+                    return Some("public");
+                }
+                current = prev;
+            }
+        }
+        _ => {}
+    }
+    let in_trait_surface = node
+        .parent()
+        .filter(|parent| parent.kind() == "declaration_list")
+        .and_then(|list| list.parent())
+        .is_some_and(|owner| {
+            owner.kind() == "trait_item"
+                || (owner.kind() == "impl_item" && owner.child_by_field_name("trait").is_some())
+        });
+    Some(if in_trait_surface {
+        "public"
+    } else {
+        "private"
+    })
+}
+
+/// Exported declarations are public and everything else at module level is
+/// private. Class members follow their accessibility modifier (`#name` is
+/// private), defaulting to public, and interface members are public.
+fn js_ts_visibility(node: Node, source: &[u8]) -> &'static str {
+    if node.kind() == "export_statement" {
+        return "public";
+    }
+    let Some(parent) = node.parent() else {
+        return "private";
+    };
+    match parent.kind() {
+        "export_statement" | "interface_body" | "object_type" => "public",
+        "class_body" => {
+            if node
+                .child_by_field_name("name")
+                .is_some_and(|name| name.kind() == "private_property_identifier")
+            {
+                return "private";
+            }
+            let mut cursor = node.walk();
+            let modifier = node
+                .named_children(&mut cursor)
+                .find(|child| child.kind() == "accessibility_modifier");
+            match modifier.map(|m| node_text(m, source)) {
+                Some("private") => "private",
+                Some("protected") => "protected",
+                _ => "public",
+            }
+        }
+        _ => "private",
+    }
+}
+
+/// Access modifiers, with Java's package-private default. Interface and
+/// annotation members are implicitly public.
+fn java_visibility(node: Node) -> &'static str {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "modifiers");
+    if let Some(modifiers) = modifiers {
+        let mut modifier_cursor = modifiers.walk();
+        for modifier in modifiers.children(&mut modifier_cursor) {
+            match modifier.kind() {
+                "public" => return "public",
+                "protected" => return "protected",
+                "private" => return "private",
+                _ => {}
+            }
+        }
+    }
+    let in_interface = node
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "interface_body" | "annotation_type_body"));
+    if in_interface {
+        "public"
+    } else {
+        "package"
+    }
+}
+
+/// Access modifiers, with C#'s defaults: members are private, interface
+/// members public, and types outside another type internal. Namespaces carry
+/// none.
+fn csharp_visibility(node: Node, source: &[u8]) -> Option<&'static str> {
+    if node.kind() == "namespace_declaration" {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let modifiers: Vec<&str> = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "modifier")
+        .map(|child| node_text(child, source))
+        .collect();
+    for (modifier, visibility) in [
+        ("public", "public"),
+        ("protected", "protected"),
+        ("internal", "internal"),
+        ("private", "private"),
+    ] {
+        if modifiers.contains(&modifier) {
+            return Some(visibility);
+        }
+    }
+    let owner = node
+        .parent()
+        .filter(|parent| parent.kind() == "declaration_list")
+        .and_then(|list| list.parent());
+    Some(match owner.map(|owner| owner.kind()) {
+        Some("interface_declaration") => "public",
+        Some(
+            "class_declaration"
+            | "struct_declaration"
+            | "record_declaration"
+            | "record_struct_declaration",
+        ) => "private",
+        _ if node.parent().is_some_and(|p| p.kind() == "record_body") => "private",
+        _ => "internal",
+    })
+}
+
 /// Check whether a C/C++ `declaration` node contains a `function_declarator`
 /// descendant, indicating it is a function prototype rather than a variable.
 fn has_function_declarator(node: Node) -> bool {
@@ -3359,7 +4560,10 @@ fn extract_ocaml_named_bindings(
                 end_line: binding.end_position().row + 1,
                 start_byte: Some(binding.start_byte()),
                 end_byte: Some(binding.end_byte()),
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             };
 
             let entity_id = entity.id.clone();
@@ -3397,35 +4601,77 @@ fn extract_go_receiver_struct(
     file_path: &str,
     entities: &[SemanticEntity],
 ) -> Option<String> {
+    let (_, struct_name) = go_receiver(node, source)?;
+    if struct_name.is_empty() {
+        return None;
+    }
+    // Find matching struct/type entity in the same file
+    for e in entities.iter().rev() {
+        if e.file_path == file_path
+            && e.name == struct_name
+            && matches!(
+                e.entity_type.as_str(),
+                "type" | "struct" | "class" | "interface"
+            )
+        {
+            return Some(e.id.clone());
+        }
+    }
+    // No struct entity found yet (might be in a different file), use synthetic ID
+    Some(format!("{}::type::{}", file_path, struct_name))
+}
+
+/// Receiver of a Go method_declaration: the type as written (`*Server`,
+/// `*Stack[T]`) and the bare name of the type it belongs to (`Server`, `Stack`).
+fn go_receiver(node: Node, source: &[u8]) -> Option<(String, String)> {
     let receiver = node.child_by_field_name("receiver")?;
     // receiver is a parameter_list containing parameter_declaration(s)
     let mut cursor = receiver.walk();
-    for param in receiver.named_children(&mut cursor) {
-        if param.kind() == "parameter_declaration" {
-            let type_node = param.child_by_field_name("type")?;
-            let type_text = node_text(type_node, source);
-            // Strip pointer: *Transaction -> Transaction
-            let struct_name = type_text.trim_start_matches('*');
-            if struct_name.is_empty() {
-                return None;
-            }
-            // Find matching struct/type entity in the same file
-            for e in entities.iter().rev() {
-                if e.file_path == file_path
-                    && e.name == struct_name
-                    && matches!(
-                        e.entity_type.as_str(),
-                        "type" | "struct" | "class" | "interface"
-                    )
-                {
-                    return Some(e.id.clone());
-                }
-            }
-            // No struct entity found yet (might be in a different file), use synthetic ID
-            return Some(format!("{}::type::{}", file_path, struct_name));
+    let param = receiver
+        .named_children(&mut cursor)
+        .find(|param| param.kind() == "parameter_declaration")?;
+    let type_node = param.child_by_field_name("type")?;
+    let mut base = type_node;
+    loop {
+        base = match base.kind() {
+            "pointer_type" | "parenthesized_type" => base.named_child(0)?,
+            "generic_type" => base.child_by_field_name("type")?,
+            _ => break,
+        };
+    }
+    Some((
+        node_text(type_node, source).to_string(),
+        node_text(base, source).to_string(),
+    ))
+}
+
+/// Record each Go method's receiver type, as written, under `go.receiver`.
+fn attach_go_receiver_metadata(root: Node, source: &[u8], entities: &mut [SemanticEntity]) {
+    let mut by_span: HashMap<(usize, usize), usize> = entities
+        .iter()
+        .enumerate()
+        .filter(|(_, entity)| entity.entity_type == "method")
+        .filter_map(|(index, entity)| Some(((entity.start_byte?, entity.end_byte?), index)))
+        .collect();
+    if by_span.is_empty() {
+        return;
+    }
+
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if node.kind() != "method_declaration" {
+            continue;
+        }
+        let Some(index) = by_span.remove(&(node.start_byte(), node.end_byte())) else {
+            continue;
+        };
+        if let Some((receiver, _)) = go_receiver(node, source) {
+            entities[index]
+                .metadata
+                .get_or_insert_with(HashMap::new)
+                .insert("go.receiver".to_string(), receiver);
         }
     }
-    None
 }
 
 /// Check if a JS/TS call_expression is a test framework call (describe, test, it, etc.).
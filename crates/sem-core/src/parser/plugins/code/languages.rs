@@ -280,6 +280,11 @@ fn get_cpp() -> Option<Language> {
     Some(tree_sitter_cpp::LANGUAGE.into())
 }
 
+#[cfg(feature = "lang-objc")]
+fn get_objc() -> Option<Language> {
+    Some(tree_sitter_objc::LANGUAGE.into())
+}
+
 #[cfg(feature = "lang-ruby")]
 fn get_ruby() -> Option<Language> {
     Some(tree_sitter_ruby::LANGUAGE.into())
@@ -400,6 +405,16 @@ fn get_fish() -> Option<Language> {
     Some(tree_sitter_fish::language())
 }
 
+#[cfg(feature = "lang-r")]
+fn get_r() -> Option<Language> {
+    Some(tree_sitter_r::LANGUAGE.into())
+}
+
+#[cfg(feature = "lang-julia")]
+fn get_julia() -> Option<Language> {
+    Some(tree_sitter_julia::LANGUAGE.into())
+}
+
 /// Inside JS/TS function bodies, suppress variable declarations so that local
 /// variables are not extracted as nested entities. Inner function/class
 /// declarations are still extracted for diff granularity.
@@ -650,6 +665,7 @@ static RUST_CONFIG: LanguageConfig = LanguageConfig {
     extensions: &[".rs"],
     entity_node_types: &[
         "function_item",
+        "function_signature_item",
         "struct_item",
         "enum_item",
         "impl_item",
@@ -742,6 +758,47 @@ static CPP_CONFIG: LanguageConfig = LanguageConfig {
     scope_resolve: Some(&CPP_SCOPE_CONFIG),
 };
 
+/// Inside Objective-C function and method bodies, suppress `declaration`
+/// nodes so that block-local variables are not extracted as nested entities.
+const OBJC_SUPPRESSED_NESTED: &[SuppressedNestedEntity] = &[
+    SuppressedNestedEntity {
+        parent_entity_node_type: "function_definition",
+        child_entity_node_type: "declaration",
+    },
+    SuppressedNestedEntity {
+        parent_entity_node_type: "method_definition",
+        child_entity_node_type: "declaration",
+    },
+];
+
+/// Objective-C and Objective-C++. `.h` headers stay with C unless their
+/// content declares an `@interface`, `@protocol`, or `@implementation`.
+#[cfg(feature = "lang-objc")]
+static OBJC_CONFIG: LanguageConfig = LanguageConfig {
+    id: "objc",
+    extensions: &[".m", ".mm"],
+    entity_node_types: &[
+        "class_interface",
+        "class_implementation",
+        "category_interface",
+        "category_implementation",
+        "protocol_declaration",
+        "method_declaration",
+        "method_definition",
+        "function_definition",
+        "struct_specifier",
+        "enum_specifier",
+        "type_definition",
+        "declaration",
+    ],
+    container_node_types: &["compound_statement"],
+    call_entity_identifiers: &[],
+    suppressed_nested_entities: OBJC_SUPPRESSED_NESTED,
+    scope_boundary_types: &[],
+    get_language: get_objc,
+    scope_resolve: None,
+};
+
 #[cfg(feature = "lang-ruby")]
 static RUBY_CONFIG: LanguageConfig = LanguageConfig {
     id: "ruby",
@@ -1070,6 +1127,7 @@ static SCALA_CONFIG: LanguageConfig = LanguageConfig {
         "function_definition",
         "function_declaration",
         "val_definition",
+        "var_definition",
         "given_definition",
         "extension_definition",
         "type_definition",
@@ -1092,7 +1150,15 @@ static ZIG_CONFIG: LanguageConfig = LanguageConfig {
         "test_declaration",
         "variable_declaration",
     ],
-    container_node_types: &["block"],
+    // `const Point = struct { ... }`: members of the container literal are
+    // nested under the const that names it.
+    container_node_types: &[
+        "block",
+        "struct_declaration",
+        "enum_declaration",
+        "union_declaration",
+        "opaque_declaration",
+    ],
     call_entity_identifiers: &[],
     suppressed_nested_entities: &[SuppressedNestedEntity {
         parent_entity_node_type: "function_declaration",
@@ -1301,6 +1367,48 @@ static FISH_CONFIG: LanguageConfig = LanguageConfig {
     scope_resolve: Some(&FISH_SCOPE_CONFIG),
 };
 
+/// R defines functions by assignment, so `binary_operator` is an entity only
+/// when it assigns a `function_definition` (`foo <- function(x)`,
+/// `foo = function(x)`); the extractor names it after the target. S4 and R6
+/// classes are `setClass("Person", ...)`-style calls named by their first
+/// string argument. Function bodies aren't walked: their assignments are
+/// locals, not top-level definitions.
+#[cfg(feature = "lang-r")]
+static R_CONFIG: LanguageConfig = LanguageConfig {
+    id: "r",
+    extensions: &[".r"],
+    entity_node_types: &["binary_operator"],
+    container_node_types: &[],
+    call_entity_identifiers: &["setClass", "setRefClass", "R6Class"],
+    suppressed_nested_entities: &[],
+    scope_boundary_types: &[],
+    get_language: get_r,
+    scope_resolve: None,
+};
+
+/// Julia. `assignment` is an entity only in the short function form
+/// (`f(x) = x^2`). Methods of one function share its name; each records its
+/// argument types under `julia.signature`. Module members sit directly under
+/// `module_definition`, which the extractor walks as a container.
+#[cfg(feature = "lang-julia")]
+static JULIA_CONFIG: LanguageConfig = LanguageConfig {
+    id: "julia",
+    extensions: &[".jl"],
+    entity_node_types: &[
+        "function_definition",
+        "assignment",
+        "struct_definition",
+        "module_definition",
+        "macro_definition",
+    ],
+    container_node_types: &[],
+    call_entity_identifiers: &[],
+    suppressed_nested_entities: &[],
+    scope_boundary_types: &[],
+    get_language: get_julia,
+    scope_resolve: None,
+};
+
 // ─── Scope Resolve Configs for Supported Languages ────────────────────────────
 
 static PYTHON_SCOPE_CONFIG: ScopeResolveConfig = ScopeResolveConfig {
@@ -2595,6 +2703,8 @@ macro_rules! all_configs {
             &C_CONFIG,
             #[cfg(feature = "lang-cpp")]
             &CPP_CONFIG,
+            #[cfg(feature = "lang-objc")]
+            &OBJC_CONFIG,
             #[cfg(feature = "lang-ruby")]
             &RUBY_CONFIG,
             #[cfg(feature = "lang-csharp")]
@@ -2645,6 +2755,10 @@ macro_rules! all_configs {
             &LUA_CONFIG,
             #[cfg(feature = "lang-fish")]
             &FISH_CONFIG,
+            #[cfg(feature = "lang-r")]
+            &R_CONFIG,
+            #[cfg(feature = "lang-julia")]
+            &JULIA_CONFIG,
         ]
     }};
 }
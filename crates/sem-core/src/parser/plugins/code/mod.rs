@@ -28,11 +28,28 @@ fn language_config_for_content(
         .map(|e| format!(".{}", e.to_lowercase()))
         .unwrap_or_default();
 
+    if ext == ".h" && looks_like_objc(content) {
+        if let Some(config) = get_language_config(".m") {
+            return Some(config);
+        }
+    }
+
     get_language_config(&ext).or_else(|| {
         detect_ext_from_content(content).and_then(|shebang_ext| get_language_config(&shebang_ext))
     })
 }
 
+/// Whether a `.h` header declares Objective-C classes or protocols rather
+/// than plain C.
+fn looks_like_objc(content: &str) -> bool {
+    content.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("@interface")
+            || line.starts_with("@protocol")
+            || line.starts_with("@implementation")
+    })
+}
+
 fn parse_tree(
     config: &'static languages::LanguageConfig,
     content: &str,
@@ -80,6 +97,27 @@ fn has_non_comment_content(node: tree_sitter::Node, source: &[u8]) -> bool {
     false
 }
 
+/// The first ERROR or MISSING node in document order.
+fn first_syntax_error(root: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    if !root.has_error() {
+        return None;
+    }
+    let mut node = root;
+    'descend: loop {
+        if node.is_error() || node.is_missing() {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.has_error() || child.is_missing() {
+                node = child;
+                continue 'descend;
+            }
+        }
+        return Some(node);
+    }
+}
+
 fn is_comment_node(kind: &str) -> bool {
     matches!(
         kind,
@@ -123,6 +161,23 @@ impl SemanticParserPlugin for CodeParserPlugin {
         (entities, Some(tree))
     }
 
+    fn parse_error(&self, content: &str, file_path: &str) -> Option<String> {
+        let Some(config) = language_config_for_content(content, file_path) else {
+            return Some("no grammar for this file type".to_string());
+        };
+        if (config.get_language)().is_none() {
+            return Some(format!(
+                "{} grammar is not included in this build",
+                config.id
+            ));
+        }
+        let Some(tree) = parse_tree(config, content) else {
+            return Some("parser gave up".to_string());
+        };
+        first_syntax_error(tree.root_node())
+            .map(|node| format!("syntax error at line {}", node.start_position().row + 1))
+    }
+
     fn structural_hash_content(&self, content: &str, file_path: &str) -> Option<String> {
         let config = language_config_for_content(content, file_path)?;
         let tree = parse_tree(config, content)?;
@@ -353,6 +408,311 @@ int main(void) {
         assert!(!names.contains(&"message"), "got: {:?}", names);
     }
 
+    #[test]
+    fn test_c_prototypes_are_marked_apart_from_definitions() {
+        let code = "int helper(int x);\n\nint helper(int x) {\n    return x;\n}\n";
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "helper.c");
+        let prototype_flags: Vec<(usize, Option<&str>)> = entities
+            .iter()
+            .filter(|e| e.name == "helper")
+            .map(|e| {
+                let flag = e.metadata.as_ref().and_then(|m| m.get("c.prototype"));
+                (e.start_line, flag.map(String::as_str))
+            })
+            .collect();
+        assert_eq!(prototype_flags, vec![(1, Some("true")), (3, None)]);
+    }
+
+    #[test]
+    fn test_c_preprocessor_guard_metadata() {
+        let code = r#"
+#ifndef CONFIG_H
+#define CONFIG_H
+
+#ifdef DEBUG
+void trace(const char *msg) {}
+#if LOG_LEVEL > 1
+void verbose(void) {}
+#endif
+#else
+void trace(const char *msg);
+#endif
+
+int always(void) { return 0; }
+
+#endif
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "config.h");
+        let guard_of = |name: &str, line: usize| {
+            entities
+                .iter()
+                .find(|e| e.name == name && e.start_line == line)
+                .unwrap_or_else(|| panic!("missing {name} at line {line}"))
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("c.preprocessor_guard"))
+                .cloned()
+        };
+
+        assert_eq!(guard_of("trace", 6).as_deref(), Some("defined(DEBUG)"));
+        assert_eq!(
+            guard_of("verbose", 8).as_deref(),
+            Some("defined(DEBUG) && LOG_LEVEL > 1")
+        );
+        assert_eq!(guard_of("trace", 11).as_deref(), Some("!defined(DEBUG)"));
+        // The include guard wraps everything and is not recorded.
+        assert_eq!(guard_of("always", 14), None);
+    }
+
+    #[test]
+    fn test_objc_entity_extraction() {
+        let header = r#"
+#import <Foundation/Foundation.h>
+
+@protocol Greeter
+- (NSString *)greet:(NSString *)name;
+@end
+
+@interface Person : NSObject <Greeter>
+- (void)setName:(NSString *)name age:(int)age;
++ (instancetype)person;
+@end
+"#;
+        let source = r#"
+#import "Person.h"
+
+@implementation Person
+- (void)setName:(NSString *)name age:(int)age {
+    int doubled = age * 2;
+    NSLog(@"%@ %d", name, doubled);
+}
+
++ (instancetype)person {
+    return [[self alloc] init];
+}
+
+- (NSString *)greet:(NSString *)name {
+    return name;
+}
+@end
+"#;
+        let plugin = CodeParserPlugin;
+
+        // A header with `@interface` is read as Objective-C, not C.
+        let declared = plugin.extract_entities(header, "Person.h");
+        let summary: Vec<(&str, &str)> = declared
+            .iter()
+            .map(|e| (e.entity_type.as_str(), e.name.as_str()))
+            .collect();
+        assert!(
+            summary.contains(&("protocol", "Greeter")),
+            "got: {:?}",
+            summary
+        );
+        assert!(
+            summary.contains(&("interface", "Person")),
+            "got: {:?}",
+            summary
+        );
+        let interface = declared
+            .iter()
+            .find(|e| e.entity_type == "interface")
+            .unwrap();
+        let setter = declared.iter().find(|e| e.name == "setName:age:").unwrap();
+        assert_eq!(setter.entity_type, "method");
+        assert_eq!(setter.parent_id.as_deref(), Some(interface.id.as_str()));
+
+        let defined = plugin.extract_entities(source, "Person.m");
+        let summary: Vec<(&str, &str)> = defined
+            .iter()
+            .map(|e| (e.entity_type.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("implementation", "Person"),
+                ("method", "setName:age:"),
+                ("method", "person"),
+                ("method", "greet:"),
+            ]
+        );
+        let implementation = &defined[0];
+        assert!(defined[1..]
+            .iter()
+            .all(|e| e.parent_id.as_deref() == Some(implementation.id.as_str())));
+    }
+
+    #[test]
+    fn test_ts_decorators_recorded_in_metadata() {
+        let code = r#"
+@Component({ selector: 'app-root' })
+export class AppComponent {
+  @Input() title: string;
+
+  @UseGuards(AuthGuard)
+  @Get('/users')
+  list() { return 1; }
+
+  plain() { return 2; }
+}
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "app.ts");
+        let decorators_of = |name: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("missing {name}"))
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("decorators"))
+                .cloned()
+        };
+
+        assert_eq!(
+            decorators_of("AppComponent").as_deref(),
+            Some("@Component({ selector: 'app-root' })")
+        );
+        assert_eq!(decorators_of("title").as_deref(), Some("@Input()"));
+        assert_eq!(
+            decorators_of("list").as_deref(),
+            Some("@UseGuards(AuthGuard)\n@Get('/users')")
+        );
+        assert_eq!(decorators_of("plain"), None);
+
+        // Member decorators are siblings of the member; they join its span so
+        // the content the hashes cover shows a decorator-only edit.
+        let list = entities.iter().find(|e| e.name == "list").unwrap();
+        assert!(list.content.starts_with("@UseGuards(AuthGuard)"));
+        assert_eq!(list.start_line, 6);
+        assert_eq!(list.content_hash, content_hash(&list.content));
+    }
+
+    #[test]
+    fn test_python_decorators_recorded_in_metadata() {
+        let code = r#"
+@app.route("/users", methods=["POST"])
+@login_required
+def create_user():
+    return 1
+
+class Items:
+    @staticmethod
+    def build():
+        return 2
+
+    @router.get('/items/{item_id}')
+    async def read_item(self, item_id):
+        return 3
+
+    def plain(self):
+        return 4
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "app.py");
+        let metadata_of = |name: &str, key: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("missing {name}"))
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(key))
+                .cloned()
+        };
+
+        assert_eq!(
+            metadata_of("create_user", "decorators").as_deref(),
+            Some("@app.route(\"/users\", methods=[\"POST\"])\n@login_required")
+        );
+        assert_eq!(
+            metadata_of("create_user", "route").as_deref(),
+            Some("POST /users")
+        );
+        assert_eq!(
+            metadata_of("build", "decorators").as_deref(),
+            Some("@staticmethod")
+        );
+        assert_eq!(metadata_of("build", "route"), None);
+        assert_eq!(
+            metadata_of("read_item", "route").as_deref(),
+            Some("GET /items/{item_id}")
+        );
+        assert_eq!(metadata_of("Items", "decorators"), None);
+        assert_eq!(metadata_of("plain", "decorators"), None);
+    }
+
+    #[test]
+    fn test_class_heritage_recorded_in_metadata() {
+        let plugin = CodeParserPlugin;
+        let heritage = |code: &str, file: &str, name: &str| {
+            let entities = plugin.extract_entities(code, file);
+            let metadata = entities
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("missing {name} in {file}"))
+                .metadata
+                .clone()
+                .unwrap_or_default();
+            (
+                metadata.get("extends").cloned(),
+                metadata.get("implements").cloned(),
+            )
+        };
+        let some = |extends: Option<&str>, implements: Option<&str>| {
+            (extends.map(str::to_string), implements.map(str::to_string))
+        };
+
+        let ts = "export class Admin extends models.User<Id> implements Auditable, Store<Id> {}\n\
+                  class Mixed extends mixin(Base) {}\n\
+                  interface Store<T> extends Reader<T>, Writer {}\n";
+        assert_eq!(
+            heritage(ts, "admin.ts", "Admin"),
+            some(Some("User"), Some("Auditable,Store"))
+        );
+        assert_eq!(heritage(ts, "admin.ts", "Mixed"), some(None, None));
+        assert_eq!(
+            heritage(ts, "admin.ts", "Store"),
+            some(Some("Reader,Writer"), None)
+        );
+
+        assert_eq!(
+            heritage("class Admin extends User {}\n", "admin.js", "Admin"),
+            some(Some("User"), None)
+        );
+
+        let java = "class Admin extends User<Id> implements Auditable, java.io.Serializable {}\n";
+        assert_eq!(
+            heritage(java, "Admin.java", "Admin"),
+            some(Some("User"), Some("Auditable,Serializable"))
+        );
+
+        let python = "@dataclass\nclass Admin(User, mixins.Audited, metaclass=Meta):\n    pass\n";
+        assert_eq!(
+            heritage(python, "admin.py", "Admin"),
+            some(Some("User,Audited"), None)
+        );
+    }
+
+    #[test]
+    fn test_ts_decorator_change_changes_entity_hash() {
+        let before = "class Api {\n  @Get('/users')\n  list() { return 1; }\n}\n";
+        let after = "class Api {\n  @Get('/people')\n  list() { return 1; }\n}\n";
+        let plugin = CodeParserPlugin;
+        let hash_of = |code: &str| {
+            plugin
+                .extract_entities(code, "api.ts")
+                .into_iter()
+                .find(|e| e.name == "list")
+                .expect("list method")
+                .content_hash
+        };
+
+        assert_ne!(hash_of(before), hash_of(after));
+    }
+
     #[test]
     fn test_cpp_entity_extraction() {
         let code = "namespace math {\nclass Vector3 {\npublic:\n    float length() const { return 0; }\n};\n}\nvoid greet() {}\n";
@@ -364,6 +724,69 @@ int main(void) {
         assert!(names.contains(&"greet"), "got: {:?}", names);
     }
 
+    #[test]
+    fn test_cpp_nested_namespaces_chain_parents() {
+        let code =
+            "namespace a {\nnamespace b {\nclass C {\npublic:\n    void stop() {}\n};\n}\n}\n";
+        let entities = CodeParserPlugin.extract_entities(code, "t.cpp");
+        let find = |name: &str| entities.iter().find(|e| e.name == name).unwrap();
+
+        let a = find("a");
+        let b = find("b");
+        let c = find("C");
+        let stop = find("stop");
+        assert_eq!(a.parent_id, None);
+        assert_eq!(b.parent_id.as_deref(), Some(a.id.as_str()));
+        assert_eq!(c.parent_id.as_deref(), Some(b.id.as_str()));
+        assert_eq!(stop.parent_id.as_deref(), Some(c.id.as_str()));
+    }
+
+    #[test]
+    fn test_cpp_templates_take_the_wrapped_declaration_type() {
+        let code = r#"
+namespace util {
+template <typename T>
+T foo(T value) { return value; }
+
+template <class T>
+class Box {
+public:
+    void take(T value) {}
+    template <class U>
+    U get() const { return U(); }
+};
+
+template <class T>
+using Ptr = T*;
+}
+"#;
+        let entities = CodeParserPlugin.extract_entities(code, "t.cpp");
+        let find = |name: &str| {
+            entities.iter().find(|e| e.name == name).unwrap_or_else(|| {
+                panic!(
+                    "missing {name}: {:?}",
+                    entities.iter().map(|e| &e.name).collect::<Vec<_>>()
+                )
+            })
+        };
+
+        let util = find("util");
+        let foo = find("foo");
+        assert_eq!(foo.entity_type, "function");
+        assert_eq!(foo.parent_id.as_deref(), Some(util.id.as_str()));
+
+        let boxed = find("Box");
+        assert_eq!(boxed.entity_type, "class");
+        assert_eq!(boxed.parent_id.as_deref(), Some(util.id.as_str()));
+        for member in ["take", "get"] {
+            let member = find(member);
+            assert_eq!(member.entity_type, "function");
+            assert_eq!(member.parent_id.as_deref(), Some(boxed.id.as_str()));
+        }
+
+        assert_eq!(find("Ptr").entity_type, "template");
+    }
+
     #[test]
     fn test_cpp_function_locals_not_extracted() {
         let code = r#"
@@ -413,6 +836,80 @@ int main() {
         assert!(names.contains(&"Role"), "got: {:?}", names);
     }
 
+    #[test]
+    fn test_csharp_partial_class_fragments_are_marked() {
+        let plugin = CodeParserPlugin;
+        let first = plugin.extract_entities(
+            "namespace App {\npublic partial class Order {\n    public void Submit() {}\n}\n}\n",
+            "Order.cs",
+        );
+        let second = plugin.extract_entities(
+            "namespace App {\npublic partial class Order {\n    public void Cancel() {}\n}\n}\n",
+            "Order.Generated.cs",
+        );
+        let fragments: Vec<&SemanticEntity> = first
+            .iter()
+            .chain(&second)
+            .filter(|e| e.name == "Order")
+            .collect();
+        assert_eq!(fragments.len(), 2, "one entity per fragment");
+        assert_ne!(fragments[0].id, fragments[1].id);
+        for fragment in fragments {
+            assert_eq!(fragment.entity_type, "class");
+            let partial = fragment
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("csharp.partial"));
+            assert_eq!(partial.map(String::as_str), Some("true"));
+        }
+
+        let plain = plugin.extract_entities("public class Invoice {}\n", "Invoice.cs");
+        let invoice = plain.iter().find(|e| e.name == "Invoice").unwrap();
+        assert!(invoice
+            .metadata
+            .as_ref()
+            .is_none_or(|m| !m.contains_key("csharp.partial")));
+    }
+
+    #[test]
+    fn test_csharp_properties_record_accessors() {
+        let code = r#"
+public class Account {
+    private decimal balance;
+
+    public string Owner { get; set; }
+
+    public decimal Balance {
+        get { return balance; }
+        private set { balance = value; }
+    }
+
+    public string Id { get; init; }
+
+    public bool IsEmpty => balance == 0;
+}
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "Account.cs");
+        let property = |name: &str| {
+            let entity = entities
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("missing {name}: {entities:#?}"));
+            assert_eq!(entity.entity_type, "property", "{name}");
+            let metadata = entity.metadata.clone().unwrap_or_default();
+            (
+                metadata.get("csharp.accessors").cloned(),
+                metadata.contains_key("csharp.auto_property"),
+            )
+        };
+
+        assert_eq!(property("Owner"), (Some("get,set".to_string()), true));
+        assert_eq!(property("Balance"), (Some("get,set".to_string()), false));
+        assert_eq!(property("Id"), (Some("get,init".to_string()), true));
+        assert_eq!(property("IsEmpty"), (Some("get".to_string()), false));
+    }
+
     #[test]
     fn test_swift_entity_extraction() {
         let code = r#"
@@ -1422,6 +1919,189 @@ echo "main script"
         }
     }
 
+    #[test]
+    fn test_name_offsets_point_at_the_identifier() {
+        let plugin = CodeParserPlugin;
+        let name_at = |code: &str, file_path: &str, name: &str| {
+            let entity = plugin
+                .extract_entities(code, file_path)
+                .into_iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("{name} should be extracted from {file_path}"));
+            let start = entity.name_start.expect("name_start");
+            let end = entity.name_end.expect("name_end");
+            assert_ne!(
+                Some(start),
+                entity.start_byte,
+                "{name} name starts the entity"
+            );
+            (start, code[start..end].to_string())
+        };
+
+        let python = "import os\n\ndef first(a):\n    return a + 1\n";
+        assert_eq!(
+            name_at(python, "demo.py", "first"),
+            (python.find("first").unwrap(), "first".to_string())
+        );
+
+        // Attributes extend the entity's span; the name still points past them.
+        let rust = "#[inline]\npub fn answer() -> u32 {\n    42\n}\n";
+        assert_eq!(
+            name_at(rust, "demo.rs", "answer"),
+            (rust.find("answer").unwrap(), "answer".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lang-r")]
+    fn test_r_entity_extraction() {
+        let code = r#"library(methods)
+
+add <- function(x, y) {
+  inner <- function(z) z + 1
+  inner(x) + y
+}
+
+scale = function(v, by = 2) {
+  threshold <- 10
+  v * by
+}
+
+setClass("Person", representation(name = "character", age = "numeric"))
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "analysis.R");
+        let summary: Vec<(&str, &str, Option<&str>)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.entity_type.as_str(),
+                    e.parent_id.as_deref(),
+                )
+            })
+            .collect();
+
+        // Top-level `<-` and `=` assignments of a function are functions
+        // named by the target; plain value assignments and anything inside a
+        // function body are not entities.
+        assert_eq!(
+            summary,
+            vec![
+                ("add", "function", None),
+                ("scale", "function", None),
+                ("Person", "class", None),
+            ]
+        );
+        let add = &entities[0];
+        assert_eq!(add.start_line, 3);
+        assert_eq!(add.end_line, 6);
+        assert_eq!(add.name_start, code.find("add"));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-haskell")]
+    fn test_haskell_equations_coalesce_into_one_function() {
+        let code = r#"module Main where
+
+factorial :: Int -> Int
+factorial 0 = 1
+-- recursive case
+factorial n = n * factorial (n - 1)
+
+double :: Int -> Int
+double x = x * 2
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "Main.hs");
+        let functions: Vec<_> = entities
+            .iter()
+            .filter(|e| e.entity_type == "function")
+            .collect();
+
+        assert_eq!(
+            functions
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["factorial", "double"]
+        );
+        let factorial = functions[0];
+        assert_eq!(factorial.id, "Main.hs::function::factorial");
+        assert_eq!(factorial.start_line, 4);
+        assert_eq!(factorial.end_line, 6);
+        assert!(factorial.content.starts_with("factorial 0 = 1"));
+        assert!(factorial.content.ends_with("factorial (n - 1)"));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-julia")]
+    fn test_julia_entity_extraction() {
+        let code = r#"module Shapes
+
+struct Point{T} <: AbstractShape
+    x::T
+    y::T
+end
+
+function area(s::Square)
+    limit = 10
+    s.side^2
+end
+
+area(c::Circle, scale::Float64) = pi * c.r^2 * scale
+
+norm2(p) = p.x^2 + p.y^2
+
+origin = Point(0, 0)
+
+end
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "shapes.jl");
+        let summary: Vec<(&str, &str, Option<&str>)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.entity_type.as_str(),
+                    e.parent_id.as_deref(),
+                )
+            })
+            .collect();
+        let module = Some("shapes.jl::module::Shapes");
+
+        // Short-form definitions are functions; plain assignments are not
+        // entities, at the top of the module or inside a function body.
+        assert_eq!(
+            summary,
+            vec![
+                ("Shapes", "module", None),
+                ("Point", "struct", module),
+                ("area", "function", module),
+                ("area", "function", module),
+                ("norm2", "function", module),
+            ]
+        );
+
+        // Methods of one function are told apart by their signatures.
+        let signature = |e: &SemanticEntity| {
+            e.metadata
+                .as_ref()
+                .and_then(|m| m.get("julia.signature"))
+                .cloned()
+        };
+        assert_eq!(signature(&entities[2]).as_deref(), Some("(Square)"));
+        assert_eq!(
+            signature(&entities[3]).as_deref(),
+            Some("(Circle, Float64)")
+        );
+        assert_eq!(signature(&entities[4]).as_deref(), Some("(Any)"));
+        assert_ne!(entities[2].id, entities[3].id);
+        assert_eq!(entities[2].start_line, 8);
+        assert_eq!(entities[2].end_line, 11);
+    }
+
     #[test]
     #[cfg(feature = "lang-lua")]
     fn test_lua_entity_extraction() {
@@ -1473,6 +2153,63 @@ return M
         assert_eq!(entities.len(), 4, "only functions, got: {:?}", names);
     }
 
+    #[test]
+    #[cfg(feature = "lang-lua")]
+    fn test_lua_function_assignments() {
+        let code = r#"local M = {}
+
+function update(dt)
+    return dt
+end
+
+local function clamp(x)
+    return math.max(0, x)
+end
+
+local draw = function()
+    return clamp(1)
+end
+
+M.load = function(path)
+    local inner = function() return path end
+    return inner()
+end
+
+return M
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "main.lua");
+        let summary: Vec<(&str, &str)> = entities
+            .iter()
+            .map(|e| (e.entity_type.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("function", "update"),
+                ("function", "clamp"),
+                ("function", "draw"),
+                ("function", "load"),
+                ("function", "inner"),
+            ]
+        );
+
+        let table_of = |name: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name)
+                .and_then(|e| e.metadata.as_ref())
+                .and_then(|m| m.get("lua.table"))
+                .cloned()
+        };
+        assert_eq!(table_of("load").as_deref(), Some("M"));
+        assert_eq!(table_of("draw"), None);
+
+        let load = entities.iter().find(|e| e.name == "load").unwrap();
+        let inner = entities.iter().find(|e| e.name == "inner").unwrap();
+        assert_eq!(inner.parent_id.as_deref(), Some(load.id.as_str()));
+    }
+
     #[test]
     #[cfg(feature = "lang-fish")]
     fn test_fish_entity_extraction() {
@@ -1769,6 +2506,52 @@ class L1 {
         assert!(inner.parent_id.is_some(), "inner should have parent_id");
     }
 
+    #[test]
+    fn test_rust_impl_methods_are_children_of_the_impl() {
+        let code = "struct Foo;\n\nimpl Foo {\n    fn new() -> Self {\n        Foo\n    }\n\n    pub fn bar(&self) -> u32 {\n        1\n    }\n}\n";
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "foo.rs");
+
+        let imp = entities
+            .iter()
+            .find(|e| e.entity_type == "impl")
+            .expect("impl entity");
+        assert_eq!(imp.name, "Foo");
+        assert_eq!(imp.parent_id, None);
+
+        for method in ["new", "bar"] {
+            let entity = entities
+                .iter()
+                .find(|e| e.name == method)
+                .unwrap_or_else(|| panic!("{method} not extracted: {entities:?}"));
+            assert_eq!(entity.parent_id.as_deref(), Some(imp.id.as_str()));
+            assert_eq!(entity.id, format!("{}::{method}", imp.id));
+        }
+    }
+
+    #[test]
+    fn test_rust_trait_method_signatures_are_methods() {
+        let code = "trait Store {\n    fn get(&self, key: &str) -> Option<String>;\n    fn has(&self, key: &str) -> bool {\n        self.get(key).is_some()\n    }\n}\n\nextern \"C\" {\n    fn abs(x: i32) -> i32;\n}\n";
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "store.rs");
+        let kind = |name: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name)
+                .map(|e| (e.entity_type.as_str(), e.parent_id.as_deref()))
+        };
+
+        assert_eq!(
+            kind("get"),
+            Some(("method", Some("store.rs::trait::Store")))
+        );
+        assert_eq!(
+            kind("has"),
+            Some(("function", Some("store.rs::trait::Store")))
+        );
+        assert_eq!(kind("abs"), Some(("function", None)));
+    }
+
     #[test]
     fn test_rust_impl_blocks_unique_names() {
         let code = r#"
@@ -1952,12 +2735,12 @@ resource "aws_instance" "web" {
             names
         );
         assert!(
-            names.contains(&"resource.aws_instance.web"),
+            names.contains(&"aws_instance.web"),
             "Should find resource block, got: {:?}",
             names
         );
         assert!(
-            names.contains(&"resource.aws_instance.web.lifecycle"),
+            names.contains(&"aws_instance.web.lifecycle"),
             "Should find nested lifecycle block with qualified name, got: {:?}",
             names
         );
@@ -1974,7 +2757,7 @@ resource "aws_instance" "web" {
 
         let lifecycle = entities
             .iter()
-            .find(|e| e.name == "resource.aws_instance.web.lifecycle")
+            .find(|e| e.name == "aws_instance.web.lifecycle")
             .unwrap();
         assert!(
             lifecycle.parent_id.is_some(),
@@ -1986,6 +2769,77 @@ resource "aws_instance" "web" {
         );
     }
 
+    #[test]
+    fn test_hcl_resource_attribute_change_is_one_modified_block() {
+        use crate::git::types::{FileChange, FileStatus};
+        use crate::model::change::ChangeType;
+        use crate::parser::differ::compute_semantic_diff;
+        use crate::parser::registry::ParserRegistry;
+
+        let before = r#"resource "aws_s3_bucket" "logs" {
+  bucket = "acme-logs"
+  acl    = "private"
+
+  versioning {
+    enabled = true
+  }
+}
+
+data "aws_iam_policy_document" "read" {
+  statement {
+    actions = ["s3:GetObject"]
+  }
+}
+
+module "vpc" {
+  source = "./vpc"
+}
+
+output "bucket_arn" {
+  value = aws_s3_bucket.logs.arn
+}
+"#;
+        let after = before.replace("\"private\"", "\"log-delivery-write\"");
+
+        let names: Vec<String> = CodeParserPlugin
+            .extract_entities(before, "main.tf")
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "aws_s3_bucket.logs",
+                "aws_s3_bucket.logs.versioning",
+                "data.aws_iam_policy_document.read",
+                "data.aws_iam_policy_document.read.statement",
+                "module.vpc",
+                "output.bucket_arn",
+            ]
+        );
+
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(CodeParserPlugin));
+        let diff = compute_semantic_diff(
+            &[FileChange {
+                file_path: "main.tf".to_string(),
+                status: FileStatus::Modified,
+                old_file_path: None,
+                before_content: Some(before.to_string()),
+                after_content: Some(after),
+            }],
+            &registry,
+            None,
+            None,
+        );
+        let changes: Vec<(&str, ChangeType)> = diff
+            .changes
+            .iter()
+            .map(|c| (c.entity_name.as_str(), c.change_type))
+            .collect();
+        assert_eq!(changes, vec![("aws_s3_bucket.logs", ChangeType::Modified)]);
+    }
+
     #[test]
     fn test_kotlin_entity_extraction() {
         let code = r#"
@@ -2452,6 +3306,25 @@ func GetFlags() []FeatureFlag {
         );
     }
 
+    #[test]
+    fn test_go_generic_receiver_links_to_base_type() {
+        let code = "package ds\n\ntype Stack[T any] struct {\n\titems []T\n}\n\nfunc (s *Stack[T]) Push(v T) {\n\ts.items = append(s.items, v)\n}\n";
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "stack.go");
+        let push = entities
+            .iter()
+            .find(|e| e.name == "Push")
+            .expect("Push should be extracted");
+        assert_eq!(push.parent_id.as_deref(), Some("stack.go::type::Stack"));
+        assert_eq!(
+            push.metadata
+                .as_ref()
+                .and_then(|m| m.get("go.receiver"))
+                .map(String::as_str),
+            Some("*Stack[T]")
+        );
+    }
+
     #[test]
     fn test_go_grouped_var_declaration() {
         let code = r#"package test
@@ -2723,6 +3596,55 @@ CREATE TABLE billing.invoices (id INT);
         );
     }
 
+    #[test]
+    fn test_dart_widget_members_nest_under_class() {
+        let code = r#"
+import 'package:flutter/widgets.dart';
+
+class Counter extends StatelessWidget {
+  final String title;
+  final int count;
+
+  const Counter({super.key, required this.title, this.count = 0});
+
+  Counter.empty() : title = '', count = 0;
+
+  @override
+  Widget build(BuildContext context) {
+    return Text('$title: $count');
+  }
+}
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "counter.dart");
+        let find = |name: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name && e.parent_id.is_some())
+                .unwrap_or_else(|| panic!("missing member {name}: {entities:#?}"))
+        };
+        let class = entities
+            .iter()
+            .find(|e| e.name == "Counter" && e.entity_type == "class")
+            .expect("Counter class");
+
+        for (name, entity_type) in [
+            ("build", "method"),
+            ("title", "field"),
+            ("count", "field"),
+            ("Counter", "constructor"),
+            ("Counter.empty", "constructor"),
+        ] {
+            let member = find(name);
+            assert_eq!(member.entity_type, entity_type, "{name}");
+            assert_eq!(
+                member.parent_id.as_deref(),
+                Some(class.id.as_str()),
+                "{name}"
+            );
+        }
+    }
+
     #[test]
     fn test_dart_top_level_function_includes_body() {
         let code = r#"
@@ -3502,6 +4424,66 @@ type Predicate[A] = A => Boolean
         );
     }
 
+    #[test]
+    fn test_scala_case_class_and_companion_object_are_distinct() {
+        let code = r#"
+trait Shape {
+  def area: Double
+}
+
+case class Circle(radius: Double) extends Shape {
+  def area: Double = math.Pi * radius * radius
+}
+
+object Circle {
+  val Unit = Circle(1.0)
+  var created = 0
+  def fromDiameter(d: Double): Circle = Circle(d / 2)
+}
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "Shapes.scala");
+        let find = |entity_type: &str, name: &str| {
+            entities
+                .iter()
+                .find(|e| e.entity_type == entity_type && e.name == name)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "missing {entity_type} {name}, got: {:?}",
+                        entities
+                            .iter()
+                            .map(|e| (&e.entity_type, &e.name))
+                            .collect::<Vec<_>>()
+                    )
+                })
+        };
+
+        let shape = find("trait", "Shape");
+        let class = find("class", "Circle");
+        let companion = find("object", "Circle");
+        assert_ne!(class.id, companion.id);
+
+        let members_of = |parent: &SemanticEntity| {
+            let mut names: Vec<(&str, &str)> = entities
+                .iter()
+                .filter(|e| e.parent_id.as_deref() == Some(parent.id.as_str()))
+                .map(|e| (e.entity_type.as_str(), e.name.as_str()))
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(members_of(shape), vec![("function", "area")]);
+        assert_eq!(members_of(class), vec![("function", "area")]);
+        assert_eq!(
+            members_of(companion),
+            vec![
+                ("function", "fromDiameter"),
+                ("val", "Unit"),
+                ("var", "created"),
+            ]
+        );
+    }
+
     #[test]
     fn test_zig_entity_extraction() {
         let code = r#"
@@ -3582,6 +4564,54 @@ test "basic addition" {
         assert_eq!(types["Person"], "struct");
     }
 
+    #[test]
+    fn test_zig_struct_methods_nest_under_const() {
+        let code = r#"
+const Point = struct {
+    x: i32,
+    y: i32,
+
+    pub fn len(self: Point) i32 {
+        const sum = self.x + self.y;
+        return sum;
+    }
+};
+
+const Shape = union(enum) {
+    circle: f32,
+
+    fn area(self: Shape) f32 {
+        return 0;
+    }
+};
+
+fn origin() Point {
+    return .{ .x = 0, .y = 0 };
+}
+"#;
+        let plugin = CodeParserPlugin;
+        let entities = plugin.extract_entities(code, "point.zig");
+        let find = |name: &str| {
+            entities
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap_or_else(|| panic!("missing {name}: {entities:#?}"))
+        };
+
+        let point = find("Point");
+        assert_eq!(point.entity_type, "struct");
+        assert_eq!(find("len").parent_id.as_deref(), Some(point.id.as_str()));
+        assert_eq!(
+            find("area").parent_id.as_deref(),
+            Some(find("Shape").id.as_str())
+        );
+        assert_eq!(find("origin").parent_id, None);
+        assert!(
+            entities.iter().all(|e| e.name != "sum"),
+            "locals inside methods are not entities: {entities:#?}"
+        );
+    }
+
     #[test]
     #[cfg(feature = "lang-edn")]
     fn test_edn_deps_edn_map_entries() {
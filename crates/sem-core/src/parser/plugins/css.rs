@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+use crate::model::entity::{build_entity_id, build_entity_id_disambiguated, SemanticEntity};
+use crate::parser::plugin::SemanticParserPlugin;
+use crate::utils::hash::content_hash;
+
+/// Stylesheets: every rule set is an entity named by its selector, and every
+/// at-rule (`@media`, `@keyframes`, `@import`) by its prelude. Rules nested
+/// in at-rules, or in other rules as SCSS allows, become children.
+pub struct CssParserPlugin;
+
+impl SemanticParserPlugin for CssParserPlugin {
+    fn id(&self) -> &str {
+        "css"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".css", ".scss"]
+    }
+
+    fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
+        let scanner = CssScanner {
+            src: content,
+            line_starts: line_starts(content),
+            line_comments: file_path.ends_with(".scss"),
+        };
+        let mut entities = Vec::new();
+        scanner.extract(0, content.len(), None, file_path, &mut entities);
+        entities
+    }
+}
+
+struct CssBlock {
+    prelude: String,
+    start: usize,
+    /// Byte offset just past the closing `}` or `;`.
+    end: usize,
+    /// Byte range between the braces; `None` for `@import ...;` statements.
+    body: Option<(usize, usize)>,
+}
+
+struct CssScanner<'a> {
+    src: &'a str,
+    line_starts: Vec<usize>,
+    /// SCSS allows `//` comments; in plain CSS `//` can start a URL.
+    line_comments: bool,
+}
+
+impl CssScanner<'_> {
+    fn extract(
+        &self,
+        from: usize,
+        to: usize,
+        parent_id: Option<&str>,
+        file_path: &str,
+        entities: &mut Vec<SemanticEntity>,
+    ) {
+        let blocks = self.scan_blocks(from, to);
+
+        // Selectors can legitimately repeat; disambiguate those by line.
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for block in &blocks {
+            *name_counts.entry(block.prelude.as_str()).or_default() += 1;
+        }
+
+        for block in &blocks {
+            let entity_type = if block.prelude.starts_with('@') {
+                "at_rule"
+            } else {
+                "rule"
+            };
+            let start_line = self.line_of(block.start);
+            let end_line = self.line_of(block.end.saturating_sub(1));
+            let id = if name_counts[block.prelude.as_str()] > 1 {
+                build_entity_id_disambiguated(
+                    file_path,
+                    entity_type,
+                    &block.prelude,
+                    parent_id,
+                    start_line,
+                )
+            } else {
+                build_entity_id(file_path, entity_type, &block.prelude, parent_id)
+            };
+            let content = &self.src[block.start..block.end];
+            entities.push(SemanticEntity {
+                id: id.clone(),
+                file_path: file_path.to_string(),
+                entity_type: entity_type.to_string(),
+                name: block.prelude.clone(),
+                parent_id: parent_id.map(String::from),
+                content_hash: content_hash(content),
+                structural_hash: Some(content_hash(&self.normalize(content))),
+                content: content.to_string(),
+                start_line,
+                end_line,
+                start_byte: Some(block.start),
+                end_byte: Some(block.end),
+                name_start: None,
+                name_end: None,
+                metadata: None,
+                stable_id: None,
+            });
+
+            if let Some((body_start, body_end)) = block.body {
+                self.extract(body_start, body_end, Some(&id), file_path, entities);
+            }
+        }
+    }
+
+    /// Blocks directly inside `from..to`. Declarations (`color: red;`) are
+    /// skipped; only at-rule statements end in `;` and still count.
+    fn scan_blocks(&self, from: usize, to: usize) -> Vec<CssBlock> {
+        let bytes = self.src.as_bytes();
+        let mut blocks = Vec::new();
+        let mut i = from;
+        while i < to {
+            i = self.skip_trivia(i, to);
+            if i >= to {
+                break;
+            }
+            let start = i;
+            let mut parens = 0usize;
+            while i < to {
+                match bytes[i] {
+                    b'"' | b'\'' => {
+                        i = skip_string(bytes, i, to);
+                        continue;
+                    }
+                    b'/' if self.comment_at(i) => {
+                        i = self.skip_comment(i, to);
+                        continue;
+                    }
+                    b'#' if bytes.get(i + 1) == Some(&b'{') => {
+                        // SCSS interpolation: `#{$name}` is part of the prelude.
+                        i = self.skip_braces(i + 1, to);
+                        continue;
+                    }
+                    b'(' => parens += 1,
+                    b')' => parens = parens.saturating_sub(1),
+                    b';' | b'{' | b'}' if parens == 0 => break,
+                    _ => {}
+                }
+                i += 1;
+            }
+            if i >= to {
+                break;
+            }
+
+            let prelude = self.normalize(&self.src[start..i]);
+            match bytes[i] {
+                b';' => {
+                    i += 1;
+                    if prelude.starts_with('@') {
+                        blocks.push(CssBlock {
+                            prelude,
+                            start,
+                            end: i,
+                            body: None,
+                        });
+                    }
+                }
+                b'{' => {
+                    let end = self.skip_braces(i, to);
+                    let body_end = if end <= to && bytes[end - 1] == b'}' {
+                        end - 1
+                    } else {
+                        end
+                    };
+                    blocks.push(CssBlock {
+                        prelude,
+                        start,
+                        end,
+                        body: Some((i + 1, body_end)),
+                    });
+                    i = end;
+                }
+                // A stray `}`: skip it rather than ending the scan.
+                _ => i += 1,
+            }
+        }
+        blocks
+    }
+
+    /// Offset just past the `}` matching the `{` at `open`.
+    fn skip_braces(&self, open: usize, to: usize) -> usize {
+        let bytes = self.src.as_bytes();
+        let mut depth = 0usize;
+        let mut i = open;
+        while i < to {
+            match bytes[i] {
+                b'"' | b'\'' => {
+                    i = skip_string(bytes, i, to);
+                    continue;
+                }
+                b'/' if self.comment_at(i) => {
+                    i = self.skip_comment(i, to);
+                    continue;
+                }
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        to
+    }
+
+    fn skip_trivia(&self, mut i: usize, to: usize) -> usize {
+        let bytes = self.src.as_bytes();
+        while i < to {
+            if bytes[i].is_ascii_whitespace() {
+                i += 1;
+            } else if self.comment_at(i) {
+                i = self.skip_comment(i, to);
+            } else {
+                break;
+            }
+        }
+        i
+    }
+
+    fn comment_at(&self, i: usize) -> bool {
+        let rest = &self.src.as_bytes()[i..];
+        rest.starts_with(b"/*") || (self.line_comments && rest.starts_with(b"//"))
+    }
+
+    fn skip_comment(&self, i: usize, to: usize) -> usize {
+        let (terminator, rest) = if self.src[i..].starts_with("/*") {
+            ("*/", i + 2)
+        } else {
+            ("\n", i + 2)
+        };
+        match self.src[rest..to].find(terminator) {
+            Some(at) => rest + at + terminator.len(),
+            None => to,
+        }
+    }
+
+    /// Text with comments dropped and whitespace runs collapsed.
+    fn normalize(&self, text: &str) -> String {
+        let scanner = CssScanner {
+            src: text,
+            line_starts: Vec::new(),
+            line_comments: self.line_comments,
+        };
+        let bytes = text.as_bytes();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < text.len() {
+            if scanner.comment_at(i) {
+                i = scanner.skip_comment(i, text.len());
+                out.push(' ');
+            } else if matches!(bytes[i], b'"' | b'\'') {
+                let end = skip_string(bytes, i, text.len());
+                out.push_str(&text[i..end]);
+                i = end;
+            } else {
+                let next = text[i..]
+                    .find(['"', '\'', '/'])
+                    .map_or(text.len(), |at| i + at.max(1));
+                out.push_str(&text[i..next]);
+                i = next;
+            }
+        }
+        out.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn line_of(&self, byte: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= byte)
+    }
+}
+
+fn line_starts(content: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// Offset just past the string literal opening at `i`.
+fn skip_string(bytes: &[u8], i: usize, to: usize) -> usize {
+    let quote = bytes[i];
+    let mut j = i + 1;
+    while j < to {
+        match bytes[j] {
+            b'\\' => j += 2,
+            b if b == quote => return j + 1,
+            b'\n' => return j,
+            _ => j += 1,
+        }
+    }
+    to
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
+
+    fn css_diff(path: &str, before: &str, after: &str) -> Vec<(String, ChangeType)> {
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(CssParserPlugin));
+        let changes = vec![FileChange {
+            file_path: path.to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some(before.to_string()),
+            after_content: Some(after.to_string()),
+        }];
+        compute_semantic_diff(&changes, &registry, None, None)
+            .changes
+            .into_iter()
+            .filter(|c| c.entity_type != "orphan")
+            .map(|c| (c.entity_name, c.change_type))
+            .collect()
+    }
+
+    const STYLES: &str = "/* buttons */
+.button,
+.button--primary {
+  color: white;
+  background: url(\"a;b.png\");
+}
+
+#header > nav a:hover {
+  text-decoration: underline;
+}
+";
+
+    #[test]
+    fn rule_sets_are_named_by_selector() {
+        let entities = CssParserPlugin.extract_entities(STYLES, "styles.css");
+        let summary: Vec<(&str, &str, usize, usize)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.entity_type.as_str(),
+                    e.start_line,
+                    e.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (".button, .button--primary", "rule", 2, 6),
+                ("#header > nav a:hover", "rule", 8, 10),
+            ]
+        );
+        assert!(entities[0].content.starts_with(".button,\n"));
+        assert!(entities[0].content.ends_with('}'));
+        assert_eq!(entities[1].id, "styles.css::rule::#header > nav a:hover");
+    }
+
+    #[test]
+    fn added_declaration_modifies_only_its_rule() {
+        let after = STYLES.replace(
+            "  text-decoration: underline;\n",
+            "  text-decoration: underline;\n  color: red;\n",
+        );
+        assert_eq!(
+            css_diff("styles.css", STYLES, &after),
+            vec![("#header > nav a:hover".to_string(), ChangeType::Modified)]
+        );
+    }
+
+    #[test]
+    fn at_rules_contain_their_rules() {
+        let content = "@import url(\"base.css\");\n\n@media (max-width: 600px) {\n  .a { color: red; }\n  .b { color: blue; }\n}\n";
+        let entities = CssParserPlugin.extract_entities(content, "m.css");
+        let summary: Vec<(&str, Option<&str>)> = entities
+            .iter()
+            .map(|e| (e.name.as_str(), e.parent_id.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("@import url(\"base.css\")", None),
+                ("@media (max-width: 600px)", None),
+                (".a", Some("m.css::at_rule::@media (max-width: 600px)")),
+                (".b", Some("m.css::at_rule::@media (max-width: 600px)")),
+            ]
+        );
+
+        let after = content.replace(".b { color: blue; }", ".b { color: green; }");
+        assert_eq!(
+            css_diff("m.css", content, &after),
+            vec![(".b".to_string(), ChangeType::Modified)]
+        );
+    }
+
+    #[test]
+    fn scss_nesting_interpolation_and_line_comments() {
+        let content = "// theme\n.card {\n  padding: 0;\n  // spacing\n  &__title-#{$size} {\n    margin: 0;\n  }\n}\n.card {\n  color: red;\n}\n";
+        let entities = CssParserPlugin.extract_entities(content, "card.scss");
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "card.scss::rule::.card@L2",
+                "card.scss::rule::.card@L2::&__title-#{$size}",
+                "card.scss::rule::.card@L9",
+            ]
+        );
+    }
+}
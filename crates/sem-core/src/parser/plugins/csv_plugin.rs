@@ -54,7 +54,10 @@ impl SemanticParserPlugin for CsvParserPlugin {
                 end_line: i + 1,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: Some(metadata),
+                stable_id: None,
             });
         }
 
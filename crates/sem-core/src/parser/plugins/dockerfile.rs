@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use crate::model::entity::{build_entity_id, SemanticEntity};
+use crate::parser::plugin::SemanticParserPlugin;
+use crate::utils::hash::content_hash;
+
+pub struct DockerfileParserPlugin;
+
+impl SemanticParserPlugin for DockerfileParserPlugin {
+    fn id(&self) -> &str {
+        "dockerfile"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".dockerfile"]
+    }
+
+    fn filenames(&self) -> &[&str] {
+        &["Dockerfile", "Containerfile"]
+    }
+
+    fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
+        // Each FROM starts a build stage, named by its `AS` alias (or
+        // `stage[n]`), and the instructions after it are the stage's
+        // children, keyed by keyword and position among same-keyword
+        // instructions in the stage (`RUN[2]`). Instructions before the
+        // first FROM (global ARGs) are top-level.
+        let lines: Vec<&str> = content.lines().collect();
+        let instructions = find_instructions(&lines);
+
+        let mut entities = Vec::new();
+        let mut stage: Option<(usize, String)> = None; // (entity index, id)
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut stage_count = 0;
+
+        for instruction in &instructions {
+            if instruction.keyword == "FROM" {
+                stage_count += 1;
+                let name = stage_alias(&instruction.arguments)
+                    .unwrap_or_else(|| format!("stage[{stage_count}]"));
+                let id = build_entity_id(file_path, "stage", &name, None);
+                entities.push(dockerfile_entity(
+                    &lines,
+                    id.clone(),
+                    file_path,
+                    "stage",
+                    name,
+                    None,
+                    instruction.start_line,
+                    instruction.end_line,
+                ));
+                stage = Some((entities.len() - 1, id));
+                counts.clear();
+                continue;
+            }
+
+            let count = counts.entry(instruction.keyword.clone()).or_insert(0);
+            *count += 1;
+            let name = format!("{}[{}]", instruction.keyword, count);
+            let parent_id = stage.as_ref().map(|(_, id)| id.as_str());
+            entities.push(dockerfile_entity(
+                &lines,
+                build_entity_id(file_path, "instruction", &name, parent_id),
+                file_path,
+                "instruction",
+                name,
+                parent_id,
+                instruction.start_line,
+                instruction.end_line,
+            ));
+
+            // The stage runs through its last instruction.
+            if let Some((index, _)) = stage {
+                let stage_entity = &mut entities[index];
+                stage_entity.end_line = instruction.end_line;
+                stage_entity.content =
+                    lines[stage_entity.start_line - 1..instruction.end_line].join("\n");
+                stage_entity.content_hash = content_hash(&stage_entity.content);
+            }
+        }
+
+        entities
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dockerfile_entity(
+    lines: &[&str],
+    id: String,
+    file_path: &str,
+    entity_type: &str,
+    name: String,
+    parent_id: Option<&str>,
+    start_line: usize,
+    end_line: usize,
+) -> SemanticEntity {
+    let content = lines[start_line - 1..end_line].join("\n");
+    SemanticEntity {
+        id,
+        file_path: file_path.to_string(),
+        entity_type: entity_type.to_string(),
+        name,
+        parent_id: parent_id.map(String::from),
+        content_hash: content_hash(&content),
+        structural_hash: None,
+        content,
+        start_line,
+        end_line,
+        start_byte: None,
+        end_byte: None,
+        name_start: None,
+        name_end: None,
+        metadata: None,
+        stable_id: None,
+    }
+}
+
+struct Instruction {
+    /// Upper-cased instruction keyword.
+    keyword: String,
+    /// Everything after the keyword, continuation lines joined.
+    arguments: String,
+    start_line: usize,
+    end_line: usize,
+}
+
+/// `FROM image AS name` → `name`.
+fn stage_alias(arguments: &str) -> Option<String> {
+    let words: Vec<&str> = arguments.split_whitespace().collect();
+    let as_index = words.iter().position(|w| w.eq_ignore_ascii_case("as"))?;
+    words.get(as_index + 1).map(|alias| alias.to_string())
+}
+
+/// Split a Dockerfile into instructions, following line continuations
+/// (honouring an `# escape=` directive) and `<<EOF` heredocs.
+fn find_instructions(lines: &[&str]) -> Vec<Instruction> {
+    let escape = escape_char(lines);
+    let mut instructions = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            i += 1;
+            continue;
+        }
+
+        let start_line = i + 1;
+        let (keyword, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+        let mut arguments = rest.trim().to_string();
+        let mut end = i;
+        // Blank and comment lines inside a continued instruction are
+        // dropped by Docker, so they don't end it.
+        let mut continued = trimmed.ends_with(escape);
+        while continued && end + 1 < lines.len() {
+            end += 1;
+            let next = lines[end].trim();
+            if next.is_empty() || next.starts_with('#') {
+                continue;
+            }
+            arguments.push(' ');
+            arguments.push_str(next);
+            continued = next.ends_with(escape);
+        }
+        for delimiter in heredoc_delimiters(&arguments) {
+            while end + 1 < lines.len() {
+                end += 1;
+                if lines[end].trim() == delimiter {
+                    break;
+                }
+            }
+        }
+
+        instructions.push(Instruction {
+            keyword: keyword.to_uppercase(),
+            arguments,
+            start_line,
+            end_line: end + 1,
+        });
+        i = end + 1;
+    }
+
+    instructions
+}
+
+/// The escape character from a leading `# escape=` parser directive.
+fn escape_char(lines: &[&str]) -> char {
+    for line in lines {
+        let Some(directive) = line.trim().strip_prefix('#') else {
+            break;
+        };
+        if let Some((key, value)) = directive.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("escape") {
+                if let Some(c) = value.trim().chars().next() {
+                    return c;
+                }
+            }
+        }
+    }
+    '\\'
+}
+
+/// Delimiters of heredocs opened in an instruction (`<<EOF`, `<<-"EOT"`).
+fn heredoc_delimiters(arguments: &str) -> Vec<String> {
+    let mut delimiters = Vec::new();
+    let mut rest = arguments;
+    while let Some(at) = rest.find("<<") {
+        rest = &rest[at + 2..];
+        let word = rest.trim_start_matches('-');
+        let word = word.trim_start_matches(['"', '\'']);
+        let delimiter: String = word
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if !delimiter.is_empty() {
+            delimiters.push(delimiter);
+        }
+    }
+    delimiters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::plugins::create_default_registry;
+
+    const DOCKERFILE: &str = r#"# syntax=docker/dockerfile:1
+ARG NODE_VERSION=20
+
+FROM node:${NODE_VERSION} AS build
+WORKDIR /app
+COPY package.json package-lock.json ./
+RUN npm ci \
+    # no audit in CI
+    --no-audit
+COPY . .
+RUN <<EOF
+npm run build
+npm prune --production
+EOF
+
+FROM gcr.io/distroless/nodejs20
+COPY --from=build /app/dist /app
+CMD ["/app/server.js"]
+"#;
+
+    fn dockerfile_diff(before: &str, after: &str) -> Vec<(String, ChangeType)> {
+        let changes = vec![FileChange {
+            file_path: "docker/Dockerfile".to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some(before.to_string()),
+            after_content: Some(after.to_string()),
+        }];
+        compute_semantic_diff(&changes, &create_default_registry(), None, None)
+            .changes
+            .into_iter()
+            .map(|c| (c.entity_id, c.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn stages_and_instructions_are_entities() {
+        let entities = DockerfileParserPlugin.extract_entities(DOCKERFILE, "Dockerfile");
+        let summary: Vec<(&str, usize, usize)> = entities
+            .iter()
+            .map(|e| (e.id.as_str(), e.start_line, e.end_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Dockerfile::instruction::ARG[1]", 2, 2),
+                ("Dockerfile::stage::build", 4, 14),
+                ("Dockerfile::stage::build::WORKDIR[1]", 5, 5),
+                ("Dockerfile::stage::build::COPY[1]", 6, 6),
+                ("Dockerfile::stage::build::RUN[1]", 7, 9),
+                ("Dockerfile::stage::build::COPY[2]", 10, 10),
+                ("Dockerfile::stage::build::RUN[2]", 11, 14),
+                ("Dockerfile::stage::stage[2]", 16, 18),
+                ("Dockerfile::stage::stage[2]::COPY[1]", 17, 17),
+                ("Dockerfile::stage::stage[2]::CMD[1]", 18, 18),
+            ]
+        );
+    }
+
+    #[test]
+    fn registry_matches_dockerfiles_by_name() {
+        let registry = create_default_registry();
+        for path in [
+            "Dockerfile",
+            "services/api/Dockerfile",
+            "dockerfile",
+            "Dockerfile.prod",
+            "api.dockerfile",
+            "Containerfile",
+        ] {
+            assert_eq!(
+                registry.get_explicit_plugin(path).map(|p| p.id()),
+                Some("dockerfile"),
+                "{path}"
+            );
+        }
+        assert!(registry.get_explicit_plugin("dockerfile.rs").unwrap().id() != "dockerfile");
+    }
+
+    #[test]
+    fn editing_one_run_is_one_modified_instruction() {
+        let after = DOCKERFILE.replace("npm run build\n", "npm run build -- --minify\n");
+        assert_eq!(
+            dockerfile_diff(DOCKERFILE, &after),
+            vec![(
+                "docker/Dockerfile::stage::build::RUN[2]".to_string(),
+                ChangeType::Modified
+            )]
+        );
+    }
+
+    #[test]
+    fn inserting_a_copy_is_one_added_instruction() {
+        let after = DOCKERFILE.replace(
+            "COPY --from=build /app/dist /app\n",
+            "COPY --from=build /app/dist /app\nCOPY --from=build /app/node_modules /app/node_modules\n",
+        );
+        assert_eq!(
+            dockerfile_diff(DOCKERFILE, &after),
+            vec![(
+                "docker/Dockerfile::stage::stage[2]::COPY[2]".to_string(),
+                ChangeType::Added
+            )]
+        );
+    }
+}
@@ -49,7 +49,10 @@ impl SemanticParserPlugin for ErbParserPlugin {
             end_line: lines.len(),
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         });
 
         // Parse with tree-sitter and extract tags
@@ -86,7 +89,10 @@ impl SemanticParserPlugin for ErbParserPlugin {
                             end_line: tag.end_line,
                             start_byte: None,
                             end_byte: None,
+                            name_start: None,
+                            name_end: None,
                             metadata: None,
+                            stable_id: None,
                         });
                     }
                 }
@@ -106,7 +112,10 @@ impl SemanticParserPlugin for ErbParserPlugin {
                         end_line: tag.end_line,
                         start_byte: None,
                         end_byte: None,
+                        name_start: None,
+                        name_end: None,
                         metadata: None,
+                        stable_id: None,
                     });
                 } // No separate Code variant needed; expressions cover all non-block tags
             }
@@ -2,9 +2,39 @@ use crate::model::entity::{build_entity_id, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
 use crate::utils::hash::content_hash;
 
-pub struct FallbackParserPlugin;
+/// Lines per chunk, or the cap on a block's length under
+/// [`FallbackChunking::Blocks`].
+pub const DEFAULT_CHUNK_SIZE: usize = 20;
 
-const CHUNK_SIZE: usize = 20;
+/// How the fallback plugin cuts a file no grammar claims into chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackChunking {
+    /// Fixed windows of this many lines. Inserting a line shifts every later
+    /// boundary, so every later chunk changes.
+    Fixed(usize),
+    /// Break at blank lines outside braces and after a line that closes the
+    /// outermost brace, so an edit only changes the block it lands in. A
+    /// block longer than this many lines is split, at its last blank line
+    /// when it has one.
+    Blocks(usize),
+}
+
+impl Default for FallbackChunking {
+    fn default() -> Self {
+        FallbackChunking::Blocks(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+#[derive(Default)]
+pub struct FallbackParserPlugin {
+    chunking: FallbackChunking,
+}
+
+impl FallbackParserPlugin {
+    pub fn new(chunking: FallbackChunking) -> Self {
+        Self { chunking }
+    }
+}
 
 impl SemanticParserPlugin for FallbackParserPlugin {
     fn id(&self) -> &str {
@@ -17,36 +47,174 @@ impl SemanticParserPlugin for FallbackParserPlugin {
 
     fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
         let lines: Vec<&str> = content.lines().collect();
-        let mut entities = Vec::new();
-
-        let mut i = 0;
-        while i < lines.len() {
-            let end = (i + CHUNK_SIZE).min(lines.len());
-            let chunk: Vec<&str> = lines[i..end].to_vec();
-            let chunk_content = chunk.join("\n");
-            let start_line = i + 1;
-            let end_line = end;
-            let name = format!("lines {start_line}-{end_line}");
-
-            entities.push(SemanticEntity {
-                id: build_entity_id(file_path, "chunk", &name, None),
-                file_path: file_path.to_string(),
-                entity_type: "chunk".to_string(),
-                name,
-                parent_id: None,
-                content_hash: content_hash(&chunk_content),
-                structural_hash: None,
-                content: chunk_content,
-                start_line,
-                end_line,
-                start_byte: None,
-                end_byte: None,
-                metadata: None,
-            });
-
-            i += CHUNK_SIZE;
+        let ranges = match self.chunking {
+            FallbackChunking::Fixed(size) => fixed_ranges(lines.len(), size.max(1)),
+            FallbackChunking::Blocks(max_lines) => block_ranges(&lines, max_lines.max(1)),
+        };
+
+        ranges
+            .into_iter()
+            .map(|(start, end)| {
+                let chunk_content = lines[start..end].join("\n");
+                let start_line = start + 1;
+                let end_line = end;
+                let name = format!("lines {start_line}-{end_line}");
+
+                SemanticEntity {
+                    id: build_entity_id(file_path, "chunk", &name, None),
+                    file_path: file_path.to_string(),
+                    entity_type: "chunk".to_string(),
+                    name,
+                    parent_id: None,
+                    content_hash: content_hash(&chunk_content),
+                    structural_hash: None,
+                    content: chunk_content,
+                    start_line,
+                    end_line,
+                    start_byte: None,
+                    end_byte: None,
+                    name_start: None,
+                    name_end: None,
+                    metadata: None,
+                    stable_id: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// `[start, end)` line ranges of `size` lines each.
+fn fixed_ranges(line_count: usize, size: usize) -> Vec<(usize, usize)> {
+    (0..line_count)
+        .step_by(size)
+        .map(|start| (start, (start + size).min(line_count)))
+        .collect()
+}
+
+/// `[start, end)` line ranges of blank-line and brace delimited blocks, with
+/// blank lines left out at either end.
+fn block_ranges(lines: &[&str], max_lines: usize) -> Vec<(usize, usize)> {
+    let is_blank = |i: usize| lines[i].trim().is_empty();
+    let mut ranges = Vec::new();
+    let mut push = |mut start: usize, mut end: usize| {
+        while start < end && is_blank(start) {
+            start += 1;
+        }
+        while end > start && is_blank(end - 1) {
+            end -= 1;
+        }
+        if start < end {
+            ranges.push((start, end));
+        }
+    };
+
+    let mut start: Option<usize> = None;
+    let mut last_blank: Option<usize> = None;
+    let mut depth = 0usize;
+    for (i, line) in lines.iter().enumerate() {
+        if is_blank(i) {
+            if let Some(s) = start {
+                if depth == 0 {
+                    push(s, i);
+                    start = None;
+                } else {
+                    last_blank = Some(i);
+                }
+            }
+            continue;
+        }
+
+        let mut s = *start.get_or_insert(i);
+        if i - s >= max_lines {
+            // Full: cut at the block's last blank line, else right here.
+            let cut = last_blank.filter(|&b| b > s).unwrap_or(i);
+            push(s, cut);
+            s = cut;
+            start = Some(s);
+            last_blank = None;
+        }
+
+        let opened = depth > 0;
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
         }
+        if opened && depth == 0 {
+            push(s, i + 1);
+            start = None;
+            last_blank = None;
+        }
+    }
+    if let Some(s) = start {
+        push(s, lines.len());
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn long_file() -> Vec<String> {
+        (0..12)
+            .map(|block| format!("block {block} a\nblock {block} b\nblock {block} c\n"))
+            .collect()
+    }
+
+    fn hashes(plugin: &FallbackParserPlugin, content: &str) -> HashSet<String> {
+        plugin
+            .extract_entities(content, "notes.unknown")
+            .into_iter()
+            .map(|entity| entity.content_hash)
+            .collect()
+    }
+
+    #[test]
+    fn inserting_a_line_changes_one_block_chunk() {
+        let blocks = long_file();
+        let before = blocks.join("\n");
+        let mut edited = blocks.clone();
+        edited[1] = format!("inserted\n{}", edited[1]);
+        let after = edited.join("\n");
+
+        let plugin = FallbackParserPlugin::default();
+        let before_hashes = hashes(&plugin, &before);
+        let after_hashes = hashes(&plugin, &after);
+        assert_eq!(before_hashes.len(), 12);
+        assert_eq!(before_hashes.difference(&after_hashes).count(), 1);
+        assert_eq!(after_hashes.difference(&before_hashes).count(), 1);
+
+        // Fixed windows cascade: every chunk from the insertion on changes.
+        let fixed = FallbackParserPlugin::new(FallbackChunking::Fixed(DEFAULT_CHUNK_SIZE));
+        let fixed_before = hashes(&fixed, &before);
+        assert_eq!(
+            fixed_before.difference(&hashes(&fixed, &after)).count(),
+            fixed_before.len()
+        );
+    }
+
+    #[test]
+    fn blocks_break_after_closing_brace_and_cap_long_blocks() {
+        let content = "fn a {\n  x\n\n  y\n}\nfn b {\n  z\n}\n";
+        let plugin = FallbackParserPlugin::default();
+        let names: Vec<String> = plugin
+            .extract_entities(content, "f.unknown")
+            .into_iter()
+            .map(|entity| entity.name)
+            .collect();
+        assert_eq!(names, vec!["lines 1-5", "lines 6-8"]);
 
-        entities
+        let long: String = (0..5).map(|i| format!("line {i}\n")).collect();
+        let capped = FallbackParserPlugin::new(FallbackChunking::Blocks(2));
+        let names: Vec<String> = capped
+            .extract_entities(&long, "f.unknown")
+            .into_iter()
+            .map(|entity| entity.name)
+            .collect();
+        assert_eq!(names, vec!["lines 1-2", "lines 3-4", "lines 5-5"]);
     }
 }
@@ -0,0 +1,632 @@
+use std::collections::HashMap;
+
+use crate::model::entity::{build_entity_id, SemanticEntity};
+use crate::parser::plugin::SemanticParserPlugin;
+use crate::utils::hash::content_hash;
+
+pub struct GraphqlParserPlugin;
+
+impl SemanticParserPlugin for GraphqlParserPlugin {
+    fn id(&self) -> &str {
+        "graphql"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".graphql", ".gql", ".graphqls"]
+    }
+
+    fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
+        // Every SDL definition (type, input, enum, interface, union, scalar,
+        // directive, schema, and their `extend` forms) is an entity, with
+        // its fields or enum values as children. Named operations and
+        // fragments are entities too, without children.
+        let tokens = tokenize(content);
+        let definitions = Parser::new(content, &tokens).definitions();
+        let lines = LineIndex::new(content);
+
+        // `extend type Query { ... }` is commonly repeated; later
+        // occurrences get an ordinal so ids survive edits above them.
+        let mut occurrences: HashMap<(&str, &str), usize> = HashMap::new();
+        let mut entities = Vec::new();
+        for def in &definitions {
+            let occurrence = occurrences
+                .entry((def.kind.as_str(), def.name.as_str()))
+                .or_insert(0);
+            *occurrence += 1;
+            let mut id = build_entity_id(file_path, &def.kind, &def.name, None);
+            if *occurrence > 1 {
+                id = format!("{id}#{occurrence}");
+            }
+            entities.push(graphql_entity(
+                content,
+                &tokens,
+                &lines,
+                id.clone(),
+                file_path,
+                &def.kind,
+                &def.name,
+                None,
+                def.span,
+                def.token_range,
+            ));
+
+            let member_type = if def.kind.ends_with("enum") {
+                "enum_value"
+            } else {
+                "field"
+            };
+            for member in &def.members {
+                entities.push(graphql_entity(
+                    content,
+                    &tokens,
+                    &lines,
+                    build_entity_id(file_path, member_type, &member.name, Some(&id)),
+                    file_path,
+                    member_type,
+                    &member.name,
+                    Some(&id),
+                    member.span,
+                    member.token_range,
+                ));
+            }
+        }
+        entities
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn graphql_entity(
+    content: &str,
+    tokens: &[Token],
+    lines: &LineIndex,
+    id: String,
+    file_path: &str,
+    entity_type: &str,
+    name: &str,
+    parent_id: Option<&str>,
+    span: (usize, usize),
+    token_range: (usize, usize),
+) -> SemanticEntity {
+    let text = &content[span.0..span.1];
+    // Comments and layout don't change the schema.
+    let structural: Vec<&str> = tokens[token_range.0..token_range.1]
+        .iter()
+        .map(|token| &content[token.start..token.end])
+        .collect();
+    SemanticEntity {
+        id,
+        file_path: file_path.to_string(),
+        entity_type: entity_type.to_string(),
+        name: name.to_string(),
+        parent_id: parent_id.map(String::from),
+        content_hash: content_hash(text),
+        structural_hash: Some(content_hash(&structural.join(" "))),
+        content: text.to_string(),
+        start_line: lines.line_of(span.0),
+        end_line: lines.line_of(span.1.saturating_sub(1)),
+        start_byte: Some(span.0),
+        end_byte: Some(span.1),
+        name_start: None,
+        name_end: None,
+        metadata: None,
+        stable_id: None,
+    }
+}
+
+struct LineIndex(Vec<usize>);
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        Self(
+            std::iter::once(0)
+                .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        )
+    }
+
+    fn line_of(&self, byte: usize) -> usize {
+        self.0.partition_point(|&start| start <= byte)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Name,
+    String,
+    Punct(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Split SDL into names (and numbers), strings and punctuation, dropping
+/// whitespace, commas and `#` comments.
+fn tokenize(src: &str) -> Vec<Token> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b if b.is_ascii_whitespace() || b == b',' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                i = src[i..].find('\n').map_or(bytes.len(), |at| i + at);
+                continue;
+            }
+            b'"' if src[i..].starts_with("\"\"\"") => {
+                i = src[i + 3..]
+                    .find("\"\"\"")
+                    .map_or(bytes.len(), |at| i + 3 + at + 3);
+                TokenKind::String
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && !matches!(bytes[i], b'"' | b'\n') {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                TokenKind::String
+            }
+            b'.' if src[i..].starts_with("...") => {
+                i += 3;
+                TokenKind::Punct(b'.')
+            }
+            b if b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.' | b'+') => {
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'.' | b'+'))
+                {
+                    i += 1;
+                }
+                i = i.max(start + 1);
+                TokenKind::Name
+            }
+            b => {
+                // Step over the whole character so we stay on a char boundary.
+                i += src[i..].chars().next().map_or(1, char::len_utf8);
+                TokenKind::Punct(b)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    tokens
+}
+
+struct Member {
+    name: String,
+    span: (usize, usize),
+    token_range: (usize, usize),
+}
+
+struct Definition {
+    kind: String,
+    name: String,
+    span: (usize, usize),
+    token_range: (usize, usize),
+    members: Vec<Member>,
+}
+
+const TYPE_SYSTEM_KEYWORDS: &[&str] = &[
+    "type",
+    "input",
+    "enum",
+    "interface",
+    "union",
+    "scalar",
+    "directive",
+    "schema",
+];
+
+const EXECUTABLE_KEYWORDS: &[&str] = &["query", "mutation", "subscription", "fragment"];
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str, tokens: &'a [Token]) -> Self {
+        Self {
+            src,
+            tokens,
+            pos: 0,
+        }
+    }
+
+    fn definitions(mut self) -> Vec<Definition> {
+        let mut definitions = Vec::new();
+        while self.pos < self.tokens.len() {
+            let start = self.pos;
+            if self.peek_kind() == Some(TokenKind::String) {
+                self.pos += 1;
+            }
+            let extend = self.eat_name("extend");
+            let keyword = self.peek_name();
+            match keyword {
+                Some(keyword) if TYPE_SYSTEM_KEYWORDS.contains(&keyword) => {
+                    self.pos += 1;
+                    let name = match keyword {
+                        "schema" => "schema".to_string(),
+                        "directive" => {
+                            self.eat_punct(b'@');
+                            format!("@{}", self.next_name().unwrap_or_default())
+                        }
+                        _ => self.next_name().unwrap_or_default().to_string(),
+                    };
+                    let members = self.skip_to_definition_end(keyword == "enum");
+                    let kind = if extend {
+                        format!("extend_{keyword}")
+                    } else {
+                        keyword.to_string()
+                    };
+                    definitions.push(self.definition(start, kind, name, members));
+                }
+                Some(keyword) if !extend && EXECUTABLE_KEYWORDS.contains(&keyword) => {
+                    self.pos += 1;
+                    let name = match self.peek_kind() {
+                        Some(TokenKind::Name) => self.next_name().map(str::to_string),
+                        _ => None,
+                    };
+                    self.skip_to_definition_end(false);
+                    // Anonymous operations have nothing stable to key on.
+                    if let Some(name) = name {
+                        definitions.push(self.definition(
+                            start,
+                            keyword.to_string(),
+                            name,
+                            Vec::new(),
+                        ));
+                    }
+                }
+                _ => {
+                    // `{ ... }` shorthand query, or something we don't know.
+                    self.pos = start + 1;
+                    self.skip_to_definition_end(false);
+                }
+            }
+        }
+        definitions
+    }
+
+    fn definition(
+        &self,
+        start: usize,
+        kind: String,
+        name: String,
+        members: Vec<Member>,
+    ) -> Definition {
+        Definition {
+            kind,
+            name,
+            span: self.span(start, self.pos),
+            token_range: (start, self.pos),
+            members,
+        }
+    }
+
+    /// Advance past the rest of a definition: its `{ ... }` body if it has
+    /// one, otherwise up to the next definition. Returns the body's members.
+    fn skip_to_definition_end(&mut self, enum_body: bool) -> Vec<Member> {
+        let mut depth = 0usize;
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token.kind {
+                TokenKind::Punct(b'(' | b'[') => depth += 1,
+                TokenKind::Punct(b')' | b']') => depth = depth.saturating_sub(1),
+                TokenKind::Punct(b'{') if depth == 0 => {
+                    self.pos += 1;
+                    return self.members(enum_body);
+                }
+                TokenKind::String if depth == 0 => return Vec::new(),
+                TokenKind::Name if depth == 0 && self.starts_definition(self.pos) => {
+                    return Vec::new();
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        Vec::new()
+    }
+
+    fn starts_definition(&self, at: usize) -> bool {
+        let text = self.text(at);
+        text == "extend"
+            || TYPE_SYSTEM_KEYWORDS.contains(&text)
+            || EXECUTABLE_KEYWORDS.contains(&text)
+    }
+
+    /// Fields (or enum values) up to and including the closing `}`. Bodies of
+    /// operations and fragments are selection sets; they come back as
+    /// members but are dropped by the caller.
+    fn members(&mut self, enum_body: bool) -> Vec<Member> {
+        let mut members = Vec::new();
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token.kind {
+                TokenKind::Punct(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                TokenKind::String | TokenKind::Name => {
+                    let start = self.pos;
+                    if token.kind == TokenKind::String {
+                        self.pos += 1;
+                    }
+                    let Some(name) = self.next_name() else {
+                        continue;
+                    };
+                    let name = name.to_string();
+                    if !enum_body {
+                        self.skip_group(b'(', b')');
+                        if self.eat_punct(b':') {
+                            self.skip_type();
+                        }
+                        if self.eat_punct(b'=') {
+                            self.skip_value();
+                        }
+                    }
+                    self.skip_directives();
+                    members.push(Member {
+                        name,
+                        span: self.span(start, self.pos),
+                        token_range: (start, self.pos),
+                    });
+                }
+                TokenKind::Punct(b'{') => {
+                    // Nested selection set.
+                    self.skip_group(b'{', b'}');
+                }
+                _ => self.pos += 1,
+            }
+        }
+        members
+    }
+
+    fn skip_type(&mut self) {
+        if !self.skip_group(b'[', b']') {
+            self.next_name();
+        }
+        while self.eat_punct(b'!') {}
+    }
+
+    fn skip_value(&mut self) {
+        if !self.skip_group(b'[', b']') && !self.skip_group(b'{', b'}') {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_directives(&mut self) {
+        while self.eat_punct(b'@') {
+            self.next_name();
+            self.skip_group(b'(', b')');
+        }
+    }
+
+    /// Skip a balanced `open ... close` group starting at the cursor.
+    fn skip_group(&mut self, open: u8, close: u8) -> bool {
+        if self.peek_kind() != Some(TokenKind::Punct(open)) {
+            return false;
+        }
+        let mut depth = 0usize;
+        while let Some(token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match token.kind {
+                TokenKind::Punct(b) if b == open => depth += 1,
+                TokenKind::Punct(b) if b == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        true
+    }
+
+    fn peek_kind(&self) -> Option<TokenKind> {
+        self.tokens.get(self.pos).map(|token| token.kind)
+    }
+
+    fn peek_name(&self) -> Option<&'a str> {
+        match self.peek_kind()? {
+            TokenKind::Name => Some(self.text(self.pos)),
+            _ => None,
+        }
+    }
+
+    fn next_name(&mut self) -> Option<&'a str> {
+        let name = self.peek_name()?;
+        self.pos += 1;
+        Some(name)
+    }
+
+    fn eat_name(&mut self, name: &str) -> bool {
+        let found = self.peek_name() == Some(name);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_punct(&mut self, punct: u8) -> bool {
+        let found = self.peek_kind() == Some(TokenKind::Punct(punct));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn text(&self, at: usize) -> &'a str {
+        let token = self.tokens[at];
+        &self.src[token.start..token.end]
+    }
+
+    /// Byte span covering tokens `start..end`.
+    fn span(&self, start: usize, end: usize) -> (usize, usize) {
+        if end <= start {
+            let at = self.tokens.get(start).map_or(self.src.len(), |t| t.start);
+            return (at, at);
+        }
+        (self.tokens[start].start, self.tokens[end - 1].end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
+
+    const SCHEMA: &str = r#"# Users and their posts.
+"""
+A registered user.
+"""
+type User implements Node @key(fields: "id") {
+  id: ID!
+  "Display name"
+  name: String
+  posts(first: Int = 10, after: String): [Post!]! @deprecated(reason: "use feed")
+}
+
+input NewUser {
+  name: String!
+  tags: [String!] = []
+}
+
+enum Role {
+  ADMIN
+  MEMBER @deprecated
+}
+
+union SearchResult = User | Post
+
+scalar DateTime @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+
+extend type Query {
+  me: User
+}
+
+extend type Query {
+  search(term: String!): [SearchResult!]!
+}
+"#;
+
+    fn graphql_diff(before: &str, after: &str) -> Vec<(String, String, ChangeType)> {
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(GraphqlParserPlugin));
+        let changes = vec![FileChange {
+            file_path: "schema.graphql".to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some(before.to_string()),
+            after_content: Some(after.to_string()),
+        }];
+        compute_semantic_diff(&changes, &registry, None, None)
+            .changes
+            .into_iter()
+            .map(|c| (c.entity_type, c.entity_name, c.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn definitions_and_fields_are_entities() {
+        let entities = GraphqlParserPlugin.extract_entities(SCHEMA, "schema.graphql");
+        let summary: Vec<(&str, &str, usize, usize)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.id.as_str(),
+                    e.entity_type.as_str(),
+                    e.start_line,
+                    e.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("schema.graphql::type::User", "type", 2, 10),
+                ("schema.graphql::type::User::id", "field", 6, 6),
+                ("schema.graphql::type::User::name", "field", 7, 8),
+                ("schema.graphql::type::User::posts", "field", 9, 9),
+                ("schema.graphql::input::NewUser", "input", 12, 15),
+                ("schema.graphql::input::NewUser::name", "field", 13, 13),
+                ("schema.graphql::input::NewUser::tags", "field", 14, 14),
+                ("schema.graphql::enum::Role", "enum", 17, 20),
+                ("schema.graphql::enum::Role::ADMIN", "enum_value", 18, 18),
+                ("schema.graphql::enum::Role::MEMBER", "enum_value", 19, 19),
+                ("schema.graphql::union::SearchResult", "union", 22, 22),
+                ("schema.graphql::scalar::DateTime", "scalar", 24, 24),
+                ("schema.graphql::extend_type::Query", "extend_type", 26, 28),
+                ("schema.graphql::extend_type::Query::me", "field", 27, 27),
+                (
+                    "schema.graphql::extend_type::Query#2",
+                    "extend_type",
+                    30,
+                    32
+                ),
+                (
+                    "schema.graphql::extend_type::Query#2::search",
+                    "field",
+                    31,
+                    31
+                ),
+            ]
+        );
+        assert_eq!(
+            entities[3].content,
+            "posts(first: Int = 10, after: String): [Post!]! @deprecated(reason: \"use feed\")"
+        );
+    }
+
+    #[test]
+    fn making_a_field_non_null_modifies_only_that_field() {
+        let after = SCHEMA.replace("  name: String\n", "  name: String!\n");
+        assert_eq!(
+            graphql_diff(SCHEMA, &after),
+            vec![(
+                "field".to_string(),
+                "name".to_string(),
+                ChangeType::Modified
+            )]
+        );
+    }
+
+    #[test]
+    fn added_field_is_a_child_level_addition() {
+        let after = SCHEMA.replace("  id: ID!\n", "  id: ID!\n  email: String\n");
+        assert_eq!(
+            graphql_diff(SCHEMA, &after),
+            vec![("field".to_string(), "email".to_string(), ChangeType::Added)]
+        );
+    }
+
+    #[test]
+    fn named_operations_and_fragments_are_entities() {
+        let content = "query Viewer($id: ID!) {\n  user(id: $id) { ...UserFields }\n}\n\nfragment UserFields on User {\n  name\n}\n\n{ anonymous }\n";
+        let names: Vec<(String, String)> = GraphqlParserPlugin
+            .extract_entities(content, "ops.graphql")
+            .into_iter()
+            .map(|e| (e.entity_type, e.name))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("query".to_string(), "Viewer".to_string()),
+                ("fragment".to_string(), "UserFields".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+
+use crate::model::entity::{build_entity_id, SemanticEntity};
+use crate::parser::plugin::SemanticParserPlugin;
+use crate::utils::hash::content_hash;
+
+/// HTML documents: elements with an `id` attribute are entities named
+/// `tag#id`, and so are the page landmarks (`<body>`, `<nav>`, `<main>`,
+/// ...) even without one. Entities nest under the closest enclosing entity;
+/// landmarks that repeat among siblings get an ordinal (`section[2]`).
+pub struct HtmlParserPlugin;
+
+impl SemanticParserPlugin for HtmlParserPlugin {
+    fn id(&self) -> &str {
+        "html"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".html", ".htm"]
+    }
+
+    fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let line_of = |byte: usize| line_starts.partition_point(|&start| start <= byte);
+
+        let mut entities: Vec<SemanticEntity> = Vec::new();
+        // (tag, entity index if the element is an entity)
+        let mut open: Vec<(String, Option<usize>)> = Vec::new();
+        // Landmark occurrences per (parent entity, tag), for ordinals.
+        let mut landmark_counts: HashMap<(Option<String>, String), usize> = HashMap::new();
+
+        let close = |entities: &mut Vec<SemanticEntity>, index: Option<usize>, end: usize| {
+            if let Some(index) = index {
+                let entity = &mut entities[index];
+                let start = entity.start_byte.unwrap_or(0);
+                entity.content = content[start..end].to_string();
+                entity.content_hash = content_hash(&entity.content);
+                entity.end_line = line_of(end.saturating_sub(1).max(start));
+                entity.end_byte = Some(end);
+            }
+        };
+
+        for token in HtmlTokenizer::new(content) {
+            match token {
+                HtmlToken::Start {
+                    tag,
+                    id,
+                    start,
+                    end,
+                    self_closing,
+                } => {
+                    let parent_id = open
+                        .iter()
+                        .rev()
+                        .find_map(|(_, index)| index.map(|i| entities[i].id.clone()));
+                    let name = match id {
+                        Some(id) => Some(format!("{tag}#{id}")),
+                        None if LANDMARKS.contains(&tag.as_str()) => {
+                            let count = landmark_counts
+                                .entry((parent_id.clone(), tag.clone()))
+                                .or_insert(0);
+                            *count += 1;
+                            Some(if *count == 1 {
+                                tag.clone()
+                            } else {
+                                format!("{tag}[{count}]")
+                            })
+                        }
+                        None => None,
+                    };
+                    let index = name.map(|name| {
+                        entities.push(SemanticEntity {
+                            id: build_entity_id(file_path, "element", &name, parent_id.as_deref()),
+                            file_path: file_path.to_string(),
+                            entity_type: "element".to_string(),
+                            name,
+                            parent_id,
+                            content_hash: String::new(),
+                            structural_hash: None,
+                            content: String::new(),
+                            start_line: line_of(start),
+                            end_line: line_of(start),
+                            start_byte: Some(start),
+                            end_byte: None,
+                            name_start: None,
+                            name_end: None,
+                            metadata: None,
+                            stable_id: None,
+                        });
+                        entities.len() - 1
+                    });
+
+                    if self_closing || VOID_ELEMENTS.contains(&tag.as_str()) {
+                        close(&mut entities, index, end);
+                    } else if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) {
+                        let end_tag = find_raw_text_end(content, end, &tag);
+                        close(&mut entities, index, end_tag);
+                    } else {
+                        open.push((tag, index));
+                    }
+                }
+                HtmlToken::End { tag, start, end } => {
+                    // Close back to the matching start tag; elements left
+                    // open inside it (`<li>`, `<p>`) end where it ends.
+                    let Some(depth) = open.iter().rposition(|(open_tag, _)| *open_tag == tag)
+                    else {
+                        continue;
+                    };
+                    for (_, index) in open.drain(depth + 1..).rev() {
+                        close(&mut entities, index, start);
+                    }
+                    if let Some((_, index)) = open.pop() {
+                        close(&mut entities, index, end);
+                    }
+                }
+            }
+        }
+        for (_, index) in open.into_iter().rev() {
+            close(&mut entities, index, content.len());
+        }
+
+        entities
+    }
+}
+
+/// Sectioning and page-structure elements worth tracking without an `id`.
+const LANDMARKS: &[&str] = &[
+    "head", "body", "header", "nav", "main", "aside", "footer", "section", "article", "form",
+    "template", "dialog",
+];
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is not markup, so a `<` inside is just text.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Offset just past the `</tag>` closing a raw-text element whose content
+/// starts at `from`.
+fn find_raw_text_end(content: &str, from: usize, tag: &str) -> usize {
+    let lower = content[from..].to_ascii_lowercase();
+    let closing = format!("</{tag}");
+    match lower.find(&closing) {
+        Some(at) => {
+            let after = from + at + closing.len();
+            content[after..]
+                .find('>')
+                .map_or(content.len(), |gt| after + gt + 1)
+        }
+        None => content.len(),
+    }
+}
+
+enum HtmlToken {
+    Start {
+        tag: String,
+        id: Option<String>,
+        start: usize,
+        end: usize,
+        self_closing: bool,
+    },
+    End {
+        tag: String,
+        start: usize,
+        end: usize,
+    },
+}
+
+/// Yields start and end tags, skipping text, comments, doctypes and the
+/// content of raw-text elements.
+struct HtmlTokenizer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> HtmlTokenizer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+}
+
+impl Iterator for HtmlTokenizer<'_> {
+    type Item = HtmlToken;
+
+    fn next(&mut self) -> Option<HtmlToken> {
+        let bytes = self.src.as_bytes();
+        loop {
+            let start = self.pos + self.src[self.pos..].find('<')?;
+            let rest = &self.src[start..];
+            if rest.starts_with("<!--") {
+                self.pos = rest.find("-->").map_or(self.src.len(), |at| start + at + 3);
+                continue;
+            }
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.pos = rest.find('>').map_or(self.src.len(), |at| start + at + 1);
+                continue;
+            }
+
+            let closing = rest.starts_with("</");
+            let name_start = start + if closing { 2 } else { 1 };
+            let name_len = self.src[name_start..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+                .unwrap_or(self.src.len() - name_start);
+            if name_len == 0 || !bytes[name_start].is_ascii_alphabetic() {
+                // A literal `<` in text.
+                self.pos = start + 1;
+                continue;
+            }
+            let tag = self.src[name_start..name_start + name_len].to_ascii_lowercase();
+
+            let (id, end, self_closing) = parse_attributes(self.src, name_start + name_len);
+            self.pos = end;
+            if closing {
+                return Some(HtmlToken::End { tag, start, end });
+            }
+            if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) && !self_closing {
+                self.pos = find_raw_text_end(self.src, end, &tag);
+            }
+            return Some(HtmlToken::Start {
+                tag,
+                id,
+                start,
+                end,
+                self_closing,
+            });
+        }
+    }
+}
+
+/// Read attributes from `from` to the end of the tag, returning the `id`
+/// value, the offset just past `>`, and whether the tag ended in `/>`.
+fn parse_attributes(src: &str, from: usize) -> (Option<String>, usize, bool) {
+    let bytes = src.as_bytes();
+    let mut id = None;
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => return (id, i + 1, false),
+            b'/' if bytes.get(i + 1) == Some(&b'>') => return (id, i + 2, true),
+            c if c.is_ascii_whitespace() || c == b'/' => i += 1,
+            _ => {
+                let name_start = i;
+                while i < bytes.len()
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'=' | b'>' | b'/')
+                {
+                    i += 1;
+                }
+                let name = &src[name_start..i];
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if bytes.get(i) != Some(&b'=') {
+                    continue;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                let value = match bytes.get(i) {
+                    Some(&quote @ (b'"' | b'\'')) => {
+                        let value_start = i + 1;
+                        let value_end = src[value_start..]
+                            .find(quote as char)
+                            .map_or(src.len(), |at| value_start + at);
+                        i = (value_end + 1).min(src.len());
+                        &src[value_start..value_end]
+                    }
+                    _ => {
+                        let value_start = i;
+                        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>'
+                        {
+                            i += 1;
+                        }
+                        &src[value_start..i]
+                    }
+                };
+                if name.eq_ignore_ascii_case("id") && !value.trim().is_empty() {
+                    id = Some(value.trim().to_string());
+                }
+            }
+        }
+    }
+    (id, src.len(), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Docs <draft></title>
+</head>
+<body>
+  <nav id="site-nav">
+    <a href="/">Home</a>
+  </nav>
+  <!-- <div id="commented-out"></div> -->
+  <main>
+    <button id='save' class="btn">Save</button>
+    <img id=logo src="logo.png">
+    <ul><li>one<li>two</ul>
+  </main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn identified_elements_and_landmarks_are_entities() {
+        let entities = HtmlParserPlugin.extract_entities(PAGE, "index.html");
+        let summary: Vec<(&str, &str, usize, usize)> = entities
+            .iter()
+            .map(|e| (e.id.as_str(), e.name.as_str(), e.start_line, e.end_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("index.html::element::head", "head", 3, 5),
+                ("index.html::element::body", "body", 6, 16),
+                (
+                    "index.html::element::body::nav#site-nav",
+                    "nav#site-nav",
+                    7,
+                    9
+                ),
+                ("index.html::element::body::main", "main", 11, 15),
+                (
+                    "index.html::element::body::main::button#save",
+                    "button#save",
+                    12,
+                    12
+                ),
+                (
+                    "index.html::element::body::main::img#logo",
+                    "img#logo",
+                    13,
+                    13
+                ),
+            ]
+        );
+        assert_eq!(
+            entities[2].content,
+            "<nav id=\"site-nav\">\n    <a href=\"/\">Home</a>\n  </nav>"
+        );
+    }
+
+    #[test]
+    fn change_inside_an_identified_element_is_reported_there() {
+        let after = PAGE.replace(">Save<", ">Save changes<");
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(HtmlParserPlugin));
+        let changes = compute_semantic_diff(
+            &[FileChange {
+                file_path: "index.html".to_string(),
+                status: FileStatus::Modified,
+                old_file_path: None,
+                before_content: Some(PAGE.to_string()),
+                after_content: Some(after),
+            }],
+            &registry,
+            None,
+            None,
+        );
+        let names: Vec<(&str, ChangeType)> = changes
+            .changes
+            .iter()
+            .map(|c| (c.entity_name.as_str(), c.change_type))
+            .collect();
+        assert_eq!(names, vec![("button#save", ChangeType::Modified)]);
+    }
+
+    #[test]
+    fn repeated_landmarks_get_ordinals() {
+        let content =
+            "<section>a</section>\n<section>b</section>\n<section id=\"faq\">c</section>\n";
+        let names: Vec<String> = HtmlParserPlugin
+            .extract_entities(content, "a.html")
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, vec!["section", "section[2]", "section#faq"]);
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+
 use crate::model::entity::{build_entity_id, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
-use crate::utils::hash::content_hash;
+use crate::utils::hash::{content_hash, value_structural_hash};
 
 pub struct JsonParserPlugin;
 
@@ -55,6 +57,11 @@ enum EntityPayloadMode {
     Brief,
 }
 
+/// Nesting depth past which object values are kept as one opaque entity
+/// rather than split into child entities, so pathologically deep documents
+/// stay bounded. Top-level keys are depth 1.
+const MAX_NESTED_DEPTH: usize = 32;
+
 struct Frame {
     content: String,
     entries: Vec<JsonEntry>,
@@ -63,6 +70,9 @@ struct Frame {
     parent_pointer: Option<String>,
     parent_entity_id: Option<String>,
     container_kind: JsonContainerKind,
+    depth: usize,
+    /// Occurrences so far of each key pointer, for disambiguating duplicates.
+    seen_pointers: HashMap<String, usize>,
 }
 
 /// Iterative walk of the JSON tree, emitting entities in DFS pre-order.
@@ -88,6 +98,8 @@ fn extract_entries(
         parent_pointer: None,
         parent_entity_id: None,
         container_kind,
+        depth: 1,
+        seen_pointers: HashMap::new(),
     }];
 
     while let Some(mut frame) = worklist.pop() {
@@ -128,9 +140,21 @@ fn extract_entries(
                 };
             let value_content = extract_value_content(&entity_content);
 
+            // Duplicate keys are legal JSON but would collide on the pointer;
+            // later occurrences get an ordinal suffix instead.
+            let occurrence = frame
+                .seen_pointers
+                .entry(entry.pointer.clone())
+                .or_insert(0);
+            *occurrence += 1;
+            let entry_pointer = if *occurrence == 1 {
+                entry.pointer.clone()
+            } else {
+                format!("{}#{}", entry.pointer, occurrence)
+            };
             let pointer = match &frame.parent_pointer {
-                Some(pp) => format!("{pp}{}", entry.pointer),
-                None => entry.pointer.clone(),
+                Some(pp) => format!("{pp}{entry_pointer}"),
+                None => entry_pointer,
             };
             let entity_id = format!("{}::{}", file_path, pointer);
             let abs_start = frame.line_offset + entry.start_line - 1;
@@ -139,7 +163,7 @@ fn extract_entries(
                 EntityPayloadMode::Full => (
                     entity_content.clone(),
                     content_hash(&entity_content),
-                    Some(content_hash(value_content)),
+                    Some(structural_value_hash(value_content)),
                 ),
                 EntityPayloadMode::Brief => (String::new(), String::new(), None),
             };
@@ -157,10 +181,16 @@ fn extract_entries(
                 end_line: abs_end,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             });
 
-            if entry.entity_type == "object" && entry.descend_into_object {
+            if entry.entity_type == "object"
+                && entry.descend_into_object
+                && frame.depth < MAX_NESTED_DEPTH
+            {
                 if let Some(obj_str) = extract_object_value(&entity_content) {
                     let obj_line_in_entity = find_value_start_line(&entity_content);
                     let child = Frame {
@@ -171,6 +201,8 @@ fn extract_entries(
                         parent_pointer: Some(pointer),
                         parent_entity_id: Some(entity_id),
                         container_kind: JsonContainerKind::Object,
+                        depth: frame.depth + 1,
+                        seen_pointers: HashMap::new(),
                     };
                     worklist.push(frame);
                     worklist.push(child);
@@ -183,6 +215,16 @@ fn extract_entries(
     entities
 }
 
+/// Hash of a JSON value in canonical form (sorted keys, no insignificant
+/// whitespace), so reindenting or reordering keys is a cosmetic change.
+/// Text that doesn't parse on its own is hashed as written.
+fn structural_value_hash(value_text: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(value_text) {
+        Ok(value) => value_structural_hash(&value),
+        Err(_) => content_hash(value_text),
+    }
+}
+
 fn document_chunk_entity(
     content: &str,
     file_path: &str,
@@ -206,7 +248,10 @@ fn document_chunk_entity(
         end_line: line_count,
         start_byte: None,
         end_byte: None,
+        name_start: None,
+        name_end: None,
         metadata: None,
+        stable_id: None,
     }
 }
 
@@ -1243,6 +1288,58 @@ mod tests {
         assert_eq!(build.entity_id, "test.json::/scripts/build");
     }
 
+    #[test]
+    fn package_script_change_reports_only_that_script() {
+        let before = r#"{
+  "name": "app",
+  "scripts": {
+    "build": "tsc",
+    "test": "jest"
+  }
+}"#;
+        let after = before.replace("\"jest\"", "\"vitest\"");
+        let changes = json_diff(before, &after);
+        assert_eq!(names(&changes), vec![("test".into(), ChangeType::Modified)]);
+        assert_eq!(changes[0].entity_id, "test.json::/scripts/test");
+        assert_eq!(changes[0].parent_name.as_deref(), Some("scripts"));
+    }
+
+    #[test]
+    fn duplicate_keys_get_distinct_pointers() {
+        let before = "{\n  \"a\": {\n    \"x\": 1,\n    \"x\": 2\n  }\n}";
+        let entities = JsonParserPlugin.extract_entities(before, "test.json");
+        let ids: Vec<&str> = entities.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["test.json::/a", "test.json::/a/x", "test.json::/a/x#2"]
+        );
+
+        let after = before.replace("2\n", "3\n");
+        let changes = json_diff(before, &after);
+        assert_eq!(names(&changes), vec![("x".into(), ChangeType::Modified)]);
+        assert_eq!(changes[0].entity_id, "test.json::/a/x#2");
+    }
+
+    #[test]
+    fn nesting_beyond_the_depth_limit_stays_opaque() {
+        let levels = MAX_NESTED_DEPTH + 8;
+        let nested = |leaf: &str| {
+            let mut content = leaf.to_string();
+            for level in (0..levels).rev() {
+                content = format!("{{\n\"k{level}\": {content}\n}}");
+            }
+            content
+        };
+        let before = nested("1");
+        let entities = JsonParserPlugin.extract_entities(&before, "test.json");
+        assert_eq!(entities.len(), MAX_NESTED_DEPTH);
+        assert!(entities.iter().all(|e| e.entity_type == "object"));
+
+        let changes = json_diff(&before, &nested("2"));
+        let deepest = format!("k{}", MAX_NESTED_DEPTH - 1);
+        assert_eq!(names(&changes), vec![(deepest, ChangeType::Modified)]);
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     //  Phase 3 fuzzy matching
     // ─────────────────────────────────────────────────────────────────────────
@@ -1474,4 +1571,14 @@ mod tests {
             names(&changes)
         );
     }
+
+    #[test]
+    fn reindented_and_reordered_object_is_cosmetic() {
+        let changes = json_diff(
+            "{\n  \"scripts\": {\"dev\": \"vite\", \"build\": \"tsc\"}\n}\n",
+            "{\n  \"scripts\": {\n    \"build\": \"tsc\",\n    \"dev\": \"vite\"\n  }\n}\n",
+        );
+        let scripts = find_change(&changes, "scripts", ChangeType::Modified);
+        assert_eq!(scripts.structural_change, Some(false));
+    }
 }
@@ -150,7 +150,10 @@ impl SemanticParserPlugin for LatexParserPlugin {
                     end_line: p_end,
                     start_byte: None,
                     end_byte: None,
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 });
 
                 // Extract command definitions from preamble
@@ -204,7 +207,10 @@ impl SemanticParserPlugin for LatexParserPlugin {
                                 end_line: p_start + def_end + 1,
                                 start_byte: None,
                                 end_byte: None,
+                                name_start: None,
+                                name_end: None,
                                 metadata: None,
+                                stable_id: None,
                             });
 
                             i = def_end + 1;
@@ -331,7 +337,10 @@ impl SemanticParserPlugin for LatexParserPlugin {
                 end_line: section.start_line + section.lines.len() - 1,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             });
         }
 
@@ -445,7 +454,10 @@ impl SemanticParserPlugin for LatexParserPlugin {
                 end_line: env.end_line,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: Some(metadata),
+                stable_id: None,
             });
         }
 
@@ -1,5 +1,6 @@
 use regex::Regex;
 use std::collections::HashMap;
+use std::sync::LazyLock;
 
 use crate::model::entity::{build_entity_id, build_entity_id_disambiguated, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
@@ -7,6 +8,54 @@ use crate::utils::hash::content_hash;
 
 pub struct MarkdownParserPlugin;
 
+/// Entity metadata key holding a section's link targets, one per line, as
+/// written (`./other.md#setup`, `#usage`). External URLs are left out.
+pub const LINKS_METADATA_KEY: &str = "links";
+
+static INLINE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(!?)\[[^\]]*\]\(\s*(?:<([^>]*)>|([^)\s]+))(?:\s+(?:"[^"]*"|'[^']*'))?\s*\)"#)
+        .unwrap()
+});
+static REFERENCE_DEFINITION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s{0,3}\[[^\]]+\]:\s*(?:<([^>]*)>|(\S+))").unwrap());
+static CODE_SPAN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`+[^`]*`+").unwrap());
+
+/// Local link targets on a line: inline links and reference definitions,
+/// skipping images, code spans and anything with a URL scheme.
+fn line_link_targets(line: &str) -> Vec<String> {
+    let line = CODE_SPAN_RE.replace_all(line, "");
+    let mut targets = Vec::new();
+    for caps in INLINE_LINK_RE.captures_iter(&line) {
+        if &caps[1] == "!" {
+            continue;
+        }
+        if let Some(target) = caps.get(2).or_else(|| caps.get(3)) {
+            targets.push(target.as_str());
+        }
+    }
+    if let Some(caps) = REFERENCE_DEFINITION_RE.captures(&line) {
+        if let Some(target) = caps.get(1).or_else(|| caps.get(2)) {
+            targets.push(target.as_str());
+        }
+    }
+    targets
+        .into_iter()
+        .map(str::trim)
+        .filter(|target| !target.is_empty() && !has_url_scheme(target))
+        .map(str::to_string)
+        .collect()
+}
+
+fn has_url_scheme(target: &str) -> bool {
+    target.starts_with("//")
+        || target.split_once(':').is_some_and(|(scheme, _)| {
+            scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                && !scheme.is_empty()
+        })
+}
+
 impl SemanticParserPlugin for MarkdownParserPlugin {
     fn id(&self) -> &str {
         "markdown"
@@ -26,6 +75,7 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
             name: String,
             start_line: usize,
             lines: Vec<String>,
+            links: Vec<String>,
             base_id: String,
             parent_index: Option<usize>,
         }
@@ -33,8 +83,22 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
         let mut sections: Vec<Section> = Vec::new();
         let mut current_section: Option<usize> = None;
         let mut section_stack: Vec<(usize, usize)> = Vec::new(); // (level, section index)
+        let mut fence: Option<&str> = None;
 
         for (i, &line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            let fence_marker = ["```", "~~~"]
+                .into_iter()
+                .find(|marker| trimmed.starts_with(marker));
+            if let Some(marker) = fence_marker {
+                match fence {
+                    Some(open) if open == marker => fence = None,
+                    Some(_) => {}
+                    None => fence = Some(marker),
+                }
+            }
+            let in_code = fence.is_some() || fence_marker.is_some();
+
             if let Some(caps) = heading_re.captures(line) {
                 let level = caps[1].len();
                 let name = caps[2].trim().to_string();
@@ -51,6 +115,7 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
                     name: name.clone(),
                     start_line: i + 1,
                     lines: vec![line.to_string()],
+                    links: Vec::new(),
                     base_id: build_entity_id(file_path, "heading", &name, None),
                     parent_index,
                 });
@@ -60,6 +125,9 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
                 section_stack.push((level, section_index));
             } else if let Some(index) = current_section {
                 sections[index].lines.push(line.to_string());
+                if !in_code {
+                    sections[index].links.extend(line_link_targets(line));
+                }
             } else {
                 // Content before first heading — preamble
                 if !line.trim().is_empty() {
@@ -69,6 +137,11 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
                             name: "(preamble)".to_string(),
                             start_line: i + 1,
                             lines: vec![line.to_string()],
+                            links: if in_code {
+                                Vec::new()
+                            } else {
+                                line_link_targets(line)
+                            },
                             base_id: build_entity_id(file_path, "preamble", "(preamble)", None),
                             parent_index: None,
                         });
@@ -105,11 +178,12 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
             })
             .collect();
 
+        // Every section starts with its heading (or first preamble) line, so a
+        // heading whose body is blank is still an entity, with that line as
+        // its content. Emptying a section reports a modification, not a
+        // deletion.
         for (index, section) in sections.iter().enumerate() {
             let section_content = section.lines.join("\n").trim().to_string();
-            if section_content.is_empty() {
-                continue;
-            }
 
             let entity_type = if section.level == 0 {
                 "preamble"
@@ -126,13 +200,24 @@ impl SemanticParserPlugin for MarkdownParserPlugin {
                     .parent_index
                     .map(|parent_index| section_ids[parent_index].clone()),
                 content_hash: content_hash(&section_content),
-                structural_hash: None,
+                // Reflowing a paragraph or reindenting doesn't change the text.
+                structural_hash: Some(content_hash(
+                    &section_content
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )),
                 content: section_content,
                 start_line: section.start_line,
                 end_line: section.start_line + section.lines.len() - 1,
                 start_byte: None,
                 end_byte: None,
-                metadata: None,
+                name_start: None,
+                name_end: None,
+                metadata: (!section.links.is_empty()).then(|| {
+                    HashMap::from([(LINKS_METADATA_KEY.to_string(), section.links.join("\n"))])
+                }),
+                stable_id: None,
             });
         }
 
@@ -154,6 +239,40 @@ mod tests {
         assert_eq!(entities[0].id, "doc.md::heading::Overview");
     }
 
+    #[test]
+    fn heading_without_body_is_still_an_entity() {
+        let content = "# Intro\n\ntext\n\n## Empty\n\n\n## Next\nmore\n";
+        let entities = MarkdownParserPlugin.extract_entities(content, "doc.md");
+
+        let empty = entities
+            .iter()
+            .find(|entity| entity.name == "Empty")
+            .expect("empty section should be extracted");
+        assert_eq!(empty.content, "## Empty");
+        assert_eq!(empty.parent_id.as_deref(), Some("doc.md::heading::Intro"));
+        assert_eq!(empty.start_line, 5);
+    }
+
+    #[test]
+    fn local_links_are_recorded_per_section() {
+        let content = "# Intro\n\nSee [setup](./setup.md#install) and [usage](#usage).\n![logo](logo.png) [site](https://example.com) `[x](code.md)`\n\n```md\n[fenced](fenced.md)\n```\n\n[guide]: <docs/guide.md>\n\n## Usage\n\nNo links here.\n";
+        let entities = MarkdownParserPlugin.extract_entities(content, "README.md");
+
+        let links = |name: &str| {
+            entities
+                .iter()
+                .find(|entity| entity.name == name)
+                .and_then(|entity| entity.metadata.as_ref())
+                .and_then(|metadata| metadata.get(LINKS_METADATA_KEY))
+                .cloned()
+        };
+        assert_eq!(
+            links("Intro").as_deref(),
+            Some("./setup.md#install\n#usage\ndocs/guide.md")
+        );
+        assert_eq!(links("Usage"), None);
+    }
+
     #[test]
     fn duplicate_heading_names_get_line_disambiguated_ids() {
         let content = "# Same Title\n\nfirst body\n\n# Same Title\n\nsecond body\n";
@@ -1,11 +1,16 @@
 pub mod code;
+pub mod css;
 pub mod csv_plugin;
+pub mod dockerfile;
 #[cfg(feature = "lang-erb")]
 pub mod erb;
 pub mod fallback;
+pub mod graphql_plugin;
+pub mod html;
 pub mod json;
 pub mod latex;
 pub mod markdown;
+pub mod proto_plugin;
 #[cfg(feature = "lang-svelte")]
 pub mod svelte;
 pub mod toml_plugin;
@@ -25,12 +30,17 @@ pub fn create_default_registry() -> ParserRegistry {
     registry.register(Box::new(yaml::YamlParserPlugin));
     registry.register(Box::new(toml_plugin::TomlParserPlugin));
     registry.register(Box::new(csv_plugin::CsvParserPlugin));
+    registry.register(Box::new(html::HtmlParserPlugin));
+    registry.register(Box::new(css::CssParserPlugin));
+    registry.register(Box::new(graphql_plugin::GraphqlParserPlugin));
+    registry.register(Box::new(proto_plugin::ProtoParserPlugin));
+    registry.register(Box::new(dockerfile::DockerfileParserPlugin));
     registry.register(Box::new(markdown::MarkdownParserPlugin));
     registry.register(Box::new(latex::LatexParserPlugin));
     #[cfg(feature = "lang-erb")]
     registry.register(Box::new(erb::ErbParserPlugin));
     // Fallback must be last
-    registry.register(Box::new(fallback::FallbackParserPlugin));
+    registry.register(Box::new(fallback::FallbackParserPlugin::default()));
 
     registry
 }
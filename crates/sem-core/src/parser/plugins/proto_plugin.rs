@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+use crate::model::entity::{build_entity_id, SemanticEntity};
+use crate::parser::plugin::SemanticParserPlugin;
+use crate::utils::hash::content_hash;
+
+const FIELD_NUMBER_KEY: &str = "proto.field_number";
+
+pub struct ProtoParserPlugin;
+
+impl SemanticParserPlugin for ProtoParserPlugin {
+    fn id(&self) -> &str {
+        "proto"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[".proto"]
+    }
+
+    fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
+        // Messages, enums, services and extends are entities; their fields,
+        // enum values, oneofs and rpcs are children, nested messages nest.
+        // A field's number is part of its content (and recorded in
+        // metadata), so renumbering a field is a Modified change on it.
+        let tokens = tokenize(content);
+        let extractor = ProtoExtractor {
+            src: content,
+            tokens: &tokens,
+            line_starts: std::iter::once(0)
+                .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+            file_path,
+        };
+        let mut entities = Vec::new();
+        extractor.extract_block(0, tokens.len(), None, &mut entities);
+        entities
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Word,
+    String,
+    Punct(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Split a `.proto` file into words (identifiers, dotted type names,
+/// numbers), string literals and punctuation, dropping comments.
+fn tokenize(src: &str) -> Vec<Token> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'/' if src[i..].starts_with("//") => {
+                i = src[i..].find('\n').map_or(bytes.len(), |at| i + at);
+                continue;
+            }
+            b'/' if src[i..].starts_with("/*") => {
+                i = src[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |at| i + 2 + at + 2);
+                continue;
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                TokenKind::String
+            }
+            b if b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-' | b'+') => {
+                i += 1;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'.'))
+                {
+                    i += 1;
+                }
+                TokenKind::Word
+            }
+            b => {
+                i += src[i..].chars().next().map_or(1, char::len_utf8);
+                TokenKind::Punct(b)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: i,
+        });
+    }
+    tokens
+}
+
+/// A statement ending in `;`, or a declaration with a `{ ... }` body.
+struct Statement {
+    /// Token range of the whole statement, terminator included.
+    range: (usize, usize),
+    /// Token range between the braces.
+    body: Option<(usize, usize)>,
+}
+
+/// Entity type, name and metadata of a tracked statement.
+type Classified = (&'static str, String, Option<HashMap<String, String>>);
+
+struct ProtoExtractor<'a> {
+    src: &'a str,
+    tokens: &'a [Token],
+    line_starts: Vec<usize>,
+    file_path: &'a str,
+}
+
+impl ProtoExtractor<'_> {
+    fn extract_block(
+        &self,
+        from: usize,
+        to: usize,
+        parent_id: Option<&str>,
+        entities: &mut Vec<SemanticEntity>,
+    ) {
+        // Names are unique within a block in valid files; keep ids unique
+        // for invalid ones too.
+        let mut occurrences: HashMap<(&str, String), usize> = HashMap::new();
+        for statement in self.statements(from, to) {
+            let Some((entity_type, name, metadata)) = self.classify(&statement) else {
+                continue;
+            };
+            let occurrence = occurrences.entry((entity_type, name.clone())).or_insert(0);
+            *occurrence += 1;
+            let mut id = build_entity_id(self.file_path, entity_type, &name, parent_id);
+            if *occurrence > 1 {
+                id = format!("{id}#{occurrence}");
+            }
+            entities.push(self.entity(
+                id.clone(),
+                entity_type,
+                name,
+                parent_id,
+                statement.range,
+                metadata,
+            ));
+            if let Some((body_start, body_end)) = statement.body {
+                self.extract_block(body_start, body_end, Some(&id), entities);
+            }
+        }
+    }
+
+    /// The entity type and name of a statement, or `None` for statements
+    /// that aren't tracked (`syntax`, `import`, `option`, `reserved`, ...).
+    fn classify(&self, statement: &Statement) -> Option<Classified> {
+        let (start, end) = statement.range;
+        let words: Vec<&str> = (start..end)
+            .take_while(|&i| {
+                !matches!(
+                    self.tokens[i].kind,
+                    TokenKind::Punct(b'{' | b';' | b'(' | b'[')
+                )
+            })
+            .map(|i| self.text(i))
+            .collect();
+        let first = *words.first()?;
+        let declaration = |entity_type| Some((entity_type, words.get(1)?.to_string(), None));
+        match first {
+            "message" if statement.body.is_some() => declaration("message"),
+            "enum" if statement.body.is_some() => declaration("enum"),
+            "service" if statement.body.is_some() => declaration("service"),
+            "oneof" if statement.body.is_some() => declaration("oneof"),
+            "extend" if statement.body.is_some() => declaration("extend"),
+            "rpc" => declaration("rpc"),
+            "syntax" | "edition" | "package" | "import" | "option" | "reserved" | "extensions" => {
+                None
+            }
+            _ if statement.body.is_none() => {
+                // `[label] type name = number [opts];`, `map<K, V> name = n;`
+                // or, inside an enum, `NAME = number [opts];`.
+                let eq = words.iter().position(|w| *w == "=")?;
+                let name = words.get(eq.checked_sub(1)?)?;
+                let number = words.get(eq + 1)?;
+                let entity_type = if eq == 1 { "enum_value" } else { "field" };
+                let metadata = HashMap::from([(FIELD_NUMBER_KEY.to_string(), number.to_string())]);
+                Some((entity_type, name.to_string(), Some(metadata)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Top-level statements in the token range `from..to`.
+    fn statements(&self, from: usize, to: usize) -> Vec<Statement> {
+        let mut statements = Vec::new();
+        let mut i = from;
+        while i < to {
+            let start = i;
+            let mut depth = 0usize;
+            let mut body = None;
+            while i < to {
+                match self.tokens[i].kind {
+                    TokenKind::Punct(b'(' | b'[' | b'<') => depth += 1,
+                    TokenKind::Punct(b')' | b']' | b'>') => depth = depth.saturating_sub(1),
+                    TokenKind::Punct(b';') if depth == 0 => {
+                        i += 1;
+                        break;
+                    }
+                    TokenKind::Punct(b'{') if depth == 0 => {
+                        let close = self.matching_brace(i, to);
+                        body = Some((i + 1, close));
+                        i = (close + 1).min(to);
+                        // `rpc Foo(A) returns (B) {}` may still end in `;`.
+                        if i < to && self.tokens[i].kind == TokenKind::Punct(b';') {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    TokenKind::Punct(b'}') if depth == 0 => {
+                        // Stray closing brace.
+                        i += 1;
+                        break;
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            statements.push(Statement {
+                range: (start, i),
+                body,
+            });
+        }
+        statements
+    }
+
+    /// Index of the `}` matching the `{` at `open`, or `to` if unclosed.
+    fn matching_brace(&self, open: usize, to: usize) -> usize {
+        let mut depth = 0usize;
+        for i in open..to {
+            match self.tokens[i].kind {
+                TokenKind::Punct(b'{') => depth += 1,
+                TokenKind::Punct(b'}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i;
+                    }
+                }
+                _ => {}
+            }
+        }
+        to
+    }
+
+    fn entity(
+        &self,
+        id: String,
+        entity_type: &str,
+        name: String,
+        parent_id: Option<&str>,
+        (start, end): (usize, usize),
+        metadata: Option<HashMap<String, String>>,
+    ) -> SemanticEntity {
+        let start_byte = self.tokens[start].start;
+        let end_byte = self.tokens[end - 1].end;
+        let content = &self.src[start_byte..end_byte];
+        // Comments and layout aren't part of the contract.
+        let structural: Vec<&str> = (start..end).map(|i| self.text(i)).collect();
+        SemanticEntity {
+            id,
+            file_path: self.file_path.to_string(),
+            entity_type: entity_type.to_string(),
+            name,
+            parent_id: parent_id.map(String::from),
+            content_hash: content_hash(content),
+            structural_hash: Some(content_hash(&structural.join(" "))),
+            content: content.to_string(),
+            start_line: self.line_of(start_byte),
+            end_line: self.line_of(end_byte.saturating_sub(1)),
+            start_byte: Some(start_byte),
+            end_byte: Some(end_byte),
+            name_start: None,
+            name_end: None,
+            metadata,
+            stable_id: None,
+        }
+    }
+
+    fn text(&self, i: usize) -> &str {
+        &self.src[self.tokens[i].start..self.tokens[i].end]
+    }
+
+    fn line_of(&self, byte: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= byte)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
+
+    const PROTO: &str = r#"syntax = "proto3";
+
+package shop.v1;
+
+import "google/protobuf/timestamp.proto";
+
+// An order placed by a customer.
+message Order {
+  string id = 1;
+  repeated LineItem items = 2 [packed = true];
+  map<string, string> labels = 3;
+  oneof payment {
+    string card_token = 4;
+    string voucher = 5;
+  }
+
+  message LineItem {
+    string sku = 1;
+    int32 quantity = 2;
+  }
+}
+
+enum Status {
+  STATUS_UNSPECIFIED = 0;
+  STATUS_PAID = 1;
+}
+
+service OrderService {
+  rpc GetOrder(GetOrderRequest) returns (Order);
+  rpc WatchOrders(WatchRequest) returns (stream Order) {
+    option (google.api.http) = { get: "/v1/orders:watch" };
+  }
+}
+"#;
+
+    fn proto_diff(before: &str, after: &str) -> Vec<(String, String, ChangeType)> {
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(ProtoParserPlugin));
+        let changes = vec![FileChange {
+            file_path: "order.proto".to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some(before.to_string()),
+            after_content: Some(after.to_string()),
+        }];
+        compute_semantic_diff(&changes, &registry, None, None)
+            .changes
+            .into_iter()
+            .map(|c| (c.entity_type, c.entity_id, c.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn definitions_fields_and_rpcs_are_entities() {
+        let entities = ProtoParserPlugin.extract_entities(PROTO, "order.proto");
+        let summary: Vec<(&str, &str, usize, usize)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.id.as_str(),
+                    e.entity_type.as_str(),
+                    e.start_line,
+                    e.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("order.proto::message::Order", "message", 8, 21),
+                ("order.proto::message::Order::id", "field", 9, 9),
+                ("order.proto::message::Order::items", "field", 10, 10),
+                ("order.proto::message::Order::labels", "field", 11, 11),
+                ("order.proto::message::Order::payment", "oneof", 12, 15),
+                (
+                    "order.proto::message::Order::payment::card_token",
+                    "field",
+                    13,
+                    13
+                ),
+                (
+                    "order.proto::message::Order::payment::voucher",
+                    "field",
+                    14,
+                    14
+                ),
+                ("order.proto::message::Order::LineItem", "message", 17, 20),
+                (
+                    "order.proto::message::Order::LineItem::sku",
+                    "field",
+                    18,
+                    18
+                ),
+                (
+                    "order.proto::message::Order::LineItem::quantity",
+                    "field",
+                    19,
+                    19
+                ),
+                ("order.proto::enum::Status", "enum", 23, 26),
+                (
+                    "order.proto::enum::Status::STATUS_UNSPECIFIED",
+                    "enum_value",
+                    24,
+                    24
+                ),
+                (
+                    "order.proto::enum::Status::STATUS_PAID",
+                    "enum_value",
+                    25,
+                    25
+                ),
+                ("order.proto::service::OrderService", "service", 28, 33),
+                (
+                    "order.proto::service::OrderService::GetOrder",
+                    "rpc",
+                    29,
+                    29
+                ),
+                (
+                    "order.proto::service::OrderService::WatchOrders",
+                    "rpc",
+                    30,
+                    32
+                ),
+            ]
+        );
+        let items = &entities[2];
+        assert_eq!(
+            items.content,
+            "repeated LineItem items = 2 [packed = true];"
+        );
+        assert_eq!(
+            items.metadata.as_ref().unwrap()[FIELD_NUMBER_KEY],
+            "2".to_string()
+        );
+    }
+
+    #[test]
+    fn renumbered_field_is_modified() {
+        let after = PROTO.replace("int32 quantity = 2;", "int32 quantity = 3;");
+        assert_eq!(
+            proto_diff(PROTO, &after),
+            vec![(
+                "field".to_string(),
+                "order.proto::message::Order::LineItem::quantity".to_string(),
+                ChangeType::Modified
+            )]
+        );
+    }
+
+    #[test]
+    fn removed_field_is_deleted() {
+        let after = PROTO.replace("  map<string, string> labels = 3;\n", "");
+        assert_eq!(
+            proto_diff(PROTO, &after),
+            vec![(
+                "field".to_string(),
+                "order.proto::message::Order::labels".to_string(),
+                ChangeType::Deleted
+            )]
+        );
+    }
+
+    #[test]
+    fn added_rpc_is_reported_on_its_service() {
+        let after = PROTO.replace(
+            "  rpc GetOrder(GetOrderRequest) returns (Order);\n",
+            "  rpc GetOrder(GetOrderRequest) returns (Order);\n  rpc CancelOrder(CancelOrderRequest) returns (Order);\n",
+        );
+        assert_eq!(
+            proto_diff(PROTO, &after),
+            vec![(
+                "rpc".to_string(),
+                "order.proto::service::OrderService::CancelOrder".to_string(),
+                ChangeType::Added
+            )]
+        );
+    }
+}
@@ -10,6 +10,8 @@ use crate::parser::plugin::SemanticParserPlugin;
 use crate::utils::hash::{content_hash, structural_hash};
 
 use super::code::CodeParserPlugin;
+use super::css::CssParserPlugin;
+use super::vue::embed_entities;
 
 const SVELTE_KIND_KEY: &str = "svelte.kind";
 const SVELTE_CONTEXT_KEY: &str = "svelte.context";
@@ -340,7 +342,28 @@ impl<'a> SvelteLowerer<'a> {
             Some(structural_hash(node, self.source_bytes)),
             Some(base_metadata(SvelteEntityKind::Style)),
         );
+        let block_id = entity.id.clone();
         self.entities.push(entity);
+
+        let Some(raw_text) = element_raw_text_node(node) else {
+            return;
+        };
+        let inner_content = text_for_node(self.source, raw_text).unwrap_or_default();
+        if !inner_content.trim().is_empty() {
+            let ext = match self.element_attribute_value(node, "lang") {
+                Some(lang) if lang.eq_ignore_ascii_case("scss") => "style.scss",
+                _ => "style.css",
+            };
+            let virtual_path = format!("{}:{}", self.file_path, ext);
+            let inner = CssParserPlugin.extract_entities(inner_content, &virtual_path);
+            self.entities.extend(embed_entities(
+                inner,
+                &virtual_path,
+                self.file_path,
+                &block_id,
+                self.node_start_line(raw_text) - 1,
+            ));
+        }
     }
 
     fn lower_fragment_entity<'tree>(
@@ -613,7 +636,10 @@ impl<'a> SvelteLowerer<'a> {
             end_line,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata,
+            stable_id: None,
         }
     }
 
@@ -751,7 +777,10 @@ fn extract_svelte_module_entities(
         end_line: last_line_number(content),
         start_byte: None,
         end_byte: None,
+        name_start: None,
+        name_end: None,
         metadata: Some(metadata),
+        stable_id: None,
     };
 
     let module_id = module_entity.id.clone();
@@ -1061,6 +1090,21 @@ h1 { color: red; }
         );
     }
 
+    #[test]
+    fn test_svelte_style_rules_are_children_of_the_style_block() {
+        let code = "<h1>hi</h1>\n\n<style>\n  @media (min-width: 600px) {\n    h1 { color: red; }\n  }\n</style>\n";
+        let entities = SvelteParserPlugin.extract_entities(code, "Title.svelte");
+        let rule = entities
+            .iter()
+            .find(|e| e.name == "h1" && e.entity_type == "rule");
+        let rule = rule.expect("style rule should be extracted");
+        assert_eq!(rule.start_line, 5);
+        assert_eq!(
+            rule.parent_id.as_deref(),
+            Some("Title.svelte::svelte_style::style::@media (min-width: 600px)")
+        );
+    }
+
     #[test]
     fn test_svelte_line_numbers() {
         let code = r#"<script lang="ts">
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::model::entity::{build_entity_id, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
 use crate::utils::hash::content_hash;
@@ -14,10 +16,11 @@ impl SemanticParserPlugin for TomlParserPlugin {
     }
 
     fn extract_entities(&self, content: &str, file_path: &str) -> Vec<SemanticEntity> {
-        // Extract top-level keys and [sections] with proper line ranges.
-        // TOML has two kinds of top-level entries:
-        //   1. Key-value pairs before any section header
-        //   2. Section headers like [package] or [dependencies]
+        // Top-level entries are root key-value pairs and section headers
+        // ([package], [[bin]]). Dotted headers nest under the closest
+        // enclosing header ([dependencies.serde] under [dependencies]), and
+        // the key-value pairs inside a section become its children, so a
+        // change deep in a table is reported where it happened.
         let lines: Vec<&str> = content.lines().collect();
         let sections = find_toml_sections(&lines);
 
@@ -30,12 +33,14 @@ impl SemanticParserPlugin for TomlParserPlugin {
             Ok(v) => v,
             Err(_) => return Vec::new(),
         };
-        let table = match parsed.as_table() {
-            Some(t) => t,
-            None => return Vec::new(),
-        };
 
         let mut entities = Vec::new();
+        // Header key segments -> (entity id, value path) of the latest entity
+        // for that header; for an array of tables, its current element.
+        let mut headers: HashMap<Vec<String>, (String, Vec<ValuePathSegment>)> = HashMap::new();
+        // Elements seen so far per array of tables, keyed by parent and name.
+        let mut array_counts: HashMap<(Option<String>, String), usize> = HashMap::new();
+
         for (i, section) in sections.iter().enumerate() {
             let end_line = if i + 1 < sections.len() {
                 let next_start = sections[i + 1].line;
@@ -45,81 +50,314 @@ impl SemanticParserPlugin for TomlParserPlugin {
             };
 
             let entity_content = lines[section.line - 1..end_line].join("\n");
+            let segments = split_dotted_key(&section.key);
+
+            if !section.is_header {
+                let path: Vec<ValuePathSegment> = segments
+                    .iter()
+                    .cloned()
+                    .map(ValuePathSegment::Key)
+                    .collect();
+                let value_str = lookup_value(&parsed, &path)
+                    .map(value_hash_text)
+                    .unwrap_or_else(|| entity_content.clone());
+                entities.push(toml_entity(
+                    file_path,
+                    section.key.clone(),
+                    "property",
+                    None,
+                    &value_str,
+                    entity_content,
+                    section.line,
+                    end_line,
+                ));
+                continue;
+            }
 
-            // Resolve the display name, entity type, and the value to hash.
-            let (name, entity_type, value_str) = if let Some(idx) = section.array_index {
+            // Nest under the closest enclosing header, if the file has one.
+            let parent = (1..segments.len())
+                .rev()
+                .find_map(|len| headers.get(&segments[..len]).map(|p| (len, p.clone())));
+            let (parent_id, mut path, own_segments) = match parent {
+                Some((len, (parent_id, parent_path))) => {
+                    (Some(parent_id), parent_path, &segments[len..])
+                }
+                None => (None, Vec::new(), &segments[..]),
+            };
+            path.extend(own_segments.iter().cloned().map(ValuePathSegment::Key));
+            let mut name = match parent_id {
+                Some(_) => own_segments.join("."),
+                None => section.key.clone(),
+            };
+
+            let value = if section.is_array {
                 // Array-of-tables entry: give it an index-based identity (key/0,
                 // key/1, ...) and hash only its own element so appending a new
                 // entry reads as an addition, not a modification of the last one.
-                let value_str = table
-                    .get(&section.key)
-                    .and_then(|v| v.as_array())
-                    .and_then(|arr| arr.get(idx))
-                    .map(|el| serde_json::to_string_pretty(el).unwrap_or_default())
-                    .unwrap_or_else(|| entity_content.clone());
-                (
-                    format!("{}/{}", section.key, idx),
-                    "array_table".to_string(),
-                    value_str,
-                )
-            } else if let Some(val) = table.get(&section.key) {
-                let is_table = val.is_table();
-                let vs = if is_table {
-                    serde_json::to_string_pretty(val).unwrap_or_default()
-                } else {
-                    toml_value_to_string(val)
-                };
-                (
-                    section.key.clone(),
-                    if is_table { "section" } else { "property" }.to_string(),
-                    vs,
-                )
+                let count = array_counts
+                    .entry((parent_id.clone(), name.clone()))
+                    .or_insert(0);
+                path.push(ValuePathSegment::Index(*count));
+                name = format!("{name}/{count}");
+                *count += 1;
+                lookup_value(&parsed, &path)
             } else {
-                (
-                    section.key.clone(),
-                    "property".to_string(),
-                    entity_content.clone(),
-                )
+                lookup_value(&parsed, &path)
             };
+            let entity_type = if section.is_array {
+                "array_table"
+            } else if value.is_some_and(toml::Value::is_table) {
+                "section"
+            } else {
+                "property"
+            };
+            let value_str = value
+                .map(value_hash_text)
+                .unwrap_or_else(|| entity_content.clone());
 
-            let id = build_entity_id(file_path, &entity_type, &name, None);
-            entities.push(SemanticEntity {
-                id,
-                file_path: file_path.to_string(),
-                entity_type,
+            let entity = toml_entity(
+                file_path,
                 name,
-                parent_id: None,
-                content_hash: content_hash(&value_str),
-                structural_hash: None,
-                content: entity_content,
-                start_line: section.line,
+                entity_type,
+                parent_id,
+                &value_str,
+                entity_content,
+                section.line,
                 end_line,
-                start_byte: None,
-                end_byte: None,
-                metadata: None,
-            });
+            );
+            let section_id = entity.id.clone();
+            headers.insert(segments, (section_id.clone(), path.clone()));
+            entities.push(entity);
+
+            for (key, start_line, end_line) in find_key_values(&lines, section.line + 1, end_line) {
+                let key_segments = split_dotted_key(&key);
+                let mut key_path = path.clone();
+                key_path.extend(key_segments.iter().cloned().map(ValuePathSegment::Key));
+                let child_content = lines[start_line - 1..end_line].join("\n");
+                let value_str = lookup_value(&parsed, &key_path)
+                    .map(value_hash_text)
+                    .unwrap_or_else(|| child_content.clone());
+                entities.push(toml_entity(
+                    file_path,
+                    key_segments.join("."),
+                    "property",
+                    Some(section_id.clone()),
+                    &value_str,
+                    child_content,
+                    start_line,
+                    end_line,
+                ));
+            }
         }
 
         entities
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn toml_entity(
+    file_path: &str,
+    name: String,
+    entity_type: &str,
+    parent_id: Option<String>,
+    value_str: &str,
+    content: String,
+    start_line: usize,
+    end_line: usize,
+) -> SemanticEntity {
+    SemanticEntity {
+        id: build_entity_id(file_path, entity_type, &name, parent_id.as_deref()),
+        file_path: file_path.to_string(),
+        entity_type: entity_type.to_string(),
+        name,
+        parent_id,
+        content_hash: content_hash(value_str),
+        // The content hash already covers only the parsed value.
+        structural_hash: Some(content_hash(value_str)),
+        content,
+        start_line,
+        end_line,
+        start_byte: None,
+        end_byte: None,
+        name_start: None,
+        name_end: None,
+        metadata: None,
+        stable_id: None,
+    }
+}
+
+/// One step from the document root to a value in the parsed tree.
+#[derive(Clone)]
+enum ValuePathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn lookup_value<'a>(root: &'a toml::Value, path: &[ValuePathSegment]) -> Option<&'a toml::Value> {
+    path.iter().try_fold(root, |value, segment| match segment {
+        ValuePathSegment::Key(key) => value.as_table()?.get(key),
+        ValuePathSegment::Index(index) => value.as_array()?.get(*index),
+    })
+}
+
+fn value_hash_text(value: &toml::Value) -> String {
+    if value.is_table() {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    } else {
+        toml_value_to_string(value)
+    }
+}
+
+/// Split a TOML key like `dependencies."serde.json" . features` into its
+/// segments, unquoting quoted parts.
+fn split_dotted_key(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = key.chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some('"'), '\\') => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), ch) => current.push(ch),
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '.') => segments.push(std::mem::take(&mut current)),
+            (None, ch) if ch.is_whitespace() => {}
+            (None, ch) => current.push(ch),
+        }
+    }
+    segments.push(current);
+    segments
+}
+
+/// Find the key-value pairs in lines `start..=end` (1-based), returning each
+/// key with the line range of its value; multi-line arrays, inline tables
+/// and strings extend the range to where they close.
+fn find_key_values(lines: &[&str], start: usize, end: usize) -> Vec<(String, usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut scanner = ValueScanner::default();
+    let mut current: Option<(String, usize)> = None;
+    for line_num in start..=end.min(lines.len()) {
+        let line = lines[line_num - 1];
+        if current.is_none() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let Some(eq) = key_value_separator(trimmed) else {
+                continue;
+            };
+            current = Some((trimmed[..eq].trim().to_string(), line_num));
+            scanner.scan(&trimmed[eq + 1..]);
+        } else {
+            scanner.scan(line);
+        }
+        if scanner.is_closed() {
+            if let Some((key, key_line)) = current.take() {
+                pairs.push((key, key_line, line_num));
+            }
+        }
+    }
+    if let Some((key, key_line)) = current {
+        pairs.push((key, key_line, end));
+    }
+    pairs
+}
+
+/// Byte offset of the `=` ending the key of a key-value line.
+fn key_value_separator(line: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, ch) in line.char_indices() {
+        match (quote, ch) {
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '=') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Tracks bracket depth and open multi-line strings across the lines of a
+/// value.
+#[derive(Default)]
+struct ValueScanner {
+    depth: i32,
+    multiline: Option<&'static str>,
+}
+
+impl ValueScanner {
+    fn is_closed(&self) -> bool {
+        self.depth <= 0 && self.multiline.is_none()
+    }
+
+    fn scan(&mut self, text: &str) {
+        let mut rest = text;
+        while !rest.is_empty() {
+            if let Some(delimiter) = self.multiline {
+                match rest.find(delimiter) {
+                    Some(at) => {
+                        self.multiline = None;
+                        rest = &rest[at + delimiter.len()..];
+                    }
+                    None => return,
+                }
+                continue;
+            }
+            if let Some(delimiter) = ["\"\"\"", "'''"].into_iter().find(|d| rest.starts_with(d)) {
+                self.multiline = Some(delimiter);
+                rest = &rest[delimiter.len()..];
+                continue;
+            }
+            let ch = rest.chars().next().unwrap_or_default();
+            match ch {
+                '#' => return,
+                '"' | '\'' => {
+                    rest = skip_string(rest, ch);
+                    continue;
+                }
+                '[' | '{' => self.depth += 1,
+                ']' | '}' => self.depth -= 1,
+                _ => {}
+            }
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+}
+
+/// Skip a single-line string starting at `text[0]`, returning what follows.
+fn skip_string(text: &str, quote: char) -> &str {
+    let mut escaped = false;
+    for (i, ch) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' && quote == '"' {
+            escaped = true;
+        } else if ch == quote {
+            return &text[i + 1..];
+        }
+    }
+    ""
+}
+
 struct TomlSection {
     key: String,
     line: usize, // 1-based
-    /// `Some(n)` for the nth entry of an array-of-tables (`[[key]]`); `None` for
-    /// a regular table (`[key]`) or a root key-value pair.
-    array_index: Option<usize>,
+    /// `[key]` or `[[key]]`, as opposed to a root key-value pair.
+    is_header: bool,
+    /// An entry of an array of tables (`[[key]]`).
+    is_array: bool,
 }
 
 /// Find top-level entries in TOML: section headers ([name]), array-of-tables
 /// (\[\[name\]\]), and root key-value pairs.
 fn find_toml_sections(lines: &[&str]) -> Vec<TomlSection> {
     let mut sections = Vec::new();
-    // Per-key occurrence counter so repeated `[[key]]` entries get distinct
-    // indexed identities (key/0, key/1, ...) instead of collapsing to one.
-    let mut array_counts: std::collections::HashMap<String, usize> =
-        std::collections::HashMap::new();
 
     for (i, line) in lines.iter().enumerate() {
         let trimmed = line.trim();
@@ -136,13 +374,12 @@ fn find_toml_sections(lines: &[&str]) -> Vec<TomlSection> {
                 .trim()
                 .to_string();
             if !key.is_empty() {
-                let idx = array_counts.entry(key.clone()).or_insert(0);
                 sections.push(TomlSection {
-                    key: key.clone(),
+                    key,
                     line: i + 1,
-                    array_index: Some(*idx),
+                    is_header: true,
+                    is_array: true,
                 });
-                *idx += 1;
             }
             continue;
         }
@@ -158,7 +395,8 @@ fn find_toml_sections(lines: &[&str]) -> Vec<TomlSection> {
                 sections.push(TomlSection {
                     key,
                     line: i + 1,
-                    array_index: None,
+                    is_header: true,
+                    is_array: false,
                 });
             }
             continue;
@@ -174,7 +412,8 @@ fn find_toml_sections(lines: &[&str]) -> Vec<TomlSection> {
                     sections.push(TomlSection {
                         key,
                         line: i + 1,
-                        array_index: None,
+                        is_header: false,
+                        is_array: false,
                     });
                 }
             }
@@ -221,6 +460,10 @@ fn toml_value_to_string(value: &toml::Value) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
 
     #[test]
     fn test_toml_line_positions() {
@@ -234,6 +477,7 @@ tokio = { version = "1", features = ["full"] }
 "#;
         let plugin = TomlParserPlugin;
         let entities = plugin.extract_entities(content, "Cargo.toml");
+        let entities: Vec<_> = entities.iter().filter(|e| e.parent_id.is_none()).collect();
 
         assert_eq!(entities.len(), 2);
 
@@ -254,6 +498,7 @@ tokio = { version = "1", features = ["full"] }
         let content = "[[array]]\nitem = 1\n[[array]]\nitem = 2\n[[array]]\nitem = 3\n";
         let plugin = TomlParserPlugin;
         let entities = plugin.extract_entities(content, "a.toml");
+        let entities: Vec<_> = entities.iter().filter(|e| e.parent_id.is_none()).collect();
 
         assert_eq!(entities.len(), 3);
         for (i, e) in entities.iter().enumerate() {
@@ -263,6 +508,7 @@ tokio = { version = "1", features = ["full"] }
         // Each element hashes independently, so the first two are stable when a
         // third is appended.
         let two = plugin.extract_entities("[[array]]\nitem = 1\n[[array]]\nitem = 2\n", "a.toml");
+        let two: Vec<_> = two.iter().filter(|e| e.parent_id.is_none()).collect();
         assert_eq!(two[0].content_hash, entities[0].content_hash);
         assert_eq!(two[1].content_hash, entities[1].content_hash);
     }
@@ -277,4 +523,186 @@ tokio = { version = "1", features = ["full"] }
         assert!(ids.iter().any(|id| id.contains("section::server")));
         assert!(ids.iter().any(|id| id.contains("array_table::worker/0")));
     }
+
+    fn toml_diff(before: &str, after: &str) -> Vec<(String, ChangeType, Option<String>)> {
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(TomlParserPlugin));
+        let changes = vec![FileChange {
+            file_path: "Cargo.toml".to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some(before.to_string()),
+            after_content: Some(after.to_string()),
+        }];
+        compute_semantic_diff(&changes, &registry, None, None)
+            .changes
+            .into_iter()
+            .filter(|c| c.entity_type != "orphan")
+            .map(|c| (c.entity_id, c.change_type, c.parent_name))
+            .collect()
+    }
+
+    const CARGO_TOML: &str = r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+tokio = { version = "1", features = [
+    "rt",
+    "macros",
+] }
+
+[dependencies.regex]
+version = "1"
+features = ["std"]
+
+[[bin]]
+name = "app"
+path = "src/main.rs"
+
+[[bin]]
+name = "tool"
+path = "src/tool.rs"
+"#;
+
+    #[test]
+    fn nested_tables_and_keys_are_child_entities() {
+        let entities = TomlParserPlugin.extract_entities(CARGO_TOML, "Cargo.toml");
+        let summary: Vec<(&str, &str, usize, usize)> = entities
+            .iter()
+            .map(|e| {
+                (
+                    e.id.as_str(),
+                    e.entity_type.as_str(),
+                    e.start_line,
+                    e.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Cargo.toml::section::package", "section", 1, 3),
+                ("Cargo.toml::section::package::name", "property", 2, 2),
+                ("Cargo.toml::section::package::version", "property", 3, 3),
+                ("Cargo.toml::section::dependencies", "section", 5, 10),
+                ("Cargo.toml::section::dependencies::serde", "property", 6, 6),
+                (
+                    "Cargo.toml::section::dependencies::tokio",
+                    "property",
+                    7,
+                    10
+                ),
+                (
+                    "Cargo.toml::section::dependencies::regex",
+                    "section",
+                    12,
+                    14
+                ),
+                (
+                    "Cargo.toml::section::dependencies::regex::version",
+                    "property",
+                    13,
+                    13
+                ),
+                (
+                    "Cargo.toml::section::dependencies::regex::features",
+                    "property",
+                    14,
+                    14
+                ),
+                ("Cargo.toml::array_table::bin/0", "array_table", 16, 18),
+                ("Cargo.toml::array_table::bin/0::name", "property", 17, 17),
+                ("Cargo.toml::array_table::bin/0::path", "property", 18, 18),
+                ("Cargo.toml::array_table::bin/1", "array_table", 20, 22),
+                ("Cargo.toml::array_table::bin/1::name", "property", 21, 21),
+                ("Cargo.toml::array_table::bin/1::path", "property", 22, 22),
+            ]
+        );
+        let regex = &entities[6];
+        assert_eq!(
+            regex.parent_id.as_deref(),
+            Some("Cargo.toml::section::dependencies")
+        );
+    }
+
+    #[test]
+    fn dependency_feature_change_is_localized_to_that_dependency() {
+        let after =
+            CARGO_TOML.replace(r#"features = ["derive"]"#, r#"features = ["derive", "rc"]"#);
+        assert_eq!(
+            toml_diff(CARGO_TOML, &after),
+            vec![(
+                "Cargo.toml::section::dependencies::serde".to_string(),
+                ChangeType::Modified,
+                Some("dependencies".to_string()),
+            )]
+        );
+
+        let after = CARGO_TOML.replace(r#"features = ["std"]"#, r#"features = ["std", "unicode"]"#);
+        assert_eq!(
+            toml_diff(CARGO_TOML, &after),
+            vec![(
+                "Cargo.toml::section::dependencies::regex::features".to_string(),
+                ChangeType::Modified,
+                Some("dependencies::regex".to_string()),
+            )]
+        );
+    }
+
+    #[test]
+    fn array_of_tables_children_keep_their_element_index() {
+        let after = CARGO_TOML.replace("src/tool.rs", "src/bin/tool.rs");
+        assert_eq!(
+            toml_diff(CARGO_TOML, &after),
+            vec![(
+                "Cargo.toml::array_table::bin/1::path".to_string(),
+                ChangeType::Modified,
+                Some("bin/1".to_string()),
+            )]
+        );
+    }
+
+    #[test]
+    fn nested_array_of_tables_index_within_their_parent_element() {
+        let content = "[[fruit]]\nname = \"apple\"\n[[fruit.variety]]\nname = \"red\"\n[[fruit.variety]]\nname = \"green\"\n[[fruit]]\nname = \"plum\"\n[[fruit.variety]]\nname = \"damson\"\n";
+        let entities = TomlParserPlugin.extract_entities(content, "a.toml");
+        let varieties: Vec<(&str, Option<&str>)> = entities
+            .iter()
+            .filter(|e| e.name.starts_with("variety/"))
+            .map(|e| (e.id.as_str(), e.parent_id.as_deref()))
+            .collect();
+        assert_eq!(
+            varieties,
+            vec![
+                (
+                    "a.toml::array_table::fruit/0::variety/0",
+                    Some("a.toml::array_table::fruit/0")
+                ),
+                (
+                    "a.toml::array_table::fruit/0::variety/1",
+                    Some("a.toml::array_table::fruit/0")
+                ),
+                (
+                    "a.toml::array_table::fruit/1::variety/0",
+                    Some("a.toml::array_table::fruit/1")
+                ),
+            ]
+        );
+        let damson = entities
+            .iter()
+            .find(|e| e.id == "a.toml::array_table::fruit/1::variety/0::name")
+            .unwrap();
+        assert_eq!(damson.content_hash, content_hash("damson"));
+    }
+
+    #[test]
+    fn split_dotted_key_unquotes_segments() {
+        assert_eq!(split_dotted_key("a.b"), vec!["a", "b"]);
+        assert_eq!(
+            split_dotted_key(r#"a . "b.c" . 'd'"#),
+            vec!["a", "b.c", "d"]
+        );
+    }
 }
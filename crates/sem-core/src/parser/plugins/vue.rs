@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use crate::model::entity::{build_entity_id, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
 use crate::utils::hash::content_hash;
 
 use super::code::CodeParserPlugin;
+use super::css::CssParserPlugin;
 
 pub struct VueParserPlugin;
 
@@ -33,46 +36,90 @@ impl SemanticParserPlugin for VueParserPlugin {
                 end_line: block.end_line,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             };
 
             let block_id = entity.id.clone();
             entities.push(entity);
 
-            // For <script> blocks, delegate to the TS/JS parser for inner entities
-            if block.tag == "script" && !block.inner_content.is_empty() {
-                let ext = if block.lang == "ts" || block.lang == "tsx" {
-                    "script.ts"
-                } else {
-                    "script.js"
-                };
-                let virtual_path = format!("{}:{}", file_path, ext);
-                let code_plugin = CodeParserPlugin;
-                let inner = code_plugin.extract_entities(&block.inner_content, &virtual_path);
-
-                for mut child in inner {
-                    // Reparent: set file_path to the real .vue file, set parent to the script block
-                    child.file_path = file_path.to_string();
-                    child.parent_id = Some(block_id.clone());
-                    // Adjust line numbers to be relative to the .vue file
-                    child.start_line += block.inner_start_line - 1;
-                    child.end_line += block.inner_start_line - 1;
-                    // Rebuild ID with correct file_path
-                    child.id = build_entity_id(
-                        file_path,
-                        &child.entity_type,
-                        &child.name,
-                        child.parent_id.as_deref(),
-                    );
-                    entities.push(child);
+            // <script> blocks go through the TS/JS parser and <style> blocks
+            // through the CSS one, for the entities inside them
+            let (plugin, ext): (&dyn SemanticParserPlugin, &str) = match block.tag.as_str() {
+                "script" if block.lang == "ts" || block.lang == "tsx" => {
+                    (&CodeParserPlugin, "script.ts")
                 }
+                "script" => (&CodeParserPlugin, "script.js"),
+                "style" if block.lang == "scss" => (&CssParserPlugin, "style.scss"),
+                "style" => (&CssParserPlugin, "style.css"),
+                _ => continue,
+            };
+            if block.inner_content.is_empty() {
+                continue;
             }
+            let virtual_path = format!("{}:{}", file_path, ext);
+            let inner = plugin.extract_entities(&block.inner_content, &virtual_path);
+            entities.extend(embed_entities(
+                inner,
+                &virtual_path,
+                file_path,
+                &block_id,
+                block.inner_start_line - 1,
+            ));
         }
 
         entities
     }
 }
 
+/// Move entities extracted from an embedded block (parsed as `virtual_path`)
+/// into the enclosing file: top-level ones become children of
+/// `parent_id`, nested ones keep their parent, and lines shift by
+/// `line_offset`. Byte offsets are relative to the block, so they are dropped.
+pub(super) fn embed_entities(
+    entities: Vec<SemanticEntity>,
+    virtual_path: &str,
+    file_path: &str,
+    parent_id: &str,
+    line_offset: usize,
+) -> Vec<SemanticEntity> {
+    let mut new_ids: HashMap<String, String> = HashMap::new();
+    entities
+        .into_iter()
+        .map(|mut entity| {
+            let parent = entity
+                .parent_id
+                .as_ref()
+                .and_then(|old| new_ids.get(old))
+                .cloned()
+                .unwrap_or_else(|| parent_id.to_string());
+            // Keep any disambiguating suffix (`@L3`) the inner parser added.
+            let inner_base = build_entity_id(
+                virtual_path,
+                &entity.entity_type,
+                &entity.name,
+                entity.parent_id.as_deref(),
+            );
+            let suffix = entity.id.strip_prefix(&inner_base).unwrap_or_default();
+            let id = build_entity_id(file_path, &entity.entity_type, &entity.name, Some(&parent))
+                + suffix;
+            new_ids.insert(std::mem::take(&mut entity.id), id.clone());
+            entity.id = id;
+            entity.file_path = file_path.to_string();
+            entity.parent_id = Some(parent);
+            entity.start_line += line_offset;
+            entity.end_line += line_offset;
+            entity.start_byte = None;
+            entity.end_byte = None;
+            entity.name_start = None;
+            entity.name_end = None;
+            entity
+        })
+        .collect()
+}
+
 struct SfcBlock {
     tag: String,
     name: String,
@@ -344,4 +391,69 @@ class Counter {
         assert_eq!(hello.start_line, 6);
         assert_eq!(hello.end_line, 8);
     }
+
+    #[test]
+    fn test_vue_script_setup_exports_and_style_rules() {
+        let code = r#"<template>
+  <p class="total">{{ total }}</p>
+</template>
+
+<script setup lang="ts">
+export function add(a: number, b: number): number {
+  return a + b
+}
+
+export function scale(x: number): number {
+  return x * 2
+}
+</script>
+
+<style lang="scss">
+.total {
+  font-weight: bold;
+}
+</style>
+"#;
+        let entities = VueParserPlugin.extract_entities(code, "Total.vue");
+        let summary: Vec<(&str, &str, usize, usize, Option<&str>)> = entities
+            .iter()
+            .filter(|e| e.entity_type != "sfc_block")
+            .map(|e| {
+                (
+                    e.id.as_str(),
+                    e.entity_type.as_str(),
+                    e.start_line,
+                    e.end_line,
+                    e.parent_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "Total.vue::sfc_block::script setup::add",
+                    "function",
+                    6,
+                    8,
+                    Some("Total.vue::sfc_block::script setup"),
+                ),
+                (
+                    "Total.vue::sfc_block::script setup::scale",
+                    "function",
+                    10,
+                    12,
+                    Some("Total.vue::sfc_block::script setup"),
+                ),
+                (
+                    "Total.vue::sfc_block::style::.total",
+                    "rule",
+                    16,
+                    18,
+                    Some("Total.vue::sfc_block::style"),
+                ),
+            ]
+        );
+        assert!(entities.iter().all(|e| e.file_path == "Total.vue"));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::model::entity::{build_entity_id, SemanticEntity};
 use crate::parser::plugin::SemanticParserPlugin;
-use crate::utils::hash::content_hash;
+use crate::utils::hash::{content_hash, value_structural_hash};
 
 pub struct YamlParserPlugin;
 
@@ -37,23 +37,26 @@ impl SemanticParserPlugin for YamlParserPlugin {
                     end_line: lines.len(),
                     start_byte: None,
                     end_byte: None,
+                    name_start: None,
+                    name_end: None,
                     metadata: None,
+                    stable_id: None,
                 }];
             }
             return Vec::new();
         }
 
         // Determine entity types using serde_yaml for section vs property.
-        let section_keys: std::collections::HashSet<String> =
-            if let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str(content) {
-                mapping
-                    .iter()
-                    .filter(|(_, v)| v.is_mapping() || v.is_sequence())
-                    .filter_map(|(k, _)| k.as_str().map(String::from))
-                    .collect()
-            } else {
-                std::collections::HashSet::new()
-            };
+        let parsed = match serde_yaml::from_str(content) {
+            Ok(serde_yaml::Value::Mapping(mapping)) => Some(mapping),
+            _ => None,
+        };
+        let section_keys: std::collections::HashSet<String> = parsed
+            .iter()
+            .flatten()
+            .filter(|(_, v)| v.is_mapping() || v.is_sequence())
+            .filter_map(|(k, _)| k.as_str().map(String::from))
+            .collect();
 
         let mut entities = Vec::new();
 
@@ -76,7 +79,10 @@ impl SemanticParserPlugin for YamlParserPlugin {
                         end_line: preamble_end,
                         start_byte: None,
                         end_byte: None,
+                        name_start: None,
+                        name_end: None,
                         metadata: None,
+                        stable_id: None,
                     });
                 }
             }
@@ -94,7 +100,12 @@ impl SemanticParserPlugin for YamlParserPlugin {
             let is_section = section_keys.contains(&tk.key);
             let entity_type = if is_section { "section" } else { "property" };
 
-            // Hash raw text so comment changes within a section are detected.
+            // Hash raw text so comment changes within a section are detected;
+            // the structural hash covers only the parsed value.
+            let structural_hash = parsed
+                .as_ref()
+                .and_then(|mapping| mapping.get(tk.key.as_str()))
+                .and_then(structural_value_hash);
             entities.push(SemanticEntity {
                 id: build_entity_id(file_path, entity_type, &tk.key, None),
                 file_path: file_path.to_string(),
@@ -102,13 +113,16 @@ impl SemanticParserPlugin for YamlParserPlugin {
                 name: tk.key.clone(),
                 parent_id: None,
                 content_hash: content_hash(&entity_content),
-                structural_hash: None,
+                structural_hash,
                 content: entity_content,
                 start_line: tk.line,
                 end_line,
                 start_byte: None,
                 end_byte: None,
+                name_start: None,
+                name_end: None,
                 metadata: None,
+                stable_id: None,
             });
         }
 
@@ -116,6 +130,16 @@ impl SemanticParserPlugin for YamlParserPlugin {
     }
 }
 
+/// Hash of a YAML value in canonical form: mappings with sorted keys and
+/// scalars as parsed (`'1'` and `"1"` agree, `1` and `0x1` agree), so
+/// reindenting, requoting or reordering keys is a cosmetic change. `None`
+/// when the value has keys JSON can't represent.
+fn structural_value_hash(value: &serde_yaml::Value) -> Option<String> {
+    serde_json::to_value(value)
+        .ok()
+        .map(|value| value_structural_hash(&value))
+}
+
 struct TopLevelKey {
     key: String,
     line: usize, // 1-based
@@ -160,6 +184,10 @@ fn trim_trailing_blanks_yaml(lines: &[&str], start: usize, next_start: usize) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::types::{FileChange, FileStatus};
+    use crate::model::change::ChangeType;
+    use crate::parser::differ::compute_semantic_diff;
+    use crate::parser::registry::ParserRegistry;
 
     #[test]
     fn test_yaml_line_positions() {
@@ -224,4 +252,23 @@ mod tests {
         // its content_hash should differ between versions.
         assert_ne!(entities_a[0].content_hash, entities_b[0].content_hash);
     }
+
+    #[test]
+    fn test_yaml_reordered_keys_are_cosmetic() {
+        let changes = vec![FileChange {
+            file_path: "config.yaml".to_string(),
+            status: FileStatus::Modified,
+            old_file_path: None,
+            before_content: Some("scripts:\n  build: tsc\n  test: jest\n".to_string()),
+            after_content: Some("scripts:\n  test:   jest\n  build: tsc\n".to_string()),
+        }];
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(YamlParserPlugin));
+        let result = compute_semantic_diff(&changes, &registry, None, None);
+
+        assert_eq!(result.changes.len(), 1);
+        assert_eq!(result.changes[0].entity_name, "scripts");
+        assert_eq!(result.changes[0].change_type, ChangeType::Modified);
+        assert_eq!(result.changes[0].structural_change, Some(false));
+    }
 }
@@ -1,9 +1,12 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Mutex;
 
-use crate::model::entity::{build_entity_id, SemanticEntity};
+use crate::model::diagnostic::{ParseDiagnostic, SkipReason};
+use crate::model::entity::{ancestors, assign_stable_ids, build_entity_id, SemanticEntity};
+use crate::utils::source::{read_source_file, DEFAULT_MAX_FILE_SIZE};
 
 macro_rules! maybe_par_iter {
     ($slice:expr) => {{
@@ -18,12 +21,33 @@ macro_rules! maybe_par_iter {
     }};
 }
 use super::plugin::{strip_entity_payloads, SemanticParserPlugin};
+use super::plugins::fallback::{FallbackChunking, FallbackParserPlugin, DEFAULT_CHUNK_SIZE};
+use super::stopwords::StopwordOverrides;
 
 pub struct ParserRegistry {
     plugins: Vec<Box<dyn SemanticParserPlugin>>,
     extension_map: HashMap<String, usize>, // ext → index into plugins
+    filename_map: HashMap<String, usize>,  // lowercased file name → index into plugins
     custom_ext_canonical: HashMap<String, String>, // ".mypy" → ".py" (custom → canonical)
     pub custom_test_dirs: Vec<String>,
+    /// Files above this size are skipped by [`ParserRegistry::read_source`].
+    pub max_file_size: u64,
+    /// Honor `sem:ignore` directives in diffs and graph builds.
+    pub ignore_directives: bool,
+    /// Entity types named by `type:` lines in `.semignore`. Extraction drops
+    /// them along with everything nested inside them.
+    pub ignored_entity_types: Vec<String>,
+    /// Leave changes in the test partition (test files and `#[cfg(test)]`
+    /// modules) out of diffs, for `--exclude-tests`.
+    pub exclude_tests: bool,
+    /// Leave changes outside the public surface (see
+    /// [`crate::parser::visibility::private_ids`]) out of diffs, for
+    /// `--public-only`.
+    pub public_only: bool,
+    /// Reference stopwords added or lifted by `stopwords` and
+    /// `reference-words` lines in `.semrc`; see [`super::stopwords`].
+    pub stopwords: StopwordOverrides,
+    diagnostics: Mutex<Vec<ParseDiagnostic>>,
 }
 
 impl ParserRegistry {
@@ -31,36 +55,110 @@ impl ParserRegistry {
         Self {
             plugins: Vec::new(),
             extension_map: HashMap::new(),
+            filename_map: HashMap::new(),
             custom_ext_canonical: HashMap::new(),
             custom_test_dirs: Vec::new(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            ignore_directives: true,
+            ignored_entity_types: Vec::new(),
+            exclude_tests: false,
+            public_only: false,
+            stopwords: StopwordOverrides::default(),
+            diagnostics: Mutex::new(Vec::new()),
         }
     }
 
+    /// Read a repo file for parsing. Files over `max_file_size` or not valid
+    /// UTF-8 are recorded as diagnostics (see
+    /// [`ParserRegistry::take_diagnostics`]) and return `None`, as do files
+    /// that can't be read.
+    pub fn read_source(&self, root: &Path, file_path: &str) -> Option<String> {
+        match read_source_file(&root.join(file_path), self.max_file_size) {
+            Ok(content) => content,
+            Err(reason) => {
+                self.record_diagnostic(file_path, reason);
+                None
+            }
+        }
+    }
+
+    pub fn record_diagnostic(&self, file_path: &str, reason: SkipReason) {
+        self.diagnostics.lock().unwrap().push(ParseDiagnostic {
+            file_path: file_path.to_string(),
+            reason,
+        });
+    }
+
+    /// Record why `content` produced no entities.
+    pub fn record_no_entities(&self, file_path: &str, content: &str) {
+        self.record_diagnostic(file_path, self.diagnose_no_entities(file_path, content));
+    }
+
+    /// Why `content` produces no entities: blank, no plugin for the file,
+    /// a plugin-reported parse error, or simply nothing to extract.
+    pub fn diagnose_no_entities(&self, file_path: &str, content: &str) -> SkipReason {
+        if content.trim().is_empty() {
+            return SkipReason::Empty;
+        }
+        let detection_path = self.resolve_file_path(file_path);
+        let detection_path = detection_path.as_deref().unwrap_or(file_path);
+        let Some(plugin) = self.get_plugin_with_content(detection_path, content) else {
+            return SkipReason::Unsupported;
+        };
+        match plugin.parse_error(content, detection_path) {
+            Some(message) => SkipReason::ParseError { message },
+            None => SkipReason::NoEntities,
+        }
+    }
+
+    /// Diagnostics recorded since the last call, one per file, sorted by path.
+    pub fn take_diagnostics(&self) -> Vec<ParseDiagnostic> {
+        let mut diagnostics = std::mem::take(&mut *self.diagnostics.lock().unwrap());
+        diagnostics.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        diagnostics.dedup_by(|a, b| a.file_path == b.file_path);
+        diagnostics
+    }
+
     pub fn register(&mut self, plugin: Box<dyn SemanticParserPlugin>) {
         let idx = self.plugins.len();
         for ext in plugin.extensions() {
             self.extension_map.insert(ext.to_string(), idx);
         }
+        for name in plugin.filenames() {
+            self.filename_map.insert(name.to_lowercase(), idx);
+        }
         self.plugins.push(plugin);
     }
 
     pub fn get_plugin(&self, file_path: &str) -> Option<&dyn SemanticParserPlugin> {
-        for ext in get_extensions(file_path) {
-            if let Some(&idx) = self.extension_map.get(&ext) {
-                return Some(self.plugins[idx].as_ref());
-            }
+        if let Some(plugin) = self.get_explicit_plugin(file_path) {
+            return Some(plugin);
         }
         // Fallback plugin
         self.get_plugin_by_id("fallback")
     }
 
+    /// The plugin claiming this path by file name or extension. An exact file
+    /// name wins over extensions, which win over a file name with a suffix
+    /// (`Dockerfile.prod`).
     pub fn get_explicit_plugin(&self, file_path: &str) -> Option<&dyn SemanticParserPlugin> {
+        let file_name = Path::new(file_path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        if let Some(&idx) = self.filename_map.get(&file_name) {
+            return Some(self.plugins[idx].as_ref());
+        }
         for ext in get_extensions(file_path) {
             if let Some(&idx) = self.extension_map.get(&ext) {
                 return Some(self.plugins[idx].as_ref());
             }
         }
-        None
+        let (base, _) = file_name.split_once('.')?;
+        self.filename_map
+            .get(base)
+            .map(|&idx| self.plugins[idx].as_ref())
     }
 
     pub fn detect_plugin_from_content(&self, content: &str) -> Option<&dyn SemanticParserPlugin> {
@@ -74,11 +172,9 @@ impl ParserRegistry {
         file_path: &str,
         content: &str,
     ) -> Option<&dyn SemanticParserPlugin> {
-        // Try extension first
-        for ext in get_extensions(file_path) {
-            if let Some(&idx) = self.extension_map.get(&ext) {
-                return Some(self.plugins[idx].as_ref());
-            }
+        // Try file name and extension first
+        if let Some(plugin) = self.get_explicit_plugin(file_path) {
+            return Some(plugin);
         }
         // Try shebang detection
         if let Some(plugin) = self.detect_from_shebang(content) {
@@ -145,6 +241,10 @@ impl ParserRegistry {
     ///   .inc = php
     ///   .j = json
     ///   .xyz = cpp
+    ///
+    /// `fallback-chunking = blocks|fixed` and `fallback-chunk-size = N` set
+    /// how files no grammar claims are cut into chunks (see
+    /// [`FallbackChunking`]).
     pub fn load_semrc(&mut self, root: &Path) {
         let semrc_path = root.join(".semrc");
         if !semrc_path.exists() {
@@ -154,6 +254,8 @@ impl ParserRegistry {
             Ok(c) => c,
             Err(_) => return,
         };
+        let mut fixed_chunks: Option<bool> = None;
+        let mut chunk_size: Option<usize> = None;
         for line in content.lines() {
             let line = line.trim();
             if line.is_empty() || line.starts_with('#') {
@@ -168,11 +270,53 @@ impl ParserRegistry {
                         .map(|s| s.trim().to_string())
                         .filter(|s| !s.is_empty())
                         .collect();
+                } else if key == "fallback-chunking" {
+                    fixed_chunks = match value {
+                        "fixed" => Some(true),
+                        "blocks" => Some(false),
+                        _ => fixed_chunks,
+                    };
+                } else if key == "fallback-chunk-size" {
+                    chunk_size = value.parse().ok().filter(|&n| n > 0).or(chunk_size);
+                } else if let Some(language) = semrc_key_language(key, "stopwords") {
+                    self.stopwords.skip(language, value.split(','));
+                } else if let Some(language) = semrc_key_language(key, "reference-words") {
+                    self.stopwords.keep(language, value.split(','));
                 } else {
                     self.add_extension_mapping(key, value);
                 }
             }
         }
+        if fixed_chunks.is_some() || chunk_size.is_some() {
+            let size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+            self.set_fallback_chunking(if fixed_chunks == Some(true) {
+                FallbackChunking::Fixed(size)
+            } else {
+                FallbackChunking::Blocks(size)
+            });
+        }
+    }
+
+    /// Swap the fallback plugin for one chunking files this way.
+    pub fn set_fallback_chunking(&mut self, chunking: FallbackChunking) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id() == "fallback") {
+            *plugin = Box::new(FallbackParserPlugin::new(chunking));
+        }
+    }
+
+    /// Load entity type exclusions from `.semignore` at the given root
+    /// directory: lines of the form `type:<entity type>`, e.g. `type:field`.
+    /// The file's other lines are path globs, applied by file discovery.
+    pub fn load_semignore(&mut self, root: &Path) {
+        let Ok(content) = std::fs::read_to_string(root.join(".semignore")) else {
+            return;
+        };
+        self.ignored_entity_types = content
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("type:"))
+            .map(|entity_type| entity_type.trim().to_string())
+            .filter(|entity_type| !entity_type.is_empty())
+            .collect();
     }
 
     /// Load extension mappings from `.gitattributes` at the given root directory.
@@ -257,6 +401,8 @@ impl ParserRegistry {
         if let Some(ref rp) = resolved {
             fix_entity_paths(&mut entities, file_path, rp);
         }
+        self.drop_ignored_entity_types(&mut entities);
+        assign_stable_ids(&mut entities);
         entities
     }
 
@@ -274,6 +420,7 @@ impl ParserRegistry {
         if let Some(ref rp) = resolved {
             fix_entity_paths(&mut entities, file_path, rp);
         }
+        self.drop_ignored_entity_types(&mut entities);
         entities
     }
 
@@ -291,9 +438,28 @@ impl ParserRegistry {
         if let Some(ref rp) = resolved {
             fix_entity_paths(&mut entities, file_path, rp);
         }
+        self.drop_ignored_entity_types(&mut entities);
+        assign_stable_ids(&mut entities);
         Some((entities, tree))
     }
 
+    pub(crate) fn drop_ignored_entity_types(&self, entities: &mut Vec<SemanticEntity>) {
+        if self.ignored_entity_types.is_empty() {
+            return;
+        }
+        let by_id: HashMap<&str, &SemanticEntity> =
+            entities.iter().map(|e| (e.id.as_str(), e)).collect();
+        let dropped: HashSet<String> = entities
+            .iter()
+            .filter(|entity| {
+                ancestors(entity, &by_id)
+                    .any(|at| self.ignored_entity_types.contains(&at.entity_type))
+            })
+            .map(|entity| entity.id.clone())
+            .collect();
+        entities.retain(|entity| !dropped.contains(&entity.id));
+    }
+
     /// Extract entities from multiple files in parallel.
     pub fn extract_all_entities(&self, root: &Path, file_paths: &[String]) -> Vec<SemanticEntity> {
         let mut entities: Vec<SemanticEntity> = maybe_par_iter!(file_paths)
@@ -454,6 +620,18 @@ fn fix_entity_paths(entities: &mut [SemanticEntity], original: &str, resolved: &
     }
 }
 
+/// Match a `.semrc` key of the form `name` or `name.<language id>`. Returns
+/// the language the line is scoped to, `None` when it applies to all.
+fn semrc_key_language<'a>(key: &'a str, name: &str) -> Option<Option<&'a str>> {
+    let rest = key.strip_prefix(name)?;
+    if rest.is_empty() {
+        return Some(None);
+    }
+    rest.strip_prefix('.')
+        .filter(|language| !language.is_empty())
+        .map(Some)
+}
+
 fn get_extensions(file_path: &str) -> Vec<String> {
     let Some(file_name) = Path::new(file_path)
         .file_name()
@@ -497,6 +675,8 @@ const LANG_MAPPING: &[(&str, &str)] = &[
     ("c", ".c"),
     ("cpp", ".cpp"),
     ("c++", ".cpp"),
+    ("objc", ".m"),
+    ("objective-c", ".m"),
     ("cs", ".cs"),
     ("csharp", ".cs"),
     ("c#", ".cs"),
@@ -521,6 +701,14 @@ const LANG_MAPPING: &[(&str, &str)] = &[
     ("erb", ".erb"),
     ("vue", ".vue"),
     ("svelte", ".svelte"),
+    ("html", ".html"),
+    ("css", ".css"),
+    ("scss", ".scss"),
+    ("graphql", ".graphql"),
+    ("proto", ".proto"),
+    ("dockerfile", ".dockerfile"),
+    ("rscript", ".r"),
+    ("julia", ".jl"),
 ];
 
 /// Detect file extension from shebang line, vim modeline, or content heuristics.
@@ -1007,6 +1195,26 @@ mod tests {
         assert_eq!(plugin.id(), "code"); // Python uses code plugin, not PHP
     }
 
+    #[test]
+    fn test_semignore_entity_types_drop_matching_entities_and_their_children() {
+        let dir = TempDir::new().unwrap();
+        write_file(
+            &dir,
+            ".semignore",
+            "dist/\ntype:field\n# comment\ntype: class\n",
+        );
+        let mut registry = create_default_registry();
+        registry.load_semignore(dir.path());
+        assert_eq!(registry.ignored_entity_types, vec!["field", "class"]);
+
+        let entities = registry.extract_entities(
+            "a.ts",
+            "class A {\n  x = 1;\n  m() {}\n}\nfunction f() {}\n",
+        );
+        let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["f"]);
+    }
+
     #[test]
     fn test_load_semrc_parses_test_dirs() {
         let dir = TempDir::new().unwrap();
@@ -1031,6 +1239,24 @@ mod tests {
         assert!(registry.get_plugin("bar.xyz").is_some());
     }
 
+    #[test]
+    fn test_load_semrc_sets_fallback_chunking() {
+        let dir = TempDir::new().unwrap();
+        write_file(
+            &dir,
+            ".semrc",
+            "fallback-chunking = fixed\nfallback-chunk-size = 2\n",
+        );
+        let mut registry = create_default_registry();
+        registry.load_semrc(dir.path());
+        let names: Vec<String> = registry
+            .extract_entities("notes.unknown", "a\nb\n\nc\n")
+            .into_iter()
+            .map(|entity| entity.name)
+            .collect();
+        assert_eq!(names, vec!["lines 1-2", "lines 3-4"]);
+    }
+
     #[test]
     fn test_load_semrc_skips_empty_test_dirs() {
         let dir = TempDir::new().unwrap();
@@ -1049,6 +1275,30 @@ mod tests {
         assert!(registry.custom_test_dirs.is_empty());
     }
 
+    #[test]
+    fn test_load_semrc_reads_stopword_overrides() {
+        use crate::parser::stopwords::Stopwords;
+
+        let dir = TempDir::new().unwrap();
+        write_file(
+            &dir,
+            ".semrc",
+            "stopwords = payload
+stopwords.go = ctx
+reference-words.python = status
+",
+        );
+        let mut registry = create_default_registry();
+        registry.load_semrc(dir.path());
+        assert!(registry.get_plugin("main.go").is_some());
+        let go = Stopwords::for_language(Some("go"), &registry.stopwords);
+        let python = Stopwords::for_language(Some("python"), &registry.stopwords);
+        assert!(go.is_keyword("payload") && python.is_keyword("payload"));
+        assert!(go.is_keyword("ctx") && !python.is_keyword("ctx"));
+        assert!(!python.is_common_local_name("status"));
+        assert!(go.is_common_local_name("status"));
+    }
+
     #[test]
     fn test_custom_extension_mapping_extracts_entities() {
         let mut registry = create_default_registry();
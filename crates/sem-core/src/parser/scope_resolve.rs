@@ -34,7 +34,7 @@ macro_rules! maybe_par_iter {
         }
     }};
 }
-use crate::parser::graph::{EntityInfo, RefType};
+use crate::parser::graph::{EntityInfo, RefType, AMBIGUOUS_NAME_TARGETS};
 use crate::parser::import_resolution::{
     find_import_file, find_import_target, import_source_matches_file, is_js_ts_file,
     js_ts_named_exports_from_content, sort_import_candidate_files, JS_TS_EXTENSIONS,
@@ -5992,9 +5992,12 @@ fn resolve_ref(
                 return Some(((*target_id).to_string(), RefType::Calls, "import"));
             }
 
-            // 3. Global symbol table fallback (constructor calls or cross-file functions)
+            // 3. Global symbol table fallback (constructor calls or cross-file functions).
+            // A name defined in too many places isn't linked across files at all.
             if let Some(target_ids) = symbol_table.get(name.as_str()) {
                 let is_constructor = name.chars().next().map_or(false, |c| c.is_uppercase());
+                let cross_file = (is_constructor || allow_cross_file_calls)
+                    && target_ids.len() <= AMBIGUOUS_NAME_TARGETS;
                 let ref_type = if is_constructor {
                     RefType::TypeRef
                 } else {
@@ -6011,7 +6014,7 @@ fn resolve_ref(
                         .first_id_by_name(name)
                         .map(str::to_string)
                         .or_else(|| {
-                            if is_constructor || allow_cross_file_calls {
+                            if cross_file {
                                 target_ids.first().cloned()
                             } else {
                                 None
@@ -6033,7 +6036,7 @@ fn resolve_ref(
                     .collect();
                 let visible_targets: Vec<&String> = if !same_file_targets.is_empty() {
                     same_file_targets
-                } else if is_constructor || allow_cross_file_calls {
+                } else if cross_file {
                     target_ids.iter().collect()
                 } else {
                     Vec::new()
@@ -6087,7 +6090,9 @@ fn resolve_ref(
         } => {
             // Strip prefix operators like ! (Swift: `!dog.validate()`)
             let receiver = normalized_method_receiver(raw_receiver);
-            if receiver == "self" || receiver == "this" {
+            // Python classmethods receive the class as `cls`.
+            let is_class_receiver = receiver == "cls" && file_path.ends_with(".py");
+            if receiver == "self" || receiver == "this" || is_class_receiver {
                 // self.method() -> find in enclosing class
                 let mut idx = scope_idx;
                 loop {
@@ -6830,6 +6835,8 @@ mod tests {
                 parent_id: None,
                 start_line: 1,
                 end_line: 3,
+                is_test: false,
+                is_private: false,
             },
         );
         entity_map.insert(
@@ -6842,6 +6849,8 @@ mod tests {
                 parent_id: None,
                 start_line: 1,
                 end_line: 3,
+                is_test: false,
+                is_private: false,
             },
         );
 
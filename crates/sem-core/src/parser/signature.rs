@@ -0,0 +1,585 @@
+//! Parameter lists and call arguments read from the syntax tree.
+//!
+//! Arity checks need real counts: a text scan for the first `(` after a
+//! function's name miscounts arrow functions, destructured parameters and
+//! calls mentioned in strings or comments. These helpers reparse an entity's
+//! `content` and read the parameter list and call expressions from the AST.
+//! Supported for Rust, TypeScript/JavaScript, Python, Java and Go; other
+//! languages return `None` so callers can fall back to their own heuristics.
+
+use tree_sitter::{Node, Parser, Tree};
+
+use crate::parser::plugins::code::languages::get_language_config;
+
+/// How a parameter binds call arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Required,
+    /// Has a default value or is marked optional (`x?: T`).
+    Optional,
+    /// Takes any number of arguments (`...rest`, `*args`, `**kwargs`,
+    /// `String... names`, `xs ...int`).
+    Variadic,
+    /// Bound by the call's receiver, not an argument (`self`, `this: T`).
+    Receiver,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// Bound name, or the pattern's source for destructured parameters.
+    /// Empty for unnamed parameters (`func(int)` in Go).
+    pub name: String,
+    /// The parameter's full source, including type and default.
+    pub text: String,
+    pub kind: ParameterKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub parameters: Vec<Parameter>,
+}
+
+impl Signature {
+    /// Fewest arguments a call must pass.
+    pub fn min_args(&self) -> usize {
+        self.count(|kind| kind == ParameterKind::Required)
+    }
+
+    /// Most arguments a call may pass; `None` when variadic.
+    pub fn max_args(&self) -> Option<usize> {
+        if self.count(|kind| kind == ParameterKind::Variadic) > 0 {
+            return None;
+        }
+        Some(self.count(|kind| matches!(kind, ParameterKind::Required | ParameterKind::Optional)))
+    }
+
+    pub fn has_receiver(&self) -> bool {
+        self.count(|kind| kind == ParameterKind::Receiver) > 0
+    }
+
+    /// Names of the parameters call arguments bind to, receivers excluded.
+    pub fn names(&self) -> Vec<&str> {
+        self.parameters
+            .iter()
+            .filter(|param| param.kind != ParameterKind::Receiver)
+            .map(|param| param.name.as_str())
+            .collect()
+    }
+
+    fn count(&self, pred: impl Fn(ParameterKind) -> bool) -> usize {
+        self.parameters
+            .iter()
+            .filter(|param| pred(param.kind))
+            .count()
+    }
+}
+
+/// One call expression found in an entity's content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallArguments {
+    /// Source of each argument, in order.
+    pub arguments: Vec<String>,
+    /// Line of the call relative to the start of the content (0-based).
+    pub line_offset: usize,
+    /// Last identifier of the receiver in `a.b.name(...)` / `Type::name(...)`.
+    pub qualifier: Option<String>,
+}
+
+/// The parameter list of a function or method entity. `None` for other
+/// entity types, unsupported languages, or content with no function node.
+pub fn parse_signature(content: &str, entity_type: &str, file_path: &str) -> Option<Signature> {
+    if !matches!(entity_type, "function" | "method" | "constructor") {
+        return None;
+    }
+    let parsed = ParsedEntity::parse(content, file_path)?;
+    let function = find_first(parsed.tree.root_node(), is_function_node)?;
+    let source = parsed.source.as_bytes();
+
+    let parameters = match function.child_by_field_name("parameters") {
+        Some(list) => collect_parameters(list, source, parsed.language),
+        // `x => x * 2`
+        None => {
+            let param = function.child_by_field_name("parameter")?;
+            vec![Parameter {
+                name: text(param, source).to_string(),
+                text: text(param, source).to_string(),
+                kind: ParameterKind::Required,
+            }]
+        }
+    };
+    Some(Signature { parameters })
+}
+
+/// Calls to `callee` in `content`, in source order. Calls that spread a
+/// collection into the arguments (`f(...xs)`, `f(*args)`, `f(xs...)`) are
+/// left out since their argument count isn't known statically. `None` for
+/// unsupported languages.
+pub fn call_arguments(content: &str, file_path: &str, callee: &str) -> Option<Vec<CallArguments>> {
+    let parsed = ParsedEntity::parse(content, file_path)?;
+    let source = parsed.source.as_bytes();
+    let mut calls = Vec::new();
+
+    let mut stack = vec![parsed.tree.root_node()];
+    while let Some(node) = stack.pop() {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+
+        let Some((name, qualifier, arguments)) = call_parts(node) else {
+            continue;
+        };
+        if text(name, source) != callee {
+            continue;
+        }
+        let arguments: Vec<Node> = match arguments.kind() {
+            "arguments" | "argument_list" => {
+                let mut cursor = arguments.walk();
+                arguments
+                    .named_children(&mut cursor)
+                    .filter(|arg| arg.kind() != "comment")
+                    .collect()
+            }
+            // `sum(x for x in xs)`
+            "generator_expression" => vec![arguments],
+            _ => continue,
+        };
+        if arguments.iter().any(|arg| is_spread(*arg, source)) {
+            continue;
+        }
+
+        calls.push(CallArguments {
+            arguments: arguments
+                .iter()
+                .map(|arg| text(*arg, source).to_string())
+                .collect(),
+            line_offset: node.start_position().row - parsed.line_offset,
+            qualifier: qualifier
+                .map(|q| text(q, source))
+                .and_then(|q| q.rsplit(|c: char| !is_ident_char(c)).next())
+                .filter(|q| !q.is_empty())
+                .map(str::to_string),
+        });
+    }
+    Some(calls)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    JavaScript,
+    Python,
+    Java,
+    Go,
+}
+
+/// An entity's content parsed on its own. Bare TS/JS/Java methods aren't
+/// valid at the top level, so they're parsed inside a placeholder class.
+struct ParsedEntity {
+    tree: Tree,
+    source: String,
+    language: Language,
+    /// Lines added before the content by wrapping.
+    line_offset: usize,
+}
+
+impl ParsedEntity {
+    fn parse(content: &str, file_path: &str) -> Option<Self> {
+        let ext = file_path.rfind('.').map(|i| &file_path[i..])?;
+        let config = get_language_config(ext)?;
+        let language = match config.id {
+            "rust" => Language::Rust,
+            "typescript" | "tsx" | "javascript" => Language::JavaScript,
+            "python" => Language::Python,
+            "java" => Language::Java,
+            "go" => Language::Go,
+            _ => return None,
+        };
+        let mut parser = Parser::new();
+        parser.set_language(&(config.get_language)()?).ok()?;
+
+        let tree = parser.parse(content, None)?;
+        let wrappable = matches!(language, Language::JavaScript | Language::Java);
+        let usable = !tree.root_node().has_error()
+            && (!wrappable || find_first(tree.root_node(), is_function_node).is_some());
+        if usable || !wrappable {
+            return Some(Self {
+                tree,
+                source: content.to_string(),
+                language,
+                line_offset: 0,
+            });
+        }
+
+        let wrapped = format!("class __SemEntity {{\n{content}\n}}\n");
+        let wrapped_tree = parser.parse(&wrapped, None)?;
+        if wrapped_tree.root_node().has_error() && !tree.root_node().has_error() {
+            return Some(Self {
+                tree,
+                source: content.to_string(),
+                language,
+                line_offset: 0,
+            });
+        }
+        Some(Self {
+            tree: wrapped_tree,
+            source: wrapped,
+            language,
+            line_offset: 1,
+        })
+    }
+}
+
+fn is_function_node(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "function_item"
+            | "function_signature_item"
+            | "function_declaration"
+            | "generator_function_declaration"
+            | "function_expression"
+            | "function"
+            | "arrow_function"
+            | "method_definition"
+            | "method_signature"
+            | "abstract_method_signature"
+            | "function_signature"
+            | "function_definition"
+            | "method_declaration"
+            | "constructor_declaration"
+            | "func_literal"
+    )
+}
+
+fn find_first<'t>(root: Node<'t>, pred: fn(Node) -> bool) -> Option<Node<'t>> {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if pred(node) {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    None
+}
+
+fn collect_parameters(list: Node, source: &[u8], language: Language) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut cursor = list.walk();
+    for child in list.named_children(&mut cursor) {
+        let param = |name: Node, kind| Parameter {
+            name: text(name, source).to_string(),
+            text: text(child, source).to_string(),
+            kind,
+        };
+        match child.kind() {
+            "comment" | "attribute_item" | "keyword_separator" | "positional_separator" => {}
+            // Rust
+            "self_parameter" => parameters.push(param(child, ParameterKind::Receiver)),
+            "parameter" => {
+                let pattern = child.child_by_field_name("pattern").unwrap_or(child);
+                parameters.push(param(pattern, ParameterKind::Required));
+            }
+            "variadic_parameter" => parameters.push(param(child, ParameterKind::Variadic)),
+            // TypeScript
+            "required_parameter" | "optional_parameter" => {
+                let pattern = child.child_by_field_name("pattern").unwrap_or(child);
+                let kind = if pattern.kind() == "rest_pattern" {
+                    ParameterKind::Variadic
+                } else if pattern.kind() == "this" || text(pattern, source) == "this" {
+                    ParameterKind::Receiver
+                } else if child.kind() == "optional_parameter"
+                    || child.child_by_field_name("value").is_some()
+                {
+                    ParameterKind::Optional
+                } else {
+                    ParameterKind::Required
+                };
+                parameters.push(param(pattern, kind));
+            }
+            // JavaScript
+            "assignment_pattern" => {
+                let left = child.child_by_field_name("left").unwrap_or(child);
+                parameters.push(param(left, ParameterKind::Optional));
+            }
+            "rest_pattern" => parameters.push(param(child, ParameterKind::Variadic)),
+            // Python
+            "typed_parameter" => {
+                let name = child.named_child(0).unwrap_or(child);
+                let kind = if matches!(
+                    name.kind(),
+                    "list_splat_pattern" | "dictionary_splat_pattern"
+                ) {
+                    ParameterKind::Variadic
+                } else {
+                    ParameterKind::Required
+                };
+                parameters.push(param(name, kind));
+            }
+            "default_parameter" | "typed_default_parameter" => {
+                let name = child.child_by_field_name("name").unwrap_or(child);
+                parameters.push(param(name, ParameterKind::Optional));
+            }
+            "list_splat_pattern" | "dictionary_splat_pattern" => {
+                parameters.push(param(child, ParameterKind::Variadic))
+            }
+            // Java
+            "formal_parameter" => {
+                let name = child.child_by_field_name("name").unwrap_or(child);
+                parameters.push(param(name, ParameterKind::Required));
+            }
+            "spread_parameter" => parameters.push(param(child, ParameterKind::Variadic)),
+            "receiver_parameter" => parameters.push(param(child, ParameterKind::Receiver)),
+            // Go: `a, b int` declares two parameters.
+            "parameter_declaration" => {
+                let mut name_cursor = child.walk();
+                let names: Vec<Node> = child
+                    .children_by_field_name("name", &mut name_cursor)
+                    .collect();
+                if names.is_empty() {
+                    parameters.push(Parameter {
+                        name: String::new(),
+                        text: text(child, source).to_string(),
+                        kind: ParameterKind::Required,
+                    });
+                }
+                for name in names {
+                    parameters.push(param(name, ParameterKind::Required));
+                }
+            }
+            "variadic_parameter_declaration" => {
+                let name = child.child_by_field_name("name").unwrap_or(child);
+                parameters.push(param(name, ParameterKind::Variadic));
+            }
+            // Plain names (JS `identifier`, Python `identifier`) and
+            // destructuring patterns.
+            _ => parameters.push(param(child, ParameterKind::Required)),
+        }
+    }
+
+    // Python receivers are ordinary first parameters by convention.
+    if language == Language::Python {
+        if let Some(first) = parameters.first_mut() {
+            if first.kind == ParameterKind::Required
+                && matches!(first.name.as_str(), "self" | "cls")
+            {
+                first.kind = ParameterKind::Receiver;
+            }
+        }
+    }
+    parameters
+}
+
+/// The called name, its receiver expression if any, and the argument list
+/// node of a call expression.
+fn call_parts(node: Node) -> Option<(Node, Option<Node>, Node)> {
+    match node.kind() {
+        // Rust, TS/JS, Go: `call_expression`; Python: `call`.
+        "call_expression" | "call" => {
+            let arguments = node.child_by_field_name("arguments")?;
+            let (name, qualifier) = callee_name(node.child_by_field_name("function")?)?;
+            Some((name, qualifier, arguments))
+        }
+        // Java
+        "method_invocation" => Some((
+            node.child_by_field_name("name")?,
+            node.child_by_field_name("object"),
+            node.child_by_field_name("arguments")?,
+        )),
+        _ => None,
+    }
+}
+
+fn callee_name(function: Node) -> Option<(Node, Option<Node>)> {
+    match function.kind() {
+        "identifier" => Some((function, None)),
+        // Rust `foo::<T>(..)`
+        "generic_function" => callee_name(function.child_by_field_name("function")?),
+        // Rust `recv.name`, `Type::name`; TS/JS `recv.name`; Python
+        // `recv.name`; Go `recv.Name`.
+        "field_expression" => Some((
+            function.child_by_field_name("field")?,
+            function.child_by_field_name("value"),
+        )),
+        "scoped_identifier" => Some((
+            function.child_by_field_name("name")?,
+            function.child_by_field_name("path"),
+        )),
+        "member_expression" => Some((
+            function.child_by_field_name("property")?,
+            function.child_by_field_name("object"),
+        )),
+        "attribute" => Some((
+            function.child_by_field_name("attribute")?,
+            function.child_by_field_name("object"),
+        )),
+        "selector_expression" => Some((
+            function.child_by_field_name("field")?,
+            function.child_by_field_name("operand"),
+        )),
+        _ => None,
+    }
+}
+
+fn is_spread(arg: Node, source: &[u8]) -> bool {
+    matches!(
+        arg.kind(),
+        "spread_element" | "list_splat" | "dictionary_splat" | "variadic_argument"
+    ) || text(arg, source).trim_end().ends_with("...")
+}
+
+fn text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or("")
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arity(content: &str, file_path: &str) -> (usize, Option<usize>) {
+        let signature = parse_signature(content, "function", file_path)
+            .unwrap_or_else(|| panic!("no signature in {file_path}"));
+        (signature.min_args(), signature.max_args())
+    }
+
+    fn call_arity(content: &str, file_path: &str, callee: &str) -> Vec<usize> {
+        call_arguments(content, file_path, callee)
+            .unwrap()
+            .iter()
+            .map(|call| call.arguments.len())
+            .collect()
+    }
+
+    #[test]
+    fn two_parameter_functions_match_two_argument_calls() {
+        for (file, function, caller) in [
+            (
+                "lib.rs",
+                "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}",
+                "fn main() {\n    add(1, 2);\n}",
+            ),
+            (
+                "app.ts",
+                "function add(a: number, b: number) {\n  return a + b;\n}",
+                "function main() {\n  add(1, 2);\n}",
+            ),
+            (
+                "app.js",
+                "function add(a, b) {\n  return a + b;\n}",
+                "function main() {\n  add(1, 2);\n}",
+            ),
+            (
+                "app.py",
+                "def add(a, b):\n    return a + b",
+                "def main():\n    add(1, 2)",
+            ),
+            (
+                "App.java",
+                "int add(int a, int b) {\n    return a + b;\n}",
+                "void main() {\n    add(1, 2);\n}",
+            ),
+            (
+                "main.go",
+                "func add(a, b int) int {\n\treturn a + b\n}",
+                "func main() {\n\tadd(1, 2)\n}",
+            ),
+        ] {
+            assert_eq!(arity(function, file), (2, Some(2)), "{file}");
+            assert_eq!(call_arity(caller, file, "add"), vec![2], "{file}");
+        }
+    }
+
+    #[test]
+    fn js_arrow_function_parameters_are_counted() {
+        let signature =
+            parse_signature("const add = (a, b = 1) => a + b;", "function", "math.js").unwrap();
+        assert_eq!(signature.names(), vec!["a", "b"]);
+        assert_eq!((signature.min_args(), signature.max_args()), (1, Some(2)));
+
+        assert_eq!(
+            arity("export const double = x => x * 2;", "math.ts"),
+            (1, Some(1))
+        );
+        assert_eq!(
+            arity("const merge = ({ a }, ...rest) => a;", "math.js"),
+            (1, None)
+        );
+    }
+
+    #[test]
+    fn receivers_defaults_and_variadics() {
+        let rust = parse_signature(
+            "pub fn merge<K, V>(&mut self, left: HashMap<K, V>, right: &'a str) {}",
+            "method",
+            "map.rs",
+        )
+        .unwrap();
+        assert!(rust.has_receiver());
+        assert_eq!(rust.names(), vec!["left", "right"]);
+        assert_eq!((rust.min_args(), rust.max_args()), (2, Some(2)));
+
+        let python = parse_signature(
+            "def load(self, key, *rest, default=None, **opts):\n        return key",
+            "method",
+            "store.py",
+        )
+        .unwrap();
+        assert!(python.has_receiver());
+        assert_eq!((python.min_args(), python.max_args()), (1, None));
+
+        assert_eq!(
+            arity(
+                "function fetch(this: Window, url: string, opts?: Options, retries = 3) {}",
+                "net.ts"
+            ),
+            (1, Some(3))
+        );
+        assert_eq!(
+            arity("void log(String fmt, Object... args) {}", "Log.java"),
+            (1, None)
+        );
+        assert_eq!(
+            arity("func logf(format string, args ...any) {}", "log.go"),
+            (1, None)
+        );
+        assert_eq!(
+            arity("greet(name: string) {\n    return name;\n  }", "greeter.ts"),
+            (1, Some(1))
+        );
+        assert_eq!(parse_signature("class A {}", "class", "a.ts"), None);
+        assert_eq!(parse_signature("int f(int a) {}", "function", "f.c"), None);
+    }
+
+    #[test]
+    fn calls_skip_strings_comments_spreads_and_report_qualifiers() {
+        let calls = call_arguments(
+            "function run() {\n  // add(1)\n  log('add(1, 2, 3)');\n  this.add(1, [2, 3]);\n  add(...xs);\n}",
+            "app.ts",
+            "add",
+        )
+        .unwrap();
+        assert_eq!(calls.len(), 1, "{calls:?}");
+        assert_eq!(calls[0].arguments, vec!["1", "[2, 3]"]);
+        assert_eq!(calls[0].line_offset, 3);
+        assert_eq!(calls[0].qualifier.as_deref(), Some("this"));
+
+        let calls = call_arguments(
+            "fn run(&self) {\n    Self::add(1, 2);\n    self.inner.add::<u8>(3);\n}",
+            "lib.rs",
+            "add",
+        )
+        .unwrap();
+        let qualifiers: Vec<Option<&str>> =
+            calls.iter().map(|call| call.qualifier.as_deref()).collect();
+        assert_eq!(qualifiers, vec![Some("Self"), Some("inner")]);
+
+        assert_eq!(
+            call_arity("def f():\n    add(*xs)\n", "a.py", "add"),
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! Words reference extraction never resolves against the symbol table.
+//!
+//! Keywords are grouped per language and looked up by the language of the
+//! file being scanned, so a Ruby method named `value` isn't dropped because
+//! `value` is a C# contextual keyword. Files in languages without a list of
+//! their own fall back to every list. On top of keywords, a handful of names
+//! that are overwhelmingly local variables (`result`, `status`, ...) are
+//! skipped too, except in Ruby, where a bare identifier is as likely a call
+//! to a method on `self`.
+//!
+//! `.semrc` can extend or override both, for every language or for one:
+//!
+//! ```text
+//! stopwords = ctx, payload
+//! reference-words.ruby = status, value
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+const COMMON_KEYWORDS: &[&str] = &[
+    "if",
+    "else",
+    "for",
+    "while",
+    "do",
+    "switch",
+    "case",
+    "break",
+    "continue",
+    "return",
+    "try",
+    "catch",
+    "finally",
+    "throw",
+    "new",
+    "delete",
+    "typeof",
+    "instanceof",
+    "in",
+    "of",
+    "true",
+    "false",
+    "null",
+    "undefined",
+    "void",
+    "this",
+    "super",
+    "class",
+    "extends",
+    "implements",
+    "interface",
+    "enum",
+    "const",
+    "let",
+    "var",
+    "function",
+    "async",
+    "await",
+    "yield",
+    "import",
+    "export",
+    "default",
+    "from",
+    "as",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "abstract",
+    "final",
+    "override",
+    // Primitive types
+    "string",
+    "number",
+    "boolean",
+    "int",
+    "float",
+    "double",
+    "bool",
+    "char",
+    "byte",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "fn", "pub", "mod", "use", "struct", "impl", "trait", "where", "type", "self", "Self", "mut",
+    "ref", "match", "loop", "move", "unsafe", "extern", "crate", "dyn", "i8", "i16", "i32", "i64",
+    "u8", "u16", "u32", "u64", "f32", "f64", "usize", "isize", "str", "String", "Vec", "Option",
+    "Result", "Box", "Arc", "Rc", "HashMap", "HashSet", "Some", "Ok", "Err",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "def", "elif", "except", "raise", "with", "pass", "lambda", "nonlocal", "global", "assert",
+    "True", "False", "and", "or", "not", "is", "self", "type", "len", "range", "map",
+];
+
+const GO_KEYWORDS: &[&str] = &[
+    "func", "package", "range", "select", "chan", "go", "defer", "map", "make", "append", "len",
+    "cap", "type", "struct", "nil",
+];
+
+const C_CPP_KEYWORDS: &[&str] = &[
+    "auto",
+    "register",
+    "volatile",
+    "sizeof",
+    "typedef",
+    "template",
+    "typename",
+    "namespace",
+    "virtual",
+    "inline",
+    "constexpr",
+    "nullptr",
+    "noexcept",
+    "explicit",
+    "friend",
+    "operator",
+    "using",
+    "cout",
+    "endl",
+    "cerr",
+    "cin",
+    "printf",
+    "scanf",
+    "malloc",
+    "free",
+    "NULL",
+    "include",
+    "ifdef",
+    "ifndef",
+    "endif",
+    "define",
+    "pragma",
+    "struct",
+    "self",
+];
+
+const RUBY_KEYWORDS: &[&str] = &[
+    "end",
+    "then",
+    "elsif",
+    "unless",
+    "until",
+    "begin",
+    "rescue",
+    "ensure",
+    "when",
+    "require",
+    "attr_accessor",
+    "attr_reader",
+    "attr_writer",
+    "puts",
+    "nil",
+    "module",
+    "defined",
+    "def",
+    "self",
+    "and",
+    "or",
+    "not",
+    "raise",
+    "lambda",
+    "loop",
+    "include",
+    "extend",
+    "alias",
+    "map",
+    "select",
+];
+
+const CSHARP_KEYWORDS: &[&str] = &[
+    "internal",
+    "sealed",
+    "readonly",
+    "partial",
+    "delegate",
+    "event",
+    "params",
+    "out",
+    "object",
+    "decimal",
+    "sbyte",
+    "ushort",
+    "uint",
+    "ulong",
+    "nint",
+    "nuint",
+    "dynamic",
+    "get",
+    "set",
+    "value",
+    "init",
+    "record",
+    "namespace",
+    "using",
+];
+
+const JS_TS_KEYWORDS: &[&str] = &[
+    "type",
+    "get",
+    "set",
+    "readonly",
+    "namespace",
+    "module",
+    "require",
+];
+
+const JAVA_KEYWORDS: &[&str] = &["package", "record", "sealed"];
+
+const COMMON_LOCAL_NAMES: &[&str] = &[
+    "result", "results", "data", "config", "value", "values", "item", "items", "input", "output",
+    "args", "opts", "name", "path", "file", "line", "count", "index", "temp", "prev", "next",
+    "curr", "current", "node", "left", "right", "root", "head", "tail", "body", "text", "content",
+    "source", "target", "entry", "error", "errors", "message", "response", "request", "context",
+    "state", "props", "event", "handler", "callback", "options", "params", "query", "list", "base",
+    "info", "meta", "kind", "mode", "flag", "size", "length", "width", "height", "start", "stop",
+    "begin", "done", "found", "status", "code",
+];
+
+/// Keyword lists beyond [`COMMON_KEYWORDS`], by language config id.
+/// Languages missing here fall back to every list.
+const LANGUAGE_KEYWORDS: &[(&str, &[&str])] = &[
+    ("rust", RUST_KEYWORDS),
+    ("python", PYTHON_KEYWORDS),
+    ("go", GO_KEYWORDS),
+    ("c", C_CPP_KEYWORDS),
+    ("cpp", C_CPP_KEYWORDS),
+    ("objc", C_CPP_KEYWORDS),
+    ("ruby", RUBY_KEYWORDS),
+    ("csharp", CSHARP_KEYWORDS),
+    ("typescript", JS_TS_KEYWORDS),
+    ("tsx", JS_TS_KEYWORDS),
+    ("javascript", JS_TS_KEYWORDS),
+    ("java", JAVA_KEYWORDS),
+];
+
+type KeywordSet = HashSet<&'static str>;
+
+fn keyword_set(lists: impl IntoIterator<Item = &'static [&'static str]>) -> KeywordSet {
+    COMMON_KEYWORDS
+        .iter()
+        .chain(lists.into_iter().flatten())
+        .copied()
+        .collect()
+}
+
+static KEYWORDS_BY_LANGUAGE: LazyLock<HashMap<&'static str, KeywordSet>> = LazyLock::new(|| {
+    LANGUAGE_KEYWORDS
+        .iter()
+        .map(|&(language, list)| (language, keyword_set([list])))
+        .collect()
+});
+
+static ALL_KEYWORDS: LazyLock<KeywordSet> =
+    LazyLock::new(|| keyword_set(LANGUAGE_KEYWORDS.iter().map(|&(_, list)| list)));
+
+static LOCAL_NAMES: LazyLock<HashSet<&'static str>> =
+    LazyLock::new(|| COMMON_LOCAL_NAMES.iter().copied().collect());
+
+/// Words to skip or keep on top of the built-in lists, from `stopwords` and
+/// `reference-words` lines in `.semrc`. Keeping a word wins over skipping it.
+#[derive(Debug, Clone, Default)]
+pub struct StopwordOverrides {
+    skip: WordLists,
+    keep: WordLists,
+}
+
+#[derive(Debug, Clone, Default)]
+struct WordLists {
+    all: HashSet<String>,
+    by_language: HashMap<String, HashSet<String>>,
+}
+
+impl WordLists {
+    fn add<'a>(&mut self, language: Option<&str>, words: impl IntoIterator<Item = &'a str>) {
+        let list = match language {
+            Some(language) => self.by_language.entry(language.to_string()).or_default(),
+            None => &mut self.all,
+        };
+        list.extend(
+            words
+                .into_iter()
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .map(str::to_string),
+        );
+    }
+
+    fn contains(&self, language: Option<&str>, word: &str) -> bool {
+        self.all.contains(word)
+            || language
+                .and_then(|language| self.by_language.get(language))
+                .is_some_and(|list| list.contains(word))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.all.is_empty() && self.by_language.values().all(HashSet::is_empty)
+    }
+}
+
+impl StopwordOverrides {
+    /// Never resolve `words` as references in files of `language` (a
+    /// language config id such as `ruby`), or in every file when `None`.
+    pub fn skip<'a>(&mut self, language: Option<&str>, words: impl IntoIterator<Item = &'a str>) {
+        self.skip.add(language, words);
+    }
+
+    /// Resolve `words` as references even where a built-in list skips them.
+    pub fn keep<'a>(&mut self, language: Option<&str>, words: impl IntoIterator<Item = &'a str>) {
+        self.keep.add(language, words);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.skip.is_empty() && self.keep.is_empty()
+    }
+}
+
+/// The stopwords for one file, resolved once from its language.
+#[derive(Clone, Copy)]
+pub(crate) struct Stopwords<'a> {
+    language: Option<&'static str>,
+    keywords: &'static KeywordSet,
+    overrides: Option<&'a StopwordOverrides>,
+}
+
+impl<'a> Stopwords<'a> {
+    /// Stopwords for files of `language` (a language config id), or for a
+    /// file of unknown language when `None`, with a registry's `.semrc`
+    /// overrides applied on top of the built-in lists.
+    pub(crate) fn for_language(
+        language: Option<&'static str>,
+        overrides: &'a StopwordOverrides,
+    ) -> Self {
+        Self::build(language, Some(overrides).filter(|o| !o.is_empty()))
+    }
+
+    fn build(language: Option<&'static str>, overrides: Option<&'a StopwordOverrides>) -> Self {
+        let keywords = language
+            .and_then(|language| KEYWORDS_BY_LANGUAGE.get(language))
+            .unwrap_or(&*ALL_KEYWORDS);
+        Self {
+            language,
+            keywords,
+            overrides,
+        }
+    }
+
+    fn kept(&self, word: &str) -> bool {
+        self.overrides
+            .is_some_and(|o| o.keep.contains(self.language, word))
+    }
+
+    pub(crate) fn is_keyword(&self, word: &str) -> bool {
+        if self.kept(word) {
+            return false;
+        }
+        self.keywords.contains(word)
+            || self
+                .overrides
+                .is_some_and(|o| o.skip.contains(self.language, word))
+    }
+
+    /// Names that are overwhelmingly local variables, not entity references.
+    /// These create massive false-positive edges in the dependency graph.
+    pub(crate) fn is_common_local_name(&self, word: &str) -> bool {
+        self.language != Some("ruby") && LOCAL_NAMES.contains(word) && !self.kept(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_follow_the_file_language() {
+        let none = StopwordOverrides::default();
+        let csharp = Stopwords::for_language(Some("csharp"), &none);
+        let ruby = Stopwords::for_language(Some("ruby"), &none);
+        assert!(csharp.is_keyword("value"));
+        assert!(!ruby.is_keyword("value"));
+        assert!(ruby.is_keyword("elsif"));
+        assert!(!csharp.is_keyword("elsif"));
+        assert!(ruby.is_keyword("return") && csharp.is_keyword("return"));
+
+        // Unknown languages keep the union of every list.
+        let unknown = Stopwords::for_language(None, &none);
+        assert!(unknown.is_keyword("value") && unknown.is_keyword("elsif"));
+        let lua = Stopwords::for_language(Some("lua"), &none);
+        assert!(lua.is_keyword("fn"));
+    }
+
+    #[test]
+    fn ruby_keeps_common_local_names() {
+        let none = StopwordOverrides::default();
+        assert!(Stopwords::for_language(Some("python"), &none).is_common_local_name("status"));
+        assert!(!Stopwords::for_language(Some("ruby"), &none).is_common_local_name("status"));
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! Single source of truth for deciding whether a file path belongs to a
 //! test/fixture/benchmark directory. Used by `graph.rs` for test-entity
-//! filtering.
+//! filtering and for tagging the test partition of the graph.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::entity::{ancestors, SemanticEntity};
 
 /// Token-level matches: directory name is split on `-`, `_`, `.` and
 /// any resulting token that equals one of these triggers a match.
@@ -77,6 +81,26 @@ pub fn is_test_path_with_custom_dirs(path: &str, custom_dirs: &[String]) -> bool
     false
 }
 
+/// Ids of the entities in the test partition: everything in a test file
+/// (per [`is_test_path`]) and everything nested in a Rust `#[cfg(test)]`
+/// module, the module included.
+pub fn test_partition_ids<'a>(
+    entities: impl IntoIterator<Item = &'a SemanticEntity>,
+) -> HashSet<String> {
+    let entities: Vec<&SemanticEntity> = entities.into_iter().collect();
+    let by_id: HashMap<&str, &SemanticEntity> =
+        entities.iter().map(|e| (e.id.as_str(), *e)).collect();
+    entities
+        .iter()
+        .filter(|entity| {
+            is_test_path(&entity.file_path)
+                || ancestors(entity, &by_id)
+                    .any(|at| at.content.trim_start().starts_with("#[cfg(test)]"))
+        })
+        .map(|entity| entity.id.clone())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,4 +198,25 @@ mod tests {
         assert!(is_test_path_with_custom_dirs("src/tests/foo.ts", &custom));
         assert!(is_test_path_with_custom_dirs("e2e-tests/bar.py", &custom));
     }
+
+    // ── Test partition ───────────────────────────────────────────────────
+
+    #[test]
+    fn partition_covers_test_files_and_cfg_test_modules() {
+        let registry = crate::parser::plugins::create_default_registry();
+        let mut entities = registry.extract_entities(
+            "src/lib.rs",
+            "pub fn prod() -> i32 { 1 }\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn checks() {}\n}\n",
+        );
+        entities
+            .extend(registry.extract_entities("src/prod.test.ts", "export function helper() {}\n"));
+
+        let partition = test_partition_ids(&entities);
+        let names: HashSet<&str> = entities
+            .iter()
+            .filter(|e| partition.contains(&e.id))
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, HashSet::from(["tests", "checks", "helper"]));
+    }
 }
@@ -0,0 +1,131 @@
+//! Public-surface detection from the `visibility` metadata recorded during
+//! extraction.
+//!
+//! Extraction normalizes each language's modifiers to `public`, `private`,
+//! `protected`, `crate` (Rust `pub(crate)` and friends), `package` (Java's
+//! default) or `internal` (C#). Languages without a visibility notion record
+//! nothing, and such entities count as public.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::entity::{ancestors, SemanticEntity};
+
+/// Metadata key holding an entity's normalized visibility.
+pub const VISIBILITY_KEY: &str = "visibility";
+
+/// The entity's own recorded visibility, if extraction found one.
+pub fn visibility(entity: &SemanticEntity) -> Option<&str> {
+    entity
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(VISIBILITY_KEY))
+        .map(String::as_str)
+}
+
+/// Ids of the entities outside the public surface: anything whose own
+/// visibility, or that of an enclosing entity, is recorded as something other
+/// than `public`. A `pub fn` on a private module is not reachable, and a
+/// `protected` member is only visible to subclasses.
+pub fn private_ids<'a>(entities: impl IntoIterator<Item = &'a SemanticEntity>) -> HashSet<String> {
+    let entities: Vec<&SemanticEntity> = entities.into_iter().collect();
+    let by_id: HashMap<&str, &SemanticEntity> =
+        entities.iter().map(|e| (e.id.as_str(), *e)).collect();
+    entities
+        .iter()
+        .filter(|entity| {
+            ancestors(entity, &by_id).any(|at| visibility(at).is_some_and(|v| v != "public"))
+        })
+        .map(|entity| entity.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visibilities(file_path: &str, source: &str) -> HashMap<String, Option<String>> {
+        let registry = crate::parser::plugins::create_default_registry();
+        registry
+            .extract_entities(file_path, source)
+            .iter()
+            .map(|e| (e.name.clone(), visibility(e).map(str::to_string)))
+            .collect()
+    }
+
+    fn public_names(file_path: &str, source: &str) -> HashSet<String> {
+        let registry = crate::parser::plugins::create_default_registry();
+        let entities = registry.extract_entities(file_path, source);
+        let private = private_ids(&entities);
+        entities
+            .iter()
+            .filter(|e| !private.contains(&e.id))
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn rust_visibility_modifiers() {
+        let source = "pub fn api() {}\npub(crate) fn shared() {}\nfn helper() {}\npub trait Greet {\n    fn greet(&self);\n}\nmod inner {\n    pub fn hidden() {}\n}\n";
+        let vis = visibilities("src/lib.rs", source);
+        assert_eq!(vis["api"].as_deref(), Some("public"));
+        assert_eq!(vis["shared"].as_deref(), Some("crate"));
+        assert_eq!(vis["helper"].as_deref(), Some("private"));
+        assert_eq!(vis["greet"].as_deref(), Some("public"));
+        assert_eq!(vis["hidden"].as_deref(), Some("public"));
+
+        assert_eq!(
+            public_names("src/lib.rs", source),
+            HashSet::from(["api".to_string(), "Greet".to_string(), "greet".to_string()])
+        );
+    }
+
+    #[test]
+    fn typescript_exports_and_member_modifiers() {
+        let source = "export class Service {\n  run() {}\n  private reset() {}\n  protected hook() {}\n}\nfunction local() {}\nconst util = 1;\nexport { util };\n";
+        let vis = visibilities("src/service.ts", source);
+        assert_eq!(vis["Service"].as_deref(), Some("public"));
+        assert_eq!(vis["run"].as_deref(), Some("public"));
+        assert_eq!(vis["reset"].as_deref(), Some("private"));
+        assert_eq!(vis["hook"].as_deref(), Some("protected"));
+        assert_eq!(vis["local"].as_deref(), Some("private"));
+        assert_eq!(vis["util"].as_deref(), Some("public"));
+
+        assert_eq!(
+            public_names("src/service.ts", source),
+            HashSet::from(["Service".to_string(), "run".to_string(), "util".to_string()])
+        );
+    }
+
+    #[test]
+    fn java_and_python_conventions() {
+        let java = visibilities(
+            "Api.java",
+            "public class Api {\n  void pkg() {}\n  private void hidden() {}\n  public void open() {}\n}\n",
+        );
+        assert_eq!(java["Api"].as_deref(), Some("public"));
+        assert_eq!(java["pkg"].as_deref(), Some("package"));
+        assert_eq!(java["hidden"].as_deref(), Some("private"));
+        assert_eq!(java["open"].as_deref(), Some("public"));
+
+        let python = visibilities(
+            "api.py",
+            "def run():\n    pass\n\ndef _helper():\n    pass\n\nclass Api:\n    def __init__(self):\n        pass\n",
+        );
+        assert_eq!(python["run"].as_deref(), Some("public"));
+        assert_eq!(python["_helper"].as_deref(), Some("private"));
+        assert_eq!(python["__init__"].as_deref(), Some("public"));
+    }
+
+    #[cfg(feature = "lang-csharp")]
+    #[test]
+    fn csharp_access_defaults() {
+        let csharp = visibilities(
+            "Api.cs",
+            "class Api {\n  void Hidden() {}\n  public void Open() {}\n  internal void Shared() {}\n}\n",
+        );
+        assert_eq!(csharp["Api"].as_deref(), Some("internal"));
+        assert_eq!(csharp["Hidden"].as_deref(), Some("private"));
+        assert_eq!(csharp["Open"].as_deref(), Some("public"));
+        assert_eq!(csharp["Shared"].as_deref(), Some("internal"));
+    }
+}
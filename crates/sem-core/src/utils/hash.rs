@@ -2,8 +2,12 @@ use std::hash::Hasher;
 use tree_sitter::Node;
 use xxhash_rust::xxh3::Xxh3;
 
+use crate::utils::source::normalize_eol;
+
+/// Hash of `content` as text. Line endings and a leading BOM are normalized
+/// first (see [`normalize_eol`]), so CRLF and LF checkouts hash the same.
 pub fn content_hash(content: &str) -> String {
-    content_hash_bytes(content.as_bytes())
+    content_hash_bytes(normalize_eol(content).as_bytes())
 }
 
 pub fn content_hash_bytes(content: &[u8]) -> String {
@@ -15,6 +19,41 @@ pub fn short_hash(content: &str, length: usize) -> String {
     hash[..length.min(hash.len())].to_string()
 }
 
+/// Hash a parsed data value (JSON, or YAML converted to JSON) in canonical
+/// form: object keys sorted at every level, no insignificant whitespace.
+/// Reformatting or reordering keys leaves the hash unchanged.
+pub fn value_structural_hash(value: &serde_json::Value) -> String {
+    let mut hasher = Xxh3::new();
+    hash_canonical_value(value, &mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn hash_canonical_value(value: &serde_json::Value, hasher: &mut Xxh3) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| key.as_str());
+            hasher.write(b"{");
+            for (key, value) in entries {
+                hasher.write(serde_json::Value::from(key.as_str()).to_string().as_bytes());
+                hasher.write(b":");
+                hash_canonical_value(value, hasher);
+                hasher.write(b",");
+            }
+            hasher.write(b"}");
+        }
+        serde_json::Value::Array(items) => {
+            hasher.write(b"[");
+            for item in items {
+                hash_canonical_value(item, hasher);
+                hasher.write(b",");
+            }
+            hasher.write(b"]");
+        }
+        scalar => hasher.write(scalar.to_string().as_bytes()),
+    }
+}
+
 /// Compute a structural hash from a tree-sitter AST node.
 /// Strips comments and normalizes whitespace so formatting-only changes
 /// produce the same hash. Uses streaming xxHash64 to avoid intermediate
@@ -191,6 +230,20 @@ mod tests {
         assert_eq!(content_hash_bytes(b"test"), content_hash("test"));
     }
 
+    #[test]
+    fn test_content_hash_ignores_line_endings_and_bom() {
+        let lf = "def foo():\n    return 1\n";
+        assert_eq!(
+            content_hash(lf),
+            content_hash("def foo():\r\n    return 1\r\n")
+        );
+        assert_eq!(
+            content_hash(lf),
+            content_hash("\u{feff}def foo():\n    return 1\n")
+        );
+        assert_ne!(content_hash(lf), content_hash("def foo():\n    return 2\n"));
+    }
+
     #[test]
     fn test_short_hash() {
         let h = short_hash("test", 8);
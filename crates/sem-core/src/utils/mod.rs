@@ -1,2 +1,4 @@
 pub mod hash;
 pub mod scan;
+pub mod source;
+pub mod walk;
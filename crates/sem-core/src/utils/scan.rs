@@ -24,6 +24,8 @@ const DEFAULT_EXCLUDED_ANY_DIRS: &[&str] = &[
     "benchmarks",
     "vendor",
     "node_modules",
+    "bower_components",
+    "__pycache__",
     "test-harness",
     ".next",
     ".turbo",
@@ -37,6 +39,8 @@ const DEFAULT_EXCLUDED_ROOT_DIRS: &[&str] = &["out", "dist", "build", "target"];
 /// File suffixes for generated text assets that do not produce useful entities.
 const DEFAULT_EXCLUDED_SUFFIXES: &[&str] = &[
     ".min.js",
+    ".min.mjs",
+    ".min.cjs",
     ".min.css",
     ".generated.ts",
     ".generated.tsx",
@@ -213,6 +217,9 @@ mod tests {
         assert!(is_default_excluded("dist/app.js"));
         assert!(is_default_excluded("site/out/_next/static/chunks/app.js"));
         assert!(is_default_excluded("src/generated.min.js"));
+        assert!(is_default_excluded("web/lib/chart.min.mjs"));
+        assert!(is_default_excluded("web/bower_components/jquery/jquery.js"));
+        assert!(is_default_excluded("pkg/__pycache__/mod.py"));
         assert!(is_default_excluded("src/__generated__/client.ts"));
         assert!(is_default_excluded("src/_generated/tokens.ts"));
         assert!(is_default_excluded("src/generated/schema.ts"));
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+use std::io::Read;
+use std::path::Path;
+
+use crate::model::diagnostic::SkipReason;
+
+/// Files larger than this are skipped by repo-wide scans unless the limit is
+/// raised. Generated bundles and data dumps above it produce no useful
+/// entities and dominate parse time and peak memory.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// `content` without a leading UTF-8 BOM and with CRLF and lone CR line
+/// endings turned into LF, so CRLF and LF checkouts of a file compare equal.
+/// Borrowed when there's nothing to change.
+pub fn normalize_eol(content: &str) -> Cow<'_, str> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if content.contains('\r') {
+        Cow::Owned(content.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
+/// Read a source file as text. The size is checked from metadata before
+/// reading, so an oversized file is never loaded. `Ok(None)` means the file
+/// couldn't be read at all (for example, deleted since it was listed).
+pub fn read_source_file(path: &Path, max_size: u64) -> Result<Option<String>, SkipReason> {
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return Ok(None);
+    };
+    let size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if size > max_size {
+        return Err(SkipReason::TooLarge {
+            size,
+            limit: max_size,
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(size as usize);
+    if file.read_to_end(&mut bytes).is_err() {
+        return Ok(None);
+    }
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|_| SkipReason::NotUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_small_utf8_files_and_skips_the_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small.py");
+        let large = dir.path().join("large.py");
+        let latin1 = dir.path().join("latin1.py");
+        std::fs::write(&small, "x = 1\n").unwrap();
+        std::fs::write(&large, "x = 1\n".repeat(100)).unwrap();
+        std::fs::write(&latin1, b"name = '\xe9'\n").unwrap();
+
+        assert_eq!(
+            read_source_file(&small, 64),
+            Ok(Some("x = 1\n".to_string()))
+        );
+        assert_eq!(
+            read_source_file(&large, 64),
+            Err(SkipReason::TooLarge {
+                size: 600,
+                limit: 64
+            })
+        );
+        assert_eq!(read_source_file(&latin1, 64), Err(SkipReason::NotUtf8));
+        assert_eq!(
+            read_source_file(&dir.path().join("missing.py"), 64),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn normalize_eol_drops_bom_and_carriage_returns() {
+        assert_eq!(normalize_eol("\u{feff}a\r\nb\rc\n"), "a\nb\nc\n");
+        assert!(matches!(normalize_eol("a\nb\n"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,188 @@
+//! Path filtering for repo-wide walks: the built-in denylist from
+//! [`scan`](super::scan), minus whatever `--include` globs opt back in.
+
+use regex::Regex;
+
+use super::scan::is_default_excluded;
+
+/// Decides which repo-relative paths a repo-wide walk skips. Shared by every
+/// command that scans the whole repository so they agree on scope.
+#[derive(Debug, Clone, Default)]
+pub struct WalkFilter {
+    no_default_excludes: bool,
+    includes: Vec<IncludeGlob>,
+}
+
+impl WalkFilter {
+    /// `include_globs` are repo-relative (`dist/**`, `vendor/acme`); a glob
+    /// without a `/` matches a file or directory name at any depth. A glob
+    /// matching a directory includes everything under it.
+    pub fn new(no_default_excludes: bool, include_globs: &[String]) -> Self {
+        Self {
+            no_default_excludes,
+            includes: include_globs
+                .iter()
+                .filter(|glob| !glob.trim().is_empty())
+                .map(|glob| IncludeGlob::new(glob))
+                .collect(),
+        }
+    }
+
+    /// Whether a walk should not descend into `rel_dir`.
+    pub fn skips_dir(&self, rel_dir: &str) -> bool {
+        if self.no_default_excludes || !is_default_excluded(rel_dir) {
+            return false;
+        }
+        let rel_dir = normalize(rel_dir);
+        !self
+            .includes
+            .iter()
+            .any(|glob| glob.may_match_under(&rel_dir))
+    }
+
+    /// Whether a walk should leave out the file at `rel_path`.
+    pub fn skips_file(&self, rel_path: &str) -> bool {
+        if self.no_default_excludes || !is_default_excluded(rel_path) {
+            return false;
+        }
+        !self.is_included(rel_path)
+    }
+
+    fn is_included(&self, rel_path: &str) -> bool {
+        let rel_path = normalize(rel_path);
+        self.includes
+            .iter()
+            .any(|glob| glob.matches_path_or_ancestor(&rel_path))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IncludeGlob {
+    regex: Regex,
+    /// Leading segments without wildcards, used to prune directories that
+    /// can't contain a match.
+    literal_prefix: Vec<String>,
+    has_wildcards: bool,
+}
+
+impl IncludeGlob {
+    fn new(glob: &str) -> Self {
+        let glob = normalize(glob.trim());
+        let glob = glob.trim_start_matches("./").trim_end_matches('/');
+        let glob = if glob.contains('/') {
+            glob.trim_start_matches('/').to_string()
+        } else {
+            format!("**/{glob}")
+        };
+
+        let segments: Vec<&str> = glob.split('/').collect();
+        let literal_prefix: Vec<String> = segments
+            .iter()
+            .take_while(|segment| !segment.contains(['*', '?', '[']))
+            .map(|segment| segment.to_string())
+            .collect();
+        let has_wildcards = literal_prefix.len() < segments.len();
+
+        let mut pattern = String::from("^");
+        for (i, segment) in segments.iter().enumerate() {
+            let last = i + 1 == segments.len();
+            if *segment == "**" {
+                pattern.push_str(if last { ".*" } else { "(?:[^/]+/)*" });
+                continue;
+            }
+            for ch in segment.chars() {
+                match ch {
+                    '*' => pattern.push_str("[^/]*"),
+                    '?' => pattern.push_str("[^/]"),
+                    _ => pattern.push_str(&regex::escape(&ch.to_string())),
+                }
+            }
+            if !last {
+                pattern.push('/');
+            }
+        }
+        pattern.push('$');
+
+        Self {
+            regex: Regex::new(&pattern).expect("include glob compiles to a valid regex"),
+            literal_prefix,
+            has_wildcards,
+        }
+    }
+
+    fn matches_path_or_ancestor(&self, rel_path: &str) -> bool {
+        rel_path
+            .match_indices('/')
+            .any(|(i, _)| self.regex.is_match(&rel_path[..i]))
+            || self.regex.is_match(rel_path)
+    }
+
+    /// Whether `rel_dir` or anything below it could match.
+    fn may_match_under(&self, rel_dir: &str) -> bool {
+        if self.matches_path_or_ancestor(rel_dir) {
+            return true;
+        }
+        for (i, component) in rel_dir.split('/').enumerate() {
+            match self.literal_prefix.get(i) {
+                Some(literal) if literal != component => return false,
+                Some(_) => {}
+                None => return self.has_wildcards,
+            }
+        }
+        true
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(includes: &[&str]) -> WalkFilter {
+        let includes: Vec<String> = includes.iter().map(|s| s.to_string()).collect();
+        WalkFilter::new(false, &includes)
+    }
+
+    #[test]
+    fn default_filter_skips_denylisted_paths() {
+        let walk = filter(&[]);
+        assert!(walk.skips_dir("dist"));
+        assert!(walk.skips_dir("packages/app/node_modules"));
+        assert!(walk.skips_file("dist/app.js"));
+        assert!(walk.skips_file("web/vendor.min.js"));
+        assert!(!walk.skips_dir("src"));
+        assert!(!walk.skips_file("src/app.js"));
+        assert!(!WalkFilter::new(true, &[]).skips_dir("dist"));
+    }
+
+    #[test]
+    fn include_globs_reopen_denylisted_paths() {
+        let walk = filter(&["dist/**"]);
+        assert!(!walk.skips_dir("dist"));
+        assert!(!walk.skips_dir("dist/esm"));
+        assert!(!walk.skips_file("dist/esm/index.js"));
+        assert!(walk.skips_dir("node_modules"));
+        assert!(walk.skips_file("build/app.js"));
+
+        let walk = filter(&["vendor/acme"]);
+        assert!(!walk.skips_dir("vendor"));
+        assert!(!walk.skips_file("vendor/acme/lib.go"));
+        assert!(walk.skips_file("vendor/other/lib.go"));
+
+        let walk = filter(&["*.min.js"]);
+        assert!(!walk.skips_file("web/vendor.min.js"));
+        assert!(walk.skips_file("dist/app.js"));
+    }
+
+    #[test]
+    fn literal_prefix_prunes_unrelated_directories() {
+        let walk = filter(&["packages/*/node_modules/**"]);
+        assert!(!walk.skips_dir("packages/app/node_modules"));
+        assert!(!walk.skips_file("packages/app/node_modules/lib/index.js"));
+        assert!(walk.skips_dir("node_modules"));
+        assert!(walk.skips_dir("examples/node_modules"));
+    }
+}
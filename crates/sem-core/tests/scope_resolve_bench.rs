@@ -424,6 +424,8 @@ fn scope_resolve_comparison() {
                     parent_id: e.parent_id.clone(),
                     start_line: e.start_line,
                     end_line: e.end_line,
+                    is_test: false,
+                    is_private: false,
                 },
             )
         })
@@ -1272,6 +1274,8 @@ fn run_scope_resolve_for_lang(
                     parent_id: e.parent_id.clone(),
                     start_line: e.start_line,
                     end_line: e.end_line,
+                    is_test: false,
+                    is_private: false,
                 },
             )
         })
@@ -17,7 +17,7 @@ use sem_core::parser::js_ts_import_source_files_from_content;
 use sem_core::parser::registry::ParserRegistry;
 use sem_core::utils::hash::content_hash_bytes;
 
-pub const CACHE_SCHEMA_VERSION: i32 = 9;
+pub const CACHE_SCHEMA_VERSION: i32 = 13;
 pub const CACHE_KIND_FULL: &str = "full";
 pub const CACHE_KIND_TOPOLOGY: &str = "topology";
 pub const CACHE_INDEXES: &[(&str, &str, &str)] = &[
@@ -100,12 +100,15 @@ CREATE TABLE IF NOT EXISTS entities (
     content_hash TEXT NOT NULL,
     structural_hash TEXT,
     parent_id TEXT,
-    metadata_json TEXT
+    metadata_json TEXT,
+    is_test INTEGER NOT NULL DEFAULT 0,
+    is_private INTEGER NOT NULL DEFAULT 0
 );
 CREATE TABLE IF NOT EXISTS edges (
     from_entity TEXT NOT NULL,
     to_entity TEXT NOT NULL,
-    ref_type TEXT NOT NULL
+    ref_type TEXT NOT NULL,
+    confidence REAL NOT NULL DEFAULT 1.0
 );
 CREATE TABLE IF NOT EXISTS file_imports (
     importing_file TEXT NOT NULL,
@@ -203,9 +206,11 @@ pub fn insert_entities_with_content_store(
         "INSERT INTO"
     };
     let sql = format!(
-        "{verb} entities (id, name, entity_type, file_path, start_line, end_line, start_byte, end_byte, content, content_hash, structural_hash, parent_id, metadata_json) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+        "{verb} entities (id, name, entity_type, file_path, start_line, end_line, start_byte, end_byte, content, content_hash, structural_hash, parent_id, metadata_json, is_test, is_private) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
     );
     let mut stmt = tx.prepare(&sql)?;
+    let test_ids = sem_core::parser::test_detect::test_partition_ids(entities.iter().copied());
+    let private_ids = sem_core::parser::visibility::private_ids(entities.iter().copied());
     let mut file_texts: HashMap<&str, Option<String>> = HashMap::new();
     let mut files_to_store: std::collections::HashSet<&str> = std::collections::HashSet::new();
 
@@ -241,6 +246,8 @@ pub fn insert_entities_with_content_store(
             e.structural_hash,
             e.parent_id,
             metadata_json,
+            test_ids.contains(&e.id),
+            private_ids.contains(&e.id),
         ])?;
     }
 
@@ -1082,15 +1089,22 @@ impl DiskCache {
 
         {
             let mut stmt = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 let rt = match edge.ref_type {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                stmt.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                stmt.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         }
 
@@ -1191,11 +1205,14 @@ impl DiskCache {
                     end_line: row.get::<_, i64>(5)? as usize,
                     start_byte: row.get::<_, Option<i64>>(11)?.map(|v| v as usize),
                     end_byte: row.get::<_, Option<i64>>(12)?.map(|v| v as usize),
+                    name_start: None,
+                    name_end: None,
                     content: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
                     content_hash: row.get(7)?,
                     structural_hash: row.get(8)?,
                     parent_id: row.get(9)?,
                     metadata,
+                    stable_id: None,
                 })
             })
             .ok()?
@@ -1219,7 +1236,7 @@ impl DiskCache {
         // Load edges
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -1227,12 +1244,15 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
@@ -1240,6 +1260,8 @@ impl DiskCache {
             .collect();
 
         // Build entity map for graph
+        let test_ids = sem_core::parser::test_detect::test_partition_ids(&entities);
+        let private_ids = sem_core::parser::visibility::private_ids(&entities);
         let entity_map: EntityInfoMap = entities
             .iter()
             .map(|e| {
@@ -1253,6 +1275,8 @@ impl DiskCache {
                         start_line: e.start_line,
                         end_line: e.end_line,
                         parent_id: e.parent_id.clone(),
+                        is_test: test_ids.contains(&e.id),
+                        is_private: private_ids.contains(&e.id),
                     },
                 )
             })
@@ -1342,7 +1366,7 @@ impl DiskCache {
         let mut entity_stmt = self
             .conn
             .prepare(
-                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id FROM entities",
+                "SELECT id, name, entity_type, file_path, start_line, end_line, parent_id, is_test, is_private FROM entities",
             )
             .ok()?;
         let entity_map: EntityInfoMap = entity_stmt
@@ -1358,6 +1382,8 @@ impl DiskCache {
                         start_line: row.get::<_, i64>(4)? as usize,
                         end_line: row.get::<_, i64>(5)? as usize,
                         parent_id: row.get(6)?,
+                        is_test: row.get::<_, i64>(7)? != 0,
+                        is_private: row.get::<_, i64>(8)? != 0,
                     },
                 ))
             })
@@ -1372,7 +1398,7 @@ impl DiskCache {
     fn load_edges(&self) -> Option<Vec<EntityRef>> {
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -1380,12 +1406,15 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
@@ -1532,11 +1561,14 @@ impl DiskCache {
                     end_line: row.get::<_, i64>(5)? as usize,
                     start_byte: row.get::<_, Option<i64>>(11)?.map(|v| v as usize),
                     end_byte: row.get::<_, Option<i64>>(12)?.map(|v| v as usize),
+                    name_start: None,
+                    name_end: None,
                     content: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
                     content_hash: row.get(7)?,
                     structural_hash: row.get(8)?,
                     parent_id: row.get(9)?,
                     metadata,
+                    stable_id: None,
                 })
             })
             .ok()?
@@ -1569,7 +1601,7 @@ impl DiskCache {
         // Load ALL cached edges (build_incremental decides which to keep)
         let mut edge_stmt = self
             .conn
-            .prepare("SELECT from_entity, to_entity, ref_type FROM edges")
+            .prepare("SELECT from_entity, to_entity, ref_type, confidence FROM edges")
             .ok()?;
         let cached_edges: Vec<EntityRef> = edge_stmt
             .query_map([], |row| {
@@ -1577,12 +1609,15 @@ impl DiskCache {
                 let ref_type = match rt.as_str() {
                     "calls" => RefType::Calls,
                     "imports" => RefType::Imports,
+                    "implements" => RefType::Implements,
+                    "extends" => RefType::Extends,
                     _ => RefType::TypeRef,
                 };
                 Ok(EntityRef {
                     from_entity: row.get(0)?,
                     to_entity: row.get(1)?,
                     ref_type,
+                    confidence: row.get(3)?,
                 })
             })
             .ok()?
@@ -1753,15 +1788,22 @@ impl DiskCache {
         if repair_changed_clean_entity_ids {
             tx.execute("DELETE FROM edges", [])?;
             let mut ins = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 let rt = match edge.ref_type {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                ins.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                ins.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         } else {
             let mut affected_sources: HashSet<String> =
@@ -1804,7 +1846,7 @@ impl DiskCache {
             }
 
             let mut ins = tx.prepare(
-                "INSERT INTO edges (from_entity, to_entity, ref_type) VALUES (?1, ?2, ?3)",
+                "INSERT INTO edges (from_entity, to_entity, ref_type, confidence) VALUES (?1, ?2, ?3, ?4)",
             )?;
             for edge in &graph.edges {
                 if !affected_sources.contains(&edge.from_entity)
@@ -1817,8 +1859,15 @@ impl DiskCache {
                     RefType::Calls => "calls",
                     RefType::TypeRef => "typeref",
                     RefType::Imports => "imports",
+                    RefType::Implements => "implements",
+                    RefType::Extends => "extends",
                 };
-                ins.execute(params![edge.from_entity, edge.to_entity, rt])?;
+                ins.execute(params![
+                    edge.from_entity,
+                    edge.to_entity,
+                    rt,
+                    edge.confidence
+                ])?;
             }
         }
 
@@ -1994,7 +2043,10 @@ mod tests {
             end_line: 1,
             start_byte: None,
             end_byte: None,
+            name_start: None,
+            name_end: None,
             metadata: None,
+            stable_id: None,
         }
     }
 
@@ -2016,6 +2068,8 @@ mod tests {
             parent_id: None,
             start_line: 1,
             end_line: 1,
+            is_test: false,
+            is_private: false,
         }
     }
 
@@ -2037,6 +2091,7 @@ mod tests {
             from_entity: from_entity.to_string(),
             to_entity: to_entity.to_string(),
             ref_type: RefType::Calls,
+            confidence: 1.0,
         }
     }
 
@@ -966,18 +966,22 @@ impl SemServer {
         // the repo per call, which at sidecar rates was the entire latency
         // budget: 6.8ms → 0.1ms measured on a 4.7K-entity graph).
         const BFS_CAP: usize = 10_000;
-        let mut seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        // `parent` doubles as the seen set and records the BFS tree, so each
+        // impacted entity carries one shortest path back to the source just
+        // like the local `impact_analysis_with_paths_bounded` answer.
+        let mut parent: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
         let mut reached: Vec<(&sem_core::parser::graph::EntityInfo, usize)> = Vec::new();
         let mut queue: std::collections::VecDeque<(&str, usize)> =
             std::collections::VecDeque::new();
-        seen.insert(entity.id.as_str());
+        parent.insert(entity.id.as_str(), entity.id.as_str());
         queue.push_back((entity.id.as_str(), 0));
         while let Some((id, depth)) = queue.pop_front() {
             if reached.len() >= BFS_CAP {
                 break;
             }
             for dependent in graph.get_dependents(id) {
-                if seen.insert(dependent.id.as_str()) {
+                if !parent.contains_key(dependent.id.as_str()) {
+                    parent.insert(dependent.id.as_str(), id);
                     reached.push((dependent, depth + 1));
                     queue.push_back((dependent.id.as_str(), depth + 1));
                 }
@@ -989,6 +993,19 @@ impl SemServer {
             .filter(|(_, depth)| max_depth == 0 || *depth <= max_depth)
             .map(|(info, depth)| ((*info).clone(), *depth))
             .collect();
+        let paths: std::collections::HashMap<&str, Vec<&str>> = impact
+            .iter()
+            .map(|(info, _)| {
+                let mut path = vec![info.id.as_str()];
+                let mut current = info.id.as_str();
+                while current != entity.id {
+                    current = parent[current];
+                    path.push(current);
+                }
+                path.reverse();
+                (info.id.as_str(), path)
+            })
+            .collect();
 
         let by_id: std::collections::HashMap<&str, &SemanticEntity> =
             all_entities.iter().map(|e| (e.id.as_str(), e)).collect();
@@ -1002,11 +1019,18 @@ impl SemServer {
             .map(|(info, _)| (*info).clone())
             .collect();
 
+        let impact_score = sem_core::parser::graph::ImpactScore::from_impact(
+            impact.iter().map(|(info, depth)| (info, *depth)),
+            |id| graph.dependents.get(id).map_or(0, Vec::len),
+        );
+
         Ok(serde_json::json!({
             "entity": entity,
             "dependencies": dependencies,
             "dependents": dependents,
             "impact": impact,
+            "impactScore": impact_score,
+            "paths": paths,
             "tests": tests,
         }))
     }
@@ -2492,6 +2516,36 @@ mod tests {
         let _ = std::fs::remove_dir_all(root);
     }
 
+    #[tokio::test]
+    async fn quick_impact_reports_a_path_per_impacted_entity() {
+        let root = temp_git_repo("impact-paths");
+        std::fs::write(
+            root.join("app.py"),
+            "def alpha():\n    return 1\n\ndef beta():\n    return alpha()\n\ndef gamma():\n    return beta()\n",
+        )
+        .unwrap();
+        let server = server_for_repo(&root).await;
+
+        let result = server
+            .quick_impact(&root, "alpha", None, 0)
+            .await
+            .expect("impact");
+        assert_eq!(
+            result["paths"]["app.py::function::gamma"],
+            serde_json::json!([
+                "app.py::function::alpha",
+                "app.py::function::beta",
+                "app.py::function::gamma"
+            ])
+        );
+        assert_eq!(
+            result["paths"]["app.py::function::beta"],
+            serde_json::json!(["app.py::function::alpha", "app.py::function::beta"])
+        );
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
     #[tokio::test]
     async fn sem_entities_returns_tool_error_for_missing_path() {
         let root = temp_git_repo("missing-path");
@@ -39,7 +39,6 @@ struct Pending {
 pub struct Drained {
     pub generation: u64,
     pub needs_rewalk: bool,
-    #[allow(dead_code)]
     pub changed: Vec<String>,
 }
 
@@ -108,6 +107,12 @@ impl RepoWatcher {
         })
     }
 
+    /// Bumped on every relevant event; compare snapshots to detect activity
+    /// without draining (e.g. to debounce a burst of saves).
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
     /// Capture the current generation and clear pending changes. Any event that
     /// races in after this only bumps the generation again, so the next poll
     /// rebuilds — at worst a redundant rebuild, never a stale read.
@@ -140,9 +145,15 @@ fn repo_relative(root: &Path, path: &Path) -> Option<String> {
     Some(rel.to_string_lossy().replace('\\', "/"))
 }
 
-/// Skip churny paths so routine git/build activity doesn't force rebuilds.
+/// Skip churny paths so routine git/build activity and sem's own `.sem/`
+/// state don't force rebuilds.
 fn is_noise(rel: &str) -> bool {
-    rel == ".git" || rel.starts_with(".git/") || rel.contains("/.git/") || is_default_excluded(rel)
+    rel == ".git"
+        || rel.starts_with(".git/")
+        || rel.contains("/.git/")
+        || rel == ".sem"
+        || rel.starts_with(".sem/")
+        || is_default_excluded(rel)
 }
 
 #[cfg(test)]
@@ -159,6 +170,13 @@ mod tests {
         assert!(is_noise("submodule/.git/HEAD"));
     }
 
+    #[test]
+    fn sem_state_writes_are_noise() {
+        assert!(is_noise(".sem"));
+        assert!(is_noise(".sem/cache.db"));
+        assert!(!is_noise("src/.semantic.rs"));
+    }
+
     #[test]
     fn real_source_edits_are_not_noise() {
         assert!(!is_noise("src/main.rs"));